@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate serde_json;
+extern crate indy_crypto;
+
+mod common;
+
+use criterion::Criterion;
+
+use indy_crypto::bls::*;
+
+use std::rc::Rc;
+
+const SIGNERS_COUNT: usize = 7;
+
+const BENCHMARKS: &'static [&'static str] = &[
+    "bls_new_keys",
+    "bls_sign",
+    "bls_verify",
+    "bls_new_multi_sig",
+    "bls_verify_multi_sig"
+];
+
+struct Signers {
+    gen: Generator,
+    sign_keys: Vec<SignKey>,
+    ver_keys: Vec<VerKey>,
+    signatures: Vec<Signature>
+}
+
+fn signers(message: &[u8]) -> Signers {
+    let gen = Generator::new().unwrap();
+    let sign_keys: Vec<SignKey> = (0..SIGNERS_COUNT).map(|_| SignKey::new(None).unwrap()).collect();
+    let ver_keys: Vec<VerKey> = sign_keys.iter().map(|sign_key| VerKey::new(&gen, sign_key).unwrap()).collect();
+    let signatures: Vec<Signature> = sign_keys.iter().map(|sign_key| Bls::sign(message, sign_key).unwrap()).collect();
+
+    Signers { gen, sign_keys, ver_keys, signatures }
+}
+
+fn bench_bls(c: &mut Criterion) {
+    let message = Rc::new(vec![1u8, 2, 3, 4, 5]);
+    let signers = Rc::new(signers(&message));
+
+    {
+        let signers = signers.clone();
+        c.bench_function("bls_new_keys", move |b| b.iter(|| {
+            let sign_key = SignKey::new(None).unwrap();
+            VerKey::new(&signers.gen, &sign_key).unwrap()
+        }));
+    }
+
+    {
+        let (message, signers) = (message.clone(), signers.clone());
+        c.bench_function("bls_sign", move |b| b.iter(|| Bls::sign(&message, &signers.sign_keys[0]).unwrap()));
+    }
+
+    {
+        let (message, signers) = (message.clone(), signers.clone());
+        c.bench_function("bls_verify", move |b| b.iter(|| {
+            assert!(Bls::verify(&signers.signatures[0], &message, &signers.ver_keys[0], &signers.gen).unwrap())
+        }));
+    }
+
+    {
+        let signers = signers.clone();
+        c.bench_function("bls_new_multi_sig", move |b| b.iter(|| {
+            let signatures: Vec<&Signature> = signers.signatures.iter().collect();
+            MultiSignature::new(&signatures).unwrap()
+        }));
+    }
+
+    c.bench_function("bls_verify_multi_sig", move |b| {
+        let signatures: Vec<&Signature> = signers.signatures.iter().collect();
+        let multi_sig = MultiSignature::new(&signatures).unwrap();
+        let ver_keys: Vec<&VerKey> = signers.ver_keys.iter().collect();
+
+        b.iter(|| assert!(Bls::verify_multi_sig(&multi_sig, &message, &ver_keys, &signers.gen).unwrap()))
+    });
+}
+
+criterion_group!(benches, bench_bls);
+
+fn main() {
+    benches();
+    common::print_summary(BENCHMARKS);
+}
@@ -0,0 +1,142 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate serde_json;
+extern crate indy_crypto;
+
+mod common;
+
+use criterion::Criterion;
+
+use indy_crypto::bn::{BigNumber, BnCtxPool};
+
+use std::rc::Rc;
+
+// Sizes used by CL signatures: modulus n, large exponent v and attribute values.
+const MODULUS_SIZE: usize = 2048;
+const EXPONENT_SIZE: usize = 2724;
+const VALUE_SIZE: usize = 256;
+
+const BENCHMARKS: &'static [&'static str] = &[
+    "bn_rand",
+    "bn_generate_prime",
+    "bn_mod_exp",
+    "bn_mod_exp_small_exponent",
+    "bn_mod_mul",
+    "bn_mod_mul_pooled_context",
+    "bn_mod_mul_unpooled_context",
+    "bn_inverse",
+    "bn_hash_array"
+];
+
+struct Numbers {
+    n: BigNumber,
+    base: BigNumber,
+    exponent: BigNumber,
+    value: BigNumber
+}
+
+fn numbers() -> Numbers {
+    // Prime modulus, so every value is invertible
+    let n = BigNumber::generate_prime(MODULUS_SIZE).unwrap();
+
+    Numbers {
+        base: n.rand_range().unwrap(),
+        n,
+        exponent: BigNumber::rand(EXPONENT_SIZE).unwrap(),
+        value: BigNumber::rand(VALUE_SIZE).unwrap()
+    }
+}
+
+fn bench_bn(c: &mut Criterion) {
+    let numbers = Rc::new(numbers());
+
+    c.bench_function("bn_rand", |b| b.iter(|| BigNumber::rand(MODULUS_SIZE).unwrap()));
+
+    c.bench_function("bn_generate_prime", |b| b.iter(|| BigNumber::generate_prime(VALUE_SIZE).unwrap()));
+
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_mod_exp", move |b| {
+            let mut ctx = BigNumber::new_context().unwrap();
+            b.iter(|| numbers.base.mod_exp(&numbers.exponent, &numbers.n, Some(&mut ctx)).unwrap())
+        });
+    }
+
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_mod_exp_small_exponent", move |b| {
+            let mut ctx = BigNumber::new_context().unwrap();
+            b.iter(|| numbers.base.mod_exp(&numbers.value, &numbers.n, Some(&mut ctx)).unwrap())
+        });
+    }
+
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_mod_mul", move |b| {
+            let mut ctx = BigNumber::new_context().unwrap();
+            b.iter(|| numbers.base.mod_mul(&numbers.base, &numbers.n, Some(&mut ctx)).unwrap())
+        });
+    }
+
+    // Helpers create context per call, pool makes it as cheap as reusing one context
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_mod_mul_pooled_context", move |b| {
+            b.iter(|| numbers.base.mod_mul(&numbers.base, &numbers.n, Some(&mut BigNumber::new_context().unwrap())).unwrap())
+        });
+    }
+
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_mod_mul_unpooled_context", move |b| {
+            b.iter(|| {
+                BnCtxPool::clear();
+                numbers.base.mod_mul(&numbers.base, &numbers.n, Some(&mut BigNumber::new_context().unwrap())).unwrap()
+            })
+        });
+    }
+
+    {
+        let numbers = numbers.clone();
+        c.bench_function("bn_inverse", move |b| {
+            let mut ctx = BigNumber::new_context().unwrap();
+            b.iter(|| numbers.value.inverse(&numbers.n, Some(&mut ctx)).unwrap())
+        });
+    }
+
+    c.bench_function("bn_hash_array", move |b| {
+        let nums: Vec<Vec<u8>> = vec![numbers.base.to_bytes().unwrap(), numbers.exponent.to_bytes().unwrap(), numbers.value.to_bytes().unwrap()];
+        b.iter(|| BigNumber::hash_array(&nums).unwrap())
+    });
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = bench_bn
+);
+
+// Counts contexts allocated by operations that don't pass context explicitly.
+fn print_ctx_allocations() {
+    const OPERATIONS: usize = 1000;
+
+    let numbers = numbers();
+    let allocated = BnCtxPool::allocated();
+
+    for _ in 0..OPERATIONS {
+        numbers.base.mod_mul(&numbers.value, &numbers.n, None).unwrap();
+    }
+
+    println!("{}", json!({
+        "benchmark": "bn_ctx_pool",
+        "operations": OPERATIONS,
+        "allocated_contexts": BnCtxPool::allocated() - allocated
+    }));
+}
+
+fn main() {
+    benches();
+    common::print_summary(BENCHMARKS);
+    print_ctx_allocations();
+}
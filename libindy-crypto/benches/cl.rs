@@ -0,0 +1,313 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate serde_json;
+extern crate indy_crypto;
+
+mod common;
+
+use criterion::Criterion;
+
+use indy_crypto::cl::*;
+use indy_crypto::cl::issuer::Issuer;
+use indy_crypto::cl::prover::Prover;
+use indy_crypto::cl::verifier::Verifier;
+use indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
+
+use std::rc::Rc;
+
+const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+const MAX_CRED_NUM: u64 = 5;
+const REV_IDX: u64 = 1;
+
+const BENCHMARKS: &'static [&'static str] = &[
+    "cl_new_credential_def",
+    "cl_new_credential_def_with_revocation",
+    "cl_sign_credential",
+    "cl_sign_credential_with_revocation",
+    "cl_precompute_credential_pub_key",
+    "cl_sign_credential_with_precomputed_key",
+    "cl_create_proof",
+    "cl_create_proof_with_revocation",
+    "cl_verify_proof",
+    "cl_verify_proof_with_revocation"
+];
+
+struct Credential {
+    schema: CredentialSchema,
+    values: CredentialValues,
+    pub_key: CredentialPublicKey,
+    priv_key: CredentialPrivateKey,
+    key_correctness_proof: CredentialKeyCorrectnessProof,
+    master_secret: MasterSecret,
+    signature: CredentialSignature,
+    revocation: Option<Revocation>
+}
+
+struct Revocation {
+    key_pub: RevocationKeyPublic,
+    key_priv: RevocationKeyPrivate,
+    reg: RevocationRegistry,
+    tails_accessor: SimpleTailsAccessor,
+    witness: Witness
+}
+
+fn gvt_credential_schema() -> CredentialSchema {
+    let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    credential_schema_builder.add_attr("name").unwrap();
+    credential_schema_builder.add_attr("sex").unwrap();
+    credential_schema_builder.add_attr("age").unwrap();
+    credential_schema_builder.add_attr("height").unwrap();
+    credential_schema_builder.finalize().unwrap()
+}
+
+fn gvt_credential_values() -> CredentialValues {
+    let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+    credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    credential_values_builder.add_value("age", "28").unwrap();
+    credential_values_builder.add_value("height", "175").unwrap();
+    credential_values_builder.finalize().unwrap()
+}
+
+fn gvt_sub_proof_request() -> SubProofRequest {
+    let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    sub_proof_request_builder.add_revealed_attr("name").unwrap();
+    sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+    sub_proof_request_builder.finalize().unwrap()
+}
+
+// Issues credential once, so benchmarks of later steps don't include generation of keys.
+fn issue_credential(support_revocation: bool) -> Credential {
+    let schema = gvt_credential_schema();
+    let values = gvt_credential_values();
+    let (pub_key, priv_key, key_correctness_proof) = Issuer::new_credential_def(&schema, support_revocation).unwrap();
+
+    let master_secret = Prover::new_master_secret().unwrap();
+    let master_secret_blinding_nonce = new_nonce().unwrap();
+    let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+        Prover::blind_master_secret(&pub_key, &key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+    let credential_issuance_nonce = new_nonce().unwrap();
+
+    if !support_revocation {
+        let (mut signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &credential_issuance_nonce,
+                                    &values,
+                                    &pub_key,
+                                    &priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut signature,
+                                             &values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        return Credential { schema, values, pub_key, priv_key, key_correctness_proof, master_secret, signature, revocation: None };
+    }
+
+    let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+        Issuer::new_revocation_registry_def(&pub_key, MAX_CRED_NUM, false).unwrap();
+    let tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+    let (mut signature, signature_correctness_proof, rev_reg_delta) =
+        Issuer::sign_credential_with_revoc(PROVER_ID,
+                                           &blinded_master_secret,
+                                           &blinded_master_secret_correctness_proof,
+                                           &master_secret_blinding_nonce,
+                                           &credential_issuance_nonce,
+                                           &values,
+                                           &pub_key,
+                                           &priv_key,
+                                           REV_IDX,
+                                           MAX_CRED_NUM,
+                                           false,
+                                           &mut rev_reg,
+                                           &rev_key_priv,
+                                           &tails_accessor).unwrap();
+
+    let witness = Witness::new(REV_IDX, MAX_CRED_NUM, &rev_reg_delta.unwrap(), &tails_accessor).unwrap();
+
+    Prover::process_credential_signature(&mut signature,
+                                         &values,
+                                         &signature_correctness_proof,
+                                         &master_secret_blinding_data,
+                                         &master_secret,
+                                         &pub_key,
+                                         &credential_issuance_nonce,
+                                         Some(&rev_key_pub),
+                                         Some(&rev_reg),
+                                         Some(&witness)).unwrap();
+
+    let revocation = Revocation { key_pub: rev_key_pub, key_priv: rev_key_priv, reg: rev_reg, tails_accessor, witness };
+
+    Credential { schema, values, pub_key, priv_key, key_correctness_proof, master_secret, signature, revocation: Some(revocation) }
+}
+
+fn create_proof(credential: &Credential, sub_proof_request: &SubProofRequest, nonce: &Nonce) -> Proof {
+    let mut proof_builder = Prover::new_proof_builder().unwrap();
+    proof_builder.add_sub_proof_request(sub_proof_request,
+                                        &credential.schema,
+                                        &credential.signature,
+                                        &credential.values,
+                                        &credential.pub_key,
+                                        credential.revocation.as_ref().map(|revocation| &revocation.reg),
+                                        credential.revocation.as_ref().map(|revocation| &revocation.witness)).unwrap();
+    proof_builder.finalize(nonce, &credential.master_secret).unwrap()
+}
+
+fn verify_proof(credential: &Credential, sub_proof_request: &SubProofRequest, proof: &Proof, nonce: &Nonce) -> bool {
+    let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    proof_verifier.add_sub_proof_request(sub_proof_request,
+                                         &credential.schema,
+                                         &credential.pub_key,
+                                         credential.revocation.as_ref().map(|revocation| &revocation.key_pub),
+                                         credential.revocation.as_ref().map(|revocation| &revocation.reg)).unwrap();
+    proof_verifier.verify(proof, nonce).unwrap()
+}
+
+fn bench_key_generation(c: &mut Criterion) {
+    c.bench_function("cl_new_credential_def", |b| {
+        let credential_schema = gvt_credential_schema();
+        b.iter(|| Issuer::new_credential_def(&credential_schema, false).unwrap())
+    });
+
+    c.bench_function("cl_new_credential_def_with_revocation", |b| {
+        let credential_schema = gvt_credential_schema();
+        b.iter(|| Issuer::new_credential_def(&credential_schema, true).unwrap())
+    });
+}
+
+fn bench_signing(c: &mut Criterion) {
+    let credential = Rc::new(issue_credential(true));
+
+    let master_secret_blinding_nonce = new_nonce().unwrap();
+    let (blinded_master_secret, _, blinded_master_secret_correctness_proof) =
+        Prover::blind_master_secret(&credential.pub_key,
+                                    &credential.key_correctness_proof,
+                                    &credential.master_secret,
+                                    &master_secret_blinding_nonce).unwrap();
+    let credential_issuance_nonce = new_nonce().unwrap();
+
+    let request = Rc::new((blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_issuance_nonce));
+
+    {
+        let credential = credential.clone();
+        let request = request.clone();
+
+        c.bench_function("cl_sign_credential", move |b| b.iter(|| {
+            Issuer::sign_credential(PROVER_ID,
+                                    &request.0,
+                                    &request.1,
+                                    &request.2,
+                                    &request.3,
+                                    &credential.values,
+                                    &credential.pub_key,
+                                    &credential.priv_key).unwrap()
+        }));
+    }
+
+    {
+        let credential = credential.clone();
+
+        c.bench_function("cl_precompute_credential_pub_key", move |b| b.iter(|| {
+            CredentialPublicKeyPrecomputed::new(&credential.pub_key).unwrap()
+        }));
+    }
+
+    // Precomputation pays off after a few credentials signed with the same key
+    {
+        let credential = credential.clone();
+        let request = request.clone();
+        let pub_key_precomputed = CredentialPublicKeyPrecomputed::new(&credential.pub_key).unwrap();
+
+        c.bench_function("cl_sign_credential_with_precomputed_key", move |b| b.iter(|| {
+            Issuer::sign_credential_with_precomputed_key(PROVER_ID,
+                                                         &request.0,
+                                                         &request.1,
+                                                         &request.2,
+                                                         &request.3,
+                                                         &credential.values,
+                                                         &pub_key_precomputed,
+                                                         &credential.priv_key).unwrap()
+        }));
+    }
+
+    // Registry is restored per iteration to issue credential with the same index every time
+    c.bench_function("cl_sign_credential_with_revocation", move |b| {
+        let revocation = credential.revocation.as_ref().unwrap();
+        let rev_reg_json = revocation.reg.to_json().unwrap();
+
+        b.iter_with_setup(
+            || RevocationRegistry::from_json(&rev_reg_json).unwrap(),
+            |mut rev_reg| {
+                Issuer::sign_credential_with_revoc(PROVER_ID,
+                                                   &request.0,
+                                                   &request.1,
+                                                   &request.2,
+                                                   &request.3,
+                                                   &credential.values,
+                                                   &credential.pub_key,
+                                                   &credential.priv_key,
+                                                   REV_IDX + 1,
+                                                   MAX_CRED_NUM,
+                                                   false,
+                                                   &mut rev_reg,
+                                                   &revocation.key_priv,
+                                                   &revocation.tails_accessor).unwrap()
+            })
+    });
+}
+
+fn bench_proofs(c: &mut Criterion) {
+    let sub_proof_request = Rc::new(gvt_sub_proof_request());
+    let nonce = Rc::new(new_nonce().unwrap());
+
+    for &(support_revocation, suffix) in [(false, ""), (true, "_with_revocation")].iter() {
+        let credential = Rc::new(issue_credential(support_revocation));
+
+        {
+            let credential = credential.clone();
+            let sub_proof_request = sub_proof_request.clone();
+            let nonce = nonce.clone();
+
+            c.bench_function(&format!("cl_create_proof{}", suffix), move |b| {
+                b.iter(|| create_proof(&credential, &sub_proof_request, &nonce))
+            });
+        }
+
+        let proof = Rc::new(create_proof(&credential, &sub_proof_request, &nonce));
+        assert!(verify_proof(&credential, &sub_proof_request, &proof, &nonce));
+
+        let sub_proof_request = sub_proof_request.clone();
+        let nonce = nonce.clone();
+
+        c.bench_function(&format!("cl_verify_proof{}", suffix), move |b| {
+            b.iter(|| verify_proof(&credential, &sub_proof_request, &proof, &nonce))
+        });
+    }
+}
+
+criterion_group!(
+    name = key_generation;
+    config = Criterion::default().sample_size(10);
+    targets = bench_key_generation
+);
+criterion_group!(
+    name = credentials;
+    config = Criterion::default().sample_size(20);
+    targets = bench_signing, bench_proofs
+);
+
+fn main() {
+    key_generation();
+    credentials();
+    common::print_summary(BENCHMARKS);
+}
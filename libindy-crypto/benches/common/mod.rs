@@ -0,0 +1,59 @@
+// Helpers shared by benchmarks.
+//
+// Criterion requires benchmarked closures to be 'static, so benchmarks share fixtures with Rc.
+//
+// Machine readable summary of benchmark results.
+//
+// After criterion finishes, every benchmark gets one JSON line on stdout:
+// {"benchmark":"cl_verify_proof","mean_ns":...,"median_ns":...,"std_dev_ns":...}
+// Lines are read from estimates criterion stores in `<target dir>/criterion/<benchmark>/new/estimates.json`,
+// so CI can compare them against stored baseline and report regressions.
+
+use serde_json;
+use serde_json::Value;
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+pub fn print_summary(benchmarks: &[&str]) {
+    for benchmark in benchmarks {
+        match _read_estimates(benchmark) {
+            Some(estimates) => println!("{}", json!({
+                "benchmark": benchmark,
+                "mean_ns": _point_estimate(&estimates, &["Mean", "mean"]),
+                "median_ns": _point_estimate(&estimates, &["Median", "median"]),
+                "std_dev_ns": _point_estimate(&estimates, &["StdDev", "std_dev"])
+            })),
+            None => println!("{}", json!({
+                "benchmark": benchmark,
+                "error": "estimates not found"
+            }))
+        }
+    }
+}
+
+fn _read_estimates(benchmark: &str) -> Option<Value> {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or("target".to_string());
+
+    let mut path = PathBuf::from(target_dir);
+    path.push("criterion");
+    path.push(benchmark);
+    path.push("new");
+    path.push("estimates.json");
+
+    let mut json = String::new();
+    File::open(path).ok()?.read_to_string(&mut json).ok()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+// Criterion 0.2 names estimates in CamelCase, later versions in snake_case.
+fn _point_estimate(estimates: &Value, names: &[&str]) -> Option<f64> {
+    names.iter()
+        .filter_map(|name| estimates.get(name))
+        .next()
+        .and_then(|estimate| estimate.get("point_estimate"))
+        .and_then(|point_estimate| point_estimate.as_f64())
+}
@@ -0,0 +1,678 @@
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, Pair, PointG1, PointG2};
+
+use std::collections::HashSet;
+
+pub mod tails;
+
+/// Value of CKS dynamic accumulator (Camenisch, Kohlweiss, Soriente) over elements `1..=max_elements`.
+///
+/// Accumulator is a sum of tails `g'^(gamma^(L + 1 - i))` of accumulated elements `i`,
+/// where `L` is max elements number and `gamma` is the trapdoor known only to accumulator manager.
+///
+/// Accumulator is universal: it supports witnesses of both membership and non-membership of element.
+/// Witness of element `i` is a sum of tails `g'^(gamma^(L + 1 - j + i))` of other accumulated elements `j`.
+/// Pairing `e(g^(gamma^i), accum) / e(g, omega)` is `z = e(g, g')^(gamma^(L + 1))` if element is accumulated
+/// and identity otherwise. Tail `g'^(gamma^(L + 1))` isn't published, so witness of the other kind can't be forged.
+pub type Accumulator = PointG2;
+
+/// Tail point `g'^(gamma^index)` used to update accumulator and witnesses.
+pub type Tail = PointG2;
+
+/// Source of accumulator tails.
+///
+/// Tails are public, so they can be stored in a file, fetched from remote storage or generated on demand.
+pub trait TailsAccessor {
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError>;
+}
+
+impl TailsAccessor for Vec<Tail> {
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        let tail = self.get(tail_id as usize)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails range {}", tail_id, self.len())))?;
+        Ok(accessor(tail))
+    }
+}
+
+/// Private key of accumulator manager.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccumulatorPrivateKey {
+    gamma: GroupOrderElement
+}
+
+impl AccumulatorPrivateKey {
+    pub fn new() -> Result<AccumulatorPrivateKey, IndyCryptoError> {
+        Ok(AccumulatorPrivateKey {
+            gamma: GroupOrderElement::new()?
+        })
+    }
+
+    /// Generates all `2 * max_elements + 1` tails of accumulator.
+    /// Tails `0` and `max_elements + 1` aren't used, so they are never accessed.
+    ///
+    /// # Arguments
+    /// * `pub_key` - Accumulator public key.
+    pub fn generate_tails(&self, pub_key: &AccumulatorPublicKey) -> Result<Vec<Tail>, IndyCryptoError> {
+        let mut tails: Vec<Tail> = Vec::new();
+        for index in 0..tails_count(pub_key.max_elements) {
+            tails.push(new_tail(index, &pub_key.g_dash, &self.gamma)?);
+        }
+        Ok(tails)
+    }
+}
+
+/// Public key of accumulator. Must be published together with tails.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AccumulatorPublicKey {
+    max_elements: u64,
+    g: PointG1,
+    g_dash: PointG2,
+    z: Pair
+}
+
+impl AccumulatorPublicKey {
+    /// Creates public key of accumulator over elements `1..=max_elements`.
+    ///
+    /// # Arguments
+    /// * `max_elements` - Max element of accumulator.
+    /// * `priv_key` - Accumulator private key.
+    pub fn new(max_elements: u64, priv_key: &AccumulatorPrivateKey) -> Result<AccumulatorPublicKey, IndyCryptoError> {
+        if max_elements == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Max elements number must be positive")));
+        }
+
+        let g = PointG1::new()?;
+        let g_dash = PointG2::new()?;
+        let z = accumulator_key(&g, &g_dash, &priv_key.gamma, max_elements)?;
+
+        Ok(AccumulatorPublicKey { max_elements, g, g_dash, z })
+    }
+
+    pub fn max_elements(&self) -> u64 {
+        self.max_elements
+    }
+}
+
+/// Witness of element membership in accumulator.
+///
+/// Contains `g^(gamma^i)` issued by accumulator manager for element `i`,
+/// so witness can be checked by anyone having public key and tails.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MembershipWitness {
+    element: u64,
+    g_i: PointG1,
+    omega: PointG2
+}
+
+impl MembershipWitness {
+    /// Creates membership witness of element.
+    ///
+    /// # Arguments
+    /// * `element` - Accumulated element.
+    /// * `members` - All elements of accumulator.
+    /// * `pub_key` - Accumulator public key.
+    /// * `priv_key` - Accumulator private key.
+    /// * `tails` - Accumulator tails accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::accumulator::*;
+    ///
+    /// let priv_key = AccumulatorPrivateKey::new().unwrap();
+    /// let pub_key = AccumulatorPublicKey::new(5, &priv_key).unwrap();
+    /// let tails = priv_key.generate_tails(&pub_key).unwrap();
+    ///
+    /// let mut accum = new_accumulator().unwrap();
+    /// add_element(&mut accum, 2, 5, &tails).unwrap();
+    /// add_element(&mut accum, 4, 5, &tails).unwrap();
+    ///
+    /// let members = vec![2, 4].into_iter().collect();
+    /// let witness = MembershipWitness::new(2, &members, &pub_key, &priv_key, &tails).unwrap();
+    /// assert!(verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    /// ```
+    pub fn new<TA>(element: u64,
+                   members: &HashSet<u64>,
+                   pub_key: &AccumulatorPublicKey,
+                   priv_key: &AccumulatorPrivateKey,
+                   tails: &TA) -> Result<MembershipWitness, IndyCryptoError> where TA: TailsAccessor {
+        trace!("MembershipWitness::new: >>> element: {:?}, members: {:?}, pub_key: {:?}", element, members, pub_key);
+
+        if !members.contains(&element) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Element {} isn't accumulated", element)));
+        }
+
+        let witness = MembershipWitness {
+            element,
+            g_i: element_point(&pub_key.g, &priv_key.gamma, element)?,
+            omega: witness_sum(element, pub_key.max_elements, members, tails)?
+        };
+
+        trace!("MembershipWitness::new: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+
+    /// Updates witness after elements were added to and removed from accumulator.
+    ///
+    /// # Arguments
+    /// * `added` - Elements added to accumulator.
+    /// * `removed` - Elements removed from accumulator.
+    /// * `pub_key` - Accumulator public key.
+    /// * `tails` - Accumulator tails accessor.
+    pub fn update<TA>(&mut self,
+                      added: &HashSet<u64>,
+                      removed: &HashSet<u64>,
+                      pub_key: &AccumulatorPublicKey,
+                      tails: &TA) -> Result<(), IndyCryptoError> where TA: TailsAccessor {
+        trace!("MembershipWitness::update: >>> added: {:?}, removed: {:?}", added, removed);
+
+        self.omega = update_witness(&self.omega, self.element, pub_key.max_elements, added, removed, tails)?;
+
+        trace!("MembershipWitness::update: <<<");
+
+        Ok(())
+    }
+
+    pub fn element(&self) -> u64 {
+        self.element
+    }
+}
+
+/// Witness of element non-membership in accumulator.
+///
+/// Allows to keep in accumulator only elements that were excluded (e.g. revoked),
+/// so including of element into the set doesn't change accumulator.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NonMembershipWitness {
+    element: u64,
+    g_i: PointG1,
+    omega: PointG2
+}
+
+impl NonMembershipWitness {
+    /// Creates non-membership witness of element.
+    ///
+    /// # Arguments
+    /// * `element` - Element that isn't accumulated.
+    /// * `members` - All elements of accumulator.
+    /// * `pub_key` - Accumulator public key.
+    /// * `priv_key` - Accumulator private key.
+    /// * `tails` - Accumulator tails accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::accumulator::*;
+    ///
+    /// let priv_key = AccumulatorPrivateKey::new().unwrap();
+    /// let pub_key = AccumulatorPublicKey::new(5, &priv_key).unwrap();
+    /// let tails = priv_key.generate_tails(&pub_key).unwrap();
+    ///
+    /// let mut accum = new_accumulator().unwrap();
+    /// add_element(&mut accum, 4, 5, &tails).unwrap();
+    ///
+    /// let members = vec![4].into_iter().collect();
+    /// let witness = NonMembershipWitness::new(2, &members, &pub_key, &priv_key, &tails).unwrap();
+    /// assert!(verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    /// ```
+    pub fn new<TA>(element: u64,
+                   members: &HashSet<u64>,
+                   pub_key: &AccumulatorPublicKey,
+                   priv_key: &AccumulatorPrivateKey,
+                   tails: &TA) -> Result<NonMembershipWitness, IndyCryptoError> where TA: TailsAccessor {
+        trace!("NonMembershipWitness::new: >>> element: {:?}, members: {:?}, pub_key: {:?}", element, members, pub_key);
+
+        if members.contains(&element) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Element {} is accumulated", element)));
+        }
+
+        let witness = NonMembershipWitness {
+            element,
+            g_i: element_point(&pub_key.g, &priv_key.gamma, element)?,
+            omega: witness_sum(element, pub_key.max_elements, members, tails)?
+        };
+
+        trace!("NonMembershipWitness::new: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+
+    /// Updates witness after elements were added to and removed from accumulator.
+    /// Returns error if element itself was added, as it has no non-membership witness anymore.
+    ///
+    /// # Arguments
+    /// * `added` - Elements added to accumulator.
+    /// * `removed` - Elements removed from accumulator.
+    /// * `pub_key` - Accumulator public key.
+    /// * `tails` - Accumulator tails accessor.
+    pub fn update<TA>(&mut self,
+                      added: &HashSet<u64>,
+                      removed: &HashSet<u64>,
+                      pub_key: &AccumulatorPublicKey,
+                      tails: &TA) -> Result<(), IndyCryptoError> where TA: TailsAccessor {
+        trace!("NonMembershipWitness::update: >>> added: {:?}, removed: {:?}", added, removed);
+
+        if added.contains(&self.element) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Element {} is accumulated", self.element)));
+        }
+
+        self.omega = update_witness(&self.omega, self.element, pub_key.max_elements, added, removed, tails)?;
+
+        trace!("NonMembershipWitness::update: <<<");
+
+        Ok(())
+    }
+
+    pub fn element(&self) -> u64 {
+        self.element
+    }
+}
+
+/// Creates empty accumulator.
+pub fn new_accumulator() -> Result<Accumulator, IndyCryptoError> {
+    Accumulator::new_inf()
+}
+
+/// Adds element to accumulator.
+///
+/// # Arguments
+/// * `accum` - Accumulator.
+/// * `element` - Element in range `1..=max_elements`.
+/// * `max_elements` - Max element of accumulator.
+/// * `tails` - Accumulator tails accessor.
+pub fn add_element<TA>(accum: &mut Accumulator, element: u64, max_elements: u64, tails: &TA) -> Result<(), IndyCryptoError> where TA: TailsAccessor {
+    _check_element(element, max_elements)?;
+
+    let mut new_accum = *accum;
+    tails.access_tail(tail_index(max_elements, element), &mut |tail| {
+        new_accum = new_accum.add(tail).unwrap();
+    })?;
+    *accum = new_accum;
+
+    Ok(())
+}
+
+/// Removes element from accumulator.
+///
+/// # Arguments
+/// * `accum` - Accumulator.
+/// * `element` - Element in range `1..=max_elements`.
+/// * `max_elements` - Max element of accumulator.
+/// * `tails` - Accumulator tails accessor.
+pub fn remove_element<TA>(accum: &mut Accumulator, element: u64, max_elements: u64, tails: &TA) -> Result<(), IndyCryptoError> where TA: TailsAccessor {
+    _check_element(element, max_elements)?;
+
+    let mut new_accum = *accum;
+    tails.access_tail(tail_index(max_elements, element), &mut |tail| {
+        new_accum = new_accum.sub(tail).unwrap();
+    })?;
+    *accum = new_accum;
+
+    Ok(())
+}
+
+/// Checks membership witness against accumulator.
+///
+/// Verifies that `g_i` of witness corresponds to its element tail, so `e(g_i, g') = e(g, g'_i)`,
+/// and that `e(g_i, accum) / e(g, omega) = z`.
+///
+/// # Arguments
+/// * `accum` - Accumulator.
+/// * `witness` - Membership witness.
+/// * `pub_key` - Accumulator public key.
+/// * `tails` - Accumulator tails accessor.
+pub fn verify_membership<TA>(accum: &Accumulator,
+                             witness: &MembershipWitness,
+                             pub_key: &AccumulatorPublicKey,
+                             tails: &TA) -> Result<bool, IndyCryptoError> where TA: TailsAccessor {
+    trace!("verify_membership: >>> accum: {:?}, witness: {:?}, pub_key: {:?}", accum, witness, pub_key);
+
+    let valid = _check_element_point(witness.element, &witness.g_i, pub_key, tails)? &&
+        Pair::pair(&witness.g_i, accum)?.mul(&Pair::pair(&pub_key.g, &witness.omega)?.inverse()?)? == pub_key.z;
+
+    trace!("verify_membership: <<< valid: {:?}", valid);
+
+    Ok(valid)
+}
+
+/// Checks non-membership witness against accumulator.
+///
+/// Verifies that `g_i` of witness corresponds to its element tail, so `e(g_i, g') = e(g, g'_i)`,
+/// and that `e(g_i, accum) = e(g, omega)`.
+///
+/// # Arguments
+/// * `accum` - Accumulator.
+/// * `witness` - Non-membership witness.
+/// * `pub_key` - Accumulator public key.
+/// * `tails` - Accumulator tails accessor.
+pub fn verify_non_membership<TA>(accum: &Accumulator,
+                                 witness: &NonMembershipWitness,
+                                 pub_key: &AccumulatorPublicKey,
+                                 tails: &TA) -> Result<bool, IndyCryptoError> where TA: TailsAccessor {
+    trace!("verify_non_membership: >>> accum: {:?}, witness: {:?}, pub_key: {:?}", accum, witness, pub_key);
+
+    let valid = _check_element_point(witness.element, &witness.g_i, pub_key, tails)? &&
+        Pair::pair(&witness.g_i, accum)? == Pair::pair(&pub_key.g, &witness.omega)?;
+
+    trace!("verify_non_membership: <<< valid: {:?}", valid);
+
+    Ok(valid)
+}
+
+/// Returns number of accumulator tails: unused 0th + valuable 1..L + unused (L+1)th + valuable (L+2)..(2L).
+pub fn tails_count(max_elements: u64) -> u64 {
+    2 * max_elements + 1
+}
+
+/// Returns index of tail accumulator is changed by when element is added or removed.
+pub fn tail_index(max_elements: u64, element: u64) -> u64 {
+    max_elements + 1 - element
+}
+
+/// Returns tail `g'^(gamma^index)`.
+pub fn new_tail(index: u64, g_dash: &PointG2, gamma: &GroupOrderElement) -> Result<Tail, IndyCryptoError> {
+    Ok(g_dash.mul(&_gamma_pow(gamma, index)?)?)
+}
+
+/// Returns `g^(gamma^element)` that is used to check membership of element.
+pub fn element_point(g: &PointG1, gamma: &GroupOrderElement, element: u64) -> Result<PointG1, IndyCryptoError> {
+    Ok(g.mul(&_gamma_pow(gamma, element)?)?)
+}
+
+/// Returns `z = e(g, g')^(gamma^(L + 1))` membership is checked against.
+pub fn accumulator_key(g: &PointG1, g_dash: &PointG2, gamma: &GroupOrderElement, max_elements: u64) -> Result<Pair, IndyCryptoError> {
+    Ok(Pair::pair(g, g_dash)?.pow(&_gamma_pow(gamma, max_elements + 1)?)?)
+}
+
+/// Returns sum of tails `g'^(gamma^(L + 1 - j + element))` for all `j` except element itself.
+///
+/// # Arguments
+/// * `element` - Element witness is computed for.
+/// * `max_elements` - Max element of accumulator.
+/// * `elements` - Elements `j`.
+/// * `tails` - Accumulator tails accessor.
+pub fn witness_sum<TA>(element: u64, max_elements: u64, elements: &HashSet<u64>, tails: &TA) -> Result<PointG2, IndyCryptoError> where TA: TailsAccessor {
+    _check_element(element, max_elements)?;
+
+    let mut omega = PointG2::new_inf()?;
+    for j in elements.iter() {
+        if *j == element { continue; }
+
+        _check_element(*j, max_elements)?;
+
+        tails.access_tail(max_elements + 1 - j + element, &mut |tail| {
+            omega = omega.add(tail).unwrap();
+        })?;
+    }
+
+    Ok(omega)
+}
+
+/// Returns witnesses `omega` of several elements computed in a single pass over tails.
+///
+/// Every required tail is accessed once and added to all witnesses it contributes to,
+/// while `witness_sum` called per element accesses tails of all accumulated elements for every witness.
+///
+/// # Arguments
+/// * `witness_elements` - Elements witnesses are computed for.
+/// * `max_elements` - Max element of accumulator.
+/// * `elements` - Accumulated elements.
+/// * `tails` - Accumulator tails accessor.
+pub fn witness_sums<TA>(witness_elements: &[u64], max_elements: u64, elements: &HashSet<u64>, tails: &TA) -> Result<Vec<PointG2>, IndyCryptoError> where TA: TailsAccessor {
+    for element in witness_elements.iter().chain(elements.iter()) {
+        _check_element(*element, max_elements)?;
+    }
+
+    let mut omegas = vec![PointG2::new_inf()?; witness_elements.len()];
+
+    let (min_element, max_element) = match (witness_elements.iter().min(), witness_elements.iter().max()) {
+        (Some(min_element), Some(max_element)) => (*min_element, *max_element),
+        _ => return Ok(omegas)
+    };
+
+    // Witness of element `i` contains tails `L + 1 - j + i` for `j` in `1..=L`, i.e. tails `i + 1..=L + i`
+    for tail_id in min_element + 1..max_element + max_elements + 1 {
+        let targets: Vec<usize> = witness_elements.iter()
+            .enumerate()
+            .filter(|&(_, i)| tail_id > *i && tail_id <= max_elements + *i)
+            .filter(|&(_, i)| {
+                let j = max_elements + 1 + *i - tail_id;
+                j != *i && elements.contains(&j)
+            })
+            .map(|(k, _)| k)
+            .collect();
+
+        if targets.is_empty() { continue; }
+
+        tails.access_tail(tail_id, &mut |tail| {
+            for k in targets.iter() {
+                omegas[*k] = omegas[*k].add(tail).unwrap();
+            }
+        })?;
+    }
+
+    Ok(omegas)
+}
+
+/// Returns witness `omega` of element updated by elements added to and removed from accumulator.
+///
+/// # Arguments
+/// * `omega` - Current witness value.
+/// * `element` - Element witness is computed for.
+/// * `max_elements` - Max element of accumulator.
+/// * `added` - Elements added to accumulator.
+/// * `removed` - Elements removed from accumulator.
+/// * `tails` - Accumulator tails accessor.
+pub fn update_witness<TA>(omega: &PointG2,
+                          element: u64,
+                          max_elements: u64,
+                          added: &HashSet<u64>,
+                          removed: &HashSet<u64>,
+                          tails: &TA) -> Result<PointG2, IndyCryptoError> where TA: TailsAccessor {
+    let omega_num = witness_sum(element, max_elements, added, tails)?;
+    let omega_denom = witness_sum(element, max_elements, removed, tails)?;
+
+    Ok(omega.add(&omega_num.sub(&omega_denom)?)?)
+}
+
+fn _check_element(element: u64, max_elements: u64) -> Result<(), IndyCryptoError> {
+    if element == 0 || element > max_elements {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid accumulator element: {}, must be in range 1..{}", element, max_elements)));
+    }
+
+    Ok(())
+}
+
+// Checks that `g_i = g^(gamma^i)` using public tail `g'^(gamma^i)`.
+fn _check_element_point<TA>(element: u64,
+                            g_i: &PointG1,
+                            pub_key: &AccumulatorPublicKey,
+                            tails: &TA) -> Result<bool, IndyCryptoError> where TA: TailsAccessor {
+    _check_element(element, pub_key.max_elements)?;
+
+    let mut element_tail: Option<Tail> = None;
+    tails.access_tail(element, &mut |tail| element_tail = Some(*tail))?;
+    let element_tail = element_tail
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Tail {} isn't accessible", element)))?;
+
+    Ok(Pair::pair(g_i, &pub_key.g_dash)? == Pair::pair(&pub_key.g, &element_tail)?)
+}
+
+fn _gamma_pow(gamma: &GroupOrderElement, exp: u64) -> Result<GroupOrderElement, IndyCryptoError> {
+    let mut exp_bytes: Vec<u8> = Vec::new();
+    for i in (0..8).rev() {
+        exp_bytes.push((exp >> i * 8) as u8);
+    }
+
+    gamma.pow_mod(&GroupOrderElement::from_bytes(&exp_bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_ELEMENTS: u64 = 5;
+
+    fn _setup() -> (AccumulatorPublicKey, AccumulatorPrivateKey, Vec<Tail>) {
+        let priv_key = AccumulatorPrivateKey::new().unwrap();
+        let pub_key = AccumulatorPublicKey::new(MAX_ELEMENTS, &priv_key).unwrap();
+        let tails = priv_key.generate_tails(&pub_key).unwrap();
+        (pub_key, priv_key, tails)
+    }
+
+    fn _elements(elements: &[u64]) -> HashSet<u64> {
+        elements.iter().cloned().collect()
+    }
+
+    #[test]
+    fn verify_membership_works() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        add_element(&mut accum, 3, MAX_ELEMENTS, &tails).unwrap();
+
+        let witness = MembershipWitness::new(3, &_elements(&[1, 3]), &pub_key, &priv_key, &tails).unwrap();
+
+        assert!(verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    }
+
+    #[test]
+    fn verify_membership_works_for_removed_element() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        add_element(&mut accum, 3, MAX_ELEMENTS, &tails).unwrap();
+
+        let witness = MembershipWitness::new(3, &_elements(&[1, 3]), &pub_key, &priv_key, &tails).unwrap();
+
+        remove_element(&mut accum, 3, MAX_ELEMENTS, &tails).unwrap();
+
+        assert!(!verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    }
+
+    #[test]
+    fn membership_witness_update_works() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        add_element(&mut accum, 2, MAX_ELEMENTS, &tails).unwrap();
+
+        let mut witness = MembershipWitness::new(2, &_elements(&[1, 2]), &pub_key, &priv_key, &tails).unwrap();
+
+        add_element(&mut accum, 5, MAX_ELEMENTS, &tails).unwrap();
+        remove_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        assert!(!verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+
+        witness.update(&_elements(&[5]), &_elements(&[1]), &pub_key, &tails).unwrap();
+        assert!(verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+        assert_eq!(MembershipWitness::new(2, &_elements(&[2, 5]), &pub_key, &priv_key, &tails).unwrap(), witness);
+    }
+
+    #[test]
+    fn verify_membership_works_for_forged_element_point() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 2, MAX_ELEMENTS, &tails).unwrap();
+
+        let mut witness = MembershipWitness::new(2, &_elements(&[2]), &pub_key, &priv_key, &tails).unwrap();
+        witness.element = 4;
+
+        assert!(!verify_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    }
+
+    #[test]
+    fn membership_witness_new_works_for_not_accumulated_element() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        assert!(MembershipWitness::new(2, &_elements(&[1]), &pub_key, &priv_key, &tails).is_err());
+    }
+
+    #[test]
+    fn verify_non_membership_works() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        add_element(&mut accum, 3, MAX_ELEMENTS, &tails).unwrap();
+
+        let witness = NonMembershipWitness::new(2, &_elements(&[1, 3]), &pub_key, &priv_key, &tails).unwrap();
+        assert!(verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+
+        add_element(&mut accum, 2, MAX_ELEMENTS, &tails).unwrap();
+        assert!(!verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+    }
+
+    #[test]
+    fn verify_non_membership_works_for_accumulated_element() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 3, MAX_ELEMENTS, &tails).unwrap();
+
+        // Membership witness doesn't prove non-membership
+        let membership_witness = MembershipWitness::new(3, &_elements(&[3]), &pub_key, &priv_key, &tails).unwrap();
+        let witness = NonMembershipWitness {
+            element: membership_witness.element,
+            g_i: membership_witness.g_i,
+            omega: membership_witness.omega
+        };
+
+        assert!(!verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+        assert!(NonMembershipWitness::new(3, &_elements(&[3]), &pub_key, &priv_key, &tails).is_err());
+    }
+
+    #[test]
+    fn non_membership_witness_update_works() {
+        let (pub_key, priv_key, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        add_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+
+        let mut witness = NonMembershipWitness::new(2, &_elements(&[1]), &pub_key, &priv_key, &tails).unwrap();
+
+        add_element(&mut accum, 4, MAX_ELEMENTS, &tails).unwrap();
+        remove_element(&mut accum, 1, MAX_ELEMENTS, &tails).unwrap();
+        assert!(!verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+
+        witness.update(&_elements(&[4]), &_elements(&[1]), &pub_key, &tails).unwrap();
+        assert!(verify_non_membership(&accum, &witness, &pub_key, &tails).unwrap());
+
+        assert!(witness.update(&_elements(&[2]), &HashSet::new(), &pub_key, &tails).is_err());
+    }
+
+    #[test]
+    fn witness_sums_works() {
+        let (_, _, tails) = _setup();
+
+        let elements = _elements(&[1, 2, 4, 5]);
+        let witness_elements = [1, 3, 5, 1];
+
+        let omegas = witness_sums(&witness_elements, MAX_ELEMENTS, &elements, &tails).unwrap();
+
+        assert_eq!(witness_elements.len(), omegas.len());
+        for (element, omega) in witness_elements.iter().zip(omegas.iter()) {
+            assert_eq!(witness_sum(*element, MAX_ELEMENTS, &elements, &tails).unwrap(), *omega);
+        }
+    }
+
+    #[test]
+    fn witness_sums_works_for_no_elements() {
+        let (_, _, tails) = _setup();
+
+        assert!(witness_sums(&[], MAX_ELEMENTS, &_elements(&[1]), &tails).unwrap().is_empty());
+        assert!(witness_sums(&[MAX_ELEMENTS + 1], MAX_ELEMENTS, &_elements(&[1]), &tails).is_err());
+    }
+
+    #[test]
+    fn add_element_works_for_invalid_element() {
+        let (_, _, tails) = _setup();
+
+        let mut accum = new_accumulator().unwrap();
+        assert!(add_element(&mut accum, 0, MAX_ELEMENTS, &tails).is_err());
+        assert!(add_element(&mut accum, MAX_ELEMENTS + 1, MAX_ELEMENTS, &tails).is_err());
+    }
+}
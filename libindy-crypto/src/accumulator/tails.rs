@@ -0,0 +1,426 @@
+use accumulator::{Tail, TailsAccessor};
+use errors::IndyCryptoError;
+use pair::PointG2;
+
+use sha2::{Digest, Sha256};
+
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Magic bytes that start every tails file.
+pub const TAILS_FILE_MAGIC: [u8; 4] = [0x49, 0x43, 0x54, 0x46]; // "ICTF"
+
+/// Current version of tails file format.
+pub const TAILS_FILE_VERSION: u8 = 1;
+
+/// Identifier of BN254 curve tails are defined over.
+pub const TAILS_CURVE_BN254: u8 = 1;
+
+/// Size of SHA-256 digest that ends tails file.
+pub const TAILS_HASH_SIZE: usize = 32;
+
+// magic + version + curve id + count
+const HEADER_SIZE: u64 = 4 + 1 + 1 + 8;
+const OFFSET_SIZE: u64 = 8;
+const ENTRY_SIZE: u64 = PointG2::BYTES_REPR_COMPRESSED_SIZE as u64;
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Writes tails in binary tails file format.
+///
+/// File layout (all integers are big-endian):
+/// 1) Header: 4 bytes magic `ICTF`, 1 byte format version, 1 byte curve id and 8 bytes tails count.
+/// 2) Offsets table: 8 bytes offset from the file start of every tail entry.
+/// 3) Tail entries: compressed G2 points in tail index order.
+/// 4) SHA-256 digest of all preceding bytes.
+///
+/// Digest returned by `finalize` is expected to be published with revocation registry definition,
+/// so holders of the file can check it with `TailsReader::verify` before use.
+pub struct TailsWriter<W> where W: Write {
+    writer: W,
+    hasher: Sha256,
+    count: u64,
+    written: u64
+}
+
+impl<W> TailsWriter<W> where W: Write {
+    /// Writes header and offsets table of tails file.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of tails file.
+    /// * `count` - Number of tails that will be written.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::accumulator::tails::{TailsReader, TailsWriter};
+    /// use indy_crypto::accumulator::{AccumulatorPrivateKey, AccumulatorPublicKey, TailsAccessor};
+    /// use std::io::Cursor;
+    ///
+    /// let priv_key = AccumulatorPrivateKey::new().unwrap();
+    /// let pub_key = AccumulatorPublicKey::new(3, &priv_key).unwrap();
+    /// let tails = priv_key.generate_tails(&pub_key).unwrap();
+    ///
+    /// let mut writer = TailsWriter::new(Vec::new(), tails.len() as u64).unwrap();
+    /// for tail in tails.iter() {
+    ///     writer.write_tail(tail).unwrap();
+    /// }
+    /// let (bytes, hash) = writer.finalize().unwrap();
+    ///
+    /// let reader = TailsReader::new(Cursor::new(bytes)).unwrap();
+    /// assert!(reader.verify(&hash).unwrap());
+    ///
+    /// reader.access_tail(2, &mut |tail| assert_eq!(tails[2], *tail)).unwrap();
+    /// ```
+    pub fn new(writer: W, count: u64) -> Result<TailsWriter<W>, IndyCryptoError> {
+        trace!("TailsWriter::new: >>> count: {:?}", count);
+
+        let mut tails_writer = TailsWriter {
+            writer,
+            hasher: Sha256::default(),
+            count,
+            written: 0
+        };
+
+        let mut header: Vec<u8> = Vec::new();
+        header.extend_from_slice(&TAILS_FILE_MAGIC);
+        header.push(TAILS_FILE_VERSION);
+        header.push(TAILS_CURVE_BN254);
+        header.extend_from_slice(&_u64_to_bytes(count));
+        tails_writer._write(&header)?;
+
+        let entries_offset = _entries_offset(count)?;
+        for i in 0..count {
+            tails_writer._write(&_u64_to_bytes(entries_offset + i * ENTRY_SIZE))?;
+        }
+
+        trace!("TailsWriter::new: <<<");
+
+        Ok(tails_writer)
+    }
+
+    /// Appends next tail to tails file.
+    ///
+    /// # Arguments
+    /// * `tail` - Tail with index equal to the number of already written tails.
+    pub fn write_tail(&mut self, tail: &Tail) -> Result<(), IndyCryptoError> {
+        if self.written >= self.count {
+            return Err(IndyCryptoError::InvalidState(format!("All {} tails are already written", self.count)));
+        }
+
+        self._write(&tail.to_bytes_compressed()?)?;
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Completes tails file with its SHA-256 digest.
+    ///
+    /// Returns underlying writer and the digest.
+    pub fn finalize(mut self) -> Result<(W, Vec<u8>), IndyCryptoError> {
+        trace!("TailsWriter::finalize: >>> written: {:?}", self.written);
+
+        if self.written != self.count {
+            return Err(IndyCryptoError::InvalidState(format!("Only {} of {} tails are written", self.written, self.count)));
+        }
+
+        let hash = self.hasher.result().as_slice().to_vec();
+
+        self.writer.write_all(&hash)
+            .and_then(|_| self.writer.flush())
+            .map_err(|err| IndyCryptoError::IOError(err))?;
+
+        trace!("TailsWriter::finalize: <<< hash: {:?}", hash);
+
+        Ok((self.writer, hash))
+    }
+
+    fn _write(&mut self, bytes: &[u8]) -> Result<(), IndyCryptoError> {
+        self.hasher.input(bytes);
+        self.writer.write_all(bytes)
+            .map_err(|err| IndyCryptoError::IOError(err))
+    }
+}
+
+/// Reads tails from tails file written by `TailsWriter`.
+///
+/// Only header is read on creation, tails are read on access.
+/// File may come from untrusted source, so `verify` must be called
+/// against digest from revocation registry definition before tails are used.
+pub struct TailsReader<R> where R: Read + Seek {
+    reader: RefCell<R>,
+    count: u64,
+    hash: Vec<u8>
+}
+
+impl<R> TailsReader<R> where R: Read + Seek {
+    /// Reads and checks header of tails file.
+    ///
+    /// # Arguments
+    /// * `reader` - Source of tails file.
+    pub fn new(mut reader: R) -> Result<TailsReader<R>, IndyCryptoError> {
+        trace!("TailsReader::new: >>>");
+
+        let len = reader.seek(SeekFrom::End(0))
+            .map_err(|err| IndyCryptoError::IOError(err))?;
+
+        if len < HEADER_SIZE + TAILS_HASH_SIZE as u64 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file is too short: {} bytes", len)));
+        }
+
+        let header = _read_at(&mut reader, 0, HEADER_SIZE as usize)?;
+
+        if header[0..4] != TAILS_FILE_MAGIC {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails file magic")));
+        }
+
+        if header[4] != TAILS_FILE_VERSION {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported tails file version: {}", header[4])));
+        }
+
+        if header[5] != TAILS_CURVE_BN254 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported tails curve: {}", header[5])));
+        }
+
+        let count = _u64_from_bytes(&header[6..14]);
+
+        let expected_len = _entries_offset(count)?
+            .checked_add(count.checked_mul(ENTRY_SIZE)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid tails count: {}", count)))?)
+            .and_then(|len| len.checked_add(TAILS_HASH_SIZE as u64))
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid tails count: {}", count)))?;
+
+        if len != expected_len {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file length {} doesn't correspond to tails count {}", len, count)));
+        }
+
+        let hash = _read_at(&mut reader, len - TAILS_HASH_SIZE as u64, TAILS_HASH_SIZE)?;
+
+        let tails_reader = TailsReader {
+            reader: RefCell::new(reader),
+            count,
+            hash
+        };
+
+        trace!("TailsReader::new: <<< count: {:?}", count);
+
+        Ok(tails_reader)
+    }
+
+    /// Returns number of tails in tails file.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns SHA-256 digest stored in tails file.
+    pub fn hash(&self) -> &[u8] {
+        &self.hash
+    }
+
+    /// Checks integrity of tails file.
+    ///
+    /// Returns true if stored digest equals to `expected_hash`, digest of file content equals to stored one,
+    /// offsets table is consistent and every tail is a valid group element.
+    ///
+    /// # Arguments
+    /// * `expected_hash` - SHA-256 digest of tails file obtained from trusted source.
+    pub fn verify(&self, expected_hash: &[u8]) -> Result<bool, IndyCryptoError> {
+        trace!("TailsReader::verify: >>> expected_hash: {:?}", expected_hash);
+
+        if self.hash.as_slice() != expected_hash {
+            trace!("TailsReader::verify: <<< valid: false (stored hash mismatch)");
+            return Ok(false);
+        }
+
+        let mut reader = self.reader.borrow_mut();
+
+        let content_len = _entries_offset(self.count)? + self.count * ENTRY_SIZE;
+
+        reader.seek(SeekFrom::Start(0))
+            .map_err(|err| IndyCryptoError::IOError(err))?;
+
+        let mut hasher = Sha256::default();
+        let mut buf = vec![0u8; READ_CHUNK_SIZE];
+        let mut remaining = content_len;
+        while remaining > 0 {
+            let chunk = if remaining < READ_CHUNK_SIZE as u64 { remaining as usize } else { READ_CHUNK_SIZE };
+            reader.read_exact(&mut buf[..chunk])
+                .map_err(|err| IndyCryptoError::IOError(err))?;
+            hasher.input(&buf[..chunk]);
+            remaining -= chunk as u64;
+        }
+
+        if hasher.result().as_slice() != self.hash.as_slice() {
+            trace!("TailsReader::verify: <<< valid: false (content hash mismatch)");
+            return Ok(false);
+        }
+
+        let entries_offset = _entries_offset(self.count)?;
+        for i in 0..self.count {
+            let offset = _u64_from_bytes(&_read_at(&mut *reader, HEADER_SIZE + i * OFFSET_SIZE, OFFSET_SIZE as usize)?);
+            if offset != entries_offset + i * ENTRY_SIZE {
+                trace!("TailsReader::verify: <<< valid: false (invalid offset of tail {})", i);
+                return Ok(false);
+            }
+
+            if PointG2::from_bytes_compressed(&_read_at(&mut *reader, offset, ENTRY_SIZE as usize)?).is_err() {
+                trace!("TailsReader::verify: <<< valid: false (invalid tail {})", i);
+                return Ok(false);
+            }
+        }
+
+        trace!("TailsReader::verify: <<< valid: true");
+
+        Ok(true)
+    }
+
+    fn _read_tail(&self, tail_id: u64) -> Result<Tail, IndyCryptoError> {
+        if tail_id >= self.count {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails range {}", tail_id, self.count)));
+        }
+
+        let mut reader = self.reader.borrow_mut();
+
+        let offset = _u64_from_bytes(&_read_at(&mut *reader, HEADER_SIZE + tail_id * OFFSET_SIZE, OFFSET_SIZE as usize)?);
+
+        let entries_offset = _entries_offset(self.count)?;
+        if offset < entries_offset || offset > entries_offset + (self.count - 1) * ENTRY_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid offset {} of tail {}", offset, tail_id)));
+        }
+
+        PointG2::from_bytes_compressed(&_read_at(&mut *reader, offset, ENTRY_SIZE as usize)?)
+    }
+}
+
+impl<R> TailsAccessor for TailsReader<R> where R: Read + Seek {
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        let tail = self._read_tail(tail_id)?;
+        Ok(accessor(&tail))
+    }
+}
+
+fn _entries_offset(count: u64) -> Result<u64, IndyCryptoError> {
+    count.checked_mul(OFFSET_SIZE)
+        .and_then(|len| len.checked_add(HEADER_SIZE))
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid tails count: {}", count)))
+}
+
+fn _read_at<R>(reader: &mut R, offset: u64, len: usize) -> Result<Vec<u8>, IndyCryptoError> where R: Read + Seek {
+    let mut buf = vec![0u8; len];
+    reader.seek(SeekFrom::Start(offset))
+        .and_then(|_| reader.read_exact(&mut buf))
+        .map_err(|err| IndyCryptoError::IOError(err))?;
+    Ok(buf)
+}
+
+fn _u64_to_bytes(value: u64) -> [u8; 8] {
+    let mut bytes = [0u8; 8];
+    for i in 0..8 {
+        bytes[i] = (value >> (56 - 8 * i)) as u8;
+    }
+    bytes
+}
+
+fn _u64_from_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accumulator::{AccumulatorPrivateKey, AccumulatorPublicKey};
+    use std::io::Cursor;
+
+    fn _tails() -> Vec<Tail> {
+        let priv_key = AccumulatorPrivateKey::new().unwrap();
+        let pub_key = AccumulatorPublicKey::new(3, &priv_key).unwrap();
+        priv_key.generate_tails(&pub_key).unwrap()
+    }
+
+    fn _tails_file(tails: &[Tail]) -> (Vec<u8>, Vec<u8>) {
+        let mut writer = TailsWriter::new(Vec::new(), tails.len() as u64).unwrap();
+        for tail in tails.iter() {
+            writer.write_tail(tail).unwrap();
+        }
+        writer.finalize().unwrap()
+    }
+
+    #[test]
+    fn tails_file_works() {
+        let tails = _tails();
+        let (bytes, hash) = _tails_file(&tails);
+
+        assert_eq!(HEADER_SIZE as usize + tails.len() * (OFFSET_SIZE + ENTRY_SIZE) as usize + TAILS_HASH_SIZE, bytes.len());
+        assert_eq!(TAILS_HASH_SIZE, hash.len());
+
+        let reader = TailsReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(tails.len() as u64, reader.count());
+        assert_eq!(hash.as_slice(), reader.hash());
+        assert!(reader.verify(&hash).unwrap());
+
+        for (i, expected) in tails.iter().enumerate() {
+            reader.access_tail(i as u64, &mut |tail| assert_eq!(expected, tail)).unwrap();
+        }
+    }
+
+    #[test]
+    fn tails_reader_access_tail_works_for_out_of_range_tail() {
+        let tails = _tails();
+        let (bytes, _) = _tails_file(&tails);
+
+        let reader = TailsReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.access_tail(tails.len() as u64, &mut |_| ()).is_err());
+    }
+
+    #[test]
+    fn tails_reader_verify_works_for_other_hash() {
+        let (bytes, mut hash) = _tails_file(&_tails());
+        hash[0] ^= 1;
+
+        let reader = TailsReader::new(Cursor::new(bytes)).unwrap();
+        assert!(!reader.verify(&hash).unwrap());
+    }
+
+    #[test]
+    fn tails_reader_verify_works_for_tampered_tail() {
+        let (mut bytes, hash) = _tails_file(&_tails());
+        let len = bytes.len();
+        bytes[len - TAILS_HASH_SIZE - 1] ^= 1;
+
+        let reader = TailsReader::new(Cursor::new(bytes)).unwrap();
+        assert!(!reader.verify(&hash).unwrap());
+    }
+
+    #[test]
+    fn tails_reader_new_works_for_invalid_magic() {
+        let (mut bytes, _) = _tails_file(&_tails());
+        bytes[0] = 0;
+
+        assert!(TailsReader::new(Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn tails_reader_new_works_for_truncated_file() {
+        let (mut bytes, _) = _tails_file(&_tails());
+        let len = bytes.len();
+        bytes.truncate(len - 1);
+
+        assert!(TailsReader::new(Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn tails_writer_finalize_works_for_missed_tails() {
+        let tails = _tails();
+        let mut writer = TailsWriter::new(Vec::new(), tails.len() as u64).unwrap();
+        writer.write_tail(&tails[0]).unwrap();
+
+        assert!(writer.finalize().is_err());
+    }
+
+    #[test]
+    fn tails_writer_write_tail_works_for_extra_tail() {
+        let tails = _tails();
+        let mut writer = TailsWriter::new(Vec::new(), 1).unwrap();
+        writer.write_tail(&tails[0]).unwrap();
+
+        assert!(writer.write_tail(&tails[1]).is_err());
+    }
+}
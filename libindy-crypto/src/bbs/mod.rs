@@ -0,0 +1,637 @@
+use bn::BigNumber;
+use cl::{max_attrs_count, Nonce};
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, Pair, PointG1, PointG2};
+use sigma::{PointG1Group, SigmaProof, Statement};
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use sha2::{Digest, Sha256};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+// Indexes of proof of knowledge secrets, responses for hidden messages follow them
+const SECRET_E: usize = 0;
+const SECRET_R2: usize = 1;
+const SECRET_R3: usize = 2;
+const SECRET_S: usize = 3;
+const SECRETS_COUNT: usize = 4;
+
+/// Names of attributes signed as BBS+ messages.
+/// Every attribute is signed as a separate message, so any subset of them can be disclosed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessagesSchema {
+    attrs: BTreeSet<String>
+}
+
+impl MessagesSchema {
+    pub fn attrs(&self) -> &BTreeSet<String> {
+        &self.attrs
+    }
+}
+
+impl JsonEncodable for MessagesSchema {}
+
+impl<'a> JsonDecodable<'a> for MessagesSchema {}
+
+/// A list of attributes a BBS+ signature is created for.
+#[derive(Debug)]
+pub struct MessagesSchemaBuilder {
+    attrs: BTreeSet<String>
+}
+
+impl MessagesSchemaBuilder {
+    pub fn new() -> Result<MessagesSchemaBuilder, IndyCryptoError> {
+        Ok(MessagesSchemaBuilder {
+            attrs: BTreeSet::new()
+        })
+    }
+
+    pub fn add_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
+        self.attrs.insert(attr.to_owned());
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<MessagesSchema, IndyCryptoError> {
+        if self.attrs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of attributes is empty")));
+        }
+
+        let max_count = max_attrs_count();
+        if self.attrs.len() > max_count {
+            return Err(IndyCryptoError::AnoncredsTooManyAttributes(
+                format!("Messages schema contains {} attributes, but at most {} are supported", self.attrs.len(), max_count)));
+        }
+
+        Ok(MessagesSchema {
+            attrs: self.attrs
+        })
+    }
+}
+
+/// Values of attributes from `MessagesSchema` represented as elements of group order.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Messages {
+    attrs_values: BTreeMap<String, GroupOrderElement>
+}
+
+impl Messages {
+    pub fn attrs_values(&self) -> &BTreeMap<String, GroupOrderElement> {
+        &self.attrs_values
+    }
+}
+
+impl JsonEncodable for Messages {}
+
+impl<'a> JsonDecodable<'a> for Messages {}
+
+#[derive(Debug)]
+pub struct MessagesBuilder {
+    attrs_values: BTreeMap<String, GroupOrderElement>
+}
+
+impl MessagesBuilder {
+    pub fn new() -> Result<MessagesBuilder, IndyCryptoError> {
+        Ok(MessagesBuilder {
+            attrs_values: BTreeMap::new()
+        })
+    }
+
+    /// Adds integer value of attribute. Value must be less than group order.
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `dec_value` - Decimal representation of value.
+    pub fn add_value(&mut self, attr: &str, dec_value: &str) -> Result<(), IndyCryptoError> {
+        let value = BigNumber::from_dec_checked(dec_value)?;
+        let order = BigNumber::from_bytes(&GroupOrderElement::order_bytes())?;
+
+        if value.is_negative()? || value >= order {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Value of attribute {} is out of range of group order", attr)));
+        }
+
+        self.attrs_values.insert(attr.to_owned(), GroupOrderElement::from_bytes(&value.to_bytes()?)?);
+        Ok(())
+    }
+
+    /// Adds arbitrary bytes value of attribute represented by its SHA-256 hash.
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `value` - Raw value.
+    pub fn add_hashed_value(&mut self, attr: &str, value: &[u8]) -> Result<(), IndyCryptoError> {
+        let mut hasher = Sha256::default();
+        hasher.input(value);
+
+        // Reduces hash modulo group order
+        let value = GroupOrderElement::from_bytes(hasher.result().as_slice())?
+            .add_mod(&GroupOrderElement::from_bytes(&[])?)?;
+
+        self.attrs_values.insert(attr.to_owned(), value);
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<Messages, IndyCryptoError> {
+        Ok(Messages {
+            attrs_values: self.attrs_values
+        })
+    }
+}
+
+/// BBS+ public key: generators `g1`, `g2`, `w = g2^x` and per attribute bases of messages.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PublicKey {
+    g1: PointG1,
+    g2: PointG2,
+    w: PointG2,
+    h0: PointG1,
+    h: BTreeMap<String /* attr_name */, PointG1>
+}
+
+impl PublicKey {
+    pub fn attrs(&self) -> BTreeSet<String> {
+        self.h.keys().cloned().collect()
+    }
+}
+
+impl JsonEncodable for PublicKey {}
+
+impl<'a> JsonDecodable<'a> for PublicKey {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PrivateKey {
+    x: GroupOrderElement
+}
+
+impl JsonEncodable for PrivateKey {}
+
+impl<'a> JsonDecodable<'a> for PrivateKey {}
+
+/// BBS+ signature `(A, e, s)` where `A = (g1 * h0^s * prod(h_i^m_i))^(1 / (e + x))`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Signature {
+    a: PointG1,
+    e: GroupOrderElement,
+    s: GroupOrderElement
+}
+
+impl JsonEncodable for Signature {}
+
+impl<'a> JsonDecodable<'a> for Signature {}
+
+/// Zero-knowledge proof of knowledge of BBS+ signature that discloses values of some attributes.
+/// Signature is randomized, so proofs created from the same signature are unlinkable.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Proof {
+    a_prime: PointG1,
+    a_bar: PointG1,
+    d: PointG1,
+    revealed_attrs: BTreeMap<String /* attr_name */, GroupOrderElement>,
+    sigma_proof: SigmaProof<GroupOrderElement>
+}
+
+impl Proof {
+    pub fn revealed_attrs(&self) -> &BTreeMap<String, GroupOrderElement> {
+        &self.revealed_attrs
+    }
+}
+
+impl JsonEncodable for Proof {}
+
+impl<'a> JsonDecodable<'a> for Proof {}
+
+pub struct Bbs {}
+
+impl Bbs {
+    /// Creates and returns BBS+ public and private keys for messages schema.
+    ///
+    /// # Arguments
+    /// * `messages_schema` - Messages schema entity.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bbs::{Bbs, MessagesSchemaBuilder};
+    ///
+    /// let mut messages_schema_builder = MessagesSchemaBuilder::new().unwrap();
+    /// messages_schema_builder.add_attr("name").unwrap();
+    /// messages_schema_builder.add_attr("age").unwrap();
+    /// let messages_schema = messages_schema_builder.finalize().unwrap();
+    ///
+    /// let (_pub_key, _priv_key) = Bbs::new_keys(&messages_schema).unwrap();
+    /// ```
+    pub fn new_keys(messages_schema: &MessagesSchema) -> Result<(PublicKey, PrivateKey), IndyCryptoError> {
+        trace!("Bbs::new_keys: >>> messages_schema: {:?}", messages_schema);
+
+        let g2 = PointG2::new()?;
+        let x = GroupOrderElement::new()?;
+
+        let mut h = BTreeMap::new();
+        for attr in messages_schema.attrs.iter() {
+            h.insert(attr.to_owned(), PointG1::new()?);
+        }
+
+        let pub_key = PublicKey {
+            g1: PointG1::new()?,
+            g2,
+            w: g2.mul(&x)?,
+            h0: PointG1::new()?,
+            h
+        };
+        let priv_key = PrivateKey { x };
+
+        trace!("Bbs::new_keys: <<< pub_key: {:?}, priv_key: {:?}", pub_key, priv_key);
+
+        Ok((pub_key, priv_key))
+    }
+
+    /// Signs messages.
+    ///
+    /// # Arguments
+    /// * `messages` - Values of all attributes of public key.
+    /// * `pub_key` - Public key.
+    /// * `priv_key` - Private key.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bbs::{Bbs, MessagesBuilder, MessagesSchemaBuilder};
+    ///
+    /// let mut messages_schema_builder = MessagesSchemaBuilder::new().unwrap();
+    /// messages_schema_builder.add_attr("name").unwrap();
+    /// messages_schema_builder.add_attr("age").unwrap();
+    /// let messages_schema = messages_schema_builder.finalize().unwrap();
+    ///
+    /// let (pub_key, priv_key) = Bbs::new_keys(&messages_schema).unwrap();
+    ///
+    /// let mut messages_builder = MessagesBuilder::new().unwrap();
+    /// messages_builder.add_hashed_value("name", b"Alex").unwrap();
+    /// messages_builder.add_value("age", "28").unwrap();
+    /// let messages = messages_builder.finalize().unwrap();
+    ///
+    /// let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+    /// assert!(Bbs::verify(&signature, &messages, &pub_key).unwrap());
+    /// ```
+    pub fn sign(messages: &Messages, pub_key: &PublicKey, priv_key: &PrivateKey) -> Result<Signature, IndyCryptoError> {
+        trace!("Bbs::sign: >>> messages: {:?}, pub_key: {:?}", messages, pub_key);
+
+        _check_messages(messages, pub_key)?;
+
+        let e = GroupOrderElement::new()?;
+        let s = GroupOrderElement::new()?;
+
+        let b = _commitment(messages, pub_key, &s)?;
+        let a = b.mul(&e.add_mod(&priv_key.x)?.inverse()?)?;
+
+        let signature = Signature { a, e, s };
+
+        trace!("Bbs::sign: <<< signature: {:?}", signature);
+
+        Ok(signature)
+    }
+
+    /// Verifies signature of messages and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature to verify.
+    /// * `messages` - Values of all attributes of public key.
+    /// * `pub_key` - Public key.
+    pub fn verify(signature: &Signature, messages: &Messages, pub_key: &PublicKey) -> Result<bool, IndyCryptoError> {
+        trace!("Bbs::verify: >>> signature: {:?}, messages: {:?}, pub_key: {:?}", signature, messages, pub_key);
+
+        _check_messages(messages, pub_key)?;
+
+        let b = _commitment(messages, pub_key, &signature.s)?;
+
+        let valid = !signature.a.is_inf()? &&
+            Pair::pair(&signature.a, &pub_key.w.add(&pub_key.g2.mul(&signature.e)?)?)?
+                .eq(&Pair::pair(&b, &pub_key.g2)?);
+
+        trace!("Bbs::verify: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Creates proof of knowledge of signature that discloses values of `revealed_attrs`
+    /// and hides values of other attributes.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature of messages.
+    /// * `messages` - Values of all attributes of public key.
+    /// * `pub_key` - Public key.
+    /// * `revealed_attrs` - Names of attributes to disclose.
+    /// * `nonce` - Verifier nonce.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bbs::{Bbs, MessagesBuilder, MessagesSchemaBuilder};
+    /// use indy_crypto::cl::new_nonce;
+    ///
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut messages_schema_builder = MessagesSchemaBuilder::new().unwrap();
+    /// messages_schema_builder.add_attr("name").unwrap();
+    /// messages_schema_builder.add_attr("age").unwrap();
+    /// let messages_schema = messages_schema_builder.finalize().unwrap();
+    ///
+    /// let (pub_key, priv_key) = Bbs::new_keys(&messages_schema).unwrap();
+    ///
+    /// let mut messages_builder = MessagesBuilder::new().unwrap();
+    /// messages_builder.add_hashed_value("name", b"Alex").unwrap();
+    /// messages_builder.add_value("age", "28").unwrap();
+    /// let messages = messages_builder.finalize().unwrap();
+    ///
+    /// let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+    ///
+    /// let mut revealed_attrs = BTreeSet::new();
+    /// revealed_attrs.insert("age".to_string());
+    ///
+    /// let nonce = new_nonce().unwrap();
+    /// let proof = Bbs::prove(&signature, &messages, &pub_key, &revealed_attrs, &nonce).unwrap();
+    ///
+    /// assert!(Bbs::verify_proof(&proof, &pub_key, &nonce).unwrap());
+    /// assert_eq!(messages.attrs_values()["age"], proof.revealed_attrs()["age"]);
+    /// ```
+    pub fn prove(signature: &Signature,
+                 messages: &Messages,
+                 pub_key: &PublicKey,
+                 revealed_attrs: &BTreeSet<String>,
+                 nonce: &Nonce) -> Result<Proof, IndyCryptoError> {
+        trace!("Bbs::prove: >>> signature: {:?}, messages: {:?}, pub_key: {:?}, revealed_attrs: {:?}, nonce: {:?}",
+               signature, messages, pub_key, revealed_attrs, nonce);
+
+        _check_messages(messages, pub_key)?;
+
+        if let Some(attr) = revealed_attrs.iter().find(|attr| !pub_key.h.contains_key(*attr)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Revealed attribute {} not found in public key", attr)));
+        }
+
+        let r1 = GroupOrderElement::new()?;
+        let r2 = GroupOrderElement::new()?;
+        let r3 = r1.inverse()?;
+
+        let b_r1 = _commitment(messages, pub_key, &signature.s)?.mul(&r1)?;
+
+        let a_prime = signature.a.mul(&r1)?;
+        let a_bar = a_prime.mul(&signature.e.mod_neg()?)?.add(&b_r1)?;
+        let d = b_r1.sub(&pub_key.h0.mul(&r2)?)?;
+        let s_prime = signature.s.sub_mod(&r2.mul_mod(&r3)?)?;
+
+        let mut revealed_values = BTreeMap::new();
+        let mut secrets = vec![signature.e.mod_neg()?, r2, r3, s_prime.mod_neg()?];
+
+        for (attr, value) in messages.attrs_values.iter() {
+            if revealed_attrs.contains(attr) {
+                revealed_values.insert(attr.to_owned(), *value);
+            } else {
+                secrets.push(value.mod_neg()?);
+            }
+        }
+
+        let statement = _proof_statement(pub_key, &a_prime, &a_bar, &d, &revealed_values)?;
+        let secrets: Vec<&GroupOrderElement> = secrets.iter().collect();
+        let sigma_proof = statement.prove(&PointG1Group::new(), &secrets, &_proof_context(nonce)?)?;
+
+        let proof = Proof {
+            a_prime,
+            a_bar,
+            d,
+            revealed_attrs: revealed_values,
+            sigma_proof
+        };
+
+        trace!("Bbs::prove: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    /// Verifies proof of knowledge of signature and returns true - if proof valid or false otherwise.
+    /// Disclosed values are available by `Proof::revealed_attrs`.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof to verify.
+    /// * `pub_key` - Public key.
+    /// * `nonce` - Verifier nonce.
+    pub fn verify_proof(proof: &Proof, pub_key: &PublicKey, nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        trace!("Bbs::verify_proof: >>> proof: {:?}, pub_key: {:?}, nonce: {:?}", proof, pub_key, nonce);
+
+        if let Some(attr) = proof.revealed_attrs.keys().find(|attr| !pub_key.h.contains_key(*attr)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Revealed attribute {} not found in public key", attr)));
+        }
+
+        let valid = !proof.a_prime.is_inf()? &&
+            Pair::pair(&proof.a_prime, &pub_key.w)?.eq(&Pair::pair(&proof.a_bar, &pub_key.g2)?) &&
+            _proof_statement(pub_key, &proof.a_prime, &proof.a_bar, &proof.d, &proof.revealed_attrs)?
+                .verify(&PointG1Group::new(), &proof.sigma_proof, &_proof_context(nonce)?)?;
+
+        trace!("Bbs::verify_proof: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+}
+
+fn _check_messages(messages: &Messages, pub_key: &PublicKey) -> Result<(), IndyCryptoError> {
+    if messages.attrs_values.len() != pub_key.h.len() ||
+        messages.attrs_values.keys().any(|attr| !pub_key.h.contains_key(attr)) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Messages don't correspond to public key")));
+    }
+    Ok(())
+}
+
+// B = g1 * h0^s * prod(h_i^m_i)
+fn _commitment(messages: &Messages, pub_key: &PublicKey, s: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+    let mut bases = vec![pub_key.h0];
+    let mut exps = vec![*s];
+
+    for (attr, value) in messages.attrs_values.iter() {
+        bases.push(pub_key.h[attr]);
+        exps.push(*value);
+    }
+
+    pub_key.g1.add(&PointG1::multi_scalar_mul(&bases, &exps)?)
+}
+
+// Relations proven for randomized signature (A' = A^r1, A_bar = A'^(-e) * B^r1, d = B^r1 * h0^(-r2)):
+// A_bar / d = A'^(-e) * h0^r2
+// g1 * prod(h_i^m_i) for revealed = d^r3 * h0^(-s') * prod(h_j^(-m_j)) for hidden
+fn _proof_statement(pub_key: &PublicKey,
+                    a_prime: &PointG1,
+                    a_bar: &PointG1,
+                    d: &PointG1,
+                    revealed_attrs: &BTreeMap<String, GroupOrderElement>) -> Result<Statement<PointG1Group>, IndyCryptoError> {
+    let hidden_attrs: Vec<&String> = pub_key.h.keys().filter(|attr| !revealed_attrs.contains_key(*attr)).collect();
+
+    let mut statement = Statement::new(SECRETS_COUNT + hidden_attrs.len());
+
+    statement.add_relation(a_bar.sub(d)?, vec![(*a_prime, SECRET_E), (pub_key.h0, SECRET_R2)])?;
+
+    let mut revealed = pub_key.g1;
+    for (attr, value) in revealed_attrs.iter() {
+        revealed = revealed.add(&pub_key.h[attr].mul(value)?)?;
+    }
+
+    let mut terms = vec![(*d, SECRET_R3), (pub_key.h0, SECRET_S)];
+    for (idx, attr) in hidden_attrs.iter().enumerate() {
+        terms.push((pub_key.h[*attr], SECRETS_COUNT + idx));
+    }
+
+    statement.add_relation(revealed, terms)?;
+
+    Ok(statement)
+}
+
+fn _proof_context(nonce: &Nonce) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+    Ok(vec![nonce.to_bytes()?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::new_nonce;
+
+    fn _messages_schema() -> MessagesSchema {
+        let mut messages_schema_builder = MessagesSchemaBuilder::new().unwrap();
+        messages_schema_builder.add_attr("name").unwrap();
+        messages_schema_builder.add_attr("sex").unwrap();
+        messages_schema_builder.add_attr("age").unwrap();
+        messages_schema_builder.add_attr("height").unwrap();
+        messages_schema_builder.finalize().unwrap()
+    }
+
+    fn _messages() -> Messages {
+        let mut messages_builder = MessagesBuilder::new().unwrap();
+        messages_builder.add_hashed_value("name", b"Alex").unwrap();
+        messages_builder.add_hashed_value("sex", b"male").unwrap();
+        messages_builder.add_value("age", "28").unwrap();
+        messages_builder.add_value("height", "175").unwrap();
+        messages_builder.finalize().unwrap()
+    }
+
+    fn _revealed_attrs(attrs: &[&str]) -> BTreeSet<String> {
+        attrs.iter().map(|attr| attr.to_string()).collect()
+    }
+
+    #[test]
+    fn messages_schema_builder_works_for_empty_schema() {
+        assert!(MessagesSchemaBuilder::new().unwrap().finalize().is_err());
+    }
+
+    #[test]
+    fn messages_builder_add_value_works_for_value_out_of_group_order() {
+        let order = BigNumber::from_bytes(&GroupOrderElement::order_bytes()).unwrap();
+
+        let mut messages_builder = MessagesBuilder::new().unwrap();
+        assert!(messages_builder.add_value("age", &order.to_dec().unwrap()).is_err());
+        assert!(messages_builder.add_value("age", "-1").is_err());
+        messages_builder.add_value("age", &order.sub(&BigNumber::from_u32(1).unwrap()).unwrap().to_dec().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn sign_and_verify_works() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+        assert!(Bbs::verify(&signature, &messages, &pub_key).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_messages() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let signature = Bbs::sign(&_messages(), &pub_key, &priv_key).unwrap();
+
+        let mut messages_builder = MessagesBuilder::new().unwrap();
+        messages_builder.add_hashed_value("name", b"Alex").unwrap();
+        messages_builder.add_hashed_value("sex", b"male").unwrap();
+        messages_builder.add_value("age", "29").unwrap();
+        messages_builder.add_value("height", "175").unwrap();
+        let messages = messages_builder.finalize().unwrap();
+
+        assert!(!Bbs::verify(&signature, &messages, &pub_key).unwrap());
+    }
+
+    #[test]
+    fn sign_works_for_missed_message() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+
+        let mut messages_builder = MessagesBuilder::new().unwrap();
+        messages_builder.add_hashed_value("name", b"Alex").unwrap();
+        let messages = messages_builder.finalize().unwrap();
+
+        assert!(Bbs::sign(&messages, &pub_key, &priv_key).is_err());
+    }
+
+    #[test]
+    fn prove_and_verify_proof_works() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+        let nonce = new_nonce().unwrap();
+
+        for revealed_attrs in [&[][..], &["age"][..], &["name", "height"][..], &["name", "sex", "age", "height"][..]].iter() {
+            let proof = Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(revealed_attrs), &nonce).unwrap();
+            assert!(Bbs::verify_proof(&proof, &pub_key, &nonce).unwrap());
+
+            assert_eq!(revealed_attrs.len(), proof.revealed_attrs().len());
+            for attr in revealed_attrs.iter() {
+                assert_eq!(messages.attrs_values()[*attr], proof.revealed_attrs()[*attr]);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_proof_works_for_other_nonce() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+
+        let proof = Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(&["age"]), &new_nonce().unwrap()).unwrap();
+        assert!(!Bbs::verify_proof(&proof, &pub_key, &new_nonce().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn verify_proof_works_for_changed_revealed_value() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let mut proof = Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(&["age"]), &nonce).unwrap();
+        proof.revealed_attrs.insert("age".to_string(), GroupOrderElement::from_bytes(&[29]).unwrap());
+
+        assert!(!Bbs::verify_proof(&proof, &pub_key, &nonce).unwrap());
+    }
+
+    #[test]
+    fn verify_proof_works_for_other_pub_key() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let (pub_key_other, _) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let proof = Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(&["age"]), &nonce).unwrap();
+        assert!(!Bbs::verify_proof(&proof, &pub_key_other, &nonce).unwrap());
+    }
+
+    #[test]
+    fn prove_works_for_unknown_revealed_attr() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+
+        assert!(Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(&["zip"]), &new_nonce().unwrap()).is_err());
+    }
+
+    #[test]
+    fn proof_serialization_works() {
+        let (pub_key, priv_key) = Bbs::new_keys(&_messages_schema()).unwrap();
+        let messages = _messages();
+        let signature = Bbs::sign(&messages, &pub_key, &priv_key).unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let proof = Bbs::prove(&signature, &messages, &pub_key, &_revealed_attrs(&["age"]), &nonce).unwrap();
+        let proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+        let pub_key = PublicKey::from_json(&pub_key.to_json().unwrap()).unwrap();
+
+        assert!(Bbs::verify_proof(&proof, &pub_key, &nonce).unwrap());
+    }
+}
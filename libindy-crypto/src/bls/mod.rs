@@ -1,8 +1,83 @@
 use errors::IndyCryptoError;
-use pair::{GroupOrderElement, PointG2, PointG1, Pair};
+use pair::{GroupOrderElement, PointG2, PointG1, Pair, MillerAccumulator};
+use utils::drbg::with_seeded_rng;
+use utils::rng::random_bytes;
 
 use sha2::{Sha256, Digest};
 
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const GENERATOR_LABEL_DST: &[u8] = b"indy-crypto:bls:generator:";
+const PROOF_OF_POSSESSION_DST: &[u8] = b"indy-crypto:bls:pop:";
+
+// Tagged bytes start with 0x10 | 0x02 (compressed) | 0x01 (little-endian)
+const FORMAT_TAG_BASE: u8 = 0x10;
+const FORMAT_TAG_COMPRESSED: u8 = 0x02;
+const FORMAT_TAG_LITTLE_ENDIAN: u8 = 0x01;
+
+/// Default size of random exponents used by `Bls::verify_batch`.
+pub const DEFAULT_BATCH_SECURITY_BITS: usize = 80;
+
+// Random exponents must be shorter than group order
+const MAX_BATCH_SECURITY_BITS: usize = 128;
+
+/// Byte order of point coordinates in serialized BLS entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    BigEndian,
+    LittleEndian
+}
+
+/// Serialization format of BLS verification keys and signatures.
+///
+/// `as_bytes` and `from_bytes` always use big-endian uncompressed format (see `BlsFormat::default`).
+/// Other formats allow to exchange keys and signatures with pools that use different conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlsFormat {
+    byte_order: ByteOrder,
+    compressed: bool
+}
+
+impl BlsFormat {
+    pub fn new(byte_order: ByteOrder, compressed: bool) -> BlsFormat {
+        BlsFormat { byte_order, compressed }
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// Returns tag byte that prefixes bytes serialized by `to_tagged_bytes` methods.
+    pub fn tag(&self) -> u8 {
+        let mut tag = FORMAT_TAG_BASE;
+        if self.compressed { tag |= FORMAT_TAG_COMPRESSED; }
+        if self.byte_order == ByteOrder::LittleEndian { tag |= FORMAT_TAG_LITTLE_ENDIAN; }
+        tag
+    }
+
+    /// Returns format identified by tag byte.
+    pub fn from_tag(tag: u8) -> Result<BlsFormat, IndyCryptoError> {
+        if tag & !(FORMAT_TAG_COMPRESSED | FORMAT_TAG_LITTLE_ENDIAN) != FORMAT_TAG_BASE {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid BLS format tag: {}", tag)));
+        }
+
+        let byte_order = if tag & FORMAT_TAG_LITTLE_ENDIAN != 0 { ByteOrder::LittleEndian } else { ByteOrder::BigEndian };
+
+        Ok(BlsFormat::new(byte_order, tag & FORMAT_TAG_COMPRESSED != 0))
+    }
+}
+
+impl Default for BlsFormat {
+    fn default() -> BlsFormat {
+        BlsFormat::new(ByteOrder::BigEndian, false)
+    }
+}
+
 /// BLS generator point.
 /// BLS algorithm requires choosing of generator point that must be known to all parties.
 /// The most of BLS methods require generator to be provided.
@@ -29,6 +104,69 @@ impl Generator {
         })
     }
 
+    /// Creates and returns generator point derived from the seed.
+    /// The same seed always gives the same generator.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of at least 16 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// Generator::new_from_seed(b"0123456789abcdef0123456789abcdef").unwrap();
+    /// ```
+    pub fn new_from_seed(seed: &[u8]) -> Result<Generator, IndyCryptoError> {
+        with_seeded_rng(seed, || Generator::new())
+    }
+
+    /// Creates and returns generator point derived from the label by hashing to the curve.
+    /// The same label always gives the same generator and nobody knows discrete logarithm
+    /// of the result, so label-derived generators are nothing-up-my-sleeve ones.
+    ///
+    /// # Arguments
+    /// * `label` - Public label (for example pool or protocol name).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// let gen = Generator::new_from_label(b"indy-pool-generator").unwrap();
+    /// assert!(gen.is_valid().unwrap());
+    /// ```
+    pub fn new_from_label(label: &[u8]) -> Result<Generator, IndyCryptoError> {
+        let mut hash = Vec::new();
+
+        for part in 0u8..2 {
+            let mut hasher = Sha256::default();
+            hasher.input(GENERATOR_LABEL_DST);
+            hasher.input(&[part]);
+            hasher.input(label);
+            hash.extend_from_slice(&hasher.result());
+        }
+
+        let point = PointG2::from_hash(&hash)?;
+        Ok(Generator {
+            point: point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Checks that generator point is on the curve, belongs to the right subgroup and isn't infinity.
+    /// Decoding from bytes already rejects points outside of the subgroup, but infinity is accepted there.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// let gen = Generator::new().unwrap();
+    /// let gen = Generator::from_bytes(gen.as_bytes()).unwrap();
+    /// assert!(gen.is_valid().unwrap());
+    /// ```
+    pub fn is_valid(&self) -> Result<bool, IndyCryptoError> {
+        self.point.is_in_subgroup()
+    }
+
     /// Returns BLS generator point bytes representation.
     ///
     /// # Example
@@ -44,6 +182,7 @@ impl Generator {
     }
 
     /// Creates and returns generator point from bytes representation.
+    /// Bytes that don't represent a point of the right subgroup are rejected.
     ///
     /// # Example
     ///
@@ -91,6 +230,22 @@ impl SignKey {
         })
     }
 
+    /// Creates and returns BLS sign key derived from the seed of any length (at least 16 bytes).
+    /// Unlike `SignKey::new` seed isn't used as raw entropy, so it can be a mnemonic phrase.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of at least 16 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// SignKey::new_from_seed(b"correct horse battery staple").unwrap();
+    /// ```
+    pub fn new_from_seed(seed: &[u8]) -> Result<SignKey, IndyCryptoError> {
+        with_seeded_rng(seed, || SignKey::new(None))
+    }
+
     /// Returns BLS sign key bytes representation.
     ///
     /// # Example
@@ -120,7 +275,7 @@ impl SignKey {
 }
 
 /// BLS verification key.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VerKey {
     point: PointG2,
     bytes: Vec<u8>
@@ -144,6 +299,37 @@ impl VerKey {
         })
     }
 
+    /// Creates and returns aggregated verification key of signers of multi signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `ver_keys` - Non empty list of verification keys
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+    ///
+    /// VerKey::aggregate(&[&ver_key1, &ver_key2]).unwrap();
+    /// ```
+    pub fn aggregate(ver_keys: &[&VerKey]) -> Result<VerKey, IndyCryptoError> {
+        let (first, rest) = ver_keys.split_first()
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Element not found")))?;
+
+        let mut point = first.point;
+        for ver_key in rest {
+            point = point.add(&ver_key.point)?;
+        }
+
+        Ok(VerKey {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
     /// Returns BLS verification key to bytes representation.
     ///
     /// # Example
@@ -171,6 +357,49 @@ impl VerKey {
             }
         )
     }
+
+    /// Returns bytes representation of BLS verification key in given format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{BlsFormat, ByteOrder, Generator, SignKey, VerKey};
+    ///
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    ///
+    /// let format = BlsFormat::new(ByteOrder::LittleEndian, true);
+    /// let bytes = ver_key.to_bytes_with_format(format).unwrap();
+    ///
+    /// let ver_key2 = VerKey::from_bytes_with_format(&bytes, format).unwrap();
+    /// assert_eq!(ver_key.as_bytes(), ver_key2.as_bytes());
+    /// ```
+    pub fn to_bytes_with_format(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        _g2_to_bytes(&self.point, format)
+    }
+
+    /// Creates and returns BLS verification key from bytes representation in given format.
+    pub fn from_bytes_with_format(bytes: &[u8], format: BlsFormat) -> Result<VerKey, IndyCryptoError> {
+        let point = _g2_from_bytes(bytes, format)?;
+        Ok(
+            VerKey {
+                point,
+                bytes: point.to_bytes()?
+            }
+        )
+    }
+
+    /// Returns bytes representation of BLS verification key in given format prefixed with format tag.
+    pub fn to_tagged_bytes(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(_tagged(format, self.to_bytes_with_format(format)?))
+    }
+
+    /// Creates and returns BLS verification key from bytes representation prefixed with format tag.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<VerKey, IndyCryptoError> {
+        let (format, bytes) = _untagged(bytes)?;
+        VerKey::from_bytes_with_format(bytes, format)
+    }
 }
 
 /// BLS signature.
@@ -208,6 +437,33 @@ impl Signature {
             }
         )
     }
+
+    /// Returns bytes representation of BLS signature in given format.
+    pub fn to_bytes_with_format(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        _g1_to_bytes(&self.point, format)
+    }
+
+    /// Creates and returns BLS signature from bytes representation in given format.
+    pub fn from_bytes_with_format(bytes: &[u8], format: BlsFormat) -> Result<Signature, IndyCryptoError> {
+        let point = _g1_from_bytes(bytes, format)?;
+        Ok(
+            Signature {
+                point,
+                bytes: point.to_bytes()?
+            }
+        )
+    }
+
+    /// Returns bytes representation of BLS signature in given format prefixed with format tag.
+    pub fn to_tagged_bytes(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(_tagged(format, self.to_bytes_with_format(format)?))
+    }
+
+    /// Creates and returns BLS signature from bytes representation prefixed with format tag.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<Signature, IndyCryptoError> {
+        let (format, bytes) = _untagged(bytes)?;
+        Signature::from_bytes_with_format(bytes, format)
+    }
 }
 
 /// BLS multi signature.
@@ -283,6 +539,87 @@ impl MultiSignature {
             }
         )
     }
+
+    /// Returns bytes representation of BLS multi signature in given format.
+    pub fn to_bytes_with_format(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        _g1_to_bytes(&self.point, format)
+    }
+
+    /// Creates and returns BLS multi signature from bytes representation in given format.
+    pub fn from_bytes_with_format(bytes: &[u8], format: BlsFormat) -> Result<MultiSignature, IndyCryptoError> {
+        let point = _g1_from_bytes(bytes, format)?;
+        Ok(
+            MultiSignature {
+                point,
+                bytes: point.to_bytes()?
+            }
+        )
+    }
+
+    /// Returns bytes representation of BLS multi signature in given format prefixed with format tag.
+    pub fn to_tagged_bytes(&self, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(_tagged(format, self.to_bytes_with_format(format)?))
+    }
+
+    /// Creates and returns BLS multi signature from bytes representation prefixed with format tag.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<MultiSignature, IndyCryptoError> {
+        let (format, bytes) = _untagged(bytes)?;
+        MultiSignature::from_bytes_with_format(bytes, format)
+    }
+}
+
+/// Proof that owner of BLS verification key knows corresponding sign key.
+///
+/// Multi signatures can be safely verified against aggregated verification key
+/// only if every aggregated key has proof of possession, otherwise rogue key attack is possible.
+#[derive(Debug)]
+pub struct ProofOfPossession {
+    point: PointG1,
+    bytes: Vec<u8>
+}
+
+impl ProofOfPossession {
+    /// Creates and returns proof of possession of sign key that corresponds to verification key.
+    ///
+    /// # Arguments
+    ///
+    /// * `ver_key` - Verification key
+    /// * `sign_key` - Sign key of `ver_key`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    ///
+    /// ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    /// ```
+    pub fn new(ver_key: &VerKey, sign_key: &SignKey) -> Result<ProofOfPossession, IndyCryptoError> {
+        let point = Bls::_hash(&_pop_message(ver_key)?)?.mul(&sign_key.group_order_element)?;
+
+        Ok(ProofOfPossession {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Returns BLS proof of possession bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns BLS proof of possession from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProofOfPossession, IndyCryptoError> {
+        let point = PointG1::from_bytes(bytes)?;
+        Ok(
+            ProofOfPossession {
+                point,
+                bytes: bytes.to_vec()
+            }
+        )
+    }
 }
 
 pub struct Bls {}
@@ -379,18 +716,137 @@ impl Bls {
     /// assert!(valid)
     /// ```
     pub fn verify_multi_sig(multi_sig: &MultiSignature, message: &[u8], ver_keys: &[&VerKey], gen: &Generator) -> Result<bool, IndyCryptoError> {
-        let mut multi_sig_e_list: Vec<Pair> = Vec::new();
-        for ver_key in ver_keys {
-            let h = Bls::_hash(message)?;
-            multi_sig_e_list.push(Pair::pair(&h, &ver_key.point)?);
+        let ver_key = VerKey::aggregate(ver_keys)?;
+        let h = Bls::_hash(message)?;
+        Ok(Pair::pair(&multi_sig.point, &gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+
+    /// Verifies proof of possession of verification key and returns true - if proof valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop` - Proof of possession to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify_proof_of_possession(&pop, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_proof_of_possession(pop: &ProofOfPossession, ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let h = Bls::_hash(&_pop_message(ver_key)?)?;
+        Ok(Pair::pair(&pop.point, &gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+
+    /// Verifies batch of signatures of (possibly different) messages made by (possibly different) keys.
+    /// Returns validity of every signature in the order of `items`.
+    ///
+    /// Signatures are combined with random exponents r_i of `security_bits` bits and checked at once:
+    /// e(Σ r_i·σ_i, g) == Π e(H(m), Σ r_i·pk_i), where product is taken over distinct messages.
+    /// Batch containing invalid signature passes this check with probability at most 2^-security_bits.
+    /// If combined check fails, every signature is verified separately to find invalid ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - List of (message, signature, verification key) triples
+    /// * `gen` - Generator point
+    /// * `security_bits` - Size of random exponents in bits, from 1 to 128 (see `DEFAULT_BATCH_SECURITY_BITS`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message1 = vec![1, 2, 3, 4, 5];
+    /// let message2 = vec![6, 7, 8, 9, 10];
+    ///
+    /// let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+    ///
+    /// let items: Vec<(&[u8], &Signature, &VerKey)> = vec![
+    ///    (&message1, &signature1, &ver_key1),
+    ///    (&message2, &signature2, &ver_key2)
+    /// ];
+    ///
+    /// let valid = Bls::verify_batch(&items, &gen, DEFAULT_BATCH_SECURITY_BITS).unwrap();
+    /// assert_eq!(vec![true, true], valid);
+    /// ```
+    pub fn verify_batch(items: &[(&[u8], &Signature, &VerKey)], gen: &Generator, security_bits: usize) -> Result<Vec<bool>, IndyCryptoError> {
+        if security_bits == 0 || security_bits > MAX_BATCH_SECURITY_BITS {
+            return Err(IndyCryptoError::InvalidParam3(
+                format!("Invalid security bits: expected from 1 to {}, actual {}", MAX_BATCH_SECURITY_BITS, security_bits)));
         }
 
-        let mut multi_sig_e = multi_sig_e_list.get(0).ok_or(IndyCryptoError::InvalidStructure(format!("Element not found")))?.clone();
-        for e in multi_sig_e_list[1..].to_vec() {
-            multi_sig_e = multi_sig_e.mul(&e)?;
+        if items.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(Pair::pair(&multi_sig.point, &gen.point)?.eq(&multi_sig_e))
+        let mut signatures: Vec<PointG1> = Vec::with_capacity(items.len());
+        let mut exponents: Vec<GroupOrderElement> = Vec::with_capacity(items.len());
+        let mut messages: BTreeMap<&[u8], (Vec<PointG2>, Vec<GroupOrderElement>)> = BTreeMap::new();
+
+        for &(message, signature, ver_key) in items {
+            let r = _batch_exponent(security_bits)?;
+
+            signatures.push(signature.point);
+            exponents.push(r);
+
+            let entry = messages.entry(message).or_insert((Vec::new(), Vec::new()));
+            entry.0.push(ver_key.point);
+            entry.1.push(r);
+        }
+
+        let mut right = MillerAccumulator::new();
+        for (message, &(ref ver_keys, ref exponents)) in messages.iter() {
+            right.add_pair(&Bls::_hash(message)?, &PointG2::multi_scalar_mul(ver_keys, exponents)?)?;
+        }
+
+        let left = Pair::pair(&PointG1::multi_scalar_mul(&signatures, &exponents)?, &gen.point)?;
+
+        if left.eq(&right.finalize()?) {
+            return Ok(vec![true; items.len()]);
+        }
+
+        items
+            .iter()
+            .map(|&(message, signature, ver_key)| Bls::verify(signature, message, ver_key, gen))
+            .collect()
+    }
+
+    /// Converts bytes representation of BLS verification key between formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Verification key bytes in `from` format.
+    /// * `from` - Format of `bytes`.
+    /// * `to` - Format of result.
+    pub fn convert_ver_key_bytes(bytes: &[u8], from: BlsFormat, to: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        _g2_to_bytes(&_g2_from_bytes(bytes, from)?, to)
+    }
+
+    /// Converts bytes representation of BLS signature or multi signature between formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Signature bytes in `from` format.
+    /// * `from` - Format of `bytes`.
+    /// * `to` - Format of result.
+    pub fn convert_signature_bytes(bytes: &[u8], from: BlsFormat, to: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+        _g1_to_bytes(&_g1_from_bytes(bytes, from)?, to)
     }
 
     fn _hash(message: &[u8]) -> Result<PointG1, IndyCryptoError> {
@@ -401,6 +857,240 @@ impl Bls {
     }
 }
 
+/// Registry of verification keys of known signers (for example pool nodes).
+///
+/// Keys are accepted only with valid proof of possession, so multi signatures
+/// can be verified against aggregated key of signers. Aggregated keys are cached
+/// per set of signers and cache is invalidated on every registry change.
+#[derive(Debug)]
+pub struct KeyRegistry {
+    gen: Generator,
+    ver_keys: BTreeMap<String, VerKey>,
+    aggregated: RefCell<HashMap<BTreeSet<String>, VerKey>>
+}
+
+impl KeyRegistry {
+    /// Creates and returns empty key registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen` - Generator point of registered verification keys
+    pub fn new(gen: Generator) -> KeyRegistry {
+        KeyRegistry {
+            gen,
+            ver_keys: BTreeMap::new(),
+            aggregated: RefCell::new(HashMap::new())
+        }
+    }
+
+    /// Adds (or replaces) verification key of signer after verification of its proof of possession.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Signer identifier
+    /// * `ver_key` - Verification key of signer
+    /// * `pop` - Proof of possession of `ver_key`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    ///
+    /// let mut registry = KeyRegistry::new(gen);
+    /// registry.insert("Node1", ver_key, &pop).unwrap();
+    /// assert!(registry.contains("Node1"));
+    /// ```
+    pub fn insert(&mut self, id: &str, ver_key: VerKey, pop: &ProofOfPossession) -> Result<(), IndyCryptoError> {
+        if !Bls::verify_proof_of_possession(pop, &ver_key, &self.gen)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid proof of possession of ver key: {}", id)));
+        }
+
+        self.ver_keys.insert(id.to_owned(), ver_key);
+        self.aggregated.borrow_mut().clear();
+        Ok(())
+    }
+
+    /// Removes verification key of signer and returns it.
+    pub fn remove(&mut self, id: &str) -> Option<VerKey> {
+        let ver_key = self.ver_keys.remove(id);
+        if ver_key.is_some() {
+            self.aggregated.borrow_mut().clear();
+        }
+        ver_key
+    }
+
+    /// Returns verification key of signer.
+    pub fn get(&self, id: &str) -> Option<&VerKey> {
+        self.ver_keys.get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ver_keys.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ver_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ver_keys.is_empty()
+    }
+
+    /// Returns generator point of registered verification keys.
+    pub fn generator(&self) -> &Generator {
+        &self.gen
+    }
+
+    /// Returns aggregated verification key of signers.
+    ///
+    /// # Arguments
+    ///
+    /// * `signers` - Non empty list of distinct registered signer identifiers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let mut registry = KeyRegistry::new(Generator::from_bytes(gen.as_bytes()).unwrap());
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// registry.insert("Node1", ver_key1.clone(), &ProofOfPossession::new(&ver_key1, &sign_key1).unwrap()).unwrap();
+    ///
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    /// registry.insert("Node2", ver_key2.clone(), &ProofOfPossession::new(&ver_key2, &sign_key2).unwrap()).unwrap();
+    ///
+    /// let ver_key = registry.aggregated_ver_key(&["Node1", "Node2"]).unwrap();
+    /// assert_eq!(VerKey::aggregate(&[&ver_key1, &ver_key2]).unwrap().as_bytes(), ver_key.as_bytes());
+    /// ```
+    pub fn aggregated_ver_key(&self, signers: &[&str]) -> Result<VerKey, IndyCryptoError> {
+        let key = signers.iter().map(|id| id.to_string()).collect::<BTreeSet<String>>();
+
+        if key.len() != signers.len() {
+            return Err(IndyCryptoError::InvalidParam1(format!("Duplicated signers")));
+        }
+
+        if let Some(ver_key) = self.aggregated.borrow().get(&key) {
+            return Ok(ver_key.clone());
+        }
+
+        let ver_keys = signers
+            .iter()
+            .map(|id| self.ver_keys.get(*id)
+                .ok_or(IndyCryptoError::InvalidParam1(format!("Unknown signer: {}", id))))
+            .collect::<Result<Vec<&VerKey>, IndyCryptoError>>()?;
+
+        let ver_key = VerKey::aggregate(&ver_keys)?;
+        self.aggregated.borrow_mut().insert(key, ver_key.clone());
+        Ok(ver_key)
+    }
+
+    /// Verifies the message multi signature made by signers and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `signers` - Non empty list of distinct registered signer identifiers
+    pub fn verify_multi_sig(&self, multi_sig: &MultiSignature, message: &[u8], signers: &[&str]) -> Result<bool, IndyCryptoError> {
+        let ver_key = self.aggregated_ver_key(signers)?;
+        let h = Bls::_hash(message)?;
+        Ok(Pair::pair(&multi_sig.point, &self.gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+
+    /// Returns number of cached aggregated verification keys.
+    pub fn cached_count(&self) -> usize {
+        self.aggregated.borrow().len()
+    }
+}
+
+// Proof of possession signs domain separated bytes of verification key
+// to be distinguishable from signature of the same bytes as message.
+fn _pop_message(ver_key: &VerKey) -> Result<Vec<u8>, IndyCryptoError> {
+    let mut message = PROOF_OF_POSSESSION_DST.to_vec();
+    message.extend_from_slice(&ver_key.point.to_bytes()?);
+    Ok(message)
+}
+
+// Random non-zero exponent of `bits` bits.
+fn _batch_exponent(bits: usize) -> Result<GroupOrderElement, IndyCryptoError> {
+    loop {
+        let mut bytes = random_bytes((bits + 7) / 8)?;
+        if bits % 8 != 0 {
+            bytes[0] &= (1u8 << (bits % 8)) - 1;
+        }
+
+        if bytes.iter().any(|b| *b != 0) {
+            return GroupOrderElement::from_bytes(&bytes);
+        }
+    }
+}
+
+// Coordinates are serialized by amcl in big-endian order:
+// uncompressed G1 is 0x04 tag, x and y followed by zero padding; uncompressed G2 is x.a, x.b, y.a and y.b;
+// compressed points are flag byte followed by x (x.a and x.b for G2).
+// Little-endian formats reverse bytes of every coordinate and keep tags and padding in place.
+fn _g1_to_bytes(point: &PointG1, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+    let mut bytes = if format.compressed { point.to_bytes_compressed()? } else { point.to_bytes()? };
+    _apply_byte_order(&mut bytes, format, 1, if format.compressed { 1 } else { 2 });
+    Ok(bytes)
+}
+
+fn _g1_from_bytes(bytes: &[u8], format: BlsFormat) -> Result<PointG1, IndyCryptoError> {
+    let mut bytes = bytes.to_vec();
+    _apply_byte_order(&mut bytes, format, 1, if format.compressed { 1 } else { 2 });
+    if format.compressed { PointG1::from_bytes_compressed(&bytes) } else { PointG1::from_bytes(&bytes) }
+}
+
+fn _g2_to_bytes(point: &PointG2, format: BlsFormat) -> Result<Vec<u8>, IndyCryptoError> {
+    let mut bytes = if format.compressed { point.to_bytes_compressed()? } else { point.to_bytes()? };
+    _apply_byte_order(&mut bytes, format, if format.compressed { 1 } else { 0 }, if format.compressed { 2 } else { 4 });
+    Ok(bytes)
+}
+
+fn _g2_from_bytes(bytes: &[u8], format: BlsFormat) -> Result<PointG2, IndyCryptoError> {
+    let mut bytes = bytes.to_vec();
+    _apply_byte_order(&mut bytes, format, if format.compressed { 1 } else { 0 }, if format.compressed { 2 } else { 4 });
+    if format.compressed { PointG2::from_bytes_compressed(&bytes) } else { PointG2::from_bytes(&bytes) }
+}
+
+// Reverses bytes of `count` coordinates starting at `offset` for little-endian format.
+// Conversion is its own inverse, so it is used for both serialization and deserialization.
+fn _apply_byte_order(bytes: &mut [u8], format: BlsFormat, offset: usize, count: usize) {
+    if format.byte_order == ByteOrder::BigEndian {
+        return;
+    }
+
+    let coordinate_size = PointG1::BYTES_REPR_COMPRESSED_SIZE - 1;
+
+    for i in 0..count {
+        let start = offset + i * coordinate_size;
+        if start + coordinate_size <= bytes.len() {
+            bytes[start..start + coordinate_size].reverse();
+        }
+    }
+}
+
+fn _tagged(format: BlsFormat, bytes: Vec<u8>) -> Vec<u8> {
+    let mut tagged = Vec::with_capacity(bytes.len() + 1);
+    tagged.push(format.tag());
+    tagged.extend_from_slice(&bytes);
+    tagged
+}
+
+fn _untagged(bytes: &[u8]) -> Result<(BlsFormat, &[u8]), IndyCryptoError> {
+    let tag = bytes.get(0)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Tagged BLS bytes are empty")))?;
+
+    Ok((BlsFormat::from_tag(*tag)?, &bytes[1..]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -415,6 +1105,73 @@ mod tests {
         SignKey::new(None).unwrap();
     }
 
+    #[test]
+    fn generator_new_from_seed_works() {
+        let seed = b"0123456789abcdef0123456789abcdef";
+        let gen1 = Generator::new_from_seed(seed).unwrap();
+        let gen2 = Generator::new_from_seed(seed).unwrap();
+        assert_eq!(gen1.as_bytes(), gen2.as_bytes());
+    }
+
+    #[test]
+    fn generator_new_from_label_works() {
+        let gen1 = Generator::new_from_label(b"label").unwrap();
+        let gen2 = Generator::new_from_label(b"label").unwrap();
+        assert_eq!(gen1.as_bytes(), gen2.as_bytes());
+        assert!(gen1.is_valid().unwrap());
+
+        let gen3 = Generator::new_from_label(b"other label").unwrap();
+        assert_ne!(gen1.as_bytes(), gen3.as_bytes());
+    }
+
+    #[test]
+    fn generator_new_from_label_works_for_sign_and_verify() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new_from_label(b"label").unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn generator_is_valid_works() {
+        let gen = Generator::new().unwrap();
+        assert!(Generator::from_bytes(gen.as_bytes()).unwrap().is_valid().unwrap());
+    }
+
+    #[test]
+    fn generator_is_valid_works_for_infinity() {
+        let gen = Generator::from_bytes(&PointG2::new_inf().unwrap().to_bytes().unwrap()).unwrap();
+        assert!(!gen.is_valid().unwrap());
+    }
+
+    #[test]
+    fn generator_from_bytes_works_for_invalid_point() {
+        let gen = Generator::new().unwrap();
+        let mut bytes = gen.as_bytes().to_vec();
+        bytes[10] ^= 0xFF;
+        assert!(Generator::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn sign_key_new_from_seed_works() {
+        let seed = b"correct horse battery staple";
+        let sign_key1 = SignKey::new_from_seed(seed).unwrap();
+        let sign_key2 = SignKey::new_from_seed(seed).unwrap();
+        assert_eq!(sign_key1.as_bytes(), sign_key2.as_bytes());
+
+        let sign_key3 = SignKey::new_from_seed(b"correct horse battery staples").unwrap();
+        assert_ne!(sign_key1.as_bytes(), sign_key3.as_bytes());
+    }
+
+    #[test]
+    fn sign_key_new_from_seed_works_for_short_seed() {
+        assert!(SignKey::new_from_seed(b"short").is_err());
+    }
+
     #[test]
     fn sign_key_new_works_for_seed() {
         let seed = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 2, 3, 4, 5, 6, 7, 8, 9, 10, 21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
@@ -454,6 +1211,82 @@ mod tests {
         MultiSignature::new(&signatures).unwrap();
     }
 
+    #[test]
+    fn bls_format_tag_works() {
+        for &byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian].iter() {
+            for &compressed in [false, true].iter() {
+                let format = BlsFormat::new(byte_order, compressed);
+                assert_eq!(format, BlsFormat::from_tag(format.tag()).unwrap());
+            }
+        }
+
+        assert_eq!(0x10, BlsFormat::default().tag());
+        assert!(BlsFormat::from_tag(0x04).is_err());
+        assert!(BlsFormat::from_tag(0x14).is_err());
+    }
+
+    #[test]
+    fn ver_key_with_format_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        assert_eq!(ver_key.as_bytes(), ver_key.to_bytes_with_format(BlsFormat::default()).unwrap().as_slice());
+
+        for &byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian].iter() {
+            for &compressed in [false, true].iter() {
+                let format = BlsFormat::new(byte_order, compressed);
+
+                let bytes = ver_key.to_bytes_with_format(format).unwrap();
+                assert_eq!(ver_key.as_bytes(), VerKey::from_bytes_with_format(&bytes, format).unwrap().as_bytes());
+
+                let tagged_bytes = ver_key.to_tagged_bytes(format).unwrap();
+                assert_eq!(format.tag(), tagged_bytes[0]);
+                assert_eq!(ver_key.as_bytes(), VerKey::from_tagged_bytes(&tagged_bytes).unwrap().as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn signature_with_format_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        let little_endian = BlsFormat::new(ByteOrder::LittleEndian, false);
+        let compressed = BlsFormat::new(ByteOrder::BigEndian, true);
+
+        let bytes = signature.to_bytes_with_format(little_endian).unwrap();
+        assert_ne!(signature.as_bytes(), bytes.as_slice());
+
+        let converted = Bls::convert_signature_bytes(&bytes, little_endian, compressed).unwrap();
+        assert_eq!(PointG1::BYTES_REPR_COMPRESSED_SIZE, converted.len());
+
+        let signature2 = Signature::from_bytes_with_format(&converted, compressed).unwrap();
+        assert!(Bls::verify(&signature2, &message, &ver_key, &gen).unwrap());
+
+        let multi_sig = MultiSignature::new(&[&signature]).unwrap();
+        let multi_sig2 = MultiSignature::from_tagged_bytes(&multi_sig.to_tagged_bytes(little_endian).unwrap()).unwrap();
+        assert_eq!(multi_sig.as_bytes(), multi_sig2.as_bytes());
+    }
+
+    #[test]
+    fn convert_ver_key_bytes_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let little_endian_compressed = BlsFormat::new(ByteOrder::LittleEndian, true);
+
+        let bytes = Bls::convert_ver_key_bytes(ver_key.as_bytes(), BlsFormat::default(), little_endian_compressed).unwrap();
+        assert_eq!(bytes, ver_key.to_bytes_with_format(little_endian_compressed).unwrap());
+
+        let bytes = Bls::convert_ver_key_bytes(&bytes, little_endian_compressed, BlsFormat::default()).unwrap();
+        assert_eq!(ver_key.as_bytes(), bytes.as_slice());
+    }
+
     #[test]
     fn verify_works() {
         let message = vec![1, 2, 3, 4, 5];
@@ -583,4 +1416,187 @@ mod tests {
 
         assert!(!valid)
     }
+
+    #[test]
+    fn verify_proof_of_possession_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+
+        assert!(Bls::verify_proof_of_possession(&pop, &ver_key, &gen).unwrap());
+
+        let pop = ProofOfPossession::from_bytes(pop.as_bytes()).unwrap();
+        assert!(Bls::verify_proof_of_possession(&pop, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_proof_of_possession_works_for_other_ver_key() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+
+        let ver_key_other = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+        assert!(!Bls::verify_proof_of_possession(&pop, &ver_key_other, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_proof_of_possession_works_for_signature_of_ver_key() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign(ver_key.as_bytes(), &sign_key).unwrap();
+        let pop = ProofOfPossession::from_bytes(signature.as_bytes()).unwrap();
+
+        assert!(!Bls::verify_proof_of_possession(&pop, &ver_key, &gen).unwrap());
+    }
+
+    fn _registry_with_signers(gen: &Generator, ids: &[&str]) -> (KeyRegistry, Vec<SignKey>) {
+        let mut registry = KeyRegistry::new(Generator::from_bytes(gen.as_bytes()).unwrap());
+        let mut sign_keys = Vec::new();
+
+        for id in ids {
+            let sign_key = SignKey::new(None).unwrap();
+            let ver_key = VerKey::new(gen, &sign_key).unwrap();
+            let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+            registry.insert(id, ver_key, &pop).unwrap();
+            sign_keys.push(sign_key);
+        }
+
+        (registry, sign_keys)
+    }
+
+    #[test]
+    fn key_registry_insert_works_for_invalid_proof_of_possession() {
+        let gen = Generator::new().unwrap();
+        let (mut registry, _) = _registry_with_signers(&gen, &["Node1"]);
+
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new(&ver_key, &SignKey::new(None).unwrap()).unwrap();
+
+        assert!(registry.insert("Node2", ver_key, &pop).is_err());
+        assert!(!registry.contains("Node2"));
+        assert_eq!(1, registry.len());
+    }
+
+    #[test]
+    fn key_registry_verify_multi_sig_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let (registry, sign_keys) = _registry_with_signers(&gen, &["Node1", "Node2", "Node3"]);
+
+        let signature1 = Bls::sign(&message, &sign_keys[0]).unwrap();
+        let signature3 = Bls::sign(&message, &sign_keys[2]).unwrap();
+        let multi_sig = MultiSignature::new(&[&signature1, &signature3]).unwrap();
+
+        assert!(registry.verify_multi_sig(&multi_sig, &message, &["Node3", "Node1"]).unwrap());
+        assert!(!registry.verify_multi_sig(&multi_sig, &message, &["Node1", "Node2"]).unwrap());
+        assert!(registry.verify_multi_sig(&multi_sig, &message, &["Node1", "Node4"]).is_err());
+        assert!(registry.verify_multi_sig(&multi_sig, &message, &["Node1", "Node1"]).is_err());
+        assert!(registry.verify_multi_sig(&multi_sig, &message, &[]).is_err());
+    }
+
+    #[test]
+    fn key_registry_aggregated_ver_key_works_for_cache() {
+        let gen = Generator::new().unwrap();
+        let (mut registry, _) = _registry_with_signers(&gen, &["Node1", "Node2", "Node3"]);
+
+        let ver_key = registry.aggregated_ver_key(&["Node1", "Node2"]).unwrap();
+        assert_eq!(1, registry.cached_count());
+
+        let ver_key_cached = registry.aggregated_ver_key(&["Node2", "Node1"]).unwrap();
+        assert_eq!(ver_key.as_bytes(), ver_key_cached.as_bytes());
+        assert_eq!(1, registry.cached_count());
+
+        registry.aggregated_ver_key(&["Node1", "Node2", "Node3"]).unwrap();
+        assert_eq!(2, registry.cached_count());
+
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key_new = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new(&ver_key_new, &sign_key).unwrap();
+        registry.insert("Node2", ver_key_new, &pop).unwrap();
+        assert_eq!(0, registry.cached_count());
+
+        let ver_key_updated = registry.aggregated_ver_key(&["Node1", "Node2"]).unwrap();
+        assert_ne!(ver_key.as_bytes(), ver_key_updated.as_bytes());
+
+        registry.remove("Node3").unwrap();
+        assert_eq!(0, registry.cached_count());
+        assert!(registry.aggregated_ver_key(&["Node1", "Node3"]).is_err());
+    }
+
+    #[test]
+    fn verify_batch_works() {
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message1, &sign_key2).unwrap();
+        let signature3 = Bls::sign(&message2, &sign_key1).unwrap();
+
+        let items: Vec<(&[u8], &Signature, &VerKey)> = vec![
+            (&message1, &signature1, &ver_key1),
+            (&message1, &signature2, &ver_key2),
+            (&message2, &signature3, &ver_key1)
+        ];
+
+        let valid = Bls::verify_batch(&items, &gen, DEFAULT_BATCH_SECURITY_BITS).unwrap();
+        assert_eq!(vec![true, true, true], valid);
+    }
+
+    #[test]
+    fn verify_batch_works_for_invalid_signature() {
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key1).unwrap();
+
+        let items: Vec<(&[u8], &Signature, &VerKey)> = vec![
+            (&message1, &signature1, &ver_key1),
+            (&message2, &signature2, &ver_key2)
+        ];
+
+        let valid = Bls::verify_batch(&items, &gen, 64).unwrap();
+        assert_eq!(vec![true, false], valid);
+    }
+
+    #[test]
+    fn verify_batch_works_for_empty_batch() {
+        let gen = Generator::new().unwrap();
+        assert!(Bls::verify_batch(&[], &gen, DEFAULT_BATCH_SECURITY_BITS).unwrap().is_empty());
+    }
+
+    #[test]
+    fn verify_batch_works_for_invalid_security_bits() {
+        let gen = Generator::new().unwrap();
+        assert!(Bls::verify_batch(&[], &gen, 0).is_err());
+        assert!(Bls::verify_batch(&[], &gen, 129).is_err());
+    }
+
+    #[test]
+    fn batch_exponent_works() {
+        for bits in [1, 7, 8, 63, 128].iter() {
+            let r = _batch_exponent(*bits).unwrap().to_bytes().unwrap();
+            let leading_zero_bits = 8 * r.iter().take_while(|b| **b == 0).count()
+                + r.iter().find(|b| **b != 0).unwrap().leading_zeros() as usize;
+            assert!(r.len() * 8 - leading_zero_bits <= *bits);
+        }
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use errors::IndyCryptoError;
+use utils::rng::random_bytes;
 
 use int_traits::IntTraits;
 
-use openssl::bn::{BigNum, BigNumRef, BigNumContext, MSB_MAYBE_ZERO};
+use openssl::bn::{BigNum, BigNumRef, BigNumContext, BigNumContextRef};
 use openssl::hash::{hash2, MessageDigest, Hasher};
 use openssl::error::ErrorStack;
 
@@ -12,13 +13,154 @@ use serde::ser::{Serialize, Serializer, Error as SError};
 #[cfg(feature = "serialization")]
 use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
 
+use std::cell::{Cell, RefCell};
 use std::error::Error;
 use std::fmt;
 use std::cmp::Ord;
 use std::cmp::Ordering;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc;
+use std::thread;
+
+const SIEVE_LIMIT: usize = 2048;
+const SIEVE_WINDOW: u32 = 1 << 16;
+
+static PRIME_SEARCH_THREADS: AtomicUsize = AtomicUsize::new(1);
+
+const MAX_POOLED_CONTEXTS: usize = 16;
+
+const PARSE_ERROR_INPUT_PREFIX_LEN: usize = 16;
+
+thread_local! {
+    static CONTEXT_POOL: RefCell<Vec<BigNumContext>> = RefCell::new(Vec::new());
+    static CONTEXTS_ALLOCATED: Cell<usize> = Cell::new(0);
+}
+
+/// Callback receiving count of candidates tested by primality test so far.
+pub type PrimeSearchProgress = Fn(usize) + Send + Sync;
+
+/// Returns count of threads used by `BigNumber::generate_safe_prime`.
+pub fn prime_search_threads() -> usize {
+    PRIME_SEARCH_THREADS.load(AtomicOrdering::SeqCst)
+}
+
+/// Sets count of threads used by `BigNumber::generate_safe_prime` (1 by default).
+///
+/// Worker threads take randomness from process wide RNG, so with more than one thread
+/// generation ignores RNG set by `utils::rng::with_rng` and isn't reproducible for seeded RNG.
+pub fn set_prime_search_threads(threads: usize) -> Result<(), IndyCryptoError> {
+    if threads == 0 {
+        return Err(IndyCryptoError::InvalidStructure("Prime search threads count must be positive".to_string()));
+    }
+
+    PRIME_SEARCH_THREADS.store(threads, AtomicOrdering::SeqCst);
+    Ok(())
+}
+
+struct PrimeSearchState {
+    stop: AtomicBool,
+    tested: AtomicUsize,
+    progress: Option<Box<PrimeSearchProgress>>
+}
+
+impl PrimeSearchState {
+    fn new(progress: Option<Box<PrimeSearchProgress>>) -> PrimeSearchState {
+        PrimeSearchState {
+            stop: AtomicBool::new(false),
+            tested: AtomicUsize::new(0),
+            progress
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop.load(AtomicOrdering::SeqCst)
+    }
+
+    fn candidate_tested(&self) {
+        let tested = self.tested.fetch_add(1, AtomicOrdering::SeqCst) + 1;
+        if let Some(ref progress) = self.progress {
+            progress(tested);
+        }
+    }
+}
 
 pub struct BigNumberContext {
-    openssl_bn_context: BigNumContext
+    openssl_bn_context: PooledContext
+}
+
+/// Thread-local pool of OpenSSL contexts (`BN_CTX`) holding temporaries of big number operations.
+/// `BigNumber::new_context` takes contexts from the pool and dropped contexts are returned back,
+/// so helpers creating context per call don't allocate temporaries again and again.
+pub struct BnCtxPool {}
+
+impl BnCtxPool {
+    /// Takes context from the pool of current thread or allocates new one if the pool is empty.
+    pub fn get() -> Result<BigNumberContext, IndyCryptoError> {
+        let ctx = match CONTEXT_POOL.with(|pool| pool.borrow_mut().pop()) {
+            Some(ctx) => ctx,
+            None => {
+                CONTEXTS_ALLOCATED.with(|allocated| allocated.set(allocated.get() + 1));
+                BigNumContext::new()?
+            }
+        };
+
+        Ok(BigNumberContext {
+            openssl_bn_context: PooledContext(Some(ctx))
+        })
+    }
+
+    /// Returns count of contexts kept in the pool of current thread.
+    pub fn len() -> usize {
+        CONTEXT_POOL.with(|pool| pool.borrow().len())
+    }
+
+    /// Returns count of contexts allocated by current thread.
+    /// Difference of two calls shows allocations made by operations between them.
+    pub fn allocated() -> usize {
+        CONTEXTS_ALLOCATED.with(|allocated| allocated.get())
+    }
+
+    /// Frees contexts kept in the pool of current thread.
+    pub fn clear() {
+        CONTEXT_POOL.with(|pool| pool.borrow_mut().clear());
+    }
+
+    fn release(ctx: BigNumContext) {
+        // Pool can be already destroyed if context is dropped during thread exit
+        let _ = CONTEXT_POOL.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < MAX_POOLED_CONTEXTS {
+                pool.push(ctx);
+            }
+        });
+    }
+}
+
+// Context is taken out only on drop, so it is always present while in use
+struct PooledContext(Option<BigNumContext>);
+
+impl Deref for PooledContext {
+    type Target = BigNumContextRef;
+
+    fn deref(&self) -> &BigNumContextRef {
+        self.0.as_ref().expect("Pooled context is released")
+    }
+}
+
+impl DerefMut for PooledContext {
+    fn deref_mut(&mut self) -> &mut BigNumContextRef {
+        self.0.as_mut().expect("Pooled context is released")
+    }
+}
+
+impl Drop for PooledContext {
+    fn drop(&mut self) {
+        if let Some(ctx) = self.0.take() {
+            BnCtxPool::release(ctx);
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -27,11 +169,9 @@ pub struct BigNumber {
 }
 
 impl BigNumber {
+    /// Returns context from thread-local `BnCtxPool`.
     pub fn new_context() -> Result<BigNumberContext, IndyCryptoError> {
-        let ctx = BigNumContext::new()?;
-        Ok(BigNumberContext {
-            openssl_bn_context: ctx
-        })
+        BnCtxPool::get()
     }
 
     pub fn new() -> Result<BigNumber, IndyCryptoError> {
@@ -42,15 +182,163 @@ impl BigNumber {
     }
 
     pub fn generate_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNumber::new()?;
-        BigNumRef::generate_prime(&mut bn.openssl_bn, size as i32, false, None, None)?;
-        Ok(bn)
+        BigNumber::_generate_prime(size, false)
     }
 
     pub fn generate_safe_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNumber::new()?;
-        BigNumRef::generate_prime(&mut bn.openssl_bn, (size + 1) as i32, true, None, None)?;
-        Ok(bn)
+        BigNumber::generate_safe_prime_parallel(size, prime_search_threads(), None)
+    }
+
+    /// Generates safe prime with `threads` workers testing candidates concurrently.
+    ///
+    /// # Arguments
+    /// * `size` - Size of `(p - 1) / 2` in bits.
+    /// * `threads` - Count of worker threads, search runs in the current thread if it is 1.
+    /// * `progress` - Optional callback called after every primality test.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bn::BigNumber;
+    ///
+    /// let progress = Box::new(|tested: usize| println!("Candidates tested: {}", tested));
+    /// let prime = BigNumber::generate_safe_prime_parallel(128, 2, Some(progress)).unwrap();
+    /// assert_eq!(129, prime.num_bits().unwrap());
+    /// ```
+    pub fn generate_safe_prime_parallel(size: usize,
+                                        threads: usize,
+                                        progress: Option<Box<PrimeSearchProgress>>) -> Result<BigNumber, IndyCryptoError> {
+        trace!("BigNumber::generate_safe_prime_parallel: >>> size: {:?}, threads: {:?}", size, threads);
+
+        if threads == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Prime search threads count must be positive".to_string()));
+        }
+
+        let state = Arc::new(PrimeSearchState::new(progress));
+
+        let prime = if threads == 1 {
+            BigNumber::_search_prime(size + 1, true, &state)?
+                .ok_or(IndyCryptoError::InvalidState("Prime search is stopped".to_string()))?
+        } else {
+            let (sender, receiver) = mpsc::channel();
+
+            let workers = (0..threads)
+                .map(|_| {
+                    let state = state.clone();
+                    let sender = sender.clone();
+                    thread::spawn(move || {
+                        match BigNumber::_search_prime(size + 1, true, &state) {
+                            Ok(Some(prime)) => { sender.send(Ok(prime)).ok(); }
+                            Ok(None) => {}
+                            Err(err) => { sender.send(Err(err)).ok(); }
+                        }
+                    })
+                })
+                .collect::<Vec<thread::JoinHandle<()>>>();
+
+            drop(sender);
+
+            let res = receiver.recv()
+                .map_err(|_| IndyCryptoError::InvalidState("Prime search workers are failed".to_string()));
+
+            state.stop.store(true, AtomicOrdering::SeqCst);
+            for worker in workers {
+                worker.join()
+                    .map_err(|_| IndyCryptoError::InvalidState("Prime search worker panicked".to_string()))?;
+            }
+
+            res??
+        };
+
+        trace!("BigNumber::generate_safe_prime_parallel: <<< tested: {:?}", state.tested.load(AtomicOrdering::SeqCst));
+
+        Ok(prime)
+    }
+
+    fn _generate_prime(size: usize, safe: bool) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::_search_prime(size, safe, &PrimeSearchState::new(None))?
+            .ok_or(IndyCryptoError::InvalidState("Prime search is stopped".to_string()))
+    }
+
+    /// Searches prime (or safe prime) of `size` bits starting from candidate taken from `utils::rng`.
+    /// Candidates with small factors are skipped by sieve, so primality test runs only for a few of them.
+    /// Returns `None` if search is stopped by another worker.
+    fn _search_prime(size: usize, safe: bool, state: &PrimeSearchState) -> Result<Option<BigNumber>, IndyCryptoError> {
+        // For safe prime p = 2q + 1 search is done over q
+        let q_size = if safe { size - 1 } else { size };
+        let small_primes = BigNumber::_small_primes(SIEVE_LIMIT);
+        let mut ctx = BigNumber::new_context()?;
+
+        loop {
+            let mut start = BigNumber::rand(q_size)?;
+            start.set_bit((q_size - 1) as i32)?;
+            start.set_bit(0)?;
+
+            let mut residues = Vec::with_capacity(small_primes.len());
+            for &small_prime in small_primes.iter() {
+                let residue = start.modulus(&BigNumber::from_u32(small_prime as usize)?, Some(&mut ctx))?;
+                residues.push(residue.to_dec()?.parse::<u32>()
+                    .map_err(|err| IndyCryptoError::InvalidState(format!("Can't parse residue: {:?}", err)))?);
+            }
+
+            let mut delta = 0;
+            while delta < SIEVE_WINDOW {
+                let has_small_factor = small_primes.iter().zip(residues.iter()).any(|(&small_prime, &residue)| {
+                    let q_residue = (residue + delta) % small_prime;
+                    q_residue == 0 || (safe && (2 * q_residue + 1) % small_prime == 0)
+                });
+
+                if !has_small_factor {
+                    let mut q = start.clone()?;
+                    q.add_word(delta)?;
+
+                    if q.num_bits()? as usize != q_size {
+                        break;
+                    }
+
+                    if state.is_stopped() {
+                        return Ok(None);
+                    }
+
+                    let is_prime = q.is_prime(Some(&mut ctx))?;
+                    state.candidate_tested();
+
+                    if is_prime {
+                        if !safe {
+                            return Ok(Some(q));
+                        }
+
+                        let mut p = q.add(&q)?;
+                        p.add_word(1)?;
+
+                        if p.is_prime(Some(&mut ctx))? {
+                            return Ok(Some(p));
+                        }
+                    }
+                }
+
+                delta += 2;
+            }
+        }
+    }
+
+    fn _small_primes(limit: usize) -> Vec<u32> {
+        let mut is_composite = vec![false; limit];
+        let mut primes = Vec::new();
+
+        for i in 3..limit {
+            if is_composite[i] || i % 2 == 0 {
+                continue;
+            }
+            primes.push(i as u32);
+
+            let mut j = i * i;
+            while j < limit {
+                is_composite[j] = true;
+                j += i;
+            }
+        }
+
+        primes
     }
 
     pub fn generate_prime_in_range(start: &BigNumber, end: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
@@ -86,15 +374,29 @@ impl BigNumber {
     }
 
     pub fn rand(size: usize) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNumber::new()?;
-        BigNumRef::rand(&mut bn.openssl_bn, size as i32, MSB_MAYBE_ZERO, false)?;
-        Ok(bn)
+        let bytes_len = (size + 7) / 8;
+        let mut bytes = random_bytes(bytes_len)?;
+
+        if bytes_len > 0 {
+            bytes[0] &= 0xFF >> (bytes_len * 8 - size);
+        }
+
+        BigNumber::from_bytes(&bytes)
     }
 
     pub fn rand_range(&self) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNumber::new()?;
-        BigNumRef::rand_range(&self.openssl_bn, &mut bn.openssl_bn)?;
-        Ok(bn)
+        if self.openssl_bn.is_negative() || self.num_bits()? == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid range: {:?}", self)));
+        }
+
+        let size = self.num_bits()? as usize;
+
+        loop {
+            let bn = BigNumber::rand(size)?;
+            if bn < *self {
+                return Ok(bn);
+            }
+        }
     }
 
     pub fn num_bits(&self) -> Result<i32, IndyCryptoError> {
@@ -110,6 +412,15 @@ impl BigNumber {
         Ok(self)
     }
 
+    pub fn is_negative(&self) -> Result<bool, IndyCryptoError> {
+        Ok(self.openssl_bn.is_negative())
+    }
+
+    pub fn set_negative(&mut self, negative: bool) -> Result<&mut BigNumber, IndyCryptoError> {
+        BigNumRef::set_negative(&mut self.openssl_bn, negative);
+        Ok(self)
+    }
+
     pub fn from_u32(n: usize) -> Result<BigNumber, IndyCryptoError> {
         let bn = BigNum::from_u32(n as u32)?;
         Ok(BigNumber {
@@ -131,6 +442,48 @@ impl BigNumber {
         })
     }
 
+    /// Parses decimal number (optionally prefixed with `-`) rejecting any other characters.
+    /// Unlike `from_dec` that can ignore trailing garbage or fail with OpenSSL error,
+    /// it fails with `ParseError` pointing to the first invalid character.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bn::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_dec("123").unwrap(), BigNumber::from_dec_checked("123").unwrap());
+    /// assert!(BigNumber::from_dec_checked("12a3").is_err());
+    /// ```
+    pub fn from_dec_checked(dec: &str) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::_check_digits(dec, |c| c.is_ascii_digit())?;
+        BigNumber::from_dec(dec)
+    }
+
+    /// Parses hexadecimal number (optionally prefixed with `-`) rejecting any other characters.
+    /// Fails with `ParseError` pointing to the first invalid character.
+    pub fn from_hex_checked(hex: &str) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::_check_digits(hex, |c| c.is_ascii_hexdigit())?;
+        BigNumber::from_hex(hex)
+    }
+
+    fn _check_digits<F>(input: &str, is_digit: F) -> Result<(), IndyCryptoError> where F: Fn(&u8) -> bool {
+        let bytes = input.as_bytes();
+        let start = if bytes.first() == Some(&b'-') { 1 } else { 0 };
+
+        let position = if bytes.len() == start {
+            Some(start)
+        } else {
+            bytes[start..].iter().position(|c| !is_digit(c)).map(|position| start + position)
+        };
+
+        match position {
+            Some(position) => Err(IndyCryptoError::ParseError {
+                input_prefix: input.chars().take(PARSE_ERROR_INPUT_PREFIX_LEN).collect(),
+                position
+            }),
+            None => Ok(())
+        }
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Result<BigNumber, IndyCryptoError> {
         let bn = BigNum::from_slice(bytes)?;
         Ok(BigNumber {
@@ -308,6 +661,140 @@ impl BigNumber {
         Ok(res)
     }
 
+    /// Returns Jacobi symbol (self/n) for odd positive `n`: 1, -1 or 0 if self and n aren't coprime.
+    pub fn jacobi(&self, n: &BigNumber) -> Result<i32, IndyCryptoError> {
+        if n.openssl_bn.is_negative() || !n.is_bit_set(0)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Jacobi symbol requires odd positive modulus: {:?}", n)));
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+        let mut a = self.modulus(n, Some(&mut ctx))?;
+        let mut n = n.clone()?;
+        let mut result = 1;
+
+        while a.num_bits()? != 0 {
+            while !a.is_bit_set(0)? {
+                a.div_word(2)?;
+                // (2/n) = -1 for n = 3, 5 mod 8
+                if n.is_bit_set(1)? != n.is_bit_set(2)? {
+                    result = -result;
+                }
+            }
+
+            ::std::mem::swap(&mut a, &mut n);
+
+            // Quadratic reciprocity: sign changes if both are 3 mod 4
+            if a.is_bit_set(1)? && n.is_bit_set(1)? {
+                result = -result;
+            }
+
+            a = a.modulus(&n, Some(&mut ctx))?;
+        }
+
+        Ok(if n == BigNumber::from_u32(1)? { result } else { 0 })
+    }
+
+    /// Returns square root of self modulo odd prime `p` (Tonelli-Shanks), fails if self isn't quadratic residue.
+    /// The other root is `p - root`.
+    pub fn mod_sqrt(&self, p: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let a = self.modulus(p, Some(&mut ctx))?;
+
+        if a.num_bits()? == 0 {
+            return Ok(a);
+        }
+
+        if a.jacobi(p)? != 1 {
+            return Err(IndyCryptoError::InvalidStructure(format!("{:?} is not quadratic residue modulo {:?}", self, p)));
+        }
+
+        let one = BigNumber::from_u32(1)?;
+
+        // p = 3 mod 4: root is a^((p + 1) / 4)
+        if p.is_bit_set(1)? {
+            let mut e = p.add(&one)?;
+            e.div_word(4)?;
+            return a.mod_exp(&e, p, Some(&mut ctx));
+        }
+
+        // p - 1 = q * 2^s with odd q
+        let mut q = p.sub(&one)?;
+        let mut s = 0;
+        while !q.is_bit_set(0)? {
+            q.div_word(2)?;
+            s += 1;
+        }
+
+        // Any quadratic non-residue
+        let mut z = BigNumber::from_u32(2)?;
+        while z.jacobi(p)? != -1 {
+            z.add_word(1)?;
+        }
+
+        let mut q_plus_one_half = q.add(&one)?;
+        q_plus_one_half.div_word(2)?;
+
+        let mut m = s;
+        let mut c = z.mod_exp(&q, p, Some(&mut ctx))?;
+        let mut t = a.mod_exp(&q, p, Some(&mut ctx))?;
+        let mut r = a.mod_exp(&q_plus_one_half, p, Some(&mut ctx))?;
+
+        while t != one {
+            // Least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t_pow = t.clone()?;
+            while t_pow != one {
+                t_pow = t_pow.mod_mul(&t_pow, p, Some(&mut ctx))?;
+                i += 1;
+                if i == m {
+                    return Err(IndyCryptoError::InvalidStructure(format!("Modulus {:?} is not prime", p)));
+                }
+            }
+
+            let mut b = c.clone()?;
+            for _ in 0..(m - i - 1) {
+                b = b.mod_mul(&b, p, Some(&mut ctx))?;
+            }
+
+            m = i;
+            c = b.mod_mul(&b, p, Some(&mut ctx))?;
+            t = t.mod_mul(&c, p, Some(&mut ctx))?;
+            r = r.mod_mul(&b, p, Some(&mut ctx))?;
+        }
+
+        Ok(r)
+    }
+
+    /// Returns x modulo p * q such that x = a_p mod p and x = a_q mod q for coprime p and q (Garner's formula).
+    pub fn crt(a_p: &BigNumber, p: &BigNumber, a_q: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        let q_inv = q.inverse(p, Some(&mut ctx))?;
+        let a_q = a_q.modulus(q, Some(&mut ctx))?;
+
+        let h = a_p
+            .mod_sub(&a_q, p, Some(&mut ctx))?
+            .mod_mul(&q_inv, p, Some(&mut ctx))?;
+
+        h.mul(q, Some(&mut ctx))?.add(&a_q)
+    }
+
+    /// Returns self^e modulo p * q for distinct primes p and q, exponentiating modulo p and q separately.
+    /// Roughly 3-4 times faster than `mod_exp` modulo p * q.
+    pub fn mod_exp_crt(&self, e: &BigNumber, p: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let one = BigNumber::from_u32(1)?;
+
+        let a_p = self.mod_exp(&e.modulus(&p.sub(&one)?, Some(&mut ctx))?, p, Some(&mut ctx))?;
+        let a_q = self.mod_exp(&e.modulus(&q.sub(&one)?, Some(&mut ctx))?, q, Some(&mut ctx))?;
+
+        // Reduced exponent is valid only for base coprime to modulus
+        let a_p = if self.modulus(p, Some(&mut ctx))?.num_bits()? == 0 { BigNumber::new()? } else { a_p };
+        let a_q = if self.modulus(q, Some(&mut ctx))?.num_bits()? == 0 { BigNumber::new()? } else { a_q };
+
+        BigNumber::crt(&a_p, p, &a_q, q)
+    }
+
     pub fn clone(&self) -> Result<BigNumber, IndyCryptoError> {
         Ok(BigNumber {
             openssl_bn: BigNum::from_slice(&self.openssl_bn.to_vec()[..])?
@@ -367,7 +854,7 @@ impl<'a> Deserialize<'a> for BigNumber {
             fn visit_str<E>(self, value: &str) -> Result<BigNumber, E>
                 where E: DError
             {
-                Ok(BigNumber::from_dec(value).map_err(DError::custom)?)
+                Ok(BigNumber::from_dec_checked(value).map_err(DError::custom)?)
             }
         }
 
@@ -385,12 +872,41 @@ impl From<ErrorStack> for IndyCryptoError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::drbg::with_seeded_rng;
 
     use serde_json;
 
     const RANGE_LEFT: usize = 592;
     const RANGE_RIGHT: usize = 592;
 
+    #[test]
+    fn bn_ctx_pool_works() {
+        BnCtxPool::clear();
+
+        {
+            let mut ctx = BigNumber::new_context().unwrap();
+            let a = BigNumber::from_u32(7).unwrap();
+            let n = BigNumber::from_u32(11).unwrap();
+            assert_eq!(BigNumber::from_u32(2).unwrap(), a.mod_exp(&BigNumber::from_u32(3).unwrap(), &n, Some(&mut ctx)).unwrap());
+        }
+        assert_eq!(1, BnCtxPool::len());
+
+        let allocated = BnCtxPool::allocated();
+        for _ in 0..10 {
+            BigNumber::new_context().unwrap();
+        }
+        assert_eq!(allocated, BnCtxPool::allocated());
+        assert_eq!(1, BnCtxPool::len());
+
+        let contexts: Vec<BigNumberContext> = (0..MAX_POOLED_CONTEXTS + 1).map(|_| BigNumber::new_context().unwrap()).collect();
+        assert_eq!(allocated + MAX_POOLED_CONTEXTS, BnCtxPool::allocated());
+        drop(contexts);
+        assert_eq!(MAX_POOLED_CONTEXTS, BnCtxPool::len());
+
+        BnCtxPool::clear();
+        assert_eq!(0, BnCtxPool::len());
+    }
+
     #[test]
     #[ignore] //TODO check
     fn generate_prime_in_range_works() {
@@ -401,6 +917,126 @@ mod tests {
         assert!(end > random_prime);
     }
 
+    #[test]
+    fn jacobi_works() {
+        let jacobi = |a: u32, n: u32| BigNumber::from_u32(a as usize).unwrap().jacobi(&BigNumber::from_u32(n as usize).unwrap()).unwrap();
+
+        assert_eq!(-1, jacobi(1001, 9907));
+        assert_eq!(1, jacobi(19, 45));
+        assert_eq!(-1, jacobi(8, 21));
+        assert_eq!(1, jacobi(5, 21));
+        assert_eq!(0, jacobi(6, 21));
+        assert_eq!(1, jacobi(30, 7));
+    }
+
+    #[test]
+    fn jacobi_works_for_even_modulus() {
+        assert!(BigNumber::from_u32(3).unwrap().jacobi(&BigNumber::from_u32(8).unwrap()).is_err());
+    }
+
+    #[test]
+    fn mod_sqrt_works() {
+        // p = 3 mod 4
+        let root = BigNumber::from_u32(2).unwrap().mod_sqrt(&BigNumber::from_u32(7).unwrap()).unwrap();
+        assert!(root == BigNumber::from_u32(3).unwrap() || root == BigNumber::from_u32(4).unwrap());
+
+        // p = 1 mod 4
+        let root = BigNumber::from_u32(10).unwrap().mod_sqrt(&BigNumber::from_u32(13).unwrap()).unwrap();
+        assert!(root == BigNumber::from_u32(6).unwrap() || root == BigNumber::from_u32(7).unwrap());
+
+        // p = 1 mod 8 (several Tonelli-Shanks iterations)
+        let root = BigNumber::from_u32(1030).unwrap().mod_sqrt(&BigNumber::from_u32(10009).unwrap()).unwrap();
+        assert_eq!(BigNumber::from_u32(1030).unwrap(),
+                   root.mod_mul(&root, &BigNumber::from_u32(10009).unwrap(), None).unwrap());
+    }
+
+    #[test]
+    fn mod_sqrt_works_for_large_prime() {
+        let p = BigNumber::generate_prime(256).unwrap();
+        let x = BigNumber::rand(255).unwrap();
+        let a = x.mod_mul(&x, &p, None).unwrap();
+
+        let root = a.mod_sqrt(&p).unwrap();
+        assert!(root == x || root == p.sub(&x).unwrap());
+    }
+
+    #[test]
+    fn mod_sqrt_works_for_non_residue() {
+        assert!(BigNumber::from_u32(3).unwrap().mod_sqrt(&BigNumber::from_u32(7).unwrap()).is_err());
+    }
+
+    #[test]
+    fn crt_works() {
+        let x = BigNumber::crt(&BigNumber::from_u32(2).unwrap(), &BigNumber::from_u32(3).unwrap(),
+                               &BigNumber::from_u32(3).unwrap(), &BigNumber::from_u32(5).unwrap()).unwrap();
+        assert_eq!(BigNumber::from_u32(8).unwrap(), x);
+    }
+
+    #[test]
+    fn mod_exp_crt_works() {
+        let p = BigNumber::generate_prime(256).unwrap();
+        let q = BigNumber::generate_prime(256).unwrap();
+        let n = p.mul(&q, None).unwrap();
+
+        let base = BigNumber::rand(500).unwrap();
+        let e = BigNumber::rand(600).unwrap();
+
+        assert_eq!(base.mod_exp(&e, &n, None).unwrap(), base.mod_exp_crt(&e, &p, &q).unwrap());
+        assert_eq!(p.mod_exp(&e, &n, None).unwrap(), p.mod_exp_crt(&e, &p, &q).unwrap());
+    }
+
+    #[test]
+    fn rand_works_for_seeded_rng() {
+        let seed = b"0123456789abcdef0123456789abcdef";
+
+        let gen = || -> Result<(BigNumber, BigNumber), IndyCryptoError> {
+            Ok((BigNumber::rand(250)?, BigNumber::from_u32(1000)?.rand_range()?))
+        };
+        let (rand1, rand_range1) = with_seeded_rng(seed, &gen).unwrap();
+        let (rand2, rand_range2) = with_seeded_rng(seed, &gen).unwrap();
+
+        assert_eq!(rand1, rand2);
+        assert!(rand1.num_bits().unwrap() <= 250);
+        assert_eq!(rand_range1, rand_range2);
+        assert!(rand_range1 < BigNumber::from_u32(1000).unwrap());
+    }
+
+    #[test]
+    fn generate_safe_prime_parallel_works() {
+        let tested = Arc::new(AtomicUsize::new(0));
+        let progress_tested = tested.clone();
+        let progress = Box::new(move |count: usize| { progress_tested.store(count, AtomicOrdering::SeqCst); });
+
+        let prime = BigNumber::generate_safe_prime_parallel(256, 4, Some(progress)).unwrap();
+        assert_eq!(257, prime.num_bits().unwrap());
+        assert!(tested.load(AtomicOrdering::SeqCst) > 0);
+
+        let mut q = prime.sub(&BigNumber::from_u32(1).unwrap()).unwrap();
+        q.div_word(2).unwrap();
+        assert!(prime.is_prime(None).unwrap());
+        assert!(q.is_prime(None).unwrap());
+    }
+
+    #[test]
+    fn generate_safe_prime_parallel_works_for_zero_threads() {
+        assert!(BigNumber::generate_safe_prime_parallel(256, 0, None).is_err());
+    }
+
+    #[test]
+    fn generate_safe_prime_works_for_seeded_rng() {
+        let seed = b"0123456789abcdef0123456789abcdef";
+
+        let prime1 = with_seeded_rng(seed, || BigNumber::generate_safe_prime(256)).unwrap();
+        let prime2 = with_seeded_rng(seed, || BigNumber::generate_safe_prime(256)).unwrap();
+        assert_eq!(prime1, prime2);
+        assert_eq!(257, prime1.num_bits().unwrap());
+
+        let mut q = prime1.sub(&BigNumber::from_u32(1).unwrap()).unwrap();
+        q.div_word(2).unwrap();
+        assert!(prime1.is_prime(None).unwrap());
+        assert!(q.is_prime(None).unwrap());
+    }
+
     #[cfg(feature = "serialization")]
     #[derive(Serialize, Deserialize)]
     struct Test {
@@ -426,4 +1062,43 @@ mod tests {
         assert!(bn.is_ok());
         assert_eq!("1", bn.unwrap().field.to_dec().unwrap());
     }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn deserialize_works_for_invalid_number() {
+        let s = "{\"field\":\"12a\"}";
+        let err = serde_json::from_str::<Test>(&s).unwrap_err();
+
+        assert!(err.to_string().contains("Invalid number '12a' at position 2"));
+    }
+
+    #[test]
+    fn from_dec_checked_works() {
+        assert_eq!(BigNumber::from_dec("-123").unwrap(), BigNumber::from_dec_checked("-123").unwrap());
+
+        match BigNumber::from_dec_checked("1234 ").unwrap_err() {
+            IndyCryptoError::ParseError { input_prefix, position } => {
+                assert_eq!("1234 ", input_prefix);
+                assert_eq!(4, position);
+            }
+            err => panic!("Unexpected error: {:?}", err)
+        }
+
+        match BigNumber::from_dec_checked(&format!("{}x", "9".repeat(40))).unwrap_err() {
+            IndyCryptoError::ParseError { input_prefix, position } => {
+                assert_eq!("9".repeat(16), input_prefix);
+                assert_eq!(40, position);
+            }
+            err => panic!("Unexpected error: {:?}", err)
+        }
+
+        assert!(BigNumber::from_dec_checked("").is_err());
+        assert!(BigNumber::from_dec_checked("-").is_err());
+    }
+
+    #[test]
+    fn from_hex_checked_works() {
+        assert_eq!(BigNumber::from_hex("FFab").unwrap(), BigNumber::from_hex_checked("FFab").unwrap());
+        assert!(BigNumber::from_hex_checked("0xFF").is_err());
+    }
 }
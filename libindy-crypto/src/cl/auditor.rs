@@ -0,0 +1,157 @@
+use bn::BigNumber;
+use cl::*;
+use cl::constants::LARGE_PRIME;
+use cl::helpers::generate_safe_prime;
+use errors::IndyCryptoError;
+
+/// Party that is able to decrypt hidden attributes verifiably encrypted by Prover (conditional disclosure).
+pub struct Auditor {}
+
+impl Auditor {
+    /// Creates and returns auditor keys.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::auditor::Auditor;
+    ///
+    /// let (_auditor_pub_key, _auditor_priv_key) = Auditor::new_keys().unwrap();
+    /// ```
+    pub fn new_keys() -> Result<(AuditorPublicKey, AuditorPrivateKey), IndyCryptoError> {
+        trace!("Auditor::new_keys: >>>");
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let p = generate_safe_prime(LARGE_PRIME)?;
+        let q = generate_safe_prime(LARGE_PRIME)?;
+
+        let n = p.mul(&q, Some(&mut ctx))?;
+        let n_sqr = n.sqr(Some(&mut ctx))?;
+
+        let mut two_n = n.clone()?;
+        two_n.mul_word(2)?;
+
+        let g = n_sqr
+            .rand_range()?
+            .mod_exp(&two_n, &n_sqr, Some(&mut ctx))?;
+
+        let mut x_bound = n_sqr.clone()?;
+        x_bound.div_word(4)?;
+        let x = x_bound.rand_range()?;
+
+        let y = g.mod_exp(&x, &n_sqr, Some(&mut ctx))?;
+
+        let auditor_pub_key = AuditorPublicKey { n, g, y };
+        let auditor_priv_key = AuditorPrivateKey { x };
+
+        trace!("Auditor::new_keys: <<< auditor_pub_key: {:?}, auditor_priv_key: {:?}", auditor_pub_key, auditor_priv_key);
+
+        Ok((auditor_pub_key, auditor_priv_key))
+    }
+
+    /// Decrypts attribute value encrypted by Prover.
+    ///
+    /// # Arguments
+    /// * `ciphertext` - Attribute ciphertext taken from verified proof.
+    /// * `auditor_pub_key` - Auditor public key.
+    /// * `auditor_priv_key` - Auditor private key.
+    pub fn decrypt(ciphertext: &AttributeCiphertext,
+                   auditor_pub_key: &AuditorPublicKey,
+                   auditor_priv_key: &AuditorPrivateKey) -> Result<BigNumber, IndyCryptoError> {
+        trace!("Auditor::decrypt: >>> ciphertext: {:?}, auditor_pub_key: {:?}", ciphertext, auditor_pub_key);
+
+        let mut ctx = BigNumber::new_context()?;
+        let n_sqr = auditor_pub_key.n_sqr()?;
+
+        // Proof binds only squares of ciphertext elements, so decryption works with squares too:
+        // (1 + n)^2m = (e / u^x)^2 mod n^2
+        let val = ciphertext.u
+            .mod_exp(&auditor_priv_key.x, &n_sqr, Some(&mut ctx))?
+            .inverse(&n_sqr, Some(&mut ctx))?
+            .mod_mul(&ciphertext.e, &n_sqr, Some(&mut ctx))?
+            .sqr(Some(&mut ctx))?
+            .modulus(&n_sqr, Some(&mut ctx))?
+            .sub(&BigNumber::from_u32(1)?)?;
+
+        if val.modulus(&auditor_pub_key.n, Some(&mut ctx))? != BigNumber::new()? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute ciphertext doesn't correspond to auditor key")));
+        }
+
+        // m = 2m * 2^-1 mod n, where 2^-1 = (n + 1) / 2
+        let mut two_inv = auditor_pub_key.n.add(&BigNumber::from_u32(1)?)?;
+        two_inv.div_word(2)?;
+
+        let value = val
+            .div(&auditor_pub_key.n, Some(&mut ctx))?
+            .mod_mul(&two_inv, &auditor_pub_key.n, Some(&mut ctx))?;
+
+        trace!("Auditor::decrypt: <<< value: {:?}", value);
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::helpers::MockHelper;
+
+    #[test]
+    fn decrypt_works() {
+        MockHelper::inject();
+
+        let (auditor_pub_key, auditor_priv_key) = Auditor::new_keys().unwrap();
+        let n_sqr = auditor_pub_key.n_sqr().unwrap();
+
+        let r = BigNumber::from_u32(12345).unwrap();
+        let m = BigNumber::from_dec("1139481716457488690172217916278103335").unwrap();
+
+        let ciphertext = AttributeCiphertext {
+            u: auditor_pub_key.g.mod_exp(&r, &n_sqr, None).unwrap(),
+            e: auditor_pub_key.y.mod_exp(&r, &n_sqr, None).unwrap()
+                .mod_mul(&auditor_pub_key.n.add(&BigNumber::from_u32(1).unwrap()).unwrap().mod_exp(&m, &n_sqr, None).unwrap(),
+                         &n_sqr, None).unwrap()
+        };
+
+        assert_eq!(m, Auditor::decrypt(&ciphertext, &auditor_pub_key, &auditor_priv_key).unwrap());
+    }
+
+    #[test]
+    fn decrypt_works_for_distinct_primes() {
+        let (auditor_pub_key, auditor_priv_key) = Auditor::new_keys().unwrap();
+        let n_sqr = auditor_pub_key.n_sqr().unwrap();
+
+        let r = BigNumber::from_u32(12345).unwrap();
+        let m = BigNumber::from_dec("1139481716457488690172217916278103335").unwrap();
+
+        let ciphertext = AttributeCiphertext {
+            u: auditor_pub_key.g.mod_exp(&r, &n_sqr, None).unwrap(),
+            e: auditor_pub_key.y.mod_exp(&r, &n_sqr, None).unwrap()
+                .mod_mul(&auditor_pub_key.n.add(&BigNumber::from_u32(1).unwrap()).unwrap().mod_exp(&m, &n_sqr, None).unwrap(),
+                         &n_sqr, None).unwrap()
+        };
+
+        assert_eq!(m, Auditor::decrypt(&ciphertext, &auditor_pub_key, &auditor_priv_key).unwrap());
+
+        let negated_ciphertext = AttributeCiphertext {
+            u: ciphertext.u.clone().unwrap(),
+            e: n_sqr.sub(&ciphertext.e).unwrap()
+        };
+
+        assert_eq!(m, Auditor::decrypt(&negated_ciphertext, &auditor_pub_key, &auditor_priv_key).unwrap());
+    }
+
+    #[test]
+    fn decrypt_works_for_other_key() {
+        MockHelper::inject();
+
+        let (auditor_pub_key, _) = Auditor::new_keys().unwrap();
+        let (_, other_priv_key) = Auditor::new_keys().unwrap();
+
+        let ciphertext = AttributeCiphertext {
+            u: auditor_pub_key.g.clone().unwrap(),
+            e: auditor_pub_key.y.clone().unwrap()
+        };
+
+        assert!(Auditor::decrypt(&ciphertext, &auditor_pub_key, &other_priv_key).is_err());
+    }
+}
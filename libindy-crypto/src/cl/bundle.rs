@@ -0,0 +1,294 @@
+use cl::*;
+use encryption::SecretBox;
+use errors::IndyCryptoError;
+use kdf::{KdfParams, derive_key, generate_salt, hkdf};
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use std::collections::BTreeMap;
+
+pub const CREDENTIAL_BUNDLE_VERSION: u32 = 1;
+
+const BUNDLE_KEY_INFO: &'static [u8] = b"indy-crypto:cl:credential-bundle";
+
+/// Everything Prover has to store to use credential later: signature, values, schema,
+/// fingerprints of keys and registry the credential is bound to, witness and wallet metadata.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialBundle {
+    signature: CredentialSignature,
+    values: CredentialValues,
+    schema: CredentialSchema,
+    credential_key_fingerprint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rev_reg_fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    witness: Option<Witness>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>
+}
+
+impl CredentialBundle {
+    /// Creates bundle of credential processed by `Prover::process_credential_signature`.
+    ///
+    /// # Arguments
+    /// * `signature` - Credential signature.
+    /// * `values` - Credential values.
+    /// * `schema` - Credential schema.
+    /// * `credential_pub_key` - Credential public key the signature is created with.
+    pub fn new(signature: CredentialSignature,
+               values: CredentialValues,
+               schema: CredentialSchema,
+               credential_pub_key: &CredentialPublicKey) -> Result<CredentialBundle, IndyCryptoError> {
+        Ok(CredentialBundle {
+            signature,
+            values,
+            schema,
+            credential_key_fingerprint: credential_pub_key.fingerprint()?,
+            rev_reg_fingerprint: None,
+            witness: None,
+            metadata: BTreeMap::new()
+        })
+    }
+
+    /// Stores witness of revocable credential together with fingerprint of registry state it is computed for.
+    pub fn set_witness(&mut self, witness: Witness, rev_reg: &RevocationRegistry) -> Result<(), IndyCryptoError> {
+        self.rev_reg_fingerprint = Some(rev_reg.fingerprint()?);
+        self.witness = Some(witness);
+        Ok(())
+    }
+
+    pub fn add_metadata(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn signature(&self) -> &CredentialSignature {
+        &self.signature
+    }
+
+    pub fn values(&self) -> &CredentialValues {
+        &self.values
+    }
+
+    pub fn schema(&self) -> &CredentialSchema {
+        &self.schema
+    }
+
+    pub fn credential_key_fingerprint(&self) -> &str {
+        &self.credential_key_fingerprint
+    }
+
+    pub fn rev_reg_fingerprint(&self) -> Option<&str> {
+        self.rev_reg_fingerprint.as_ref().map(String::as_str)
+    }
+
+    pub fn witness(&self) -> Option<&Witness> {
+        self.witness.as_ref()
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Encrypts bundle with key derived from passphrase by Argon2id.
+    ///
+    /// # Arguments
+    /// * `passphrase` - Passphrase.
+    /// * `params` - Argon2id parameters, stored in sealed bundle.
+    pub fn seal_with_passphrase(&self, passphrase: &[u8], params: &KdfParams) -> Result<SealedCredentialBundle, IndyCryptoError> {
+        trace!("CredentialBundle::seal_with_passphrase: >>> params: {:?}", params);
+
+        let salt = generate_salt()?;
+        let key = derive_key(passphrase, &salt, params)?.expand(BUNDLE_KEY_INFO)?;
+
+        let sealed = SealedCredentialBundle {
+            version: CREDENTIAL_BUNDLE_VERSION,
+            kdf: Some(BundleKdf { salt, params: params.clone() }),
+            ciphertext: SecretBox::seal(self.to_json()?.as_bytes(), key.as_bytes())?
+        };
+
+        trace!("CredentialBundle::seal_with_passphrase: <<<");
+
+        Ok(sealed)
+    }
+
+    /// Encrypts bundle with wallet key.
+    ///
+    /// # Arguments
+    /// * `key` - Secret key of at least `SecretBox::KEY_SIZE` bytes.
+    pub fn seal_with_key(&self, key: &[u8]) -> Result<SealedCredentialBundle, IndyCryptoError> {
+        trace!("CredentialBundle::seal_with_key: >>>");
+
+        let sealed = SealedCredentialBundle {
+            version: CREDENTIAL_BUNDLE_VERSION,
+            kdf: None,
+            ciphertext: SecretBox::seal(self.to_json()?.as_bytes(), &_bundle_key(key)?)?
+        };
+
+        trace!("CredentialBundle::seal_with_key: <<<");
+
+        Ok(sealed)
+    }
+}
+
+impl JsonEncodable for CredentialBundle {}
+
+impl<'a> JsonDecodable<'a> for CredentialBundle {}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BundleKdf {
+    salt: Vec<u8>,
+    params: KdfParams
+}
+
+/// Encrypted `CredentialBundle`. Ciphertext contains Poly1305 tag, so any modification
+/// of stored blob (including KDF parameters) is detected on opening.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SealedCredentialBundle {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<BundleKdf>,
+    ciphertext: Vec<u8>
+}
+
+impl SealedCredentialBundle {
+    /// Returns true if bundle is sealed with passphrase.
+    pub fn is_passphrase_protected(&self) -> bool {
+        self.kdf.is_some()
+    }
+
+    /// Decrypts bundle sealed by `CredentialBundle::seal_with_passphrase`.
+    pub fn open_with_passphrase(&self, passphrase: &[u8]) -> Result<CredentialBundle, IndyCryptoError> {
+        trace!("SealedCredentialBundle::open_with_passphrase: >>>");
+
+        self._check_version()?;
+
+        let kdf = self.kdf.as_ref()
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Credential bundle isn't sealed with passphrase")))?;
+
+        let key = derive_key(passphrase, &kdf.salt, &kdf.params)?.expand(BUNDLE_KEY_INFO)?;
+        let bundle = SealedCredentialBundle::_open(&self.ciphertext, key.as_bytes())?;
+
+        trace!("SealedCredentialBundle::open_with_passphrase: <<<");
+
+        Ok(bundle)
+    }
+
+    /// Decrypts bundle sealed by `CredentialBundle::seal_with_key`.
+    pub fn open_with_key(&self, key: &[u8]) -> Result<CredentialBundle, IndyCryptoError> {
+        trace!("SealedCredentialBundle::open_with_key: >>>");
+
+        self._check_version()?;
+
+        if self.kdf.is_some() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential bundle is sealed with passphrase")));
+        }
+
+        let bundle = SealedCredentialBundle::_open(&self.ciphertext, &_bundle_key(key)?)?;
+
+        trace!("SealedCredentialBundle::open_with_key: <<<");
+
+        Ok(bundle)
+    }
+
+    fn _check_version(&self) -> Result<(), IndyCryptoError> {
+        if self.version != CREDENTIAL_BUNDLE_VERSION {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Unsupported credential bundle version: {}", self.version)));
+        }
+        Ok(())
+    }
+
+    fn _open(ciphertext: &[u8], key: &[u8]) -> Result<CredentialBundle, IndyCryptoError> {
+        let plaintext = SecretBox::open(ciphertext, key)?;
+        let json = String::from_utf8(plaintext)
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Invalid credential bundle encoding")))?;
+        CredentialBundle::from_json(&json)
+    }
+}
+
+impl JsonEncodable for SealedCredentialBundle {}
+
+impl<'a> JsonDecodable<'a> for SealedCredentialBundle {}
+
+fn _bundle_key(key: &[u8]) -> Result<Vec<u8>, IndyCryptoError> {
+    if key.len() < SecretBox::KEY_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of key: expected at least {}, actual {}", SecretBox::KEY_SIZE, key.len())));
+    }
+
+    hkdf(key, &[], BUNDLE_KEY_INFO, SecretBox::KEY_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::issuer;
+
+    const KEY: &'static [u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn _bundle() -> CredentialBundle {
+        let mut bundle = CredentialBundle::new(issuer::mocks::credential(),
+                                               issuer::mocks::credential_values(),
+                                               issuer::mocks::credential_schema(),
+                                               &issuer::mocks::credential_public_key()).unwrap();
+        bundle.add_metadata("cred_def_id", "NcYxiDXkpYi6ov5FcYDi1e:3:CL:1");
+        bundle
+    }
+
+    #[test]
+    fn credential_bundle_seal_with_key_works() {
+        let bundle = _bundle();
+
+        let sealed = SealedCredentialBundle::from_json(&bundle.seal_with_key(KEY).unwrap().to_json().unwrap()).unwrap();
+        assert!(!sealed.is_passphrase_protected());
+
+        let opened = sealed.open_with_key(KEY).unwrap();
+        assert_eq!(bundle.to_json().unwrap(), opened.to_json().unwrap());
+        assert_eq!(Some(&"NcYxiDXkpYi6ov5FcYDi1e:3:CL:1".to_string()), opened.metadata().get("cred_def_id"));
+    }
+
+    #[test]
+    fn credential_bundle_seal_with_passphrase_works() {
+        let bundle = _bundle();
+
+        let sealed = bundle.seal_with_passphrase(b"passphrase", &KdfParams::new(1, 8192).unwrap()).unwrap();
+        assert!(sealed.is_passphrase_protected());
+
+        let opened = sealed.open_with_passphrase(b"passphrase").unwrap();
+        assert_eq!(bundle.to_json().unwrap(), opened.to_json().unwrap());
+
+        assert!(sealed.open_with_passphrase(b"other passphrase").is_err());
+        assert!(sealed.open_with_key(KEY).is_err());
+    }
+
+    #[test]
+    fn sealed_credential_bundle_open_works_for_tampered_ciphertext() {
+        let mut sealed = _bundle().seal_with_key(KEY).unwrap();
+        let last = sealed.ciphertext.len() - 1;
+        sealed.ciphertext[last] ^= 1;
+
+        assert!(sealed.open_with_key(KEY).is_err());
+    }
+
+    #[test]
+    fn sealed_credential_bundle_open_works_for_other_version() {
+        let mut sealed = _bundle().seal_with_key(KEY).unwrap();
+        sealed.version = CREDENTIAL_BUNDLE_VERSION + 1;
+
+        assert!(sealed.open_with_key(KEY).is_err());
+    }
+
+    #[test]
+    fn credential_bundle_seal_with_key_works_for_short_key() {
+        assert!(_bundle().seal_with_key(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn credential_bundle_set_witness_works() {
+        let mut bundle = _bundle();
+        bundle.set_witness(issuer::mocks::witness(), &issuer::mocks::revocation_registry()).unwrap();
+
+        let opened = bundle.seal_with_key(KEY).unwrap().open_with_key(KEY).unwrap();
+        assert!(opened.witness().is_some());
+        assert_eq!(bundle.rev_reg_fingerprint(), opened.rev_reg_fingerprint());
+    }
+}
@@ -0,0 +1,735 @@
+use bn::BigNumber;
+use cl::*;
+use cl::constants::ITERATION;
+use errors::IndyCryptoError;
+use pair::*;
+
+use std::collections::HashMap;
+
+const COMPACT_PROOF_VERSION: u8 = 1;
+// Version of proofs with challenge hash other than default, hash id follows version byte.
+const COMPACT_PROOF_VERSION_WITH_HASH: u8 = 2;
+
+impl Proof {
+    /// Serializes proof in compact binary form.
+    ///
+    /// Compared to JSON the compact form:
+    /// 1) Omits aggregated `c_list` as all its elements are already present in sub proofs
+    ///    and restores it on decoding.
+    /// 2) Omits `mj` of GE proofs that equals to corresponding value of equality proof.
+    /// 3) Stores big numbers as binary, G1/G2 points in compressed form.
+    /// 4) Omits t-values of proof built for batch verification, so decoded proof is verified individually in batch.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    /// use indy_crypto::cl::Proof;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    ///
+    /// let proof_request_nonce = new_nonce().unwrap();
+    /// let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+    ///
+    /// let compact_proof = proof.to_compact_bytes().unwrap();
+    /// let _proof = Proof::from_compact_bytes(&compact_proof).unwrap();
+    /// ```
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        trace!("Proof::to_compact_bytes: >>> proof: {:?}", self);
+
+        if self.aggregated_proof.c_list != _c_list(&self.proofs)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Aggregated c_list doesn't correspond to sub proofs")));
+        }
+
+        if self.proofs.iter().any(|sub_proof| sub_proof.key_id.is_some()) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Key ids of sub proofs aren't supported by compact form")));
+        }
+
+        if self.proofs.iter().any(|sub_proof| !sub_proof.primary_proof.range_proofs.is_empty()) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Range proofs of predicates aren't supported by compact form")));
+        }
+
+        if !self.nonces.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Proofs bound to multiple nonces aren't supported by compact form")));
+        }
+
+        #[cfg(feature = "sign_sodium")]
+        {
+            if self.device_binding.is_some() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Proofs bound to device key aren't supported by compact form")));
+            }
+        }
+
+        let mut writer = CompactWriter::new();
+        if self.aggregated_proof.challenge_hash.is_default() {
+            writer.put_u8(COMPACT_PROOF_VERSION);
+        } else {
+            writer.put_u8(COMPACT_PROOF_VERSION_WITH_HASH);
+            writer.put_u8(_challenge_hash_id(self.aggregated_proof.challenge_hash));
+        }
+
+        writer.put_len(self.proofs.len());
+        for sub_proof in self.proofs.iter() {
+            _write_sub_proof(&mut writer, sub_proof)?;
+        }
+
+        writer.put_bn(&self.aggregated_proof.c_hash)?;
+
+        writer.put_len(self.non_ownership_proofs.len());
+        for proof in self.non_ownership_proofs.iter() {
+            writer.put_bn(&proof.c_d)?;
+            writer.put_bn(&proof.c_r)?;
+            writer.put_bn(&proof.a_hat)?;
+            writer.put_bn(&proof.r_hat)?;
+            writer.put_bn(&proof.s_hat)?;
+            writer.put_bn(&proof.rx_hat)?;
+            writer.put_bn(&proof.sx_hat)?;
+        }
+
+        writer.put_len(self.verifiable_encryptions.len());
+        for proof in self.verifiable_encryptions.iter() {
+            writer.put_bn(&proof.ciphertext.u)?;
+            writer.put_bn(&proof.ciphertext.e)?;
+            writer.put_bn(&proof.r_hat)?;
+        }
+
+        writer.put_len(self.pseudonyms.len());
+        for pseudonym in self.pseudonyms.iter() {
+            writer.put_str(&pseudonym.domain);
+            writer.put_bn(&pseudonym.value)?;
+        }
+
+        let bytes = writer.finalize();
+
+        trace!("Proof::to_compact_bytes: <<< bytes: {:?}", bytes);
+
+        Ok(bytes)
+    }
+
+    /// Restores proof from compact binary form created by `Proof::to_compact_bytes`.
+    ///
+    /// # Arguments
+    /// * `bytes` - Compact proof bytes.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Proof, IndyCryptoError> {
+        trace!("Proof::from_compact_bytes: >>> bytes: {:?}", bytes);
+
+        let mut reader = CompactReader::new(bytes);
+
+        let challenge_hash = match reader.get_u8()? {
+            COMPACT_PROOF_VERSION => ChallengeHash::default(),
+            COMPACT_PROOF_VERSION_WITH_HASH => _challenge_hash_from_id(reader.get_u8()?)?,
+            version => return Err(IndyCryptoError::InvalidStructure(format!("Unsupported compact proof version: {}", version)))
+        };
+
+        let mut proofs: Vec<SubProof> = Vec::new();
+        for _ in 0..reader.get_len()? {
+            proofs.push(_read_sub_proof(&mut reader)?);
+        }
+
+        let c_hash = reader.get_bn()?;
+        let c_list = _c_list(&proofs)?;
+
+        let mut non_ownership_proofs: Vec<NonOwnershipProof> = Vec::new();
+        for _ in 0..reader.get_len()? {
+            non_ownership_proofs.push(NonOwnershipProof {
+                c_d: reader.get_bn()?,
+                c_r: reader.get_bn()?,
+                a_hat: reader.get_bn()?,
+                r_hat: reader.get_bn()?,
+                s_hat: reader.get_bn()?,
+                rx_hat: reader.get_bn()?,
+                sx_hat: reader.get_bn()?
+            });
+        }
+
+        let mut verifiable_encryptions: Vec<VerifiableEncryptionProof> = Vec::new();
+        for _ in 0..reader.get_len()? {
+            verifiable_encryptions.push(VerifiableEncryptionProof {
+                ciphertext: AttributeCiphertext {
+                    u: reader.get_bn()?,
+                    e: reader.get_bn()?
+                },
+                r_hat: reader.get_bn()?
+            });
+        }
+
+        let mut pseudonyms: Vec<Pseudonym> = Vec::new();
+        for _ in 0..reader.get_len()? {
+            pseudonyms.push(Pseudonym {
+                domain: reader.get_str()?,
+                value: reader.get_bn()?
+            });
+        }
+
+        reader.finalize()?;
+
+        let proof = Proof {
+            proofs,
+            aggregated_proof: AggregatedProof { c_hash, c_list, challenge_hash, t_list: Vec::new() },
+            non_ownership_proofs,
+            verifiable_encryptions,
+            pseudonyms,
+            nonces: Vec::new(),
+            #[cfg(feature = "sign_sodium")]
+            device_binding: None
+        };
+
+        trace!("Proof::from_compact_bytes: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+}
+
+fn _challenge_hash_id(challenge_hash: ChallengeHash) -> u8 {
+    match challenge_hash {
+        ChallengeHash::Sha256 => 0,
+        ChallengeHash::Sha384 => 1,
+        ChallengeHash::Sha3_256 => 2
+    }
+}
+
+fn _challenge_hash_from_id(id: u8) -> Result<ChallengeHash, IndyCryptoError> {
+    match id {
+        0 => Ok(ChallengeHash::Sha256),
+        1 => Ok(ChallengeHash::Sha384),
+        2 => Ok(ChallengeHash::Sha3_256),
+        _ => Err(IndyCryptoError::InvalidStructure(format!("Unknown challenge hash id: {}", id)))
+    }
+}
+
+// Restores aggregated c_list in the same order as `ProofBuilder::finalize` builds it.
+fn _c_list(proofs: &[SubProof]) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+    let mut c_list: Vec<Vec<u8>> = Vec::new();
+
+    for proof in proofs.iter() {
+        if let Some(ref non_revoc_proof) = proof.non_revoc_proof {
+            c_list.extend_from_slice(&non_revoc_proof.c_list.as_list()?);
+        }
+
+        c_list.push(proof.primary_proof.eq_proof.a_prime.to_bytes()?);
+
+        for ge_proof in proof.primary_proof.ge_proofs.iter() {
+            _append_ge_c_list(&mut c_list, ge_proof)?;
+        }
+    }
+
+    for proof in proofs.iter() {
+        for ge_proof in proof.primary_proof.linear_ge_proofs.iter() {
+            _append_ge_c_list(&mut c_list, ge_proof)?;
+        }
+    }
+
+    Ok(c_list)
+}
+
+fn _append_ge_c_list(c_list: &mut Vec<Vec<u8>>, ge_proof: &PrimaryPredicateGEProof) -> Result<(), IndyCryptoError> {
+    for i in 0..ITERATION {
+        let t = ge_proof.t.get(&i.to_string())
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in ge_proof.t", i)))?;
+        c_list.push(t.to_bytes()?);
+    }
+
+    let t_delta = ge_proof.t.get("DELTA")
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key 'DELTA' not found in ge_proof.t")))?;
+    c_list.push(t_delta.to_bytes()?);
+
+    Ok(())
+}
+
+fn _write_sub_proof(writer: &mut CompactWriter, sub_proof: &SubProof) -> Result<(), IndyCryptoError> {
+    let eq_proof = &sub_proof.primary_proof.eq_proof;
+
+    writer.put_bn_map(&eq_proof.revealed_attrs)?;
+    writer.put_bn(&eq_proof.a_prime)?;
+    writer.put_bn(&eq_proof.e)?;
+    writer.put_bn(&eq_proof.v)?;
+    writer.put_bn_map(&eq_proof.m)?;
+    writer.put_bn(&eq_proof.m1)?;
+    writer.put_bn(&eq_proof.m2)?;
+
+    writer.put_len(sub_proof.primary_proof.ge_proofs.len());
+    for ge_proof in sub_proof.primary_proof.ge_proofs.iter() {
+        _write_ge_proof(writer, ge_proof, Some(&eq_proof.m))?;
+    }
+
+    writer.put_len(sub_proof.primary_proof.linear_ge_proofs.len());
+    for ge_proof in sub_proof.primary_proof.linear_ge_proofs.iter() {
+        _write_ge_proof(writer, ge_proof, None)?;
+    }
+
+    match sub_proof.non_revoc_proof {
+        Some(ref non_revoc_proof) => {
+            writer.put_u8(1);
+
+            for x in non_revoc_proof.x_list.as_list()?.iter() {
+                writer.put_group_order_element(x)?;
+            }
+
+            let c_list = &non_revoc_proof.c_list;
+            writer.put_point_g1(&c_list.e)?;
+            writer.put_point_g1(&c_list.d)?;
+            writer.put_point_g1(&c_list.a)?;
+            writer.put_point_g1(&c_list.g)?;
+            writer.put_point_g2(&c_list.w)?;
+            writer.put_point_g2(&c_list.s)?;
+            writer.put_point_g2(&c_list.u)?;
+
+            // Bit 0 marks timestamp, bit 1 marks revocation registry id
+            let mut flags = 0u8;
+            if non_revoc_proof.timestamp.is_some() { flags |= 1; }
+            if non_revoc_proof.rev_reg_id.is_some() { flags |= 2; }
+            writer.put_u8(flags);
+
+            if let Some(timestamp) = non_revoc_proof.timestamp {
+                writer.put_u64(timestamp);
+            }
+
+            if let Some(ref rev_reg_id) = non_revoc_proof.rev_reg_id {
+                writer.put_str(rev_reg_id);
+            }
+        }
+        None => writer.put_u8(0)
+    }
+
+    Ok(())
+}
+
+fn _read_sub_proof(reader: &mut CompactReader) -> Result<SubProof, IndyCryptoError> {
+    let eq_proof = PrimaryEqualProof {
+        revealed_attrs: reader.get_bn_map()?,
+        a_prime: reader.get_bn()?,
+        e: reader.get_bn()?,
+        v: reader.get_bn()?,
+        m: reader.get_bn_map()?,
+        m1: reader.get_bn()?,
+        m2: reader.get_bn()?
+    };
+
+    let mut ge_proofs: Vec<PrimaryPredicateGEProof> = Vec::new();
+    for _ in 0..reader.get_len()? {
+        ge_proofs.push(_read_ge_proof(reader, Some(&eq_proof.m))?);
+    }
+
+    let mut linear_ge_proofs: Vec<PrimaryPredicateGEProof> = Vec::new();
+    for _ in 0..reader.get_len()? {
+        linear_ge_proofs.push(_read_ge_proof(reader, None)?);
+    }
+
+    let non_revoc_proof = match reader.get_flag()? {
+        true => {
+            let mut x_list: Vec<GroupOrderElement> = Vec::new();
+            for _ in 0..14 {
+                x_list.push(reader.get_group_order_element()?);
+            }
+
+            let c_list = NonRevocProofCList {
+                e: reader.get_point_g1()?,
+                d: reader.get_point_g1()?,
+                a: reader.get_point_g1()?,
+                g: reader.get_point_g1()?,
+                w: reader.get_point_g2()?,
+                s: reader.get_point_g2()?,
+                u: reader.get_point_g2()?
+            };
+
+            let flags = reader.get_u8()?;
+            if flags & !3 != 0 {
+                return Err(IndyCryptoError::InvalidStructure(format!("Invalid non-revocation proof flags in compact proof: {}", flags)));
+            }
+
+            let timestamp = match flags & 1 {
+                0 => None,
+                _ => Some(reader.get_u64()?)
+            };
+
+            let rev_reg_id = match flags & 2 {
+                0 => None,
+                _ => Some(reader.get_str()?)
+            };
+
+            Some(NonRevocProof {
+                x_list: NonRevocProofXList::from_list(x_list),
+                c_list,
+                timestamp,
+                rev_reg_id
+            })
+        }
+        false => None
+    };
+
+    Ok(SubProof {
+        primary_proof: PrimaryProof { eq_proof, ge_proofs, linear_ge_proofs, range_proofs: Vec::new() },
+        non_revoc_proof,
+        key_id: None
+    })
+}
+
+// `eq_m` is set for GE proofs over credential attributes: `mj` equal to `eq_m[attr_name]` isn't stored.
+fn _write_ge_proof(writer: &mut CompactWriter,
+                   ge_proof: &PrimaryPredicateGEProof,
+                   eq_m: Option<&HashMap<String, BigNumber>>) -> Result<(), IndyCryptoError> {
+    writer.put_str(&ge_proof.predicate.attr_name);
+    writer.put_u8(match ge_proof.predicate.p_type {
+        PredicateType::GE => 0,
+        PredicateType::GT => 1,
+        PredicateType::LE => 2,
+        PredicateType::LT => 3
+    });
+    writer.put_i32(ge_proof.predicate.value);
+
+    writer.put_bn_map(&ge_proof.u)?;
+    writer.put_bn_map(&ge_proof.r)?;
+
+    match eq_m.and_then(|m| m.get(&ge_proof.predicate.attr_name)) {
+        Some(mj) if *mj == ge_proof.mj => writer.put_u8(0),
+        _ => {
+            writer.put_u8(1);
+            writer.put_bn(&ge_proof.mj)?;
+        }
+    }
+
+    writer.put_bn(&ge_proof.alpha)?;
+    writer.put_bn_map(&ge_proof.t)?;
+
+    Ok(())
+}
+
+fn _read_ge_proof(reader: &mut CompactReader,
+                  eq_m: Option<&HashMap<String, BigNumber>>) -> Result<PrimaryPredicateGEProof, IndyCryptoError> {
+    let attr_name = reader.get_str()?;
+    let p_type = match reader.get_u8()? {
+        0 => PredicateType::GE,
+        1 => PredicateType::GT,
+        2 => PredicateType::LE,
+        3 => PredicateType::LT,
+        p_type => return Err(IndyCryptoError::InvalidStructure(format!("Unknown predicate type: {}", p_type)))
+    };
+    let predicate = Predicate { attr_name, p_type, value: reader.get_i32()?, proof_version: PredicateProofVersion::FourSquares };
+
+    let u = reader.get_bn_map()?;
+    let r = reader.get_bn_map()?;
+
+    let mj = match reader.get_flag()? {
+        true => reader.get_bn()?,
+        false => eq_m
+            .and_then(|m| m.get(&predicate.attr_name))
+            .ok_or(IndyCryptoError::MissingAttribute { attr: predicate.attr_name.to_string(), context: "eq_proof.m".to_string() })?
+            .clone()?
+    };
+
+    Ok(PrimaryPredicateGEProof {
+        u,
+        r,
+        mj,
+        alpha: reader.get_bn()?,
+        t: reader.get_bn_map()?,
+        predicate
+    })
+}
+
+struct CompactWriter {
+    bytes: Vec<u8>
+}
+
+impl CompactWriter {
+    fn new() -> CompactWriter {
+        CompactWriter {
+            bytes: Vec::new()
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn put_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn put_u64(&mut self, value: u64) {
+        for i in (0..8).rev() {
+            self.bytes.push((value >> (i * 8)) as u8);
+        }
+    }
+
+    fn put_i32(&mut self, value: i32) {
+        for i in (0..4).rev() {
+            self.bytes.push((value >> (i * 8)) as u8);
+        }
+    }
+
+    // LEB128 encoded unsigned integer
+    fn put_len(&mut self, len: usize) {
+        let mut len = len;
+        while len >= 0x80 {
+            self.bytes.push((len as u8 & 0x7F) | 0x80);
+            len >>= 7;
+        }
+        self.bytes.push(len as u8);
+    }
+
+    fn put_str(&mut self, value: &str) {
+        self.put_len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    // Magnitude length and sign are packed into one prefix: (len << 1) | is_negative
+    fn put_bn(&mut self, value: &BigNumber) -> Result<(), IndyCryptoError> {
+        let bytes = value.to_bytes()?;
+        self.put_len((bytes.len() << 1) | value.is_negative()? as usize);
+        self.bytes.extend_from_slice(&bytes);
+        Ok(())
+    }
+
+    fn put_bn_map(&mut self, map: &HashMap<String, BigNumber>) -> Result<(), IndyCryptoError> {
+        let mut keys: Vec<&String> = map.keys().collect();
+        keys.sort();
+
+        self.put_len(keys.len());
+        for key in keys {
+            self.put_str(key);
+            self.put_bn(&map[key])?;
+        }
+        Ok(())
+    }
+
+    fn put_group_order_element(&mut self, value: &GroupOrderElement) -> Result<(), IndyCryptoError> {
+        self.bytes.extend_from_slice(&value.to_bytes()?);
+        Ok(())
+    }
+
+    fn put_point_g1(&mut self, value: &PointG1) -> Result<(), IndyCryptoError> {
+        self.bytes.extend_from_slice(&value.to_bytes_compressed()?);
+        Ok(())
+    }
+
+    fn put_point_g2(&mut self, value: &PointG2) -> Result<(), IndyCryptoError> {
+        self.bytes.extend_from_slice(&value.to_bytes_compressed()?);
+        Ok(())
+    }
+}
+
+struct CompactReader<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> CompactReader<'a> {
+    fn new(bytes: &'a [u8]) -> CompactReader<'a> {
+        CompactReader {
+            bytes,
+            pos: 0
+        }
+    }
+
+    fn finalize(self) -> Result<(), IndyCryptoError> {
+        if self.pos != self.bytes.len() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unexpected trailing data in compact proof")));
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IndyCryptoError> {
+        if len > self.bytes.len() - self.pos {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unexpected end of compact proof")));
+        }
+
+        let all_bytes: &'a [u8] = self.bytes;
+        let bytes = &all_bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn get_u8(&mut self) -> Result<u8, IndyCryptoError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn get_flag(&mut self) -> Result<bool, IndyCryptoError> {
+        match self.get_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            flag => Err(IndyCryptoError::InvalidStructure(format!("Invalid flag in compact proof: {}", flag)))
+        }
+    }
+
+    fn get_u64(&mut self) -> Result<u64, IndyCryptoError> {
+        Ok(self.take(8)?.iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64))
+    }
+
+    fn get_i32(&mut self) -> Result<i32, IndyCryptoError> {
+        Ok(self.take(4)?.iter().fold(0u32, |acc, byte| (acc << 8) | *byte as u32) as i32)
+    }
+
+    fn get_len(&mut self) -> Result<usize, IndyCryptoError> {
+        let mut len: usize = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.get_u8()?;
+
+            if shift >= 32 {
+                return Err(IndyCryptoError::InvalidStructure(format!("Invalid length in compact proof")));
+            }
+
+            len |= ((byte & 0x7F) as usize) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+        }
+    }
+
+    fn get_str(&mut self) -> Result<String, IndyCryptoError> {
+        let len = self.get_len()?;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|err| IndyCryptoError::InvalidStructure(format!("Invalid string in compact proof: {:?}", err)))
+    }
+
+    fn get_bn(&mut self) -> Result<BigNumber, IndyCryptoError> {
+        let prefix = self.get_len()?;
+        let mut value = BigNumber::from_bytes(self.take(prefix >> 1)?)?;
+        value.set_negative(prefix & 1 == 1)?;
+        Ok(value)
+    }
+
+    fn get_bn_map(&mut self) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+        let mut map: HashMap<String, BigNumber> = HashMap::new();
+        for _ in 0..self.get_len()? {
+            let key = self.get_str()?;
+            let value = self.get_bn()?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+
+    fn get_group_order_element(&mut self) -> Result<GroupOrderElement, IndyCryptoError> {
+        GroupOrderElement::from_bytes(self.take(GroupOrderElement::BYTES_REPR_SIZE)?)
+    }
+
+    fn get_point_g1(&mut self) -> Result<PointG1, IndyCryptoError> {
+        PointG1::from_bytes_compressed(self.take(PointG1::BYTES_REPR_COMPRESSED_SIZE)?)
+    }
+
+    fn get_point_g2(&mut self) -> Result<PointG2, IndyCryptoError> {
+        PointG2::from_bytes_compressed(self.take(PointG2::BYTES_REPR_COMPRESSED_SIZE)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::mocks;
+    use utils::json::{JsonEncodable, JsonDecodable};
+
+    use serde_json;
+
+    use proptest::prelude::ProptestConfig;
+
+    #[test]
+    fn compact_writer_reader_work() {
+        let mut map: HashMap<String, BigNumber> = HashMap::new();
+        map.insert("a".to_string(), BigNumber::from_dec("-123456789012345678901234567890").unwrap());
+        map.insert("b".to_string(), BigNumber::from_dec("0").unwrap());
+
+        let mut writer = CompactWriter::new();
+        writer.put_len(300);
+        writer.put_u64(1_500_000_000);
+        writer.put_i32(-18);
+        writer.put_str("name");
+        writer.put_bn_map(&map).unwrap();
+        let bytes = writer.finalize();
+
+        let mut reader = CompactReader::new(&bytes);
+        assert_eq!(300, reader.get_len().unwrap());
+        assert_eq!(1_500_000_000, reader.get_u64().unwrap());
+        assert_eq!(-18, reader.get_i32().unwrap());
+        assert_eq!("name", reader.get_str().unwrap());
+        assert_eq!(map, reader.get_bn_map().unwrap());
+        reader.finalize().unwrap();
+    }
+
+    #[test]
+    fn from_compact_bytes_works_for_empty_proof() {
+        let proof = Proof::from_compact_bytes(&[COMPACT_PROOF_VERSION, 0, 0, 0, 0, 0]).unwrap();
+        assert!(proof.proofs.is_empty());
+        assert!(proof.aggregated_proof.c_list.is_empty());
+    }
+
+    #[test]
+    fn from_compact_bytes_works_for_invalid_bytes() {
+        assert!(Proof::from_compact_bytes(&[]).is_err());
+        assert!(Proof::from_compact_bytes(&[COMPACT_PROOF_VERSION + 1, 0, 0, 0, 0, 0, 0]).is_err());
+        assert!(Proof::from_compact_bytes(&[COMPACT_PROOF_VERSION, 0, 0, 0, 0]).is_err());
+        assert!(Proof::from_compact_bytes(&[COMPACT_PROOF_VERSION, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn proof_round_trip_works(scenario in mocks::proof_scenario()) {
+            let fixture = scenario.prove().unwrap();
+
+            let compact_proof = Proof::from_compact_bytes(&fixture.proof.to_compact_bytes().unwrap()).unwrap();
+            prop_assert!(fixture.verify(&compact_proof, &fixture.nonce).unwrap());
+
+            let json_proof = Proof::from_json(&fixture.proof.to_json().unwrap()).unwrap();
+            prop_assert!(fixture.verify(&json_proof, &fixture.nonce).unwrap());
+
+            // Maps are serialized in arbitrary order, so JSON is compared as values
+            let json = serde_json::from_str::<serde_json::Value>(&fixture.proof.to_json().unwrap()).unwrap();
+            let restored_json = serde_json::from_str::<serde_json::Value>(&json_proof.to_json().unwrap()).unwrap();
+            prop_assert_eq!(json, restored_json);
+        }
+    }
+}
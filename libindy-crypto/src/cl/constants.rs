@@ -12,6 +12,7 @@ pub const LARGE_MTILDE: usize = 593;
 pub const LARGE_VPRIME_TILDE: usize = 673;
 pub const LARGE_RTILDE: usize = 672;
 pub const ITERATION: usize = 4;
+pub const CHALLENGE_BYTES: usize = 32;
 /*
   LARGE_M1_TILDE: now it differs from the paper v0.3, but author of the paper,
   Dmitry Khovratovich, suggests to use same size as LARGE_MVECT
@@ -19,4 +20,46 @@ pub const ITERATION: usize = 4;
 */
 pub const LARGE_M1_TILDE: usize = LARGE_MVECT;
 pub const LARGE_NONCE: usize = 80;
+pub const MAX_NONCE_SIZE: usize = 1024;
 pub const LARGE_ALPHATILDE: usize = 2787;
+pub const LARGE_ATTR_CHUNK: usize = 256;
+pub const ATTR_CHUNK_SEPARATOR: &'static str = "#";
+pub const DEFAULT_MAX_ATTRS_COUNT: usize = 256;
+pub const LARGE_AUDIT_R: usize = 2128;
+pub const LARGE_AUDIT_R_TILDE: usize = 2464;
+/*
+  Non-ownership proof commits to witness with randomness r, s of LARGE_NON_OWNERSHIP_R bits.
+  Blinding factors of r * x and s * x, where x is 256-bit registry member, cover the product size.
+*/
+pub const LARGE_NON_OWNERSHIP_R: usize = 2128;
+pub const LARGE_NON_OWNERSHIP_R_TILDE: usize = 2464;
+pub const LARGE_NON_OWNERSHIP_RX_TILDE: usize = 2720;
+/*
+  2048-bit safe prime from RFC 3526 (group 14). Pseudonyms are computed in the subgroup of quadratic residues modulo it.
+*/
+pub const PSEUDONYM_GROUP_MODULUS: &'static str = "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DD\
+EF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE6\
+49286651ECE45B3DC2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804\
+F1746C08CA18217C32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6955817183995497CEA956AE515D2261898FA0510\
+15728E5A8AACAA68FFFFFFFFFFFFFFFF";
+pub const FIXED_BASE_EXP_WINDOW: usize = 4;
+pub const LARGE_PRECOMPUTED_S_EXP: usize = 3328;
+pub const LARGE_PRECOMPUTED_EXP: usize = 640;
+/*
+  Version 0 proofs bind only z and attributes bases, version 1 proofs also bind n, s, rms, rctxt and revocation key.
+*/
+pub const CREDENTIAL_KEY_CORRECTNESS_PROOF_VERSION: u32 = 1;
+/*
+  Tails generator holds 2 * max_cred_num + 1 tails, so max_cred_num is limited to keep tails count in u64.
+*/
+pub const MAX_CRED_NUM_LIMIT: u64 = (::std::u64::MAX - 1) / 2;
+pub const DEFAULT_PRECOMPUTED_TAILS_COUNT: u64 = 1 << 16;
+/*
+  Caching tails accessor fetches tails by chunks of 1024 entries and keeps up to 16 MiB of tails.
+*/
+pub const DEFAULT_TAILS_CHUNK_SIZE: u64 = 1 << 10;
+pub const DEFAULT_TAILS_CACHE_SIZE: usize = 1 << 24;
+/*
+  Size of random exponents used to combine equations of batch verification.
+*/
+pub const LARGE_BATCH_EXP: usize = 80;
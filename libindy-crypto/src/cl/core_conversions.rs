@@ -0,0 +1,399 @@
+use bn::BigNumber;
+use cl::*;
+use errors::{IndyCryptoError, RejectionReason};
+
+use verifier_core;
+use verifier_core::{BigUint, VerifierCoreError};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+impl CredentialPublicKey {
+    /// Converts primary part of credential public key for `verifier_core::PrimaryProofVerifier`.
+    pub fn to_verifier_core(&self) -> Result<verifier_core::PrimaryPublicKey, IndyCryptoError> {
+        let p_key = &self.p_key;
+
+        if p_key.without_master_secret {
+            return Err(IndyCryptoError::InvalidStructure(format!("Keys without master secret are not supported by verifier core")));
+        }
+
+        Ok(verifier_core::PrimaryPublicKey {
+            n: _to_big_uint(&p_key.n)?,
+            s: _to_big_uint(&p_key.s)?,
+            rms: _to_big_uint(&p_key.rms)?,
+            r: _to_big_uint_map(p_key.r.iter())?,
+            rctxt: _to_big_uint(&p_key.rctxt)?,
+            z: _to_big_uint(&p_key.z)?
+        })
+    }
+}
+
+impl CredentialSchema {
+    /// Returns attribute names of credential schema for `verifier_core::PrimaryProofVerifier`.
+    pub fn to_verifier_core(&self) -> BTreeSet<String> {
+        self.attrs.iter().cloned().collect()
+    }
+}
+
+impl Predicate {
+    fn to_verifier_core(&self) -> verifier_core::Predicate {
+        verifier_core::Predicate {
+            attr_name: self.attr_name.clone(),
+            value: self.value
+        }
+    }
+}
+
+impl SubProofRequest {
+    /// Converts sub proof request for `verifier_core::PrimaryProofVerifier`.
+    /// Attribute names must be already canonicalized by credential schema.
+    ///
+    /// Returns error for requests with linear predicates, range proof predicates, attribute value checks or non-revocation interval.
+    pub fn to_verifier_core(&self) -> Result<verifier_core::SubProofRequest, IndyCryptoError> {
+        if !self.linear_predicates.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Linear predicates aren't supported by verifier core")));
+        }
+
+        if self.non_revoked.is_some() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Non-revocation interval isn't supported by verifier core")));
+        }
+
+        if !self.attr_value_checks.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute value checks aren't supported by verifier core")));
+        }
+
+        if self.predicates.iter().any(|predicate| predicate.proof_version != PredicateProofVersion::FourSquares) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Range proofs of predicates aren't supported by verifier core")));
+        }
+
+        Ok(verifier_core::SubProofRequest {
+            revealed_attrs: self.revealed_attrs.iter().cloned().collect(),
+            predicates: self.predicates.iter().map(|predicate| predicate.to_verifier_core()).collect()
+        })
+    }
+}
+
+impl Proof {
+    /// Converts proof for `verifier_core::PrimaryProofVerifier`.
+    ///
+    /// Returns error for proofs with non-revocation proofs, linear predicates,
+    /// pseudonyms, verifiable encryptions, non-ownership proofs, multiple nonces or device binding.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{new_nonce, nonce_to_verifier_core};
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    /// use indy_crypto::verifier_core::PrimaryProofVerifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    ///
+    /// let proof_request_nonce = new_nonce().unwrap();
+    /// let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+    ///
+    /// let mut proof_verifier = PrimaryProofVerifier::new();
+    /// proof_verifier.add_sub_proof_request(sub_proof_request.to_verifier_core().unwrap(),
+    ///                                      credential_schema.to_verifier_core(),
+    ///                                      credential_pub_key.to_verifier_core().unwrap()).unwrap();
+    ///
+    /// assert!(proof_verifier.verify(&proof.to_verifier_core().unwrap(),
+    ///                               &nonce_to_verifier_core(&proof_request_nonce).unwrap()).unwrap());
+    /// ```
+    pub fn to_verifier_core(&self) -> Result<verifier_core::Proof, IndyCryptoError> {
+        trace!("Proof::to_verifier_core: >>> proof: {:?}", self);
+
+        if !self.non_ownership_proofs.is_empty() || !self.verifiable_encryptions.is_empty() || !self.pseudonyms.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Non-ownership proofs, verifiable encryptions and pseudonyms aren't supported by verifier core")));
+        }
+
+        if !self.nonces.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Proofs bound to multiple nonces aren't supported by verifier core")));
+        }
+
+        #[cfg(feature = "sign_sodium")]
+        {
+            if self.device_binding.is_some() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Proofs bound to device key aren't supported by verifier core")));
+            }
+        }
+
+        let mut proofs: Vec<verifier_core::PrimaryProof> = Vec::new();
+
+        for sub_proof in self.proofs.iter() {
+            if sub_proof.non_revoc_proof.is_some() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Non-revocation proofs aren't supported by verifier core")));
+            }
+
+            proofs.push(sub_proof.primary_proof.to_verifier_core()?);
+        }
+
+        let proof = verifier_core::Proof {
+            proofs,
+            c_hash: _to_big_uint(&self.aggregated_proof.c_hash)?,
+            c_list: self.aggregated_proof.c_list.clone(),
+            challenge_hash: match self.aggregated_proof.challenge_hash {
+                ChallengeHash::Sha256 => verifier_core::ChallengeHash::Sha256,
+                ChallengeHash::Sha384 => verifier_core::ChallengeHash::Sha384,
+                ChallengeHash::Sha3_256 => verifier_core::ChallengeHash::Sha3_256
+            }
+        };
+
+        trace!("Proof::to_verifier_core: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+}
+
+impl PrimaryProof {
+    fn to_verifier_core(&self) -> Result<verifier_core::PrimaryProof, IndyCryptoError> {
+        if !self.linear_ge_proofs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Linear predicates aren't supported by verifier core")));
+        }
+
+        if !self.range_proofs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Range proofs of predicates aren't supported by verifier core")));
+        }
+
+        let eq_proof = &self.eq_proof;
+
+        let mut ge_proofs: Vec<verifier_core::PrimaryPredicateGEProof> = Vec::new();
+
+        for ge_proof in self.ge_proofs.iter() {
+            ge_proofs.push(verifier_core::PrimaryPredicateGEProof {
+                u: _to_big_uint_map(ge_proof.u.iter())?,
+                r: _to_big_uint_map(ge_proof.r.iter())?,
+                mj: _to_big_uint(&ge_proof.mj)?,
+                alpha: _to_big_uint(&ge_proof.alpha)?,
+                t: _to_big_uint_map(ge_proof.t.iter())?,
+                predicate: ge_proof.predicate.to_verifier_core()
+            });
+        }
+
+        Ok(verifier_core::PrimaryProof {
+            eq_proof: verifier_core::PrimaryEqualProof {
+                revealed_attrs: _to_big_uint_map(eq_proof.revealed_attrs.iter())?,
+                a_prime: _to_big_uint(&eq_proof.a_prime)?,
+                e: _to_big_uint(&eq_proof.e)?,
+                v: _to_big_uint(&eq_proof.v)?,
+                m: _to_big_uint_map(eq_proof.m.iter())?,
+                m1: _to_big_uint(&eq_proof.m1)?,
+                m2: _to_big_uint(&eq_proof.m2)?
+            },
+            ge_proofs
+        })
+    }
+}
+
+/// Converts nonce proof was created for to `verifier_core::PrimaryProofVerifier::verify` argument.
+pub fn nonce_to_verifier_core(nonce: &Nonce) -> Result<BigUint, IndyCryptoError> {
+    _to_big_uint(nonce)
+}
+
+impl From<VerifierCoreError> for IndyCryptoError {
+    fn from(err: VerifierCoreError) -> IndyCryptoError {
+        match err {
+            VerifierCoreError::InvalidStructure(description) => IndyCryptoError::InvalidStructure(description),
+            VerifierCoreError::ProofRejected(description) =>
+                IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::MalformedProof(description) }
+        }
+    }
+}
+
+fn _to_big_uint(num: &BigNumber) -> Result<BigUint, IndyCryptoError> {
+    if num.is_negative()? {
+        return Err(IndyCryptoError::InvalidStructure(format!("Negative values aren't supported by verifier core")));
+    }
+
+    Ok(BigUint::from_bytes_be(&num.to_bytes()?))
+}
+
+fn _to_big_uint_map<'a, I>(nums: I) -> Result<BTreeMap<String, BigUint>, IndyCryptoError>
+    where I: Iterator<Item=(&'a String, &'a BigNumber)> {
+    let mut map = BTreeMap::new();
+
+    for (key, num) in nums {
+        map.insert(key.clone(), _to_big_uint(num)?);
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::constants::{LARGE_MVECT, LARGE_VTILDE, PSEUDONYM_GROUP_MODULUS};
+    use cl::issuer;
+    use cl::issuer::Issuer;
+    use cl::prover;
+    use cl::prover::Prover;
+    use cl::verifier::Verifier;
+    use verifier_core::PrimaryProofVerifier;
+
+    #[test]
+    fn verifier_core_works_for_primary_proof() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let mut core_proof_verifier = PrimaryProofVerifier::new();
+        core_proof_verifier.add_sub_proof_request(sub_proof_request.to_verifier_core().unwrap(),
+                                                  credential_schema.to_verifier_core(),
+                                                  cred_pub_key.to_verifier_core().unwrap()).unwrap();
+
+        let core_proof = proof.to_verifier_core().unwrap();
+        assert!(core_proof_verifier.verify(&core_proof, &nonce_to_verifier_core(&nonce).unwrap()).unwrap());
+        assert!(!core_proof_verifier.verify(&core_proof, &nonce_to_verifier_core(&new_nonce().unwrap()).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn sub_proof_request_to_verifier_core_works_for_linear_predicate() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "age", 1)], "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert!(sub_proof_request.to_verifier_core().is_err());
+    }
+
+    fn _moduli() -> Vec<BigNumber> {
+        let n = issuer::mocks::credential_primary_public_key().n;
+        let n_even = n.add(&BigNumber::from_u32(1).unwrap()).unwrap();
+        let prime = BigNumber::from_hex(PSEUDONYM_GROUP_MODULUS).unwrap();
+
+        vec![n, n_even, prime]
+    }
+
+    #[test]
+    fn big_uint_mod_exp_works_for_2048_bit_modulus() {
+        let p_key = issuer::mocks::credential_primary_public_key();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        for m in _moduli() {
+            let bases = vec![p_key.s.clone().unwrap(), p_key.rctxt.clone().unwrap(),
+                             BigNumber::rand(2048).unwrap(), BigNumber::rand(3000).unwrap()];
+            let exps = vec![BigNumber::from_u32(0).unwrap(), BigNumber::from_u32(1).unwrap(),
+                            BigNumber::rand(LARGE_MVECT).unwrap(), BigNumber::rand(LARGE_VTILDE).unwrap()];
+
+            for base in bases.iter() {
+                for exp in exps.iter() {
+                    let expected = base.mod_exp(exp, &m, Some(&mut ctx)).unwrap();
+                    let actual = _to_big_uint(base).unwrap()
+                        .mod_exp(&_to_big_uint(exp).unwrap(), &_to_big_uint(&m).unwrap()).unwrap();
+
+                    assert_eq!(_to_big_uint(&expected).unwrap(), actual);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn big_uint_mod_inverse_works_for_2048_bit_modulus() {
+        let p_key = issuer::mocks::credential_primary_public_key();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        for m in _moduli().into_iter().filter(|m| m.is_bit_set(0).unwrap()) {
+            let nums = vec![p_key.s.clone().unwrap(), p_key.z.clone().unwrap(),
+                            BigNumber::rand(2048).unwrap(), BigNumber::rand(3000).unwrap()];
+
+            for num in nums.iter() {
+                let expected = num.inverse(&m, Some(&mut ctx)).unwrap();
+                let actual = _to_big_uint(num).unwrap().mod_inverse(&_to_big_uint(&m).unwrap()).unwrap();
+
+                assert_eq!(_to_big_uint(&expected).unwrap(), actual);
+            }
+        }
+    }
+
+    #[test]
+    fn to_big_uint_works_for_negative() {
+        let mut num = BigNumber::from_u32(5).unwrap();
+        num.set_negative(true).unwrap();
+
+        assert!(_to_big_uint(&num).is_err());
+        assert_eq!(BigUint::from_u32(5), _to_big_uint(&BigNumber::from_u32(5).unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,60 @@
+use cl::AttrValueEncoding;
+use errors::IndyCryptoError;
+
+/// Encodes raw attribute value per standard encoding of Aries RFC 0036 and returns
+/// decimal string of the integer signed by Issuer. Together with `AttrValueEncoding::for_raw`
+/// this gives values that verify across Aries stacks: 32-bit integers are passed through,
+/// any other value is replaced with big-endian integer of its SHA-256 hash.
+///
+/// # Arguments
+/// * `raw` - Raw attribute value.
+/// * `kind` - Encoding method.
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::AttrValueEncoding;
+/// use indy_crypto::cl::encoding::encode_attribute;
+///
+/// assert_eq!("28", encode_attribute("28", AttrValueEncoding::for_raw("28")).unwrap());
+/// assert_eq!("101327353979588246869873249766058188995681113722618593621043638294296500696424",
+///            encode_attribute("SLC", AttrValueEncoding::for_raw("SLC")).unwrap());
+/// ```
+pub fn encode_attribute(raw: &str, kind: AttrValueEncoding) -> Result<String, IndyCryptoError> {
+    trace!("encode_attribute: >>> raw: {:?}, kind: {:?}", raw, kind);
+
+    let encoded = kind.encode(raw)?.to_dec()?;
+
+    trace!("encode_attribute: <<< encoded: {:?}", encoded);
+
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard(raw: &str) -> String {
+        encode_attribute(raw, AttrValueEncoding::for_raw(raw)).unwrap()
+    }
+
+    #[test]
+    fn encode_attribute_works_for_rfc_0036_vectors() {
+        assert_eq!("72896232743708443677449555551687504476536417389324439453514323796296385992918", standard("Alice Jones"));
+        assert_eq!("68086943237164982734333428280784300550565381723532936263016368251445461241953", standard("101 Wilson Lane"));
+        assert_eq!("101327353979588246869873249766058188995681113722618593621043638294296500696424", standard("SLC"));
+        assert_eq!("62838607218564353630028473473939957328943626306458686867332534889076311281879", standard("0.0"));
+    }
+
+    #[test]
+    fn encode_attribute_works_for_32_bit_bounds() {
+        assert_eq!("2147483647", standard("2147483647"));
+        assert_eq!("-2147483648", standard("-2147483648"));
+        assert_eq!("26221484005389514539852548961319751347124425277437769688639924217837557266135", standard("2147483648"));
+        assert_eq!("68956915425095939579909400566452872085353864667122112803508671228696852865689", standard("-2147483649"));
+    }
+
+    #[test]
+    fn encode_attribute_fails_for_non_integer_with_integer_kind() {
+        assert!(encode_attribute("Alex", AttrValueEncoding::Integer).is_err());
+    }
+}
@@ -1,10 +1,11 @@
-use bn::BigNumber;
+use bn::{BigNumber, BigNumberContext};
 use cl::*;
-use errors::IndyCryptoError;
+use errors::{IndyCryptoError, RejectionReason};
 use pair::GroupOrderElement;
 use super::constants::*;
 
 use std::cmp::max;
+use std::fmt;
 use std::collections::{HashMap, HashSet};
 
 #[cfg(test)]
@@ -115,25 +116,25 @@ pub fn encode_attribute(attribute: &str, byte_order: ByteOrder) -> Result<BigNum
 }
 
 #[cfg(test)]
-pub fn generate_v_prime_prime() -> Result<BigNumber, IndyCryptoError> {
-    if MockHelper::is_injected() {
+pub fn generate_v_prime_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
+    if MockHelper::is_injected() && size == LARGE_VPRIME_PRIME {
         return BigNumber::from_dec("6620937836014079781509458870800001917950459774302786434315639456568768602266735503527631640833663968617512880802104566048179854406925811731340920442625764155409951969854303612644125623549271204625894424804352003689903192473464433927658013251120302922648839652919662117216521257876025436906282750361355336367533874548955283776610021309110505377492806210342214471251451681722267655419075635703240258044336607001296052867746675049720589092355650996711033859489737240617860392914314205277920274997312351322125481593636904917159990500837822414761512231315313922792934655437808723096823124948039695324591344458785345326611693414625458359651738188933757751726392220092781991665483583988703321457480411992304516676385323318285847376271589157730040526123521479652961899368891914982347831632139045838008837541334927738208491424027");
     }
-    _generate_v_prime_prime()
+    _generate_v_prime_prime(size)
 }
 
 #[cfg(not(test))]
-pub fn generate_v_prime_prime() -> Result<BigNumber, IndyCryptoError> {
-    _generate_v_prime_prime()
+pub fn generate_v_prime_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
+    _generate_v_prime_prime(size)
 }
 
-pub fn _generate_v_prime_prime() -> Result<BigNumber, IndyCryptoError> {
-    trace!("Helpers::generate_v_prime_prime: >>>");
+pub fn _generate_v_prime_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::generate_v_prime_prime: >>> size: {:?}", size);
 
-    let a = bn_rand(LARGE_VPRIME_PRIME)?;
+    let a = bn_rand(size)?;
 
     let b = BigNumber::from_u32(2)?
-        .exp(&BigNumber::from_u32(LARGE_VPRIME_PRIME - 1)?, None)?;
+        .exp(&BigNumber::from_u32(size - 1)?, None)?;
 
     let v_prime_prime = bitwise_or_big_int(&a, &b)?;
 
@@ -279,24 +280,106 @@ pub fn transform_u32_to_array_of_u8(x: u32) -> Vec<u8> {
     result
 }
 
+//Byte order: Little
+pub fn transform_u64_to_array_of_u8(x: u64) -> Vec<u8> {
+    trace!("Helpers::transform_u64_to_array_of_u8: >>> x: {:?}", x);
+
+    let mut result: Vec<u8> = Vec::new();
+    for i in (0..8).rev() {
+        result.push((x >> i * 8) as u8);
+    }
+
+    trace!("Helpers::transform_u64_to_array_of_u8: <<< res: {:?}", result);
+
+    result
+}
+
+pub fn check_max_cred_num(max_cred_num: u64) -> Result<(), IndyCryptoError> {
+    if max_cred_num == 0 || max_cred_num > MAX_CRED_NUM_LIMIT {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid max credential number: {}, must be in range 1..{}", max_cred_num, MAX_CRED_NUM_LIMIT)));
+    }
+
+    Ok(())
+}
+
+pub fn check_rev_idx(rev_idx: u64, max_cred_num: u64) -> Result<(), IndyCryptoError> {
+    check_max_cred_num(max_cred_num)?;
+
+    if rev_idx == 0 || rev_idx > max_cred_num {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid revocation index: {}, must be in range 1..{}", rev_idx, max_cred_num)));
+    }
+
+    Ok(())
+}
+
 pub fn get_hash_as_int(nums: &Vec<Vec<u8>>) -> Result<BigNumber, IndyCryptoError> {
-    trace!("Helpers::get_hash_as_int: >>> nums: {:?}", nums);
+    get_hash_as_int_with(ChallengeHash::default(), nums)
+}
 
-    let hashed_array: Vec<u8> = BigNumber::hash_array(&nums)?;
+pub fn get_hash_as_int_with(challenge_hash: ChallengeHash, nums: &Vec<Vec<u8>>) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::get_hash_as_int_with: >>> challenge_hash: {:?}, nums: {:?}", challenge_hash, nums);
+
+    let hashed_array: Vec<u8> = challenge_hash.digest(&nums)?;
 
     let hash = BigNumber::from_bytes(&hashed_array[..]);
 
-    trace!("Helpers::get_hash_as_int: <<< hash: {:?}", hash);
+    trace!("Helpers::get_hash_as_int_with: <<< hash: {:?}", hash);
 
     hash
 }
 
+pub fn get_hash_as_prime(bytes: &[u8]) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::get_hash_as_prime: >>> bytes: {:?}", bytes);
+
+    let mut ctx = BigNumber::new_context()?;
+    let mut counter: u32 = 0;
+
+    let prime = loop {
+        let mut candidate = BigNumber::from_bytes(&BigNumber::hash_array(&vec![bytes.to_vec(), transform_u32_to_array_of_u8(counter)])?)?;
+        candidate.set_bit(255)?.set_bit(0)?;
+
+        if candidate.is_prime(Some(&mut ctx))? {
+            break candidate;
+        }
+        counter += 1;
+    };
+
+    trace!("Helpers::get_hash_as_prime: <<< prime: {:?}", prime);
+
+    Ok(prime)
+}
+
+pub fn get_full_domain_hash_as_int(nums: &Vec<Vec<u8>>, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::get_full_domain_hash_as_int: >>> nums: {:?}, n: {:?}", nums, n);
+
+    let digest = BigNumber::hash_array(nums)?;
+
+    let mut expanded: Vec<u8> = Vec::new();
+    let mut counter: u32 = 0;
+    while expanded.len() * 8 < n.num_bits()? as usize + 128 {
+        expanded.extend_from_slice(&BigNumber::hash_array(&vec![digest.clone(), transform_u32_to_array_of_u8(counter)])?);
+        counter += 1;
+    }
+
+    let hash = BigNumber::from_bytes(&expanded)?.modulus(&n, None)?;
+
+    trace!("Helpers::get_full_domain_hash_as_int: <<< hash: {:?}", hash);
+
+    Ok(hash)
+}
+
 pub fn get_mtilde(unrevealed_attrs: &HashSet<String>) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
     trace!("Helpers::get_mtilde: >>> unrevealed_attrs: {:?}", unrevealed_attrs);
 
     let mut mtilde: HashMap<String, BigNumber> = HashMap::new();
 
-    for attr in unrevealed_attrs.iter() {
+    // Randomness is consumed in sorted order, so results are reproducible with seeded RNG
+    let mut unrevealed_attrs: Vec<&String> = unrevealed_attrs.iter().collect();
+    unrevealed_attrs.sort();
+
+    for attr in unrevealed_attrs {
         mtilde.insert(attr.clone(), bn_rand(LARGE_MVECT)?);
     }
 
@@ -305,7 +388,126 @@ pub fn get_mtilde(unrevealed_attrs: &HashSet<String>) -> Result<HashMap<String,
     Ok(mtilde)
 }
 
-pub fn calc_teq(p_pub_key: &CredentialPrimaryPublicKey,
+/// Exponentiation of primary public key bases.
+/// Implemented by primary public key itself and by `CredentialPublicKeyPrecomputed` that uses fixed-base tables.
+pub trait PrimaryPublicKeyExp {
+    fn primary_key(&self) -> &CredentialPrimaryPublicKey;
+
+    fn s_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError>;
+
+    fn z_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError>;
+
+    fn rms_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError>;
+
+    fn rctxt_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError>;
+
+    fn r_exp(&self, attr: &str, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError>;
+}
+
+impl PrimaryPublicKeyExp for CredentialPrimaryPublicKey {
+    fn primary_key(&self) -> &CredentialPrimaryPublicKey {
+        self
+    }
+
+    fn s_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.s.mod_exp(exp, &self.n, Some(ctx))
+    }
+
+    fn z_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.z.mod_exp(exp, &self.n, Some(ctx))
+    }
+
+    fn rms_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.rms.mod_exp(exp, &self.n, Some(ctx))
+    }
+
+    fn rctxt_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.rctxt.mod_exp(exp, &self.n, Some(ctx))
+    }
+
+    fn r_exp(&self, attr: &str, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.r.get(attr)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "pk.r".to_string() })?
+            .mod_exp(exp, &self.n, Some(ctx))
+    }
+}
+
+/// Table of powers base^(j * 2^(w * i)) mod n of fixed base for all windows i of w bits,
+/// so exponentiation requires only one multiplication per window of exponent and no squarings.
+pub struct FixedBaseExpTable {
+    base: BigNumber,
+    n: BigNumber,
+    table: Vec<Vec<BigNumber>>
+}
+
+impl fmt::Debug for FixedBaseExpTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FixedBaseExpTable {{ base: {:?}, n: {:?}, windows: {} }}", self.base, self.n, self.table.len())
+    }
+}
+
+impl FixedBaseExpTable {
+    pub fn new(base: &BigNumber, n: &BigNumber, max_exp_bits: usize) -> Result<FixedBaseExpTable, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let windows_count = (max_exp_bits + FIXED_BASE_EXP_WINDOW - 1) / FIXED_BASE_EXP_WINDOW;
+        let row_len = 1 << FIXED_BASE_EXP_WINDOW;
+
+        let mut table: Vec<Vec<BigNumber>> = Vec::with_capacity(windows_count);
+        // base^(2^(w * i)) for current window i
+        let mut window_base = base.modulus(n, Some(&mut ctx))?;
+
+        for _ in 0..windows_count {
+            let mut row: Vec<BigNumber> = Vec::with_capacity(row_len);
+            row.push(BigNumber::from_u32(1)?);
+            for j in 1..row_len {
+                let power = row[j - 1].mod_mul(&window_base, n, Some(&mut ctx))?;
+                row.push(power);
+            }
+
+            window_base = row[row_len - 1].mod_mul(&window_base, n, Some(&mut ctx))?;
+            table.push(row);
+        }
+
+        Ok(FixedBaseExpTable {
+            base: base.clone()?,
+            n: n.clone()?,
+            table
+        })
+    }
+
+    /// Calculates base^exp mod n. Exponents longer than table are exponentiated in regular way.
+    pub fn mod_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let exp_bits = exp.num_bits()? as usize;
+
+        if exp_bits > self.table.len() * FIXED_BASE_EXP_WINDOW {
+            return self.base.mod_exp(exp, &self.n, Some(ctx));
+        }
+
+        let mut result = BigNumber::from_u32(1)?;
+
+        for (i, row) in self.table.iter().enumerate() {
+            let offset = i * FIXED_BASE_EXP_WINDOW;
+            if offset >= exp_bits {
+                break;
+            }
+
+            let mut digit = 0;
+            for bit in 0..FIXED_BASE_EXP_WINDOW {
+                if exp.is_bit_set((offset + bit) as i32)? {
+                    digit |= 1 << bit;
+                }
+            }
+
+            if digit != 0 {
+                result = result.mod_mul(&row[digit], &self.n, Some(ctx))?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+pub fn calc_teq(p_pub_key: &PrimaryPublicKeyExp,
                 a_prime: &BigNumber,
                 e: &BigNumber,
                 v: &BigNumber,
@@ -314,48 +516,95 @@ pub fn calc_teq(p_pub_key: &CredentialPrimaryPublicKey,
                 m2tilde: &BigNumber,
                 unrevealed_attrs: &HashSet<String>) -> Result<BigNumber, IndyCryptoError> {
     trace!("Helpers::calc_teq: >>> p_pub_key: {:?}, p_pub_key: {:?}, e: {:?}, v: {:?}, m_tilde: {:?}, m1_tilde: {:?}, m2tilde: {:?}, \
-    unrevealed_attrs: {:?}", p_pub_key, a_prime, e, v, m_tilde, m1_tilde, m2tilde, unrevealed_attrs);
+    unrevealed_attrs: {:?}", p_pub_key.primary_key(), a_prime, e, v, m_tilde, m1_tilde, m2tilde, unrevealed_attrs);
 
+    let n = &p_pub_key.primary_key().n;
     let mut ctx = BigNumber::new_context()?;
     let mut result: BigNumber = a_prime
-        .mod_exp(&e, &p_pub_key.n, Some(&mut ctx))?;
+        .mod_exp(&e, n, Some(&mut ctx))?;
 
     for k in unrevealed_attrs.iter() {
-        let cur_r = p_pub_key.r.get(k)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", k)))?;
         let cur_m = m_tilde.get(k)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in mtilde", k)))?;
+            .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "mtilde".to_string() })?;
 
-        result = cur_r
-            .mod_exp(&cur_m, &p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+        result = p_pub_key.r_exp(k, &cur_m, &mut ctx)?
+            .mod_mul(&result, n, Some(&mut ctx))?;
     }
 
-    result = p_pub_key.s
-        .mod_exp(&v, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+    result = p_pub_key.s_exp(&v, &mut ctx)?
+        .mod_mul(&result, n, Some(&mut ctx))?;
 
-    result = p_pub_key.rms
-        .mod_exp(&m1_tilde, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+    if !p_pub_key.primary_key().without_master_secret {
+        result = p_pub_key.rms_exp(&m1_tilde, &mut ctx)?
+            .mod_mul(&result, n, Some(&mut ctx))?;
+    }
 
-    result = p_pub_key.rctxt
-        .mod_exp(&m2tilde, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+    result = p_pub_key.rctxt_exp(&m2tilde, &mut ctx)?
+        .mod_mul(&result, n, Some(&mut ctx))?;
 
     trace!("Helpers::calc_teq: <<< t: {:?}", result);
 
     Ok(result)
 }
 
-pub fn calc_tge(p_pub_key: &CredentialPrimaryPublicKey,
+/// Inverts value received from Prover modulo `n`.
+/// Value that isn't invertible (e.g. zero) means proof is malformed, so it's rejected instead of returning OpenSSL error.
+pub fn inverse_proof_value(value: &BigNumber, n: &BigNumber, name: &str, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    if value.modulus(n, Some(ctx))? == BigNumber::new()? {
+        return Err(IndyCryptoError::AnoncredsProofRejected {
+            reason: RejectionReason::MalformedProof(format!("Value '{}' isn't invertible", name))
+        });
+    }
+
+    value.inverse(n, Some(ctx))
+        .map_err(|_| IndyCryptoError::AnoncredsProofRejected {
+            reason: RejectionReason::MalformedProof(format!("Value '{}' isn't invertible", name))
+        })
+}
+
+/// Encodes key id sub proof refers to with its index and length, so it's hashed into challenge unambiguously
+/// and proof can't be redirected to other key id after challenge is computed.
+pub fn key_id_bytes(sub_proof_idx: usize, key_id: &str) -> Vec<u8> {
+    let mut bytes = transform_u32_to_array_of_u8(sub_proof_idx as u32);
+    bytes.extend_from_slice(&transform_u32_to_array_of_u8(key_id.len() as u32));
+    bytes.extend_from_slice(key_id.as_bytes());
+    bytes
+}
+
+/// Returns absolute value of residue modulo `n`: the smaller one of `value` and `n - value`.
+/// Residues that differ only by factor -1 have the same absolute value, so t-values are committed to
+/// by their absolute values and equations over them can be checked in squared subgroup, where -1 vanishes.
+pub fn abs_mod(value: &BigNumber, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    let neg_value = n.sub(value)?;
+    if neg_value < *value { Ok(neg_value) } else { value.clone() }
+}
+
+/// Returns value by key of map received from Prover, rejects proof as malformed if there is no such key.
+pub fn get_proof_value<'a>(values: &'a HashMap<String, BigNumber>, key: &str, name: &str) -> Result<&'a BigNumber, IndyCryptoError> {
+    values.get(key)
+        .ok_or(IndyCryptoError::AnoncredsProofRejected {
+            reason: RejectionReason::MalformedProof(format!("Value by key '{}' not found in {}", key, name))
+        })
+}
+
+pub fn mod_exp_signed(base: &BigNumber, exp: &BigNumber, n: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    if exp.is_negative()? {
+        base.inverse(n, Some(ctx))?
+            .mod_exp(&BigNumber::new()?.sub(exp)?, n, Some(ctx))
+    } else {
+        base.mod_exp(exp, n, Some(ctx))
+    }
+}
+
+pub fn calc_tge(p_pub_key: &PrimaryPublicKeyExp,
                 u: &HashMap<String, BigNumber>,
                 r: &HashMap<String, BigNumber>,
                 mj: &BigNumber,
                 alpha: &BigNumber,
                 t: &HashMap<String, BigNumber>) -> Result<Vec<BigNumber>, IndyCryptoError> {
-    trace!("Helpers::calc_tge: >>> p_pub_key: {:?}, u: {:?}, r: {:?}, mj: {:?}, alpha: {:?}, t: {:?}", p_pub_key, u, r, mj, alpha, t);
+    trace!("Helpers::calc_tge: >>> p_pub_key: {:?}, u: {:?}, r: {:?}, mj: {:?}, alpha: {:?}, t: {:?}", p_pub_key.primary_key(), u, r, mj, alpha, t);
 
+    let n = &p_pub_key.primary_key().n;
     let mut tau_list: Vec<BigNumber> = Vec::new();
     let mut ctx = BigNumber::new_context()?;
 
@@ -365,11 +614,10 @@ pub fn calc_tge(p_pub_key: &CredentialPrimaryPublicKey,
         let cur_r = r.get(&i.to_string())
             .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", i)))?;
 
-        let t_tau = p_pub_key.z
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
+        let t_tau = p_pub_key.z_exp(&cur_u, &mut ctx)?
             .mod_mul(
-                &p_pub_key.s.mod_exp(&cur_r, &p_pub_key.n, Some(&mut ctx))?,
-                &p_pub_key.n, Some(&mut ctx)
+                &p_pub_key.s_exp(&cur_r, &mut ctx)?,
+                n, Some(&mut ctx)
             )?;
 
         tau_list.push(t_tau);
@@ -378,11 +626,18 @@ pub fn calc_tge(p_pub_key: &CredentialPrimaryPublicKey,
     let delta = r.get("DELTA")
         .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", "DELTA")))?;
 
-    let t_tau = p_pub_key.z
-        .mod_exp(&mj, &p_pub_key.n, Some(&mut ctx))?
+    // mj can be negative for predicates over linear combination of attributes
+    let z_mj = if mj.is_negative()? {
+        p_pub_key.z_exp(&BigNumber::new()?.sub(mj)?, &mut ctx)?
+            .inverse(n, Some(&mut ctx))?
+    } else {
+        p_pub_key.z_exp(mj, &mut ctx)?
+    };
+
+    let t_tau = z_mj
         .mod_mul(
-            &p_pub_key.s.mod_exp(&delta, &p_pub_key.n, Some(&mut ctx))?,
-            &p_pub_key.n, Some(&mut ctx)
+            &p_pub_key.s_exp(&delta, &mut ctx)?,
+            n, Some(&mut ctx)
         )?;
 
     tau_list.push(t_tau);
@@ -396,13 +651,12 @@ pub fn calc_tge(p_pub_key: &CredentialPrimaryPublicKey,
             .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
 
         q = cur_t
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
+            .mod_exp(&cur_u, n, Some(&mut ctx))?
             .mul(&q, Some(&mut ctx))?;
     }
 
-    q = p_pub_key.s
-        .mod_exp(&alpha, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&q, &p_pub_key.n, Some(&mut ctx))?;
+    q = p_pub_key.s_exp(&alpha, &mut ctx)?
+        .mod_mul(&q, n, Some(&mut ctx))?;
 
     tau_list.push(q);
 
@@ -411,64 +665,186 @@ pub fn calc_tge(p_pub_key: &CredentialPrimaryPublicKey,
     Ok(tau_list)
 }
 
-fn largest_square_less_than(delta: usize) -> usize {
-    (delta as f64).sqrt().floor() as usize
+// Integer square root, exact for the whole `u64` range unlike rounding of `f64` square root.
+fn largest_root(n: u64) -> u64 {
+    let mut root = (n as f64).sqrt() as u64;
+    while root * root > n {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+    root
 }
 
-//Express the natural number `delta` as a sum of four integer squares,
-// i.e `delta = a^2 + b^2 + c^2 + d^2` using Lagrange's four-square theorem
-pub fn four_squares(delta: i32) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
-    trace!("Helpers::four_squares: >>> delta: {:?}", delta);
+// Legendre's three-square theorem: `n` is a sum of three squares iff it isn't of the form `4^a * (8b + 7)`.
+fn is_sum_of_three_squares(mut n: u64) -> bool {
+    if n == 0 {
+        return true;
+    }
+    while n % 4 == 0 {
+        n /= 4;
+    }
+    n % 8 != 7
+}
 
-    if delta < 0 {
-        return Err(IndyCryptoError::InvalidStructure(format!("Cannot express a negative number as sum of four squares {} ", delta)));
+// Finds `count` roots of squares summing up to `n`, trying the largest roots first.
+// If `root^2 * count < n`, some of the remaining roots is larger than `root`, so the same
+// decomposition with that root first was already tried and search at this level can stop.
+fn sum_of_squares(n: u64, count: u64) -> Option<Vec<u64>> {
+    if n == 0 {
+        return Some(vec![0; count as usize]);
     }
 
-    let d = delta as usize;
-    let mut roots: [usize; 4] = [largest_square_less_than(d), 0, 0, 0];
+    if count == 0 {
+        return None;
+    }
 
-    'outer: for i in (1..roots[0] + 1).rev() {
-        roots[0] = i;
-        if d == roots[0].pow(2) {
-            roots[1] = 0;
-            roots[2] = 0;
-            roots[3] = 0;
-            break 'outer;
-        }
-        roots[1] = largest_square_less_than(d - roots[0].pow(2));
-        for j in (1..roots[1] + 1).rev() {
-            roots[1] = j;
-            if d == roots[0].pow(2) + roots[1].pow(2) {
-                roots[2] = 0;
-                roots[3] = 0;
-                break 'outer;
-            }
-            roots[2] = largest_square_less_than(d - roots[0].pow(2) - roots[1].pow(2));
-            for k in (1..roots[2] + 1).rev() {
-                roots[2] = k;
-                if d == roots[0].pow(2) + roots[1].pow(2) + roots[2].pow(2) {
-                    roots[3] = 0;
-                    break 'outer;
-                }
-                roots[3] = largest_square_less_than(d - roots[0].pow(2) - roots[1].pow(2) - roots[2].pow(2));
-                if d == roots[0].pow(2) + roots[1].pow(2) + roots[2].pow(2) + roots[3].pow(2) {
-                    break 'outer;
-                }
+    let mut root = largest_root(n);
+    while root > 0 && root * root * count >= n {
+        let rest = n - root * root;
+
+        // Remainder of four squares decomposition must be a sum of three squares, otherwise it is skipped without search
+        if count != 4 || is_sum_of_three_squares(rest) {
+            if let Some(mut roots) = sum_of_squares(rest, count - 1) {
+                roots.insert(0, root);
+                return Some(roots);
             }
         }
+
+        root -= 1;
     }
 
+    None
+}
+
+/// Expresses non-negative `delta` as a sum of four integer squares `delta = u0^2 + u1^2 + u2^2 + u3^2`
+/// using Lagrange's four-square theorem. Roots are returned by keys "0".."3" in non-increasing order.
+///
+/// Predicate proof shows `delta` computed by `predicate_delta` is non-negative, so negative `delta`
+/// means predicate isn't satisfied and is rejected. Decomposition exists for any non-negative `i32`
+/// and is found without floating point rounding issues, so it never fails for values near bounds.
+pub fn four_squares(delta: i32) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+    trace!("Helpers::four_squares: >>> delta: {:?}", delta);
+
+    if delta < 0 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Cannot express a negative number as sum of four squares {} ", delta)));
+    }
+
+    let roots = sum_of_squares(delta as u64, ITERATION as u64)
+        .ok_or(IndyCryptoError::InvalidState(format!("Four squares decomposition of {} not found", delta)))?;
+
     let mut res: HashMap<String, BigNumber> = HashMap::new();
-    res.insert("0".to_string(), BigNumber::from_dec(&roots[0].to_string()[..])?);
-    res.insert("1".to_string(), BigNumber::from_dec(&roots[1].to_string()[..])?);
-    res.insert("2".to_string(), BigNumber::from_dec(&roots[2].to_string()[..])?);
-    res.insert("3".to_string(), BigNumber::from_dec(&roots[3].to_string()[..])?);
+    for (i, root) in roots.iter().enumerate() {
+        res.insert(i.to_string(), BigNumber::from_u32(*root as usize)?);
+    }
 
     trace!("Helpers::four_squares: <<< res: {:?}", res);
 
     Ok(res)
 }
 
+/// Returns bound `b` predicate is reduced to: predicate holds if `attr_value >= b` for GE and GT
+/// or if `-attr_value >= b` for LE and LT (see `predicate_signed`).
+pub fn predicate_bound(p_type: &PredicateType, value: i32) -> i64 {
+    match *p_type {
+        PredicateType::GE => value as i64,
+        PredicateType::GT => value as i64 + 1,
+        PredicateType::LE => -(value as i64),
+        PredicateType::LT => 1 - value as i64
+    }
+}
+
+/// Negates attribute value (or its blinding or response) for LE and LT predicates,
+/// so every predicate is proven as GE predicate over signed attribute.
+pub fn predicate_signed(p_type: &PredicateType, value: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    if p_type.is_less() {
+        BigNumber::new()?.sub(value)
+    } else {
+        value.clone()
+    }
+}
+
+/// Returns `delta` proved non-negative by predicate proof: `attr_value - value` for GE,
+/// `attr_value - value - 1` for GT, `value - attr_value` for LE and `value - attr_value - 1` for LT.
+/// Difference is computed without `i32` overflow, so predicate values near bounds are either
+/// proved or rejected with an error.
+pub fn predicate_delta(p_type: &PredicateType, attr_value: i32, value: i32) -> Result<i32, IndyCryptoError> {
+    let signed_attr_value = if p_type.is_less() { -(attr_value as i64) } else { attr_value as i64 };
+    let delta = signed_attr_value - predicate_bound(p_type, value);
+
+    if delta < 0 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Predicate is not satisfied")));
+    }
+
+    if delta > i32::max_value() as i64 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Predicate delta {} is out of range", delta)));
+    }
+
+    Ok(delta as i32)
+}
+
+pub fn attr_chunks_count(max_bits: usize) -> Result<usize, IndyCryptoError> {
+    if max_bits == 0 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid max bits of chunked attribute: {}", max_bits)));
+    }
+
+    Ok((max_bits + LARGE_ATTR_CHUNK - 1) / LARGE_ATTR_CHUNK)
+}
+
+pub fn attr_chunk_name(attr: &str, idx: usize) -> String {
+    format!("{}{}{}", attr, ATTR_CHUNK_SEPARATOR, idx)
+}
+
+pub fn split_attr_value(value: &BigNumber, chunks_count: usize) -> Result<Vec<BigNumber>, IndyCryptoError> {
+    trace!("Helpers::split_attr_value: >>> value: {:?}, chunks_count: {:?}", value, chunks_count);
+
+    if value.num_bits()? as usize > chunks_count * LARGE_ATTR_CHUNK {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Attribute value doesn't fit into {} chunks of {} bits", chunks_count, LARGE_ATTR_CHUNK)));
+    }
+
+    let mut ctx = BigNumber::new_context()?;
+    let chunk_modulus = BigNumber::from_u32(2)?
+        .exp(&BigNumber::from_u32(LARGE_ATTR_CHUNK)?, Some(&mut ctx))?;
+
+    let mut rest = value.clone()?;
+    let mut chunks: Vec<BigNumber> = Vec::new();
+
+    for _ in 0..chunks_count {
+        chunks.push(rest.modulus(&chunk_modulus, Some(&mut ctx))?);
+        rest = rest.div(&chunk_modulus, Some(&mut ctx))?;
+    }
+
+    trace!("Helpers::split_attr_value: <<< chunks: {:?}", chunks);
+
+    Ok(chunks)
+}
+
+pub fn join_attr_chunks(chunks: &[&BigNumber]) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::join_attr_chunks: >>> chunks: {:?}", chunks);
+
+    let mut ctx = BigNumber::new_context()?;
+    let chunk_modulus = BigNumber::from_u32(2)?
+        .exp(&BigNumber::from_u32(LARGE_ATTR_CHUNK)?, Some(&mut ctx))?;
+
+    let mut value = BigNumber::from_u32(0)?;
+
+    for chunk in chunks.iter().rev() {
+        if chunk.num_bits()? as usize > LARGE_ATTR_CHUNK {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute chunk exceeds {} bits", LARGE_ATTR_CHUNK)));
+        }
+
+        value = value
+            .mul(&chunk_modulus, Some(&mut ctx))?
+            .add(chunk)?;
+    }
+
+    trace!("Helpers::join_attr_chunks: <<< value: {:?}", value);
+
+    Ok(value)
+}
+
 pub fn group_element_to_bignum(el: &GroupOrderElement) -> Result<BigNumber, IndyCryptoError> {
     Ok(BigNumber::from_bytes(&el.to_bytes()?)?)
 }
@@ -477,6 +853,7 @@ pub fn bignum_to_group_element(num: &BigNumber) -> Result<GroupOrderElement, Ind
     Ok(GroupOrderElement::from_bytes(&num.to_bytes()?)?)
 }
 
+#[cfg(test)]
 pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey,
                                        rev_reg: &RevocationRegistry,
                                        rev_acc_pub_key: &RevocationKeyPublic,
@@ -484,18 +861,21 @@ pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey
     trace!("Helpers::create_tau_list_expected_values: >>> r_pub_key: {:?}, rev_reg: {:?}, rev_acc_pub_key: {:?}, proof_c: {:?}",
            r_pub_key, rev_reg, rev_acc_pub_key, proof_c);
 
+    let g_neg = r_pub_key.g.neg()?;
+
     let t1 = proof_c.e;
     let t2 = PointG1::new_inf()?;
-    let t3 = Pair::pair(&r_pub_key.h0.add(&proof_c.g)?, &r_pub_key.h_cap)?
-        .mul(&Pair::pair(&proof_c.a, &r_pub_key.y)?.inverse()?)?;
-    let t4 = Pair::pair(&proof_c.g, &rev_reg.accum)?
-        .mul(&Pair::pair(&r_pub_key.g, &proof_c.w)?.mul(&rev_acc_pub_key.z)?.inverse()?)?;
+    let t3 = Pair::product_of_pairings(&[(r_pub_key.h0.add(&proof_c.g)?, r_pub_key.h_cap),
+                                         (proof_c.a.neg()?, r_pub_key.y)])?;
+    let t4 = Pair::product_of_pairings(&[(proof_c.g, rev_reg.accum),
+                                         (g_neg, proof_c.w)])?
+        .mul(&rev_acc_pub_key.witness_pairing()?.inverse()?)?;
     let t5 = proof_c.d;
     let t6 = PointG1::new_inf()?;
-    let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &proof_c.s)?
-        .mul(&Pair::pair(&r_pub_key.g, &r_pub_key.g_dash)?.inverse()?)?;
-    let t8 = Pair::pair(&proof_c.g, &r_pub_key.u)?
-        .mul(&Pair::pair(&r_pub_key.g, &proof_c.u)?.inverse()?)?;
+    let t7 = Pair::product_of_pairings(&[(r_pub_key.pk.add(&proof_c.g)?, proof_c.s),
+                                         (g_neg, r_pub_key.g_dash)])?;
+    let t8 = Pair::product_of_pairings(&[(proof_c.g, r_pub_key.u),
+                                         (g_neg, proof_c.u)])?;
 
     let non_revoc_proof_tau_list = NonRevocProofTauList {
         t1,
@@ -513,6 +893,80 @@ pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey
     Ok(non_revoc_proof_tau_list)
 }
 
+/// Calculates tau list of non-revocation proof verification: expected values raised to challenge
+/// (`create_tau_list_expected_values`) multiplied by values calculated from proof responses (`create_tau_list_values`).
+/// Challenge is moved into scalars of pairings arguments, so Miller loops of both parts are accumulated
+/// and each pairing value requires one final exponentiation.
+pub fn create_tau_list_hat_values(r_pub_key: &CredentialRevocationPublicKey,
+                                  rev_reg: &RevocationRegistry,
+                                  rev_acc_pub_key: &RevocationKeyPublic,
+                                  c_hash: &GroupOrderElement,
+                                  params: &NonRevocProofXList,
+                                  proof_c: &NonRevocProofCList) -> Result<NonRevocProofTauList, IndyCryptoError> {
+    trace!("Helpers::create_tau_list_hat_values: >>> r_pub_key: {:?}, rev_reg: {:?}, rev_acc_pub_key: {:?}, c_hash: {:?}, params: {:?}, proof_c: {:?}",
+           r_pub_key, rev_reg, rev_acc_pub_key, c_hash, params, proof_c);
+
+    let g_neg = r_pub_key.g.neg()?;
+    let g_neg_c_hash = g_neg.mul(c_hash)?;
+    let pk_g = r_pub_key.pk.add(&proof_c.g)?;
+    let g_htilde = PointG1::multi_scalar_mul(&[proof_c.g, r_pub_key.htilde], &[*c_hash, params.r])?;
+
+    let t1 = PointG1::multi_scalar_mul(&[proof_c.e, r_pub_key.h, r_pub_key.htilde],
+                                       &[*c_hash, params.rho, params.o])?;
+    let mut t2 = PointG1::multi_scalar_mul(&[proof_c.e, r_pub_key.h, r_pub_key.htilde],
+                                           &[params.c, params.m.mod_neg()?, params.t.mod_neg()?])?;
+    if t2.is_inf()? {
+        t2 = PointG1::new_inf()?;
+    }
+
+    let mut t3 = MillerAccumulator::new();
+    t3.add_pair(&PointG1::multi_scalar_mul(&[r_pub_key.h0.add(&proof_c.g)?, proof_c.a, r_pub_key.htilde, r_pub_key.htilde, r_pub_key.h1, r_pub_key.h2],
+                                           &[*c_hash, params.c, params.r, params.m.mod_neg()?, params.m2.mod_neg()?, params.s.mod_neg()?])?,
+                &r_pub_key.h_cap)?;
+    t3.add_pair(&PointG1::multi_scalar_mul(&[proof_c.a, r_pub_key.htilde],
+                                           &[c_hash.mod_neg()?, params.rho.mod_neg()?])?,
+                &r_pub_key.y)?;
+
+    let mut t4 = MillerAccumulator::new();
+    t4.add_pair(&g_htilde, &rev_reg.accum)?;
+    t4.add_pair(&g_neg_c_hash, &proof_c.w)?;
+    t4.add_pair(&g_neg.mul(&params.r_prime)?, &r_pub_key.h_cap)?;
+
+    let t5 = PointG1::multi_scalar_mul(&[proof_c.d, r_pub_key.g, r_pub_key.htilde],
+                                       &[*c_hash, params.r, params.o_prime])?;
+    let mut t6 = PointG1::multi_scalar_mul(&[proof_c.d, r_pub_key.g, r_pub_key.htilde],
+                                           &[params.r_prime_prime, params.m_prime.mod_neg()?, params.t_prime.mod_neg()?])?;
+    if t6.is_inf()? {
+        t6 = PointG1::new_inf()?;
+    }
+
+    let mut t7 = MillerAccumulator::new();
+    t7.add_pair(&PointG1::multi_scalar_mul(&[pk_g, r_pub_key.htilde], &[*c_hash, params.r])?, &proof_c.s)?;
+    t7.add_pair(&g_neg_c_hash, &r_pub_key.g_dash)?;
+    t7.add_pair(&PointG1::multi_scalar_mul(&[pk_g, r_pub_key.htilde], &[params.r_prime_prime, params.m_prime.mod_neg()?])?,
+                &r_pub_key.h_cap)?;
+
+    let mut t8 = MillerAccumulator::new();
+    t8.add_pair(&g_htilde, &r_pub_key.u)?;
+    t8.add_pair(&g_neg_c_hash, &proof_c.u)?;
+    t8.add_pair(&g_neg.mul(&params.r_prime_prime_prime)?, &r_pub_key.h_cap)?;
+
+    let non_revoc_proof_tau_list = NonRevocProofTauList {
+        t1,
+        t2,
+        t3: t3.finalize()?,
+        t4: t4.finalize()?.mul(&rev_acc_pub_key.witness_pairing()?.pow(c_hash)?.inverse()?)?,
+        t5,
+        t6,
+        t7: t7.finalize()?,
+        t8: t8.finalize()?
+    };
+
+    trace!("Helpers::create_tau_list_hat_values: <<< non_revoc_proof_tau_list: {:?}", non_revoc_proof_tau_list);
+
+    Ok(non_revoc_proof_tau_list)
+}
+
 pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
                               rev_reg: &RevocationRegistry,
                               params: &NonRevocProofXList,
@@ -520,34 +974,38 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
     trace!("Helpers::create_tau_list_values: >>> r_pub_key: {:?}, rev_reg: {:?}, params: {:?}, proof_c: {:?}",
            r_pub_key, rev_reg, params, proof_c);
 
-    let t1 = r_pub_key.h.mul(&params.rho)?.add(&r_pub_key.htilde.mul(&params.o)?)?;
-    let mut t2 = proof_c.e.mul(&params.c)?
-        .add(&r_pub_key.h.mul(&params.m.mod_neg()?)?)?
-        .add(&r_pub_key.htilde.mul(&params.t.mod_neg()?)?)?;
+    let g_neg = r_pub_key.g.neg()?;
+
+    let t1 = PointG1::multi_scalar_mul(&[r_pub_key.h, r_pub_key.htilde],
+                                       &[params.rho, params.o])?;
+    let mut t2 = PointG1::multi_scalar_mul(&[proof_c.e, r_pub_key.h, r_pub_key.htilde],
+                                           &[params.c, params.m.mod_neg()?, params.t.mod_neg()?])?;
     if t2.is_inf()? {
         t2 = PointG1::new_inf()?;
     }
-    let t3 = Pair::pair(&proof_c.a, &r_pub_key.h_cap)?.pow(&params.c)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.r)?)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.y)?.pow(&params.rho)?
-            .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m)?)?
-            .mul(&Pair::pair(&r_pub_key.h1, &r_pub_key.h_cap)?.pow(&params.m2)?)?
-            .mul(&Pair::pair(&r_pub_key.h2, &r_pub_key.h_cap)?.pow(&params.s)?)?.inverse()?)?;
-    let t4 = Pair::pair(&r_pub_key.htilde, &rev_reg.accum)?
-        .pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime)?)?;
-    let t5 = r_pub_key.g.mul(&params.r)?.add(&r_pub_key.htilde.mul(&params.o_prime)?)?;
-    let mut t6 = proof_c.d.mul(&params.r_prime_prime)?
-        .add(&r_pub_key.g.mul(&params.m_prime.mod_neg()?)?)?
-        .add(&r_pub_key.htilde.mul(&params.t_prime.mod_neg()?)?)?;
+    let t3 = Pair::product_of_pairings(&[
+        (PointG1::multi_scalar_mul(&[proof_c.a, r_pub_key.htilde, r_pub_key.htilde, r_pub_key.h1, r_pub_key.h2],
+                                   &[params.c, params.r, params.m.mod_neg()?, params.m2.mod_neg()?, params.s.mod_neg()?])?,
+         r_pub_key.h_cap),
+        (r_pub_key.htilde.mul(&params.rho.mod_neg()?)?, r_pub_key.y)
+    ])?;
+    let t4 = Pair::product_of_pairings(&[(r_pub_key.htilde.mul(&params.r)?, rev_reg.accum),
+                                         (g_neg.mul(&params.r_prime)?, r_pub_key.h_cap)])?;
+    let t5 = PointG1::multi_scalar_mul(&[r_pub_key.g, r_pub_key.htilde],
+                                       &[params.r, params.o_prime])?;
+    let mut t6 = PointG1::multi_scalar_mul(&[proof_c.d, r_pub_key.g, r_pub_key.htilde],
+                                           &[params.r_prime_prime, params.m_prime.mod_neg()?, params.t_prime.mod_neg()?])?;
     if t6.is_inf()? {
         t6 = PointG1::new_inf()?;
     }
-    let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m_prime.mod_neg()?)?)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &proof_c.s)?.pow(&params.r)?)?;
-    let t8 = Pair::pair(&r_pub_key.htilde, &r_pub_key.u)?.pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime_prime)?)?;
+    let t7 = Pair::product_of_pairings(&[
+        (PointG1::multi_scalar_mul(&[r_pub_key.pk.add(&proof_c.g)?, r_pub_key.htilde],
+                                   &[params.r_prime_prime, params.m_prime.mod_neg()?])?,
+         r_pub_key.h_cap),
+        (r_pub_key.htilde.mul(&params.r)?, proof_c.s)
+    ])?;
+    let t8 = Pair::product_of_pairings(&[(r_pub_key.htilde.mul(&params.r)?, r_pub_key.u),
+                                         (g_neg.mul(&params.r_prime_prime_prime)?, r_pub_key.h_cap)])?;
 
     let non_revoc_proof_tau_list = NonRevocProofTauList {
         t1,
@@ -589,12 +1047,50 @@ mod tests {
         assert_eq!(test_answer, encode_attribute(test_str, ByteOrder::Big).unwrap().to_dec().unwrap());
     }
 
+    #[test]
+    fn attr_chunks_count_works() {
+        assert_eq!(1, attr_chunks_count(1).unwrap());
+        assert_eq!(1, attr_chunks_count(256).unwrap());
+        assert_eq!(2, attr_chunks_count(257).unwrap());
+        assert!(attr_chunks_count(0).is_err());
+    }
+
+    #[test]
+    fn split_attr_value_works() {
+        let value = BigNumber::from_u32(2).unwrap()
+            .exp(&BigNumber::from_u32(300).unwrap(), None).unwrap()
+            .add(&BigNumber::from_u32(5).unwrap()).unwrap();
+
+        let chunks = split_attr_value(&value, 2).unwrap();
+        assert_eq!(2, chunks.len());
+        assert_eq!(BigNumber::from_u32(5).unwrap(), chunks[0]);
+        assert_eq!(BigNumber::from_u32(2).unwrap().exp(&BigNumber::from_u32(44).unwrap(), None).unwrap(), chunks[1]);
+
+        assert_eq!(value, join_attr_chunks(&chunks.iter().collect::<Vec<&BigNumber>>()).unwrap());
+    }
+
+    #[test]
+    fn split_attr_value_works_for_too_large_value() {
+        let value = BigNumber::from_u32(2).unwrap()
+            .exp(&BigNumber::from_u32(512).unwrap(), None).unwrap();
+
+        assert!(split_attr_value(&value, 2).is_err());
+    }
+
+    #[test]
+    fn join_attr_chunks_works_for_too_large_chunk() {
+        let chunk = BigNumber::from_u32(2).unwrap()
+            .exp(&BigNumber::from_u32(256).unwrap(), None).unwrap();
+
+        assert!(join_attr_chunks(&[&chunk]).is_err());
+    }
+
     #[test]
     fn generate_v_prime_prime_works() {
         MockHelper::inject();
 
         let result = BigNumber::from_dec("6620937836014079781509458870800001917950459774302786434315639456568768602266735503527631640833663968617512880802104566048179854406925811731340920442625764155409951969854303612644125623549271204625894424804352003689903192473464433927658013251120302922648839652919662117216521257876025436906282750361355336367533874548955283776610021309110505377492806210342214471251451681722267655419075635703240258044336607001296052867746675049720589092355650996711033859489737240617860392914314205277920274997312351322125481593636904917159990500837822414761512231315313922792934655437808723096823124948039695324591344458785345326611693414625458359651738188933757751726392220092781991665483583988703321457480411992304516676385323318285847376271589157730040526123521479652961899368891914982347831632139045838008837541334927738208491424027").unwrap();
-        assert_eq!(generate_v_prime_prime().unwrap(), result);
+        assert_eq!(generate_v_prime_prime(LARGE_VPRIME_PRIME).unwrap(), result);
     }
 
     #[test]
@@ -617,6 +1113,18 @@ mod tests {
         assert_eq!("2C2566C22E04AB3F18B3BA693823175002F10F400811363D26BBB33633AC8BAD", res.unwrap().to_hex().unwrap());
     }
 
+    #[test]
+    fn get_hash_as_int_with_works() {
+        let nums = vec![b"abc".to_vec()];
+
+        assert_eq!("BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+                   get_hash_as_int_with(ChallengeHash::Sha256, &nums).unwrap().to_hex().unwrap());
+        assert_eq!("CB00753F45A35E8BB5A03D699AC65007272C32AB0EDED1631A8B605A43FF5BED",
+                   get_hash_as_int_with(ChallengeHash::Sha384, &nums).unwrap().to_hex().unwrap());
+        assert_eq!("3A985DA74FE225B2045C172D6BD390BD855F086E3E9D525B46BFE24511431532",
+                   get_hash_as_int_with(ChallengeHash::Sha3_256, &nums).unwrap().to_hex().unwrap());
+    }
+
     #[test]
     fn four_squares_works() {
         let res = four_squares(107 as i32);
@@ -653,6 +1161,167 @@ mod tests {
         assert_eq!("11".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
     }
 
+    fn check_four_squares(delta: i32) {
+        let roots = four_squares(delta).unwrap();
+        let sum = (0..ITERATION)
+            .map(|i| roots[&i.to_string()].to_dec().unwrap().parse::<i64>().unwrap())
+            .fold(0, |sum, root| sum + root * root);
+        assert_eq!(delta as i64, sum, "invalid decomposition of {}", delta);
+    }
+
+    #[test]
+    fn four_squares_works_for_bounds() {
+        for delta in [0, 1, 2, 3, 4, 7, 15, 28, 60, 112, 448, 1792,
+            46340 * 46340, 46340 * 46340 - 1, 46340 * 46340 + 1,
+            i32::max_value() - 1, i32::max_value()].iter() {
+            check_four_squares(*delta);
+        }
+    }
+
+    #[test]
+    fn four_squares_works_for_small_deltas() {
+        for delta in 0..10000 {
+            check_four_squares(delta);
+        }
+    }
+
+    #[test]
+    fn four_squares_works_for_sampled_32_bit_range() {
+        // Stride is prime, so sampled deltas cover all residues modulo small powers of two
+        let mut delta: i64 = 0;
+        while delta <= i32::max_value() as i64 {
+            check_four_squares(delta as i32);
+            delta += 42_949;
+        }
+    }
+
+    #[test]
+    fn four_squares_works_for_numbers_of_form_4a_8b_7() {
+        // Numbers that aren't sums of three squares require all four roots
+        let mut delta: i64 = 7;
+        while delta <= i32::max_value() as i64 {
+            let roots = four_squares(delta as i32).unwrap();
+            assert!(roots["3"] != BigNumber::from_u32(0).unwrap());
+            check_four_squares(delta as i32);
+            delta *= 4;
+        }
+    }
+
+    #[test]
+    fn four_squares_fails_for_negative_delta() {
+        assert!(four_squares(-1).is_err());
+        assert!(four_squares(i32::min_value()).is_err());
+    }
+
+    #[test]
+    fn four_squares_works_for_random_deltas() {
+        for _ in 0..1000 {
+            let delta = _bn_rand(31).unwrap().to_dec().unwrap().parse::<i32>().unwrap();
+            check_four_squares(delta);
+        }
+    }
+
+    #[test]
+    fn predicate_delta_works() {
+        assert_eq!(0, predicate_delta(&PredicateType::GE, 18, 18).unwrap());
+        assert_eq!(10, predicate_delta(&PredicateType::GE, 28, 18).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::GE, i32::max_value(), 0).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::GE, -1, i32::min_value()).unwrap());
+    }
+
+    #[test]
+    fn predicate_delta_fails_for_unsatisfied_or_overflowing_predicate() {
+        assert!(predicate_delta(&PredicateType::GE, 17, 18).is_err());
+        assert!(predicate_delta(&PredicateType::GE, i32::min_value(), i32::max_value()).is_err());
+        assert!(predicate_delta(&PredicateType::GE, i32::max_value(), -1).is_err());
+        assert!(predicate_delta(&PredicateType::GE, i32::max_value(), i32::min_value()).is_err());
+    }
+
+    #[test]
+    fn predicate_delta_works_for_gt() {
+        assert_eq!(0, predicate_delta(&PredicateType::GT, 19, 18).unwrap());
+        assert!(predicate_delta(&PredicateType::GT, 18, 18).is_err());
+        assert_eq!(0, predicate_delta(&PredicateType::GT, i32::max_value(), i32::max_value() - 1).unwrap());
+        assert!(predicate_delta(&PredicateType::GT, i32::max_value(), i32::max_value()).is_err());
+        assert_eq!(i32::max_value() - 1, predicate_delta(&PredicateType::GT, i32::max_value(), 0).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::GT, 0, i32::min_value()).unwrap());
+        assert!(predicate_delta(&PredicateType::GT, 1, i32::min_value()).is_err());
+        assert!(predicate_delta(&PredicateType::GT, i32::min_value(), i32::min_value()).is_err());
+    }
+
+    #[test]
+    fn predicate_delta_works_for_le() {
+        assert_eq!(0, predicate_delta(&PredicateType::LE, 18, 18).unwrap());
+        assert_eq!(10, predicate_delta(&PredicateType::LE, 8, 18).unwrap());
+        assert!(predicate_delta(&PredicateType::LE, 19, 18).is_err());
+        assert_eq!(0, predicate_delta(&PredicateType::LE, i32::min_value(), i32::min_value()).unwrap());
+        assert_eq!(0, predicate_delta(&PredicateType::LE, i32::max_value(), i32::max_value()).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::LE, 0, i32::max_value()).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::LE, i32::min_value(), -1).unwrap());
+        assert!(predicate_delta(&PredicateType::LE, i32::min_value(), 0).is_err());
+        assert!(predicate_delta(&PredicateType::LE, i32::min_value(), i32::max_value()).is_err());
+    }
+
+    #[test]
+    fn predicate_delta_works_for_lt() {
+        assert_eq!(0, predicate_delta(&PredicateType::LT, 17, 18).unwrap());
+        assert!(predicate_delta(&PredicateType::LT, 18, 18).is_err());
+        assert!(predicate_delta(&PredicateType::LT, i32::min_value(), i32::min_value()).is_err());
+        assert_eq!(0, predicate_delta(&PredicateType::LT, i32::min_value(), i32::min_value() + 1).unwrap());
+        assert_eq!(i32::max_value() - 1, predicate_delta(&PredicateType::LT, 0, i32::max_value()).unwrap());
+        assert_eq!(i32::max_value(), predicate_delta(&PredicateType::LT, i32::min_value(), 0).unwrap());
+        assert!(predicate_delta(&PredicateType::LT, i32::min_value(), 1).is_err());
+        assert!(predicate_delta(&PredicateType::LT, i32::min_value(), i32::max_value()).is_err());
+    }
+
+    #[test]
+    fn predicate_signed_works() {
+        let value = BigNumber::from_u32(18).unwrap();
+        assert_eq!(value, predicate_signed(&PredicateType::GE, &value).unwrap());
+        assert_eq!(value, predicate_signed(&PredicateType::GT, &value).unwrap());
+        assert_eq!(BigNumber::from_dec("-18").unwrap(), predicate_signed(&PredicateType::LE, &value).unwrap());
+        assert_eq!(BigNumber::from_dec("-18").unwrap(), predicate_signed(&PredicateType::LT, &value).unwrap());
+    }
+
+    #[test]
+    fn fixed_base_exp_table_works() {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let n = issuer::mocks::credential_primary_public_key().n;
+        let base = BigNumber::rand(2048).unwrap().modulus(&n, None).unwrap();
+        let table = FixedBaseExpTable::new(&base, &n, 600).unwrap();
+
+        for bits in vec![1, 255, 593, 600, 601, 1024] {
+            let exp = BigNumber::rand(bits).unwrap();
+            assert_eq!(base.mod_exp(&exp, &n, None).unwrap(), table.mod_exp(&exp, &mut ctx).unwrap());
+        }
+
+        assert_eq!(BigNumber::from_u32(1).unwrap(), table.mod_exp(&BigNumber::new().unwrap(), &mut ctx).unwrap());
+    }
+
+    #[test]
+    fn mod_exp_signed_works() {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let n = BigNumber::from_u32(23).unwrap();
+        let base = BigNumber::from_u32(5).unwrap();
+        let exp = BigNumber::from_u32(3).unwrap();
+        let neg_exp = BigNumber::new().unwrap().sub(&exp).unwrap();
+
+        let pos = mod_exp_signed(&base, &exp, &n, &mut ctx).unwrap();
+        let neg = mod_exp_signed(&base, &neg_exp, &n, &mut ctx).unwrap();
+
+        assert_eq!(BigNumber::from_u32(1).unwrap(), pos.mod_mul(&neg, &n, None).unwrap());
+    }
+
+    #[test]
+    fn get_hash_as_prime_works() {
+        let prime = get_hash_as_prime(&[1, 2, 3]).unwrap();
+
+        assert!(prime.is_prime(None).unwrap());
+        assert_eq!(256, prime.num_bits().unwrap());
+        assert_eq!(prime, get_hash_as_prime(&[1, 2, 3]).unwrap());
+        assert_ne!(prime, get_hash_as_prime(&[1, 2, 4]).unwrap());
+    }
+
     #[test]
     fn transform_u32_to_array_of_u8_works() {
         let int = 0x74BA7445;
@@ -660,6 +1329,31 @@ mod tests {
         assert_eq!(transform_u32_to_array_of_u8(int), answer)
     }
 
+    #[test]
+    fn transform_u64_to_array_of_u8_works() {
+        let int = 0x0102030474BA7445;
+        let answer = vec![0x01, 0x02, 0x03, 0x04, 0x74, 0xBA, 0x74, 0x45];
+        assert_eq!(transform_u64_to_array_of_u8(int), answer)
+    }
+
+    #[test]
+    fn check_rev_idx_works() {
+        assert!(check_rev_idx(1, 5).is_ok());
+        assert!(check_rev_idx(5, 5).is_ok());
+        assert!(check_rev_idx(0, 5).is_err());
+        assert!(check_rev_idx(6, 5).is_err());
+        assert!(check_rev_idx(1, 0).is_err());
+        assert!(check_rev_idx(1, MAX_CRED_NUM_LIMIT + 1).is_err());
+    }
+
+    #[test]
+    fn abs_mod_works() {
+        let n = BigNumber::from_u32(23).unwrap();
+        assert_eq!(BigNumber::from_u32(5).unwrap(), abs_mod(&BigNumber::from_u32(5).unwrap(), &n).unwrap());
+        assert_eq!(BigNumber::from_u32(5).unwrap(), abs_mod(&BigNumber::from_u32(18).unwrap(), &n).unwrap());
+        assert_eq!(BigNumber::from_u32(11).unwrap(), abs_mod(&BigNumber::from_u32(12).unwrap(), &n).unwrap());
+    }
+
     #[test]
     fn test_encode_attribute_fail_simple_collision_on_internal_truncate() {
         let ea3079 = encode_attribute("3079", ByteOrder::Big).unwrap();
@@ -0,0 +1,454 @@
+use cl::*;
+use cl::issuer::Issuer;
+use cl::prover::Prover;
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+/// First message of issuance protocol sent by Issuer to Prover.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialOffer {
+    key_correctness_proof: CredentialKeyCorrectnessProof,
+    nonce: Nonce
+}
+
+impl JsonEncodable for CredentialOffer {}
+
+impl<'a> JsonDecodable<'a> for CredentialOffer {}
+
+/// Message sent by Prover to Issuer in response to `CredentialOffer`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialRequest {
+    prover_id: String,
+    blinded_master_secret: BlindedMasterSecret,
+    blinded_master_secret_correctness_proof: BlindedMasterSecretCorrectnessProof,
+    nonce: Nonce
+}
+
+impl JsonEncodable for CredentialRequest {}
+
+impl<'a> JsonDecodable<'a> for CredentialRequest {}
+
+/// Last message of issuance protocol sent by Issuer to Prover. Contains credential signature.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialResponse {
+    credential_signature: CredentialSignature,
+    signature_correctness_proof: SignatureCorrectnessProof
+}
+
+impl JsonEncodable for CredentialResponse {}
+
+impl<'a> JsonDecodable<'a> for CredentialResponse {}
+
+#[derive(Debug, Deserialize, Serialize)]
+enum IssuanceState {
+    Created,
+    Offered { nonce: Nonce },
+    Issued
+}
+
+/// Issuer side of issuance protocol: `offer` -> `issue`.
+/// Session can be serialized between protocol steps.
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::issuance::{IssuanceSession, CredentialRequestSession};
+/// use indy_crypto::cl::issuer::Issuer;
+/// use indy_crypto::cl::prover::Prover;
+///
+/// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+/// credential_schema_builder.add_attr("sex").unwrap();
+/// let credential_schema = credential_schema_builder.finalize().unwrap();
+///
+/// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+/// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+/// let credential_values = credential_values_builder.finalize().unwrap();
+///
+/// let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+/// let master_secret = Prover::new_master_secret().unwrap();
+///
+/// let mut issuance_session = IssuanceSession::new();
+/// let mut request_session = CredentialRequestSession::new("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW");
+///
+/// let offer = issuance_session.offer(&cred_key_correctness_proof).unwrap();
+/// let request = request_session.request(&offer, &cred_pub_key, &master_secret).unwrap();
+/// let response = issuance_session.issue(&request, &credential_values, &cred_pub_key, &cred_priv_key).unwrap();
+/// let _credential_signature = request_session.process(response, &credential_values, &cred_pub_key, &master_secret, None, None, None).unwrap();
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssuanceSession {
+    state: IssuanceState
+}
+
+impl IssuanceSession {
+    pub fn new() -> IssuanceSession {
+        IssuanceSession {
+            state: IssuanceState::Created
+        }
+    }
+
+    /// Creates credential offer with fresh nonce.
+    ///
+    /// # Arguments
+    /// * `credential_key_correctness_proof` - Credential key correctness proof.
+    pub fn offer(&mut self, credential_key_correctness_proof: &CredentialKeyCorrectnessProof) -> Result<CredentialOffer, IndyCryptoError> {
+        trace!("IssuanceSession::offer: >>> state: {:?}", self.state);
+
+        match self.state {
+            IssuanceState::Created => {}
+            _ => return Err(IndyCryptoError::InvalidState("Credential offer is already created".to_string()))
+        }
+
+        let nonce = new_nonce()?;
+
+        let offer = CredentialOffer {
+            key_correctness_proof: credential_key_correctness_proof.clone()?,
+            nonce: nonce.clone()?
+        };
+
+        self.state = IssuanceState::Offered { nonce };
+
+        trace!("IssuanceSession::offer: <<< offer: {:?}", offer);
+
+        Ok(offer)
+    }
+
+    /// Signs credential values for Prover that sent the request. Session is completed on success.
+    ///
+    /// # Arguments
+    /// * `request` - Credential request received from Prover.
+    /// * `credential_values` - Credential values to be signed.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_priv_key` - Credential private key.
+    pub fn issue(&mut self,
+                 request: &CredentialRequest,
+                 credential_values: &CredentialValues,
+                 credential_pub_key: &CredentialPublicKey,
+                 credential_priv_key: &CredentialPrivateKey) -> Result<CredentialResponse, IndyCryptoError> {
+        trace!("IssuanceSession::issue: >>> state: {:?}, request: {:?}, credential_values: {:?}", self.state, request, credential_values);
+
+        let (credential_signature, signature_correctness_proof) = {
+            let nonce = self._offered_nonce()?;
+
+            Issuer::sign_credential(&request.prover_id,
+                                    &request.blinded_master_secret,
+                                    &request.blinded_master_secret_correctness_proof,
+                                    nonce,
+                                    &request.nonce,
+                                    credential_values,
+                                    credential_pub_key,
+                                    credential_priv_key)?
+        };
+
+        self.state = IssuanceState::Issued;
+
+        let response = CredentialResponse { credential_signature, signature_correctness_proof };
+
+        trace!("IssuanceSession::issue: <<< response: {:?}", response);
+
+        Ok(response)
+    }
+
+    /// Signs credential values and adds credential to revocation registry. Session is completed on success.
+    ///
+    /// # Arguments
+    /// * `request` - Credential request received from Prover.
+    /// * `credential_values` - Credential values to be signed.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_priv_key` - Credential private key.
+    /// * `rev_idx` - User index in revocation accumulator.
+    /// * `max_cred_num` - Max credential number in generated registry.
+    /// * `issuance_by_default` - Type of issuance.
+    /// * `rev_reg` - Revocation registry.
+    /// * `rev_key_priv` - Revocation registry private key.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn issue_with_revoc<RTA>(&mut self,
+                                 request: &CredentialRequest,
+                                 credential_values: &CredentialValues,
+                                 credential_pub_key: &CredentialPublicKey,
+                                 credential_priv_key: &CredentialPrivateKey,
+                                 rev_idx: u64,
+                                 max_cred_num: u64,
+                                 issuance_by_default: bool,
+                                 rev_reg: &mut RevocationRegistry,
+                                 rev_key_priv: &RevocationKeyPrivate,
+                                 rev_tails_accessor: &RTA) -> Result<(CredentialResponse, Option<RevocationRegistryDelta>),
+                                                                     IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("IssuanceSession::issue_with_revoc: >>> state: {:?}, request: {:?}, credential_values: {:?}, rev_idx: {:?}",
+               self.state, request, credential_values, rev_idx);
+
+        let (credential_signature, signature_correctness_proof, rev_reg_delta) = {
+            let nonce = self._offered_nonce()?;
+
+            Issuer::sign_credential_with_revoc(&request.prover_id,
+                                               &request.blinded_master_secret,
+                                               &request.blinded_master_secret_correctness_proof,
+                                               nonce,
+                                               &request.nonce,
+                                               credential_values,
+                                               credential_pub_key,
+                                               credential_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               rev_reg,
+                                               rev_key_priv,
+                                               rev_tails_accessor)?
+        };
+
+        self.state = IssuanceState::Issued;
+
+        let response = CredentialResponse { credential_signature, signature_correctness_proof };
+
+        trace!("IssuanceSession::issue_with_revoc: <<< response: {:?}, rev_reg_delta: {:?}", response, rev_reg_delta);
+
+        Ok((response, rev_reg_delta))
+    }
+
+    /// Returns true if credential is issued.
+    pub fn is_completed(&self) -> bool {
+        match self.state {
+            IssuanceState::Issued => true,
+            _ => false
+        }
+    }
+
+    fn _offered_nonce(&self) -> Result<&Nonce, IndyCryptoError> {
+        match self.state {
+            IssuanceState::Offered { ref nonce } => Ok(nonce),
+            IssuanceState::Created => Err(IndyCryptoError::InvalidState("Credential offer isn't created".to_string())),
+            IssuanceState::Issued => Err(IndyCryptoError::InvalidState("Credential is already issued".to_string()))
+        }
+    }
+}
+
+impl JsonEncodable for IssuanceSession {}
+
+impl<'a> JsonDecodable<'a> for IssuanceSession {}
+
+#[derive(Debug, Deserialize, Serialize)]
+enum CredentialRequestState {
+    Created,
+    Requested {
+        master_secret_blinding_data: MasterSecretBlindingData,
+        nonce: Nonce
+    },
+    Completed
+}
+
+/// Prover side of issuance protocol: `request` -> `process`.
+/// Session keeps master secret blinding data between protocol steps
+/// and can be serialized, so it must be stored as secret as master secret.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialRequestSession {
+    prover_id: String,
+    state: CredentialRequestState
+}
+
+impl CredentialRequestSession {
+    /// Creates session.
+    ///
+    /// # Arguments
+    /// * `prover_id` - Prover identifier.
+    pub fn new(prover_id: &str) -> CredentialRequestSession {
+        CredentialRequestSession {
+            prover_id: prover_id.to_string(),
+            state: CredentialRequestState::Created
+        }
+    }
+
+    /// Blinds master secret and creates credential request for the offer.
+    ///
+    /// # Arguments
+    /// * `offer` - Credential offer received from Issuer.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `master_secret` - Master secret.
+    pub fn request(&mut self,
+                   offer: &CredentialOffer,
+                   credential_pub_key: &CredentialPublicKey,
+                   master_secret: &MasterSecret) -> Result<CredentialRequest, IndyCryptoError> {
+        trace!("CredentialRequestSession::request: >>> state: {:?}, offer: {:?}, credential_pub_key: {:?}", self.state, offer, credential_pub_key);
+
+        match self.state {
+            CredentialRequestState::Created => {}
+            _ => return Err(IndyCryptoError::InvalidState("Credential request is already created".to_string()))
+        }
+
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(credential_pub_key,
+                                        &offer.key_correctness_proof,
+                                        master_secret,
+                                        &offer.nonce)?;
+
+        let nonce = new_nonce()?;
+
+        let request = CredentialRequest {
+            prover_id: self.prover_id.clone(),
+            blinded_master_secret,
+            blinded_master_secret_correctness_proof,
+            nonce: nonce.clone()?
+        };
+
+        self.state = CredentialRequestState::Requested { master_secret_blinding_data, nonce };
+
+        trace!("CredentialRequestSession::request: <<< request: {:?}", request);
+
+        Ok(request)
+    }
+
+    /// Checks and processes credential signature received from Issuer. Session is completed on success.
+    ///
+    /// # Arguments
+    /// * `response` - Credential response received from Issuer.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `master_secret` - Master secret.
+    /// * `rev_key_pub` - (Optional) Revocation registry public key.
+    /// * `rev_reg` - (Optional) Revocation registry.
+    /// * `witness` - (Optional) Witness.
+    pub fn process(&mut self,
+                   response: CredentialResponse,
+                   credential_values: &CredentialValues,
+                   credential_pub_key: &CredentialPublicKey,
+                   master_secret: &MasterSecret,
+                   rev_key_pub: Option<&RevocationKeyPublic>,
+                   rev_reg: Option<&RevocationRegistry>,
+                   witness: Option<&Witness>) -> Result<CredentialSignature, IndyCryptoError> {
+        trace!("CredentialRequestSession::process: >>> state: {:?}, response: {:?}, credential_values: {:?}", self.state, response, credential_values);
+
+        let CredentialResponse { mut credential_signature, signature_correctness_proof } = response;
+
+        match self.state {
+            CredentialRequestState::Requested { ref master_secret_blinding_data, ref nonce } =>
+                Prover::process_credential_signature(&mut credential_signature,
+                                                     credential_values,
+                                                     &signature_correctness_proof,
+                                                     master_secret_blinding_data,
+                                                     master_secret,
+                                                     credential_pub_key,
+                                                     nonce,
+                                                     rev_key_pub,
+                                                     rev_reg,
+                                                     witness)?,
+            CredentialRequestState::Created =>
+                return Err(IndyCryptoError::InvalidState("Credential request isn't created".to_string())),
+            CredentialRequestState::Completed =>
+                return Err(IndyCryptoError::InvalidState("Credential is already processed".to_string()))
+        }
+
+        // Blinding data isn't needed anymore
+        self.state = CredentialRequestState::Completed;
+
+        trace!("CredentialRequestSession::process: <<< credential_signature: {:?}", credential_signature);
+
+        Ok(credential_signature)
+    }
+
+    /// Returns true if credential is processed.
+    pub fn is_completed(&self) -> bool {
+        match self.state {
+            CredentialRequestState::Completed => true,
+            _ => false
+        }
+    }
+}
+
+impl JsonEncodable for CredentialRequestSession {}
+
+impl<'a> JsonDecodable<'a> for CredentialRequestSession {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+
+    fn _credential_values() -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    fn _credential_def() -> (CredentialPublicKey, CredentialPrivateKey, CredentialKeyCorrectnessProof) {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        Issuer::new_credential_def(&credential_schema, false).unwrap()
+    }
+
+    #[test]
+    fn issuance_sessions_work() {
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = _credential_def();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+
+        let mut issuance_session = IssuanceSession::new();
+        let mut request_session = CredentialRequestSession::new(PROVER_ID);
+
+        let offer = issuance_session.offer(&cred_key_correctness_proof).unwrap();
+        let offer = CredentialOffer::from_json(&offer.to_json().unwrap()).unwrap();
+
+        let request = request_session.request(&offer, &cred_pub_key, &master_secret).unwrap();
+        let request = CredentialRequest::from_json(&request.to_json().unwrap()).unwrap();
+
+        // Sessions are persisted between protocol steps
+        let mut issuance_session = IssuanceSession::from_json(&issuance_session.to_json().unwrap()).unwrap();
+        let mut request_session = CredentialRequestSession::from_json(&request_session.to_json().unwrap()).unwrap();
+
+        let response = issuance_session.issue(&request, &credential_values, &cred_pub_key, &cred_priv_key).unwrap();
+        let response = CredentialResponse::from_json(&response.to_json().unwrap()).unwrap();
+        assert!(issuance_session.is_completed());
+
+        request_session.process(response, &credential_values, &cred_pub_key, &master_secret, None, None, None).unwrap();
+        assert!(request_session.is_completed());
+    }
+
+    #[test]
+    fn issuance_session_issue_works_for_missed_offer() {
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = _credential_def();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let offer = IssuanceSession::new().offer(&cred_key_correctness_proof).unwrap();
+        let request = CredentialRequestSession::new(PROVER_ID).request(&offer, &cred_pub_key, &master_secret).unwrap();
+
+        let mut issuance_session = IssuanceSession::new();
+        assert!(issuance_session.issue(&request, &_credential_values(), &cred_pub_key, &cred_priv_key).is_err());
+    }
+
+    #[test]
+    fn issuance_session_works_for_repeated_steps() {
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = _credential_def();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+
+        let mut issuance_session = IssuanceSession::new();
+        let mut request_session = CredentialRequestSession::new(PROVER_ID);
+
+        let offer = issuance_session.offer(&cred_key_correctness_proof).unwrap();
+        assert!(issuance_session.offer(&cred_key_correctness_proof).is_err());
+
+        let request = request_session.request(&offer, &cred_pub_key, &master_secret).unwrap();
+        assert!(request_session.request(&offer, &cred_pub_key, &master_secret).is_err());
+
+        issuance_session.issue(&request, &credential_values, &cred_pub_key, &cred_priv_key).unwrap();
+        assert!(issuance_session.issue(&request, &credential_values, &cred_pub_key, &cred_priv_key).is_err());
+    }
+
+    #[test]
+    fn credential_request_session_process_works_for_another_offer() {
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = _credential_def();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+
+        let mut issuance_session = IssuanceSession::new();
+        let offer = issuance_session.offer(&cred_key_correctness_proof).unwrap();
+        let request = CredentialRequestSession::new(PROVER_ID).request(&offer, &cred_pub_key, &master_secret).unwrap();
+        let response = issuance_session.issue(&request, &credential_values, &cred_pub_key, &cred_priv_key).unwrap();
+
+        let mut request_session = CredentialRequestSession::new(PROVER_ID);
+        request_session.request(&offer, &cred_pub_key, &master_secret).unwrap();
+
+        assert!(request_session.process(response, &credential_values, &cred_pub_key, &master_secret, None, None, None).is_err());
+        assert!(!request_session.is_completed());
+    }
+}
@@ -1,12 +1,23 @@
+use accumulator;
 use bn::BigNumber;
+use bls::{Bls, SignKey};
 use cl::*;
 use errors::IndyCryptoError;
 use pair::*;
 use cl::constants::*;
 use cl::helpers::*;
+use cl::key_pool::{IssuerKeyPool, SafePrimePair};
+use cl::ps;
+use commitments::get_pedersen_commitment;
+use sigma::{BnGroup, Statement};
+use utils::drbg::with_seeded_rng;
+use utils::hash::ChallengeHasher;
+use utils::logger::OperationTimer;
 
 use std::collections::{BTreeMap, HashSet};
 
+const SIGNING_SEED_DOMAIN: &'static [u8] = b"indy-crypto:cl:signing-seed";
+
 /// Trust source that provides credentials to prover.
 pub struct Issuer {}
 
@@ -53,29 +64,355 @@ impl Issuer {
                                                                    CredentialKeyCorrectnessProof), IndyCryptoError> {
         trace!("Issuer::new_credential_def: >>> credential_schema: {:?}, support_revocation: {:?}", credential_schema, support_revocation);
 
-        let (p_pub_key, p_priv_key, p_key_meta) =
-            Issuer::_new_credential_primary_keys(credential_schema)?;
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, support_revocation, None, None)?;
 
-        let (r_pub_key, r_priv_key) = if support_revocation {
-            Issuer::_new_credential_revocation_keys()
-                .map(|(r_pub_key, r_priv_key)| (Some(r_pub_key), Some(r_priv_key)))?
-        } else {
-            (None, None)
+        trace!("Issuer::new_credential_def: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, cred_priv_key, cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    /// Creates and returns credential definition entities for selected signature backend.
+    /// CL backend gives the same entities as `Issuer::new_credential_def`.
+    /// PS backend gives much smaller keys and proofs, but doesn't support predicates and revocation.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated (CL backend only).
+    /// * `backend` - Signature backend.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{CredentialDefinition, CredentialSignatureBackend};
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let credential_def = Issuer::new_credential_def_with_backend(&credential_schema, false, CredentialSignatureBackend::PS).unwrap();
+    /// assert_eq!(CredentialSignatureBackend::PS, credential_def.backend());
+    /// ```
+    pub fn new_credential_def_with_backend(credential_schema: &CredentialSchema,
+                                           support_revocation: bool,
+                                           backend: CredentialSignatureBackend) -> Result<CredentialDefinition, IndyCryptoError> {
+        trace!("Issuer::new_credential_def_with_backend: >>> credential_schema: {:?}, support_revocation: {:?}, backend: {:?}",
+               credential_schema, support_revocation, backend);
+
+        let credential_def = match backend {
+            CredentialSignatureBackend::CL => {
+                let (pub_key, priv_key, key_correctness_proof) = Issuer::new_credential_def(credential_schema, support_revocation)?;
+                CredentialDefinition::CL { pub_key, priv_key, key_correctness_proof }
+            }
+            CredentialSignatureBackend::PS => {
+                if support_revocation {
+                    return Err(IndyCryptoError::InvalidParam2(format!("PS credentials don't support revocation")));
+                }
+
+                let (pub_key, priv_key) = ps::new_keys(credential_schema)?;
+                CredentialDefinition::PS { pub_key, priv_key }
+            }
         };
 
-        let cred_pub_key = CredentialPublicKey { p_key: p_pub_key, r_key: r_pub_key };
-        let cred_priv_key = CredentialPrivateKey { p_key: p_priv_key, r_key: r_priv_key };
-        let cred_key_correctness_proof =
-            Issuer::_new_credential_key_correctness_proof(&cred_pub_key.p_key,
-                                                          &cred_priv_key.p_key,
-                                                          &p_key_meta)?;
+        trace!("Issuer::new_credential_def_with_backend: <<< credential_def: {:?}", credential_def);
 
-        trace!("Issuer::new_credential_def: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+        Ok(credential_def)
+    }
+
+    /// Creates and returns credential definition entities with sizes of modulus and other
+    /// security parameters defined by the profile. Profile is stored in public key,
+    /// so prover and verifier math uses the same sizes.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    /// * `profile` - Security profile.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::SecurityProfile;
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_with_profile(&credential_schema, false, SecurityProfile::modulus_2048()).unwrap();
+    /// assert_eq!(2048, cred_pub_key.get_primary_key().unwrap().security_profile().unwrap().modulus_bits());
+    /// ```
+    pub fn new_credential_def_with_profile(credential_schema: &CredentialSchema,
+                                           support_revocation: bool,
+                                           profile: SecurityProfile) -> Result<(CredentialPublicKey,
+                                                                                CredentialPrivateKey,
+                                                                                CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_with_profile: >>> credential_schema: {:?}, support_revocation: {:?}, profile: {:?}",
+               credential_schema, support_revocation, profile);
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, support_revocation, None, Some(profile))?;
+
+        trace!("Issuer::new_credential_def_with_profile: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, cred_priv_key, cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    /// Creates and returns credential definition entities for credentials without master secret.
+    /// Such credentials are bound to devices instead of holders, so prover doesn't blind master secret
+    /// during issuance and proofs for them don't prove knowledge of master secret.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("serial").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_without_master_secret(&credential_schema, false).unwrap();
+    /// assert!(!cred_pub_key.requires_master_secret());
+    /// ```
+    pub fn new_credential_def_without_master_secret(credential_schema: &CredentialSchema,
+                                                    support_revocation: bool) -> Result<(CredentialPublicKey,
+                                                                                         CredentialPrivateKey,
+                                                                                         CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_without_master_secret: >>> credential_schema: {:?}, support_revocation: {:?}",
+               credential_schema, support_revocation);
+
+        let (mut cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, support_revocation, None, None)?;
+
+        cred_pub_key.p_key.without_master_secret = true;
+
+        trace!("Issuer::new_credential_def_without_master_secret: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, cred_priv_key, cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    /// Creates and returns credential definition entities using safe primes taken from the pool.
+    /// Falls back to safe primes generation if the pool is empty.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    /// * `key_pool` - Pool of pregenerated safe prime pairs.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::key_pool::IssuerKeyPool;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let key_pool = IssuerKeyPool::new(1).unwrap();
+    /// key_pool.fill().unwrap();
+    ///
+    /// let (_cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_from_pool(&credential_schema, false, &key_pool).unwrap();
+    /// assert!(key_pool.is_empty());
+    /// ```
+    pub fn new_credential_def_from_pool(credential_schema: &CredentialSchema,
+                                        support_revocation: bool,
+                                        key_pool: &IssuerKeyPool) -> Result<(CredentialPublicKey,
+                                                                             CredentialPrivateKey,
+                                                                             CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_from_pool: >>> credential_schema: {:?}, support_revocation: {:?}, key_pool len: {:?}",
+               credential_schema, support_revocation, key_pool.len());
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, support_revocation, key_pool.take(), None)?;
+
+        trace!("Issuer::new_credential_def_from_pool: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, cred_priv_key, cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    /// Creates and returns credential definition (public and private keys, correctness proof) entities
+    /// with all randomness derived from the seed. The same seed and schema always give the same keys,
+    /// so keys can be restored from the seed backup.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `support_revocation` - If true non revocation part of credential keys will be generated.
+    /// * `seed` - Secret seed of at least 16 bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (_cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_with_seed(&credential_schema, false, b"0123456789abcdef0123456789abcdef").unwrap();
+    /// ```
+    pub fn new_credential_def_with_seed(credential_schema: &CredentialSchema,
+                                        support_revocation: bool,
+                                        seed: &[u8]) -> Result<(CredentialPublicKey,
+                                                                CredentialPrivateKey,
+                                                                CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_with_seed: >>> credential_schema: {:?}, support_revocation: {:?}", credential_schema, support_revocation);
+
+        let res = with_seeded_rng(seed, || Issuer::new_credential_def(credential_schema, support_revocation))?;
+
+        trace!("Issuer::new_credential_def_with_seed: <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    /// Extends credential definition with new attributes without keys rotation.
+    /// Returned public key contains the same modulus and bases for existing attributes,
+    /// so credentials issued before extension stay valid for original schema.
+    /// Returned correctness proof contains original proof and proof for new attributes bases.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key entity.
+    /// * `credential_priv_key` - Credential private key entity.
+    /// * `credential_key_correctness_proof` - Credential key correctness proof entity.
+    /// * `new_attrs` - Schema with attributes to add.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let mut new_attrs_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// new_attrs_builder.add_attr("sex").unwrap();
+    /// let new_attrs = new_attrs_builder.finalize().unwrap();
+    ///
+    /// let (_cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::extend_credential_def(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &new_attrs).unwrap();
+    /// ```
+    pub fn extend_credential_def(credential_pub_key: &CredentialPublicKey,
+                                 credential_priv_key: &CredentialPrivateKey,
+                                 credential_key_correctness_proof: &CredentialKeyCorrectnessProof,
+                                 new_attrs: &CredentialSchema) -> Result<(CredentialPublicKey,
+                                                                          CredentialPrivateKey,
+                                                                          CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::extend_credential_def: >>> credential_pub_key: {:?}, credential_priv_key: {:?}, \
+               credential_key_correctness_proof: {:?}, new_attrs: {:?}",
+               credential_pub_key, credential_priv_key, credential_key_correctness_proof, new_attrs);
+
+        if new_attrs.attrs.len() == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of attributes is empty")));
+        }
+
+        check_attrs_count(credential_pub_key.p_key.r.len() + new_attrs.attrs.len())?;
+
+        let mut ctx = BigNumber::new_context()?;
+        let mut p_pub_key = credential_pub_key.p_key.clone()?;
+        let p_priv_key = &credential_priv_key.p_key;
+
+        let mut attrs: Vec<&String> = new_attrs.attrs.iter().collect();
+        attrs.sort();
+
+        let mut xr = BTreeMap::new();
+        for attribute in attrs {
+            if p_pub_key.r.contains_key(attribute) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' already exists in credential public key", attribute)));
+            }
+            xr.insert(attribute.to_string(), gen_x(&p_priv_key.p, &p_priv_key.q)?);
+        }
+
+        for (key, xr_value) in xr.iter() {
+            p_pub_key.r.insert(key.to_string(), p_pub_key.s.mod_exp(&xr_value, &p_pub_key.n, Some(&mut ctx))?);
+        }
+
+        let extension_proof = Issuer::_new_credential_key_extension_proof(&p_pub_key, p_priv_key, &xr)?;
+
+        let mut cred_key_correctness_proof = credential_key_correctness_proof.clone()?;
+        cred_key_correctness_proof.extensions.push(extension_proof);
+
+        let cred_pub_key = CredentialPublicKey { p_key: p_pub_key, r_key: credential_pub_key.r_key.clone() };
+        let cred_priv_key = credential_priv_key.clone()?;
+
+        trace!("Issuer::extend_credential_def: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
                cred_pub_key, cred_priv_key, cred_key_correctness_proof);
 
         Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
     }
 
+    /// Creates new credential definition that replaces existing one and statement linking old and new keys.
+    /// Linkage statement refers to keys by their fingerprints and is signed by Issuer BLS key,
+    /// so verifiers that trust Issuer verification key can accept proofs created with old key until the end of grace period.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `credential_pub_key` - Credential public key to replace.
+    /// * `sign_key` - Issuer BLS sign key.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    /// * `grace_period_end` - Timestamp (seconds since epoch) until proofs created with old key are accepted.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bls::{Generator, SignKey, VerKey};
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    ///
+    /// let (_new_cred_pub_key, _new_cred_priv_key, _new_cred_key_correctness_proof, key_rotation) =
+    ///     Issuer::rotate_credential_def(&credential_schema, &cred_pub_key, &sign_key, false, 1514764800).unwrap();
+    /// assert!(key_rotation.verify_signature(&ver_key, &gen).unwrap());
+    /// ```
+    pub fn rotate_credential_def(credential_schema: &CredentialSchema,
+                                 credential_pub_key: &CredentialPublicKey,
+                                 sign_key: &SignKey,
+                                 support_revocation: bool,
+                                 grace_period_end: u64) -> Result<(CredentialPublicKey,
+                                                                   CredentialPrivateKey,
+                                                                   CredentialKeyCorrectnessProof,
+                                                                   CredentialKeyRotation), IndyCryptoError> {
+        trace!("Issuer::rotate_credential_def: >>> credential_schema: {:?}, credential_pub_key: {:?}, \
+               support_revocation: {:?}, grace_period_end: {:?}",
+               credential_schema, credential_pub_key, support_revocation, grace_period_end);
+
+        // New key keeps security profile of the old one
+        let (new_cred_pub_key, new_cred_priv_key, new_cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, support_revocation, None, credential_pub_key.p_key.profile)?;
+
+        let old_key_id = credential_pub_key.fingerprint()?;
+        let new_key_id = new_cred_pub_key.fingerprint()?;
+
+        let signature = Bls::sign(&CredentialKeyRotation::_signed_bytes(&old_key_id, &new_key_id, grace_period_end), sign_key)?;
+
+        let key_rotation = CredentialKeyRotation {
+            old_key_id,
+            new_key_id,
+            grace_period_end,
+            signature: signature.as_bytes().to_vec()
+        };
+
+        trace!("Issuer::rotate_credential_def: <<< new_cred_pub_key: {:?}, new_cred_priv_key: {:?}, new_cred_key_correctness_proof: {:?}, key_rotation: {:?}",
+               new_cred_pub_key, new_cred_priv_key, new_cred_key_correctness_proof, key_rotation);
+
+        Ok((new_cred_pub_key, new_cred_priv_key, new_cred_key_correctness_proof, key_rotation))
+    }
+
     /// Creates and returns revocation registry definition (public and private keys, accumulator and tails generator) entities.
     ///
     /// # Arguments
@@ -99,7 +436,7 @@ impl Issuer {
     /// let (_rev_key_pub, _rev_key_priv, _rev_reg, _rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, 5, false).unwrap();
     /// ```
     pub fn new_revocation_registry_def(credential_pub_key: &CredentialPublicKey,
-                                       max_cred_num: u32,
+                                       max_cred_num: u64,
                                        issuance_by_default: bool) -> Result<(RevocationKeyPublic,
                                                                              RevocationKeyPrivate,
                                                                              RevocationRegistry,
@@ -107,28 +444,100 @@ impl Issuer {
         trace!("Issuer::new_revocation_registry_def: >>> credential_pub_key: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}",
                credential_pub_key, max_cred_num, issuance_by_default);
 
-        let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
-            .as_ref()
-            .ok_or(IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
-
-        let (rev_key_pub, rev_key_priv) = Issuer::_new_revocation_registry_keys(cred_rev_pub_key, max_cred_num)?;
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) =
+            Issuer::_new_revocation_registry_def(credential_pub_key, max_cred_num, issuance_by_default, RevocationRegistryMode::Membership)?;
 
-        let rev_reg = Issuer::_new_revocation_registry(cred_rev_pub_key,
-                                                       &rev_key_priv,
-                                                       max_cred_num,
-                                                       issuance_by_default)?;
+        trace!("Issuer::new_revocation_registry_def: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
+               rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
 
-        let rev_tails_generator = RevocationTailsGenerator::new(
-            max_cred_num,
-            rev_key_priv.gamma.clone(),
-            cred_rev_pub_key.g_dash.clone());
+        Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator))
+    }
 
-        trace!("Issuer::new_revocation_registry_def: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
+    /// Creates and returns revocation registry definition which accumulator records revoked indices only.
+    ///
+    /// Every index is issued from the start and accumulator is initially empty.
+    /// Revocation adds index to accumulator, so size of revocation registry deltas depends only on
+    /// number of revocations. Prover proves that credential index is NOT accumulated with non-membership witness.
+    ///
+    /// Such registries can't be split or merged.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key entity.
+    /// * `max_cred_num` - Max credential number in generated registry.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::RevocationRegistryMode;
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+    ///
+    /// let (rev_key_pub, _rev_key_priv, rev_reg, _rev_tails_generator) =
+    ///     Issuer::new_non_membership_revocation_registry_def(&cred_pub_key, 5).unwrap();
+    ///
+    /// assert_eq!(RevocationRegistryMode::NonMembership, rev_key_pub.mode());
+    /// assert_eq!(RevocationRegistryMode::NonMembership, rev_reg.mode());
+    /// ```
+    pub fn new_non_membership_revocation_registry_def(credential_pub_key: &CredentialPublicKey,
+                                                      max_cred_num: u64) -> Result<(RevocationKeyPublic,
+                                                                                    RevocationKeyPrivate,
+                                                                                    RevocationRegistry,
+                                                                                    RevocationTailsGenerator), IndyCryptoError> {
+        trace!("Issuer::new_non_membership_revocation_registry_def: >>> credential_pub_key: {:?}, max_cred_num: {:?}",
+               credential_pub_key, max_cred_num);
+
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) =
+            Issuer::_new_revocation_registry_def(credential_pub_key, max_cred_num, false, RevocationRegistryMode::NonMembership)?;
+
+        trace!("Issuer::new_non_membership_revocation_registry_def: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
                rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
 
         Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator))
     }
 
+    /// Creates offer of credential signed with given credential key.
+    /// Offer contains fresh nonce, so Issuer must keep it until credential request is received.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_key_correctness_proof` - Credential key correctness proof.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, _credential_priv_key, credential_key_correctness_proof) =
+    ///     Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let credential_offer = Issuer::new_credential_offer(&credential_pub_key, &credential_key_correctness_proof).unwrap();
+    /// Prover::check_credential_offer(&credential_offer, &credential_pub_key).unwrap();
+    /// ```
+    pub fn new_credential_offer(credential_pub_key: &CredentialPublicKey,
+                                credential_key_correctness_proof: &CredentialKeyCorrectnessProof) -> Result<CredentialOffer, IndyCryptoError> {
+        trace!("Issuer::new_credential_offer: >>> credential_pub_key: {:?}, credential_key_correctness_proof: {:?}",
+               credential_pub_key, credential_key_correctness_proof);
+
+        let credential_offer = CredentialOffer {
+            key_fingerprint: credential_pub_key.fingerprint()?,
+            key_correctness_proof: credential_key_correctness_proof.clone()?,
+            nonce: new_nonce()?
+        };
+
+        trace!("Issuer::new_credential_offer: <<< credential_offer: {:?}", credential_offer);
+
+        Ok(credential_offer)
+    }
+
     /// Creates and returns credential values entity builder.
     ///
     /// The purpose of credential values builder is building of credential values entity that
@@ -206,28 +615,16 @@ impl Issuer {
                prover_id, blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_values, credential_issuance_nonce,
                credential_pub_key, credential_priv_key);
 
-        Issuer::_check_blinded_master_secret_correctness_proof(blinded_master_secret,
-                                                               blinded_master_secret_correctness_proof,
-                                                               master_secret_blinding_nonce,
-                                                               &credential_pub_key.p_key)?;
-
-        // In the anoncreds whitepaper, `credential context` is denoted by `m2`
-        let cred_context = Issuer::_gen_credential_context(prover_id, None)?;
-
-        let (p_cred, q) = Issuer::_new_primary_credential(&cred_context,
-                                                          credential_pub_key,
-                                                          credential_priv_key,
-                                                          blinded_master_secret,
-                                                          credential_values)?;
-
-        let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: None };
-
-        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(&credential_pub_key.p_key,
-                                                                                   &credential_priv_key.p_key,
-                                                                                   &cred_signature.p_credential,
-                                                                                   &q,
-                                                                                   credential_issuance_nonce)?;
+        let _timer = OperationTimer::start("Issuer::sign_credential");
 
+        let (cred_signature, signature_correctness_proof) = Issuer::_sign_credential(prover_id,
+                                                                                    blinded_master_secret,
+                                                                                    blinded_master_secret_correctness_proof,
+                                                                                    master_secret_blinding_nonce,
+                                                                                    credential_issuance_nonce,
+                                                                                    credential_values,
+                                                                                    &credential_pub_key.p_key,
+                                                                                    credential_priv_key)?;
 
         trace!("Issuer::sign_credential: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
                cred_signature, signature_correctness_proof);
@@ -235,7 +632,10 @@ impl Issuer {
         Ok((cred_signature, signature_correctness_proof))
     }
 
-    /// Signs credential values with both primary and revocation keys.
+    /// Signs credential values with primary keys only like `sign_credential`, but derives all randomness
+    /// of signature (`v`, `e`) and signature correctness proof from credential private key, prover id,
+    /// blinded master secret, credential values and nonces. Retried issuance of the same credential
+    /// gives bit-identical signature instead of a duplicate with different randomness.
     ///
     /// # Arguments
     /// * `prover_id` - Prover identifier.
@@ -246,17 +646,178 @@ impl Issuer {
     /// * `credential_values` - Claim values to be signed.
     /// * `credential_pub_key` - Credential public key.
     /// * `credential_priv_key` - Credential private key.
-    /// * `rev_idx` - User index in revocation accumulator. Required for non-revocation credential_signature part generation.
-    /// * `max_cred_num` - Max credential number in generated registry.
-    /// * `rev_reg` - Revocation registry.
-    /// * `rev_key_priv` - Revocation registry private key.
-    /// * `rev_tails_accessor` - Revocation registry tails accessor.
-    ///
-    /// # Example
-    /// ```
-    /// use indy_crypto::cl::{new_nonce, SimpleTailsAccessor};
-    /// use indy_crypto::cl::issuer::Issuer;
-    /// use indy_crypto::cl::prover::Prover;
+    pub fn sign_credential_deterministic(prover_id: &str,
+                                         blinded_master_secret: &BlindedMasterSecret,
+                                         blinded_master_secret_correctness_proof: &BlindedMasterSecretCorrectnessProof,
+                                         master_secret_blinding_nonce: &Nonce,
+                                         credential_issuance_nonce: &Nonce,
+                                         credential_values: &CredentialValues,
+                                         credential_pub_key: &CredentialPublicKey,
+                                         credential_priv_key: &CredentialPrivateKey) -> Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::sign_credential_deterministic: >>> prover_id: {:?}, blinded_master_secret: {:?}, blinded_master_secret_correctness_proof: {:?},\
+        master_secret_blinding_nonce: {:?}, credential_issuance_nonce: {:?}, credential_values: {:?}, credential_pub_key: {:?}, credential_priv_key: {:?}",
+               prover_id, blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_issuance_nonce,
+               credential_values, credential_pub_key, credential_priv_key);
+
+        let seed = Issuer::_gen_signing_seed(prover_id,
+                                             blinded_master_secret,
+                                             master_secret_blinding_nonce,
+                                             credential_issuance_nonce,
+                                             credential_values,
+                                             credential_priv_key)?;
+
+        let (cred_signature, signature_correctness_proof) =
+            with_seeded_rng(&seed, || Issuer::_sign_credential(prover_id,
+                                                               blinded_master_secret,
+                                                               blinded_master_secret_correctness_proof,
+                                                               master_secret_blinding_nonce,
+                                                               credential_issuance_nonce,
+                                                               credential_values,
+                                                               &credential_pub_key.p_key,
+                                                               credential_priv_key))?;
+
+        trace!("Issuer::sign_credential_deterministic: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
+               cred_signature, signature_correctness_proof);
+
+        Ok((cred_signature, signature_correctness_proof))
+    }
+
+    /// Signs credential values and blinded master secret with PS credential private key.
+    ///
+    /// # Arguments
+    /// * `blinded_master_secret` - Blinded master secret created by `Prover::blind_master_secret_ps`.
+    /// * `blinded_master_secret_correctness_proof` - Blinded master secret correctness proof.
+    /// * `master_secret_blinding_nonce` - Nonce used for verification of blinded_master_secret_correctness_proof.
+    /// * `credential_values` - Claim values to be signed, must be less than curve group order.
+    /// * `credential_pub_key` - PS credential public key.
+    /// * `credential_priv_key` - PS credential private key.
+    pub fn sign_ps_credential(blinded_master_secret: &PsBlindedMasterSecret,
+                              blinded_master_secret_correctness_proof: &PsBlindedMasterSecretCorrectnessProof,
+                              master_secret_blinding_nonce: &Nonce,
+                              credential_values: &CredentialValues,
+                              credential_pub_key: &PsCredentialPublicKey,
+                              credential_priv_key: &PsCredentialPrivateKey) -> Result<PsCredentialSignature, IndyCryptoError> {
+        trace!("Issuer::sign_ps_credential: >>> blinded_master_secret: {:?}, blinded_master_secret_correctness_proof: {:?}, \
+               master_secret_blinding_nonce: {:?}, credential_values: {:?}, credential_pub_key: {:?}",
+               blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_values, credential_pub_key);
+
+        let credential_signature = ps::sign(blinded_master_secret,
+                                            blinded_master_secret_correctness_proof,
+                                            master_secret_blinding_nonce,
+                                            credential_values,
+                                            credential_pub_key,
+                                            credential_priv_key)?;
+
+        trace!("Issuer::sign_ps_credential: <<< credential_signature: {:?}", credential_signature);
+
+        Ok(credential_signature)
+    }
+
+    /// Signs credential values with primary keys only using precomputed exponentiation tables of credential public key.
+    /// Produces the same signature as `sign_credential`, but faster for issuers signing many credentials.
+    ///
+    /// # Arguments
+    /// * `prover_id` - Prover identifier.
+    /// * `blinded_master_secret` - Blinded master secret generated by Prover.
+    /// * `blinded_master_secret_correctness_proof` - Blinded master secret correctness proof.
+    /// * `master_secret_blinding_nonce` - Nonce used for verification of blinded_master_secret_correctness_proof.
+    /// * `credential_issuance_nonce` - Nonce used for creation of signature_correctness_proof.
+    /// * `credential_values` - Claim values to be signed.
+    /// * `credential_pub_key_precomputed` - Credential public key with precomputed exponentiation tables.
+    /// * `credential_priv_key` - Credential private key.
+    pub fn sign_credential_with_precomputed_key(prover_id: &str,
+                                                blinded_master_secret: &BlindedMasterSecret,
+                                                blinded_master_secret_correctness_proof: &BlindedMasterSecretCorrectnessProof,
+                                                master_secret_blinding_nonce: &Nonce,
+                                                credential_issuance_nonce: &Nonce,
+                                                credential_values: &CredentialValues,
+                                                credential_pub_key_precomputed: &CredentialPublicKeyPrecomputed,
+                                                credential_priv_key: &CredentialPrivateKey) -> Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::sign_credential_with_precomputed_key: >>> prover_id: {:?}, blinded_master_secret: {:?}, blinded_master_secret_correctness_proof: {:?},\
+        master_secret_blinding_nonce: {:?}, credential_issuance_nonce: {:?}, credential_values: {:?}, credential_pub_key: {:?}, credential_priv_key: {:?}",
+               prover_id, blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_values, credential_issuance_nonce,
+               credential_pub_key_precomputed.pub_key(), credential_priv_key);
+
+        let (cred_signature, signature_correctness_proof) = Issuer::_sign_credential(prover_id,
+                                                                                    blinded_master_secret,
+                                                                                    blinded_master_secret_correctness_proof,
+                                                                                    master_secret_blinding_nonce,
+                                                                                    credential_issuance_nonce,
+                                                                                    credential_values,
+                                                                                    credential_pub_key_precomputed,
+                                                                                    credential_priv_key)?;
+
+        trace!("Issuer::sign_credential_with_precomputed_key: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
+               cred_signature, signature_correctness_proof);
+
+        Ok((cred_signature, signature_correctness_proof))
+    }
+
+    /// Signs credential values with primary keys only delegating private key operations to key provider.
+    /// Allows to sign credentials without primary private key in the process memory (HSM, secure enclave).
+    ///
+    /// # Arguments
+    /// * `prover_id` - Prover identifier.
+    /// * `blinded_master_secret` - Blinded master secret generated by Prover.
+    /// * `blinded_master_secret_correctness_proof` - Blinded master secret correctness proof.
+    /// * `master_secret_blinding_nonce` - Nonce used for verification of blinded_master_secret_correctness_proof.
+    /// * `credential_issuance_nonce` - Nonce used for creation of signature_correctness_proof.
+    /// * `credential_values` - Claim values to be signed.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `key_provider` - Provider of credential primary private key operations.
+    pub fn sign_credential_with_key_provider(prover_id: &str,
+                                             blinded_master_secret: &BlindedMasterSecret,
+                                             blinded_master_secret_correctness_proof: &BlindedMasterSecretCorrectnessProof,
+                                             master_secret_blinding_nonce: &Nonce,
+                                             credential_issuance_nonce: &Nonce,
+                                             credential_values: &CredentialValues,
+                                             credential_pub_key: &CredentialPublicKey,
+                                             key_provider: &PrivateKeyProvider) -> Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::sign_credential_with_key_provider: >>> prover_id: {:?}, blinded_master_secret: {:?}, blinded_master_secret_correctness_proof: {:?},\
+        master_secret_blinding_nonce: {:?}, credential_issuance_nonce: {:?}, credential_values: {:?}, credential_pub_key: {:?}",
+               prover_id, blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_values, credential_issuance_nonce,
+               credential_pub_key);
+
+        let (cred_signature, signature_correctness_proof) = Issuer::_sign_credential(prover_id,
+                                                                                    blinded_master_secret,
+                                                                                    blinded_master_secret_correctness_proof,
+                                                                                    master_secret_blinding_nonce,
+                                                                                    credential_issuance_nonce,
+                                                                                    credential_values,
+                                                                                    &credential_pub_key.p_key,
+                                                                                    key_provider)?;
+
+        trace!("Issuer::sign_credential_with_key_provider: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
+               cred_signature, signature_correctness_proof);
+
+        Ok((cred_signature, signature_correctness_proof))
+    }
+
+    /// Signs credential values with both primary and revocation keys.
+    ///
+    /// For registries created by `new_non_membership_revocation_registry_def` issuance doesn't change
+    /// accumulator, so no revocation registry delta is returned regardless of `issuance_by_default`.
+    ///
+    /// # Arguments
+    /// * `prover_id` - Prover identifier.
+    /// * `blinded_master_secret` - Blinded master secret generated by Prover.
+    /// * `blinded_master_secret_correctness_proof` - Blinded master secret correctness proof.
+    /// * `master_secret_blinding_nonce` - Nonce used for verification of blinded_master_secret_correctness_proof.
+    /// * `credential_issuance_nonce` - Nonce used for creation of signature_correctness_proof.
+    /// * `credential_values` - Claim values to be signed.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_priv_key` - Credential private key.
+    /// * `rev_idx` - User index in revocation accumulator. Required for non-revocation credential_signature part generation.
+    /// * `max_cred_num` - Max credential number in generated registry.
+    /// * `rev_reg` - Revocation registry.
+    /// * `rev_key_priv` - Revocation registry private key.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{new_nonce, SimpleTailsAccessor};
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
     ///
     /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
     /// credential_schema_builder.add_attr("name").unwrap();
@@ -306,8 +867,8 @@ impl Issuer {
                                            credential_values: &CredentialValues,
                                            credential_pub_key: &CredentialPublicKey,
                                            credential_priv_key: &CredentialPrivateKey,
-                                           rev_idx: u32,
-                                           max_cred_num: u32,
+                                           rev_idx: u64,
+                                           max_cred_num: u64,
                                            issuance_by_default: bool,
                                            rev_reg: &mut RevocationRegistry,
                                            rev_key_priv: &RevocationKeyPrivate,
@@ -320,6 +881,8 @@ impl Issuer {
                prover_id, blinded_master_secret, blinded_master_secret_correctness_proof, master_secret_blinding_nonce, credential_values, credential_issuance_nonce,
                credential_pub_key, credential_priv_key, rev_idx, max_cred_num, rev_reg, rev_key_priv);
 
+        check_rev_idx(rev_idx, max_cred_num)?;
+
         Issuer::_check_blinded_master_secret_correctness_proof(blinded_master_secret,
                                                                blinded_master_secret_correctness_proof,
                                                                master_secret_blinding_nonce,
@@ -329,7 +892,7 @@ impl Issuer {
         let cred_context = Issuer::_gen_credential_context(prover_id, Some(rev_idx))?;
 
         let (p_cred, q) = Issuer::_new_primary_credential(&cred_context,
-                                                          credential_pub_key,
+                                                          &credential_pub_key.p_key,
                                                           credential_priv_key,
                                                           blinded_master_secret,
                                                           credential_values)?;
@@ -348,7 +911,7 @@ impl Issuer {
         let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: Some(r_cred) };
 
         let signature_correctness_proof = Issuer::_new_signature_correctness_proof(&credential_pub_key.p_key,
-                                                                                   &credential_priv_key.p_key,
+                                                                                   credential_priv_key,
                                                                                    &cred_signature.p_credential,
                                                                                    &q,
                                                                                    credential_issuance_nonce)?;
@@ -417,24 +980,29 @@ impl Issuer {
     /// Issuer::revoke_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
     /// ```
     pub fn revoke_credential<RTA>(rev_reg: &mut RevocationRegistry,
-                                  max_cred_num: u32,
-                                  rev_idx: u32,
+                                  max_cred_num: u64,
+                                  rev_idx: u64,
                                   rev_tails_accessor: &RTA) -> Result<RevocationRegistryDelta, IndyCryptoError> where RTA: RevocationTailsAccessor {
         trace!("Issuer::revoke_credential: >>> rev_reg: {:?}, max_cred_num: {:?}, rev_idx: {:?}", rev_reg, max_cred_num, rev_idx);
 
-        let prev_accum = rev_reg.accum.clone();
+        check_rev_idx(rev_idx, max_cred_num)?;
 
-        let index = Issuer::_get_index(max_cred_num, rev_idx);
+        let prev_accum = rev_reg.accum.clone();
 
-        rev_tails_accessor.access_tail(index, &mut |tail| {
-            rev_reg.accum = rev_reg.accum.sub(tail).unwrap();
-        })?;
+        match rev_reg.mode {
+            RevocationRegistryMode::Membership =>
+                accumulator::remove_element(&mut rev_reg.accum, rev_idx, max_cred_num, rev_tails_accessor)?,
+            RevocationRegistryMode::NonMembership =>
+                accumulator::add_element(&mut rev_reg.accum, rev_idx, max_cred_num, rev_tails_accessor)?
+        }
 
         let rev_reg_delta = RevocationRegistryDelta {
             prev_accum: Some(prev_accum),
             accum: rev_reg.accum.clone(),
             issued: HashSet::new(),
-            revoked: hashset![rev_idx]
+            revoked: hashset![rev_idx],
+            signature: None,
+            mode: rev_reg.mode
         };
 
         trace!("Issuer::revoke_credential: <<< rev_reg_delta: {:?}", rev_reg_delta);
@@ -500,24 +1068,29 @@ impl Issuer {
     /// Issuer::recovery_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
     /// ```
     pub fn recovery_credential<RTA>(rev_reg: &mut RevocationRegistry,
-                                    max_cred_num: u32,
-                                    rev_idx: u32,
+                                    max_cred_num: u64,
+                                    rev_idx: u64,
                                     rev_tails_accessor: &RTA) -> Result<RevocationRegistryDelta, IndyCryptoError> where RTA: RevocationTailsAccessor {
         trace!("Issuer::recovery_credential: >>> rev_reg: {:?}, max_cred_num: {:?}, rev_idx: {:?}", rev_reg, max_cred_num, rev_idx);
 
-        let prev_accum = rev_reg.accum.clone();
+        check_rev_idx(rev_idx, max_cred_num)?;
 
-        let index = Issuer::_get_index(max_cred_num, rev_idx);
+        let prev_accum = rev_reg.accum.clone();
 
-        rev_tails_accessor.access_tail(index, &mut |tail| {
-            rev_reg.accum = rev_reg.accum.add(tail).unwrap();
-        })?;
+        match rev_reg.mode {
+            RevocationRegistryMode::Membership =>
+                accumulator::add_element(&mut rev_reg.accum, rev_idx, max_cred_num, rev_tails_accessor)?,
+            RevocationRegistryMode::NonMembership =>
+                accumulator::remove_element(&mut rev_reg.accum, rev_idx, max_cred_num, rev_tails_accessor)?
+        }
 
         let rev_reg_delta = RevocationRegistryDelta {
             prev_accum: Some(prev_accum),
             accum: rev_reg.accum.clone(),
             issued: hashset![rev_idx],
-            revoked: HashSet::new()
+            revoked: HashSet::new(),
+            signature: None,
+            mode: rev_reg.mode
         };
 
         trace!("Issuer::recovery_credential: <<< rev_reg_delta: {:?}", rev_reg_delta);
@@ -525,10 +1098,489 @@ impl Issuer {
         Ok(rev_reg_delta)
     }
 
-    fn _new_credential_primary_keys(credential_schema: &CredentialSchema) -> Result<(CredentialPrimaryPublicKey,
-                                                                                     CredentialPrimaryPrivateKey,
-                                                                                     CredentialPrimaryPublicKeyMetadata), IndyCryptoError> {
-        trace!("Issuer::_new_credential_primary_keys: >>> credential_schema: {:?}", credential_schema);
+    /// Moves credentials with given indices from revocation registry into new registry.
+    /// New registry shares revocation keys and tails with the original one, so moved credentials keep
+    /// their revocation indices and signatures. Moved indices are removed from original registry.
+    ///
+    /// Returns new registry, delta of original registry (for holders of remaining credentials)
+    /// and migration delta for holders of moved credentials (see `Witness::translate`).
+    ///
+    /// # Arguments
+    /// * `rev_reg` - Revocation registry.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `issued` - Indices issued in revocation registry.
+    /// * `moved` - Indices to move into new registry. Must be issued.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn split_revocation_registry<RTA>(rev_reg: &mut RevocationRegistry,
+                                          max_cred_num: u64,
+                                          issued: &HashSet<u64>,
+                                          moved: &HashSet<u64>,
+                                          rev_tails_accessor: &RTA) -> Result<(RevocationRegistry,
+                                                                               RevocationRegistryDelta,
+                                                                               RevocationRegistryDelta), IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Issuer::split_revocation_registry: >>> rev_reg: {:?}, max_cred_num: {:?}, issued: {:?}, moved: {:?}",
+               rev_reg, max_cred_num, issued, moved);
+
+        if rev_reg.mode != RevocationRegistryMode::Membership {
+            return Err(IndyCryptoError::InvalidStructure(format!("Only membership revocation registries can be split")));
+        }
+
+        for rev_idx in issued.iter() {
+            check_rev_idx(*rev_idx, max_cred_num)?;
+        }
+
+        if !moved.is_subset(issued) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Moved indices must be issued in revocation registry")));
+        }
+
+        let prev_rev_reg = rev_reg.clone();
+
+        let mut moved_accum = accumulator::new_accumulator()?;
+        for rev_idx in moved.iter() {
+            accumulator::add_element(&mut moved_accum, *rev_idx, max_cred_num, rev_tails_accessor)?;
+        }
+
+        rev_reg.accum = rev_reg.accum.sub(&moved_accum)?;
+
+        let new_rev_reg = RevocationRegistry {
+            accum: moved_accum,
+            mode: RevocationRegistryMode::Membership
+        };
+
+        let remaining: HashSet<u64> = issued.difference(moved).cloned().collect();
+
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(Some(&prev_rev_reg), rev_reg, &HashSet::new(), moved);
+        let migration_delta = RevocationRegistryDelta::from_parts(Some(&prev_rev_reg), &new_rev_reg, &HashSet::new(), &remaining);
+
+        trace!("Issuer::split_revocation_registry: <<< new_rev_reg: {:?}, rev_reg_delta: {:?}, migration_delta: {:?}",
+               new_rev_reg, rev_reg_delta, migration_delta);
+
+        Ok((new_rev_reg, rev_reg_delta, migration_delta))
+    }
+
+    /// Merges revocation registry that shares revocation keys and tails with another one into it
+    /// (e.g. registries created by `split_revocation_registry`). Indices issued in registries must not intersect.
+    ///
+    /// Returns delta of merged registry for holders of its credentials
+    /// and migration delta for holders of credentials of other registry (see `Witness::translate`).
+    ///
+    /// # Arguments
+    /// * `rev_reg` - Revocation registry to merge other registry into.
+    /// * `issued` - Indices issued in revocation registry.
+    /// * `other_rev_reg` - Other revocation registry.
+    /// * `other_issued` - Indices issued in other revocation registry.
+    pub fn merge_revocation_registries(rev_reg: &mut RevocationRegistry,
+                                       issued: &HashSet<u64>,
+                                       other_rev_reg: &RevocationRegistry,
+                                       other_issued: &HashSet<u64>) -> Result<(RevocationRegistryDelta,
+                                                                               RevocationRegistryDelta), IndyCryptoError> {
+        trace!("Issuer::merge_revocation_registries: >>> rev_reg: {:?}, issued: {:?}, other_rev_reg: {:?}, other_issued: {:?}",
+               rev_reg, issued, other_rev_reg, other_issued);
+
+        if rev_reg.mode != RevocationRegistryMode::Membership || other_rev_reg.mode != RevocationRegistryMode::Membership {
+            return Err(IndyCryptoError::InvalidStructure(format!("Only membership revocation registries can be merged")));
+        }
+
+        if !issued.is_disjoint(other_issued) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Merged revocation registries contain the same indices")));
+        }
+
+        let prev_rev_reg = rev_reg.clone();
+
+        rev_reg.accum = rev_reg.accum.add(&other_rev_reg.accum)?;
+
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(Some(&prev_rev_reg), rev_reg, other_issued, &HashSet::new());
+        let migration_delta = RevocationRegistryDelta::from_parts(Some(other_rev_reg), rev_reg, issued, &HashSet::new());
+
+        trace!("Issuer::merge_revocation_registries: <<< rev_reg_delta: {:?}, migration_delta: {:?}", rev_reg_delta, migration_delta);
+
+        Ok((rev_reg_delta, migration_delta))
+    }
+
+    /// Creates larger revocation registry definition that contains credentials issued in revocation registry.
+    /// Credentials keep their revocation indices and signatures, as new registry is built with the same revocation private key.
+    ///
+    /// Tails of new registry disclose the secret part of original registry public key,
+    /// so original registry must be retired once new registry is published.
+    /// New max credential number must be greater than double original one, otherwise tails of original registry
+    /// disclose the secret part of new registry public key.
+    ///
+    /// Returns revocation key public, registry and tails generator of new registry
+    /// and migration delta describing full state of new registry (see `Witness::translate`).
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_key_priv` - Revocation key private of original registry.
+    /// * `max_cred_num` - Max credential number in original revocation registry.
+    /// * `issued` - Indices issued in original revocation registry.
+    /// * `new_max_cred_num` - Max credential number in new revocation registry.
+    pub fn migrate_revocation_registry_def(credential_pub_key: &CredentialPublicKey,
+                                           rev_key_priv: &RevocationKeyPrivate,
+                                           max_cred_num: u64,
+                                           issued: &HashSet<u64>,
+                                           new_max_cred_num: u64) -> Result<(RevocationKeyPublic,
+                                                                             RevocationRegistry,
+                                                                             RevocationTailsGenerator,
+                                                                             RevocationRegistryDelta), IndyCryptoError> {
+        trace!("Issuer::migrate_revocation_registry_def: >>> credential_pub_key: {:?}, rev_key_priv: {:?}, max_cred_num: {:?}, issued: {:?}, \
+        new_max_cred_num: {:?}", credential_pub_key, rev_key_priv, max_cred_num, issued, new_max_cred_num);
+
+        check_max_cred_num(max_cred_num)?;
+        check_max_cred_num(new_max_cred_num)?;
+
+        if new_max_cred_num <= 2 * max_cred_num {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("New max credential number must be greater than {}", 2 * max_cred_num)));
+        }
+
+        for rev_idx in issued.iter() {
+            check_rev_idx(*rev_idx, max_cred_num)?;
+        }
+
+        let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
+            .as_ref()
+            .ok_or(IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
+
+        let rev_key_pub = Issuer::_new_revocation_key_public(cred_rev_pub_key, &rev_key_priv.gamma, new_max_cred_num, RevocationRegistryMode::Membership)?;
+
+        let mut accum = accumulator::new_accumulator()?;
+        for rev_idx in issued.iter() {
+            let index = accumulator::tail_index(new_max_cred_num, *rev_idx);
+            accum = accum.add(&accumulator::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?)?;
+        }
+
+        let rev_reg = RevocationRegistry { accum, mode: RevocationRegistryMode::Membership };
+
+        let rev_tails_generator = RevocationTailsGenerator::new(
+            new_max_cred_num,
+            rev_key_priv.gamma.clone(),
+            cred_rev_pub_key.g_dash.clone());
+
+        let migration_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, issued, &HashSet::new());
+
+        trace!("Issuer::migrate_revocation_registry_def: <<< rev_key_pub: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}, migration_delta: {:?}",
+               rev_key_pub, rev_reg, rev_tails_generator, migration_delta);
+
+        Ok((rev_key_pub, rev_reg, rev_tails_generator, migration_delta))
+    }
+
+    /// Computes witnesses of several revocation indices in a single pass over tails.
+    /// Intended for witness services that maintain witnesses on behalf of many holders:
+    /// every tail is accessed once regardless of number of requested indices.
+    ///
+    /// Returns witnesses in the order of `rev_idxs`.
+    ///
+    /// # Arguments
+    /// * `rev_idxs` - Revocation indices witnesses are computed for.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `rev_reg_delta` - Revocation registry delta describing full registry state.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{RevocationRegistryDelta, SimpleTailsAccessor};
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+    ///
+    /// let max_cred_num = 5;
+    /// let (_rev_key_pub, _rev_key_priv, rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
+    ///
+    /// let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+    ///
+    /// let issued: HashSet<u64> = (1..max_cred_num + 1).collect();
+    /// let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &issued, &HashSet::new());
+    ///
+    /// let witnesses = Issuer::compute_witnesses_batch(&[1, 3, 5], max_cred_num, &rev_reg_delta, &simple_tail_accessor).unwrap();
+    /// assert_eq!(3, witnesses.len());
+    /// ```
+    pub fn compute_witnesses_batch<RTA>(rev_idxs: &[u64],
+                                        max_cred_num: u64,
+                                        rev_reg_delta: &RevocationRegistryDelta,
+                                        rev_tails_accessor: &RTA) -> Result<Vec<Witness>, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Issuer::compute_witnesses_batch: >>> rev_idxs: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
+               rev_idxs, max_cred_num, rev_reg_delta);
+
+        for rev_idx in rev_idxs.iter() {
+            check_rev_idx(*rev_idx, max_cred_num)?;
+        }
+        rev_reg_delta._check_indices(max_cred_num)?;
+
+        let witnesses: Vec<Witness> =
+            accumulator::witness_sums(rev_idxs, max_cred_num, rev_reg_delta._accumulated(), rev_tails_accessor)?
+                .into_iter()
+                .map(|omega| Witness { omega })
+                .collect();
+
+        trace!("Issuer::compute_witnesses_batch: <<< witnesses: {:?}", witnesses);
+
+        Ok(witnesses)
+    }
+
+    /// Signs revocation registry delta by Issuer BLS key.
+    /// Signed delta can be safely distributed off-ledger: Prover checks signature by `Witness::update_verified`.
+    /// Signature is bound to registry identifier, so it isn't valid for delta of other registry.
+    ///
+    /// # Arguments
+    /// * `rev_reg_delta` - Revocation registry delta.
+    /// * `rev_reg_id` - Identifier of revocation registry delta belongs to.
+    /// * `sign_key` - Issuer BLS sign key.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bls::{Generator, SignKey, VerKey};
+    /// use indy_crypto::cl::SimpleTailsAccessor;
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+    ///
+    /// let max_cred_num = 5;
+    /// let (_rev_key_pub, _rev_key_priv, mut rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
+    ///
+    /// let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+    ///
+    /// let mut rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, 1, &simple_tail_accessor).unwrap();
+    ///
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    ///
+    /// Issuer::sign_delta(&mut rev_reg_delta, "rev_reg_1", &sign_key).unwrap();
+    /// assert!(rev_reg_delta.verify_signature("rev_reg_1", &ver_key, &gen).unwrap());
+    /// ```
+    pub fn sign_delta(rev_reg_delta: &mut RevocationRegistryDelta,
+                      rev_reg_id: &str,
+                      sign_key: &SignKey) -> Result<(), IndyCryptoError> {
+        trace!("Issuer::sign_delta: >>> rev_reg_delta: {:?}, rev_reg_id: {:?}", rev_reg_delta, rev_reg_id);
+
+        let signature = Bls::sign(&rev_reg_delta._signed_bytes(rev_reg_id)?, sign_key)?;
+        rev_reg_delta.signature = Some(signature.as_bytes().to_vec());
+
+        trace!("Issuer::sign_delta: <<< rev_reg_delta: {:?}", rev_reg_delta);
+
+        Ok(())
+    }
+
+    /// Creates and returns empty non-ownership registry (e.g. sanctions list).
+    /// Holders enrolled to registry can't prove that they aren't enrolled.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let (_registry, _registry_members) = Issuer::new_non_ownership_registry().unwrap();
+    /// ```
+    pub fn new_non_ownership_registry() -> Result<(NonOwnershipRegistry, NonOwnershipRegistryMembers), IndyCryptoError> {
+        trace!("Issuer::new_non_ownership_registry: >>>");
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let p_safe = generate_safe_prime(LARGE_PRIME)?;
+        let q_safe = generate_safe_prime(LARGE_PRIME)?;
+
+        let n = p_safe.mul(&q_safe, Some(&mut ctx))?;
+        let g = random_qr(&n)?;
+
+        let registry = NonOwnershipRegistry {
+            h: random_qr(&n)?,
+            accum: g.clone()?,
+            g,
+            n
+        };
+
+        let registry_members = NonOwnershipRegistryMembers { members: Vec::new(), p_safe, q_safe };
+
+        trace!("Issuer::new_non_ownership_registry: <<< registry: {:?}, registry_members: {:?}", registry, registry_members);
+
+        Ok((registry, registry_members))
+    }
+
+    /// Enrolls holder to non-ownership registry.
+    /// Holder identifier must be proven to be derived from master secret blinded for credential issuance.
+    ///
+    /// # Arguments
+    /// * `registry` - Non-ownership registry.
+    /// * `registry_members` - Identifiers enrolled to non-ownership registry.
+    /// * `identifier` - Holder identifier in registry.
+    /// * `enrollment_proof` - Proof of identifier correctness.
+    /// * `blinded_ms` - Blinded master secret received from holder.
+    /// * `nonce` - Nonce used by holder for enrollment proof creation.
+    /// * `credential_pub_key` - Credential public key used by holder for master secret blinding.
+    pub fn enroll(registry: &mut NonOwnershipRegistry,
+                  registry_members: &mut NonOwnershipRegistryMembers,
+                  identifier: &BigNumber,
+                  enrollment_proof: &EnrollmentProof,
+                  blinded_ms: &BlindedMasterSecret,
+                  nonce: &Nonce,
+                  credential_pub_key: &CredentialPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("Issuer::enroll: >>> registry: {:?}, registry_members: {:?}, identifier: {:?}, enrollment_proof: {:?}, blinded_ms: {:?}, nonce: {:?}, \
+               credential_pub_key: {:?}", registry, registry_members, identifier, enrollment_proof, blinded_ms, nonce, credential_pub_key);
+
+        if !credential_pub_key.requires_master_secret() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential public key doesn't use master secret")));
+        }
+
+        Issuer::_check_enrollment_proof(registry, identifier, enrollment_proof, blinded_ms, nonce, &credential_pub_key.p_key)?;
+
+        let member = get_hash_as_prime(&identifier.to_bytes()?)?;
+
+        if registry_members.members.contains(&member) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Holder is already enrolled")));
+        }
+
+        registry.accum = registry.accum.mod_exp(&member, &registry.n, None)?;
+        registry_members.members.push(member);
+
+        trace!("Issuer::enroll: <<< registry: {:?}, registry_members: {:?}", registry, registry_members);
+
+        Ok(())
+    }
+
+    /// Removes holder from non-ownership registry.
+    ///
+    /// # Arguments
+    /// * `registry` - Non-ownership registry.
+    /// * `registry_members` - Identifiers enrolled to non-ownership registry.
+    /// * `identifier` - Holder identifier in registry.
+    pub fn unenroll(registry: &mut NonOwnershipRegistry,
+                    registry_members: &mut NonOwnershipRegistryMembers,
+                    identifier: &BigNumber) -> Result<(), IndyCryptoError> {
+        trace!("Issuer::unenroll: >>> registry: {:?}, registry_members: {:?}, identifier: {:?}", registry, registry_members, identifier);
+
+        let member = get_hash_as_prime(&identifier.to_bytes()?)?;
+
+        let position = registry_members.members.iter().position(|m| *m == member)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Holder isn't enrolled")))?;
+
+        let mut ctx = BigNumber::new_context()?;
+
+        // accum = accum^(1 / x mod phi(n)), where phi(n) = (p' - 1)(q' - 1) is known to Issuer only
+        let one = BigNumber::from_u32(1)?;
+        let phi = registry_members.p_safe.sub(&one)?
+            .mul(&registry_members.q_safe.sub(&one)?, Some(&mut ctx))?;
+        let member_inv = member.inverse(&phi, Some(&mut ctx))?;
+
+        registry.accum = registry.accum.mod_exp(&member_inv, &registry.n, Some(&mut ctx))?;
+        registry_members.members.remove(position);
+
+        trace!("Issuer::unenroll: <<< registry: {:?}, registry_members: {:?}", registry, registry_members);
+
+        Ok(())
+    }
+
+    /// Checks that holder identifier is derived from master secret blinded for credential issuance
+    /// and returns registry member for identifier.
+    /// Member must be signed as hidden attribute of credential issued for the same blinded master secret,
+    /// so holder can prove non-ownership without disclosing identifier (see `ProofBuilder::add_non_ownership_request`).
+    ///
+    /// # Arguments
+    /// * `registry` - Non-ownership registry.
+    /// * `identifier` - Holder identifier in registry.
+    /// * `enrollment_proof` - Proof of identifier correctness.
+    /// * `blinded_ms` - Blinded master secret received from holder.
+    /// * `nonce` - Nonce used by holder for enrollment proof creation.
+    /// * `credential_pub_key` - Credential public key used by holder for master secret blinding.
+    pub fn new_non_ownership_member(registry: &NonOwnershipRegistry,
+                                    identifier: &BigNumber,
+                                    enrollment_proof: &EnrollmentProof,
+                                    blinded_ms: &BlindedMasterSecret,
+                                    nonce: &Nonce,
+                                    credential_pub_key: &CredentialPublicKey) -> Result<BigNumber, IndyCryptoError> {
+        trace!("Issuer::new_non_ownership_member: >>> registry: {:?}, identifier: {:?}, enrollment_proof: {:?}, blinded_ms: {:?}, nonce: {:?}, \
+               credential_pub_key: {:?}", registry, identifier, enrollment_proof, blinded_ms, nonce, credential_pub_key);
+
+        if !credential_pub_key.requires_master_secret() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential public key doesn't use master secret")));
+        }
+
+        Issuer::_check_enrollment_proof(registry, identifier, enrollment_proof, blinded_ms, nonce, &credential_pub_key.p_key)?;
+
+        let member = get_hash_as_prime(&identifier.to_bytes()?)?;
+
+        trace!("Issuer::new_non_ownership_member: <<< member: {:?}", member);
+
+        Ok(member)
+    }
+
+    /// Creates witness that holder isn't enrolled to non-ownership registry.
+    /// Witness is valid for the current registry state only.
+    ///
+    /// # Arguments
+    /// * `registry` - Non-ownership registry.
+    /// * `registry_members` - Identifiers enrolled to non-ownership registry.
+    /// * `member` - Registry member of holder (see `Issuer::new_non_ownership_member`).
+    pub fn new_non_ownership_witness(registry: &NonOwnershipRegistry,
+                                     registry_members: &NonOwnershipRegistryMembers,
+                                     member: &BigNumber) -> Result<NonOwnershipWitness, IndyCryptoError> {
+        trace!("Issuer::new_non_ownership_witness: >>> registry: {:?}, registry_members: {:?}, member: {:?}",
+               registry, registry_members, member);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        if registry_members.members.contains(member) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Holder is enrolled to non-ownership registry")));
+        }
+
+        // Non-membership witness: a * u + b * x = 1, where u is product of registry members and b = -(a * u - 1) / x
+        let mut u = BigNumber::from_u32(1)?;
+        for m in registry_members.members.iter() {
+            u = u.mul(m, Some(&mut ctx))?;
+        }
+
+        let a = u.inverse(member, Some(&mut ctx))?;
+        let b = a.mul(&u, Some(&mut ctx))?
+            .sub(&BigNumber::from_u32(1)?)?
+            .div(member, Some(&mut ctx))?;
+        let d = registry.g.mod_exp(&b, &registry.n, Some(&mut ctx))?;
+
+        let witness = NonOwnershipWitness { a, d };
+
+        trace!("Issuer::new_non_ownership_witness: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+
+    fn _new_credential_def(credential_schema: &CredentialSchema,
+                           support_revocation: bool,
+                           safe_primes: Option<SafePrimePair>,
+                           profile: Option<SecurityProfile>) -> Result<(CredentialPublicKey,
+                                                                        CredentialPrivateKey,
+                                                                        CredentialKeyCorrectnessProof), IndyCryptoError> {
+        let (p_pub_key, p_priv_key, p_key_meta) =
+            Issuer::_new_credential_primary_keys(credential_schema, safe_primes, profile)?;
+
+        let (r_pub_key, r_priv_key) = if support_revocation {
+            Issuer::_new_credential_revocation_keys()
+                .map(|(r_pub_key, r_priv_key)| (Some(r_pub_key), Some(r_priv_key)))?
+        } else {
+            (None, None)
+        };
+
+        let cred_pub_key = CredentialPublicKey { p_key: p_pub_key, r_key: r_pub_key };
+        let cred_priv_key = CredentialPrivateKey { p_key: p_priv_key, r_key: r_priv_key };
+        let cred_key_correctness_proof =
+            Issuer::_new_credential_key_correctness_proof(&cred_pub_key,
+                                                          &cred_priv_key,
+                                                          &p_key_meta)?;
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    fn _new_credential_primary_keys(credential_schema: &CredentialSchema,
+                                    safe_primes: Option<SafePrimePair>,
+                                    profile: Option<SecurityProfile>) -> Result<(CredentialPrimaryPublicKey,
+                                                                                 CredentialPrimaryPrivateKey,
+                                                                                 CredentialPrimaryPublicKeyMetadata), IndyCryptoError> {
+        trace!("Issuer::_new_credential_primary_keys: >>> credential_schema: {:?}, profile: {:?}", credential_schema, profile);
 
         let mut ctx = BigNumber::new_context()?;
 
@@ -536,8 +1588,22 @@ impl Issuer {
             return Err(IndyCryptoError::InvalidStructure(format!("List of attributes is empty")));
         }
 
-        let p_safe = generate_safe_prime(LARGE_PRIME)?;
-        let q_safe = generate_safe_prime(LARGE_PRIME)?;
+        check_attrs_count(credential_schema.attrs.len())?;
+
+        let prime_bits = profile.unwrap_or_default().prime_bits();
+
+        let safe_primes = match safe_primes {
+            Some(safe_primes) => {
+                if safe_primes.prime_bits()? != prime_bits {
+                    return Err(IndyCryptoError::InvalidStructure(format!("Safe primes must be {} bits for security profile", prime_bits)));
+                }
+                safe_primes
+            }
+            None => SafePrimePair::new_with_size(prime_bits)?
+        };
+
+        let p_safe = safe_primes.p_safe();
+        let q_safe = safe_primes.q_safe();
 
         let mut p = p_safe.sub(&BigNumber::from_u32(1)?)?;
         p.div_word(2)?;
@@ -549,8 +1615,12 @@ impl Issuer {
         let s = random_qr(&n)?;
         let xz = gen_x(&p, &q)?;
 
+        // Randomness is consumed in sorted order, so keys are reproducible with seeded RNG
+        let mut attrs: Vec<&String> = credential_schema.attrs.iter().collect();
+        attrs.sort();
+
         let mut xr = BTreeMap::new();
-        for attribute in &credential_schema.attrs {
+        for attribute in attrs {
             xr.insert(attribute.to_string(), gen_x(&p, &q)?);
         }
 
@@ -561,12 +1631,16 @@ impl Issuer {
 
         let z = s.mod_exp(&xz, &n, Some(&mut ctx))?;
 
-        let rms = s.mod_exp(&gen_x(&p, &q)?, &n, Some(&mut ctx))?;
-        let rctxt = s.mod_exp(&gen_x(&p, &q)?, &n, Some(&mut ctx))?;
+        let xrms = gen_x(&p, &q)?;
+        let xrctxt = gen_x(&p, &q)?;
+
+        let rms = s.mod_exp(&xrms, &n, Some(&mut ctx))?;
+        let rctxt = s.mod_exp(&xrctxt, &n, Some(&mut ctx))?;
 
-        let cred_pr_pub_key = CredentialPrimaryPublicKey { n, s, rms, rctxt, r, z };
+        let cred_pr_pub_key = CredentialPrimaryPublicKey { n, s, rms, rctxt, r, z, profile, without_master_secret: false };
+        cred_pr_pub_key.security_profile()?;
         let cred_pr_priv_key = CredentialPrimaryPrivateKey { p, q };
-        let cred_pr_pub_key_metadata = CredentialPrimaryPublicKeyMetadata { xz, xr };
+        let cred_pr_pub_key_metadata = CredentialPrimaryPublicKeyMetadata { xz, xr, xrms, xrctxt };
 
         trace!("Issuer::_new_credential_primary_keys: <<< cred_pr_pub_key: {:?}, cred_pr_priv_key: {:?}, cred_pr_pub_key_metadata: {:?}",
                cred_pr_pub_key, cred_pr_priv_key, cred_pr_pub_key_metadata);
@@ -603,15 +1677,20 @@ impl Issuer {
         Ok((cred_rev_pub_key, cred_rev_priv_key))
     }
 
-    fn _new_credential_key_correctness_proof(cred_pr_pub_key: &CredentialPrimaryPublicKey,
-                                             cred_pr_priv_key: &CredentialPrimaryPrivateKey,
+    fn _new_credential_key_correctness_proof(cred_pub_key: &CredentialPublicKey,
+                                             cred_priv_key: &CredentialPrivateKey,
                                              cred_pr_pub_key_meta: &CredentialPrimaryPublicKeyMetadata) -> Result<CredentialKeyCorrectnessProof, IndyCryptoError> {
-        trace!("Issuer::_new_credential_key_correctness_proof: >>> cred_pr_pub_key: {:?}, cred_pr_priv_key: {:?}, cred_pr_pub_key_meta: {:?}",
-               cred_pr_pub_key, cred_pr_priv_key, cred_pr_pub_key_meta);
+        trace!("Issuer::_new_credential_key_correctness_proof: >>> cred_pub_key: {:?}, cred_priv_key: {:?}, cred_pr_pub_key_meta: {:?}",
+               cred_pub_key, cred_priv_key, cred_pr_pub_key_meta);
+
+        let cred_pr_pub_key = &cred_pub_key.p_key;
+        let cred_pr_priv_key = &cred_priv_key.p_key;
 
         let mut ctx = BigNumber::new_context()?;
 
         let xz_tilda = gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?;
+        let xrms_tilda = gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?;
+        let xrctxt_tilda = gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?;
 
         let mut xr_tilda = BTreeMap::new();
         for key in cred_pr_pub_key.r.keys() {
@@ -619,6 +1698,8 @@ impl Issuer {
         }
 
         let z_tilda = cred_pr_pub_key.s.mod_exp(&xz_tilda, &cred_pr_pub_key.n, Some(&mut ctx))?;
+        let rms_tilda = cred_pr_pub_key.s.mod_exp(&xrms_tilda, &cred_pr_pub_key.n, Some(&mut ctx))?;
+        let rctxt_tilda = cred_pr_pub_key.s.mod_exp(&xrctxt_tilda, &cred_pr_pub_key.n, Some(&mut ctx))?;
 
         let mut r_tilda = BTreeMap::new();
         for (key, xr_tilda_value) in xr_tilda.iter() {
@@ -626,11 +1707,17 @@ impl Issuer {
         }
 
         let mut values: Vec<u8> = Vec::new();
+        values.extend_from_slice(&cred_pr_pub_key.n.to_bytes()?);
+        values.extend_from_slice(&cred_pr_pub_key.s.to_bytes()?);
         values.extend_from_slice(&cred_pr_pub_key.z.to_bytes()?);
+        values.extend_from_slice(&cred_pr_pub_key.rms.to_bytes()?);
+        values.extend_from_slice(&cred_pr_pub_key.rctxt.to_bytes()?);
         for val in cred_pr_pub_key.r.values() {
             values.extend_from_slice(&val.to_bytes()?);
         }
         values.extend_from_slice(&z_tilda.to_bytes()?);
+        values.extend_from_slice(&rms_tilda.to_bytes()?);
+        values.extend_from_slice(&rctxt_tilda.to_bytes()?);
         for val in r_tilda.values() {
             values.extend_from_slice(&val.to_bytes()?);
         }
@@ -641,39 +1728,211 @@ impl Issuer {
             c.mul(&cred_pr_pub_key_meta.xz, Some(&mut ctx))?
                 .add(&xz_tilda)?;
 
-        let mut xr_cap: BTreeMap<String, BigNumber> = BTreeMap::new();
-        for (key, xr_tilda_value) in xr_tilda {
-            let val =
-                c.mul(&cred_pr_pub_key_meta.xr[&key], Some(&mut ctx))?
-                    .add(&xr_tilda_value)?;
-            xr_cap.insert(key.to_string(), val);
-        }
+        let xrms_cap =
+            c.mul(&cred_pr_pub_key_meta.xrms, Some(&mut ctx))?
+                .add(&xrms_tilda)?;
+
+        let xrctxt_cap =
+            c.mul(&cred_pr_pub_key_meta.xrctxt, Some(&mut ctx))?
+                .add(&xrctxt_tilda)?;
+
+        let mut xr_cap: BTreeMap<String, BigNumber> = BTreeMap::new();
+        for (key, xr_tilda_value) in xr_tilda {
+            let val =
+                c.mul(&cred_pr_pub_key_meta.xr[&key], Some(&mut ctx))?
+                    .add(&xr_tilda_value)?;
+            xr_cap.insert(key.to_string(), val);
+        }
+
+        let r_key_proof = match (&cred_pub_key.r_key, &cred_priv_key.r_key) {
+            (&Some(ref r_pub_key), &Some(ref r_priv_key)) =>
+                Some(Issuer::_new_credential_revocation_key_correctness_proof(r_pub_key, r_priv_key)?),
+            (&None, &None) => None,
+            _ => return Err(IndyCryptoError::InvalidStructure(format!("Revocation parts of credential public and private keys don't match")))
+        };
+
+        let key_correctness_proof = CredentialKeyCorrectnessProof {
+            version: CREDENTIAL_KEY_CORRECTNESS_PROOF_VERSION,
+            c,
+            xz_cap,
+            xr_cap,
+            xrms_cap: Some(xrms_cap),
+            xrctxt_cap: Some(xrctxt_cap),
+            r_key_proof,
+            extensions: Vec::new()
+        };
+
+        trace!("Issuer::_new_credential_key_correctness_proof: <<< key_correctness_proof: {:?}", key_correctness_proof);
+
+        Ok(key_correctness_proof)
+    }
+
+    fn _new_credential_revocation_key_correctness_proof(cred_rev_pub_key: &CredentialRevocationPublicKey,
+                                                        cred_rev_priv_key: &CredentialRevocationPrivateKey) -> Result<CredentialRevocationKeyCorrectnessProof, IndyCryptoError> {
+        trace!("Issuer::_new_credential_revocation_key_correctness_proof: >>> cred_rev_pub_key: {:?}, cred_rev_priv_key: {:?}",
+               cred_rev_pub_key, cred_rev_priv_key);
+
+        let sk_tilda = GroupOrderElement::new()?;
+        let x_tilda = GroupOrderElement::new()?;
+
+        let pk_tilda = cred_rev_pub_key.g.mul(&sk_tilda)?;
+        let y_tilda = cred_rev_pub_key.h_cap.mul(&x_tilda)?;
+
+        let c = CredentialRevocationKeyCorrectnessProof::challenge(cred_rev_pub_key, &pk_tilda, &y_tilda)?;
+        let ch = bignum_to_group_element(&c)?;
+
+        let sk_cap = ch.mul_mod(&cred_rev_priv_key.sk)?.add_mod(&sk_tilda)?;
+        let x_cap = ch.mul_mod(&cred_rev_priv_key.x)?.add_mod(&x_tilda)?;
+
+        let r_key_proof = CredentialRevocationKeyCorrectnessProof { c, sk_cap, x_cap };
+
+        trace!("Issuer::_new_credential_revocation_key_correctness_proof: <<< r_key_proof: {:?}", r_key_proof);
+
+        Ok(r_key_proof)
+    }
+
+    fn _new_credential_key_extension_proof(cred_pr_pub_key: &CredentialPrimaryPublicKey,
+                                           cred_pr_priv_key: &CredentialPrimaryPrivateKey,
+                                           xr: &BTreeMap<String, BigNumber>) -> Result<CredentialKeyExtensionProof, IndyCryptoError> {
+        trace!("Issuer::_new_credential_key_extension_proof: >>> cred_pr_pub_key: {:?}, cred_pr_priv_key: {:?}, xr: {:?}",
+               cred_pr_pub_key, cred_pr_priv_key, xr);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let mut xr_tilda = BTreeMap::new();
+        for key in xr.keys() {
+            xr_tilda.insert(key.to_string(), gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?);
+        }
+
+        let mut r_tilda = BTreeMap::new();
+        for (key, xr_tilda_value) in xr_tilda.iter() {
+            r_tilda.insert(key.to_string(), cred_pr_pub_key.s.mod_exp(&xr_tilda_value, &cred_pr_pub_key.n, Some(&mut ctx))?);
+        }
+
+        let mut values: Vec<u8> = Vec::new();
+        for key in xr.keys() {
+            values.extend_from_slice(&cred_pr_pub_key.r[key].to_bytes()?);
+        }
+        for val in r_tilda.values() {
+            values.extend_from_slice(&val.to_bytes()?);
+        }
+
+        let c = get_hash_as_int(&mut vec![values])?;
+
+        let mut xr_cap: BTreeMap<String, BigNumber> = BTreeMap::new();
+        for (key, xr_tilda_value) in xr_tilda {
+            let val =
+                c.mul(&xr[&key], Some(&mut ctx))?
+                    .add(&xr_tilda_value)?;
+            xr_cap.insert(key.to_string(), val);
+        }
+
+        let extension_proof = CredentialKeyExtensionProof { c, xr_cap };
+
+        trace!("Issuer::_new_credential_key_extension_proof: <<< extension_proof: {:?}", extension_proof);
+
+        Ok(extension_proof)
+    }
+
+    fn _check_enrollment_proof(registry: &NonOwnershipRegistry,
+                               identifier: &BigNumber,
+                               enrollment_proof: &EnrollmentProof,
+                               blinded_ms: &BlindedMasterSecret,
+                               nonce: &Nonce,
+                               cred_pr_pub_key: &CredentialPrimaryPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("Issuer::_check_enrollment_proof: >>> registry: {:?}, identifier: {:?}, enrollment_proof: {:?}, blinded_ms: {:?}, nonce: {:?}, \
+               cred_pr_pub_key: {:?}", registry, identifier, enrollment_proof, blinded_ms, nonce, cred_pr_pub_key);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let u_cap =
+            blinded_ms.u
+                .inverse(&cred_pr_pub_key.n, Some(&mut ctx))?
+                .mod_exp(&enrollment_proof.c, &cred_pr_pub_key.n, Some(&mut ctx))?
+                .mod_mul(
+                    &get_pedersen_commitment(&cred_pr_pub_key.rms, &enrollment_proof.ms_cap, &cred_pr_pub_key.s,
+                                             &enrollment_proof.v_dash_cap, &cred_pr_pub_key.n, &mut ctx)?,
+                    &cred_pr_pub_key.n,
+                    Some(&mut ctx)
+                )?;
+
+        let identifier_cap =
+            identifier
+                .inverse(&registry.n, Some(&mut ctx))?
+                .mod_exp(&enrollment_proof.c, &registry.n, Some(&mut ctx))?
+                .mod_mul(
+                    &registry.h.mod_exp(&enrollment_proof.ms_cap, &registry.n, Some(&mut ctx))?,
+                    &registry.n,
+                    Some(&mut ctx)
+                )?;
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        values.push(blinded_ms.u.to_bytes()?);
+        values.push(u_cap.to_bytes()?);
+        values.push(identifier.to_bytes()?);
+        values.push(identifier_cap.to_bytes()?);
+        values.push(nonce.to_bytes()?);
+
+        let c = get_hash_as_int(&values)?;
+
+        if enrollment_proof.c != c {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid enrollment proof")));
+        }
+
+        trace!("Issuer::_check_enrollment_proof: <<<");
+
+        Ok(())
+    }
+
+    fn _new_revocation_registry_def(credential_pub_key: &CredentialPublicKey,
+                                    max_cred_num: u64,
+                                    issuance_by_default: bool,
+                                    mode: RevocationRegistryMode) -> Result<(RevocationKeyPublic,
+                                                                             RevocationKeyPrivate,
+                                                                             RevocationRegistry,
+                                                                             RevocationTailsGenerator), IndyCryptoError> {
+        check_max_cred_num(max_cred_num)?;
+
+        let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
+            .as_ref()
+            .ok_or(IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
+
+        let (rev_key_pub, rev_key_priv) = Issuer::_new_revocation_registry_keys(cred_rev_pub_key, max_cred_num, mode)?;
 
-        let key_correctness_proof = CredentialKeyCorrectnessProof { c, xz_cap, xr_cap };
+        let rev_reg = Issuer::_new_revocation_registry(cred_rev_pub_key,
+                                                       &rev_key_priv,
+                                                       max_cred_num,
+                                                       issuance_by_default,
+                                                       mode)?;
 
-        trace!("Issuer::_new_credential_key_correctness_proof: <<< key_correctness_proof: {:?}", key_correctness_proof);
+        let rev_tails_generator = RevocationTailsGenerator::new(
+            max_cred_num,
+            rev_key_priv.gamma.clone(),
+            cred_rev_pub_key.g_dash.clone());
 
-        Ok(key_correctness_proof)
+        Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator))
     }
 
     fn _new_revocation_registry(cred_rev_pub_key: &CredentialRevocationPublicKey,
                                 rev_key_priv: &RevocationKeyPrivate,
-                                max_cred_num: u32,
-                                issuance_by_default: bool) -> Result<RevocationRegistry, IndyCryptoError> {
-        trace!("Issuer::_new_revocation_registry: >>> cred_rev_pub_key: {:?}, rev_key_priv: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}",
-               cred_rev_pub_key, rev_key_priv, max_cred_num, issuance_by_default);
+                                max_cred_num: u64,
+                                issuance_by_default: bool,
+                                mode: RevocationRegistryMode) -> Result<RevocationRegistry, IndyCryptoError> {
+        trace!("Issuer::_new_revocation_registry: >>> cred_rev_pub_key: {:?}, rev_key_priv: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, mode: {:?}",
+               cred_rev_pub_key, rev_key_priv, max_cred_num, issuance_by_default, mode);
 
-        let mut accum = Accumulator::new_inf()?;
+        let mut accum = accumulator::new_accumulator()?;
 
         if issuance_by_default {
             for i in 1..max_cred_num + 1 {
-                let index = Issuer::_get_index(max_cred_num, i);
-                accum = accum.add(&Tail::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?)?;
+                let index = accumulator::tail_index(max_cred_num, i);
+                accum = accum.add(&accumulator::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?)?;
             }
         };
 
         let rev_reg = RevocationRegistry {
-            accum
+            accum,
+            mode
         };
 
         trace!("Issuer::_new_revocation_registry: <<< rev_reg: {:?}", rev_reg);
@@ -682,18 +1941,14 @@ impl Issuer {
     }
 
     fn _new_revocation_registry_keys(cred_rev_pub_key: &CredentialRevocationPublicKey,
-                                     max_cred_num: u32) -> Result<(RevocationKeyPublic, RevocationKeyPrivate), IndyCryptoError> {
-        trace!("Issuer::_new_revocation_registry_keys: >>> cred_rev_pub_key: {:?}, max_cred_num: {:?}",
-               cred_rev_pub_key, max_cred_num);
+                                     max_cred_num: u64,
+                                     mode: RevocationRegistryMode) -> Result<(RevocationKeyPublic, RevocationKeyPrivate), IndyCryptoError> {
+        trace!("Issuer::_new_revocation_registry_keys: >>> cred_rev_pub_key: {:?}, max_cred_num: {:?}, mode: {:?}",
+               cred_rev_pub_key, max_cred_num, mode);
 
         let gamma = GroupOrderElement::new()?;
 
-        let mut z = Pair::pair(&cred_rev_pub_key.g, &cred_rev_pub_key.g_dash)?;
-        let mut pow = GroupOrderElement::from_bytes(&transform_u32_to_array_of_u8(max_cred_num + 1))?;
-        pow = gamma.pow_mod(&pow)?;
-        z = z.pow(&pow)?;
-
-        let rev_key_pub = RevocationKeyPublic { z };
+        let rev_key_pub = Issuer::_new_revocation_key_public(cred_rev_pub_key, &gamma, max_cred_num, mode)?;
         let rev_key_priv = RevocationKeyPrivate { gamma };
 
         trace!("Issuer::_new_revocation_registry_keys: <<< rev_key_pub: {:?}, rev_key_priv: {:?}", rev_key_pub, rev_key_priv);
@@ -701,6 +1956,15 @@ impl Issuer {
         Ok((rev_key_pub, rev_key_priv))
     }
 
+    fn _new_revocation_key_public(cred_rev_pub_key: &CredentialRevocationPublicKey,
+                                  gamma: &GroupOrderElement,
+                                  max_cred_num: u64,
+                                  mode: RevocationRegistryMode) -> Result<RevocationKeyPublic, IndyCryptoError> {
+        let z = accumulator::accumulator_key(&cred_rev_pub_key.g, &cred_rev_pub_key.g_dash, gamma, max_cred_num)?;
+
+        Ok(RevocationKeyPublic { z, mode })
+    }
+
     fn _check_blinded_master_secret_correctness_proof(blinded_ms: &BlindedMasterSecret,
                                                       blinded_ms_correctness_proof: &BlindedMasterSecretCorrectnessProof,
                                                       nonce: &Nonce,
@@ -708,22 +1972,27 @@ impl Issuer {
         trace!("Issuer::_check_blinded_master_secret_correctness_proof: >>> blinded_ms: {:?}, blinded_ms_correctness_proof: {:?},\
          nonce: {:?}, cred_pr_pub_key: {:?}", blinded_ms, blinded_ms_correctness_proof, nonce, cred_pr_pub_key);
 
-        let mut ctx = BigNumber::new_context()?;
+        let group = BnGroup::new(cred_pr_pub_key.n.clone()?, LARGE_VPRIME_TILDE)?;
 
-        let u_cap =
-            blinded_ms.u
-                .inverse(&cred_pr_pub_key.n, Some(&mut ctx))?
-                .mod_exp(&blinded_ms_correctness_proof.c, &cred_pr_pub_key.n, Some(&mut ctx))?
-                .mod_mul(
-                    &cred_pr_pub_key.s.mod_exp(&blinded_ms_correctness_proof.v_dash_cap, &cred_pr_pub_key.n, Some(&mut ctx))?,
-                    &cred_pr_pub_key.n,
-                    Some(&mut ctx)
-                )?
-                .mod_mul(
-                    &cred_pr_pub_key.rms.mod_exp(&blinded_ms_correctness_proof.ms_cap, &cred_pr_pub_key.n, Some(&mut ctx))?,
-                    &cred_pr_pub_key.n,
-                    Some(&mut ctx)
-                )?;
+        // Without master secret blinded value is commitment to blinding factor only
+        let u_cap = if cred_pr_pub_key.without_master_secret {
+            let mut statement: Statement<BnGroup> = Statement::new(1);
+            statement.add_relation(blinded_ms.u.clone()?, vec![(cred_pr_pub_key.s.clone()?, 0)])?;
+
+            statement.commitments_from_responses(&group,
+                                                 &blinded_ms_correctness_proof.c,
+                                                 &[&blinded_ms_correctness_proof.v_dash_cap])?
+                .remove(0)
+        } else {
+            let mut statement: Statement<BnGroup> = Statement::new(2);
+            statement.add_relation(blinded_ms.u.clone()?,
+                                   vec![(cred_pr_pub_key.rms.clone()?, 0), (cred_pr_pub_key.s.clone()?, 1)])?;
+
+            statement.commitments_from_responses(&group,
+                                                 &blinded_ms_correctness_proof.c,
+                                                 &[&blinded_ms_correctness_proof.ms_cap, &blinded_ms_correctness_proof.v_dash_cap])?
+                .remove(0)
+        };
 
         let mut values: Vec<u8> = Vec::new();
         values.extend_from_slice(&blinded_ms.u.to_bytes()?);
@@ -744,10 +2013,10 @@ impl Issuer {
     }
 
     // In the anoncreds whitepaper, `credential context` is denoted by `m2`
-    fn _gen_credential_context(prover_id: &str, rev_idx: Option<u32>) -> Result<BigNumber, IndyCryptoError> {
+    fn _gen_credential_context(prover_id: &str, rev_idx: Option<u64>) -> Result<BigNumber, IndyCryptoError> {
         trace!("Issuer::_calc_m2: >>> prover_id: {:?}, rev_idx: {:?}", prover_id, rev_idx);
 
-        let rev_idx = rev_idx.map(|i| i as i32).unwrap_or(-1);
+        let rev_idx = rev_idx.map(|i| i as i64).unwrap_or(-1);
 
         let prover_id_bn = encode_attribute(prover_id, ByteOrder::Little)?;
         let rev_idx_bn = encode_attribute(&rev_idx.to_string(), ByteOrder::Little)?;
@@ -763,15 +2032,93 @@ impl Issuer {
         Ok(credential_context)
     }
 
+    /// PRF keyed by primary private key, so randomness of signature correctness proof
+    /// can't be predicted by anybody but Issuer.
+    fn _gen_signing_seed(prover_id: &str,
+                         blinded_master_secret: &BlindedMasterSecret,
+                         master_secret_blinding_nonce: &Nonce,
+                         credential_issuance_nonce: &Nonce,
+                         credential_values: &CredentialValues,
+                         credential_priv_key: &CredentialPrivateKey) -> Result<Vec<u8>, IndyCryptoError> {
+        trace!("Issuer::_gen_signing_seed: >>> prover_id: {:?}, credential_values: {:?}", prover_id, credential_values);
+
+        let mut hasher = ChallengeHasher::sha256();
+        hasher.update(SIGNING_SEED_DOMAIN);
+
+        for value in [&credential_priv_key.p_key.p, &credential_priv_key.p_key.q].iter() {
+            let bytes = value.to_bytes()?;
+            hasher.update(&transform_u32_to_array_of_u8(bytes.len() as u32)).update(&bytes);
+        }
+
+        hasher.update(&transform_u32_to_array_of_u8(prover_id.len() as u32)).update(prover_id.as_bytes());
+
+        let values: BTreeMap<&String, &BigNumber> = credential_values.attrs_values.iter().collect();
+        for (attr, value) in values {
+            let bytes = value.to_bytes()?;
+            hasher.update(&transform_u32_to_array_of_u8(attr.len() as u32)).update(attr.as_bytes());
+            hasher.update(&transform_u32_to_array_of_u8(bytes.len() as u32)).update(&bytes);
+        }
+
+        for value in [&blinded_master_secret.u, master_secret_blinding_nonce, credential_issuance_nonce].iter() {
+            let bytes = value.to_bytes()?;
+            hasher.update(&transform_u32_to_array_of_u8(bytes.len() as u32)).update(&bytes);
+        }
+
+        let seed = hasher.finalize_bytes();
+
+        trace!("Issuer::_gen_signing_seed: <<<");
+
+        Ok(seed)
+    }
+
+    fn _sign_credential(prover_id: &str,
+                        blinded_master_secret: &BlindedMasterSecret,
+                        blinded_master_secret_correctness_proof: &BlindedMasterSecretCorrectnessProof,
+                        master_secret_blinding_nonce: &Nonce,
+                        credential_issuance_nonce: &Nonce,
+                        credential_values: &CredentialValues,
+                        p_key_exp: &PrimaryPublicKeyExp,
+                        key_provider: &PrivateKeyProvider) -> Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::_sign_credential: >>> prover_id: {:?}, credential_values: {:?}, p_pub_key: {:?}", prover_id, credential_values, p_key_exp.primary_key());
+
+        Issuer::_check_blinded_master_secret_correctness_proof(blinded_master_secret,
+                                                               blinded_master_secret_correctness_proof,
+                                                               master_secret_blinding_nonce,
+                                                               p_key_exp.primary_key())?;
+
+        // In the anoncreds whitepaper, `credential context` is denoted by `m2`
+        let cred_context = Issuer::_gen_credential_context(prover_id, None)?;
+
+        let (p_cred, q) = Issuer::_new_primary_credential(&cred_context,
+                                                          p_key_exp,
+                                                          key_provider,
+                                                          blinded_master_secret,
+                                                          credential_values)?;
+
+        let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: None };
+
+        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(p_key_exp.primary_key(),
+                                                                                   key_provider,
+                                                                                   &cred_signature.p_credential,
+                                                                                   &q,
+                                                                                   credential_issuance_nonce)?;
+
+
+        trace!("Issuer::_sign_credential: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
+               cred_signature, signature_correctness_proof);
+
+        Ok((cred_signature, signature_correctness_proof))
+    }
+
     fn _new_primary_credential(credential_context: &BigNumber,
-                               cred_pub_key: &CredentialPublicKey,
-                               cred_priv_key: &CredentialPrivateKey,
+                               p_key_exp: &PrimaryPublicKeyExp,
+                               key_provider: &PrivateKeyProvider,
                                blinded_ms: &BlindedMasterSecret,
                                cred_values: &CredentialValues) -> Result<(PrimaryCredentialSignature, BigNumber), IndyCryptoError> {
-        trace!("Issuer::_new_primary_credential: >>> credential_context: {:?}, cred_pub_key: {:?}, cred_priv_key: {:?}, blinded_ms: {:?},\
-         cred_values: {:?}", credential_context, cred_pub_key, cred_priv_key, blinded_ms, cred_values);
+        trace!("Issuer::_new_primary_credential: >>> credential_context: {:?}, cred_pub_key: {:?}, blinded_ms: {:?}, cred_values: {:?}",
+               credential_context, p_key_exp.primary_key(), blinded_ms, cred_values);
 
-        let v = generate_v_prime_prime()?;
+        let v = generate_v_prime_prime(p_key_exp.primary_key().security_profile()?.vprime_prime_bits())?;
 
         let e_start = BigNumber::from_u32(2)?.exp(&BigNumber::from_u32(LARGE_E_START)?, None)?;
         let e_end = BigNumber::from_u32(2)?
@@ -779,7 +2126,7 @@ impl Issuer {
             .add(&e_start)?;
 
         let e = generate_prime_in_range(&e_start, &e_end)?;
-        let (a, q) = Issuer::_sign_primary_credential(cred_pub_key, cred_priv_key, &credential_context, &cred_values, &v, blinded_ms, &e)?;
+        let (a, q) = Issuer::_sign_primary_credential(p_key_exp, key_provider, &credential_context, &cred_values, &v, blinded_ms, &e)?;
 
         let pr_cred_sig = PrimaryCredentialSignature { m_2: credential_context.clone()?, a, e, v };
 
@@ -788,47 +2135,38 @@ impl Issuer {
         Ok((pr_cred_sig, q))
     }
 
-    fn _sign_primary_credential(cred_pub_key: &CredentialPublicKey,
-                                cred_priv_key: &CredentialPrivateKey,
+    fn _sign_primary_credential(p_key_exp: &PrimaryPublicKeyExp,
+                                key_provider: &PrivateKeyProvider,
                                 cred_context: &BigNumber,
                                 cred_values: &CredentialValues,
                                 v: &BigNumber,
                                 blnd_ms: &BlindedMasterSecret,
                                 e: &BigNumber) -> Result<(BigNumber, BigNumber), IndyCryptoError> {
-        trace!("Issuer::_sign_primary_credential: >>> cred_pub_key: {:?}, cred_priv_key: {:?}, cred_context: {:?}, cred_values: {:?}, v: {:?},\
-         blnd_ms: {:?}, e: {:?}", cred_pub_key, cred_priv_key, cred_context, cred_values, v, blnd_ms, e);
+        let p_pub_key = p_key_exp.primary_key();
 
-        let p_pub_key = &cred_pub_key.p_key;
-        let p_priv_key = &cred_priv_key.p_key;
+        trace!("Issuer::_sign_primary_credential: >>> p_pub_key: {:?}, cred_context: {:?}, cred_values: {:?}, v: {:?},\
+         blnd_ms: {:?}, e: {:?}", p_pub_key, cred_context, cred_values, v, blnd_ms, e);
 
         let mut context = BigNumber::new_context()?;
 
-        let mut rx = p_pub_key.s
-            .mod_exp(&v, &p_pub_key.n, Some(&mut context))?;
+        let mut rx = p_key_exp.s_exp(&v, &mut context)?;
 
         if blnd_ms.u != BigNumber::from_u32(0)? {
             rx = blnd_ms.u.modulus(&p_pub_key.n, Some(&mut context))?
                 .mul(&rx, Some(&mut context))?;
         }
 
-        rx = p_pub_key.rctxt.mod_exp(&cred_context, &p_pub_key.n, Some(&mut context))?
+        rx = p_key_exp.rctxt_exp(&cred_context, &mut context)?
             .mul(&rx, Some(&mut context))?;
 
         for (key, value) in &cred_values.attrs_values {
-            let pk_r = p_pub_key.r
-                .get(key)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", key)))?;
-
-            rx = pk_r.mod_exp(&value, &p_pub_key.n, Some(&mut context))?
+            rx = p_key_exp.r_exp(key, &value, &mut context)?
                 .mod_mul(&rx, &p_pub_key.n, Some(&mut context))?;
         }
 
         let q = p_pub_key.z.mod_div(&rx, &p_pub_key.n)?;
 
-        let n = p_priv_key.p.mul(&p_priv_key.q, Some(&mut context))?;
-        let e_inverse = e.inverse(&n, Some(&mut context))?;
-
-        let a = q.mod_exp(&e_inverse, &p_pub_key.n, Some(&mut context))?;
+        let a = key_provider.sign_primary(&q, e, &p_pub_key.n)?;
 
         trace!("Issuer::_sign_primary_credential: <<< a: {:?}, q: {:?}", a, q);
 
@@ -836,51 +2174,30 @@ impl Issuer {
     }
 
     fn _new_signature_correctness_proof(p_pub_key: &CredentialPrimaryPublicKey,
-                                        p_priv_key: &CredentialPrimaryPrivateKey,
+                                        key_provider: &PrivateKeyProvider,
                                         p_cred_signature: &PrimaryCredentialSignature,
                                         q: &BigNumber,
                                         nonce: &BigNumber) -> Result<SignatureCorrectnessProof, IndyCryptoError> {
-        trace!("Issuer::_new_signature_correctness_proof: >>> p_pub_key: {:?}, p_priv_key: {:?}, p_cred_signature: {:?}, q: {:?}, nonce: {:?}",
-               p_pub_key, p_priv_key, p_cred_signature, q, nonce);
-
-        let mut ctx = BigNumber::new_context()?;
-
-        let n = p_priv_key.p.mul(&p_priv_key.q, Some(&mut ctx))?;
-        let r = bn_rand_range(&n)?;
-
-        let a_cap = q.mod_exp(&r, &p_pub_key.n, Some(&mut ctx))?;
-
-        let mut values: Vec<u8> = Vec::new();
-        values.extend_from_slice(&q.to_bytes()?);
-        values.extend_from_slice(&p_cred_signature.a.to_bytes()?);
-        values.extend_from_slice(&a_cap.to_bytes()?);
-        values.extend_from_slice(&nonce.to_bytes()?);
-
-        let c = get_hash_as_int(&mut vec![values])?;
-
-        let se = r.mod_sub(
-            &c.mod_mul(&p_cred_signature.e.inverse(&n, Some(&mut ctx))?, &n, Some(&mut ctx))?,
-            &n,
-            Some(&mut ctx)
-        )?;
+        trace!("Issuer::_new_signature_correctness_proof: >>> p_pub_key: {:?}, p_cred_signature: {:?}, q: {:?}, nonce: {:?}",
+               p_pub_key, p_cred_signature, q, nonce);
 
-        let signature_correctness_proof = SignatureCorrectnessProof { c, se };
+        let signature_correctness_proof = key_provider.prove_primary_signature(q,
+                                                                               &p_cred_signature.a,
+                                                                               &p_cred_signature.e,
+                                                                               &p_pub_key.n,
+                                                                               nonce)?;
 
         trace!("Issuer::_new_signature_correctness_proof: <<< signature_correctness_proof: {:?}", signature_correctness_proof);
 
         Ok(signature_correctness_proof)
     }
 
-    fn _get_index(max_cred_num: u32, rev_idx: u32) -> u32 {
-        max_cred_num + 1 - rev_idx
-    }
-
-    fn _new_non_revocation_credential(rev_idx: u32,
+    fn _new_non_revocation_credential(rev_idx: u64,
                                       cred_context: &BigNumber,
                                       blinded_ms: &BlindedMasterSecret,
                                       cred_pub_key: &CredentialPublicKey,
                                       cred_priv_key: &CredentialPrivateKey,
-                                      max_cred_num: u32,
+                                      max_cred_num: u64,
                                       issuance_by_default: bool,
                                       rev_reg: &mut RevocationRegistry,
                                       rev_key_priv: &RevocationKeyPrivate,
@@ -905,12 +2222,7 @@ impl Issuer {
         let c = GroupOrderElement::new()?;
         let m2 = GroupOrderElement::from_bytes(&cred_context.to_bytes()?)?;
 
-        let g_i = {
-            let i_bytes = transform_u32_to_array_of_u8(rev_idx);
-            let mut pow = GroupOrderElement::from_bytes(&i_bytes)?;
-            pow = rev_key_priv.gamma.pow_mod(&pow)?;
-            r_pub_key.g.mul(&pow)?
-        };
+        let g_i = accumulator::element_point(&r_pub_key.g, &rev_key_priv.gamma, rev_idx)?;
 
         let sigma =
             r_pub_key.h0.add(&r_pub_key.h1.mul(&m2)?)?
@@ -923,28 +2235,26 @@ impl Issuer {
         let sigma_i = r_pub_key.g_dash
             .mul(&r_priv_key.sk
                 .add_mod(&rev_key_priv.gamma
-                    .pow_mod(&GroupOrderElement::from_bytes(&transform_u32_to_array_of_u8(rev_idx))?)?)?
+                    .pow_mod(&GroupOrderElement::from_bytes(&transform_u64_to_array_of_u8(rev_idx))?)?)?
                 .inverse()?)?;
         let u_i = r_pub_key.u
             .mul(&rev_key_priv.gamma
-                .pow_mod(&GroupOrderElement::from_bytes(&transform_u32_to_array_of_u8(rev_idx))?)?)?;
-
-        let index = Issuer::_get_index(max_cred_num, rev_idx);
+                .pow_mod(&GroupOrderElement::from_bytes(&transform_u64_to_array_of_u8(rev_idx))?)?)?;
 
-        let rev_reg_delta = if issuance_by_default {
+        let rev_reg_delta = if issuance_by_default || rev_reg.mode == RevocationRegistryMode::NonMembership {
             None
         } else {
             let prev_acc = rev_reg.accum.clone();
 
-            rev_tails_accessor.access_tail(index, &mut |tail| {
-                rev_reg.accum = rev_reg.accum.add(tail).unwrap();
-            })?;
+            accumulator::add_element(&mut rev_reg.accum, rev_idx, max_cred_num, rev_tails_accessor)?;
 
             Some(RevocationRegistryDelta {
                 prev_accum: Some(prev_acc),
                 accum: rev_reg.accum.clone(),
                 issued: hashset![rev_idx],
-                revoked: HashSet::new()
+                revoked: HashSet::new(),
+                signature: None,
+                mode: rev_reg.mode
             })
         };
 
@@ -973,9 +2283,13 @@ impl Issuer {
 
 #[cfg(test)]
 mod tests {
+    extern crate time;
+
     use super::*;
     use cl::issuer::{Issuer, mocks};
     use cl::helpers::MockHelper;
+    use cl::prover::Prover;
+    use bls::{Generator, VerKey};
 
     #[test]
     fn generate_context_attribute_works() {
@@ -1016,14 +2330,37 @@ mod tests {
     fn issuer_new_credential_def_works() {
         MockHelper::inject();
 
-        let (pub_key, priv_key, key_correctness_proof) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let (pub_key, priv_key, mut key_correctness_proof) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
         assert_eq!(pub_key.p_key, mocks::credential_primary_public_key());
         assert_eq!(priv_key.p_key, mocks::credential_primary_private_key());
+        assert!(key_correctness_proof.r_key_proof.take().is_some());
         assert_eq!(key_correctness_proof, mocks::credential_key_correctness_proof());
         assert!(pub_key.r_key.is_some());
         assert!(priv_key.r_key.is_some());
     }
 
+    #[test]
+    fn issuer_new_credential_def_from_pool_works() {
+        MockHelper::inject();
+
+        let key_pool = IssuerKeyPool::new(1).unwrap();
+        key_pool.fill().unwrap();
+
+        let (pub_key, priv_key, _) = Issuer::new_credential_def_from_pool(&mocks::credential_schema(), false, &key_pool).unwrap();
+        assert_eq!(pub_key.p_key, mocks::credential_primary_public_key());
+        assert_eq!(priv_key.p_key, mocks::credential_primary_private_key());
+        assert!(key_pool.is_empty());
+    }
+
+    #[test]
+    fn issuer_new_credential_def_from_pool_works_for_other_prime_size() {
+        let key_pool = IssuerKeyPool::new_with_size(1, 128).unwrap();
+        key_pool.add(SafePrimePair::from_primes(BigNumber::generate_safe_prime(128).unwrap(),
+                                                BigNumber::generate_safe_prime(128).unwrap()).unwrap()).unwrap();
+
+        assert!(Issuer::new_credential_def_from_pool(&mocks::credential_schema(), false, &key_pool).is_err());
+    }
+
     #[test]
     fn issuer_new_credential_def_works_without_revocation_part() {
         MockHelper::inject();
@@ -1036,13 +2373,119 @@ mod tests {
         assert!(priv_key.r_key.is_none());
     }
 
+    #[test]
+    fn issuer_new_credential_def_with_seed_works() {
+        let seed = b"0123456789abcdef0123456789abcdef";
+
+        let (pub_key1, priv_key1, _) = Issuer::new_credential_def_with_seed(&mocks::credential_schema(), true, seed).unwrap();
+        let (pub_key2, priv_key2, _) = Issuer::new_credential_def_with_seed(&mocks::credential_schema(), true, seed).unwrap();
+        assert_eq!(pub_key1.p_key, pub_key2.p_key);
+        assert_eq!(priv_key1.p_key, priv_key2.p_key);
+        assert_eq!(pub_key1.r_key.unwrap().g, pub_key2.r_key.unwrap().g);
+
+        let (pub_key3, _, _) = Issuer::new_credential_def_with_seed(&mocks::credential_schema(), false, b"fedcba9876543210fedcba9876543210").unwrap();
+        assert_ne!(pub_key1.p_key, pub_key3.p_key);
+    }
+
+    #[test]
+    fn issuer_new_credential_def_with_seed_works_for_short_seed() {
+        assert!(Issuer::new_credential_def_with_seed(&mocks::credential_schema(), false, b"short").is_err());
+    }
+
     #[test]
     fn issuer_new_credential_works_for_empty_attributes() {
-        let cred_attrs = CredentialSchema { attrs: HashSet::new() };
+        let cred_attrs = CredentialSchema { attrs: HashSet::new(), attr_canonicalization: AttrNameCanonicalization::Exact };
         let res = Issuer::new_credential_def(&cred_attrs, false);
         assert!(res.is_err())
     }
 
+    #[test]
+    fn issuer_extend_credential_def_works() {
+        MockHelper::inject();
+
+        let (pub_key, priv_key, key_correctness_proof) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+
+        let mut new_attrs_builder = Issuer::new_credential_schema_builder().unwrap();
+        new_attrs_builder.add_attr("weight").unwrap();
+        let new_attrs = new_attrs_builder.finalize().unwrap();
+
+        let (ext_pub_key, ext_priv_key, ext_key_correctness_proof) =
+            Issuer::extend_credential_def(&pub_key, &priv_key, &key_correctness_proof, &new_attrs).unwrap();
+
+        assert!(ext_pub_key.p_key.r.contains_key("weight"));
+        for (attr, r) in pub_key.p_key.r.iter() {
+            assert_eq!(r, &ext_pub_key.p_key.r[attr]);
+        }
+        assert_eq!(pub_key.p_key.z, ext_pub_key.p_key.z);
+        assert_eq!(priv_key.p_key, ext_priv_key.p_key);
+        assert_eq!(1, ext_key_correctness_proof.extensions.len());
+
+        Prover::blind_master_secret(&ext_pub_key,
+                                    &ext_key_correctness_proof,
+                                    &prover::mocks::master_secret(),
+                                    &new_nonce().unwrap()).unwrap();
+
+        assert!(Prover::blind_master_secret(&ext_pub_key,
+                                            &key_correctness_proof,
+                                            &prover::mocks::master_secret(),
+                                            &new_nonce().unwrap()).is_err());
+    }
+
+    #[test]
+    fn issuer_extend_credential_def_works_for_existing_attribute() {
+        MockHelper::inject();
+
+        let (pub_key, priv_key, key_correctness_proof) = Issuer::new_credential_def(&mocks::credential_schema(), false).unwrap();
+        let res = Issuer::extend_credential_def(&pub_key, &priv_key, &key_correctness_proof, &mocks::credential_schema());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn issuer_new_credential_def_works_for_too_many_attributes() {
+        let cred_attrs = CredentialSchema {
+            attrs: (0..max_attrs_count() + 1).map(|i| format!("attr{}", i)).collect(),
+            attr_canonicalization: AttrNameCanonicalization::Exact
+        };
+
+        match Issuer::new_credential_def(&cred_attrs, false) {
+            Err(IndyCryptoError::AnoncredsTooManyAttributes(_)) => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    /*
+    Credential definition generation time for max attributes count (256) with mocked primes
+    and correctness proof checking time.
+    */
+    #[test]
+    fn issuer_new_credential_def_works_for_max_attributes_count() {
+        MockHelper::inject();
+
+        let cred_attrs = CredentialSchema {
+            attrs: (0..max_attrs_count()).map(|i| format!("attr{}", i)).collect(),
+            attr_canonicalization: AttrNameCanonicalization::Exact
+        };
+
+        let start_time = time::get_time();
+
+        let (pub_key, _, key_correctness_proof) = Issuer::new_credential_def(&cred_attrs, false).unwrap();
+
+        let end_time = time::get_time();
+
+        println!("Create CredentialDefinition for {} attributes Time: {:?}", max_attrs_count(), end_time - start_time);
+
+        let start_time = time::get_time();
+
+        Prover::blind_master_secret(&pub_key,
+                                    &key_correctness_proof,
+                                    &prover::mocks::master_secret(),
+                                    &new_nonce().unwrap()).unwrap();
+
+        let end_time = time::get_time();
+
+        println!("Check CredentialKeyCorrectnessProof for {} attributes Time: {:?}", max_attrs_count(), end_time - start_time);
+    }
+
     #[test]
     fn issuer_new_revocation_registry_def_works() {
         MockHelper::inject();
@@ -1051,6 +2494,201 @@ mod tests {
         Issuer::new_revocation_registry_def(&pub_key, 100, false).unwrap();
     }
 
+    #[test]
+    fn issuer_new_revocation_registry_def_works_for_invalid_max_cred_num() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        assert!(Issuer::new_revocation_registry_def(&pub_key, 0, false).is_err());
+        assert!(Issuer::new_revocation_registry_def(&pub_key, ::std::u64::MAX, false).is_err());
+    }
+
+    #[test]
+    fn revoke_credential_works_for_index_out_of_range() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, true).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        assert!(Issuer::revoke_credential(&mut rev_reg, max_cred_num, 0, &lazy_tails_accessor).is_err());
+        assert!(Issuer::revoke_credential(&mut rev_reg, max_cred_num, max_cred_num + 1, &lazy_tails_accessor).is_err());
+        assert!(Issuer::recovery_credential(&mut rev_reg, max_cred_num, max_cred_num + 1, &lazy_tails_accessor).is_err());
+        Issuer::revoke_credential(&mut rev_reg, max_cred_num, max_cred_num, &lazy_tails_accessor).unwrap();
+    }
+
+    #[test]
+    fn split_and_merge_revocation_registry_works() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, true).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        let issued: HashSet<u64> = (1..max_cred_num + 1).collect();
+        let moved: HashSet<u64> = hashset![2, 4];
+        let remaining: HashSet<u64> = hashset![1, 3, 5];
+
+        let initial_accum = rev_reg.accum;
+        let witness = Witness::new(2, max_cred_num,
+                                   &RevocationRegistryDelta::from_parts(None, &rev_reg, &issued, &HashSet::new()),
+                                   &lazy_tails_accessor).unwrap();
+
+        let (new_rev_reg, _, migration_delta) =
+            Issuer::split_revocation_registry(&mut rev_reg, max_cred_num, &issued, &moved, &lazy_tails_accessor).unwrap();
+
+        let moved_witness = witness.translate(2, max_cred_num, &migration_delta, &lazy_tails_accessor).unwrap();
+        let expected_witness = Witness::new(2, max_cred_num,
+                                            &RevocationRegistryDelta::from_parts(None, &new_rev_reg, &moved, &HashSet::new()),
+                                            &lazy_tails_accessor).unwrap();
+        assert_eq!(expected_witness.omega, moved_witness.omega);
+
+        assert!(Issuer::merge_revocation_registries(&mut rev_reg, &remaining, &new_rev_reg, &hashset![3, 4]).is_err());
+
+        let (_, migration_delta) = Issuer::merge_revocation_registries(&mut rev_reg, &remaining, &new_rev_reg, &moved).unwrap();
+        assert_eq!(initial_accum, rev_reg.accum);
+
+        let merged_witness = moved_witness.translate(2, max_cred_num, &migration_delta, &lazy_tails_accessor).unwrap();
+        assert_eq!(witness.omega, merged_witness.omega);
+    }
+
+    #[test]
+    fn split_revocation_registry_works_for_not_issued_index() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, false).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        assert!(Issuer::split_revocation_registry(&mut rev_reg, max_cred_num, &hashset![1], &hashset![2], &lazy_tails_accessor).is_err());
+    }
+
+    #[test]
+    fn migrate_revocation_registry_def_works() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, rev_key_priv, _, _) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, true).unwrap();
+        let issued: HashSet<u64> = hashset![1, 3, 5];
+
+        assert!(Issuer::migrate_revocation_registry_def(&pub_key, &rev_key_priv, max_cred_num, &issued, 2 * max_cred_num).is_err());
+
+        let new_max_cred_num = 2 * max_cred_num + 1;
+        let (_, new_rev_reg, new_rev_tails_generator, migration_delta) =
+            Issuer::migrate_revocation_registry_def(&pub_key, &rev_key_priv, max_cred_num, &issued, new_max_cred_num).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&new_rev_tails_generator, None).unwrap();
+
+        let mut expected_accum = Accumulator::new_inf().unwrap();
+        for rev_idx in issued.iter() {
+            lazy_tails_accessor.access_tail(new_max_cred_num + 1 - rev_idx, &mut |tail| {
+                expected_accum = expected_accum.add(tail).unwrap();
+            }).unwrap();
+        }
+        assert_eq!(expected_accum, new_rev_reg.accum);
+        assert_eq!(issued, migration_delta.issued);
+        assert!(migration_delta.prev_accum.is_none());
+    }
+
+    #[test]
+    fn revocation_registry_verify_consistency_works() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, true).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        let from = rev_reg.checkpoint().unwrap();
+
+        let mut rev_reg_deltas = Vec::new();
+        rev_reg_deltas.push(Issuer::revoke_credential(&mut rev_reg, max_cred_num, 1, &lazy_tails_accessor).unwrap());
+        rev_reg_deltas.push(Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &lazy_tails_accessor).unwrap());
+        rev_reg_deltas.push(Issuer::recovery_credential(&mut rev_reg, max_cred_num, 1, &lazy_tails_accessor).unwrap());
+
+        let to = rev_reg.checkpoint().unwrap();
+
+        assert!(RevocationRegistry::verify_consistency(&from, &to, &rev_reg_deltas, max_cred_num, &lazy_tails_accessor).unwrap());
+        assert!(RevocationRegistry::verify_consistency(&from, &from, &[], max_cred_num, &lazy_tails_accessor).unwrap());
+        assert!(!RevocationRegistry::verify_consistency(&from, &to, &rev_reg_deltas[..2], max_cred_num, &lazy_tails_accessor).unwrap());
+
+        rev_reg_deltas.swap(0, 1);
+        assert!(!RevocationRegistry::verify_consistency(&from, &to, &rev_reg_deltas, max_cred_num, &lazy_tails_accessor).unwrap());
+        rev_reg_deltas.swap(0, 1);
+
+        rev_reg_deltas[1].revoked = hashset![3];
+        assert!(!RevocationRegistry::verify_consistency(&from, &to, &rev_reg_deltas, max_cred_num, &lazy_tails_accessor).unwrap());
+    }
+
+    #[test]
+    fn compute_witnesses_batch_works() {
+        MockHelper::inject();
+
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), true).unwrap();
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, max_cred_num, true).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &lazy_tails_accessor).unwrap();
+
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &hashset![1, 3, 4, 5], &HashSet::new());
+
+        let rev_idxs = [1, 3, 5];
+        let witnesses = Issuer::compute_witnesses_batch(&rev_idxs, max_cred_num, &rev_reg_delta, &lazy_tails_accessor).unwrap();
+
+        assert_eq!(rev_idxs.len(), witnesses.len());
+        for (rev_idx, witness) in rev_idxs.iter().zip(witnesses.iter()) {
+            let expected = Witness::new(*rev_idx, max_cred_num, &rev_reg_delta, &lazy_tails_accessor).unwrap();
+            assert_eq!(expected.omega, witness.omega);
+        }
+
+        assert!(Issuer::compute_witnesses_batch(&[max_cred_num + 1], max_cred_num, &rev_reg_delta, &lazy_tails_accessor).is_err());
+    }
+
+    #[test]
+    fn sign_delta_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let mut rev_reg_delta = mocks::revocation_registry_delta();
+        assert!(!rev_reg_delta.verify_signature("rev_reg_1", &ver_key, &gen).unwrap());
+
+        Issuer::sign_delta(&mut rev_reg_delta, "rev_reg_1", &sign_key).unwrap();
+        assert!(rev_reg_delta.is_signed());
+        assert!(rev_reg_delta.verify_signature("rev_reg_1", &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn sign_delta_works_for_other_registry() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let mut rev_reg_delta = mocks::revocation_registry_delta();
+        Issuer::sign_delta(&mut rev_reg_delta, "rev_reg_1", &sign_key).unwrap();
+
+        assert!(!rev_reg_delta.verify_signature("rev_reg_2", &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn sign_delta_works_for_other_key_and_tampered_delta() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let other_ver_key = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+
+        let mut rev_reg_delta = mocks::revocation_registry_delta();
+        Issuer::sign_delta(&mut rev_reg_delta, "rev_reg_1", &sign_key).unwrap();
+        assert!(!rev_reg_delta.verify_signature("rev_reg_1", &other_ver_key, &gen).unwrap());
+
+        rev_reg_delta.issued.insert(2);
+        assert!(!rev_reg_delta.verify_signature("rev_reg_1", &ver_key, &gen).unwrap());
+    }
+
     #[test]
     fn sign_primary_credential_works() {
         MockHelper::inject();
@@ -1068,7 +2706,7 @@ mod tests {
         let expected_signature = BigNumber::from_dec("28748151213526235356806559302394713234708919908503693283861771311017778909029307989059154007823711057388221409308121224597301914007508580498985253922086489241065285193059997346332076248684330624957067344016446755572964815456056930278425883796750731908534333384959509746585564275501093362841366335955561237226624645170675067095743367895186059835073250297480315430811087601896371266213408739927940580173817412189118678276094925364341985978659550229327835510932814819830163166484857629278032552734675432915303389204079219287453130354714417551011163735621955266079226631695289893390164242695387374962452897413162593627569").unwrap();
         let expected_q = BigNumber::from_dec("62363291072105309734429421111781667277622338652614541474016228570557906784227711277508032382480694834439351345015578229222850418542973745058822742491558379363835885374702190788016205722518754261589148352959080144887818045985349619774317322203773276528345285327751288976595236600315518298828390132112243875494849058906743589411550479599132880095939710337582014885376559000168175623243474494316451725482257681430068204444716308910642965319732117037454207020297910067729648337741704358082947378729621100937295340132926246628185800807161500324382279607244112382284981206740340575810018294756514449073650757734843766249759").unwrap();
 
-        let (credential_signature, q) = Issuer::_sign_primary_credential(&pub_key, &secret_key, &context_attribute, &credential_values, &v, &BlindedMasterSecret { u: u, ur: None }, &e).unwrap();
+        let (credential_signature, q) = Issuer::_sign_primary_credential(&pub_key.p_key, &secret_key, &context_attribute, &credential_values, &v, &BlindedMasterSecret { u: u, ur: None }, &e).unwrap();
         assert_eq!(expected_signature, credential_signature);
         assert_eq!(expected_q, q);
     }
@@ -1095,6 +2733,75 @@ mod tests {
         assert_eq!(mocks::primary_credential(), credential_signature_signature.p_credential);
         assert_eq!(mocks::signature_correctness_proof(), signature_correctness_proof);
     }
+
+    #[test]
+    fn sign_credential_with_precomputed_key_works() {
+        MockHelper::inject();
+
+        let (pub_key, priv_key) = (mocks::credential_public_key(), mocks::credential_private_key());
+        let pub_key_precomputed = CredentialPublicKeyPrecomputed::new(&pub_key).unwrap();
+        let blinded_master_secret_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, blinded_master_secret_correctness_proof) =
+            (prover::mocks::blinded_master_secret(), prover::mocks::blinded_master_secret_correctness_proof());
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let (credential_signature, signature_correctness_proof) =
+            Issuer::sign_credential_with_precomputed_key("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                         &blinded_master_secret,
+                                                         &blinded_master_secret_correctness_proof,
+                                                         &blinded_master_secret_nonce,
+                                                         &credential_issuance_nonce,
+                                                         &mocks::credential_values(),
+                                                         &pub_key_precomputed,
+                                                         &priv_key).unwrap();
+
+        assert_eq!(mocks::primary_credential(), credential_signature.p_credential);
+        assert_eq!(mocks::signature_correctness_proof(), signature_correctness_proof);
+    }
+
+    struct CountingKeyProvider {
+        key: CredentialPrimaryPrivateKey,
+        calls: ::std::cell::Cell<usize>
+    }
+
+    impl PrivateKeyProvider for CountingKeyProvider {
+        fn sign_primary(&self, q: &BigNumber, e: &BigNumber, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+            self.calls.set(self.calls.get() + 1);
+            self.key.sign_primary(q, e, n)
+        }
+
+        fn prove_primary_signature(&self, q: &BigNumber, a: &BigNumber, e: &BigNumber, n: &BigNumber,
+                                   nonce: &Nonce) -> Result<SignatureCorrectnessProof, IndyCryptoError> {
+            self.calls.set(self.calls.get() + 1);
+            self.key.prove_primary_signature(q, a, e, n, nonce)
+        }
+    }
+
+    #[test]
+    fn sign_credential_with_key_provider_works() {
+        MockHelper::inject();
+
+        let pub_key = mocks::credential_public_key();
+        let key_provider = CountingKeyProvider { key: mocks::credential_primary_private_key(), calls: ::std::cell::Cell::new(0) };
+        let blinded_master_secret_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, blinded_master_secret_correctness_proof) =
+            (prover::mocks::blinded_master_secret(), prover::mocks::blinded_master_secret_correctness_proof());
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let (credential_signature, signature_correctness_proof) =
+            Issuer::sign_credential_with_key_provider("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                      &blinded_master_secret,
+                                                      &blinded_master_secret_correctness_proof,
+                                                      &blinded_master_secret_nonce,
+                                                      &credential_issuance_nonce,
+                                                      &mocks::credential_values(),
+                                                      &pub_key,
+                                                      &key_provider).unwrap();
+
+        assert_eq!(mocks::primary_credential(), credential_signature.p_credential);
+        assert_eq!(mocks::signature_correctness_proof(), signature_correctness_proof);
+        assert_eq!(2, key_provider.calls.get());
+    }
 }
 
 pub mod mocks {
@@ -1115,15 +2822,38 @@ pub mod mocks {
     }
 
     pub fn credential_key_correctness_proof() -> CredentialKeyCorrectnessProof {
+        let mut xr_cap = BTreeMap::new();
+        xr_cap.insert("age".to_string(), BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap());
+        xr_cap.insert("height".to_string(), BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap());
+        xr_cap.insert("name".to_string(), BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap());
+        xr_cap.insert("sex".to_string(), BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap());
+        CredentialKeyCorrectnessProof {
+            version: 1,
+            c: BigNumber::from_dec("21321792380807488402447173748726543342482421309499718411025557408490137648273").unwrap(),
+            xz_cap: BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap(),
+            xr_cap,
+            xrms_cap: Some(BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap()),
+            xrctxt_cap: Some(BigNumber::from_dec("463886367571244027991279724839800988989840322039512356856430831747871655207226627703956448001378889068576062360997601937881809738143291903800591537334831309571349649436390180594996209830538289753749158971180575787517481452342058729608795399061742540569477969612572029245645281907594906854514714202874741551335105693428559784574876952023282334149746949458808781473229279847714150521346954400405892254226676582194251812372375806151291104607632964574525603337040545892753556275335549881025547164157271325932479948876286917596821469708930506853661736234368049867364099982528083332194708406270585927903611987108793400505784795169541293660314178663558412835565169559817803519691473307001729092375012").unwrap()),
+            r_key_proof: None,
+            extensions: Vec::new()
+        }
+    }
+
+    pub fn legacy_credential_key_correctness_proof() -> CredentialKeyCorrectnessProof {
         let mut xr_cap = BTreeMap::new();
         xr_cap.insert("age".to_string(), BigNumber::from_dec("2516904592338755834741109659686253294038573833111694784007436928725318583812511846008369125626926444734846802781477574676417758586056981191221652551550193090228026528883764896789801561561609196747458369281991019066604817890496496747223911785592455919492222482364124608999938846731997013676214567629890883396200281694561718730776579543953507896781693145625128834339578873996732164474026368971057605849777778695637974709852923324621731782945879185901228940857180131204450954045127942319962024229750363778735892622899951269681334101290418813371990669022143438966520935712358233239571548957800946114891949225067663952404516050540641561114719747155756118840817675126563730456626147019057611648713954").unwrap());
         xr_cap.insert("height".to_string(), BigNumber::from_dec("2516904592338755834741109659686253294038573833111694784007436928725318583812511846008369125626926444734846802781477574676417758586056981191221652551550193090228026528883764896789801561561609196747458369281991019066604817890496496747223911785592455919492222482364124608999938846731997013676214567629890883396200281694561718730776579543953507896781693145625128834339578873996732164474026368971057605849777778695637974709852923324621731782945879185901228940857180131204450954045127942319962024229750363778735892622899951269681334101290418813371990669022143438966520935712358233239571548957800946114891949225067663952404516050540641561114719747155756118840817675126563730456626147019057611648713954").unwrap());
         xr_cap.insert("name".to_string(), BigNumber::from_dec("2516904592338755834741109659686253294038573833111694784007436928725318583812511846008369125626926444734846802781477574676417758586056981191221652551550193090228026528883764896789801561561609196747458369281991019066604817890496496747223911785592455919492222482364124608999938846731997013676214567629890883396200281694561718730776579543953507896781693145625128834339578873996732164474026368971057605849777778695637974709852923324621731782945879185901228940857180131204450954045127942319962024229750363778735892622899951269681334101290418813371990669022143438966520935712358233239571548957800946114891949225067663952404516050540641561114719747155756118840817675126563730456626147019057611648713954").unwrap());
         xr_cap.insert("sex".to_string(), BigNumber::from_dec("2516904592338755834741109659686253294038573833111694784007436928725318583812511846008369125626926444734846802781477574676417758586056981191221652551550193090228026528883764896789801561561609196747458369281991019066604817890496496747223911785592455919492222482364124608999938846731997013676214567629890883396200281694561718730776579543953507896781693145625128834339578873996732164474026368971057605849777778695637974709852923324621731782945879185901228940857180131204450954045127942319962024229750363778735892622899951269681334101290418813371990669022143438966520935712358233239571548957800946114891949225067663952404516050540641561114719747155756118840817675126563730456626147019057611648713954").unwrap());
         CredentialKeyCorrectnessProof {
+            version: 0,
             c: BigNumber::from_dec("115685480134110563659502023918400734311361769059518507192058954879522711620032").unwrap(),
             xz_cap: BigNumber::from_dec("2516904592338755834741109659686253294038573833111694784007436928725318583812511846008369125626926444734846802781477574676417758586056981191221652551550193090228026528883764896789801561561609196747458369281991019066604817890496496747223911785592455919492222482364124608999938846731997013676214567629890883396200281694561718730776579543953507896781693145625128834339578873996732164474026368971057605849777778695637974709852923324621731782945879185901228940857180131204450954045127942319962024229750363778735892622899951269681334101290418813371990669022143438966520935712358233239571548957800946114891949225067663952404516050540641561114719747155756118840817675126563730456626147019057611648713954").unwrap(),
-            xr_cap
+            xr_cap,
+            xrms_cap: None,
+            xrctxt_cap: None,
+            r_key_proof: None,
+            extensions: Vec::new()
         }
     }
 
@@ -1141,7 +2871,7 @@ pub mod mocks {
         let rctxt = BigNumber::from_dec("58606710922154038918005745652863947546479611221487923871520854046018234465128105585608812090213473225037875788462225679336791123783441657062831589984290779844020407065450830035885267846722229953206567087435754612694085258455822926492275621650532276267042885213400704012011608869094703483233081911010530256094461587809601298503874283124334225428746479707531278882536314925285434699376158578239556590141035593717362562548075653598376080466948478266094753818404986494459240364648986755479857098110402626477624280802323635285059064580583239726433768663879431610261724430965980430886959304486699145098822052003020688956471").unwrap();
         let z = BigNumber::from_dec("58606710922154038918005745652863947546479611221487923871520854046018234465128105585608812090213473225037875788462225679336791123783441657062831589984290779844020407065450830035885267846722229953206567087435754612694085258455822926492275621650532276267042885213400704012011608869094703483233081911010530256094461587809601298503874283124334225428746479707531278882536314925285434699376158578239556590141035593717362562548075653598376080466948478266094753818404986494459240364648986755479857098110402626477624280802323635285059064580583239726433768663879431610261724430965980430886959304486699145098822052003020688956471").unwrap();
 
-        CredentialPrimaryPublicKey { n, s, rms, r, rctxt, z }
+        CredentialPrimaryPublicKey { n, s, rms, r, rctxt, z, profile: None, without_master_secret: false }
     }
 
     pub fn credential_primary_private_key() -> CredentialPrimaryPrivateKey {
@@ -1245,6 +2975,7 @@ pub mod mocks {
     pub fn revocation_key_public() -> RevocationKeyPublic {
         RevocationKeyPublic {
             z: Pair::from_string("B0C52EBB799E8 6FC6F7D6883390 BC4244EDBC1787 FDEA974C84C1F1 234FA3A6 F411BCC525581F B238C8B10BBACB 8536CC797D203D DEFEAA1B1DBC5B 736EAC 529F008C0398B9 CD0B30B71A1F14 2D332E37CEBF1B A3D9B3319DCDAD CA1AAD2 E5B506C98D6F95 575329E5789B3B CA3A9AB8CED863 BB16612D7EDFC9 241D0C39 810C5FA05825E3 C8A863BA7721CD DCCCB939E4BC22 1817F872AA9906 E423204 C38DCA6D9C80D6 5DE52EA7CFE23E FB41FA284C112E E438D18C192C1D 88A018F EF8569C86B3916 119FE81D359A09 6D5A0088955ED3 6904F412A28BD4 11F6C539 29AD474B03EE99 D0353A66812CA7 C9763FC9EEB4A3 217160B2B8982E 10983B69 7F67C0FCFD4244 45C9665E75EC5B 4A23D9F0D1182F 3A8C685A922F6 20A176A9 883FF71EB14569 5030243F2B2B79 95A67EF0922D07 A6D74310BFE00A F8BBB21 476E55B2836798 16B49B2120D6EB 68EABD968A44DE E8DF358500A99A 15A3F96B 28749CC7A07F60 F82B17A0CA933F EE4166241C77F2 9BE2BB4B802250 19F0D85E").unwrap(),
+            mode: RevocationRegistryMode::Membership
         }
     }
 
@@ -1254,11 +2985,12 @@ pub mod mocks {
 
     pub fn revocation_registry() -> RevocationRegistry {
         RevocationRegistry {
-            accum: accumulator()
+            accum: accumulator(),
+            mode: RevocationRegistryMode::Membership
         }
     }
 
-    pub fn max_cred_num() -> u32 {
+    pub fn max_cred_num() -> u64 {
         5
     }
 
@@ -1267,7 +2999,9 @@ pub mod mocks {
             prev_accum: None,
             accum: accumulator(),
             issued: hashset![1],
-            revoked: HashSet::new()
+            revoked: HashSet::new(),
+            signature: None,
+            mode: RevocationRegistryMode::Membership
         }
     }
 
@@ -0,0 +1,448 @@
+use bn::BigNumber;
+use cl::constants::LARGE_PRIME;
+use cl::helpers::generate_safe_prime;
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use serde::{Deserialize, Deserializer};
+use serde::de::Error as DError;
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// Pair of safe primes `p' = 2p + 1`, `q' = 2q + 1` that defines credential primary key modulus `n = p'q'`.
+/// Safe primes are the factorization of `n`, so pair must be kept as secret as credential private key.
+/// Deserialized pair is checked to be valid as pair created with `from_primes`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SafePrimePair {
+    p_safe: BigNumber,
+    q_safe: BigNumber
+}
+
+impl SafePrimePair {
+    /// Generates new pair of safe primes of credential primary key size.
+    pub fn new() -> Result<SafePrimePair, IndyCryptoError> {
+        SafePrimePair::new_with_size(LARGE_PRIME)
+    }
+
+    /// Generates new pair of safe primes of given size in bits.
+    pub fn new_with_size(size: usize) -> Result<SafePrimePair, IndyCryptoError> {
+        Ok(SafePrimePair {
+            p_safe: generate_safe_prime(size)?,
+            q_safe: generate_safe_prime(size)?
+        })
+    }
+
+    /// Creates pair from already generated distinct safe primes of the same size.
+    pub fn from_primes(p_safe: BigNumber, q_safe: BigNumber) -> Result<SafePrimePair, IndyCryptoError> {
+        let pair = SafePrimePair { p_safe, q_safe };
+
+        if !pair.is_valid(pair.prime_bits()?)? {
+            return Err(IndyCryptoError::InvalidStructure("Invalid safe prime pair".to_string()));
+        }
+
+        Ok(pair)
+    }
+
+    pub fn p_safe(&self) -> &BigNumber {
+        &self.p_safe
+    }
+
+    pub fn q_safe(&self) -> &BigNumber {
+        &self.q_safe
+    }
+
+    /// Size of safe primes in bits, as passed to `new_with_size` (size of `(p' - 1) / 2`).
+    pub fn prime_bits(&self) -> Result<usize, IndyCryptoError> {
+        Ok(self.p_safe.num_bits()? as usize - 1)
+    }
+
+    /// Checks that numbers of the pair are distinct safe primes of given size.
+    ///
+    /// # Arguments
+    /// * `prime_bits` - Size of safe primes in bits, e.g. `SecurityProfile::prime_bits`.
+    pub fn is_valid(&self, prime_bits: usize) -> Result<bool, IndyCryptoError> {
+        if self.p_safe == self.q_safe ||
+            self.p_safe.num_bits()? as usize != prime_bits + 1 ||
+            self.q_safe.num_bits()? as usize != prime_bits + 1 {
+            return Ok(false);
+        }
+
+        Ok(SafePrimePair::_is_safe_prime(&self.p_safe)? && SafePrimePair::_is_safe_prime(&self.q_safe)?)
+    }
+
+    pub fn clone(&self) -> Result<SafePrimePair, IndyCryptoError> {
+        Ok(SafePrimePair {
+            p_safe: self.p_safe.clone()?,
+            q_safe: self.q_safe.clone()?
+        })
+    }
+
+    fn _is_safe_prime(prime: &BigNumber) -> Result<bool, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        if !prime.is_prime(Some(&mut ctx))? {
+            return Ok(false);
+        }
+
+        let mut q = prime.sub(&BigNumber::from_u32(1)?)?;
+        q.div_word(2)?;
+
+        q.is_prime(Some(&mut ctx))
+    }
+}
+
+impl<'a> Deserialize<'a> for SafePrimePair {
+    fn deserialize<D>(deserializer: D) -> Result<SafePrimePair, D::Error> where D: Deserializer<'a> {
+        #[derive(Deserialize)]
+        struct SafePrimePairData {
+            p_safe: BigNumber,
+            q_safe: BigNumber
+        }
+
+        let data = SafePrimePairData::deserialize(deserializer)?;
+
+        SafePrimePair::from_primes(data.p_safe, data.q_safe).map_err(DError::custom)
+    }
+}
+
+impl JsonEncodable for SafePrimePair {}
+
+impl<'a> JsonDecodable<'a> for SafePrimePair {}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct IssuerKeyPoolState {
+    #[serde(default = "default_prime_bits")]
+    prime_bits: usize,
+    pairs: Vec<SafePrimePair>
+}
+
+fn default_prime_bits() -> usize {
+    LARGE_PRIME
+}
+
+impl JsonEncodable for IssuerKeyPoolState {}
+
+impl<'a> JsonDecodable<'a> for IssuerKeyPoolState {}
+
+/// Pool of safe prime pairs generated in advance,
+/// so `Issuer::new_credential_def_from_pool` doesn't stall on prime generation.
+///
+/// Pool can be filled synchronously with `fill` or by background worker started with `start`.
+/// Background worker takes randomness from process wide RNG.
+pub struct IssuerKeyPool {
+    capacity: usize,
+    prime_bits: usize,
+    pairs: Arc<Mutex<VecDeque<SafePrimePair>>>,
+    stopped: Arc<AtomicBool>,
+    worker: Option<thread::JoinHandle<()>>
+}
+
+impl IssuerKeyPool {
+    /// Creates empty pool.
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximal count of safe prime pairs kept in the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::key_pool::IssuerKeyPool;
+    ///
+    /// let pool = IssuerKeyPool::new(2).unwrap();
+    /// assert!(pool.is_empty());
+    /// ```
+    pub fn new(capacity: usize) -> Result<IssuerKeyPool, IndyCryptoError> {
+        IssuerKeyPool::new_with_size(capacity, LARGE_PRIME)
+    }
+
+    /// Creates empty pool of safe prime pairs of given size in bits.
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximal count of safe prime pairs kept in the pool.
+    /// * `prime_bits` - Size of safe primes in bits, e.g. `SecurityProfile::prime_bits`.
+    pub fn new_with_size(capacity: usize, prime_bits: usize) -> Result<IssuerKeyPool, IndyCryptoError> {
+        if capacity == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Key pool capacity must be positive".to_string()));
+        }
+
+        Ok(IssuerKeyPool {
+            capacity,
+            prime_bits,
+            pairs: Arc::new(Mutex::new(VecDeque::new())),
+            stopped: Arc::new(AtomicBool::new(true)),
+            worker: None
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn prime_bits(&self) -> usize {
+        self.prime_bits
+    }
+
+    /// Returns count of safe prime pairs available in the pool.
+    pub fn len(&self) -> usize {
+        IssuerKeyPool::_lock(&self.pairs).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds safe prime pair of pool size to the pool.
+    pub fn add(&self, pair: SafePrimePair) -> Result<(), IndyCryptoError> {
+        if pair.prime_bits()? != self.prime_bits {
+            return Err(IndyCryptoError::InvalidStructure(format!("Safe primes of pair must be {} bits", self.prime_bits)));
+        }
+
+        let mut pairs = IssuerKeyPool::_lock(&self.pairs);
+
+        if pairs.len() >= self.capacity {
+            return Err(IndyCryptoError::InvalidState("Key pool is full".to_string()));
+        }
+
+        pairs.push_back(pair);
+        Ok(())
+    }
+
+    /// Takes safe prime pair from the pool. Every pair is returned only once.
+    pub fn take(&self) -> Option<SafePrimePair> {
+        let pair = IssuerKeyPool::_lock(&self.pairs).pop_front();
+
+        if let Some(ref worker) = self.worker {
+            worker.thread().unpark();
+        }
+
+        pair
+    }
+
+    /// Generates safe prime pairs in the current thread until pool is full.
+    pub fn fill(&self) -> Result<(), IndyCryptoError> {
+        trace!("IssuerKeyPool::fill: >>> len: {:?}, capacity: {:?}", self.len(), self.capacity);
+
+        while self.len() < self.capacity {
+            let pair = SafePrimePair::new_with_size(self.prime_bits)?;
+            IssuerKeyPool::_push(&self.pairs, self.capacity, pair);
+        }
+
+        trace!("IssuerKeyPool::fill: <<<");
+
+        Ok(())
+    }
+
+    /// Starts background worker that keeps pool full.
+    pub fn start(&mut self) -> Result<(), IndyCryptoError> {
+        trace!("IssuerKeyPool::start: >>> capacity: {:?}", self.capacity);
+
+        if self.worker.is_some() {
+            return Err(IndyCryptoError::InvalidState("Key pool worker is already started".to_string()));
+        }
+
+        self.stopped.store(false, Ordering::SeqCst);
+
+        let capacity = self.capacity;
+        let prime_bits = self.prime_bits;
+        let pairs = self.pairs.clone();
+        let stopped = self.stopped.clone();
+
+        let worker = thread::Builder::new()
+            .name("indy-crypto-key-pool".to_string())
+            .spawn(move || {
+                while !stopped.load(Ordering::SeqCst) {
+                    if IssuerKeyPool::_lock(&pairs).len() >= capacity {
+                        thread::park();
+                        continue;
+                    }
+
+                    match SafePrimePair::new_with_size(prime_bits) {
+                        Ok(pair) => IssuerKeyPool::_push(&pairs, capacity, pair),
+                        Err(err) => {
+                            error!("Key pool worker is stopped: {}", err);
+                            break;
+                        }
+                    }
+                }
+            })
+            .map_err(|err| IndyCryptoError::IOError(err))?;
+
+        self.worker = Some(worker);
+
+        trace!("IssuerKeyPool::start: <<<");
+
+        Ok(())
+    }
+
+    /// Stops background worker. Waits for generation of the current pair to finish.
+    pub fn stop(&mut self) -> Result<(), IndyCryptoError> {
+        trace!("IssuerKeyPool::stop: >>>");
+
+        self.stopped.store(true, Ordering::SeqCst);
+
+        if let Some(worker) = self.worker.take() {
+            worker.thread().unpark();
+            worker.join()
+                .map_err(|_| IndyCryptoError::InvalidState("Key pool worker panicked".to_string()))?;
+        }
+
+        trace!("IssuerKeyPool::stop: <<<");
+
+        Ok(())
+    }
+
+    /// Serializes safe prime pairs of the pool, so they can be restored with `from_json` after restart.
+    /// Result contains factorization of future credential keys and must be stored encrypted.
+    pub fn to_json(&self) -> Result<String, IndyCryptoError> {
+        let pairs = IssuerKeyPool::_lock(&self.pairs)
+            .iter()
+            .map(|pair| pair.clone())
+            .collect::<Result<Vec<SafePrimePair>, IndyCryptoError>>()?;
+
+        IssuerKeyPoolState { prime_bits: self.prime_bits, pairs }.to_json()
+    }
+
+    /// Restores pool persisted by `to_json`. Every restored pair is checked to be distinct safe primes of pool size.
+    ///
+    /// # Arguments
+    /// * `json` - Persisted safe prime pairs.
+    /// * `capacity` - Maximal count of safe prime pairs kept in the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::key_pool::IssuerKeyPool;
+    ///
+    /// let pool = IssuerKeyPool::new(2).unwrap();
+    /// let restored_pool = IssuerKeyPool::from_json(&pool.to_json().unwrap(), 2).unwrap();
+    /// assert!(restored_pool.is_empty());
+    /// ```
+    pub fn from_json(json: &str, capacity: usize) -> Result<IssuerKeyPool, IndyCryptoError> {
+        trace!("IssuerKeyPool::from_json: >>> capacity: {:?}", capacity);
+
+        // Pairs are validated on deserialization, pool checks their size
+        let state = IssuerKeyPoolState::from_json(json)?;
+        let pool = IssuerKeyPool::new_with_size(capacity, state.prime_bits)?;
+
+        for pair in state.pairs {
+            pool.add(pair)?;
+        }
+
+        trace!("IssuerKeyPool::from_json: <<< len: {:?}", pool.len());
+
+        Ok(pool)
+    }
+
+    fn _lock(pairs: &Mutex<VecDeque<SafePrimePair>>) -> MutexGuard<VecDeque<SafePrimePair>> {
+        pairs.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn _push(pairs: &Mutex<VecDeque<SafePrimePair>>, capacity: usize, pair: SafePrimePair) {
+        let mut pairs = IssuerKeyPool::_lock(pairs);
+        if pairs.len() < capacity {
+            pairs.push_back(pair);
+        }
+    }
+}
+
+impl Drop for IssuerKeyPool {
+    fn drop(&mut self) {
+        self.stop().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIME_BITS: usize = 128;
+
+    fn _safe_prime_pair() -> SafePrimePair {
+        SafePrimePair::from_primes(BigNumber::generate_safe_prime(PRIME_BITS).unwrap(),
+                                   BigNumber::generate_safe_prime(PRIME_BITS).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn issuer_key_pool_works() {
+        let pool = IssuerKeyPool::new_with_size(2, PRIME_BITS).unwrap();
+
+        let pair = _safe_prime_pair();
+        pool.add(pair.clone().unwrap()).unwrap();
+        pool.add(_safe_prime_pair()).unwrap();
+        assert!(pool.add(_safe_prime_pair()).is_err());
+
+        assert_eq!(2, pool.len());
+        assert_eq!(pair, pool.take().unwrap());
+        assert_eq!(1, pool.len());
+    }
+
+    #[test]
+    fn issuer_key_pool_works_for_zero_capacity() {
+        assert!(IssuerKeyPool::new(0).is_err());
+    }
+
+    #[test]
+    fn issuer_key_pool_persistence_works() {
+        let pool = IssuerKeyPool::new_with_size(2, PRIME_BITS).unwrap();
+        pool.add(_safe_prime_pair()).unwrap();
+        pool.add(_safe_prime_pair()).unwrap();
+
+        let restored_pool = IssuerKeyPool::from_json(&pool.to_json().unwrap(), 2).unwrap();
+        assert_eq!(2, restored_pool.len());
+        assert_eq!(pool.take().unwrap(), restored_pool.take().unwrap());
+        assert_eq!(pool.take().unwrap(), restored_pool.take().unwrap());
+    }
+
+    #[test]
+    fn issuer_key_pool_persistence_works_for_invalid_pair() {
+        let pair = SafePrimePair {
+            p_safe: BigNumber::from_u32(15).unwrap(),
+            q_safe: BigNumber::from_u32(23).unwrap()
+        };
+        let json = IssuerKeyPoolState { prime_bits: 4, pairs: vec![pair] }.to_json().unwrap();
+
+        assert!(IssuerKeyPool::from_json(&json, 2).is_err());
+    }
+
+    #[test]
+    fn issuer_key_pool_works_for_other_size() {
+        let pool = IssuerKeyPool::new(2).unwrap();
+        assert!(pool.add(_safe_prime_pair()).is_err());
+    }
+
+    #[test]
+    fn issuer_key_pool_persistence_works_for_other_size() {
+        let pool = IssuerKeyPool::new_with_size(1, PRIME_BITS).unwrap();
+        pool.add(_safe_prime_pair()).unwrap();
+
+        let json = pool.to_json().unwrap().replace(&format!("\"prime_bits\":{}", PRIME_BITS), "\"prime_bits\":1024");
+
+        assert!(IssuerKeyPool::from_json(&json, 1).is_err());
+    }
+
+    #[test]
+    fn safe_prime_pair_from_primes_works_for_not_safe_prime() {
+        assert!(SafePrimePair::from_primes(BigNumber::from_u32(13).unwrap(), BigNumber::from_u32(23).unwrap()).is_err());
+    }
+
+    #[test]
+    fn safe_prime_pair_from_primes_works_for_same_primes() {
+        let prime = BigNumber::generate_safe_prime(PRIME_BITS).unwrap();
+        assert!(SafePrimePair::from_primes(prime.clone().unwrap(), prime).is_err());
+    }
+
+    #[test]
+    fn safe_prime_pair_from_primes_works_for_different_sizes() {
+        assert!(SafePrimePair::from_primes(BigNumber::generate_safe_prime(PRIME_BITS).unwrap(),
+                                           BigNumber::generate_safe_prime(PRIME_BITS + 8).unwrap()).is_err());
+    }
+
+    #[test]
+    fn safe_prime_pair_from_json_works_for_same_primes() {
+        let prime = BigNumber::generate_safe_prime(PRIME_BITS).unwrap();
+        let pair = SafePrimePair { p_safe: prime.clone().unwrap(), q_safe: prime };
+
+        assert!(SafePrimePair::from_json(&pair.to_json().unwrap()).is_err());
+    }
+}
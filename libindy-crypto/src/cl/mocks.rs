@@ -0,0 +1,362 @@
+//! Random credentials, proof requests and revocation states for property-based tests.
+//!
+//! Strategies generate plain data (`ProofScenario`, `ProofMutation`), so failing cases are shrunk
+//! and printed readably; `ProofScenario::prove` turns scenario into real keys, credential and proof.
+use bn::BigNumber;
+use cl::*;
+use cl::issuer::Issuer;
+use cl::key_pool::{IssuerKeyPool, SafePrimePair};
+use cl::prover::Prover;
+use cl::verifier::Verifier;
+use errors::IndyCryptoError;
+
+use proptest::prelude::*;
+use proptest::collection::{btree_map, vec};
+use proptest::option;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+const MAX_ATTRS: usize = 6;
+const MAX_CRED_NUM: u64 = 6;
+
+// Pregenerated distinct safe primes of credential key size, so generation of primes doesn't dominate test time.
+// `P_SAFE_PRIME` is also used by helpers mocks.
+const P_SAFE_PRIME: &'static str = "298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623";
+const Q_SAFE_PRIME: &'static str = "282895826466241988230985986853186373443123140573599345062573527426862260886867003965135047532757886356165632567987900575264430012443822256209532715298811096222623420820678326696512714340697917061572738467805055425814047554147957108677795276699377356612978374519427284360883195504639492819477371434289610878679";
+
+/// Credential content and sub proof request over it. All predicates are satisfied by values.
+#[derive(Debug, Clone)]
+pub struct ProofScenario {
+    pub values: BTreeMap<String, String>,
+    pub revealed_attrs: BTreeSet<String>,
+    pub predicates: BTreeMap<String, i32>,
+    pub revocation: Option<RevocationScenario>
+}
+
+/// State of revocation registry at the moment of proving.
+/// Indexes in `others` are revoked for issuance by default and issued for issuance on demand.
+#[derive(Debug, Clone)]
+pub struct RevocationScenario {
+    pub max_cred_num: u64,
+    pub rev_idx: u64,
+    pub issuance_by_default: bool,
+    pub others: BTreeSet<u64>
+}
+
+/// Real keys, credential and proof created for `ProofScenario`.
+pub struct ProofFixture {
+    pub credential_schema: CredentialSchema,
+    pub credential_pub_key: CredentialPublicKey,
+    pub sub_proof_request: SubProofRequest,
+    pub rev_key_pub: Option<RevocationKeyPublic>,
+    pub rev_reg: Option<RevocationRegistry>,
+    pub proof: Proof,
+    pub nonce: Nonce
+}
+
+/// Change of single proof value that must make proof invalid.
+#[derive(Debug, Clone)]
+pub enum ProofMutation {
+    CHash,
+    CList(usize),
+    RevealedAttr(usize),
+    HiddenAttr(usize),
+    EqProofValue(usize),
+    GeProofValue(usize),
+    Nonce
+}
+
+fn attr_name() -> BoxedStrategy<String> {
+    "[a-z][a-z0-9_]{0,7}".boxed()
+}
+
+// Small values can be used in predicates, large ones look like encoded strings.
+fn attr_value() -> BoxedStrategy<String> {
+    prop_oneof![
+        (0..i32::max_value()).prop_map(|value| value.to_string()),
+        "[1-9][0-9]{10,70}"
+    ].boxed()
+}
+
+pub fn revocation_scenario() -> BoxedStrategy<RevocationScenario> {
+    (1..MAX_CRED_NUM + 1, any::<bool>())
+        .prop_flat_map(|(max_cred_num, issuance_by_default)| {
+            (Just(max_cred_num), 1..max_cred_num + 1, Just(issuance_by_default), vec(any::<bool>(), max_cred_num as usize))
+        })
+        .prop_map(|(max_cred_num, rev_idx, issuance_by_default, other_flags)| {
+            let others = other_flags.iter()
+                .enumerate()
+                .filter(|&(idx, flag)| *flag && idx as u64 + 1 != rev_idx)
+                .map(|(idx, _)| idx as u64 + 1)
+                .collect();
+
+            RevocationScenario { max_cred_num, rev_idx, issuance_by_default, others }
+        })
+        .boxed()
+}
+
+pub fn proof_scenario() -> BoxedStrategy<ProofScenario> {
+    btree_map(attr_name(), attr_value(), 1..MAX_ATTRS + 1)
+        .prop_flat_map(|values| {
+            let attrs_count = values.len();
+            (Just(values),
+             vec(any::<bool>(), attrs_count),
+             vec(option::of(any::<u32>()), attrs_count),
+             option::of(revocation_scenario()))
+        })
+        .prop_map(|(values, reveal_flags, predicate_seeds, revocation)| {
+            let mut revealed_attrs = BTreeSet::new();
+            let mut predicates = BTreeMap::new();
+
+            for (((attr, value), reveal), predicate_seed) in values.iter().zip(reveal_flags).zip(predicate_seeds) {
+                if reveal {
+                    revealed_attrs.insert(attr.clone());
+                    continue;
+                }
+
+                if let (Some(seed), Ok(value)) = (predicate_seed, value.parse::<i32>()) {
+                    predicates.insert(attr.clone(), (seed % (value as u32 + 1)) as i32);
+                }
+            }
+
+            ProofScenario { values, revealed_attrs, predicates, revocation }
+        })
+        .boxed()
+}
+
+pub fn proof_mutation() -> BoxedStrategy<ProofMutation> {
+    prop_oneof![
+        Just(ProofMutation::CHash),
+        any::<usize>().prop_map(ProofMutation::CList),
+        any::<usize>().prop_map(ProofMutation::RevealedAttr),
+        any::<usize>().prop_map(ProofMutation::HiddenAttr),
+        any::<usize>().prop_map(ProofMutation::EqProofValue),
+        any::<usize>().prop_map(ProofMutation::GeProofValue),
+        Just(ProofMutation::Nonce)
+    ].boxed()
+}
+
+impl ProofScenario {
+    pub fn credential_schema(&self) -> Result<CredentialSchema, IndyCryptoError> {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new()?;
+        for attr in self.values.keys() {
+            credential_schema_builder.add_attr(attr)?;
+        }
+        credential_schema_builder.finalize()
+    }
+
+    pub fn credential_values(&self) -> Result<CredentialValues, IndyCryptoError> {
+        let mut credential_values_builder = CredentialValuesBuilder::new()?;
+        for (attr, value) in self.values.iter() {
+            credential_values_builder.add_value(attr, value)?;
+        }
+        credential_values_builder.finalize()
+    }
+
+    pub fn sub_proof_request(&self) -> Result<SubProofRequest, IndyCryptoError> {
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new()?;
+        for attr in self.revealed_attrs.iter() {
+            sub_proof_request_builder.add_revealed_attr(attr)?;
+        }
+        for (attr, value) in self.predicates.iter() {
+            sub_proof_request_builder.add_predicate(attr, "GE", *value)?;
+        }
+        sub_proof_request_builder.finalize()
+    }
+
+    /// Issues credential described by scenario and creates proof for its sub proof request.
+    pub fn prove(&self) -> Result<ProofFixture, IndyCryptoError> {
+        let credential_schema = self.credential_schema()?;
+        let credential_values = self.credential_values()?;
+        let sub_proof_request = self.sub_proof_request()?;
+
+        let key_pool = IssuerKeyPool::new(1)?;
+        key_pool.add(SafePrimePair::from_primes(BigNumber::from_dec(P_SAFE_PRIME)?, BigNumber::from_dec(Q_SAFE_PRIME)?)?)?;
+
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def_from_pool(&credential_schema, self.revocation.is_some(), &key_pool)?;
+
+        let master_secret = Prover::new_master_secret()?;
+        let master_secret_blinding_nonce = new_nonce()?;
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key, &credential_key_correctness_proof, &master_secret, &master_secret_blinding_nonce)?;
+        let credential_issuance_nonce = new_nonce()?;
+
+        let (credential_signature, rev_key_pub, rev_reg, witness) = match self.revocation {
+            None => {
+                let (mut credential_signature, signature_correctness_proof) =
+                    Issuer::sign_credential(PROVER_ID,
+                                            &blinded_master_secret,
+                                            &blinded_master_secret_correctness_proof,
+                                            &master_secret_blinding_nonce,
+                                            &credential_issuance_nonce,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            &credential_priv_key)?;
+
+                Prover::process_credential_signature(&mut credential_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &credential_pub_key,
+                                                     &credential_issuance_nonce,
+                                                     None, None, None)?;
+
+                (credential_signature, None, None, None)
+            }
+            Some(ref revocation) => {
+                let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+                    Issuer::new_revocation_registry_def(&credential_pub_key, revocation.max_cred_num, revocation.issuance_by_default)?;
+                let tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator)?;
+
+                let (mut credential_signature, signature_correctness_proof, _) =
+                    Issuer::sign_credential_with_revoc(PROVER_ID,
+                                                       &blinded_master_secret,
+                                                       &blinded_master_secret_correctness_proof,
+                                                       &master_secret_blinding_nonce,
+                                                       &credential_issuance_nonce,
+                                                       &credential_values,
+                                                       &credential_pub_key,
+                                                       &credential_priv_key,
+                                                       revocation.rev_idx,
+                                                       revocation.max_cred_num,
+                                                       revocation.issuance_by_default,
+                                                       &mut rev_reg,
+                                                       &rev_key_priv,
+                                                       &tails_accessor)?;
+
+                for &idx in revocation.others.iter() {
+                    if revocation.issuance_by_default {
+                        Issuer::revoke_credential(&mut rev_reg, revocation.max_cred_num, idx, &tails_accessor)?;
+                    } else {
+                        Issuer::sign_credential_with_revoc(PROVER_ID,
+                                                           &blinded_master_secret,
+                                                           &blinded_master_secret_correctness_proof,
+                                                           &master_secret_blinding_nonce,
+                                                           &new_nonce()?,
+                                                           &credential_values,
+                                                           &credential_pub_key,
+                                                           &credential_priv_key,
+                                                           idx,
+                                                           revocation.max_cred_num,
+                                                           revocation.issuance_by_default,
+                                                           &mut rev_reg,
+                                                           &rev_key_priv,
+                                                           &tails_accessor)?;
+                    }
+                }
+
+                // Witness is computed from the final state of registry
+                let issued: HashSet<u64> = (1..revocation.max_cred_num + 1)
+                    .filter(|idx| *idx == revocation.rev_idx || revocation.others.contains(idx) != revocation.issuance_by_default)
+                    .collect();
+
+                let rev_reg_delta = RevocationRegistryDelta {
+                    prev_accum: None,
+                    accum: rev_reg.accum,
+                    issued,
+                    revoked: HashSet::new(),
+                    signature: None,
+                    mode: RevocationRegistryMode::Membership
+                };
+
+                let witness = Witness::new(revocation.rev_idx, revocation.max_cred_num, &rev_reg_delta, &tails_accessor)?;
+
+                Prover::process_credential_signature(&mut credential_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &credential_pub_key,
+                                                     &credential_issuance_nonce,
+                                                     Some(&rev_key_pub),
+                                                     Some(&rev_reg),
+                                                     Some(&witness))?;
+
+                (credential_signature, Some(rev_key_pub), Some(rev_reg), Some(witness))
+            }
+        };
+
+        let nonce = new_nonce()?;
+
+        let mut proof_builder = Prover::new_proof_builder()?;
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            rev_reg.as_ref(),
+                                            witness.as_ref())?;
+        let proof = proof_builder.finalize(&nonce, &master_secret)?;
+
+        Ok(ProofFixture { credential_schema, credential_pub_key, sub_proof_request, rev_key_pub, rev_reg, proof, nonce })
+    }
+}
+
+impl ProofFixture {
+    pub fn verify(&self, proof: &Proof, nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        let mut proof_verifier = Verifier::new_proof_verifier()?;
+        proof_verifier.add_sub_proof_request(&self.sub_proof_request,
+                                             &self.credential_schema,
+                                             &self.credential_pub_key,
+                                             self.rev_key_pub.as_ref(),
+                                             self.rev_reg.as_ref())?;
+        proof_verifier.verify(proof, nonce)
+    }
+}
+
+impl ProofMutation {
+    /// Applies mutation to proof and nonce. Mutations of values absent in proof fall back to change of `c_hash`.
+    pub fn apply(&self, proof: &mut Proof, nonce: &mut Nonce) -> Result<(), IndyCryptoError> {
+        let primary_proof = &mut proof.proofs[0].primary_proof;
+        let aggregated_proof = &mut proof.aggregated_proof;
+
+        let value: &mut BigNumber = match *self {
+            ProofMutation::CList(idx) if !aggregated_proof.c_list.is_empty() => {
+                let len = aggregated_proof.c_list.len();
+                let c = &mut aggregated_proof.c_list[idx % len];
+                c.push(1);
+                return Ok(());
+            }
+            ProofMutation::RevealedAttr(idx) if !primary_proof.eq_proof.revealed_attrs.is_empty() =>
+                _nth_value(&mut primary_proof.eq_proof.revealed_attrs, idx),
+            ProofMutation::HiddenAttr(idx) if !primary_proof.eq_proof.m.is_empty() =>
+                _nth_value(&mut primary_proof.eq_proof.m, idx),
+            ProofMutation::EqProofValue(idx) => {
+                let eq_proof = &mut primary_proof.eq_proof;
+                match idx % 5 {
+                    0 => &mut eq_proof.a_prime,
+                    1 => &mut eq_proof.e,
+                    2 => &mut eq_proof.v,
+                    3 => &mut eq_proof.m1,
+                    _ => &mut eq_proof.m2
+                }
+            }
+            ProofMutation::GeProofValue(idx) if !primary_proof.ge_proofs.is_empty() => {
+                let len = primary_proof.ge_proofs.len();
+                let ge_proof = &mut primary_proof.ge_proofs[idx % len];
+                match (idx / len) % 5 {
+                    0 => &mut ge_proof.mj,
+                    1 => &mut ge_proof.alpha,
+                    2 => _nth_value(&mut ge_proof.u, idx),
+                    3 => _nth_value(&mut ge_proof.r, idx),
+                    _ => _nth_value(&mut ge_proof.t, idx)
+                }
+            }
+            ProofMutation::Nonce => nonce,
+            _ => &mut aggregated_proof.c_hash
+        };
+
+        *value = value.add(&BigNumber::from_u32(1)?)?;
+
+        Ok(())
+    }
+}
+
+fn _nth_value<K>(values: &mut HashMap<K, BigNumber>, idx: usize) -> &mut BigNumber where K: Eq + Hash {
+    let len = values.len();
+    values.values_mut().nth(idx % len).unwrap()
+}
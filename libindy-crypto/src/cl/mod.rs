@@ -3,17 +3,59 @@ extern crate serde_json;
 mod constants;
 #[macro_use]
 mod helpers;
+pub mod auditor;
+#[cfg(all(feature = "kdf_sodium", feature = "box_sodium"))]
+pub mod bundle;
+mod compact;
+#[cfg(feature = "verifier_core")]
+mod core_conversions;
+pub mod encoding;
+pub mod issuance;
 pub mod issuer;
+pub mod key_pool;
+#[cfg(test)]
+pub mod mocks;
+pub mod presentation;
 pub mod prover;
+mod ps;
 pub mod verifier;
 
-use bn::BigNumber;
+use accumulator;
+use bn::{BigNumber, BigNumberContext};
+use bls::{Bls, Generator, VerKey, Signature as BlsSignature};
+#[cfg(feature = "sign_sodium")]
+use ed25519;
+#[cfg(feature = "box_sodium")]
+use encryption::{PublicKey, SealedBox};
 use errors::IndyCryptoError;
 use pair::*;
-use utils::json::{JsonEncodable, JsonDecodable};
-
-use std::collections::{BTreeMap, HashMap, HashSet};
+use range_proof::RangeProof;
+use sss::{SecretShare, split_secret, recover_secret};
+use utils::json::{JsonEncodable, JsonDecodable, serialize_sorted_set};
+use utils::hash::ChallengeHasher;
+use utils::rng::{CryptoRng, with_rng};
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, Bound, HashMap, HashSet};
+use std::fmt;
 use std::hash::Hash;
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "verifier_core")]
+pub use self::core_conversions::nonce_to_verifier_core;
+pub use self::ps::{CredentialDefinition,
+                   CredentialSignatureBackend,
+                   PsBlindedMasterSecret,
+                   PsBlindedMasterSecretCorrectnessProof,
+                   PsCredentialPrivateKey,
+                   PsCredentialPublicKey,
+                   PsCredentialSignature,
+                   PsMasterSecretBlindingData,
+                   PsProof};
+
+static MAX_ATTRS_COUNT: AtomicUsize = AtomicUsize::new(constants::DEFAULT_MAX_ATTRS_COUNT);
 
 /// Creates random nonce
 ///
@@ -27,39 +69,321 @@ pub fn new_nonce() -> Result<Nonce, IndyCryptoError> {
     Ok(helpers::bn_rand(constants::LARGE_NONCE)?)
 }
 
+/// Creates random nonce of exactly `bits` bits (the most significant bit is always set),
+/// so Verifier can check nonce size with `ProofVerifier::set_min_nonce_size`.
+///
+/// # Arguments
+/// * `bits` - Nonce size in bits (from 80 to 1024).
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::new_nonce_with_size;
+///
+/// let nonce = new_nonce_with_size(128).unwrap();
+/// assert_eq!(128, nonce.num_bits().unwrap());
+/// ```
+pub fn new_nonce_with_size(bits: usize) -> Result<Nonce, IndyCryptoError> {
+    trace!("new_nonce_with_size: >>> bits: {:?}", bits);
+
+    check_nonce_size(bits)?;
+
+    let mut nonce = helpers::_bn_rand(bits)?;
+    nonce.set_bit(bits as i32 - 1)?;
+
+    trace!("new_nonce_with_size: <<< nonce: {:?}", nonce);
+
+    Ok(nonce)
+}
+
+/// Creates random nonce of exactly `bits` bits using caller provided randomness source
+/// instead of the one configured in `utils::rng`.
+///
+/// # Arguments
+/// * `bits` - Nonce size in bits (from 80 to 1024).
+/// * `rng` - Randomness source.
+pub fn new_nonce_with_rng(bits: usize, rng: Box<CryptoRng>) -> Result<Nonce, IndyCryptoError> {
+    with_rng(rng, || new_nonce_with_size(bits))
+}
+
+/// Creates nonce from caller provided entropy. Nonce size is `8 * entropy.len()` bits
+/// (the most significant bit is set as for `new_nonce_with_size`).
+/// Entropy must be uniformly random: it is used as is.
+///
+/// # Arguments
+/// * `entropy` - Random bytes (from 10 to 128 bytes).
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::new_nonce_from_entropy;
+///
+/// let nonce = new_nonce_from_entropy(&[7; 16]).unwrap();
+/// assert_eq!(128, nonce.num_bits().unwrap());
+/// ```
+pub fn new_nonce_from_entropy(entropy: &[u8]) -> Result<Nonce, IndyCryptoError> {
+    trace!("new_nonce_from_entropy: >>> entropy len: {:?}", entropy.len());
+
+    let bits = entropy.len() * 8;
+    check_nonce_size(bits)?;
+
+    let mut nonce = BigNumber::from_bytes(entropy)?;
+    nonce.set_bit(bits as i32 - 1)?;
+
+    trace!("new_nonce_from_entropy: <<< nonce: {:?}", nonce);
+
+    Ok(nonce)
+}
+
+fn check_nonce_size(bits: usize) -> Result<(), IndyCryptoError> {
+    if bits < constants::LARGE_NONCE || bits > constants::MAX_NONCE_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid nonce size {}: expected from {} to {} bits", bits, constants::LARGE_NONCE, constants::MAX_NONCE_SIZE)));
+    }
+    Ok(())
+}
+
+/// Returns maximum number of attributes in credential schema.
+/// Key generation and proof size grow linearly with attributes count,
+/// so schemas above this limit are rejected with `AnoncredsTooManyAttributes` error.
+pub fn max_attrs_count() -> usize {
+    MAX_ATTRS_COUNT.load(Ordering::SeqCst)
+}
+
+/// Sets maximum number of attributes in credential schema (256 by default).
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::{max_attrs_count, set_max_attrs_count};
+///
+/// let default_max_attrs_count = max_attrs_count();
+/// set_max_attrs_count(default_max_attrs_count).unwrap();
+/// ```
+pub fn set_max_attrs_count(count: usize) -> Result<(), IndyCryptoError> {
+    if count == 0 {
+        return Err(IndyCryptoError::InvalidParam1(format!("Max attributes count must be positive")));
+    }
+
+    MAX_ATTRS_COUNT.store(count, Ordering::SeqCst);
+    Ok(())
+}
+
+fn check_attrs_count(count: usize) -> Result<(), IndyCryptoError> {
+    let max_count = max_attrs_count();
+
+    if count > max_count {
+        return Err(IndyCryptoError::AnoncredsTooManyAttributes(
+            format!("Credential schema contains {} attributes, but at most {} are supported", count, max_count)));
+    }
+
+    Ok(())
+}
+
+/// Canonicalization of attribute names.
+/// Applied by builders and by lookups of requested attributes in credential schema,
+/// so differently spelled names (e.g. "First Name" and "firstname") refer to the same attribute.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum AttrNameCanonicalization {
+    /// Names are used as is.
+    Exact,
+    /// Names are converted to lowercase and whitespaces are removed.
+    LowercaseNoSpaces
+}
+
+impl AttrNameCanonicalization {
+    /// Returns canonical form of attribute name.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::AttrNameCanonicalization;
+    ///
+    /// assert_eq!("firstname", AttrNameCanonicalization::LowercaseNoSpaces.canonicalize(" First Name"));
+    /// assert_eq!("First Name", AttrNameCanonicalization::Exact.canonicalize("First Name"));
+    /// ```
+    pub fn canonicalize(&self, attr: &str) -> String {
+        match *self {
+            AttrNameCanonicalization::Exact => attr.to_owned(),
+            AttrNameCanonicalization::LowercaseNoSpaces => attr.chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(|c| c.to_lowercase())
+                .collect()
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        *self == AttrNameCanonicalization::Exact
+    }
+}
+
+impl Default for AttrNameCanonicalization {
+    fn default() -> AttrNameCanonicalization {
+        AttrNameCanonicalization::Exact
+    }
+}
+
 /// A list of attributes a Claim is based on.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CredentialSchema {
-    attrs: HashSet<String> /* attr names */
+    #[serde(serialize_with = "serialize_sorted_set")]
+    attrs: HashSet<String>, /* attr names */
+    #[serde(default, skip_serializing_if = "AttrNameCanonicalization::is_exact")]
+    attr_canonicalization: AttrNameCanonicalization
+}
+
+impl JsonEncodable for CredentialSchema {}
+
+impl<'a> JsonDecodable<'a> for CredentialSchema {}
+
+impl CredentialSchema {
+    /// Returns canonicalization of attribute names selected for credential definition of the schema.
+    pub fn attr_canonicalization(&self) -> AttrNameCanonicalization {
+        self.attr_canonicalization
+    }
+
+    /// Returns fingerprint of the schema: hex encoded SHA-256 digest of attributes count,
+    /// length-prefixed attribute names sorted bytewise and canonicalization id (0 - exact, 1 - lowercase without spaces).
+    /// Fingerprint doesn't depend on order attributes were added in.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// credential_schema_builder.add_attr("age").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// assert_eq!("e6584fd06bac60df74046af9f9e14fd81ca2d0949fe48f3c2f137157663501b1", credential_schema.fingerprint().unwrap());
+    /// ```
+    pub fn fingerprint(&self) -> Result<String, IndyCryptoError> {
+        trace!("CredentialSchema::fingerprint: >>>");
+
+        let mut attrs = self.attrs.iter().collect::<Vec<&String>>();
+        attrs.sort();
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        values.push(helpers::transform_u32_to_array_of_u8(attrs.len() as u32));
+        for attr in attrs {
+            append_with_len(&mut values, attr.as_bytes());
+        }
+        values.push(vec![match self.attr_canonicalization {
+            AttrNameCanonicalization::Exact => 0,
+            AttrNameCanonicalization::LowercaseNoSpaces => 1
+        }]);
+
+        let fingerprint = hex_digest(&values)?;
+
+        trace!("CredentialSchema::fingerprint: <<< fingerprint: {:?}", fingerprint);
+
+        Ok(fingerprint)
+    }
+
+    /// Converts attribute names of sub proof request to canonical form of the schema.
+    /// Terms of linear predicates can refer to other schemas, so they are kept as is.
+    fn canonicalize_sub_proof_request(&self, sub_proof_request: &SubProofRequest) -> SubProofRequest {
+        if self.attr_canonicalization.is_exact() {
+            return sub_proof_request.clone();
+        }
+
+        SubProofRequest {
+            revealed_attrs: sub_proof_request.revealed_attrs.iter()
+                .map(|attr| self.attr_canonicalization.canonicalize(attr))
+                .collect(),
+            predicates: sub_proof_request.predicates.iter()
+                .map(|predicate| Predicate {
+                    attr_name: self.attr_canonicalization.canonicalize(&predicate.attr_name),
+                    p_type: predicate.p_type.clone(),
+                    value: predicate.value,
+                    proof_version: predicate.proof_version
+                })
+                .collect(),
+            linear_predicates: sub_proof_request.linear_predicates.clone(),
+            non_revoked: sub_proof_request.non_revoked.clone(),
+            attr_value_checks: sub_proof_request.attr_value_checks.iter()
+                .map(|(attr, value)| (self.attr_canonicalization.canonicalize(attr), value.clone()))
+                .collect()
+        }
+    }
+
+    /// Converts attribute names of credential values to canonical form of the schema.
+    fn canonicalize_credential_values(&self, credential_values: &CredentialValues) -> Result<CredentialValues, IndyCryptoError> {
+        let mut attrs_values = HashMap::new();
+
+        for (attr, value) in credential_values.attrs_values.iter() {
+            if attrs_values.insert(self.attr_canonicalization.canonicalize(attr), value.clone()?).is_some() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Credential values contain duplicated attribute: {:?}", attr)));
+            }
+        }
+
+        Ok(CredentialValues { attrs_values })
+    }
 }
 
 /// A Builder of `Claim Schema`.
 #[derive(Debug)]
 pub struct CredentialSchemaBuilder {
-    attrs: HashSet<String> /* attr names */
+    attrs: HashSet<String>, /* attr names */
+    attr_canonicalization: AttrNameCanonicalization
 }
 
 impl CredentialSchemaBuilder {
     pub fn new() -> Result<CredentialSchemaBuilder, IndyCryptoError> {
         Ok(CredentialSchemaBuilder {
-            attrs: HashSet::new()
+            attrs: HashSet::new(),
+            attr_canonicalization: AttrNameCanonicalization::Exact
         })
     }
 
+    /// Selects canonicalization of attribute names for the schema and credential definitions created for it.
+    /// Must be called before attributes are added.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{AttrNameCanonicalization, CredentialSchemaBuilder};
+    ///
+    /// let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+    /// credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).unwrap();
+    /// credential_schema_builder.add_attr("Last Name").unwrap();
+    /// credential_schema_builder.add_attr("lastname").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// assert_eq!(AttrNameCanonicalization::LowercaseNoSpaces, credential_schema.attr_canonicalization());
+    /// ```
+    pub fn set_attr_canonicalization(&mut self, attr_canonicalization: AttrNameCanonicalization) -> Result<(), IndyCryptoError> {
+        if !self.attrs.is_empty() {
+            return Err(IndyCryptoError::InvalidState(format!("Attribute name canonicalization must be set before attributes are added")));
+        }
+
+        self.attr_canonicalization = attr_canonicalization;
+        Ok(())
+    }
+
     pub fn add_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
-        self.attrs.insert(attr.to_owned());
+        self.attrs.insert(self.attr_canonicalization.canonicalize(attr));
+        Ok(())
+    }
+
+    /// Adds attribute which encoded value can exceed CL message space (256 bits).
+    /// Attribute is represented by several signed chunks named `<attr>#<idx>`.
+    pub fn add_chunked_attr(&mut self, attr: &str, max_bits: usize) -> Result<(), IndyCryptoError> {
+        let attr = self.attr_canonicalization.canonicalize(attr);
+
+        for idx in 0..helpers::attr_chunks_count(max_bits)? {
+            self.attrs.insert(helpers::attr_chunk_name(&attr, idx));
+        }
         Ok(())
     }
 
     pub fn finalize(self) -> Result<CredentialSchema, IndyCryptoError> {
+        check_attrs_count(self.attrs.len())?;
+
         Ok(CredentialSchema {
-            attrs: self.attrs
+            attrs: self.attrs,
+            attr_canonicalization: self.attr_canonicalization
         })
     }
 }
 
 /// Values of attributes from `Claim Schema` (must be integers).
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CredentialValues {
     attrs_values: HashMap<String, BigNumber>
 }
@@ -75,18 +399,53 @@ impl CredentialValues {
 /// A Builder of `Claim Values`.
 #[derive(Debug)]
 pub struct CredentialValuesBuilder {
-    attrs_values: HashMap<String, BigNumber> /* attr_name -> int representation of value */
+    attrs_values: HashMap<String, BigNumber>, /* attr_name -> int representation of value */
+    attr_canonicalization: AttrNameCanonicalization
 }
 
 impl CredentialValuesBuilder {
     pub fn new() -> Result<CredentialValuesBuilder, IndyCryptoError> {
         Ok(CredentialValuesBuilder {
-            attrs_values: HashMap::new()
+            attrs_values: HashMap::new(),
+            attr_canonicalization: AttrNameCanonicalization::Exact
         })
     }
 
+    /// Selects canonicalization of attribute names. Must match canonicalization of credential schema
+    /// and be called before values are added.
+    pub fn set_attr_canonicalization(&mut self, attr_canonicalization: AttrNameCanonicalization) -> Result<(), IndyCryptoError> {
+        if !self.attrs_values.is_empty() {
+            return Err(IndyCryptoError::InvalidState(format!("Attribute name canonicalization must be set before values are added")));
+        }
+
+        self.attr_canonicalization = attr_canonicalization;
+        Ok(())
+    }
+
     pub fn add_value(&mut self, attr: &str, dec_value: &str) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(attr.to_owned(), BigNumber::from_dec(dec_value)?);
+        self.attrs_values.insert(self.attr_canonicalization.canonicalize(attr), BigNumber::from_dec_checked(dec_value)?);
+        Ok(())
+    }
+
+    /// Adds raw value of attribute encoded per standard encoding (see `encoding::encode_attribute`).
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `raw_value` - Raw attribute value.
+    pub fn add_str_value(&mut self, attr: &str, raw_value: &str) -> Result<(), IndyCryptoError> {
+        let encoded = encoding::encode_attribute(raw_value, AttrValueEncoding::for_raw(raw_value))?;
+        self.add_value(attr, &encoded)
+    }
+
+    /// Adds value of attribute added to schema by `CredentialSchemaBuilder::add_chunked_attr`.
+    /// Value is split into 256 bits chunks, so it is signed without truncation.
+    pub fn add_chunked_value(&mut self, attr: &str, dec_value: &str, max_bits: usize) -> Result<(), IndyCryptoError> {
+        let chunks = helpers::split_attr_value(&BigNumber::from_dec_checked(dec_value)?, helpers::attr_chunks_count(max_bits)?)?;
+        let attr = self.attr_canonicalization.canonicalize(attr);
+
+        for (idx, chunk) in chunks.into_iter().enumerate() {
+            self.attrs_values.insert(helpers::attr_chunk_name(&attr, idx), chunk);
+        }
         Ok(())
     }
 
@@ -97,6 +456,121 @@ impl CredentialValuesBuilder {
     }
 }
 
+/// Method used to encode raw attribute value into integer value signed by Issuer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum AttrValueEncoding {
+    /// Raw value is 32-bit signed integer that is signed as is.
+    Integer,
+    /// Raw value is signed as big-endian integer representation of its SHA-256 hash.
+    Sha256
+}
+
+impl AttrValueEncoding {
+    /// Returns conventional encoding of raw value: `Integer` for 32-bit integers and `Sha256` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::AttrValueEncoding;
+    ///
+    /// assert_eq!(AttrValueEncoding::Integer, AttrValueEncoding::for_raw("28"));
+    /// assert_eq!(AttrValueEncoding::Sha256, AttrValueEncoding::for_raw("Alex"));
+    /// ```
+    pub fn for_raw(raw: &str) -> AttrValueEncoding {
+        match raw.parse::<i32>() {
+            Ok(_) => AttrValueEncoding::Integer,
+            Err(_) => AttrValueEncoding::Sha256
+        }
+    }
+
+    /// Encodes raw attribute value.
+    ///
+    /// # Arguments
+    /// * `raw` - Raw attribute value.
+    pub fn encode(&self, raw: &str) -> Result<BigNumber, IndyCryptoError> {
+        match *self {
+            AttrValueEncoding::Integer => {
+                let value = raw.parse::<i32>()
+                    .map_err(|_| IndyCryptoError::InvalidStructure(format!("Raw value {:?} isn't 32-bit integer", raw)))?;
+                BigNumber::from_dec(&value.to_string())
+            }
+            AttrValueEncoding::Sha256 => helpers::encode_attribute(raw, helpers::ByteOrder::Big)
+        }
+    }
+}
+
+/// Raw value of revealed attribute with its encoded value and encoding method.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevealedAttribute {
+    raw: String,
+    encoded: BigNumber,
+    encoding: AttrValueEncoding
+}
+
+impl RevealedAttribute {
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn encoded(&self) -> &BigNumber {
+        &self.encoded
+    }
+
+    pub fn encoding(&self) -> AttrValueEncoding {
+        self.encoding
+    }
+}
+
+/// Revealed attributes of a sub proof transported together with their raw values.
+/// Encoded values are verified cryptographically by proof, raw values are checked against them
+/// by `Verifier::check_revealed_attribute_bundle`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevealedAttributeBundle {
+    attrs: BTreeMap<String /* attr_name */, RevealedAttribute>
+}
+
+impl RevealedAttributeBundle {
+    /// Creates empty bundle.
+    pub fn new() -> Result<RevealedAttributeBundle, IndyCryptoError> {
+        Ok(RevealedAttributeBundle {
+            attrs: BTreeMap::new()
+        })
+    }
+
+    /// Adds raw value of revealed attribute encoded with conventional encoding (see `AttrValueEncoding::for_raw`).
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `raw` - Raw attribute value.
+    pub fn add_raw_value(&mut self, attr: &str, raw: &str) -> Result<(), IndyCryptoError> {
+        self.add_raw_value_with_encoding(attr, raw, AttrValueEncoding::for_raw(raw))
+    }
+
+    /// Adds raw value of revealed attribute encoded with given encoding.
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `raw` - Raw attribute value.
+    /// * `encoding` - Encoding method used by Issuer.
+    pub fn add_raw_value_with_encoding(&mut self, attr: &str, raw: &str, encoding: AttrValueEncoding) -> Result<(), IndyCryptoError> {
+        let encoded = encoding.encode(raw)?;
+        self.attrs.insert(attr.to_owned(), RevealedAttribute { raw: raw.to_owned(), encoded, encoding });
+        Ok(())
+    }
+
+    pub fn get(&self, attr: &str) -> Option<&RevealedAttribute> {
+        self.attrs.get(attr)
+    }
+
+    /// Returns raw value of attribute.
+    pub fn get_raw(&self, attr: &str) -> Option<&str> {
+        self.attrs.get(attr).map(RevealedAttribute::raw)
+    }
+}
+
+impl JsonEncodable for RevealedAttributeBundle {}
+
+impl<'a> JsonDecodable<'a> for RevealedAttributeBundle {}
+
 /// `Issuer Public Key` contains 2 internal parts.
 /// One for signing primary credentials and second for signing non-revocation credentials.
 /// These keys are used to proof that credential was issued and doesn’t revoked by this issuer.
@@ -123,18 +597,223 @@ impl CredentialPublicKey {
         Ok(self.r_key.clone())
     }
 
+    /// Returns false if credentials signed with this key don't contain master secret.
+    pub fn requires_master_secret(&self) -> bool {
+        !self.p_key.without_master_secret
+    }
+
     pub fn build_from_parts(p_key: &CredentialPrimaryPublicKey, r_key: Option<&CredentialRevocationPublicKey>) -> Result<CredentialPublicKey, IndyCryptoError> {
         Ok(CredentialPublicKey {
             p_key: p_key.clone()?,
             r_key: r_key.map(|key| key.clone())
         })
     }
+
+    /// Derives self-certifying credential definition identifier from key material.
+    /// Identifier has form `<issuer_did>:<tag>:<hex sha256 digest>` where digest covers
+    /// issuer did, schema digest, tag and canonical representation of the key,
+    /// so it can be checked by any party without access to ledger.
+    ///
+    /// # Arguments
+    /// * `issuer_did` - Issuer DID.
+    /// * `schema_digest` - Digest of schema the key was created for.
+    /// * `tag` - Tag to distinguish credential definitions for the same schema.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, _credential_priv_key, _credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let id = credential_pub_key.derive_id("NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap();
+    /// assert!(credential_pub_key.verify_id(&id, "NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap());
+    /// ```
+    pub fn derive_id(&self, issuer_did: &str, schema_digest: &[u8], tag: &str) -> Result<String, IndyCryptoError> {
+        trace!("CredentialPublicKey::derive_id: >>> issuer_did: {:?}, schema_digest: {:?}, tag: {:?}", issuer_did, schema_digest, tag);
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        append_with_len(&mut values, issuer_did.as_bytes());
+        append_with_len(&mut values, schema_digest);
+        append_with_len(&mut values, tag.as_bytes());
+        values.extend_from_slice(&self._canonical_bytes()?);
+
+        let id = format!("{}:{}:{}", issuer_did, tag, hex_digest(&values)?);
+
+        trace!("CredentialPublicKey::derive_id: <<< id: {:?}", id);
+
+        Ok(id)
+    }
+
+    /// Checks that credential definition identifier corresponds to this key and metadata.
+    ///
+    /// # Arguments
+    /// * `id` - Credential definition identifier received from peer.
+    /// * `issuer_did` - Issuer DID.
+    /// * `schema_digest` - Digest of schema the key was created for.
+    /// * `tag` - Credential definition tag.
+    pub fn verify_id(&self, id: &str, issuer_did: &str, schema_digest: &[u8], tag: &str) -> Result<bool, IndyCryptoError> {
+        trace!("CredentialPublicKey::verify_id: >>> id: {:?}, issuer_did: {:?}, schema_digest: {:?}, tag: {:?}", id, issuer_did, schema_digest, tag);
+
+        let valid = self.derive_id(issuer_did, schema_digest, tag)? == id;
+
+        trace!("CredentialPublicKey::verify_id: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Returns fingerprint of the key: hex encoded SHA-256 digest of its canonical representation.
+    /// Canonical representation is a sequence of length-prefixed big-endian numbers and points
+    /// in fixed order (attribute bases sorted by name), so fingerprint is stable across versions.
+    /// Optional parts (security profile, revocation key) are preceded by presence tag byte.
+    /// Unlike identifier derived with `derive_id` fingerprint doesn't depend on metadata.
+    pub fn fingerprint(&self) -> Result<String, IndyCryptoError> {
+        trace!("CredentialPublicKey::fingerprint: >>>");
+
+        let fingerprint = hex_digest(&self._canonical_bytes()?)?;
+
+        trace!("CredentialPublicKey::fingerprint: <<< fingerprint: {:?}", fingerprint);
+
+        Ok(fingerprint)
+    }
+
+    fn _canonical_bytes(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let mut values: Vec<Vec<u8>> = Vec::new();
+
+        append_with_len(&mut values, &self.p_key.n.to_bytes()?);
+        append_with_len(&mut values, &self.p_key.s.to_bytes()?);
+        append_with_len(&mut values, &self.p_key.rms.to_bytes()?);
+        for (attr, r) in self.p_key.r.iter() {
+            append_with_len(&mut values, attr.as_bytes());
+            append_with_len(&mut values, &r.to_bytes()?);
+        }
+        append_with_len(&mut values, &self.p_key.rctxt.to_bytes()?);
+        append_with_len(&mut values, &self.p_key.z.to_bytes()?);
+        match self.p_key.profile {
+            Some(profile) => {
+                values.push(vec![1]);
+                append_with_len(&mut values, &helpers::transform_u64_to_array_of_u8(profile.modulus_bits as u64));
+                append_with_len(&mut values, &helpers::transform_u64_to_array_of_u8(profile.challenge_bits as u64));
+                append_with_len(&mut values, &helpers::transform_u64_to_array_of_u8(profile.statistical_bits as u64));
+            }
+            None => values.push(vec![0])
+        }
+        values.push(vec![self.p_key.without_master_secret as u8]);
+
+        match self.r_key {
+            Some(ref r_key) => {
+                values.push(vec![1]);
+                for point in [&r_key.g, &r_key.h, &r_key.h0, &r_key.h1, &r_key.h2, &r_key.htilde, &r_key.pk].iter() {
+                    append_with_len(&mut values, &point.to_bytes()?);
+                }
+                for point in [&r_key.g_dash, &r_key.h_cap, &r_key.u, &r_key.y].iter() {
+                    append_with_len(&mut values, &point.to_bytes()?);
+                }
+            }
+            None => values.push(vec![0])
+        }
+
+        Ok(values)
+    }
 }
 
 impl JsonEncodable for CredentialPublicKey {}
 
 impl<'a> JsonDecodable<'a> for CredentialPublicKey {}
 
+/// Credential public key with precomputed fixed-base exponentiation tables for all bases of primary key (S, Z, R_i).
+/// Tables take about 3.5 MB for S and 0.7 MB for each other base and are expensive to build,
+/// so precomputed key is worth creating once by issuers and verifiers handling many credentials of the same definition.
+#[derive(Debug)]
+pub struct CredentialPublicKeyPrecomputed {
+    pub_key: CredentialPublicKey,
+    s: helpers::FixedBaseExpTable,
+    z: helpers::FixedBaseExpTable,
+    rms: helpers::FixedBaseExpTable,
+    rctxt: helpers::FixedBaseExpTable,
+    r: HashMap<String, helpers::FixedBaseExpTable>
+}
+
+impl CredentialPublicKeyPrecomputed {
+    /// Builds exponentiation tables for credential public key.
+    ///
+    /// # Arguments
+    /// * `cred_pub_key` - Credential public key.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::CredentialPublicKeyPrecomputed;
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, _credential_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let _credential_pub_key_precomputed = CredentialPublicKeyPrecomputed::new(&credential_pub_key).unwrap();
+    /// ```
+    pub fn new(cred_pub_key: &CredentialPublicKey) -> Result<CredentialPublicKeyPrecomputed, IndyCryptoError> {
+        trace!("CredentialPublicKeyPrecomputed::new: >>> cred_pub_key: {:?}", cred_pub_key);
+
+        let p_key = &cred_pub_key.p_key;
+
+        let mut r: HashMap<String, helpers::FixedBaseExpTable> = HashMap::new();
+        for (attr, r_attr) in p_key.r.iter() {
+            r.insert(attr.clone(), helpers::FixedBaseExpTable::new(r_attr, &p_key.n, constants::LARGE_PRECOMPUTED_EXP)?);
+        }
+
+        let cred_pub_key_precomputed = CredentialPublicKeyPrecomputed {
+            pub_key: cred_pub_key.clone()?,
+            s: helpers::FixedBaseExpTable::new(&p_key.s, &p_key.n,
+                                               constants::LARGE_PRECOMPUTED_S_EXP + p_key.security_profile()?.vtilde_bits() - constants::LARGE_VTILDE)?,
+            z: helpers::FixedBaseExpTable::new(&p_key.z, &p_key.n, constants::LARGE_PRECOMPUTED_EXP)?,
+            rms: helpers::FixedBaseExpTable::new(&p_key.rms, &p_key.n, constants::LARGE_PRECOMPUTED_EXP)?,
+            rctxt: helpers::FixedBaseExpTable::new(&p_key.rctxt, &p_key.n, constants::LARGE_PRECOMPUTED_EXP)?,
+            r
+        };
+
+        trace!("CredentialPublicKeyPrecomputed::new: <<<");
+
+        Ok(cred_pub_key_precomputed)
+    }
+
+    pub fn pub_key(&self) -> &CredentialPublicKey {
+        &self.pub_key
+    }
+}
+
+impl helpers::PrimaryPublicKeyExp for CredentialPublicKeyPrecomputed {
+    fn primary_key(&self) -> &CredentialPrimaryPublicKey {
+        &self.pub_key.p_key
+    }
+
+    fn s_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.s.mod_exp(exp, ctx)
+    }
+
+    fn z_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.z.mod_exp(exp, ctx)
+    }
+
+    fn rms_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.rms.mod_exp(exp, ctx)
+    }
+
+    fn rctxt_exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.rctxt.mod_exp(exp, ctx)
+    }
+
+    fn r_exp(&self, attr: &str, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        self.r.get(attr)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "pk.r".to_string() })?
+            .mod_exp(exp, ctx)
+    }
+}
+
 /// `Issuer Private Key`: contains 2 internal parts.
 /// One for signing primary credentials and second for signing non-revocation credentials.
 #[derive(Debug, Deserialize, Serialize)]
@@ -143,10 +822,110 @@ pub struct CredentialPrivateKey {
     r_key: Option<CredentialRevocationPrivateKey>,
 }
 
+impl CredentialPrivateKey {
+    pub fn clone(&self) -> Result<CredentialPrivateKey, IndyCryptoError> {
+        Ok(CredentialPrivateKey {
+            p_key: self.p_key.clone()?,
+            r_key: self.r_key.clone()
+        })
+    }
+}
+
 impl JsonEncodable for CredentialPrivateKey {}
 
 impl<'a> JsonDecodable<'a> for CredentialPrivateKey {}
 
+/// Sizes of security parameters of credential primary key math.
+/// Profile is chosen by issuer at credential definition creation and carried in public key,
+/// so prover and verifier use sizes that correspond to the key modulus.
+///
+/// Default profile (2048-bit modulus) corresponds to sizes from `cl::constants`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SecurityProfile {
+    modulus_bits: usize,
+    challenge_bits: usize,
+    statistical_bits: usize
+}
+
+impl SecurityProfile {
+    /// 2048-bit modulus, 256-bit challenge and 80-bit statistical zero-knowledge parameter.
+    pub fn modulus_2048() -> SecurityProfile {
+        SecurityProfile { modulus_bits: 2048, challenge_bits: constants::CHALLENGE_BYTES * 8, statistical_bits: 80 }
+    }
+
+    /// 3072-bit modulus, 256-bit challenge and 128-bit statistical zero-knowledge parameter.
+    pub fn modulus_3072() -> SecurityProfile {
+        SecurityProfile { modulus_bits: 3072, challenge_bits: constants::CHALLENGE_BYTES * 8, statistical_bits: 128 }
+    }
+
+    /// 4096-bit modulus, 256-bit challenge and 128-bit statistical zero-knowledge parameter.
+    pub fn modulus_4096() -> SecurityProfile {
+        SecurityProfile { modulus_bits: 4096, challenge_bits: constants::CHALLENGE_BYTES * 8, statistical_bits: 128 }
+    }
+
+    pub fn modulus_bits(&self) -> usize {
+        self.modulus_bits
+    }
+
+    pub fn challenge_bits(&self) -> usize {
+        self.challenge_bits
+    }
+
+    pub fn statistical_bits(&self) -> usize {
+        self.statistical_bits
+    }
+
+    /// Size of safe primes which product is the modulus.
+    pub fn prime_bits(&self) -> usize {
+        self.modulus_bits / 2
+    }
+
+    /// Size of prover blinding factor `v'` and of randomizers of `A` and predicate commitments.
+    pub fn vprime_bits(&self) -> usize {
+        self.modulus_bits + self.statistical_bits
+    }
+
+    /// Size of issuer part `v''` of signature value `v`.
+    pub fn vprime_prime_bits(&self) -> usize {
+        self.vprime_bits() + constants::LARGE_E_START
+    }
+
+    /// Size of `v~` blinding of `v` in equality proof.
+    pub fn vtilde_bits(&self) -> usize {
+        self.vprime_prime_bits() + self.challenge_bits + self.statistical_bits
+    }
+
+    /// Size of `alpha~` blinding in predicate proof.
+    pub fn alphatilde_bits(&self) -> usize {
+        constants::LARGE_ALPHATILDE + self.vtilde_bits() - constants::LARGE_VTILDE
+    }
+
+    /// Checks that profile is one of supported profiles and corresponds to the modulus.
+    pub fn check_modulus(&self, n: &BigNumber) -> Result<(), IndyCryptoError> {
+        let supported = [SecurityProfile::modulus_2048(), SecurityProfile::modulus_3072(), SecurityProfile::modulus_4096()];
+
+        if !supported.contains(self) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported security profile: {:?}", self)));
+        }
+
+        // Product of two safe primes of half modulus size can be 1 bit shorter than modulus
+        let n_bits = n.num_bits()? as usize;
+
+        if n_bits != self.modulus_bits && n_bits + 1 != self.modulus_bits {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Modulus size doesn't correspond to security profile with {}-bit modulus", self.modulus_bits)));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SecurityProfile {
+    fn default() -> SecurityProfile {
+        SecurityProfile::modulus_2048()
+    }
+}
+
 /// Issuer's "Public Key" is used to verify the Issuer's signature over the Claim's attributes' values (primary credential).
 #[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct CredentialPrimaryPublicKey {
@@ -155,7 +934,13 @@ pub struct CredentialPrimaryPublicKey {
     rms: BigNumber,
     r: BTreeMap<String /* attr_name */, BigNumber>,
     rctxt: BigNumber,
-    z: BigNumber
+    z: BigNumber,
+    /// Keys without profile use default 2048-bit profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<SecurityProfile>,
+    /// Credentials signed with such keys (e.g. bound to devices) don't contain master secret.
+    #[serde(default, skip_serializing_if = "is_false")]
+    without_master_secret: bool
 }
 
 impl CredentialPrimaryPublicKey {
@@ -166,9 +951,23 @@ impl CredentialPrimaryPublicKey {
             rms: self.rms.clone()?,
             r: clone_btree_bignum_map(&self.r)?,
             rctxt: self.rctxt.clone()?,
-            z: self.z.clone()?
+            z: self.z.clone()?,
+            profile: self.profile,
+            without_master_secret: self.without_master_secret
         })
     }
+
+    /// Returns security profile of the key checked against the key modulus.
+    /// Keys created without explicit profile use default profile.
+    pub fn security_profile(&self) -> Result<SecurityProfile, IndyCryptoError> {
+        match self.profile {
+            Some(profile) => {
+                profile.check_modulus(&self.n)?;
+                Ok(profile)
+            }
+            None => Ok(SecurityProfile::default())
+        }
+    }
 }
 
 /// Issuer's "Private Key" used for signing Claim's attributes' values (primary credential)
@@ -178,25 +977,303 @@ pub struct CredentialPrimaryPrivateKey {
     q: BigNumber
 }
 
-/// `Primary Public Key Metadata` required for building of Proof Correctness of `Issuer Public Key`
-#[derive(Debug)]
-pub struct CredentialPrimaryPublicKeyMetadata {
-    xz: BigNumber,
-    xr: BTreeMap<String, BigNumber>
+impl CredentialPrimaryPrivateKey {
+    pub fn clone(&self) -> Result<CredentialPrimaryPrivateKey, IndyCryptoError> {
+        Ok(CredentialPrimaryPrivateKey {
+            p: self.p.clone()?,
+            q: self.q.clone()?
+        })
+    }
+
+    // Returns safe primes `2p + 1` and `2q + 1` that are factors of `n`
+    fn _safe_primes(&self) -> Result<(BigNumber, BigNumber), IndyCryptoError> {
+        let mut p_safe = self.p.clone()?;
+        p_safe.mul_word(2)?.add_word(1)?;
+
+        let mut q_safe = self.q.clone()?;
+        q_safe.mul_word(2)?.add_word(1)?;
+
+        Ok((p_safe, q_safe))
+    }
 }
 
-/// Proof of `Issuer Public Key` correctness
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// Performs issuer operations that require knowledge of primary private key (factorization of `n`).
+/// Allows to keep primary private key outside of the process memory (HSM, secure enclave)
+/// and delegate signing to it. `CredentialPrimaryPrivateKey` is the software implementation.
+pub trait PrivateKeyProvider {
+    /// Returns primary signature `A = q ^ (e ^ -1 mod p'q') mod n`.
+    ///
+    /// # Arguments
+    /// * `q` - Value to be signed.
+    /// * `e` - Prime exponent of signature.
+    /// * `n` - Modulus of credential primary public key.
+    fn sign_primary(&self, q: &BigNumber, e: &BigNumber, n: &BigNumber) -> Result<BigNumber, IndyCryptoError>;
+
+    /// Returns proof of knowledge of `e ^ -1 mod p'q'` used for creation of primary signature `A`.
+    ///
+    /// # Arguments
+    /// * `q` - Signed value.
+    /// * `a` - Primary signature.
+    /// * `e` - Prime exponent of signature.
+    /// * `n` - Modulus of credential primary public key.
+    /// * `nonce` - Credential issuance nonce.
+    fn prove_primary_signature(&self,
+                               q: &BigNumber,
+                               a: &BigNumber,
+                               e: &BigNumber,
+                               n: &BigNumber,
+                               nonce: &Nonce) -> Result<SignatureCorrectnessProof, IndyCryptoError>;
+}
+
+impl PrivateKeyProvider for CredentialPrimaryPrivateKey {
+    fn sign_primary(&self, q: &BigNumber, e: &BigNumber, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        let order = self.p.mul(&self.q, Some(&mut ctx))?;
+        let e_inverse = e.inverse(&order, Some(&mut ctx))?;
+
+        // CRT requires distinct factors of n
+        if self.p == self.q {
+            return q.mod_exp(&e_inverse, n, Some(&mut ctx));
+        }
+
+        let (p_safe, q_safe) = self._safe_primes()?;
+        q.mod_exp_crt(&e_inverse, &p_safe, &q_safe)
+    }
+
+    fn prove_primary_signature(&self,
+                               q: &BigNumber,
+                               a: &BigNumber,
+                               e: &BigNumber,
+                               n: &BigNumber,
+                               nonce: &Nonce) -> Result<SignatureCorrectnessProof, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        let order = self.p.mul(&self.q, Some(&mut ctx))?;
+        let r = helpers::bn_rand_range(&order)?;
+
+        let a_cap = q.mod_exp(&r, n, Some(&mut ctx))?;
+
+        let mut values: Vec<u8> = Vec::new();
+        values.extend_from_slice(&q.to_bytes()?);
+        values.extend_from_slice(&a.to_bytes()?);
+        values.extend_from_slice(&a_cap.to_bytes()?);
+        values.extend_from_slice(&nonce.to_bytes()?);
+
+        let c = helpers::get_hash_as_int(&mut vec![values])?;
+
+        let se = r.mod_sub(
+            &c.mod_mul(&e.inverse(&order, Some(&mut ctx))?, &order, Some(&mut ctx))?,
+            &order,
+            Some(&mut ctx)
+        )?;
+
+        Ok(SignatureCorrectnessProof { c, se })
+    }
+}
+
+impl PrivateKeyProvider for CredentialPrivateKey {
+    fn sign_primary(&self, q: &BigNumber, e: &BigNumber, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        self.p_key.sign_primary(q, e, n)
+    }
+
+    fn prove_primary_signature(&self,
+                               q: &BigNumber,
+                               a: &BigNumber,
+                               e: &BigNumber,
+                               n: &BigNumber,
+                               nonce: &Nonce) -> Result<SignatureCorrectnessProof, IndyCryptoError> {
+        self.p_key.prove_primary_signature(q, a, e, n, nonce)
+    }
+}
+
+/// `Primary Public Key Metadata` required for building of Proof Correctness of `Issuer Public Key`
+#[derive(Debug)]
+pub struct CredentialPrimaryPublicKeyMetadata {
+    xz: BigNumber,
+    xr: BTreeMap<String, BigNumber>,
+    xrms: BigNumber,
+    xrctxt: BigNumber
+}
+
+const KEY_ROTATION_DOMAIN: &'static [u8] = b"indy-crypto:cl:key-rotation";
+
+/// Statement that links old and new `Issuer Public Key` during key rotation.
+/// Keys are referred to by their fingerprints (see `CredentialPublicKey::fingerprint`),
+/// statement is signed by Issuer BLS key. Proofs created with old key are accepted until the end of grace period.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct CredentialKeyRotation {
+    old_key_id: String,
+    new_key_id: String,
+    grace_period_end: u64,
+    signature: Vec<u8>
+}
+
+impl CredentialKeyRotation {
+    pub fn grace_period_end(&self) -> u64 {
+        self.grace_period_end
+    }
+
+    /// Returns fingerprint of credential public key before rotation.
+    /// Prover sets it as key id of sub proof created with old key (see `ProofBuilder::set_sub_proof_key_id`).
+    pub fn old_key_id(&self) -> &str {
+        &self.old_key_id
+    }
+
+    /// Returns fingerprint of credential public key after rotation.
+    pub fn new_key_id(&self) -> &str {
+        &self.new_key_id
+    }
+
+    /// Verifies Issuer signature over statement.
+    ///
+    /// # Arguments
+    /// * `issuer_vk` - Issuer BLS verification key.
+    /// * `gen` - BLS generator used for Issuer verification key creation.
+    pub fn verify_signature(&self, issuer_vk: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let signature = BlsSignature::from_bytes(&self.signature)?;
+
+        Bls::verify(&signature, &CredentialKeyRotation::_signed_bytes(&self.old_key_id, &self.new_key_id, self.grace_period_end),
+                    issuer_vk, gen)
+    }
+
+    fn _signed_bytes(old_key_id: &str, new_key_id: &str, grace_period_end: u64) -> Vec<u8> {
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        append_with_len(&mut values, KEY_ROTATION_DOMAIN);
+        append_with_len(&mut values, old_key_id.as_bytes());
+        append_with_len(&mut values, new_key_id.as_bytes());
+        values.push(helpers::transform_u64_to_array_of_u8(grace_period_end));
+        values.concat()
+    }
+}
+
+impl JsonEncodable for CredentialKeyRotation {}
+
+impl<'a> JsonDecodable<'a> for CredentialKeyRotation {}
+
+/// Proof of `Issuer Public Key` correctness
+///
+/// Proofs without version (version 0) cover only z and attributes bases and are still accepted.
+/// Version 1 proofs also cover rms, rctxt and revocation part of the key.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct CredentialKeyCorrectnessProof {
+    #[serde(default)]
+    version: u32,
     c: BigNumber,
     xz_cap: BigNumber,
-    xr_cap: BTreeMap<String, BigNumber>
+    xr_cap: BTreeMap<String, BigNumber>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    xrms_cap: Option<BigNumber>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    xrctxt_cap: Option<BigNumber>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    r_key_proof: Option<CredentialRevocationKeyCorrectnessProof>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extensions: Vec<CredentialKeyExtensionProof>
+}
+
+impl CredentialKeyCorrectnessProof {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn clone(&self) -> Result<CredentialKeyCorrectnessProof, IndyCryptoError> {
+        let mut extensions = Vec::new();
+        for extension in self.extensions.iter() {
+            extensions.push(extension.clone()?);
+        }
+
+        Ok(CredentialKeyCorrectnessProof {
+            version: self.version,
+            c: self.c.clone()?,
+            xz_cap: self.xz_cap.clone()?,
+            xr_cap: clone_btree_bignum_map(&self.xr_cap)?,
+            xrms_cap: clone_option_bignum(&self.xrms_cap)?,
+            xrctxt_cap: clone_option_bignum(&self.xrctxt_cap)?,
+            r_key_proof: match self.r_key_proof {
+                Some(ref r_key_proof) => Some(r_key_proof.clone()?),
+                None => None
+            },
+            extensions
+        })
+    }
 }
 
 impl JsonEncodable for CredentialKeyCorrectnessProof {}
 
 impl<'a> JsonDecodable<'a> for CredentialKeyCorrectnessProof {}
 
+/// Offer of credential sent by Issuer to Prover, starts offer/request/issue handshake.
+/// Contains fingerprint of `Issuer Public Key` the credential will be signed with, proof of the key correctness
+/// and nonce Prover must use to blind master secret.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct CredentialOffer {
+    key_fingerprint: String,
+    key_correctness_proof: CredentialKeyCorrectnessProof,
+    nonce: Nonce
+}
+
+impl CredentialOffer {
+    pub fn key_fingerprint(&self) -> &str {
+        &self.key_fingerprint
+    }
+
+    pub fn key_correctness_proof(&self) -> &CredentialKeyCorrectnessProof {
+        &self.key_correctness_proof
+    }
+
+    /// Returns nonce to be used as `master_secret_blinding_nonce`.
+    pub fn nonce(&self) -> &Nonce {
+        &self.nonce
+    }
+}
+
+impl JsonEncodable for CredentialOffer {}
+
+impl<'a> JsonDecodable<'a> for CredentialOffer {}
+
+/// Proof of correctness of attribute bases added to `Issuer Public Key` by key extension.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct CredentialKeyExtensionProof {
+    c: BigNumber,
+    xr_cap: BTreeMap<String, BigNumber>
+}
+
+impl CredentialKeyExtensionProof {
+    pub fn clone(&self) -> Result<CredentialKeyExtensionProof, IndyCryptoError> {
+        Ok(CredentialKeyExtensionProof {
+            c: self.c.clone()?,
+            xr_cap: clone_btree_bignum_map(&self.xr_cap)?
+        })
+    }
+}
+
+/// Proof of knowledge of `Revocation Private Key` corresponding to pk and y of `Revocation Public Key`.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct CredentialRevocationKeyCorrectnessProof {
+    c: BigNumber,
+    sk_cap: GroupOrderElement,
+    x_cap: GroupOrderElement
+}
+
+impl CredentialRevocationKeyCorrectnessProof {
+    pub fn clone(&self) -> Result<CredentialRevocationKeyCorrectnessProof, IndyCryptoError> {
+        Ok(CredentialRevocationKeyCorrectnessProof {
+            c: self.c.clone()?,
+            sk_cap: self.sk_cap,
+            x_cap: self.x_cap
+        })
+    }
+
+    fn challenge(r_pub_key: &CredentialRevocationPublicKey,
+                 pk_tilda: &PointG1,
+                 y_tilda: &PointG2) -> Result<BigNumber, IndyCryptoError> {
+        helpers::get_hash_as_int(&mut vec![r_pub_key.g.to_bytes()?, r_pub_key.pk.to_bytes()?,
+                                           r_pub_key.h_cap.to_bytes()?, r_pub_key.y.to_bytes()?,
+                                           pk_tilda.to_bytes()?, y_tilda.to_bytes()?])
+    }
+}
+
 /// `Revocation Public Key` is used to verify that credential was'nt revoked by Issuer.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct CredentialRevocationPublicKey {
@@ -214,26 +1291,52 @@ pub struct CredentialRevocationPublicKey {
 }
 
 /// `Revocation Private Key` is used for signing Claim.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CredentialRevocationPrivateKey {
     x: GroupOrderElement,
     sk: GroupOrderElement
 }
 
-pub type Accumulator = PointG2;
+pub use accumulator::Accumulator;
+
+/// Kind of credentials indices accumulated in revocation registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RevocationRegistryMode {
+    /// Accumulator contains issued credentials, prover proves membership of credential index.
+    Membership,
+    /// Accumulator contains revoked credentials only, prover proves non-membership of credential index.
+    /// Issuance of credentials doesn't change registry.
+    NonMembership
+}
+
+impl RevocationRegistryMode {
+    fn is_default(&self) -> bool {
+        *self == RevocationRegistryMode::default()
+    }
+}
+
+impl Default for RevocationRegistryMode {
+    fn default() -> RevocationRegistryMode {
+        RevocationRegistryMode::Membership
+    }
+}
 
 /// `Revocation Registry` contains accumulator.
 /// Must be published by Issuer on a tamper-evident and highly available storage
 /// Used by prover to prove that a claim hasn't revoked by the issuer
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RevocationRegistry {
-    accum: Accumulator
+    #[serde(with = "::pair::compressed")]
+    accum: Accumulator,
+    #[serde(default, skip_serializing_if = "RevocationRegistryMode::is_default")]
+    mode: RevocationRegistryMode
 }
 
 impl From<RevocationRegistryDelta> for RevocationRegistry {
     fn from(rev_reg_delta: RevocationRegistryDelta) -> RevocationRegistry {
         RevocationRegistry {
-            accum: rev_reg_delta.accum
+            accum: rev_reg_delta.accum,
+            mode: rev_reg_delta.mode
         }
     }
 }
@@ -242,18 +1345,125 @@ impl JsonEncodable for RevocationRegistry {}
 
 impl<'a> JsonDecodable<'a> for RevocationRegistry {}
 
+impl RevocationRegistry {
+    pub fn mode(&self) -> RevocationRegistryMode {
+        self.mode
+    }
+
+    /// Returns fingerprint of registry state: hex encoded SHA-256 digest of length-prefixed compressed accumulator.
+    pub fn fingerprint(&self) -> Result<String, IndyCryptoError> {
+        trace!("RevocationRegistry::fingerprint: >>>");
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        append_with_len(&mut values, &self.accum.to_bytes_compressed()?);
+
+        let fingerprint = hex_digest(&values)?;
+
+        trace!("RevocationRegistry::fingerprint: <<< fingerprint: {:?}", fingerprint);
+
+        Ok(fingerprint)
+    }
+
+    /// Returns compact digest of registry state that can be stored instead of the accumulator.
+    pub fn checkpoint(&self) -> Result<RevocationRegistryCheckpoint, IndyCryptoError> {
+        Ok(RevocationRegistryCheckpoint {
+            digest: BigNumber::hash(&self.accum.to_bytes()?)?
+        })
+    }
+
+    /// Checks that registry state of `to` checkpoint is derived from state of `from` checkpoint
+    /// by applying sequence of deltas. Accumulator change of each delta is recomputed from its issued
+    /// and revoked indices using published tails, so revocation history can be validated
+    /// without trusting deltas source.
+    ///
+    /// # Arguments
+    /// * `from` - Checkpoint of initial registry state.
+    /// * `to` - Checkpoint of final registry state.
+    /// * `rev_reg_deltas` - Sequence of deltas between states in order of application.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn verify_consistency<RTA>(from: &RevocationRegistryCheckpoint,
+                                   to: &RevocationRegistryCheckpoint,
+                                   rev_reg_deltas: &[RevocationRegistryDelta],
+                                   max_cred_num: u64,
+                                   rev_tails_accessor: &RTA) -> Result<bool, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("RevocationRegistry::verify_consistency: >>> from: {:?}, to: {:?}, rev_reg_deltas: {:?}, max_cred_num: {:?}",
+               from, to, rev_reg_deltas, max_cred_num);
+
+        let mut checkpoint = from.clone();
+        let mut valid = true;
+
+        for rev_reg_delta in rev_reg_deltas.iter() {
+            rev_reg_delta._check_indices(max_cred_num)?;
+
+            let prev_accum = match rev_reg_delta.prev_accum {
+                Some(prev_accum) => prev_accum,
+                None => {
+                    valid = false;
+                    break;
+                }
+            };
+
+            let prev_rev_reg = RevocationRegistry { accum: prev_accum, mode: rev_reg_delta.mode };
+
+            if prev_rev_reg.checkpoint()? != checkpoint || !rev_reg_delta.issued.is_disjoint(&rev_reg_delta.revoked) {
+                valid = false;
+                break;
+            }
+
+            let mut accum = prev_accum;
+            for rev_idx in rev_reg_delta._accumulated().iter() {
+                accumulator::add_element(&mut accum, *rev_idx, max_cred_num, rev_tails_accessor)?;
+            }
+            for rev_idx in rev_reg_delta._removed().iter() {
+                accumulator::remove_element(&mut accum, *rev_idx, max_cred_num, rev_tails_accessor)?;
+            }
+
+            if accum != rev_reg_delta.accum {
+                valid = false;
+                break;
+            }
+
+            checkpoint = RevocationRegistry { accum, mode: rev_reg_delta.mode }.checkpoint()?;
+        }
+
+        let valid = valid && checkpoint == *to;
+
+        trace!("RevocationRegistry::verify_consistency: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+}
+
+/// Digest of revocation registry state.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RevocationRegistryCheckpoint {
+    digest: Vec<u8>
+}
+
+impl JsonEncodable for RevocationRegistryCheckpoint {}
+
+impl<'a> JsonDecodable<'a> for RevocationRegistryCheckpoint {}
+
+const REV_REG_DELTA_DOMAIN: &'static [u8] = b"indy-crypto:cl:rev-reg-delta";
+
 /// `Revocation Registry Delta` contains Accumulator changes.
 /// Must be applied to `Revocation Registry`
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RevocationRegistryDelta {
     prev_accum: Option<Accumulator>,
     accum: Accumulator,
-    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(skip_serializing_if = "HashSet::is_empty", serialize_with = "serialize_sorted_set")]
+    #[serde(default)]
+    issued: HashSet<u64>,
+    #[serde(skip_serializing_if = "HashSet::is_empty", serialize_with = "serialize_sorted_set")]
     #[serde(default)]
-    issued: HashSet<u32>,
-    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    revoked: HashSet<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    revoked: HashSet<u32>
+    signature: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "RevocationRegistryMode::is_default")]
+    mode: RevocationRegistryMode
 }
 
 impl JsonEncodable for RevocationRegistryDelta {}
@@ -261,13 +1471,36 @@ impl JsonEncodable for RevocationRegistryDelta {}
 impl<'a> JsonDecodable<'a> for RevocationRegistryDelta {}
 
 impl RevocationRegistryDelta {
+    /// Creates delta between two revocation registry states.
+    ///
+    /// # Arguments
+    /// * `rev_reg_from` - (Optional) Initial registry state. If None delta describes full registry state.
+    /// * `rev_reg_to` - Final registry state.
+    /// * `issued` - Indices issued between states.
+    /// * `revoked` - Indices revoked between states.
+    pub fn from_parts(rev_reg_from: Option<&RevocationRegistry>,
+                      rev_reg_to: &RevocationRegistry,
+                      issued: &HashSet<u64>,
+                      revoked: &HashSet<u64>) -> RevocationRegistryDelta {
+        RevocationRegistryDelta {
+            prev_accum: rev_reg_from.map(|rev_reg| rev_reg.accum),
+            accum: rev_reg_to.accum,
+            issued: issued.clone(),
+            revoked: revoked.clone(),
+            signature: None,
+            mode: rev_reg_to.mode
+        }
+    }
+
     pub fn merge(&mut self, other_delta: &RevocationRegistryDelta) -> Result<(), IndyCryptoError> {
-        if other_delta.prev_accum.is_none() || self.accum != other_delta.prev_accum.unwrap() {
+        if other_delta.prev_accum.is_none() || self.accum != other_delta.prev_accum.unwrap() || self.mode != other_delta.mode {
             return Err(IndyCryptoError::InvalidStructure(format!("Deltas can not be merged.")));
         }
 
         self.prev_accum = Some(self.accum);
         self.accum = other_delta.accum;
+        // Issuer signature covers only original delta content
+        self.signature = None;
 
         self.issued.extend(
             other_delta.issued.difference(&self.revoked));
@@ -285,13 +1518,111 @@ impl RevocationRegistryDelta {
 
         Ok(())
     }
+
+    /// Returns true if delta contains Issuer signature.
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    /// Verifies Issuer signature over delta of given revocation registry.
+    /// Returns false if delta isn't signed or is signed for other registry.
+    ///
+    /// # Arguments
+    /// * `rev_reg_id` - Identifier of revocation registry delta was signed for.
+    /// * `issuer_vk` - Issuer BLS verification key.
+    /// * `gen` - BLS generator used for Issuer verification key creation.
+    pub fn verify_signature(&self, rev_reg_id: &str, issuer_vk: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let signature = match self.signature {
+            Some(ref signature) => BlsSignature::from_bytes(signature)?,
+            None => return Ok(false)
+        };
+
+        Bls::verify(&signature, &self._signed_bytes(rev_reg_id)?, issuer_vk, gen)
+    }
+
+    // Indices which tails were added to accumulator.
+    fn _accumulated(&self) -> &HashSet<u64> {
+        match self.mode {
+            RevocationRegistryMode::Membership => &self.issued,
+            RevocationRegistryMode::NonMembership => &self.revoked
+        }
+    }
+
+    // Indices which tails were removed from accumulator.
+    fn _removed(&self) -> &HashSet<u64> {
+        match self.mode {
+            RevocationRegistryMode::Membership => &self.revoked,
+            RevocationRegistryMode::NonMembership => &self.issued
+        }
+    }
+
+    fn _check_indices(&self, max_cred_num: u64) -> Result<(), IndyCryptoError> {
+        for index in self.issued.iter().chain(self.revoked.iter()) {
+            helpers::check_rev_idx(*index, max_cred_num)?;
+        }
+
+        Ok(())
+    }
+
+    // Signature is bound to registry, so delta of one registry can't be replayed as delta of another one.
+    fn _signed_bytes(&self, rev_reg_id: &str) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        append_with_len(&mut values, REV_REG_DELTA_DOMAIN);
+        append_with_len(&mut values, rev_reg_id.as_bytes());
+
+        let mut bytes: Vec<u8> = values.concat();
+
+        match self.prev_accum {
+            Some(ref prev_accum) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&prev_accum.to_bytes()?);
+            }
+            None => bytes.push(0)
+        }
+
+        bytes.extend_from_slice(&self.accum.to_bytes()?);
+
+        for set in [&self.issued, &self.revoked].iter() {
+            let mut indices: Vec<&u64> = set.iter().collect();
+            indices.sort();
+
+            bytes.extend_from_slice(&helpers::transform_u64_to_array_of_u8(indices.len() as u64));
+            for index in indices {
+                bytes.extend_from_slice(&helpers::transform_u64_to_array_of_u8(*index));
+            }
+        }
+
+        bytes.push(match self.mode {
+            RevocationRegistryMode::Membership => 0,
+            RevocationRegistryMode::NonMembership => 1
+        });
+
+        Ok(bytes)
+    }
 }
 
 /// `Revocation Key Public` Accumulator public key.
 /// Must be published together with Accumulator
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RevocationKeyPublic {
-    z: Pair
+    z: Pair,
+    #[serde(default, skip_serializing_if = "RevocationRegistryMode::is_default")]
+    mode: RevocationRegistryMode
+}
+
+impl RevocationKeyPublic {
+    pub fn mode(&self) -> RevocationRegistryMode {
+        self.mode
+    }
+
+    // Value of `e(g_i, accum) / e(g, omega)` for valid witness:
+    // `z` if credential index is accumulated and identity otherwise.
+    fn witness_pairing(&self) -> Result<Pair, IndyCryptoError> {
+        match self.mode {
+            RevocationRegistryMode::Membership => Ok(self.z),
+            RevocationRegistryMode::NonMembership => self.z.mul(&self.z.inverse()?)
+        }
+    }
 }
 
 impl JsonEncodable for RevocationKeyPublic {}
@@ -308,61 +1639,79 @@ impl JsonEncodable for RevocationKeyPrivate {}
 
 impl<'a> JsonDecodable<'a> for RevocationKeyPrivate {}
 
-/// `Tail` point of curve used to update accumulator.
-pub type Tail = PointG2;
-
-impl Tail {
-    fn new_tail(index: u32, g_dash: &PointG2, gamma: &GroupOrderElement) -> Result<Tail, IndyCryptoError> {
-        let i_bytes = helpers::transform_u32_to_array_of_u8(index);
-        let mut pow = GroupOrderElement::from_bytes(&i_bytes)?;
-        pow = gamma.pow_mod(&pow)?;
-        Ok(g_dash.mul(&pow)?)
-    }
-}
+pub use accumulator::Tail;
 
 /// Generator of `Tail's`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RevocationTailsGenerator {
-    size: u32,
-    current_index: u32,
+    size: u64,
+    current_index: u64,
     g_dash: PointG2,
     gamma: GroupOrderElement
 }
 
 impl RevocationTailsGenerator {
-    fn new(max_cred_num: u32, gamma: GroupOrderElement, g_dash: PointG2) -> Self {
+    fn new(max_cred_num: u64, gamma: GroupOrderElement, g_dash: PointG2) -> Self {
         RevocationTailsGenerator {
-            size: 2 * max_cred_num + 1, /* Unused 0th + valuable 1..L + unused (L+1)th + valuable (L+2)..(2L) */
+            size: accumulator::tails_count(max_cred_num),
             current_index: 0,
             gamma,
             g_dash,
         }
     }
 
-    pub fn count(&self) -> u32 {
+    pub fn count(&self) -> u64 {
         self.size - self.current_index
     }
 
+    /// Returns total number of tails in revocation registry.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
     pub fn next(&mut self) -> Result<Option<Tail>, IndyCryptoError> {
         if self.current_index >= self.size {
             return Ok(None);
         }
 
-        let tail = Tail::new_tail(self.current_index, &self.g_dash, &self.gamma)?;
+        let tail = accumulator::new_tail(self.current_index, &self.g_dash, &self.gamma)?;
 
         self.current_index += 1;
 
         Ok(Some(tail))
     }
+
+    /// Writes all tails to binary tails file (see `TailsWriter`) and returns its SHA-256 digest.
+    ///
+    /// Generator must not be advanced before the call.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of tails file.
+    pub fn write_tails_file<W>(&mut self, writer: W) -> Result<Vec<u8>, IndyCryptoError> where W: Write {
+        trace!("RevocationTailsGenerator::write_tails_file: >>> size: {:?}, current_index: {:?}", self.size, self.current_index);
+
+        if self.current_index != 0 {
+            return Err(IndyCryptoError::InvalidState(format!("Tails generator is already advanced to tail {}", self.current_index)));
+        }
+
+        let mut tails_writer = TailsWriter::new(writer, self.size)?;
+        while let Some(tail) = self.next()? {
+            tails_writer.write_tail(&tail)?;
+        }
+        let (_, hash) = tails_writer.finalize()?;
+
+        trace!("RevocationTailsGenerator::write_tails_file: <<< hash: {:?}", hash);
+
+        Ok(hash)
+    }
 }
 
 impl JsonEncodable for RevocationTailsGenerator {}
 
 impl<'a> JsonDecodable<'a> for RevocationTailsGenerator {}
 
-pub trait RevocationTailsAccessor {
-    fn access_tail(&self, tail_id: u32, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError>;
-}
+pub use accumulator::TailsAccessor as RevocationTailsAccessor;
+pub use accumulator::tails::{TailsReader, TailsWriter};
 
 /// Simple implementation of `RevocationTailsAccessor` that stores all tails as HashMap.
 #[derive(Debug, Clone)]
@@ -371,8 +1720,10 @@ pub struct SimpleTailsAccessor {
 }
 
 impl RevocationTailsAccessor for SimpleTailsAccessor {
-    fn access_tail(&self, tail_id: u32, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
-        Ok(accessor(&self.tails[tail_id as usize]))
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        let tail = self.tails.get(tail_id as usize)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails range {}", tail_id, self.tails.len())))?;
+        Ok(accessor(tail))
     }
 }
 
@@ -388,130 +1739,411 @@ impl SimpleTailsAccessor {
     }
 }
 
-
-/// Issuer's signature over Claim attribute values.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct CredentialSignature {
-    p_credential: PrimaryCredentialSignature,
-    r_credential: Option<NonRevocationCredentialSignature> /* will be used to proof is credential revoked preparation */,
+/// Implementation of `RevocationTailsAccessor` for large revocation registries.
+/// Stores tails up to configurable threshold and generates the rest on demand,
+/// so memory usage doesn't depend on max credential number.
+/// Requires tails generator, so it is suitable for Issuer side only.
+#[derive(Debug, Clone)]
+pub struct LazyTailsAccessor {
+    tails: Vec<Tail>,
+    size: u64,
+    g_dash: PointG2,
+    gamma: GroupOrderElement
 }
 
-impl CredentialSignature {
-    pub fn extract_index(&self) -> Option<u32> {
-        self.r_credential
-            .as_ref()
-            .map(|r_credential| r_credential.i)
+impl RevocationTailsAccessor for LazyTailsAccessor {
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        if tail_id >= self.size {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails range {}", tail_id, self.size)));
+        }
+
+        if tail_id < self.tails.len() as u64 {
+            return Ok(accessor(&self.tails[tail_id as usize]));
+        }
+
+        let tail = accumulator::new_tail(tail_id, &self.g_dash, &self.gamma)?;
+        Ok(accessor(&tail))
     }
 }
 
-impl JsonEncodable for CredentialSignature {}
+impl LazyTailsAccessor {
+    /// Creates tails accessor that stores first `precomputed_count` tails.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation registry tails generator.
+    /// * `precomputed_count` - Count of tails to store, default threshold is used if not set.
+    pub fn new(rev_tails_generator: &RevocationTailsGenerator, precomputed_count: Option<u64>) -> Result<LazyTailsAccessor, IndyCryptoError> {
+        let precomputed_count = precomputed_count.unwrap_or(constants::DEFAULT_PRECOMPUTED_TAILS_COUNT);
 
-impl<'a> JsonDecodable<'a> for CredentialSignature {}
+        let mut tails: Vec<Tail> = Vec::new();
+        for index in 0..::std::cmp::min(precomputed_count, rev_tails_generator.size) {
+            tails.push(accumulator::new_tail(index, &rev_tails_generator.g_dash, &rev_tails_generator.gamma)?);
+        }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct PrimaryCredentialSignature {
-    m_2: BigNumber,
-    a: BigNumber,
-    e: BigNumber,
-    v: BigNumber
+        Ok(LazyTailsAccessor {
+            tails,
+            size: rev_tails_generator.size,
+            g_dash: rev_tails_generator.g_dash,
+            gamma: rev_tails_generator.gamma
+        })
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NonRevocationCredentialSignature {
-    sigma: PointG1,
-    c: GroupOrderElement,
-    vr_prime_prime: GroupOrderElement,
-    witness_signature: WitnessSignature,
-    g_i: PointG1,
-    i: u32,
-    m2: GroupOrderElement
+/// Implementation of `RevocationTailsAccessor` that caches tails of another (usually remote or file based) accessor.
+/// Tails are fetched by chunks of consecutive entries and kept in LRU cache limited by size in bytes,
+/// so provers can use registries which tails don't fit in memory.
+pub struct CachingTailsAccessor<RTA> where RTA: RevocationTailsAccessor {
+    tails_accessor: RTA,
+    size: u64,
+    chunk_size: u64,
+    max_chunks: usize,
+    cache: RefCell<TailsCache>
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct SignatureCorrectnessProof {
-    se: BigNumber,
-    c: BigNumber
+struct TailsCache {
+    chunks: HashMap<u64 /* chunk index */, (Vec<Tail>, u64 /* last use */)>,
+    clock: u64
 }
 
-impl JsonEncodable for SignatureCorrectnessProof {}
+impl<RTA> RevocationTailsAccessor for CachingTailsAccessor<RTA> where RTA: RevocationTailsAccessor {
+    fn access_tail(&self, tail_id: u64, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        if tail_id >= self.size {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails range {}", tail_id, self.size)));
+        }
 
-impl<'a> JsonDecodable<'a> for SignatureCorrectnessProof {}
+        let chunk_idx = tail_id / self.chunk_size;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Witness {
-    omega: PointG2
-}
+        let mut cache = self.cache.borrow_mut();
+        cache.clock += 1;
+        let clock = cache.clock;
 
-impl JsonEncodable for Witness {}
+        if !cache.chunks.contains_key(&chunk_idx) {
+            let chunk = self._fetch_chunk(chunk_idx)?;
 
-impl<'a> JsonDecodable<'a> for Witness {}
+            if cache.chunks.len() >= self.max_chunks {
+                let lru_chunk_idx = cache.chunks.iter()
+                    .min_by_key(|&(_, &(_, last_use))| last_use)
+                    .map(|(chunk_idx, _)| *chunk_idx);
 
-impl Witness {
-    pub fn new<RTA>(rev_idx: u32,
-                    max_cred_num: u32,
-                    rev_reg_delta: &RevocationRegistryDelta,
-                    rev_tails_accessor: &RTA) -> Result<Witness, IndyCryptoError> where RTA: RevocationTailsAccessor {
-        trace!("Witness::new: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
-               rev_idx, max_cred_num, rev_reg_delta);
+                if let Some(lru_chunk_idx) = lru_chunk_idx {
+                    cache.chunks.remove(&lru_chunk_idx);
+                }
+            }
 
-        let mut omega = PointG2::new_inf()?;
+            cache.chunks.insert(chunk_idx, (chunk, clock));
+        }
+
+        let entry = cache.chunks.get_mut(&chunk_idx).unwrap();
+        entry.1 = clock;
+
+        Ok(accessor(&entry.0[(tail_id - chunk_idx * self.chunk_size) as usize]))
+    }
+}
+
+impl<RTA> CachingTailsAccessor<RTA> where RTA: RevocationTailsAccessor {
+    /// Creates caching accessor over given tails accessor.
+    ///
+    /// # Arguments
+    /// * `tails_accessor` - Accessor tails are fetched from.
+    /// * `size` - Total number of tails in revocation registry (see `RevocationTailsGenerator::size`).
+    /// * `chunk_size` - Count of consecutive tails fetched at once, default chunk size is used if not set.
+    /// * `cache_size` - Max size of cached tails in bytes, default cache size is used if not set.
+    ///   Cache must fit at least one chunk.
+    pub fn new(tails_accessor: RTA,
+               size: u64,
+               chunk_size: Option<u64>,
+               cache_size: Option<usize>) -> Result<CachingTailsAccessor<RTA>, IndyCryptoError> {
+        trace!("CachingTailsAccessor::new: >>> size: {:?}, chunk_size: {:?}, cache_size: {:?}", size, chunk_size, cache_size);
+
+        let chunk_size = chunk_size.unwrap_or(constants::DEFAULT_TAILS_CHUNK_SIZE);
+        let cache_size = cache_size.unwrap_or(constants::DEFAULT_TAILS_CACHE_SIZE);
+
+        if chunk_size == 0 {
+            return Err(IndyCryptoError::InvalidParam3(format!("Tails chunk size must be positive")));
+        }
 
-        let mut issued = rev_reg_delta.issued.clone();
-        issued.remove(&rev_idx);
+        let chunk_bytes = (chunk_size as usize).saturating_mul(::std::mem::size_of::<Tail>());
+        let max_chunks = cache_size / chunk_bytes;
 
-        for j in issued.iter() {
-            let index = max_cred_num + 1 - j + rev_idx;
-            rev_tails_accessor.access_tail(index, &mut |tail| {
-                omega = omega.add(tail).unwrap();
-            })?;
+        if max_chunks == 0 {
+            return Err(IndyCryptoError::InvalidParam4(format!("Tails cache of {} bytes can't fit chunk of {} bytes", cache_size, chunk_bytes)));
         }
 
-        let witness = Witness {
-            omega
+        let caching_tails_accessor = CachingTailsAccessor {
+            tails_accessor,
+            size,
+            chunk_size,
+            max_chunks,
+            cache: RefCell::new(TailsCache {
+                chunks: HashMap::new(),
+                clock: 0
+            })
         };
 
-        trace!("Witness::new: <<< witness: {:?}", witness);
+        trace!("CachingTailsAccessor::new: <<< max_chunks: {:?}", max_chunks);
 
-        Ok(witness)
+        Ok(caching_tails_accessor)
     }
 
-    pub fn update<RTA>(&mut self,
-                       rev_idx: u32,
-                       max_cred_num: u32,
-                       rev_reg_delta: &RevocationRegistryDelta,
-                       rev_tails_accessor: &RTA) -> Result<(), IndyCryptoError> where RTA: RevocationTailsAccessor {
-        trace!("Witness::update: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
-               rev_idx, max_cred_num, rev_reg_delta);
+    /// Returns number of currently cached tails.
+    pub fn cached_count(&self) -> u64 {
+        self.cache.borrow().chunks.values().map(|&(ref chunk, _)| chunk.len() as u64).sum()
+    }
 
-        let mut omega_denom = PointG2::new_inf()?;
-        for j in rev_reg_delta.revoked.iter() {
-            if rev_idx.eq(j) { continue; }
+    fn _fetch_chunk(&self, chunk_idx: u64) -> Result<Vec<Tail>, IndyCryptoError> {
+        let from = chunk_idx * self.chunk_size;
+        let to = ::std::cmp::min(from.saturating_add(self.chunk_size), self.size);
 
-            let index = max_cred_num + 1 - j + rev_idx;
-            rev_tails_accessor.access_tail(index, &mut |tail| {
-                omega_denom = omega_denom.add(tail).unwrap();
-            })?;
+        let mut chunk: Vec<Tail> = Vec::with_capacity((to - from) as usize);
+        for tail_id in from..to {
+            self.tails_accessor.access_tail(tail_id, &mut |tail| chunk.push(*tail))?;
         }
 
-        let mut omega_num = PointG2::new_inf()?;
-        for j in rev_reg_delta.issued.iter() {
-            if rev_idx.eq(j) { continue; }
+        Ok(chunk)
+    }
+}
+
+/// Source of `Revocation Registry Delta's` used for witness update.
+/// Allows to fetch deltas from ledger or remote service without copying accumulator math.
+pub trait WitnessUpdater {
+    /// Returns deltas of revocation registry made after `from` timestamp and up to `to` timestamp (inclusive)
+    /// in the order they were applied to registry.
+    fn get_deltas(&self, rev_reg_id: &str, from: u64, to: u64) -> Result<Vec<RevocationRegistryDelta>, IndyCryptoError>;
+}
+
+/// Simple implementation of `WitnessUpdater` that stores all deltas in memory.
+#[derive(Debug, Clone)]
+pub struct SimpleWitnessUpdater {
+    deltas: HashMap<String /* rev_reg_id */, BTreeMap<u64 /* timestamp */, RevocationRegistryDelta>>
+}
+
+impl WitnessUpdater for SimpleWitnessUpdater {
+    fn get_deltas(&self, rev_reg_id: &str, from: u64, to: u64) -> Result<Vec<RevocationRegistryDelta>, IndyCryptoError> {
+        if from > to {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid deltas interval: from {} is after to {}", from, to)));
+        }
+
+        Ok(self.deltas.get(rev_reg_id)
+            .map(|deltas| deltas.range((Bound::Excluded(from), Bound::Included(to))).map(|(_, delta)| delta.clone()).collect())
+            .unwrap_or(Vec::new()))
+    }
+}
+
+impl SimpleWitnessUpdater {
+    pub fn new() -> SimpleWitnessUpdater {
+        SimpleWitnessUpdater {
+            deltas: HashMap::new()
+        }
+    }
+
+    /// Stores delta applied to revocation registry at given timestamp.
+    pub fn add_delta(&mut self, rev_reg_id: &str, timestamp: u64, rev_reg_delta: &RevocationRegistryDelta) -> Result<(), IndyCryptoError> {
+        let deltas = self.deltas.entry(rev_reg_id.to_owned()).or_insert(BTreeMap::new());
+
+        if deltas.contains_key(&timestamp) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Delta for timestamp {} already added", timestamp)));
+        }
+
+        deltas.insert(timestamp, rev_reg_delta.clone());
+        Ok(())
+    }
+}
+
+/// Issuer's signature over Claim attribute values.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialSignature {
+    p_credential: PrimaryCredentialSignature,
+    r_credential: Option<NonRevocationCredentialSignature> /* will be used to proof is credential revoked preparation */,
+}
+
+impl CredentialSignature {
+    pub fn extract_index(&self) -> Option<u64> {
+        self.r_credential
+            .as_ref()
+            .map(|r_credential| r_credential.i)
+    }
 
-            let index = max_cred_num + 1 - j + rev_idx;
-            rev_tails_accessor.access_tail(index, &mut |tail| {
-                omega_num = omega_num.add(tail).unwrap();
-            })?;
+    /// Returns digest that identifies credential signature.
+    pub fn digest(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        values.push(self.p_credential.m_2.to_bytes()?);
+        values.push(self.p_credential.a.to_bytes()?);
+        values.push(self.p_credential.e.to_bytes()?);
+        values.push(self.p_credential.v.to_bytes()?);
+        if let Some(index) = self.extract_index() {
+            values.push(helpers::transform_u64_to_array_of_u8(index));
         }
 
-        let new_omega: PointG2 = self.omega.add(
-            &omega_num.sub(&omega_denom)?)?;
+        BigNumber::hash_array(&values)
+    }
+}
+
+impl JsonEncodable for CredentialSignature {}
+
+impl<'a> JsonDecodable<'a> for CredentialSignature {}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimaryCredentialSignature {
+    m_2: BigNumber,
+    a: BigNumber,
+    e: BigNumber,
+    v: BigNumber
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonRevocationCredentialSignature {
+    sigma: PointG1,
+    c: GroupOrderElement,
+    vr_prime_prime: GroupOrderElement,
+    witness_signature: WitnessSignature,
+    g_i: PointG1,
+    i: u64,
+    m2: GroupOrderElement
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SignatureCorrectnessProof {
+    se: BigNumber,
+    c: BigNumber
+}
+
+impl JsonEncodable for SignatureCorrectnessProof {}
+
+impl<'a> JsonDecodable<'a> for SignatureCorrectnessProof {}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Witness {
+    omega: PointG2
+}
+
+impl JsonEncodable for Witness {}
+
+impl<'a> JsonDecodable<'a> for Witness {}
+
+/// Witness bound to the credential signature and revocation registry it was created for.
+/// Can be stored separately from credential without risk of mismatch.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BoundWitness {
+    witness: Witness,
+    cred_signature_digest: Vec<u8>,
+    rev_reg_id: String
+}
+
+impl JsonEncodable for BoundWitness {}
+
+impl<'a> JsonDecodable<'a> for BoundWitness {}
+
+impl Witness {
+    pub fn new<RTA>(rev_idx: u64,
+                    max_cred_num: u64,
+                    rev_reg_delta: &RevocationRegistryDelta,
+                    rev_tails_accessor: &RTA) -> Result<Witness, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Witness::new: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
+               rev_idx, max_cred_num, rev_reg_delta);
+
+        helpers::check_rev_idx(rev_idx, max_cred_num)?;
+        rev_reg_delta._check_indices(max_cred_num)?;
+
+        let witness = Witness {
+            omega: accumulator::witness_sum(rev_idx, max_cred_num, rev_reg_delta._accumulated(), rev_tails_accessor)?
+        };
+
+        trace!("Witness::new: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+
+    pub fn update<RTA>(&mut self,
+                       rev_idx: u64,
+                       max_cred_num: u64,
+                       rev_reg_delta: &RevocationRegistryDelta,
+                       rev_tails_accessor: &RTA) -> Result<(), IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Witness::update: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
+               rev_idx, max_cred_num, rev_reg_delta);
 
-        self.omega = new_omega;
+        helpers::check_rev_idx(rev_idx, max_cred_num)?;
+        rev_reg_delta._check_indices(max_cred_num)?;
+
+        self.omega = accumulator::update_witness(&self.omega,
+                                                 rev_idx,
+                                                 max_cred_num,
+                                                 rev_reg_delta._accumulated(),
+                                                 rev_reg_delta._removed(),
+                                                 rev_tails_accessor)?;
 
         trace!("Witness::update: <<<");
 
         Ok(())
     }
+
+    /// Translates witness to revocation registry created by `Issuer::split_revocation_registry`,
+    /// `Issuer::merge_revocation_registries` or `Issuer::migrate_revocation_registry_def`.
+    /// Credential keeps its revocation index in the new registry.
+    ///
+    /// # Arguments
+    /// * `rev_idx` - Index of the credential in revocation registry.
+    /// * `max_cred_num` - Max credential number in the new revocation registry.
+    /// * `migration_delta` - Delta returned by Issuer for holders of credential.
+    ///   Delta without previous accumulator describes full state of the new registry.
+    /// * `rev_tails_accessor` - Tails accessor of the new revocation registry.
+    pub fn translate<RTA>(&self,
+                          rev_idx: u64,
+                          max_cred_num: u64,
+                          migration_delta: &RevocationRegistryDelta,
+                          rev_tails_accessor: &RTA) -> Result<Witness, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Witness::translate: >>> rev_idx: {:?}, max_cred_num: {:?}, migration_delta: {:?}",
+               rev_idx, max_cred_num, migration_delta);
+
+        let witness = match migration_delta.prev_accum {
+            Some(_) => {
+                let mut witness = self.clone();
+                witness.update(rev_idx, max_cred_num, migration_delta, rev_tails_accessor)?;
+                witness
+            }
+            None => Witness::new(rev_idx, max_cred_num, migration_delta, rev_tails_accessor)?
+        };
+
+        trace!("Witness::translate: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+
+    /// Updates witness by delta only if delta is signed by Issuer.
+    /// Protects Prover from deltas with poisoned accumulator changes received from untrusted sources.
+    ///
+    /// # Arguments
+    /// * `rev_idx` - Index of the credential in revocation registry.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `rev_reg_delta` - Revocation registry delta signed by `Issuer::sign_delta`.
+    /// * `rev_reg_id` - Identifier of revocation registry delta must be signed for.
+    /// * `issuer_vk` - Issuer BLS verification key.
+    /// * `gen` - BLS generator used for Issuer verification key creation.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn update_verified<RTA>(&mut self,
+                                rev_idx: u64,
+                                max_cred_num: u64,
+                                rev_reg_delta: &RevocationRegistryDelta,
+                                rev_reg_id: &str,
+                                issuer_vk: &VerKey,
+                                gen: &Generator,
+                                rev_tails_accessor: &RTA) -> Result<(), IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Witness::update_verified: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}, rev_reg_id: {:?}, issuer_vk: {:?}",
+               rev_idx, max_cred_num, rev_reg_delta, rev_reg_id, issuer_vk);
+
+        if !rev_reg_delta.is_signed() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Revocation registry delta isn't signed")));
+        }
+
+        if !rev_reg_delta.verify_signature(rev_reg_id, issuer_vk, gen)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid revocation registry delta signature")));
+        }
+
+        self.update(rev_idx, max_cred_num, rev_reg_delta, rev_tails_accessor)?;
+
+        trace!("Witness::update_verified: <<<");
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -535,6 +2167,36 @@ impl MasterSecret {
     pub fn clone(&self) -> Result<MasterSecret, IndyCryptoError> {
         Ok(MasterSecret { ms: self.ms.clone()? })
     }
+
+    /// Splits master secret into `shares_count` shares so any `threshold` of them restore it (backup or social recovery).
+    ///
+    /// # Arguments
+    /// * `threshold` - Count of shares required to restore master secret.
+    /// * `shares_count` - Count of shares.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::MasterSecret;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::utils::json::JsonEncodable;
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let shares = master_secret.split(2, 3).unwrap();
+    ///
+    /// let restored_master_secret = MasterSecret::recover(&shares[1..]).unwrap();
+    /// assert_eq!(master_secret.to_json().unwrap(), restored_master_secret.to_json().unwrap());
+    /// ```
+    pub fn split(&self, threshold: usize, shares_count: usize) -> Result<Vec<SecretShare>, IndyCryptoError> {
+        split_secret(&self.ms, threshold, shares_count)
+    }
+
+    /// Restores master secret from shares created by `MasterSecret::split`.
+    ///
+    /// # Arguments
+    /// * `shares` - At least `threshold` distinct shares.
+    pub fn recover(shares: &[SecretShare]) -> Result<MasterSecret, IndyCryptoError> {
+        Ok(MasterSecret { ms: recover_secret(shares)? })
+    }
 }
 
 impl JsonEncodable for MasterSecret {}
@@ -587,428 +2249,1875 @@ impl JsonEncodable for BlindedMasterSecretCorrectnessProof {}
 
 impl<'a> JsonDecodable<'a> for BlindedMasterSecretCorrectnessProof {}
 
-/// “Sub Proof Request” - input to create a Proof for a credential;
-/// Contains attributes to be revealed and predicates.
-#[derive(Debug, Clone)]
-pub struct SubProofRequest {
-    revealed_attrs: HashSet<String>,
-    predicates: HashSet<Predicate>,
-}
-
-/// Builder of “Sub Proof Request”.
-#[derive(Debug)]
-pub struct SubProofRequestBuilder {
-    value: SubProofRequest
+/// Registry of enrolled holders (e.g. sanctions list) maintained by Issuer.
+/// Holder is enrolled by identifier derived from master secret; identifiers are accumulated in RSA accumulator.
+/// Holder that isn't enrolled gets credential with identifier hashed to prime as hidden attribute
+/// (see `Issuer::new_non_ownership_member`) and proves non-membership of committed attribute
+/// (see `ProofBuilder::add_non_ownership_request`), so proofs don't disclose identifier.
+/// Only accumulator value is published, enrolled identifiers are kept by Issuer (see `NonOwnershipRegistryMembers`).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NonOwnershipRegistry {
+    n: BigNumber,
+    g: BigNumber,
+    h: BigNumber,
+    accum: BigNumber
 }
 
-impl SubProofRequestBuilder {
-    pub fn new() -> Result<SubProofRequestBuilder, IndyCryptoError> {
-        Ok(SubProofRequestBuilder {
-            value: SubProofRequest {
-                revealed_attrs: HashSet::new(),
-                predicates: HashSet::new()
-            }
+impl NonOwnershipRegistry {
+    pub fn clone(&self) -> Result<NonOwnershipRegistry, IndyCryptoError> {
+        Ok(NonOwnershipRegistry {
+            n: self.n.clone()?,
+            g: self.g.clone()?,
+            h: self.h.clone()?,
+            accum: self.accum.clone()?
         })
     }
 
-    pub fn add_revealed_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
-        self.value.revealed_attrs.insert(attr.to_owned());
-        Ok(())
+    /// Returns holder identifier in registry for master secret.
+    pub fn identifier(&self, master_secret: &MasterSecret) -> Result<BigNumber, IndyCryptoError> {
+        self.h.mod_exp(&master_secret.ms, &self.n, None)
     }
+}
 
-    pub fn add_predicate(&mut self, attr_name: &str, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
-        let p_type = match p_type {
-            "GE" => PredicateType::GE,
-            p_type => return Err(IndyCryptoError::InvalidStructure(format!("Invalid predicate type: {:?}", p_type)))
-        };
+impl JsonEncodable for NonOwnershipRegistry {}
 
-        let predicate = Predicate {
-            attr_name: attr_name.to_owned(),
-            p_type,
-            value
-        };
+impl<'a> JsonDecodable<'a> for NonOwnershipRegistry {}
 
-        self.value.predicates.insert(predicate);
-        Ok(())
+/// Identifiers enrolled to non-ownership registry and factorization of registry modulus.
+/// Kept by Issuer and must not be published.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NonOwnershipRegistryMembers {
+    members: Vec<BigNumber>,
+    p_safe: BigNumber,
+    q_safe: BigNumber
+}
+
+impl NonOwnershipRegistryMembers {
+    pub fn clone(&self) -> Result<NonOwnershipRegistryMembers, IndyCryptoError> {
+        Ok(NonOwnershipRegistryMembers {
+            members: clone_bignum_vec(&self.members)?,
+            p_safe: self.p_safe.clone()?,
+            q_safe: self.q_safe.clone()?
+        })
     }
 
-    pub fn finalize(self) -> Result<SubProofRequest, IndyCryptoError> {
-        Ok(self.value)
+    /// Returns true if holder with identifier is enrolled.
+    pub fn is_enrolled(&self, identifier: &BigNumber) -> Result<bool, IndyCryptoError> {
+        let member = helpers::get_hash_as_prime(&identifier.to_bytes()?)?;
+        Ok(self.members.contains(&member))
     }
 }
 
-/// Some condition that must be satisfied.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
-pub struct Predicate {
-    attr_name: String,
-    p_type: PredicateType,
-    value: i32,
-}
+impl JsonEncodable for NonOwnershipRegistryMembers {}
 
-/// Condition type (Currently GE only).
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum PredicateType {
-    GE
-}
+impl<'a> JsonDecodable<'a> for NonOwnershipRegistryMembers {}
 
-/// Proof is complex crypto structure created by prover over multiple credentials that allows to prove that prover:
-/// 1) Knows signature over credentials issued with specific issuer keys (identified by key id)
-/// 2) Claim contains attributes with specific values that prover wants to disclose
-/// 3) Claim contains attributes with valid predicates that verifier wants the prover to satisfy.
+/// Witness that holder identifier isn't accumulated in non-ownership registry.
+/// Issued by Issuer (see `Issuer::new_non_ownership_witness`) and valid only for the current accumulator value,
+/// so it must be requested again after registry update.
 #[derive(Debug, Deserialize, Serialize)]
-pub struct Proof {
-    proofs: Vec<SubProof>,
-    aggregated_proof: AggregatedProof,
+pub struct NonOwnershipWitness {
+    a: BigNumber,
+    d: BigNumber
 }
 
-impl JsonEncodable for Proof {}
+impl NonOwnershipWitness {
+    pub fn clone(&self) -> Result<NonOwnershipWitness, IndyCryptoError> {
+        Ok(NonOwnershipWitness {
+            a: self.a.clone()?,
+            d: self.d.clone()?
+        })
+    }
+}
 
-impl<'a> JsonDecodable<'a> for Proof {}
+impl JsonEncodable for NonOwnershipWitness {}
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct SubProof {
-    primary_proof: PrimaryProof,
-    non_revoc_proof: Option<NonRevocProof>
-}
+impl<'a> JsonDecodable<'a> for NonOwnershipWitness {}
 
+/// Domain-specific identifier of holder derived from master secret: H(domain)^ms mod p,
+/// where p is fixed safe prime and H(domain) is quadratic residue modulo p.
+/// Pseudonym is the same each time holder presents to the same domain,
+/// but pseudonyms for different domains can't be linked to each other.
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
-pub struct AggregatedProof {
-    c_hash: BigNumber,
-    c_list: Vec<Vec<u8>>
+pub struct Pseudonym {
+    domain: String,
+    value: BigNumber
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct PrimaryProof {
-    eq_proof: PrimaryEqualProof,
-    ge_proofs: Vec<PrimaryPredicateGEProof>
-}
+impl Pseudonym {
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct PrimaryEqualProof {
-    revealed_attrs: HashMap<String /* attr_name of revealed */, BigNumber>,
-    a_prime: BigNumber,
-    e: BigNumber,
-    v: BigNumber,
-    m: HashMap<String /* attr_name of all except revealed */, BigNumber>,
-    m1: BigNumber,
-    m2: BigNumber
-}
+    pub fn value(&self) -> &BigNumber {
+        &self.value
+    }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct PrimaryPredicateGEProof {
-    u: HashMap<String, BigNumber>,
-    r: HashMap<String, BigNumber>,
-    mj: BigNumber,
-    alpha: BigNumber,
-    t: HashMap<String, BigNumber>,
-    predicate: Predicate
-}
+    pub fn clone(&self) -> Result<Pseudonym, IndyCryptoError> {
+        Ok(Pseudonym {
+            domain: self.domain.clone(),
+            value: self.value.clone()?
+        })
+    }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct NonRevocProof {
-    x_list: NonRevocProofXList,
-    c_list: NonRevocProofCList
-}
+    fn modulus() -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::from_hex(constants::PSEUDONYM_GROUP_MODULUS)
+    }
 
-#[derive(Debug)]
-pub struct InitProof {
-    primary_init_proof: PrimaryInitProof,
-    non_revoc_init_proof: Option<NonRevocInitProof>,
-    credential_values: CredentialValues,
-    sub_proof_request: SubProofRequest,
-    credential_schema: CredentialSchema
+    fn base(domain: &str) -> Result<BigNumber, IndyCryptoError> {
+        let p = Pseudonym::modulus()?;
+        helpers::get_full_domain_hash_as_int(&vec![domain.as_bytes().to_vec()], &p)?
+            .mod_exp(&BigNumber::from_u32(2)?, &p, None)
+    }
+
+    // Pseudonym received from Prover must be reduced and belong to the subgroup of quadratic residues except 1
+    fn is_in_subgroup(&self, ctx: &mut BigNumberContext) -> Result<bool, IndyCryptoError> {
+        let p = Pseudonym::modulus()?;
+        let q = p.sub(&BigNumber::from_u32(1)?)?.div(&BigNumber::from_u32(2)?, Some(ctx))?;
+        let one = BigNumber::from_u32(1)?;
+
+        Ok(self.value > one && self.value < p && self.value.mod_exp(&q, &p, Some(ctx))? == one)
+    }
 }
 
+impl JsonEncodable for Pseudonym {}
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct PrimaryInitProof {
-    eq_proof: PrimaryEqualInitProof,
-    ge_proofs: Vec<PrimaryPredicateGEInitProof>
+impl<'a> JsonDecodable<'a> for Pseudonym {}
+
+/// Proof of knowledge of master secret behind pseudonym that doesn't require any credential.
+/// Allows holder to bind pseudonym to verifier session before any credential is issued.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct MasterSecretProof {
+    pseudonym: Pseudonym,
+    c: BigNumber,
+    s: BigNumber
 }
 
-impl PrimaryInitProof {
-    pub fn as_c_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let mut c_list: Vec<Vec<u8>> = self.eq_proof.as_list()?;
-        for ge_proof in self.ge_proofs.iter() {
-            c_list.append_vec(ge_proof.as_list()?)?;
-        }
-        Ok(c_list)
+impl MasterSecretProof {
+    pub fn pseudonym(&self) -> &Pseudonym {
+        &self.pseudonym
     }
 
-    pub fn as_tau_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let mut tau_list: Vec<Vec<u8>> = self.eq_proof.as_tau_list()?;
-        for ge_proof in self.ge_proofs.iter() {
-            tau_list.append_vec(ge_proof.as_tau_list()?)?;
-        }
-        Ok(tau_list)
+    fn challenge(pseudonym: &Pseudonym, t: &BigNumber, nonce: &Nonce) -> Result<BigNumber, IndyCryptoError> {
+        helpers::get_hash_as_int(&vec![pseudonym.domain.as_bytes().to_vec(),
+                                       pseudonym.value.to_bytes()?,
+                                       t.to_bytes()?,
+                                       nonce.to_bytes()?])
     }
 }
 
-#[derive(Debug)]
-pub struct NonRevocInitProof {
-    c_list_params: NonRevocProofXList,
-    tau_list_params: NonRevocProofXList,
-    c_list: NonRevocProofCList,
-    tau_list: NonRevocProofTauList
-}
+impl JsonEncodable for MasterSecretProof {}
 
-impl NonRevocInitProof {
-    pub fn as_c_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let vec = self.c_list.as_list()?;
-        Ok(vec)
-    }
-
-    pub fn as_tau_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let vec = self.tau_list.as_slice()?;
-        Ok(vec)
-    }
-}
+impl<'a> JsonDecodable<'a> for MasterSecretProof {}
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct PrimaryEqualInitProof {
-    a_prime: BigNumber,
-    t: BigNumber,
-    e_tilde: BigNumber,
-    e_prime: BigNumber,
-    v_tilde: BigNumber,
-    v_prime: BigNumber,
-    m_tilde: HashMap<String, BigNumber>,
-    m1_tilde: BigNumber,
-    m2_tilde: BigNumber,
-    m2: BigNumber
+/// Auditor public key used for verifiable encryption of hidden attributes.
+/// Encryption is performed in Z*(n^2) group, so Auditor can decrypt attribute value of any size below n.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditorPublicKey {
+    n: BigNumber,
+    g: BigNumber,
+    y: BigNumber
 }
 
-impl PrimaryEqualInitProof {
-    pub fn as_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        Ok(vec![self.a_prime.to_bytes()?])
+impl AuditorPublicKey {
+    pub fn clone(&self) -> Result<AuditorPublicKey, IndyCryptoError> {
+        Ok(AuditorPublicKey {
+            n: self.n.clone()?,
+            g: self.g.clone()?,
+            y: self.y.clone()?
+        })
     }
 
-    pub fn as_tau_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        Ok(vec![self.t.to_bytes()?])
+    fn n_sqr(&self) -> Result<BigNumber, IndyCryptoError> {
+        self.n.sqr(None)
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct PrimaryPredicateGEInitProof {
-    c_list: Vec<BigNumber>,
-    tau_list: Vec<BigNumber>,
-    u: HashMap<String, BigNumber>,
-    u_tilde: HashMap<String, BigNumber>,
-    r: HashMap<String, BigNumber>,
-    r_tilde: HashMap<String, BigNumber>,
-    alpha_tilde: BigNumber,
-    predicate: Predicate,
-    t: HashMap<String, BigNumber>
-}
+impl JsonEncodable for AuditorPublicKey {}
 
-impl PrimaryPredicateGEInitProof {
-    pub fn as_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
-        Ok(&self.c_list)
-    }
+impl<'a> JsonDecodable<'a> for AuditorPublicKey {}
 
-    pub fn as_tau_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
-        Ok(&self.tau_list)
-    }
+/// Auditor private key used for decryption of hidden attributes.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditorPrivateKey {
+    x: BigNumber
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NonRevocProofXList {
-    rho: GroupOrderElement,
-    r: GroupOrderElement,
-    r_prime: GroupOrderElement,
-    r_prime_prime: GroupOrderElement,
-    r_prime_prime_prime: GroupOrderElement,
-    o: GroupOrderElement,
-    o_prime: GroupOrderElement,
-    m: GroupOrderElement,
-    m_prime: GroupOrderElement,
-    t: GroupOrderElement,
-    t_prime: GroupOrderElement,
-    m2: GroupOrderElement,
-    s: GroupOrderElement,
-    c: GroupOrderElement
+impl JsonEncodable for AuditorPrivateKey {}
+
+impl<'a> JsonDecodable<'a> for AuditorPrivateKey {}
+
+/// Attribute value encrypted under `AuditorPublicKey`: u = g^r, e = y^r * (1 + n)^m mod n^2.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AttributeCiphertext {
+    u: BigNumber,
+    e: BigNumber
 }
 
-impl NonRevocProofXList {
-    pub fn as_list(&self) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
-        Ok(vec![self.rho, self.o, self.c, self.o_prime, self.m, self.m_prime, self.t, self.t_prime,
-                self.m2, self.s, self.r, self.r_prime, self.r_prime_prime, self.r_prime_prime_prime])
-    }
+impl JsonEncodable for AttributeCiphertext {}
 
-    pub fn from_list(seq: Vec<GroupOrderElement>) -> NonRevocProofXList {
-        NonRevocProofXList {
-            rho: seq[0],
-            r: seq[10],
-            r_prime: seq[11],
-            r_prime_prime: seq[12],
-            r_prime_prime_prime: seq[13],
-            o: seq[1],
-            o_prime: seq[3],
-            m: seq[4],
-            m_prime: seq[5],
-            t: seq[6],
-            t_prime: seq[7],
-            m2: seq[8],
-            s: seq[9],
-            c: seq[2]
-        }
-    }
+impl<'a> JsonDecodable<'a> for AttributeCiphertext {}
+
+/// Proof that holder identifier in `NonOwnershipRegistry` is derived
+/// from the same master secret as `BlindedMasterSecret` used in credential issuance.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EnrollmentProof {
+    c: BigNumber,
+    v_dash_cap: BigNumber,
+    ms_cap: BigNumber
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NonRevocProofCList {
-    e: PointG1,
-    d: PointG1,
-    a: PointG1,
-    g: PointG1,
-    w: PointG2,
-    s: PointG2,
-    u: PointG2
+impl JsonEncodable for EnrollmentProof {}
+
+impl<'a> JsonDecodable<'a> for EnrollmentProof {}
+
+/// “Sub Proof Request” - input to create a Proof for a credential;
+/// Contains attributes to be revealed and predicates.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubProofRequest {
+    revealed_attrs: HashSet<String>,
+    predicates: HashSet<Predicate>,
+    linear_predicates: Vec<LinearPredicate>,
+    non_revoked: Option<NonRevokedInterval>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    attr_value_checks: BTreeMap<String /* attr_name */, String /* decimal encoded value */>
 }
 
-impl NonRevocProofCList {
-    pub fn as_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        Ok(vec![self.e.to_bytes()?, self.d.to_bytes()?, self.a.to_bytes()?, self.g.to_bytes()?,
-                self.w.to_bytes()?, self.s.to_bytes()?, self.u.to_bytes()?])
+impl SubProofRequest {
+    pub fn revealed_attrs(&self) -> &HashSet<String> {
+        &self.revealed_attrs
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct NonRevocProofTauList {
-    t1: PointG1,
-    t2: PointG1,
-    t3: Pair,
-    t4: Pair,
-    t5: PointG1,
-    t6: PointG1,
-    t7: Pair,
-    t8: Pair
-}
+    /// Attributes Prover proves to be equal to values required by Verifier without revealing them in proof.
+    pub fn attr_value_checks(&self) -> &BTreeMap<String, String> {
+        &self.attr_value_checks
+    }
 
-impl NonRevocProofTauList {
-    pub fn as_slice(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        Ok(vec![self.t1.to_bytes()?, self.t2.to_bytes()?, self.t3.to_bytes()?, self.t4.to_bytes()?,
-                self.t5.to_bytes()?, self.t6.to_bytes()?, self.t7.to_bytes()?, self.t8.to_bytes()?])
+    // Checked attribute is neither revealed nor hidden in equality proof, so it can't be revealed
+    // (it would be accounted twice) or used in predicate (predicate proof requires hidden attribute).
+    fn _check_attr_value_checks(&self) -> Result<(), IndyCryptoError> {
+        if let Some(attr) = self.attr_value_checks.keys().find(|attr| self.revealed_attrs.contains(*attr)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' is both revealed and checked against value", attr)));
+        }
+
+        if let Some(predicate) = self.predicates.iter().find(|predicate| self.attr_value_checks.contains_key(&predicate.attr_name)) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Attribute '{}' is both used in predicate and checked against value", predicate.attr_name)));
+        }
+
+        Ok(())
     }
-}
 
-/// Random BigNumber that uses `Prover` for proof generation and `Verifier` for proof verification.
-pub type Nonce = BigNumber;
+    /// Attributes hidden in equality proof: all schema attributes except revealed and checked ones.
+    fn unrevealed_attrs(&self, cred_schema: &CredentialSchema) -> HashSet<String> {
+        cred_schema.attrs.iter()
+            .filter(|attr| !self.revealed_attrs.contains(*attr) && !self.attr_value_checks.contains_key(*attr))
+            .cloned()
+            .collect()
+    }
 
-impl JsonEncodable for Nonce {}
+    pub fn predicates(&self) -> &HashSet<Predicate> {
+        &self.predicates
+    }
 
-impl<'a> JsonDecodable<'a> for Nonce {}
+    pub fn linear_predicates(&self) -> &[LinearPredicate] {
+        &self.linear_predicates
+    }
 
-#[derive(Debug)]
-pub struct VerifiableCredential {
-    pub_key: CredentialPublicKey,
-    sub_proof_request: SubProofRequest,
-    credential_schema: CredentialSchema,
-    rev_key_pub: Option<RevocationKeyPublic>,
-    rev_reg: Option<RevocationRegistry>
+    pub fn non_revoked(&self) -> Option<&NonRevokedInterval> {
+        self.non_revoked.as_ref()
+    }
 }
 
-trait BytesView {
-    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError>;
+/// Interval of timestamps (seconds since epoch, inclusive) revocation registry state
+/// used for non-revocation proof must belong to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NonRevokedInterval {
+    from: u64,
+    to: u64
 }
 
-impl BytesView for BigNumber {
-    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
-        Ok(self.to_bytes()?)
+impl NonRevokedInterval {
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.from <= timestamp && timestamp <= self.to
     }
 }
 
-impl BytesView for PointG1 {
-    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
-        Ok(self.to_bytes()?)
-    }
+/// Builder of “Sub Proof Request”.
+#[derive(Debug)]
+pub struct SubProofRequestBuilder {
+    value: SubProofRequest,
+    attr_canonicalization: AttrNameCanonicalization
 }
 
-impl BytesView for GroupOrderElement {
-    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
-        Ok(self.to_bytes()?)
+impl SubProofRequestBuilder {
+    pub fn new() -> Result<SubProofRequestBuilder, IndyCryptoError> {
+        Ok(SubProofRequestBuilder {
+            value: SubProofRequest {
+                revealed_attrs: HashSet::new(),
+                predicates: HashSet::new(),
+                linear_predicates: Vec::new(),
+                non_revoked: None,
+                attr_value_checks: BTreeMap::new()
+            },
+            attr_canonicalization: AttrNameCanonicalization::Exact
+        })
     }
-}
 
-impl BytesView for Pair {
-    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
-        Ok(self.to_bytes()?)
+    /// Selects canonicalization of attribute names used by following `add_*` calls.
+    /// Names of revealed attributes and predicates are also canonicalized by Prover and Verifier
+    /// with canonicalization of credential schema.
+    pub fn set_attr_canonicalization(&mut self, attr_canonicalization: AttrNameCanonicalization) -> Result<(), IndyCryptoError> {
+        self.attr_canonicalization = attr_canonicalization;
+        Ok(())
     }
-}
 
-trait AppendByteArray {
-    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError>;
-}
+    pub fn add_revealed_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
+        self.value.revealed_attrs.insert(self.attr_canonicalization.canonicalize(attr));
+        Ok(())
+    }
 
-impl AppendByteArray for Vec<Vec<u8>> {
-    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError> {
-        for el in other.iter() {
-            self.push(el.to_bytes()?);
+    pub fn add_revealed_chunked_attr(&mut self, attr: &str, max_bits: usize) -> Result<(), IndyCryptoError> {
+        let attr = self.attr_canonicalization.canonicalize(attr);
+
+        for idx in 0..helpers::attr_chunks_count(max_bits)? {
+            self.value.revealed_attrs.insert(helpers::attr_chunk_name(&attr, idx));
         }
         Ok(())
     }
-}
 
-fn clone_bignum_map<K: Clone + Eq + Hash>(other: &HashMap<K, BigNumber>)
-                                          -> Result<HashMap<K, BigNumber>, IndyCryptoError> {
-    let mut res: HashMap<K, BigNumber> = HashMap::new();
-    for (k, v) in other {
-        res.insert(k.clone(), v.clone()?);
+    /// Requires attribute to be equal to encoded value known to Verifier. Value isn't included in proof,
+    /// so proof doesn't carry the attribute like revealed attributes do.
+    /// Checked attribute can't be revealed or used in predicate, `finalize` fails for such request.
+    ///
+    /// # Arguments
+    /// * `attr` - Attribute name.
+    /// * `dec_value` - Required encoded value of attribute in decimal.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_attr_value_check("role", "1139481716457488690172217916278103335").unwrap();
+    /// let _sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    /// ```
+    pub fn add_attr_value_check(&mut self, attr: &str, dec_value: &str) -> Result<(), IndyCryptoError> {
+        let value = BigNumber::from_dec_checked(dec_value)?.to_dec()?;
+        self.value.attr_value_checks.insert(self.attr_canonicalization.canonicalize(attr), value);
+        Ok(())
     }
-    Ok(res)
-}
 
-fn clone_btree_bignum_map<K: Clone + Eq + Hash + Ord>(other: &BTreeMap<K, BigNumber>)
-                                                      -> Result<BTreeMap<K, BigNumber>, IndyCryptoError> {
-    let mut res: BTreeMap<K, BigNumber> = BTreeMap::new();
-    for (k, v) in other {
-        res.insert(k.clone(), v.clone()?);
+    pub fn add_predicate(&mut self, attr_name: &str, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
+        self.add_predicate_with_proof_version(attr_name, p_type, value, PredicateProofVersion::FourSquares)
     }
-    Ok(res)
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use self::issuer::Issuer;
-    use self::prover::Prover;
-    use self::verifier::Verifier;
+    /// Adds predicate proven with specific proof system.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Attribute name.
+    /// * `p_type` - Predicate type (GE, GT, LE or LT). Range proofs support GE and GT only.
+    /// * `value` - Value the attribute is compared with.
+    /// * `proof_version` - Proof system Prover must use for the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::PredicateProofVersion;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_predicate_with_proof_version("age", "GE", 18, PredicateProofVersion::RangeProof).unwrap();
+    /// let _sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    /// ```
+    pub fn add_predicate_with_proof_version(&mut self, attr_name: &str, p_type: &str, value: i32,
+                                            proof_version: PredicateProofVersion) -> Result<(), IndyCryptoError> {
+        let p_type = PredicateType::from_name(p_type)?;
+
+        if p_type.is_less() && proof_version == PredicateProofVersion::RangeProof {
+            return Err(IndyCryptoError::InvalidStructure(format!("Range proof doesn't support predicate type: {:?}", p_type)));
+        }
 
-    #[test]
-    fn demo() {
-        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("name").unwrap();
-        credential_schema_builder.add_attr("sex").unwrap();
-        credential_schema_builder.add_attr("age").unwrap();
-        credential_schema_builder.add_attr("height").unwrap();
-        let credential_schema = credential_schema_builder.finalize().unwrap();
+        let predicate = Predicate {
+            attr_name: self.attr_canonicalization.canonicalize(attr_name),
+            p_type,
+            value,
+            proof_version
+        };
 
-        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+        self.value.predicates.insert(predicate);
+        Ok(())
+    }
 
-        let master_secret = Prover::new_master_secret().unwrap();
+    /// Adds predicate over linear combination of hidden attributes, e.g. salary_1 + salary_2 >= 50000.
+    /// Attributes can belong to any sub proof of the proof; the combination is proven under key of this sub proof.
+    ///
+    /// # Arguments
+    /// * `terms` - Terms of combination: index of sub proof (in order of sub proof requests), attribute name and coefficient.
+    /// * `p_type` - Predicate type (GE, GT, LE or LT).
+    /// * `value` - Value the combination is compared with.
+    pub fn add_linear_predicate(&mut self, terms: &[(usize, &str, i32)], p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
+        let p_type = PredicateType::from_name(p_type)?;
+
+        if terms.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of linear predicate terms is empty")));
+        }
 
-        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let linear_predicate = LinearPredicate {
+            terms: terms.iter()
+                .map(|&(sub_proof_idx, attr_name, coefficient)| LinearTerm {
+                    sub_proof_idx,
+                    attr_name: self.attr_canonicalization.canonicalize(attr_name),
+                    coefficient
+                })
+                .collect(),
+            p_type,
+            value
+        };
 
-        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
-            Prover::blind_master_secret(&cred_pub_key,
-                                        &cred_key_correctness_proof,
-                                        &master_secret,
-                                        &master_secret_blinding_nonce).unwrap();
+        self.value.linear_predicates.push(linear_predicate);
+        Ok(())
+    }
 
-        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
-        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
-        credential_values_builder.add_value("age", "28").unwrap();
-        credential_values_builder.add_value("height", "175").unwrap();
-        let cred_values = credential_values_builder.finalize().unwrap();
+    /// Requests non-revocation as of revocation registry state from the interval.
+    /// Prover must build non-revocation proof against registry state with timestamp from the interval.
+    ///
+    /// # Arguments
+    /// * `from` - Interval start timestamp (seconds since epoch).
+    /// * `to` - Interval end timestamp (seconds since epoch).
+    pub fn set_non_revoked_interval(&mut self, from: u64, to: u64) -> Result<(), IndyCryptoError> {
+        if from > to {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid non-revoked interval: from {} is after to {}", from, to)));
+        }
 
-        let cred_issuance_nonce = new_nonce().unwrap();
+        self.value.non_revoked = Some(NonRevokedInterval { from, to });
+        Ok(())
+    }
 
-        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
-                                                                                        &blinded_master_secret,
-                                                                                        &blinded_master_secret_correctness_proof,
-                                                                                        &master_secret_blinding_nonce,
-                                                                                        &cred_issuance_nonce,
+    pub fn finalize(self) -> Result<SubProofRequest, IndyCryptoError> {
+        self.value._check_attr_value_checks()?;
+        Ok(self.value)
+    }
+}
+
+/// Some condition that must be satisfied.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Predicate {
+    attr_name: String,
+    p_type: PredicateType,
+    value: i32,
+    #[serde(default, skip_serializing_if = "PredicateProofVersion::is_default")]
+    proof_version: PredicateProofVersion
+}
+
+impl Predicate {
+    pub fn attr_name(&self) -> &str {
+        &self.attr_name
+    }
+
+    pub fn p_type(&self) -> &PredicateType {
+        &self.p_type
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn proof_version(&self) -> PredicateProofVersion {
+        self.proof_version
+    }
+}
+
+/// Condition over linear combination of attributes of one or more credentials.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct LinearPredicate {
+    terms: Vec<LinearTerm>,
+    p_type: PredicateType,
+    value: i32,
+}
+
+impl LinearPredicate {
+    // Represents linear predicate as predicate over synthetic attribute named by combination expression,
+    // so GE proof machinery can be reused.
+    fn as_predicate(&self) -> Predicate {
+        let attr_name = self.terms.iter()
+            .map(|term| format!("{}*{}:{}", term.coefficient, term.sub_proof_idx, term.attr_name))
+            .collect::<Vec<String>>()
+            .join("+");
+
+        Predicate {
+            attr_name,
+            p_type: self.p_type.clone(),
+            value: self.value,
+            proof_version: PredicateProofVersion::FourSquares
+        }
+    }
+
+    // Calculates sum of coefficient * f(term) over all terms of combination.
+    fn combine<F>(&self, mut f: F) -> Result<BigNumber, IndyCryptoError>
+        where F: FnMut(&LinearTerm) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let mut sum = BigNumber::new()?;
+
+        for term in self.terms.iter() {
+            let coefficient = BigNumber::from_dec(&term.coefficient.to_string())?;
+            sum = sum.add(&f(term)?.mul(&coefficient, Some(&mut ctx))?)?;
+        }
+
+        Ok(sum)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct LinearTerm {
+    sub_proof_idx: usize,
+    attr_name: String,
+    coefficient: i32
+}
+
+/// Condition type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PredicateType {
+    GE,
+    GT,
+    LE,
+    LT
+}
+
+impl PredicateType {
+    fn from_name(p_type: &str) -> Result<PredicateType, IndyCryptoError> {
+        match p_type {
+            "GE" => Ok(PredicateType::GE),
+            "GT" => Ok(PredicateType::GT),
+            "LE" => Ok(PredicateType::LE),
+            "LT" => Ok(PredicateType::LT),
+            p_type => Err(IndyCryptoError::InvalidStructure(format!("Invalid predicate type: {:?}", p_type)))
+        }
+    }
+
+    /// Whether predicate bounds attribute from above, so it's proven as GE predicate over negated attribute.
+    pub fn is_less(&self) -> bool {
+        match *self {
+            PredicateType::LE | PredicateType::LT => true,
+            PredicateType::GE | PredicateType::GT => false
+        }
+    }
+}
+
+/// Proof system used to prove predicate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PredicateProofVersion {
+    /// Decomposition of difference into four squares committed in the group of credential key.
+    FourSquares,
+    /// Range proof of logarithmic size over commitment in pairing group linked to the attribute,
+    /// proves difference fits `range_proof::RANGE_BITS` bits.
+    RangeProof
+}
+
+impl PredicateProofVersion {
+    fn is_default(&self) -> bool {
+        *self == PredicateProofVersion::default()
+    }
+}
+
+impl Default for PredicateProofVersion {
+    fn default() -> PredicateProofVersion {
+        PredicateProofVersion::FourSquares
+    }
+}
+
+/// Proof is complex crypto structure created by prover over multiple credentials that allows to prove that prover:
+/// 1) Knows signature over credentials issued with specific issuer keys (identified by key id)
+/// 2) Claim contains attributes with specific values that prover wants to disclose
+/// 3) Claim contains attributes with valid predicates that verifier wants the prover to satisfy.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Proof {
+    proofs: Vec<SubProof>,
+    aggregated_proof: AggregatedProof,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    non_ownership_proofs: Vec<NonOwnershipProof>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    verifiable_encryptions: Vec<VerifiableEncryptionProof>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pseudonyms: Vec<Pseudonym>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    nonces: Vec<Nonce>,
+    #[cfg(feature = "sign_sodium")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    device_binding: Option<DeviceBinding>
+}
+
+impl Proof {
+    /// Returns fingerprint of the proof: hex encoded SHA-256 digest of its canonical JSON (see `JsonEncodable::to_canonical_json`).
+    pub fn fingerprint(&self) -> Result<String, IndyCryptoError> {
+        trace!("Proof::fingerprint: >>>");
+
+        let fingerprint = hex_digest(&vec![self.to_canonical_json()?.into_bytes()])?;
+
+        trace!("Proof::fingerprint: <<< fingerprint: {:?}", fingerprint);
+
+        Ok(fingerprint)
+    }
+
+    /// Returns sub proofs in the order of sub proof requests.
+    /// Proof doesn't contain identifiers of credential keys: sub proof is matched with key by its position.
+    pub fn sub_proofs(&self) -> &[SubProof] {
+        &self.proofs
+    }
+
+    /// Returns verifiable encryptions of attributes in the order of verifiable encryption requests.
+    pub fn verifiable_encryptions(&self) -> &[VerifiableEncryptionProof] {
+        &self.verifiable_encryptions
+    }
+
+    /// Returns pseudonyms of prover in the order of pseudonym requests.
+    pub fn pseudonyms(&self) -> &[Pseudonym] {
+        &self.pseudonyms
+    }
+
+    /// Returns nonces of all verifiers the proof is bound to.
+    /// Empty for proofs created by `ProofBuilder::finalize` that are bound to the single nonce which isn't stored in proof.
+    pub fn nonces(&self) -> &[Nonce] {
+        &self.nonces
+    }
+
+    /// Returns Fiat-Shamir challenge the proof responses are computed for.
+    pub fn challenge(&self) -> &BigNumber {
+        &self.aggregated_proof.c_hash
+    }
+
+    /// Returns binding of the proof to holder device key, if it was added by Prover.
+    #[cfg(feature = "sign_sodium")]
+    pub fn device_binding(&self) -> Option<&DeviceBinding> {
+        self.device_binding.as_ref()
+    }
+
+    /// Returns message device key must sign to bind the proof to device: Fiat-Shamir challenge of the proof
+    /// prefixed with domain separator. Challenge depends on nonce, so signature can't be replayed with other proofs.
+    #[cfg(feature = "sign_sodium")]
+    pub fn device_binding_message(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut message = DEVICE_BINDING_DOMAIN.to_vec();
+        message.extend_from_slice(&self.aggregated_proof.c_hash.to_bytes()?);
+        Ok(message)
+    }
+
+    /// Binds finalized proof to holder device key ("credential + device" presentation).
+    /// Device signs `Proof::device_binding_message` with key which verification key is committed
+    /// in credential attribute (see `DeviceBinding::encode_ver_key`).
+    ///
+    /// # Arguments
+    /// * `ver_key` - Device verification key.
+    /// * `signature` - Signature of device binding message by device key.
+    #[cfg(feature = "sign_sodium")]
+    pub fn add_device_binding(&mut self, ver_key: &ed25519::VerKey, signature: &ed25519::Signature) -> Result<(), IndyCryptoError> {
+        trace!("Proof::add_device_binding: >>> ver_key: {:?}, signature: {:?}", ver_key, signature);
+
+        if !ed25519::verify(signature, &self.device_binding_message()?, ver_key)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Signature of device binding message is invalid")));
+        }
+
+        self.device_binding = Some(DeviceBinding {
+            ver_key: ver_key.clone(),
+            signature: signature.clone()
+        });
+
+        trace!("Proof::add_device_binding: <<<");
+
+        Ok(())
+    }
+
+    /// Encrypts proof for the verifier, so transport intermediaries can't read revealed attributes.
+    /// Envelope uses ephemeral sender key and doesn't identify prover.
+    ///
+    /// # Arguments
+    /// * `verifier_pk` - Verifier encryption public key.
+    #[cfg(feature = "box_sodium")]
+    pub fn seal_for(&self, verifier_pk: &PublicKey) -> Result<SealedProof, IndyCryptoError> {
+        trace!("Proof::seal_for: >>> verifier_pk: {:?}", verifier_pk);
+
+        let proof_json = self.to_json()?;
+        let sealed_proof = SealedProof {
+            ciphertext: SealedBox::seal(proof_json.as_bytes(), verifier_pk)?
+        };
+
+        trace!("Proof::seal_for: <<< sealed_proof: {:?}", sealed_proof);
+
+        Ok(sealed_proof)
+    }
+
+    /// Restores value of revealed chunked attribute from its signed chunks.
+    /// Must be called only for proofs accepted by `ProofVerifier::verify`.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests).
+    /// * `attr` - Chunked attribute name.
+    /// * `max_bits` - Max bits of attribute value used by Issuer.
+    pub fn get_revealed_chunked_attr(&self, sub_proof_idx: usize, attr: &str, max_bits: usize) -> Result<BigNumber, IndyCryptoError> {
+        trace!("Proof::get_revealed_chunked_attr: >>> sub_proof_idx: {:?}, attr: {:?}, max_bits: {:?}", sub_proof_idx, attr, max_bits);
+
+        let revealed_attrs = &self.proofs.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof by index '{}' not found", sub_proof_idx)))?
+            .primary_proof.eq_proof.revealed_attrs;
+
+        let mut chunks: Vec<&BigNumber> = Vec::new();
+        for idx in 0..helpers::attr_chunks_count(max_bits)? {
+            let chunk_name = helpers::attr_chunk_name(attr, idx);
+            chunks.push(revealed_attrs.get(&chunk_name)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: chunk_name.to_string(), context: "revealed attributes".to_string() })?);
+        }
+
+        let value = helpers::join_attr_chunks(&chunks)?;
+
+        trace!("Proof::get_revealed_chunked_attr: <<< value: {:?}", value);
+
+        Ok(value)
+    }
+}
+
+impl JsonEncodable for Proof {}
+
+impl<'a> JsonDecodable<'a> for Proof {}
+
+#[cfg(feature = "sign_sodium")]
+const DEVICE_BINDING_DOMAIN: &'static [u8] = b"indy-crypto:cl:device-binding:";
+
+/// Signature of proof challenge by holder device key together with device verification key.
+/// Verifier accepts binding only if verification key matches value of revealed credential attribute.
+#[cfg(feature = "sign_sodium")]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct DeviceBinding {
+    ver_key: ed25519::VerKey,
+    signature: ed25519::Signature
+}
+
+#[cfg(feature = "sign_sodium")]
+impl DeviceBinding {
+    pub fn ver_key(&self) -> &ed25519::VerKey {
+        &self.ver_key
+    }
+
+    pub fn signature(&self) -> &ed25519::Signature {
+        &self.signature
+    }
+
+    /// Encodes device verification key as credential attribute value: SHA-256 digest of key bytes as decimal.
+    /// Issuer puts the value into credential, so the key is committed in credential signature.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Device verification key.
+    pub fn encode_ver_key(ver_key: &ed25519::VerKey) -> Result<String, IndyCryptoError> {
+        BigNumber::from_bytes(&BigNumber::hash(ver_key.as_bytes())?)?.to_dec()
+    }
+}
+
+/// Check performed by `ProofVerifier` which result is reported with `ProofEvent`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum ProofCheck {
+    /// Proof is bound to verifier nonce.
+    Nonce,
+    /// Credentials of same holder groups share master secret.
+    SameHolder,
+    /// Proof is signed by device key committed in credential.
+    DeviceBinding,
+    /// Pseudonyms are derived from master secret.
+    Pseudonyms,
+    /// Recomputed challenge equals challenge of proof.
+    Challenge,
+    /// Final decision on proof.
+    Proof
+}
+
+/// Structured event of proof building or verification reported to `ProofObserver`.
+/// Events contain only public data (indices, counts and challenge), so they can be logged and replayed
+/// by security teams without parsing trace logs.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub enum ProofEvent {
+    /// Processing of sub proof is started.
+    SubProofStarted { sub_proof_idx: usize },
+    /// T-values (commitments) of sub proof are computed.
+    TValuesComputed { sub_proof_idx: usize, count: usize },
+    /// Fiat-Shamir challenge (hex) is computed (by Prover) or recomputed (by Verifier).
+    ChallengeComputed { challenge: String },
+    CheckPassed { check: ProofCheck },
+    CheckFailed { check: ProofCheck, reason: String }
+}
+
+/// Receiver of `ProofEvent`s of `ProofBuilder` or `ProofVerifier` (see `set_observer`).
+/// Called synchronously, so implementation shouldn't block.
+pub trait ProofObserver: Send + Sync {
+    fn on_event(&self, event: &ProofEvent);
+}
+
+/// Optional observer of builder or verifier. Isn't part of serialized builder state.
+#[derive(Clone, Default)]
+pub struct ProofObserverSlot(Option<Arc<ProofObserver>>);
+
+impl ProofObserverSlot {
+    fn set(&mut self, observer: Arc<ProofObserver>) {
+        self.0 = Some(observer);
+    }
+
+    fn notify(&self, event: ProofEvent) {
+        if let Some(ref observer) = self.0 {
+            observer.on_event(&event);
+        }
+    }
+}
+
+impl fmt::Debug for ProofObserverSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ProofObserverSlot {{ set: {} }}", self.0.is_some())
+    }
+}
+
+/// Disclosure of proof that is being built: everything Verifier will learn about credentials from finalized proof.
+/// Allows holder to review the disclosure before `ProofBuilder::finalize` is called.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProofPreview {
+    sub_proofs: Vec<SubProofPreview>
+}
+
+impl ProofPreview {
+    /// Returns disclosures of sub proofs in the order of sub proof requests.
+    pub fn sub_proofs(&self) -> &[SubProofPreview] {
+        &self.sub_proofs
+    }
+}
+
+impl JsonEncodable for ProofPreview {}
+
+impl<'a> JsonDecodable<'a> for ProofPreview {}
+
+/// Disclosure of a single sub proof.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubProofPreview {
+    revealed_attrs: BTreeMap<String /* attr_name */, BigNumber>,
+    predicates: Vec<Predicate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    linear_predicates: Vec<LinearPredicate>,
+    revocation_included: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>
+}
+
+impl SubProofPreview {
+    /// Returns revealed attributes with encoded values (attribute names are canonicalized by credential schema).
+    pub fn revealed_attrs(&self) -> &BTreeMap<String, BigNumber> {
+        &self.revealed_attrs
+    }
+
+    /// Returns predicates in the order they will be proved.
+    pub fn predicates(&self) -> &[Predicate] {
+        &self.predicates
+    }
+
+    pub fn linear_predicates(&self) -> &[LinearPredicate] {
+        &self.linear_predicates
+    }
+
+    /// Returns true if proof will state that credential isn't revoked.
+    pub fn is_revocation_included(&self) -> bool {
+        self.revocation_included
+    }
+
+    /// Returns timestamp of revocation registry state non-revocation will be proved against, if it was specified.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+}
+
+/// Proof encrypted for a particular verifier.
+#[cfg(feature = "box_sodium")]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SealedProof {
+    ciphertext: Vec<u8>
+}
+
+#[cfg(feature = "box_sodium")]
+impl JsonEncodable for SealedProof {}
+
+#[cfg(feature = "box_sodium")]
+impl<'a> JsonDecodable<'a> for SealedProof {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubProof {
+    primary_proof: PrimaryProof,
+    non_revoc_proof: Option<NonRevocProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_id: Option<String>
+}
+
+impl SubProof {
+    /// Returns revealed attributes with their encoded values.
+    pub fn revealed_attrs(&self) -> &HashMap<String, BigNumber> {
+        &self.primary_proof.eq_proof.revealed_attrs
+    }
+
+    /// Returns predicates over attributes of this credential.
+    /// Linear predicates spanning several credentials aren't included.
+    pub fn predicates(&self) -> Vec<&Predicate> {
+        self.primary_proof.ge_proofs.iter().map(|ge_proof| &ge_proof.predicate)
+            .chain(self.primary_proof.range_proofs.iter().map(|range_proof| &range_proof.predicate))
+            .collect()
+    }
+
+    /// Returns non-revocation proof if credential was proven to be not revoked.
+    pub fn non_revoc_proof(&self) -> Option<&NonRevocProof> {
+        self.non_revoc_proof.as_ref()
+    }
+
+    pub fn is_revocation_included(&self) -> bool {
+        self.non_revoc_proof.is_some()
+    }
+
+    /// Returns identifier of credential key sub proof was built with, if it was specified by Prover.
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_ref().map(String::as_str)
+    }
+}
+
+/// Encryption of hidden attribute under auditor key with proof that ciphertext contains signed attribute value.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct VerifiableEncryptionProof {
+    ciphertext: AttributeCiphertext,
+    r_hat: BigNumber
+}
+
+impl VerifiableEncryptionProof {
+    pub fn ciphertext(&self) -> &AttributeCiphertext {
+        &self.ciphertext
+    }
+}
+
+/// Proof that hidden attribute isn't accumulated in `NonOwnershipRegistry`.
+/// Non-membership witness (a, d): accum^a = d^x * g, where x is attribute value, isn't disclosed:
+/// `c_d` = d * h^r and `c_r` = g^r * h^s commit to it and responses prove
+/// g = accum^a * c_d^-x * h^(r * x), c_r = g^r * h^s and 1 = c_r^x * g^-(r * x) * h^-(s * x).
+/// Response for x is taken from equality proof of sub proof.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NonOwnershipProof {
+    c_d: BigNumber,
+    c_r: BigNumber,
+    a_hat: BigNumber,
+    r_hat: BigNumber,
+    s_hat: BigNumber,
+    rx_hat: BigNumber,
+    sx_hat: BigNumber
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AggregatedProof {
+    c_hash: BigNumber,
+    c_list: Vec<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "ChallengeHash::is_default")]
+    challenge_hash: ChallengeHash,
+    /// T-values of equality and GE proofs in the order challenge is computed over them,
+    /// present only in proofs built for batch verification (see `ProofBuilder::set_batch_verifiable`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    t_list: Vec<BigNumber>
+}
+
+/// Hash function used to compute Fiat-Shamir challenge of proof.
+/// All hashes give 256 bit challenges (SHA-384 output is truncated), so sizes of blinding factors stay the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ChallengeHash {
+    #[serde(rename = "SHA-256")]
+    Sha256,
+    #[serde(rename = "SHA-384")]
+    Sha384,
+    #[serde(rename = "SHA3-256")]
+    Sha3_256
+}
+
+impl ChallengeHash {
+    /// Returns challenge digest of concatenated values.
+    pub fn digest(&self, nums: &Vec<Vec<u8>>) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut hasher = self.hasher();
+        hasher.update_all(nums);
+        Ok(hasher.finalize_bytes())
+    }
+
+    /// Returns streaming hasher producing the same challenge as `digest`.
+    pub fn hasher(&self) -> ChallengeHasher {
+        match *self {
+            ChallengeHash::Sha256 => ChallengeHasher::sha256(),
+            ChallengeHash::Sha384 => ChallengeHasher::sha384_truncated(constants::CHALLENGE_BYTES),
+            ChallengeHash::Sha3_256 => ChallengeHasher::sha3_256()
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == ChallengeHash::default()
+    }
+}
+
+impl Default for ChallengeHash {
+    fn default() -> ChallengeHash {
+        ChallengeHash::Sha256
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimaryProof {
+    eq_proof: PrimaryEqualProof,
+    ge_proofs: Vec<PrimaryPredicateGEProof>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    linear_ge_proofs: Vec<PrimaryPredicateGEProof>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    range_proofs: Vec<PrimaryPredicateRangeProof>
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimaryEqualProof {
+    revealed_attrs: HashMap<String /* attr_name of revealed */, BigNumber>,
+    a_prime: BigNumber,
+    e: BigNumber,
+    v: BigNumber,
+    m: HashMap<String /* attr_name of all except revealed */, BigNumber>,
+    m1: BigNumber,
+    m2: BigNumber
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimaryPredicateGEProof {
+    u: HashMap<String, BigNumber>,
+    r: HashMap<String, BigNumber>,
+    mj: BigNumber,
+    alpha: BigNumber,
+    t: HashMap<String, BigNumber>,
+    predicate: Predicate
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct PrimaryPredicateRangeProof {
+    predicate: Predicate,
+    #[serde(with = "::pair::compressed")]
+    commitment: PointG1,
+    #[serde(with = "::pair::compressed")]
+    gamma: GroupOrderElement,
+    range_proof: RangeProof
+}
+
+impl Eq for PrimaryPredicateRangeProof {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NonRevocProof {
+    x_list: NonRevocProofXList,
+    c_list: NonRevocProofCList,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rev_reg_id: Option<String>
+}
+
+impl NonRevocProof {
+    /// Returns timestamp of revocation registry state proof was built against, if it was specified by Prover.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// Returns identifier of revocation registry proof was built against, if it was specified by Prover.
+    pub fn rev_reg_id(&self) -> Option<&str> {
+        self.rev_reg_id.as_ref().map(String::as_str)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VerifiableEncryptionInitProof {
+    ciphertext: AttributeCiphertext,
+    r: BigNumber,
+    r_tilde: BigNumber,
+    u_tilde: BigNumber,
+    e_tilde: BigNumber
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PseudonymInitProof {
+    pseudonym: Pseudonym,
+    t: BigNumber
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NonOwnershipInitProof {
+    c_d: BigNumber,
+    c_r: BigNumber,
+    a: BigNumber,
+    r: BigNumber,
+    s: BigNumber,
+    rx: BigNumber,
+    sx: BigNumber,
+    a_tilde: BigNumber,
+    r_tilde: BigNumber,
+    s_tilde: BigNumber,
+    rx_tilde: BigNumber,
+    sx_tilde: BigNumber,
+    t_list: Vec<BigNumber>
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InitProof {
+    credential_pub_key: CredentialPrimaryPublicKey,
+    primary_init_proof: PrimaryInitProof,
+    non_revoc_init_proof: Option<NonRevocInitProof>,
+    credential_values: CredentialValues,
+    sub_proof_request: SubProofRequest,
+    credential_schema: CredentialSchema,
+    #[serde(default)]
+    key_id: Option<String>,
+    #[serde(default)]
+    master_secret_idx: usize
+}
+
+impl InitProof {
+    /// Returns sub proof request this init proof was built for.
+    pub fn sub_proof_request(&self) -> &SubProofRequest {
+        &self.sub_proof_request
+    }
+
+    pub fn is_revocation_included(&self) -> bool {
+        self.non_revoc_init_proof.is_some()
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PrimaryInitProof {
+    eq_proof: PrimaryEqualInitProof,
+    ge_proofs: Vec<PrimaryPredicateGEInitProof>,
+    #[serde(default)]
+    range_proofs: Vec<PrimaryPredicateRangeInitProof>
+}
+
+impl PrimaryInitProof {
+    pub fn as_c_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let mut c_list: Vec<Vec<u8>> = self.eq_proof.as_list()?;
+        for ge_proof in self.ge_proofs.iter() {
+            c_list.append_vec(ge_proof.as_list()?)?;
+        }
+        Ok(c_list)
+    }
+
+    pub fn as_tau_list(&self, n: &BigNumber) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let mut tau_list: Vec<Vec<u8>> = self.eq_proof.as_tau_list(n)?;
+        for ge_proof in self.ge_proofs.iter() {
+            tau_list.append_vec(&ge_proof.as_tau_list(n)?)?;
+        }
+        // Commitment is hashed with its link, so it can't be chosen after the challenge
+        for range_proof in self.range_proofs.iter() {
+            tau_list.push(range_proof.commitment.to_bytes()?);
+            tau_list.push(range_proof.t.to_bytes()?);
+        }
+        Ok(tau_list)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NonRevocInitProof {
+    c_list_params: NonRevocProofXList,
+    tau_list_params: NonRevocProofXList,
+    c_list: NonRevocProofCList,
+    tau_list: NonRevocProofTauList,
+    timestamp: Option<u64>,
+    rev_reg_id: Option<String>
+}
+
+impl NonRevocInitProof {
+    pub fn as_c_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let vec = self.c_list.as_list()?;
+        Ok(vec)
+    }
+
+    pub fn as_tau_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let vec = self.tau_list.as_slice()?;
+        Ok(vec)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PrimaryEqualInitProof {
+    a_prime: BigNumber,
+    t: BigNumber,
+    e_tilde: BigNumber,
+    e_prime: BigNumber,
+    v_tilde: BigNumber,
+    v_prime: BigNumber,
+    m_tilde: HashMap<String, BigNumber>,
+    m1_tilde: BigNumber,
+    m2_tilde: BigNumber,
+    m2: BigNumber
+}
+
+impl PrimaryEqualInitProof {
+    pub fn as_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        Ok(vec![self.a_prime.to_bytes()?])
+    }
+
+    pub fn as_tau_list(&self, n: &BigNumber) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        Ok(vec![helpers::abs_mod(&self.t, n)?.to_bytes()?])
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PrimaryPredicateGEInitProof {
+    c_list: Vec<BigNumber>,
+    tau_list: Vec<BigNumber>,
+    u: HashMap<String, BigNumber>,
+    u_tilde: HashMap<String, BigNumber>,
+    r: HashMap<String, BigNumber>,
+    r_tilde: HashMap<String, BigNumber>,
+    alpha_tilde: BigNumber,
+    predicate: Predicate,
+    t: HashMap<String, BigNumber>
+}
+
+impl PrimaryPredicateGEInitProof {
+    pub fn as_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
+        Ok(&self.c_list)
+    }
+
+    pub fn as_tau_list(&self, n: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        self.tau_list.iter().map(|tau| helpers::abs_mod(tau, n)).collect()
+    }
+}
+
+// Commitment `g^delta * h^gamma` to difference between attribute and predicate value
+// with its range proof. Commitment is linked to the attribute by `t = g^m_tilde * h^gamma_tilde`,
+// where `m_tilde` is blinding of the attribute in equality proof.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct PrimaryPredicateRangeInitProof {
+    predicate: Predicate,
+    commitment: PointG1,
+    gamma: GroupOrderElement,
+    gamma_tilde: GroupOrderElement,
+    t: PointG1,
+    range_proof: RangeProof
+}
+
+impl Eq for PrimaryPredicateRangeInitProof {}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonRevocProofXList {
+    #[serde(with = "::pair::compressed")]
+    rho: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    r: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    r_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    r_prime_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    r_prime_prime_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    o: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    o_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    m: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    m_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    t: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    t_prime: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    m2: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    s: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    c: GroupOrderElement
+}
+
+impl NonRevocProofXList {
+    pub fn as_list(&self) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
+        Ok(vec![self.rho, self.o, self.c, self.o_prime, self.m, self.m_prime, self.t, self.t_prime,
+                self.m2, self.s, self.r, self.r_prime, self.r_prime_prime, self.r_prime_prime_prime])
+    }
+
+    pub fn from_list(seq: Vec<GroupOrderElement>) -> NonRevocProofXList {
+        NonRevocProofXList {
+            rho: seq[0],
+            r: seq[10],
+            r_prime: seq[11],
+            r_prime_prime: seq[12],
+            r_prime_prime_prime: seq[13],
+            o: seq[1],
+            o_prime: seq[3],
+            m: seq[4],
+            m_prime: seq[5],
+            t: seq[6],
+            t_prime: seq[7],
+            m2: seq[8],
+            s: seq[9],
+            c: seq[2]
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonRevocProofCList {
+    #[serde(with = "::pair::compressed")]
+    e: PointG1,
+    #[serde(with = "::pair::compressed")]
+    d: PointG1,
+    #[serde(with = "::pair::compressed")]
+    a: PointG1,
+    #[serde(with = "::pair::compressed")]
+    g: PointG1,
+    #[serde(with = "::pair::compressed")]
+    w: PointG2,
+    #[serde(with = "::pair::compressed")]
+    s: PointG2,
+    #[serde(with = "::pair::compressed")]
+    u: PointG2
+}
+
+impl NonRevocProofCList {
+    pub fn as_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        Ok(vec![self.e.to_bytes()?, self.d.to_bytes()?, self.a.to_bytes()?, self.g.to_bytes()?,
+                self.w.to_bytes()?, self.s.to_bytes()?, self.u.to_bytes()?])
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonRevocProofTauList {
+    t1: PointG1,
+    t2: PointG1,
+    t3: Pair,
+    t4: Pair,
+    t5: PointG1,
+    t6: PointG1,
+    t7: Pair,
+    t8: Pair
+}
+
+impl NonRevocProofTauList {
+    pub fn as_slice(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        Ok(vec![self.t1.to_bytes()?, self.t2.to_bytes()?, self.t3.to_bytes()?, self.t4.to_bytes()?,
+                self.t5.to_bytes()?, self.t6.to_bytes()?, self.t7.to_bytes()?, self.t8.to_bytes()?])
+    }
+}
+
+/// Random BigNumber that uses `Prover` for proof generation and `Verifier` for proof verification.
+pub type Nonce = BigNumber;
+
+impl JsonEncodable for Nonce {}
+
+impl<'a> JsonDecodable<'a> for Nonce {}
+
+#[derive(Debug)]
+pub struct NonOwnershipRequest {
+    sub_proof_idx: usize,
+    attr: String,
+    registry: NonOwnershipRegistry
+}
+
+#[derive(Debug)]
+pub struct VerifiableEncryptionRequest {
+    sub_proof_idx: usize,
+    attr: String,
+    auditor_pub_key: AuditorPublicKey
+}
+
+#[derive(Debug)]
+pub struct VerifiableCredential {
+    pub_key: CredentialPublicKey,
+    precomputed_key: Option<Arc<CredentialPublicKeyPrecomputed>>,
+    candidate_pub_keys: BTreeMap<String /* key_id */, CredentialPublicKey>,
+    // If false, sub proof without key id is verified with `pub_key`
+    key_id_required: bool,
+    sub_proof_request: SubProofRequest,
+    credential_schema: CredentialSchema,
+    rev_key_pub: Option<RevocationKeyPublic>,
+    rev_reg: Option<RevocationRegistry>,
+    rev_reg_states: BTreeMap<u64 /* timestamp */, RevocationRegistry>,
+    rev_regs: BTreeMap<String /* rev_reg_id */, (RevocationKeyPublic, RevocationRegistry)>
+}
+
+trait BytesView {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError>;
+}
+
+impl BytesView for BigNumber {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(self.to_bytes()?)
+    }
+}
+
+impl BytesView for PointG1 {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(self.to_bytes()?)
+    }
+}
+
+impl BytesView for GroupOrderElement {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(self.to_bytes()?)
+    }
+}
+
+impl BytesView for Pair {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(self.to_bytes()?)
+    }
+}
+
+trait AppendByteArray {
+    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError>;
+    fn append_bytes(&mut self, bytes: &[u8]);
+}
+
+impl AppendByteArray for Vec<Vec<u8>> {
+    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError> {
+        for el in other.iter() {
+            self.push(el.to_bytes()?);
+        }
+        Ok(())
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.push(bytes.to_vec());
+    }
+}
+
+impl AppendByteArray for ChallengeHasher {
+    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError> {
+        for el in other.iter() {
+            self.update(&el.to_bytes()?);
+        }
+        Ok(())
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+fn append_with_len(values: &mut Vec<Vec<u8>>, bytes: &[u8]) {
+    values.push(helpers::transform_u32_to_array_of_u8(bytes.len() as u32));
+    values.push(bytes.to_vec());
+}
+
+// Hex encoded SHA-256 digest of concatenated values.
+fn hex_digest(values: &Vec<Vec<u8>>) -> Result<String, IndyCryptoError> {
+    let digest = BigNumber::hash_array(values)?;
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn clone_bignum_map<K: Clone + Eq + Hash>(other: &HashMap<K, BigNumber>)
+                                          -> Result<HashMap<K, BigNumber>, IndyCryptoError> {
+    let mut res: HashMap<K, BigNumber> = HashMap::new();
+    for (k, v) in other {
+        res.insert(k.clone(), v.clone()?);
+    }
+    Ok(res)
+}
+
+fn clone_bignum_vec(other: &Vec<BigNumber>) -> Result<Vec<BigNumber>, IndyCryptoError> {
+    let mut res: Vec<BigNumber> = Vec::new();
+    for v in other {
+        res.push(v.clone()?);
+    }
+    Ok(res)
+}
+
+fn clone_option_bignum(other: &Option<BigNumber>) -> Result<Option<BigNumber>, IndyCryptoError> {
+    match *other {
+        Some(ref v) => Ok(Some(v.clone()?)),
+        None => Ok(None)
+    }
+}
+
+fn clone_btree_bignum_map<K: Clone + Eq + Hash + Ord>(other: &BTreeMap<K, BigNumber>)
+                                                      -> Result<BTreeMap<K, BigNumber>, IndyCryptoError> {
+    let mut res: BTreeMap<K, BigNumber> = BTreeMap::new();
+    for (k, v) in other {
+        res.insert(k.clone(), v.clone()?);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json;
+    use self::issuer::Issuer;
+    use self::prover::Prover;
+    use self::verifier::Verifier;
+
+    #[test]
+    fn new_nonce_with_size_works() {
+        let nonce = new_nonce_with_size(128).unwrap();
+        assert_eq!(128, nonce.num_bits().unwrap());
+        assert_ne!(nonce, new_nonce_with_size(128).unwrap());
+
+        assert!(new_nonce_with_size(64).is_err());
+        assert!(new_nonce_with_size(2048).is_err());
+    }
+
+    #[test]
+    fn new_nonce_with_rng_works() {
+        use utils::drbg::HashDrbg;
+
+        let seed = [3; 32];
+        let nonce1 = new_nonce_with_rng(160, Box::new(HashDrbg::new(&seed).unwrap())).unwrap();
+        let nonce2 = new_nonce_with_rng(160, Box::new(HashDrbg::new(&seed).unwrap())).unwrap();
+
+        assert_eq!(160, nonce1.num_bits().unwrap());
+        assert_eq!(nonce1, nonce2);
+    }
+
+    #[test]
+    fn new_nonce_from_entropy_works() {
+        let nonce = new_nonce_from_entropy(&[0; 12]).unwrap();
+        assert_eq!(96, nonce.num_bits().unwrap());
+        assert_eq!(BigNumber::from_dec("39614081257132168796771975168").unwrap(), nonce);
+
+        assert!(new_nonce_from_entropy(&[1; 8]).is_err());
+    }
+
+    #[test]
+    fn sign_primary_works_for_crt() {
+        let p_safe = BigNumber::generate_safe_prime(256).unwrap();
+        let q_safe = BigNumber::generate_safe_prime(256).unwrap();
+        let n = p_safe.mul(&q_safe, None).unwrap();
+
+        let mut p = p_safe.sub(&BigNumber::from_u32(1).unwrap()).unwrap();
+        p.div_word(2).unwrap();
+        let mut q = q_safe.sub(&BigNumber::from_u32(1).unwrap()).unwrap();
+        q.div_word(2).unwrap();
+
+        let order = p.mul(&q, None).unwrap();
+        let priv_key = CredentialPrimaryPrivateKey { p, q };
+
+        let value = helpers::random_qr(&n).unwrap();
+        let e = BigNumber::generate_prime(128).unwrap();
+
+        let a = priv_key.sign_primary(&value, &e, &n).unwrap();
+
+        assert_eq!(value.mod_exp(&e.inverse(&order, None).unwrap(), &n, None).unwrap(), a);
+        assert_eq!(value, a.mod_exp(&e, &n, None).unwrap());
+    }
+
+    #[test]
+    fn security_profile_works_for_default_profile() {
+        let profile = SecurityProfile::default();
+        assert_eq!(constants::LARGE_PRIME, profile.prime_bits());
+        assert_eq!(constants::LARGE_VPRIME, profile.vprime_bits());
+        assert_eq!(constants::LARGE_VPRIME_PRIME, profile.vprime_prime_bits());
+        assert_eq!(constants::LARGE_VTILDE, profile.vtilde_bits());
+        assert_eq!(constants::LARGE_ALPHATILDE, profile.alphatilde_bits());
+    }
+
+    #[test]
+    fn security_profile_check_modulus_works() {
+        let two = BigNumber::from_u32(2).unwrap();
+        let n_3072 = two.exp(&BigNumber::from_u32(3071).unwrap(), None).unwrap();
+        let n_2048 = two.exp(&BigNumber::from_u32(2047).unwrap(), None).unwrap();
+
+        SecurityProfile::modulus_3072().check_modulus(&n_3072).unwrap();
+        assert!(SecurityProfile::modulus_3072().check_modulus(&n_2048).is_err());
+        assert!(SecurityProfile::modulus_4096().check_modulus(&n_3072).is_err());
+
+        let unsupported = SecurityProfile { modulus_bits: 3072, challenge_bits: 256, statistical_bits: 40 };
+        assert!(unsupported.check_modulus(&n_3072).is_err());
+    }
+
+    #[test]
+    fn credential_primary_public_key_deserialization_works_for_key_without_profile() {
+        let mut pk = issuer::mocks::credential_primary_public_key();
+        let json = serde_json::to_string(&pk).unwrap();
+        assert!(!json.contains("profile"));
+
+        let pk_deserialized: CredentialPrimaryPublicKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(SecurityProfile::default(), pk_deserialized.security_profile().unwrap());
+
+        pk.profile = Some(SecurityProfile::modulus_4096());
+        let pk_deserialized: CredentialPrimaryPublicKey = serde_json::from_str(&serde_json::to_string(&pk).unwrap()).unwrap();
+        assert_eq!(Some(SecurityProfile::modulus_4096()), pk_deserialized.profile);
+        assert!(pk_deserialized.security_profile().is_err());
+    }
+
+    #[test]
+    fn credential_primary_public_key_serialization_works_for_key_without_master_secret() {
+        let mut pk = issuer::mocks::credential_primary_public_key();
+        let json = serde_json::to_string(&pk).unwrap();
+        assert!(!json.contains("without_master_secret"));
+
+        pk.without_master_secret = true;
+        let pk_deserialized: CredentialPrimaryPublicKey = serde_json::from_str(&serde_json::to_string(&pk).unwrap()).unwrap();
+        assert!(pk_deserialized.without_master_secret);
+
+        let cred_pub_key = CredentialPublicKey { p_key: pk, r_key: None };
+        assert!(!cred_pub_key.requires_master_secret());
+    }
+
+    #[test]
+    fn credential_schema_builder_works_for_max_attrs_count() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        for i in 0..max_attrs_count() {
+            credential_schema_builder.add_attr(&format!("attr{}", i)).unwrap();
+        }
+        credential_schema_builder.finalize().unwrap();
+    }
+
+    #[test]
+    fn credential_schema_builder_works_for_too_many_attrs() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        for i in 0..max_attrs_count() + 1 {
+            credential_schema_builder.add_attr(&format!("attr{}", i)).unwrap();
+        }
+
+        match credential_schema_builder.finalize() {
+            Err(IndyCryptoError::AnoncredsTooManyAttributes(_)) => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn credential_schema_builder_works_for_attr_canonicalization() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).unwrap();
+        credential_schema_builder.add_attr("First Name").unwrap();
+        credential_schema_builder.add_attr("firstname").unwrap();
+        credential_schema_builder.add_chunked_attr("Photo", 512).unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let expected_attrs: HashSet<String> = vec!["firstname", "photo#0", "photo#1"].into_iter().map(String::from).collect();
+        assert_eq!(expected_attrs, credential_schema.attrs);
+    }
+
+    #[test]
+    fn credential_schema_builder_works_for_attr_canonicalization_set_after_attrs() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        assert!(credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).is_err());
+    }
+
+    #[test]
+    fn credential_schema_serialization_works_for_attr_canonicalization() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+        assert_eq!(r#"{"attrs":["name"]}"#, serde_json::to_string(&credential_schema).unwrap());
+
+        let credential_schema: CredentialSchema =
+            serde_json::from_str(r#"{"attrs":["name"],"attr_canonicalization":"LowercaseNoSpaces"}"#).unwrap();
+        assert_eq!(AttrNameCanonicalization::LowercaseNoSpaces, credential_schema.attr_canonicalization());
+    }
+
+    #[test]
+    fn credential_schema_canonicalize_credential_values_works_for_duplicated_attr() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut credential_values_builder = CredentialValuesBuilder::new().unwrap();
+        credential_values_builder.add_value("Name", "1").unwrap();
+        credential_values_builder.add_value("name", "2").unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        assert!(credential_schema.canonicalize_credential_values(&credential_values).is_err());
+    }
+
+    #[test]
+    fn credential_values_builder_add_str_value_works() {
+        let mut credential_values_builder = CredentialValuesBuilder::new().unwrap();
+        credential_values_builder.add_str_value("age", "28").unwrap();
+        credential_values_builder.add_str_value("name", "Alex").unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        assert_eq!(BigNumber::from_dec("28").unwrap(), credential_values.attrs_values["age"]);
+        assert_eq!(AttrValueEncoding::Sha256.encode("Alex").unwrap(), credential_values.attrs_values["name"]);
+    }
+
+    #[test]
+    fn set_max_attrs_count_works_for_zero() {
+        assert!(set_max_attrs_count(0).is_err());
+    }
+
+    #[test]
+    fn revocation_registry_serialization_works_for_compressed_accumulator() {
+        let rev_reg = issuer::mocks::revocation_registry();
+
+        let rev_reg_json = rev_reg.to_json().unwrap();
+        assert!(rev_reg_json.len() < format!(r#"{{"accum":"{}"}}"#, rev_reg.accum.to_string().unwrap()).len() / 2);
+
+        let deserialized = RevocationRegistry::from_json(&rev_reg_json).unwrap();
+        assert_eq!(rev_reg.accum.to_bytes().unwrap(), deserialized.accum.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn revocation_registry_deserialization_works_for_legacy_accumulator() {
+        let rev_reg = issuer::mocks::revocation_registry();
+
+        let legacy_json = format!(r#"{{"accum":"{}"}}"#, rev_reg.accum.to_string().unwrap());
+        let deserialized = RevocationRegistry::from_json(&legacy_json).unwrap();
+        assert_eq!(rev_reg.accum, deserialized.accum);
+    }
+
+    #[test]
+    fn credential_public_key_derive_id_works() {
+        let cred_pub_key = issuer::mocks::credential_public_key();
+
+        let id = cred_pub_key.derive_id("NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap();
+        assert!(id.starts_with("NcYxiDXkpYi6ov5FcYDi1e:tag:"));
+        assert_eq!(id, cred_pub_key.derive_id("NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap());
+
+        assert!(cred_pub_key.verify_id(&id, "NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap());
+        assert!(!cred_pub_key.verify_id(&id, "NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 4], "tag").unwrap());
+        assert!(!cred_pub_key.verify_id(&id, "NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "other").unwrap());
+    }
+
+    #[test]
+    fn credential_public_key_verify_id_works_for_other_key() {
+        let cred_pub_key = issuer::mocks::credential_public_key();
+        let id = cred_pub_key.derive_id("NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap();
+
+        let mut other_p_key = cred_pub_key.get_primary_key().unwrap();
+        other_p_key.z = other_p_key.z.add(&BigNumber::from_u32(1).unwrap()).unwrap();
+        let other_pub_key = CredentialPublicKey::build_from_parts(&other_p_key, None).unwrap();
+
+        assert!(!other_pub_key.verify_id(&id, "NcYxiDXkpYi6ov5FcYDi1e", &[1, 2, 3], "tag").unwrap());
+    }
+
+    #[test]
+    fn credential_public_key_canonical_bytes_works_for_optional_fields() {
+        let cred_pub_key = issuer::mocks::credential_public_key();
+        let mut p_key = cred_pub_key.get_primary_key().unwrap();
+        p_key.profile = None;
+        p_key.without_master_secret = false;
+
+        let mut with_profile = p_key.clone().unwrap();
+        with_profile.profile = Some(SecurityProfile::default());
+
+        let mut without_master_secret = p_key.clone().unwrap();
+        without_master_secret.without_master_secret = true;
+
+        let fingerprints = [&p_key, &with_profile, &without_master_secret].iter()
+            .map(|p_key| CredentialPublicKey::build_from_parts(p_key, None).unwrap().fingerprint().unwrap())
+            .collect::<HashSet<String>>();
+        assert_eq!(3, fingerprints.len());
+
+        let bytes = CredentialPublicKey::build_from_parts(&with_profile, None).unwrap()._canonical_bytes().unwrap();
+        let len = bytes.len();
+        assert_eq!(with_profile.z.to_bytes().unwrap(), bytes[len - 10]);
+        assert_eq!(vec![1], bytes[len - 9]);
+        assert_eq!(vec![0], bytes[len - 2]);
+        assert_eq!(vec![0], bytes[len - 1]);
+    }
+
+    #[test]
+    fn demo_chunked_attr() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_chunked_attr("document", 512).unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        let document = BigNumber::from_u32(2).unwrap()
+            .exp(&BigNumber::from_u32(500).unwrap(), None).unwrap()
+            .add(&BigNumber::from_u32(175).unwrap()).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_chunked_value("document", &document.to_dec().unwrap(), 512).unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                                        &blinded_master_secret,
+                                                                                        &blinded_master_secret_correctness_proof,
+                                                                                        &master_secret_blinding_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_chunked_attr("document", 512).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+        assert_eq!(document, proof.get_revealed_chunked_attr(0, "document", 512).unwrap());
+    }
+
+    #[test]
+    fn demo() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value("age", "28").unwrap();
+        credential_values_builder.add_value("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                                        &blinded_master_secret,
+                                                                                        &blinded_master_secret_correctness_proof,
+                                                                                        &master_secret_blinding_nonce,
+                                                                                        &cred_issuance_nonce,
                                                                                         &cred_values,
                                                                                         &cred_pub_key,
                                                                                         &cred_priv_key).unwrap();
@@ -1019,76 +4128,545 @@ mod test {
                                              &master_secret_blinding_data,
                                              &master_secret,
                                              &cred_pub_key,
-                                             &cred_issuance_nonce,
-                                             None,
-                                             None,
-                                             None).unwrap();
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_predicate_types() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("age", "28").unwrap();
+        credential_values_builder.add_value("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW", &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof, &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce, &cred_values, &cred_pub_key, &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature, &cred_values, &signature_correctness_proof,
+                                             &master_secret_blinding_data, &master_secret, &cred_pub_key,
+                                             &cred_issuance_nonce, None, None, None).unwrap();
+
+        // age > 27, age <= 28, height < 176 and height - age <= 147
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GT", 27).unwrap();
+        sub_proof_request_builder.add_predicate("age", "LE", 28).unwrap();
+        sub_proof_request_builder.add_predicate("height", "LT", 176).unwrap();
+        sub_proof_request_builder.add_predicate_with_proof_version("height", "GT", 174, PredicateProofVersion::RangeProof).unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "height", 1), (0, "age", -1)], "LE", 147).unwrap();
+        assert!(sub_proof_request_builder.add_predicate("age", "NE", 28).is_err());
+        assert!(sub_proof_request_builder.add_predicate_with_proof_version("age", "LE", 28, PredicateProofVersion::RangeProof).is_err());
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &cred_values, &cred_pub_key, None, None).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // Strict predicates aren't satisfied by equal value
+        for &(p_type, value) in [("GT", 28), ("LT", 28), ("LE", 27)].iter() {
+            let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_predicate("age", p_type, value).unwrap();
+            let unsatisfied_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            assert!(proof_builder.add_sub_proof_request(&unsatisfied_sub_proof_request, &credential_schema, &cred_signature,
+                                                        &cred_values, &cred_pub_key, None, None).is_err());
+        }
+
+        // Proof of LE predicate doesn't prove GE predicate with the same value
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "LE", 30).unwrap();
+        let le_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&le_sub_proof_request, &credential_schema, &cred_signature,
+                                            &cred_values, &cred_pub_key, None, None).unwrap();
+        let mut le_proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+        le_proof.proofs[0].primary_proof.ge_proofs[0].predicate.p_type = PredicateType::GE;
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 30).unwrap();
+        let ge_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&ge_sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(!proof_verifier.verify(&le_proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_revocation() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let issuance_by_default = false;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value("age", "28").unwrap();
+        credential_values_builder.add_value("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        let rev_idx = 1;
+        let (mut cred_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &cred_values,
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+
+        let witness = Witness::new(rev_idx, max_cred_num, &rev_reg_delta.unwrap(), &simple_tail_accessor).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &cred_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert_eq!(true, proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn witness_update_verified_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let rev_idx = 1;
+        let mut witness = Witness::new(rev_idx, max_cred_num, &issuer::mocks::revocation_registry_delta(), &simple_tail_accessor).unwrap();
+
+        let gen = Generator::new().unwrap();
+        let sign_key = ::bls::SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let mut rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &simple_tail_accessor).unwrap();
+        assert!(witness.update_verified(rev_idx, max_cred_num, &rev_reg_delta, "rev_reg_1", &ver_key, &gen, &simple_tail_accessor).is_err());
+
+        Issuer::sign_delta(&mut rev_reg_delta, "rev_reg_1", &sign_key).unwrap();
+        witness.update_verified(rev_idx, max_cred_num, &rev_reg_delta, "rev_reg_1", &ver_key, &gen, &simple_tail_accessor).unwrap();
+
+        let other_ver_key = VerKey::new(&gen, &::bls::SignKey::new(None).unwrap()).unwrap();
+        assert!(witness.update_verified(rev_idx, max_cred_num, &rev_reg_delta, "rev_reg_1", &other_ver_key, &gen, &simple_tail_accessor).is_err());
+        assert!(witness.update_verified(rev_idx, max_cred_num, &rev_reg_delta, "rev_reg_2", &ver_key, &gen, &simple_tail_accessor).is_err());
+    }
+
+    #[test]
+    fn lazy_tails_accessor_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (_, _, _, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, Some(3)).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        for tail_id in 0..2 * max_cred_num + 1 {
+            let mut expected = None;
+            simple_tail_accessor.access_tail(tail_id, &mut |tail| expected = Some(*tail)).unwrap();
+
+            let mut actual = None;
+            lazy_tails_accessor.access_tail(tail_id, &mut |tail| actual = Some(*tail)).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        assert!(lazy_tails_accessor.access_tail(2 * max_cred_num + 1, &mut |_| ()).is_err());
+        assert!(simple_tail_accessor.access_tail(2 * max_cred_num + 1, &mut |_| ()).is_err());
+    }
+
+    #[test]
+    fn caching_tails_accessor_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (_, _, _, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+
+        let size = rev_tails_generator.size();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let cache_size = 2 * 3 * ::std::mem::size_of::<Tail>();
+        let caching_tails_accessor = CachingTailsAccessor::new(simple_tail_accessor.clone(), size, Some(3), Some(cache_size)).unwrap();
+
+        for tail_id in (0..size).chain(0..size).rev() {
+            let mut expected = None;
+            simple_tail_accessor.access_tail(tail_id, &mut |tail| expected = Some(*tail)).unwrap();
+
+            let mut actual = None;
+            caching_tails_accessor.access_tail(tail_id, &mut |tail| actual = Some(*tail)).unwrap();
+
+            assert_eq!(expected, actual);
+            assert!(caching_tails_accessor.cached_count() <= 6);
+        }
+
+        assert!(caching_tails_accessor.access_tail(size, &mut |_| ()).is_err());
+    }
+
+    #[test]
+    fn caching_tails_accessor_new_works_for_too_small_cache() {
+        assert!(CachingTailsAccessor::new(Vec::<Tail>::new(), 11, Some(3), Some(::std::mem::size_of::<Tail>())).is_err());
+        assert!(CachingTailsAccessor::new(Vec::<Tail>::new(), 11, Some(0), None).is_err());
+    }
+
+    #[test]
+    fn revocation_tails_generator_write_tails_file_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (_, _, _, rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+
+        let mut file_tails_generator = rev_tails_generator.clone();
+        let hash = file_tails_generator.write_tails_file(Vec::new()).unwrap();
+
+        let mut bytes = Vec::new();
+        rev_tails_generator.clone().write_tails_file(&mut bytes).unwrap();
+
+        let tails_reader = TailsReader::new(::std::io::Cursor::new(bytes)).unwrap();
+        assert!(tails_reader.verify(&hash).unwrap());
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator.clone()).unwrap();
+
+        for tail_id in 0..2 * max_cred_num + 1 {
+            let mut expected = None;
+            simple_tail_accessor.access_tail(tail_id, &mut |tail| expected = Some(*tail)).unwrap();
+
+            let mut actual = None;
+            tails_reader.access_tail(tail_id, &mut |tail| actual = Some(*tail)).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        assert!(file_tails_generator.write_tails_file(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn witness_new_works_for_index_out_of_range() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (_, _, _, rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
+        let lazy_tails_accessor = LazyTailsAccessor::new(&rev_tails_generator, None).unwrap();
+
+        let rev_reg_delta = issuer::mocks::revocation_registry_delta();
+        assert!(Witness::new(0, max_cred_num, &rev_reg_delta, &lazy_tails_accessor).is_err());
+        assert!(Witness::new(max_cred_num + 1, max_cred_num, &rev_reg_delta, &lazy_tails_accessor).is_err());
+
+        let mut other_rev_reg_delta = rev_reg_delta.clone();
+        other_rev_reg_delta.issued.insert(max_cred_num + 1);
+        assert!(Witness::new(1, max_cred_num, &other_rev_reg_delta, &lazy_tails_accessor).is_err());
+    }
+
+    #[test]
+    fn demo_witness_updater() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let cred_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let issuance_by_default = true;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let rev_idx = 1;
+        let (mut cred_signature, signature_correctness_proof, _) =
+            Issuer::sign_credential_with_revoc(prover::mocks::PROVER_DID,
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &cred_values,
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+
+        let issued_by_default_delta = RevocationRegistryDelta {
+            prev_accum: None,
+            accum: rev_reg.accum,
+            issued: (1..max_cred_num + 1).collect(),
+            revoked: HashSet::new(),
+            signature: None,
+            mode: RevocationRegistryMode::Membership
+        };
+        let mut witness = Witness::new(rev_idx, max_cred_num, &issued_by_default_delta, &simple_tail_accessor).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        let rev_reg_id = "rev_reg_1";
+        let mut witness_updater = SimpleWitnessUpdater::new();
+        witness_updater.add_delta(rev_reg_id, 10, &Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &simple_tail_accessor).unwrap()).unwrap();
+        witness_updater.add_delta(rev_reg_id, 20, &Issuer::revoke_credential(&mut rev_reg, max_cred_num, 3, &simple_tail_accessor).unwrap()).unwrap();
+
+        assert!(Prover::update_non_revocation_witness(&mut witness, rev_idx, max_cred_num, rev_reg_id, 20, 30,
+                                                      &witness_updater, &simple_tail_accessor).unwrap().is_none());
+
+        let updated_rev_reg = Prover::update_non_revocation_witness(&mut witness, rev_idx, max_cred_num, rev_reg_id, 0, 20,
+                                                                    &witness_updater, &simple_tail_accessor).unwrap().unwrap();
+        assert_eq!(rev_reg.accum, updated_rev_reg.accum);
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            Some(&updated_rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &cred_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_timestamped_non_revocation() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let cred_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let issuance_by_default = false;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let rev_idx = 1;
+        let (mut cred_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc(prover::mocks::PROVER_DID,
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &cred_values,
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+
+        let witness = Witness::new(rev_idx, max_cred_num, &rev_reg_delta.unwrap(), &simple_tail_accessor).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        // Registry state at 100 is used by Prover, state at 200 is the current one
+        let mut rev_reg_states = BTreeMap::new();
+        rev_reg_states.insert(100, rev_reg.clone());
+        Issuer::revoke_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
+        rev_reg_states.insert(200, rev_reg.clone());
 
         let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
         sub_proof_request_builder.add_revealed_attr("name").unwrap();
-        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        sub_proof_request_builder.set_non_revoked_interval(50, 150).unwrap();
         let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
-        let mut proof_builder = Prover::new_proof_builder().unwrap();
-        proof_builder.add_sub_proof_request(&sub_proof_request,
-                                            &credential_schema,
-                                            &cred_signature,
-                                            &cred_values,
-                                            &cred_pub_key,
-                                            None,
-                                            None).unwrap();
 
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        assert!(proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature, &cred_values,
+                                                    &cred_pub_key, Some(&rev_reg_states[&100]), Some(&witness)).is_err());
+        assert!(proof_builder.add_sub_proof_request_with_timestamp(&sub_proof_request, &credential_schema, &cred_signature, &cred_values,
+                                                                   &cred_pub_key, &rev_reg_states[&200], &witness, 200).is_err());
+        proof_builder.add_sub_proof_request_with_timestamp(&sub_proof_request, &credential_schema, &cred_signature, &cred_values,
+                                                           &cred_pub_key, &rev_reg_states[&100], &witness, 100).unwrap();
         let proof_request_nonce = new_nonce().unwrap();
         let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+        assert_eq!(Some(100), proof.proofs[0].non_revoc_proof.as_ref().unwrap().timestamp());
 
         let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &cred_pub_key,
-                                             None,
-                                             None).unwrap();
+        proof_verifier.add_sub_proof_request_with_rev_reg_states(&sub_proof_request, &credential_schema, &cred_pub_key,
+                                                                 &rev_key_pub, &rev_reg_states).unwrap();
         assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+
+        rev_reg_states.remove(&100);
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_rev_reg_states(&sub_proof_request, &credential_schema, &cred_pub_key,
+                                                                 &rev_key_pub, &rev_reg_states).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).is_err());
     }
 
     #[test]
-    fn demo_revocation() {
-        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("name").unwrap();
-        credential_schema_builder.add_attr("sex").unwrap();
-        credential_schema_builder.add_attr("age").unwrap();
-        credential_schema_builder.add_attr("height").unwrap();
-        let credential_schema = credential_schema_builder.finalize().unwrap();
-
+    fn demo_sharded_revocation_registries() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let cred_values = issuer::mocks::credential_values();
         let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
 
         let max_cred_num = 5;
-        let issuance_by_default = false;
         let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
-            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
-
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+        let (other_rev_key_pub, _, other_rev_reg, _) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
         let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
 
         let master_secret = Prover::new_master_secret().unwrap();
-
         let master_secret_blinding_nonce = new_nonce().unwrap();
-
         let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
-            Prover::blind_master_secret(&cred_pub_key,
-                                        &cred_key_correctness_proof,
-                                        &master_secret,
-                                        &master_secret_blinding_nonce).unwrap();
-
-        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
-        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
-        credential_values_builder.add_value("age", "28").unwrap();
-        credential_values_builder.add_value("height", "175").unwrap();
-        let cred_values = credential_values_builder.finalize().unwrap();
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
 
         let credential_issuance_nonce = new_nonce().unwrap();
-
         let rev_idx = 1;
         let (mut cred_signature, signature_correctness_proof, rev_reg_delta) =
-            Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+            Issuer::sign_credential_with_revoc(prover::mocks::PROVER_DID,
                                                &blinded_master_secret,
                                                &blinded_master_secret_correctness_proof,
                                                &master_secret_blinding_nonce,
@@ -1098,7 +4676,7 @@ mod test {
                                                &cred_priv_key,
                                                rev_idx,
                                                max_cred_num,
-                                               issuance_by_default,
+                                               false,
                                                &mut rev_reg,
                                                &rev_key_priv,
                                                &simple_tail_accessor).unwrap();
@@ -1116,27 +4694,546 @@ mod test {
                                              Some(&rev_reg),
                                              Some(&witness)).unwrap();
 
+        let mut rev_regs = BTreeMap::new();
+        rev_regs.insert("reg1".to_string(), (rev_key_pub.clone(), rev_reg.clone()));
+        rev_regs.insert("reg2".to_string(), (other_rev_key_pub, other_rev_reg));
+
         let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
         sub_proof_request_builder.add_revealed_attr("name").unwrap();
-        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
         let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
-        let mut proof_builder = Prover::new_proof_builder().unwrap();
-        proof_builder.add_sub_proof_request(&sub_proof_request,
-                                            &credential_schema,
-                                            &cred_signature,
-                                            &cred_values,
-                                            &cred_pub_key,
-                                            Some(&rev_reg),
-                                            Some(&witness)).unwrap();
+
         let proof_request_nonce = new_nonce().unwrap();
-        let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+
+        let build_proof = |rev_reg_id: Option<&str>| {
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            match rev_reg_id {
+                Some(rev_reg_id) => {
+                    let bound_witness = Prover::bind_witness(&witness, &cred_signature, rev_reg_id).unwrap();
+                    proof_builder.add_sub_proof_request_with_rev_reg_id(&sub_proof_request, &credential_schema, &cred_signature, &cred_values,
+                                                                        &cred_pub_key, rev_reg_id, &rev_reg, &bound_witness, None).unwrap()
+                }
+                None =>
+                    proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature, &cred_values,
+                                                        &cred_pub_key, Some(&rev_reg), Some(&witness)).unwrap()
+            }
+            proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap()
+        };
+
+        let verify_proof = |proof: &Proof| {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request_with_rev_regs(&sub_proof_request, &credential_schema, &cred_pub_key, &rev_regs).unwrap();
+            proof_verifier.verify(proof, &proof_request_nonce)
+        };
+
+        let proof = build_proof(Some("reg1"));
+        assert_eq!(Some("reg1"), proof.proofs[0].non_revoc_proof.as_ref().unwrap().rev_reg_id());
+        assert!(verify_proof(&proof).unwrap());
+
+        // Proof against registry that is claimed to be another one
+        assert!(!verify_proof(&build_proof(Some("reg2"))).unwrap());
+
+        // Proof without registry id or with unknown one
+        assert!(verify_proof(&build_proof(None)).is_err());
+        assert!(verify_proof(&build_proof(Some("reg3"))).is_err());
+    }
+
+    #[test]
+    fn demo_non_ownership() {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("registry_member").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let (mut registry, mut registry_members) = Issuer::new_non_ownership_registry().unwrap();
+
+        // Enrolled holder
+        let enrolled_master_secret = Prover::new_master_secret().unwrap();
+        let enrollment_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, _) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &enrolled_master_secret, &enrollment_nonce).unwrap();
+        let (identifier, enrollment_proof) =
+            Prover::new_enrollment_proof(&registry, &cred_pub_key, &blinded_master_secret, &master_secret_blinding_data,
+                                         &enrolled_master_secret, &enrollment_nonce).unwrap();
+
+        assert!(Issuer::enroll(&mut registry, &mut registry_members, &identifier, &enrollment_proof, &blinded_master_secret,
+                               &new_nonce().unwrap(), &cred_pub_key).is_err());
+        Issuer::enroll(&mut registry, &mut registry_members, &identifier, &enrollment_proof, &blinded_master_secret,
+                       &enrollment_nonce, &cred_pub_key).unwrap();
+        assert!(registry_members.is_enrolled(&identifier).unwrap());
+
+        let enrolled_member = Issuer::new_non_ownership_member(&registry, &identifier, &enrollment_proof, &blinded_master_secret,
+                                                               &enrollment_nonce, &cred_pub_key).unwrap();
+        assert!(Issuer::new_non_ownership_witness(&registry, &registry_members, &enrolled_member).is_err());
+
+        // Published registry doesn't disclose enrolled identifiers
+        let registry_json = registry.to_json().unwrap();
+        assert!(!registry_json.contains("members"));
+        let mut registry = NonOwnershipRegistry::from_json(&registry_json).unwrap();
+
+        // Not enrolled holder gets registry member signed as hidden attribute
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+        let (identifier, enrollment_proof) =
+            Prover::new_enrollment_proof(&registry, &cred_pub_key, &blinded_master_secret, &master_secret_blinding_data,
+                                         &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        assert!(Issuer::new_non_ownership_member(&registry, &identifier, &enrollment_proof, &blinded_master_secret,
+                                                 &new_nonce().unwrap(), &cred_pub_key).is_err());
+        let member = Issuer::new_non_ownership_member(&registry, &identifier, &enrollment_proof, &blinded_master_secret,
+                                                      &master_secret_blinding_nonce, &cred_pub_key).unwrap();
+
+        let mut credential_values_builder = CredentialValuesBuilder::new().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("registry_member", &member.to_dec().unwrap()).unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let witness = Issuer::new_non_ownership_witness(&registry, &registry_members, &member).unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        assert!(proof_builder.add_non_ownership_request(0, "name", &registry, &witness).is_err());
+        proof_builder.add_non_ownership_request(0, "registry_member", &registry, &witness).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
 
         let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_non_ownership_request(0, "registry_member", &registry).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // Proofs don't disclose identifier and aren't linkable
+        let proof_json = proof.to_json().unwrap();
+        assert!(!proof_json.contains(&identifier.to_dec().unwrap()));
+        assert!(!proof_json.contains(&member.to_dec().unwrap()));
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_non_ownership_request(0, "registry_member", &registry, &witness).unwrap();
+        let other_proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+        assert_ne!(proof.non_ownership_proofs[0].c_d, other_proof.non_ownership_proofs[0].c_d);
+
+        // Registry was updated after proof creation
+        let registry_before_enrollment = registry.clone().unwrap();
+        Issuer::enroll(&mut registry, &mut registry_members, &identifier, &enrollment_proof, &blinded_master_secret,
+                       &master_secret_blinding_nonce, &cred_pub_key).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_non_ownership_request(0, "registry_member", &registry).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // Witness issued before registry update
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        assert!(proof_builder.add_non_ownership_request(0, "registry_member", &registry, &witness).is_err());
+        assert!(Issuer::new_non_ownership_witness(&registry, &registry_members, &member).is_err());
+
+        // Unenrollment restores accumulator
+        Issuer::unenroll(&mut registry, &mut registry_members, &identifier).unwrap();
+        assert!(!registry_members.is_enrolled(&identifier).unwrap());
+        assert_eq!(registry_before_enrollment.accum, registry.accum);
+    }
+
+    #[test]
+    fn demo_verifiable_encryption() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
                                              &cred_pub_key,
-                                             Some(&rev_key_pub),
-                                             Some(&rev_reg)).unwrap();
-        assert_eq!(true, proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let (auditor_pub_key, auditor_priv_key) = auditor::Auditor::new_keys().unwrap();
+        let (other_auditor_pub_key, _) = auditor::Auditor::new_keys().unwrap();
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        assert!(proof_builder.add_verifiable_encryption_request(0, "name", &auditor_pub_key).is_err());
+        proof_builder.add_verifiable_encryption_request(0, "sex", &auditor_pub_key).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_verifiable_encryption_request(0, "sex", &auditor_pub_key).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_verifiable_encryption_request(0, "sex", &other_auditor_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let value = auditor::Auditor::decrypt(proof.verifiable_encryptions()[0].ciphertext(), &auditor_pub_key, &auditor_priv_key).unwrap();
+        assert_eq!(credential_values.attrs_values["sex"], value);
+
+        let mut tampered_proof = proof;
+        let n_sqr = auditor_pub_key.n_sqr().unwrap();
+        tampered_proof.verifiable_encryptions[0].ciphertext.e = n_sqr.sub(&tampered_proof.verifiable_encryptions[0].ciphertext.e).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_verifiable_encryption_request(0, "sex", &auditor_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&tampered_proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_linear_predicates() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request_1 = prover::mocks::sub_proof_request();
+
+        // height - age >= 100 (175 - 28 = 147) and age + age >= 56
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        assert!(sub_proof_request_builder.add_linear_predicate(&[], "GE", 0).is_err());
+        sub_proof_request_builder.add_linear_predicate(&[(0, "height", 1), (1, "age", -1)], "GE", 100).unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "age", 1), (1, "age", 1)], "GE", 56).unwrap();
+        let sub_proof_request_2 = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request_2, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request_2, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // Negative combination and negative bound: age - height >= -150 (28 - 175 = -147)
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(1, "age", 1), (0, "height", -1)], "GE", -150).unwrap();
+        let negative_sub_proof_request_2 = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&negative_sub_proof_request_2, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        let negative_proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&negative_sub_proof_request_2, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&negative_proof, &nonce).unwrap());
+
+        // Verifier expecting other bound rejects proof
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "height", 1), (1, "age", -1)], "GE", 140).unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "age", 1), (1, "age", 1)], "GE", 56).unwrap();
+        let other_sub_proof_request_2 = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&other_sub_proof_request_2, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).is_err());
+
+        // Unsatisfied combination: height - age >= 148
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "height", 1), (1, "age", -1)], "GE", 148).unwrap();
+        let unsatisfied_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&unsatisfied_sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        assert!(proof_builder.finalize(&nonce, &master_secret).is_err());
+
+        // Revealed attributes can't be used in linear predicate
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_linear_predicate(&[(0, "name", 1)], "GE", 0).unwrap();
+        let revealed_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request_1, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&revealed_sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        assert!(proof_builder.finalize(&nonce, &master_secret).is_err());
+    }
+
+    #[test]
+    fn demo_pseudonym() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+        let domain = "verifier.example.com";
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_pseudonym_request(domain, &master_secret).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_pseudonym_request(domain).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        assert_eq!(Prover::create_pseudonym(domain, &master_secret).unwrap(), proof.pseudonyms()[0]);
+        assert_ne!(Prover::create_pseudonym("other.example.com", &master_secret).unwrap(), proof.pseudonyms()[0]);
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_pseudonym_request("other.example.com").unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).is_err());
+
+        // Pseudonym of other master secret can't be proven with presented credential
+        let other_master_secret = Prover::new_master_secret().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        proof_builder.add_pseudonym_request(domain, &other_master_secret).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        proof_verifier.add_pseudonym_request(domain).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_precomputed_key() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let cred_pub_key_precomputed = Arc::new(CredentialPublicKeyPrecomputed::new(&cred_pub_key).unwrap());
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential_with_precomputed_key(prover::mocks::PROVER_DID,
+                                                         &blinded_master_secret,
+                                                         &blinded_master_secret_correctness_proof,
+                                                         &master_secret_blinding_nonce,
+                                                         &cred_issuance_nonce,
+                                                         &credential_values,
+                                                         &cred_pub_key_precomputed,
+                                                         &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+
+        assert_eq!(sub_proof_request.revealed_attrs(), proof_builder.init_proofs[0].sub_proof_request().revealed_attrs());
+        assert!(!proof_builder.init_proofs[0].is_revocation_included());
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_precomputed_key(&sub_proof_request, &credential_schema, &cred_pub_key_precomputed, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_precomputed_key(&sub_proof_request, &credential_schema, &cred_pub_key_precomputed, None, None).unwrap();
+        assert!(!proof_verifier.verify(&proof, &new_nonce().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn credential_schema_fingerprint_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let fingerprint = credential_schema.fingerprint().unwrap();
+        assert_eq!(64, fingerprint.len());
+
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).unwrap();
+        for attr in credential_schema.attrs.iter() {
+            credential_schema_builder.add_attr(attr).unwrap();
+        }
+        let lowercase_credential_schema = credential_schema_builder.finalize().unwrap();
+        assert_ne!(fingerprint, lowercase_credential_schema.fingerprint().unwrap());
+
+        let credential_schema: CredentialSchema = serde_json::from_str(&serde_json::to_string(&credential_schema).unwrap()).unwrap();
+        assert_eq!(fingerprint, credential_schema.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn credential_schema_to_canonical_json_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        assert_eq!(r#"{"attrs":["age","height","name","sex"]}"#, credential_schema.to_canonical_json().unwrap());
+    }
+
+    #[test]
+    fn revocation_registry_fingerprint_works() {
+        let rev_reg = issuer::mocks::revocation_registry();
+        let restored_rev_reg = RevocationRegistry::from_json(&rev_reg.to_json().unwrap()).unwrap();
+        assert_eq!(rev_reg.fingerprint().unwrap(), restored_rev_reg.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn proof_fingerprint_works() {
+        let proof = prover::mocks::proof();
+        let fingerprint = proof.fingerprint().unwrap();
+
+        let restored_proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+        assert_eq!(fingerprint, restored_proof.fingerprint().unwrap());
+
+        let mut other_proof = prover::mocks::proof();
+        other_proof.aggregated_proof.challenge_hash = ChallengeHash::Sha3_256;
+        assert_ne!(fingerprint, other_proof.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn proof_inspection_works() {
+        let proof = prover::mocks::proof();
+
+        assert_eq!(1, proof.sub_proofs().len());
+
+        let sub_proof = &proof.sub_proofs()[0];
+        assert_eq!(prover::mocks::revealed_attrs(), sub_proof.revealed_attrs().keys().cloned().collect::<HashSet<String>>());
+        assert_eq!(&BigNumber::from_dec("1139481716457488690172217916278103335").unwrap(), &sub_proof.revealed_attrs()["name"]);
+        assert_eq!(vec![&prover::mocks::predicate()], sub_proof.predicates());
+        assert!(!sub_proof.is_revocation_included());
+        assert!(sub_proof.non_revoc_proof().is_none());
+    }
+
+    #[test]
+    fn sub_proof_request_inspection_works() {
+        let sub_proof_request = prover::mocks::sub_proof_request();
+
+        assert_eq!(&prover::mocks::revealed_attrs(), sub_proof_request.revealed_attrs());
+        assert_eq!(1, sub_proof_request.predicates().len());
+
+        let predicate = sub_proof_request.predicates().iter().next().unwrap();
+        assert_eq!("age", predicate.attr_name());
+        assert_eq!(&PredicateType::GE, predicate.p_type());
+        assert_eq!(18, predicate.value());
+
+        assert!(sub_proof_request.linear_predicates().is_empty());
+        assert!(sub_proof_request.non_revoked().is_none());
     }
 }
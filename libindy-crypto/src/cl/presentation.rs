@@ -0,0 +1,552 @@
+use bn::BigNumber;
+use cl::*;
+use cl::prover::Prover;
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Restrictions on credentials acceptable for sub proof request.
+/// Empty set of identifiers means that any identifier is acceptable.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CredentialRestrictions {
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    issuer_key_ids: HashSet<String>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    schema_ids: HashSet<String>,
+    #[serde(default)]
+    non_revoked: bool
+}
+
+impl CredentialRestrictions {
+    pub fn new() -> Result<CredentialRestrictions, IndyCryptoError> {
+        Ok(CredentialRestrictions::default())
+    }
+
+    /// Adds identifier of acceptable credential public key.
+    pub fn add_issuer_key_id(&mut self, issuer_key_id: &str) -> Result<(), IndyCryptoError> {
+        self.issuer_key_ids.insert(issuer_key_id.to_owned());
+        Ok(())
+    }
+
+    /// Adds identifier of acceptable credential schema.
+    pub fn add_schema_id(&mut self, schema_id: &str) -> Result<(), IndyCryptoError> {
+        self.schema_ids.insert(schema_id.to_owned());
+        Ok(())
+    }
+
+    /// Requires proof of non-revocation for credential.
+    pub fn set_non_revoked(&mut self, non_revoked: bool) -> Result<(), IndyCryptoError> {
+        self.non_revoked = non_revoked;
+        Ok(())
+    }
+
+    pub fn non_revoked(&self) -> bool {
+        self.non_revoked
+    }
+
+    /// Checks that credential with given identifiers is acceptable.
+    pub fn is_allowed(&self, issuer_key_id: &str, schema_id: &str) -> bool {
+        (self.issuer_key_ids.is_empty() || self.issuer_key_ids.contains(issuer_key_id)) &&
+            (self.schema_ids.is_empty() || self.schema_ids.contains(schema_id))
+    }
+}
+
+/// Proof request is a set of sub proof requests keyed by credential referent.
+/// Sub proofs are added to proof in the order of referents, so Prover and Verifier
+/// that use the same proof request get the same sub proofs order.
+///
+/// Every sub proof request has restrictions on credentials that can be used to satisfy it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProofRequest {
+    sub_proof_requests: BTreeMap<String /* credential referent */, SubProofRequest>,
+    #[serde(default)]
+    restrictions: BTreeMap<String /* credential referent */, CredentialRestrictions>
+}
+
+impl ProofRequest {
+    /// Returns credential referents in the order sub proofs will be added to proof.
+    pub fn referents(&self) -> Vec<String> {
+        self.sub_proof_requests.keys().cloned().collect()
+    }
+
+    pub fn sub_proof_request(&self, referent: &str) -> Option<&SubProofRequest> {
+        self.sub_proof_requests.get(referent)
+    }
+
+    /// Returns restrictions on credential for referent (no restrictions if they weren't set).
+    pub fn restrictions(&self, referent: &str) -> CredentialRestrictions {
+        self.restrictions.get(referent).cloned().unwrap_or_default()
+    }
+
+    /// Returns indexes of Prover credentials that satisfy sub proof request for referent:
+    /// credential is allowed by restrictions, contains all requested attributes
+    /// and its values satisfy predicates. Linear predicates aren't checked,
+    /// as they can depend on several credentials.
+    ///
+    /// # Arguments
+    /// * `referent` - Credential referent from proof request.
+    /// * `credentials` - Prover credentials.
+    pub fn find_credentials(&self, referent: &str, credentials: &[HeldCredential]) -> Result<Vec<usize>, IndyCryptoError> {
+        trace!("ProofRequest::find_credentials: >>> referent: {:?}, credentials: {:?}", referent, credentials);
+
+        let sub_proof_request = self.sub_proof_request(referent)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request for referent '{}' not found", referent)))?;
+        let restrictions = self.restrictions(referent);
+
+        let mut indexes: Vec<usize> = Vec::new();
+        for (idx, held_credential) in credentials.iter().enumerate() {
+            if ProofRequest::_is_satisfied_by(referent, sub_proof_request, &restrictions, held_credential)? {
+                indexes.push(idx);
+            }
+        }
+
+        trace!("ProofRequest::find_credentials: <<< indexes: {:?}", indexes);
+
+        Ok(indexes)
+    }
+
+    fn _is_satisfied_by(referent: &str,
+                        sub_proof_request: &SubProofRequest,
+                        restrictions: &CredentialRestrictions,
+                        held_credential: &HeldCredential) -> Result<bool, IndyCryptoError> {
+        let credential = &held_credential.credential;
+
+        if !restrictions.is_allowed(held_credential.issuer_key_id, held_credential.schema_id) {
+            return Ok(false);
+        }
+
+        if restrictions.non_revoked &&
+            (credential.credential_signature.r_credential.is_none() || credential.rev_reg.is_none() || credential.witness.is_none()) {
+            return Ok(false);
+        }
+
+        if Presentation::_check_attrs_availability(referent, sub_proof_request, credential).is_err() {
+            return Ok(false);
+        }
+
+        for predicate in sub_proof_request.predicates.iter() {
+            let value = &credential.credential_values.attrs_values[&predicate.attr_name];
+            let bound = BigNumber::from_dec(&predicate.value.to_string())?;
+
+            let satisfied = match predicate.p_type {
+                PredicateType::GE => *value >= bound,
+                PredicateType::GT => *value > bound,
+                PredicateType::LE => *value <= bound,
+                PredicateType::LT => *value < bound
+            };
+
+            if !satisfied {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl JsonEncodable for ProofRequest {}
+
+impl<'a> JsonDecodable<'a> for ProofRequest {}
+
+/// A Builder of `Proof Request`.
+#[derive(Debug)]
+pub struct ProofRequestBuilder {
+    sub_proof_requests: BTreeMap<String, SubProofRequest>,
+    restrictions: BTreeMap<String, CredentialRestrictions>
+}
+
+impl ProofRequestBuilder {
+    pub fn new() -> Result<ProofRequestBuilder, IndyCryptoError> {
+        Ok(ProofRequestBuilder {
+            sub_proof_requests: BTreeMap::new(),
+            restrictions: BTreeMap::new()
+        })
+    }
+
+    pub fn add_sub_proof_request(&mut self, referent: &str, sub_proof_request: &SubProofRequest) -> Result<(), IndyCryptoError> {
+        self.add_sub_proof_request_with_restrictions(referent, sub_proof_request, &CredentialRestrictions::default())
+    }
+
+    /// Adds sub proof request that can be satisfied only by credentials allowed by restrictions.
+    ///
+    /// # Arguments
+    /// * `referent` - Credential referent.
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `restrictions` - Restrictions on credential.
+    pub fn add_sub_proof_request_with_restrictions(&mut self,
+                                                   referent: &str,
+                                                   sub_proof_request: &SubProofRequest,
+                                                   restrictions: &CredentialRestrictions) -> Result<(), IndyCryptoError> {
+        if self.sub_proof_requests.contains_key(referent) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Sub proof request for referent '{}' already added", referent)));
+        }
+
+        self.sub_proof_requests.insert(referent.to_owned(), sub_proof_request.clone());
+        self.restrictions.insert(referent.to_owned(), restrictions.clone());
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<ProofRequest, IndyCryptoError> {
+        Ok(ProofRequest {
+            sub_proof_requests: self.sub_proof_requests,
+            restrictions: self.restrictions
+        })
+    }
+}
+
+/// Credential held by Prover that can be used to satisfy sub proof request.
+#[derive(Debug)]
+pub struct PresentationCredential<'a> {
+    pub credential_schema: &'a CredentialSchema,
+    pub credential_signature: &'a CredentialSignature,
+    pub credential_values: &'a CredentialValues,
+    pub credential_pub_key: &'a CredentialPublicKey,
+    pub rev_reg: Option<&'a RevocationRegistry>,
+    pub witness: Option<&'a Witness>
+}
+
+/// Prover credential together with identifiers used to check credential restrictions.
+#[derive(Debug)]
+pub struct HeldCredential<'a> {
+    pub schema_id: &'a str,
+    pub issuer_key_id: &'a str,
+    pub credential: PresentationCredential<'a>
+}
+
+/// Verifier knowledge of credential definition used for sub proof of presentation.
+#[derive(Debug)]
+pub struct PresentationCredentialDef<'a> {
+    pub schema_id: &'a str,
+    pub issuer_key_id: &'a str,
+    pub credential_schema: &'a CredentialSchema,
+    pub credential_pub_key: &'a CredentialPublicKey,
+    pub rev_key_pub: Option<&'a RevocationKeyPublic>,
+    pub rev_reg: Option<&'a RevocationRegistry>
+}
+
+/// Proof together with revealed attributes values grouped by credential referent.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Presentation {
+    pub proof: Proof,
+    pub revealed_attrs: BTreeMap<String /* credential referent */, BTreeMap<String /* attr name */, BigNumber>>
+}
+
+impl JsonEncodable for Presentation {}
+
+impl<'a> JsonDecodable<'a> for Presentation {}
+
+impl Presentation {
+    /// Creates presentation for proof request from Prover credentials.
+    ///
+    /// # Arguments
+    /// * `proof_request` - Proof request.
+    /// * `credentials` - Prover credentials keyed by referent from proof request.
+    /// * `nonce` - Proof request nonce.
+    /// * `master_secret` - Prover master secret.
+    pub fn new(proof_request: &ProofRequest,
+               credentials: &HashMap<String, PresentationCredential>,
+               nonce: &Nonce,
+               master_secret: &MasterSecret) -> Result<Presentation, IndyCryptoError> {
+        trace!("Presentation::new: >>> proof_request: {:?}, credentials: {:?}, nonce: {:?}", proof_request, credentials, nonce);
+
+        let mut proof_builder = Prover::new_proof_builder()?;
+        let mut revealed_attrs: BTreeMap<String, BTreeMap<String, BigNumber>> = BTreeMap::new();
+
+        for (referent, sub_proof_request) in proof_request.sub_proof_requests.iter() {
+            let credential = credentials.get(referent)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Credential for referent '{}' not found", referent)))?;
+
+            Presentation::_check_attrs_availability(referent, sub_proof_request, credential)?;
+
+            proof_builder.add_sub_proof_request(sub_proof_request,
+                                                credential.credential_schema,
+                                                credential.credential_signature,
+                                                credential.credential_values,
+                                                credential.credential_pub_key,
+                                                credential.rev_reg,
+                                                credential.witness)?;
+
+            let mut credential_revealed_attrs: BTreeMap<String, BigNumber> = BTreeMap::new();
+            for attr in sub_proof_request.revealed_attrs.iter() {
+                let value = credential.credential_values.attrs_values.get(attr)
+                    .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "credential values".to_string() })?;
+                credential_revealed_attrs.insert(attr.clone(), value.clone()?);
+            }
+            revealed_attrs.insert(referent.clone(), credential_revealed_attrs);
+        }
+
+        let presentation = Presentation {
+            proof: proof_builder.finalize(nonce, master_secret)?,
+            revealed_attrs
+        };
+
+        trace!("Presentation::new: <<< presentation: {:?}", presentation);
+
+        Ok(presentation)
+    }
+
+    fn _check_attrs_availability(referent: &str,
+                                 sub_proof_request: &SubProofRequest,
+                                 credential: &PresentationCredential) -> Result<(), IndyCryptoError> {
+        trace!("Presentation::_check_attrs_availability: >>> referent: {:?}, sub_proof_request: {:?}", referent, sub_proof_request);
+
+        let requested_attrs = sub_proof_request.revealed_attrs.iter()
+            .chain(sub_proof_request.predicates.iter().map(|predicate| &predicate.attr_name));
+
+        for attr in requested_attrs {
+            if !credential.credential_schema.attrs.contains(attr) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Credential for referent '{}' doesn't contain requested attribute '{}'", referent, attr)));
+            }
+
+            if !credential.credential_values.attrs_values.contains_key(attr) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Credential values for referent '{}' don't contain requested attribute '{}'", referent, attr)));
+            }
+        }
+
+        trace!("Presentation::_check_attrs_availability: <<<");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::issuer::Issuer;
+    use cl::verifier::Verifier;
+
+    #[test]
+    fn proof_request_builder_works_for_duplicated_referent() {
+        let sub_proof_request = prover::mocks::sub_proof_request();
+
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request("cred1", &sub_proof_request).unwrap();
+        assert!(proof_request_builder.add_sub_proof_request("cred1", &sub_proof_request).is_err());
+    }
+
+    #[test]
+    fn presentation_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = prover::mocks::sub_proof_request();
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request("cred1", &sub_proof_request).unwrap();
+        let proof_request = proof_request_builder.finalize().unwrap();
+
+        let mut credentials = HashMap::new();
+        credentials.insert("cred1".to_owned(), PresentationCredential {
+            credential_schema: &credential_schema,
+            credential_signature: &cred_signature,
+            credential_values: &credential_values,
+            credential_pub_key: &cred_pub_key,
+            rev_reg: None,
+            witness: None
+        });
+
+        let nonce = new_nonce().unwrap();
+        let presentation = Presentation::new(&proof_request, &credentials, &nonce, &master_secret).unwrap();
+
+        assert_eq!(credential_values.attrs_values["name"], presentation.revealed_attrs["cred1"]["name"]);
+        assert_eq!(1, presentation.revealed_attrs["cred1"].len());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&presentation.proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn presentation_works_for_missed_credential() {
+        let sub_proof_request = prover::mocks::sub_proof_request();
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request("cred1", &sub_proof_request).unwrap();
+        let proof_request = proof_request_builder.finalize().unwrap();
+
+        let res = Presentation::new(&proof_request, &HashMap::new(), &new_nonce().unwrap(), &prover::mocks::master_secret());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn check_attrs_availability_works_for_missed_value() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = prover::mocks::credential_revealed_attributes_values();
+        let cred_pub_key = issuer::mocks::credential_public_key();
+        let cred_signature = prover::mocks::credential();
+
+        let credential = PresentationCredential {
+            credential_schema: &credential_schema,
+            credential_signature: &cred_signature,
+            credential_values: &credential_values,
+            credential_pub_key: &cred_pub_key,
+            rev_reg: None,
+            witness: None
+        };
+
+        let res = Presentation::_check_attrs_availability("cred1", &prover::mocks::sub_proof_request(), &credential);
+        assert!(res.is_err());
+    }
+
+    fn _sub_proof_request_with_predicate(value: i32) -> SubProofRequest {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", value).unwrap();
+        sub_proof_request_builder.finalize().unwrap()
+    }
+
+    #[test]
+    fn proof_request_find_credentials_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let cred_pub_key = issuer::mocks::credential_public_key();
+        let cred_signature = prover::mocks::credential();
+
+        let held_credential = |schema_id, issuer_key_id| HeldCredential {
+            schema_id,
+            issuer_key_id,
+            credential: PresentationCredential {
+                credential_schema: &credential_schema,
+                credential_signature: &cred_signature,
+                credential_values: &credential_values,
+                credential_pub_key: &cred_pub_key,
+                rev_reg: None,
+                witness: None
+            }
+        };
+        let credentials = vec![held_credential("schema1", "key1"), held_credential("schema2", "key1"), held_credential("schema1", "key2")];
+
+        let mut restrictions = CredentialRestrictions::new().unwrap();
+        restrictions.add_schema_id("schema1").unwrap();
+        restrictions.add_issuer_key_id("key1").unwrap();
+
+        let mut non_revoked_restrictions = CredentialRestrictions::new().unwrap();
+        non_revoked_restrictions.set_non_revoked(true).unwrap();
+
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request_with_restrictions("restricted", &_sub_proof_request_with_predicate(18), &restrictions).unwrap();
+        proof_request_builder.add_sub_proof_request("any", &_sub_proof_request_with_predicate(18)).unwrap();
+        proof_request_builder.add_sub_proof_request("unsatisfied_predicate", &_sub_proof_request_with_predicate(30)).unwrap();
+        proof_request_builder.add_sub_proof_request_with_restrictions("non_revoked", &_sub_proof_request_with_predicate(18), &non_revoked_restrictions).unwrap();
+        let proof_request = proof_request_builder.finalize().unwrap();
+
+        assert_eq!(vec![0], proof_request.find_credentials("restricted", &credentials).unwrap());
+        assert_eq!(vec![0, 1, 2], proof_request.find_credentials("any", &credentials).unwrap());
+        assert!(proof_request.find_credentials("unsatisfied_predicate", &credentials).unwrap().is_empty());
+        assert!(proof_request.find_credentials("non_revoked", &credentials).unwrap().is_empty());
+        assert!(proof_request.find_credentials("unknown", &credentials).is_err());
+    }
+
+    #[test]
+    fn proof_request_serialization_works() {
+        let mut restrictions = CredentialRestrictions::new().unwrap();
+        restrictions.add_schema_id("schema1").unwrap();
+
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request_with_restrictions("cred1", &prover::mocks::sub_proof_request(), &restrictions).unwrap();
+        let proof_request = proof_request_builder.finalize().unwrap();
+
+        let proof_request = ProofRequest::from_json(&proof_request.to_json().unwrap()).unwrap();
+        assert_eq!(restrictions, proof_request.restrictions("cred1"));
+        assert!(proof_request.sub_proof_request("cred1").is_some());
+    }
+
+    #[test]
+    fn verify_presentation_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(prover::mocks::PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let mut restrictions = CredentialRestrictions::new().unwrap();
+        restrictions.add_issuer_key_id("key1").unwrap();
+
+        let mut proof_request_builder = ProofRequestBuilder::new().unwrap();
+        proof_request_builder.add_sub_proof_request_with_restrictions("cred1", &prover::mocks::sub_proof_request(), &restrictions).unwrap();
+        let proof_request = proof_request_builder.finalize().unwrap();
+
+        let mut credentials = HashMap::new();
+        credentials.insert("cred1".to_owned(), PresentationCredential {
+            credential_schema: &credential_schema,
+            credential_signature: &cred_signature,
+            credential_values: &credential_values,
+            credential_pub_key: &cred_pub_key,
+            rev_reg: None,
+            witness: None
+        });
+
+        let nonce = new_nonce().unwrap();
+        let mut presentation = Presentation::new(&proof_request, &credentials, &nonce, &master_secret).unwrap();
+
+        let credential_def = |issuer_key_id| PresentationCredentialDef {
+            schema_id: "schema1",
+            issuer_key_id,
+            credential_schema: &credential_schema,
+            credential_pub_key: &cred_pub_key,
+            rev_key_pub: None,
+            rev_reg: None
+        };
+
+        let mut credential_defs = HashMap::new();
+        credential_defs.insert("cred1".to_owned(), credential_def("key1"));
+        assert!(Verifier::verify_presentation(&presentation, &proof_request, &credential_defs, &nonce).unwrap());
+
+        let mut other_credential_defs = HashMap::new();
+        other_credential_defs.insert("cred1".to_owned(), credential_def("key2"));
+        assert!(!Verifier::verify_presentation(&presentation, &proof_request, &other_credential_defs, &nonce).unwrap());
+
+        presentation.revealed_attrs.get_mut("cred1").unwrap().insert("name".to_owned(), BigNumber::from_u32(1).unwrap());
+        assert!(!Verifier::verify_presentation(&presentation, &proof_request, &credential_defs, &nonce).unwrap());
+    }
+}
@@ -1,17 +1,41 @@
-use bn::BigNumber;
+use bn::{BigNumber, BigNumberContext};
 use cl::*;
 use cl::constants::*;
+use cl::ps;
 use errors::IndyCryptoError;
 use pair::*;
+use range_proof::{RangeProof, RANGE_BITS, bignum_to_scalar, commit};
 use super::helpers::*;
-use utils::commitment::{get_pedersen_commitment, get_exponentiated_generators};
+use commitments::{get_pedersen_commitment, get_exponentiated_generators};
+use sigma::{BnGroup, Statement};
+use utils::drbg::with_seeded_rng;
+use utils::json::{JsonEncodable, JsonDecodable};
+use utils::logger::OperationTimer;
+use utils::rng::random_bytes;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter::FromIterator;
+use std::sync::Arc;
 
 /// Credentials owner that can proof and partially disclose the credentials to verifier.
 pub struct Prover {}
 
+/// Credential received from Issuer together with data required to process its signature.
+/// Used by `Prover::process_credential_signatures` to accept many credentials issued with the same key.
+#[derive(Debug)]
+pub struct IssuedCredential<'a> {
+    pub credential_signature: &'a mut CredentialSignature,
+    pub credential_values: &'a CredentialValues,
+    pub signature_correctness_proof: &'a SignatureCorrectnessProof,
+    pub master_secret_blinding_data: &'a MasterSecretBlindingData,
+    pub nonce: &'a Nonce,
+    pub rev_reg: Option<&'a RevocationRegistry>,
+    pub witness: Option<&'a Witness>
+}
+
 impl Prover {
     /// Creates a master secret.
     ///
@@ -27,6 +51,80 @@ impl Prover {
         })
     }
 
+    /// Creates pseudonym of master secret owner for domain (e.g. verifier service).
+    /// Pseudonym is deterministic, so it can be reused as login identifier in the domain,
+    /// but pseudonyms for different domains are unlinkable.
+    /// Prover proves that pseudonym is derived from master secret of presented credentials
+    /// with `ProofBuilder::add_pseudonym_request`.
+    ///
+    /// # Arguments
+    /// * `domain` - Domain pseudonym is created for.
+    /// * `master_secret` - Master secret.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let pseudonym = Prover::create_pseudonym("example.com", &master_secret).unwrap();
+    /// assert_eq!(pseudonym, Prover::create_pseudonym("example.com", &master_secret).unwrap());
+    /// assert_ne!(pseudonym, Prover::create_pseudonym("example.org", &master_secret).unwrap());
+    /// ```
+    pub fn create_pseudonym(domain: &str, master_secret: &MasterSecret) -> Result<Pseudonym, IndyCryptoError> {
+        trace!("Prover::create_pseudonym: >>> domain: {:?}, master_secret: {:?}", domain, master_secret);
+
+        let value = Pseudonym::base(domain)?
+            .mod_exp(&master_secret.ms, &Pseudonym::modulus()?, None)?;
+
+        let pseudonym = Pseudonym { domain: domain.to_owned(), value };
+
+        trace!("Prover::create_pseudonym: <<< pseudonym: {:?}", pseudonym);
+
+        Ok(pseudonym)
+    }
+
+    /// Creates proof of knowledge of master secret behind pseudonym for domain.
+    /// Proof doesn't require any credential, so it can be used to bind holder before issuance;
+    /// later presentations with `ProofBuilder::add_pseudonym_request` for the same domain reveal the same pseudonym.
+    ///
+    /// # Arguments
+    /// * `domain` - Domain of pseudonym.
+    /// * `master_secret` - Master secret.
+    /// * `nonce` - Nonce provided by verifier.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let nonce = new_nonce().unwrap();
+    ///
+    /// let master_secret_proof = Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+    /// assert!(Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &nonce).unwrap());
+    /// ```
+    pub fn create_master_secret_proof(domain: &str,
+                                      master_secret: &MasterSecret,
+                                      nonce: &Nonce) -> Result<MasterSecretProof, IndyCryptoError> {
+        trace!("Prover::create_master_secret_proof: >>> domain: {:?}, master_secret: {:?}, nonce: {:?}", domain, master_secret, nonce);
+
+        let pseudonym = Prover::create_pseudonym(domain, master_secret)?;
+
+        let r = bn_rand(LARGE_M1_TILDE)?;
+        let t = Pseudonym::base(domain)?
+            .mod_exp(&r, &Pseudonym::modulus()?, None)?;
+
+        let c = MasterSecretProof::challenge(&pseudonym, &t, nonce)?;
+        let s = c.mul(&master_secret.ms, None)?.add(&r)?;
+
+        let master_secret_proof = MasterSecretProof { pseudonym, c, s };
+
+        trace!("Prover::create_master_secret_proof: <<< master_secret_proof: {:?}", master_secret_proof);
+
+        Ok(master_secret_proof)
+    }
+
     /// Creates blinded master secret for given issuer key and master secret.
     ///
     /// # Arguments
@@ -64,38 +162,127 @@ impl Prover {
         trace!("Prover::blind_master_secret: >>> credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, master_secret: {:?}, \
         master_secret_blinding_nonce: {:?}", credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce);
 
-        Prover::_check_credential_key_correctness_proof(&credential_pub_key.p_key, credential_key_correctness_proof)?;
+        if !credential_pub_key.requires_master_secret() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential public key doesn't use master secret")));
+        }
 
-        let blinded_primary_master_secret =
-            Prover::_generate_blinded_primary_master_secret(&credential_pub_key.p_key, &master_secret)?;
+        let (blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof) =
+            Prover::_blind_master_secret(credential_pub_key, credential_key_correctness_proof, Some(master_secret), master_secret_blinding_nonce)?;
 
-        let blinded_revocation_master_secret = match credential_pub_key.r_key {
-            Some(ref r_pk) => Some(Prover::_generate_blinded_revocation_master_secret(r_pk)?),
-            _ => None
-        };
+        trace!("Prover::blind_master_secret: <<< blinded_master_secret: {:?}, master_secret_blinding_factor: {:?}, blinded_master_secret_correctness_proof: {:?},",
+               blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof);
 
-        let blinded_master_secret_correctness_proof =
-            Prover::_new_blinded_master_secret_correctness_proof(&credential_pub_key.p_key,
-                                                                 &blinded_primary_master_secret,
-                                                                 &master_secret_blinding_nonce,
-                                                                 &master_secret)?;
+        Ok((blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof))
+    }
 
-        let blinded_master_secret = BlindedMasterSecret {
-            u: blinded_primary_master_secret.u,
-            ur: blinded_revocation_master_secret.as_ref().map(|d| d.ur)
-        };
+    /// Creates blinding data for credential signed with key without master secret (e.g. device credential).
+    /// Only blinding factor is committed, so no master secret is required for issuance.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key created without master secret.
+    /// * `credential_key_correctness_proof` - Credential key correctness proof.
+    /// * `blinding_nonce` - Nonce used for creation of blinding correctness proof.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("serial").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, _credential_priv_key, cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_without_master_secret(&credential_schema, false).unwrap();
+    ///
+    /// let blinding_nonce = new_nonce().unwrap();
+    /// let (_blinded_master_secret, _master_secret_blinding_data, _blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_credential_without_master_secret(&credential_pub_key,
+    ///                                                    &cred_key_correctness_proof,
+    ///                                                    &blinding_nonce).unwrap();
+    /// ```
+    pub fn blind_credential_without_master_secret(credential_pub_key: &CredentialPublicKey,
+                                                  credential_key_correctness_proof: &CredentialKeyCorrectnessProof,
+                                                  blinding_nonce: &Nonce) -> Result<(BlindedMasterSecret,
+                                                                                     MasterSecretBlindingData,
+                                                                                     BlindedMasterSecretCorrectnessProof), IndyCryptoError> {
+        trace!("Prover::blind_credential_without_master_secret: >>> credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, \
+        blinding_nonce: {:?}", credential_pub_key, credential_key_correctness_proof, blinding_nonce);
+
+        if credential_pub_key.requires_master_secret() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential public key requires master secret")));
+        }
 
-        let master_secret_blinding_factor = MasterSecretBlindingData {
-            v_prime: blinded_primary_master_secret.v_prime,
-            vr_prime: blinded_revocation_master_secret.map(|d| d.vr_prime)
-        };
+        let (blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof) =
+            Prover::_blind_master_secret(credential_pub_key, credential_key_correctness_proof, None, blinding_nonce)?;
 
-        trace!("Prover::blind_master_secret: <<< blinded_master_secret: {:?}, master_secret_blinding_factor: {:?}, blinded_master_secret_correctness_proof: {:?},",
-               blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof);
+        trace!("Prover::blind_credential_without_master_secret: <<< blinded_master_secret: {:?}, master_secret_blinding_factor: {:?}, \
+        blinded_master_secret_correctness_proof: {:?},", blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof);
 
         Ok((blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof))
     }
 
+    /// Creates holder identifier in non-ownership registry and proof that identifier is derived
+    /// from the same master secret as blinded master secret sent to Issuer.
+    ///
+    /// # Arguments
+    /// * `registry` - Non-ownership registry holder is enrolled to.
+    /// * `credential_pub_key` - Credential public key used for master secret blinding.
+    /// * `blinded_master_secret` - Blinded master secret.
+    /// * `master_secret_blinding_data` - Master secret blinding data.
+    /// * `master_secret` - Master secret.
+    /// * `nonce` - Nonce provided by Issuer.
+    pub fn new_enrollment_proof(registry: &NonOwnershipRegistry,
+                                credential_pub_key: &CredentialPublicKey,
+                                blinded_master_secret: &BlindedMasterSecret,
+                                master_secret_blinding_data: &MasterSecretBlindingData,
+                                master_secret: &MasterSecret,
+                                nonce: &Nonce) -> Result<(BigNumber, EnrollmentProof), IndyCryptoError> {
+        trace!("Prover::new_enrollment_proof: >>> registry: {:?}, credential_pub_key: {:?}, blinded_master_secret: {:?}, \
+               master_secret_blinding_data: {:?}, master_secret: {:?}, nonce: {:?}",
+               registry, credential_pub_key, blinded_master_secret, master_secret_blinding_data, master_secret, nonce);
+
+        if !credential_pub_key.requires_master_secret() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential public key doesn't use master secret")));
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+        let p_pub_key = &credential_pub_key.p_key;
+
+        let identifier = registry.identifier(master_secret)?;
+
+        let ms_tilde = bn_rand(LARGE_MTILDE)?;
+        let v_dash_tilde = bn_rand(LARGE_VPRIME_TILDE)?;
+
+        let u_tilde = get_pedersen_commitment(&p_pub_key.rms, &ms_tilde, &p_pub_key.s,
+                                              &v_dash_tilde, &p_pub_key.n, &mut ctx)?;
+        let identifier_tilde = registry.h.mod_exp(&ms_tilde, &registry.n, Some(&mut ctx))?;
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        values.push(blinded_master_secret.u.to_bytes()?);
+        values.push(u_tilde.to_bytes()?);
+        values.push(identifier.to_bytes()?);
+        values.push(identifier_tilde.to_bytes()?);
+        values.push(nonce.to_bytes()?);
+
+        let c = get_hash_as_int(&values)?;
+
+        let v_dash_cap =
+            c.mul(&master_secret_blinding_data.v_prime, Some(&mut ctx))?
+                .add(&v_dash_tilde)?;
+
+        let ms_cap =
+            c.mul(&master_secret.ms, Some(&mut ctx))?
+                .add(&ms_tilde)?;
+
+        let enrollment_proof = EnrollmentProof { c, v_dash_cap, ms_cap };
+
+        trace!("Prover::new_enrollment_proof: <<< identifier: {:?}, enrollment_proof: {:?}", identifier, enrollment_proof);
+
+        Ok((identifier, enrollment_proof))
+    }
+
     /// Updates the credential signature by a master secret blinding data.
     ///
     /// # Arguments
@@ -166,112 +353,706 @@ impl Prover {
         master_secret: {:?}, credential_pub_key: {:?}, nonce: {:?}, rev_key_pub: {:?}, rev_reg: {:?}, witness: {:?}", credential_signature,
                signature_correctness_proof, master_secret_blinding_data, master_secret, credential_pub_key, nonce, rev_key_pub, rev_reg, witness);
 
-        Prover::_process_primary_credential(&mut credential_signature.p_credential, &master_secret_blinding_data.v_prime)?;
+        Prover::_process_credential_signature(credential_signature,
+                                              credential_values,
+                                              signature_correctness_proof,
+                                              master_secret_blinding_data,
+                                              Some(master_secret),
+                                              credential_pub_key,
+                                              nonce,
+                                              rev_key_pub,
+                                              rev_reg,
+                                              witness)?;
 
-        Prover::_check_signature_correctness_proof(&credential_signature.p_credential,
-                                                   credential_values,
-                                                   signature_correctness_proof,
-                                                   master_secret,
-                                                   &credential_pub_key.p_key,
-                                                   nonce)?;
+        trace!("Prover::process_credential_signature: <<<");
 
-        if let (&mut Some(ref mut non_revocation_cred), Some(ref vr_prime), &Some(ref r_key),
-            Some(ref r_key_pub), Some(ref r_reg), Some(ref witness)) = (&mut credential_signature.r_credential,
-                                                                        master_secret_blinding_data.vr_prime,
-                                                                        &credential_pub_key.r_key,
-                                                                        rev_key_pub,
-                                                                        rev_reg,
-                                                                        witness) {
-            Prover::_process_non_revocation_credential(non_revocation_cred,
-                                                       vr_prime,
-                                                       &r_key,
-                                                       r_key_pub,
-                                                       r_reg,
-                                                       witness)?;
+        Ok(())
+    }
+
+    /// Updates the signature of credential signed with key without master secret (e.g. device credential).
+    ///
+    /// # Arguments
+    /// * `credential_signature` - Credential signature generated by Issuer.
+    /// * `credential_values` - Credential values.
+    /// * `signature_correctness_proof` - Credential signature correctness proof.
+    /// * `master_secret_blinding_data` - Blinding data created by `Prover::blind_credential_without_master_secret`.
+    /// * `credential_pub_key` - Credential public key created without master secret.
+    /// * `nonce` -  Nonce was used by Issuer for the creation of signature_correctness_proof.
+    /// * `rev_key_pub` - (Optional) Revocation registry public key.
+    /// * `rev_reg` - (Optional) Revocation registry.
+    /// * `witness` - (Optional) Witness.
+    pub fn process_credential_signature_without_master_secret(credential_signature: &mut CredentialSignature,
+                                                              credential_values: &CredentialValues,
+                                                              signature_correctness_proof: &SignatureCorrectnessProof,
+                                                              master_secret_blinding_data: &MasterSecretBlindingData,
+                                                              credential_pub_key: &CredentialPublicKey,
+                                                              nonce: &Nonce,
+                                                              rev_key_pub: Option<&RevocationKeyPublic>,
+                                                              rev_reg: Option<&RevocationRegistry>,
+                                                              witness: Option<&Witness>) -> Result<(), IndyCryptoError> {
+        trace!("Prover::process_credential_signature_without_master_secret: >>> credential_signature: {:?}, signature_correctness_proof: {:?}, \
+        master_secret_blinding_data: {:?}, credential_pub_key: {:?}, nonce: {:?}, rev_key_pub: {:?}, rev_reg: {:?}, witness: {:?}", credential_signature,
+               signature_correctness_proof, master_secret_blinding_data, credential_pub_key, nonce, rev_key_pub, rev_reg, witness);
+
+        Prover::_process_credential_signature(credential_signature,
+                                              credential_values,
+                                              signature_correctness_proof,
+                                              master_secret_blinding_data,
+                                              None,
+                                              credential_pub_key,
+                                              nonce,
+                                              rev_key_pub,
+                                              rev_reg,
+                                              witness)?;
+
+        trace!("Prover::process_credential_signature_without_master_secret: <<<");
+
+        Ok(())
+    }
+
+    /// Updates batch of credential signatures issued with the same credential public key (e.g. during bulk onboarding).
+    /// Equivalent to calling `process_credential_signature` for each credential, but signature equations
+    /// of all credentials are checked together as one equation combined with random exponents.
+    /// Fails if any of signature correctness proofs is invalid.
+    ///
+    /// # Arguments
+    /// * `credentials` - Received credentials.
+    /// * `master_secret` - Master secret.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_key_pub` - (Optional) Revocation registry public key.
+    pub fn process_credential_signatures(credentials: &mut [IssuedCredential],
+                                         master_secret: &MasterSecret,
+                                         credential_pub_key: &CredentialPublicKey,
+                                         rev_key_pub: Option<&RevocationKeyPublic>) -> Result<(), IndyCryptoError> {
+        trace!("Prover::process_credential_signatures: >>> credentials: {:?}, master_secret: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}",
+               credentials, master_secret, credential_pub_key, rev_key_pub);
+
+        for credential in credentials.iter_mut() {
+            Prover::_process_primary_credential(&mut credential.credential_signature.p_credential,
+                                                &credential.master_secret_blinding_data.v_prime)?;
         }
 
-        trace!("Prover::process_credential_signature: <<<");
+        {
+            let primary_credentials: Vec<(&PrimaryCredentialSignature, &CredentialValues, &SignatureCorrectnessProof, &Nonce)> =
+                credentials.iter()
+                    .map(|credential| (&credential.credential_signature.p_credential,
+                                       credential.credential_values,
+                                       credential.signature_correctness_proof,
+                                       credential.nonce))
+                    .collect();
+
+            Prover::_check_signature_correctness_proofs(&primary_credentials, Some(master_secret), &credential_pub_key.p_key)?;
+        }
+
+        for credential in credentials.iter_mut() {
+            if let (&mut Some(ref mut non_revocation_cred), Some(ref vr_prime), &Some(ref r_key),
+                Some(ref r_key_pub), Some(ref r_reg), Some(ref witness)) = (&mut credential.credential_signature.r_credential,
+                                                                            credential.master_secret_blinding_data.vr_prime,
+                                                                            &credential_pub_key.r_key,
+                                                                            rev_key_pub,
+                                                                            credential.rev_reg,
+                                                                            credential.witness) {
+                Prover::_process_non_revocation_credential(non_revocation_cred,
+                                                           vr_prime,
+                                                           &r_key,
+                                                           r_key_pub,
+                                                           r_reg,
+                                                           witness)?;
+            }
+        }
+
+        trace!("Prover::process_credential_signatures: <<<");
 
         Ok(())
     }
 
-    /// Creates and returns proof builder.
+    /// Checks that stored credential signature is still valid signature of credential values and master secret.
+    /// Unlike `process_credential_signature` it checks signature equation itself instead of the issuance
+    /// correctness proof, so wallets can detect corrupted credentials before building proofs with them.
+    /// Master secret is required as it is signed blindly together with credential values.
+    /// Only primary part of signature is checked.
+    ///
+    /// # Arguments
+    /// * `credential_signature` - Processed credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `master_secret` - Master secret.
     ///
-    /// The purpose of proof builder is building of proof entity according to the given request .
     /// # Example
     /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
     /// use indy_crypto::cl::prover::Prover;
     ///
-    /// let _proof_builder = Prover::new_proof_builder();
-    pub fn new_proof_builder() -> Result<ProofBuilder, IndyCryptoError> {
-        Ok(ProofBuilder {
-            m1_tilde: bn_rand(LARGE_M1_TILDE)?,
-            init_proofs: Vec::new(),
-            c_list: Vec::new(),
-            tau_list: Vec::new()
-        })
-    }
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// assert!(Prover::check_credential_signature(&credential_signature, &credential_values, &credential_pub_key, &master_secret).unwrap());
+    /// ```
+    pub fn check_credential_signature(credential_signature: &CredentialSignature,
+                                      credential_values: &CredentialValues,
+                                      credential_pub_key: &CredentialPublicKey,
+                                      master_secret: &MasterSecret) -> Result<bool, IndyCryptoError> {
+        trace!("Prover::check_credential_signature: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, master_secret: {:?}",
+               credential_signature, credential_values, credential_pub_key, master_secret);
 
-    fn _check_credential_key_correctness_proof(pr_pub_key: &CredentialPrimaryPublicKey,
-                                               key_correctness_proof: &CredentialKeyCorrectnessProof) -> Result<(), IndyCryptoError> {
-        trace!("Prover::_check_credential_key_correctness_proof: >>> pr_pub_key: {:?}, key_correctness_proof: {:?}",
-               pr_pub_key, key_correctness_proof);
+        let valid = Prover::_check_primary_credential_signature(&credential_signature.p_credential,
+                                                                credential_values,
+                                                                Some(master_secret),
+                                                                &credential_pub_key.p_key)?;
 
-        let mut ctx = BigNumber::new_context()?;
+        trace!("Prover::check_credential_signature: <<< valid: {:?}", valid);
 
-        let z_inverse = pr_pub_key.z.inverse(&pr_pub_key.n, Some(&mut ctx))?;
-        let z_cap = get_pedersen_commitment(&z_inverse, &key_correctness_proof.c,
-                                            &pr_pub_key.s, &key_correctness_proof.xz_cap, &pr_pub_key.n, &mut ctx)?;
+        Ok(valid)
+    }
 
-        let mut r_cap: BTreeMap<String, BigNumber> = BTreeMap::new();
-        for (key, r_value) in pr_pub_key.r.iter() {
-            let xr_cap_value = key_correctness_proof.xr_cap
-                .get(key)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in key_correctness_proof.xr_cap", key)))?;
+    /// Updates witness by revocation registry changes made between two timestamps.
+    /// Deltas are fetched from witness updater, so integrations can plug in ledger or remote delta sources.
+    /// Returns revocation registry that corresponds to updated witness or None if registry wasn't changed.
+    ///
+    /// # Arguments
+    /// * `witness` - Witness to update.
+    /// * `rev_idx` - Index of the credential in revocation registry.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `rev_reg_id` - Revocation registry identifier.
+    /// * `from` - Timestamp of registry state witness corresponds to.
+    /// * `to` - Timestamp of registry state to update witness to.
+    /// * `witness_updater` - Source of revocation registry deltas.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn update_non_revocation_witness<WU, RTA>(witness: &mut Witness,
+                                                  rev_idx: u64,
+                                                  max_cred_num: u64,
+                                                  rev_reg_id: &str,
+                                                  from: u64,
+                                                  to: u64,
+                                                  witness_updater: &WU,
+                                                  rev_tails_accessor: &RTA) -> Result<Option<RevocationRegistry>, IndyCryptoError>
+        where WU: WitnessUpdater, RTA: RevocationTailsAccessor {
+        trace!("Prover::update_non_revocation_witness: >>> witness: {:?}, rev_idx: {:?}, max_cred_num: {:?}, rev_reg_id: {:?}, from: {:?}, to: {:?}",
+               witness, rev_idx, max_cred_num, rev_reg_id, from, to);
+
+        let mut deltas = witness_updater.get_deltas(rev_reg_id, from, to)?.into_iter();
+
+        let rev_reg = match deltas.next() {
+            Some(mut rev_reg_delta) => {
+                for delta in deltas {
+                    rev_reg_delta.merge(&delta)?;
+                }
+
+                witness.update(rev_idx, max_cred_num, &rev_reg_delta, rev_tails_accessor)?;
+
+                Some(RevocationRegistry::from(rev_reg_delta))
+            }
+            None => None
+        };
 
-            let r_inverse = r_value.inverse(&pr_pub_key.n, Some(&mut ctx))?;
-            let val = get_pedersen_commitment(&r_inverse, &key_correctness_proof.c,
-                                              &pr_pub_key.s, &xr_cap_value, &pr_pub_key.n, &mut ctx)?;
+        trace!("Prover::update_non_revocation_witness: <<< rev_reg: {:?}", rev_reg);
 
-            r_cap.insert(key.to_owned(), val);
-        }
+        Ok(rev_reg)
+    }
 
-        let mut values: Vec<u8> = Vec::new();
-        values.extend_from_slice(&pr_pub_key.z.to_bytes()?);
-        for val in pr_pub_key.r.values() {
-            values.extend_from_slice(&val.to_bytes()?);
-        }
-        values.extend_from_slice(&z_cap.to_bytes()?);
-        for val in r_cap.values() {
-            values.extend_from_slice(&val.to_bytes()?);
+    /// Binds witness to credential signature and revocation registry identifier.
+    /// Binding is checked when sub proof is added by `ProofBuilder::add_sub_proof_request_with_rev_reg_id`.
+    ///
+    /// # Arguments
+    /// * `witness` - Witness for credential index in revocation registry.
+    /// * `credential_signature` - Processed credential signature.
+    /// * `rev_reg_id` - Revocation registry identifier.
+    pub fn bind_witness(witness: &Witness,
+                        credential_signature: &CredentialSignature,
+                        rev_reg_id: &str) -> Result<BoundWitness, IndyCryptoError> {
+        trace!("Prover::bind_witness: >>> witness: {:?}, credential_signature: {:?}, rev_reg_id: {:?}",
+               witness, credential_signature, rev_reg_id);
+
+        if credential_signature.r_credential.is_none() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential signature doesn't contain non revocation part")));
         }
 
-        let c = get_hash_as_int(&mut vec![values])?;
+        let bound_witness = BoundWitness {
+            witness: witness.clone(),
+            cred_signature_digest: credential_signature.digest()?,
+            rev_reg_id: rev_reg_id.to_owned()
+        };
 
-        let valid = key_correctness_proof.c.eq(&c);
+        trace!("Prover::bind_witness: <<< bound_witness: {:?}", bound_witness);
 
-        if !valid {
-            return Err(IndyCryptoError::InvalidStructure(format!("Invalid Credential key correctness proof")));
+        Ok(bound_witness)
+    }
+
+    /// Returns witness from bound witness after checking it was bound to the same
+    /// credential signature and revocation registry.
+    /// Returns `AnoncredsWitnessMismatch` error otherwise.
+    ///
+    /// # Arguments
+    /// * `bound_witness` - Bound witness.
+    /// * `credential_signature` - Processed credential signature.
+    /// * `rev_reg_id` - Revocation registry identifier.
+    pub fn unbind_checked(bound_witness: &BoundWitness,
+                          credential_signature: &CredentialSignature,
+                          rev_reg_id: &str) -> Result<Witness, IndyCryptoError> {
+        trace!("Prover::unbind_checked: >>> bound_witness: {:?}, credential_signature: {:?}, rev_reg_id: {:?}",
+               bound_witness, credential_signature, rev_reg_id);
+
+        if bound_witness.rev_reg_id != rev_reg_id {
+            return Err(IndyCryptoError::AnoncredsWitnessMismatch(
+                format!("Witness is bound to revocation registry '{}', but '{}' is used", bound_witness.rev_reg_id, rev_reg_id)));
         }
 
-        trace!("Prover::_check_credential_key_correctness_proof: <<<");
+        if bound_witness.cred_signature_digest != credential_signature.digest()? {
+            return Err(IndyCryptoError::AnoncredsWitnessMismatch(format!("Witness is bound to other credential signature")));
+        }
 
-        Ok(())
-    }
+        let witness = bound_witness.witness.clone();
 
-    fn _generate_blinded_primary_master_secret(p_pub_key: &CredentialPrimaryPublicKey,
-                                               master_secret: &MasterSecret) -> Result<PrimaryBlindedMasterSecretData, IndyCryptoError> {
-        trace!("Prover::_generate_blinded_primary_master_secret: >>> p_pub_key: {:?}, master_secret: {:?}", p_pub_key, master_secret);
+        trace!("Prover::unbind_checked: <<< witness: {:?}", witness);
 
-        let mut ctx = BigNumber::new_context()?;
-        let v_prime = bn_rand(LARGE_VPRIME)?;
+        Ok(witness)
+    }
 
-        let u = get_pedersen_commitment(&p_pub_key.s, &v_prime, &p_pub_key.rms,
-                                        &master_secret.ms, &p_pub_key.n, &mut ctx)?;
+    /// Checks that credential offer was created for given credential public key
+    /// and that the key is correct. Must be called before master secret is blinded with offer nonce.
+    ///
+    /// # Arguments
+    /// * `credential_offer` - Credential offer received from Issuer.
+    /// * `credential_pub_key` - Credential public key (e.g. fetched from ledger).
+    pub fn check_credential_offer(credential_offer: &CredentialOffer,
+                                  credential_pub_key: &CredentialPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("Prover::check_credential_offer: >>> credential_offer: {:?}, credential_pub_key: {:?}", credential_offer, credential_pub_key);
+
+        if credential_offer.key_fingerprint != credential_pub_key.fingerprint()? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential offer doesn't correspond to credential public key")));
+        }
 
-        let primary_blinded_master_secret = PrimaryBlindedMasterSecretData { u, v_prime };
+        Prover::_check_credential_key_correctness_proof(credential_pub_key, &credential_offer.key_correctness_proof)?;
 
-        trace!("Prover::_generate_blinded_primary_master_secret: <<< primary_blinded_master_secret: {:?}", primary_blinded_master_secret);
+        trace!("Prover::check_credential_offer: <<<");
+
+        Ok(())
+    }
+
+    /// Creates blinded master secret for PS credential signing.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - PS credential public key.
+    /// * `master_secret` - Master secret.
+    /// * `master_secret_blinding_nonce` - Nonce used for creation of blinded_master_secret_correctness_proof.
+    pub fn blind_master_secret_ps(credential_pub_key: &PsCredentialPublicKey,
+                                  master_secret: &MasterSecret,
+                                  master_secret_blinding_nonce: &Nonce) -> Result<(PsBlindedMasterSecret,
+                                                                                   PsMasterSecretBlindingData,
+                                                                                   PsBlindedMasterSecretCorrectnessProof), IndyCryptoError> {
+        trace!("Prover::blind_master_secret_ps: >>> credential_pub_key: {:?}, master_secret_blinding_nonce: {:?}",
+               credential_pub_key, master_secret_blinding_nonce);
+
+        let res = ps::blind_master_secret(credential_pub_key, master_secret, master_secret_blinding_nonce)?;
+
+        trace!("Prover::blind_master_secret_ps: <<< blinded_master_secret: {:?}, blinded_master_secret_correctness_proof: {:?}",
+               res.0, res.2);
+
+        Ok(res)
+    }
+
+    /// Unblinds PS credential signature and checks that it signs credential values and master secret.
+    ///
+    /// # Arguments
+    /// * `credential_signature` - PS credential signature received from Issuer.
+    /// * `credential_values` - Values of attributes.
+    /// * `master_secret_blinding_data` - Master secret blinding data.
+    /// * `master_secret` - Master secret.
+    /// * `credential_pub_key` - PS credential public key.
+    pub fn process_ps_credential_signature(credential_signature: &mut PsCredentialSignature,
+                                           credential_values: &CredentialValues,
+                                           master_secret_blinding_data: &PsMasterSecretBlindingData,
+                                           master_secret: &MasterSecret,
+                                           credential_pub_key: &PsCredentialPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("Prover::process_ps_credential_signature: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}",
+               credential_signature, credential_values, credential_pub_key);
+
+        ps::process_signature(credential_signature, credential_values, master_secret_blinding_data, master_secret, credential_pub_key)?;
+
+        trace!("Prover::process_ps_credential_signature: <<<");
+
+        Ok(())
+    }
+
+    /// Creates proof of PS credential that discloses revealed attributes of sub proof request.
+    /// Sub proof request must not contain predicates and non revocation interval.
+    ///
+    /// # Arguments
+    /// * `credential_signature` - Processed PS credential signature.
+    /// * `credential_values` - Values of attributes.
+    /// * `master_secret` - Master secret.
+    /// * `credential_pub_key` - PS credential public key.
+    /// * `sub_proof_request` - Requested attributes.
+    /// * `nonce` - Proof request nonce.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::{new_nonce, CredentialDefinition, CredentialSignatureBackend};
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// credential_schema_builder.add_attr("age").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (pub_key, priv_key) = match Issuer::new_credential_def_with_backend(&credential_schema, false, CredentialSignatureBackend::PS).unwrap() {
+    ///     CredentialDefinition::PS { pub_key, priv_key } => (pub_key, priv_key),
+    ///     _ => unreachable!()
+    /// };
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret_ps(&pub_key, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+    /// credential_values_builder.add_value("age", "28").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let mut credential_signature = Issuer::sign_ps_credential(&blinded_master_secret,
+    ///                                                           &blinded_master_secret_correctness_proof,
+    ///                                                           &master_secret_blinding_nonce,
+    ///                                                           &credential_values,
+    ///                                                           &pub_key,
+    ///                                                           &priv_key).unwrap();
+    ///
+    /// Prover::process_ps_credential_signature(&mut credential_signature,
+    ///                                         &credential_values,
+    ///                                         &master_secret_blinding_data,
+    ///                                         &master_secret,
+    ///                                         &pub_key).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("age").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let nonce = new_nonce().unwrap();
+    /// let proof = Prover::new_ps_proof(&credential_signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &nonce).unwrap();
+    ///
+    /// assert!(Verifier::verify_ps_proof(&proof, &sub_proof_request, &pub_key, &nonce).unwrap());
+    /// assert_eq!("28", proof.revealed_attrs()["age"].to_dec().unwrap());
+    /// ```
+    pub fn new_ps_proof(credential_signature: &PsCredentialSignature,
+                        credential_values: &CredentialValues,
+                        master_secret: &MasterSecret,
+                        credential_pub_key: &PsCredentialPublicKey,
+                        sub_proof_request: &SubProofRequest,
+                        nonce: &Nonce) -> Result<PsProof, IndyCryptoError> {
+        trace!("Prover::new_ps_proof: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+               sub_proof_request: {:?}, nonce: {:?}",
+               credential_signature, credential_values, credential_pub_key, sub_proof_request, nonce);
+
+        let proof = ps::prove(credential_signature, credential_values, master_secret, credential_pub_key, sub_proof_request, nonce)?;
+
+        trace!("Prover::new_ps_proof: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    /// Creates and returns proof builder.
+    ///
+    /// The purpose of proof builder is building of proof entity according to the given request .
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let _proof_builder = Prover::new_proof_builder();
+    pub fn new_proof_builder() -> Result<ProofBuilder, IndyCryptoError> {
+        Ok(ProofBuilder {
+            m1_tilde: bn_rand(LARGE_M1_TILDE)?,
+            init_proofs: Vec::new(),
+            non_ownership_init_proofs: Vec::new(),
+            verifiable_encryption_init_proofs: Vec::new(),
+            pseudonym_init_proofs: Vec::new(),
+            c_list: Vec::new(),
+            tau_list: Vec::new(),
+            challenge_hash: ChallengeHash::default(),
+            extra_m1_tildes: BTreeMap::new(),
+            batch_verifiable: false,
+            finalized: false,
+            observer: ProofObserverSlot::default()
+        })
+    }
+
+    fn _check_credential_key_correctness_proof(pub_key: &CredentialPublicKey,
+                                               key_correctness_proof: &CredentialKeyCorrectnessProof) -> Result<(), IndyCryptoError> {
+        trace!("Prover::_check_credential_key_correctness_proof: >>> pub_key: {:?}, key_correctness_proof: {:?}",
+               pub_key, key_correctness_proof);
+
+        let pr_pub_key = &pub_key.p_key;
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let z_inverse = pr_pub_key.z.inverse(&pr_pub_key.n, Some(&mut ctx))?;
+        let z_cap = get_pedersen_commitment(&z_inverse, &key_correctness_proof.c,
+                                            &pr_pub_key.s, &key_correctness_proof.xz_cap, &pr_pub_key.n, &mut ctx)?;
+
+        let (r_values, r_cap) = Prover::_calc_credential_key_r_cap(pr_pub_key, &key_correctness_proof.c, &key_correctness_proof.xr_cap, &mut ctx)?;
+
+        let mut values: Vec<u8> = Vec::new();
+
+        match key_correctness_proof.version {
+            0 => {
+                values.extend_from_slice(&pr_pub_key.z.to_bytes()?);
+                for val in r_values.iter() {
+                    values.extend_from_slice(&val.to_bytes()?);
+                }
+                values.extend_from_slice(&z_cap.to_bytes()?);
+                for val in r_cap.values() {
+                    values.extend_from_slice(&val.to_bytes()?);
+                }
+            }
+            CREDENTIAL_KEY_CORRECTNESS_PROOF_VERSION => {
+                let xrms_cap = key_correctness_proof.xrms_cap.as_ref()
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Field xrms_cap not found in key_correctness_proof")))?;
+                let xrctxt_cap = key_correctness_proof.xrctxt_cap.as_ref()
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Field xrctxt_cap not found in key_correctness_proof")))?;
+
+                let rms_inverse = pr_pub_key.rms.inverse(&pr_pub_key.n, Some(&mut ctx))?;
+                let rms_cap = get_pedersen_commitment(&rms_inverse, &key_correctness_proof.c,
+                                                      &pr_pub_key.s, xrms_cap, &pr_pub_key.n, &mut ctx)?;
+
+                let rctxt_inverse = pr_pub_key.rctxt.inverse(&pr_pub_key.n, Some(&mut ctx))?;
+                let rctxt_cap = get_pedersen_commitment(&rctxt_inverse, &key_correctness_proof.c,
+                                                        &pr_pub_key.s, xrctxt_cap, &pr_pub_key.n, &mut ctx)?;
+
+                values.extend_from_slice(&pr_pub_key.n.to_bytes()?);
+                values.extend_from_slice(&pr_pub_key.s.to_bytes()?);
+                values.extend_from_slice(&pr_pub_key.z.to_bytes()?);
+                values.extend_from_slice(&pr_pub_key.rms.to_bytes()?);
+                values.extend_from_slice(&pr_pub_key.rctxt.to_bytes()?);
+                for val in r_values.iter() {
+                    values.extend_from_slice(&val.to_bytes()?);
+                }
+                values.extend_from_slice(&z_cap.to_bytes()?);
+                values.extend_from_slice(&rms_cap.to_bytes()?);
+                values.extend_from_slice(&rctxt_cap.to_bytes()?);
+                for val in r_cap.values() {
+                    values.extend_from_slice(&val.to_bytes()?);
+                }
+
+                match (&pub_key.r_key, &key_correctness_proof.r_key_proof) {
+                    (&Some(ref r_pub_key), &Some(ref r_key_proof)) =>
+                        Prover::_check_credential_revocation_key_correctness_proof(r_pub_key, r_key_proof)?,
+                    (&None, &None) => {}
+                    (&Some(_), &None) =>
+                        return Err(IndyCryptoError::InvalidStructure(format!("Field r_key_proof not found in key_correctness_proof"))),
+                    (&None, &Some(_)) =>
+                        return Err(IndyCryptoError::InvalidStructure(format!("Field r_key not found in credential public key")))
+                }
+            }
+            version => {
+                return Err(IndyCryptoError::InvalidStructure(format!("Unsupported Credential key correctness proof version: {}", version)));
+            }
+        }
+
+        let c = get_hash_as_int(&mut vec![values])?;
+
+        let valid = key_correctness_proof.c.eq(&c);
+
+        if !valid {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid Credential key correctness proof")));
+        }
+
+        let mut covered_attrs: HashSet<&String> = key_correctness_proof.xr_cap.keys().collect();
+
+        for extension in key_correctness_proof.extensions.iter() {
+            let (r_values, r_cap) = Prover::_calc_credential_key_r_cap(pr_pub_key, &extension.c, &extension.xr_cap, &mut ctx)?;
+
+            let mut values: Vec<u8> = Vec::new();
+            for val in r_values.iter() {
+                values.extend_from_slice(&val.to_bytes()?);
+            }
+            for val in r_cap.values() {
+                values.extend_from_slice(&val.to_bytes()?);
+            }
+
+            if extension.c != get_hash_as_int(&mut vec![values])? {
+                return Err(IndyCryptoError::InvalidStructure(format!("Invalid Credential key extension proof")));
+            }
+
+            covered_attrs.extend(extension.xr_cap.keys());
+        }
+
+        for key in pr_pub_key.r.keys() {
+            if !covered_attrs.contains(key) {
+                return Err(IndyCryptoError::MissingAttribute { attr: key.to_string(), context: "key_correctness_proof.xr_cap".to_string() });
+            }
+        }
+
+        trace!("Prover::_check_credential_key_correctness_proof: <<<");
+
+        Ok(())
+    }
+
+    fn _check_credential_revocation_key_correctness_proof(r_pub_key: &CredentialRevocationPublicKey,
+                                                          r_key_proof: &CredentialRevocationKeyCorrectnessProof) -> Result<(), IndyCryptoError> {
+        trace!("Prover::_check_credential_revocation_key_correctness_proof: >>> r_pub_key: {:?}, r_key_proof: {:?}",
+               r_pub_key, r_key_proof);
+
+        let ch = bignum_to_group_element(&r_key_proof.c)?;
+
+        let pk_tilda = r_pub_key.g.mul(&r_key_proof.sk_cap)?.sub(&r_pub_key.pk.mul(&ch)?)?;
+        let y_tilda = r_pub_key.h_cap.mul(&r_key_proof.x_cap)?.sub(&r_pub_key.y.mul(&ch)?)?;
+
+        let c = CredentialRevocationKeyCorrectnessProof::challenge(r_pub_key, &pk_tilda, &y_tilda)?;
+
+        if r_key_proof.c != c {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid Credential revocation key correctness proof")));
+        }
+
+        trace!("Prover::_check_credential_revocation_key_correctness_proof: <<<");
+
+        Ok(())
+    }
+
+    fn _calc_credential_key_r_cap(pr_pub_key: &CredentialPrimaryPublicKey,
+                                  c: &BigNumber,
+                                  xr_cap: &BTreeMap<String, BigNumber>,
+                                  ctx: &mut BigNumberContext) -> Result<(Vec<BigNumber>, BTreeMap<String, BigNumber>), IndyCryptoError> {
+        let mut r_values: Vec<BigNumber> = Vec::new();
+        let mut r_cap: BTreeMap<String, BigNumber> = BTreeMap::new();
+
+        for (key, xr_cap_value) in xr_cap.iter() {
+            let r_value = pr_pub_key.r
+                .get(key)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: key.to_string(), context: "pr_pub_key.r".to_string() })?;
+
+            let r_inverse = r_value.inverse(&pr_pub_key.n, Some(ctx))?;
+            let val = get_pedersen_commitment(&r_inverse, c,
+                                              &pr_pub_key.s, &xr_cap_value, &pr_pub_key.n, ctx)?;
+
+            r_values.push(r_value.clone()?);
+            r_cap.insert(key.to_owned(), val);
+        }
+
+        Ok((r_values, r_cap))
+    }
+
+    fn _blind_master_secret(credential_pub_key: &CredentialPublicKey,
+                            credential_key_correctness_proof: &CredentialKeyCorrectnessProof,
+                            master_secret: Option<&MasterSecret>,
+                            master_secret_blinding_nonce: &Nonce) -> Result<(BlindedMasterSecret,
+                                                                             MasterSecretBlindingData,
+                                                                             BlindedMasterSecretCorrectnessProof), IndyCryptoError> {
+        Prover::_check_credential_key_correctness_proof(credential_pub_key, credential_key_correctness_proof)?;
+
+        let blinded_primary_master_secret =
+            Prover::_generate_blinded_primary_master_secret(&credential_pub_key.p_key, master_secret)?;
+
+        let blinded_revocation_master_secret = match credential_pub_key.r_key {
+            Some(ref r_pk) => Some(Prover::_generate_blinded_revocation_master_secret(r_pk)?),
+            _ => None
+        };
+
+        let blinded_master_secret_correctness_proof =
+            Prover::_new_blinded_master_secret_correctness_proof(&credential_pub_key.p_key,
+                                                                 &blinded_primary_master_secret,
+                                                                 &master_secret_blinding_nonce,
+                                                                 master_secret)?;
+
+        let blinded_master_secret = BlindedMasterSecret {
+            u: blinded_primary_master_secret.u,
+            ur: blinded_revocation_master_secret.as_ref().map(|d| d.ur)
+        };
+
+        let master_secret_blinding_factor = MasterSecretBlindingData {
+            v_prime: blinded_primary_master_secret.v_prime,
+            vr_prime: blinded_revocation_master_secret.map(|d| d.vr_prime)
+        };
+
+        Ok((blinded_master_secret, master_secret_blinding_factor, blinded_master_secret_correctness_proof))
+    }
+
+    fn _process_credential_signature(credential_signature: &mut CredentialSignature,
+                                     credential_values: &CredentialValues,
+                                     signature_correctness_proof: &SignatureCorrectnessProof,
+                                     master_secret_blinding_data: &MasterSecretBlindingData,
+                                     master_secret: Option<&MasterSecret>,
+                                     credential_pub_key: &CredentialPublicKey,
+                                     nonce: &Nonce,
+                                     rev_key_pub: Option<&RevocationKeyPublic>,
+                                     rev_reg: Option<&RevocationRegistry>,
+                                     witness: Option<&Witness>) -> Result<(), IndyCryptoError> {
+        Prover::_process_primary_credential(&mut credential_signature.p_credential, &master_secret_blinding_data.v_prime)?;
+
+        Prover::_check_signature_correctness_proof(&credential_signature.p_credential,
+                                                   credential_values,
+                                                   signature_correctness_proof,
+                                                   master_secret,
+                                                   &credential_pub_key.p_key,
+                                                   nonce)?;
+
+        if let (&mut Some(ref mut non_revocation_cred), Some(ref vr_prime), &Some(ref r_key),
+            Some(ref r_key_pub), Some(ref r_reg), Some(ref witness)) = (&mut credential_signature.r_credential,
+                                                                        master_secret_blinding_data.vr_prime,
+                                                                        &credential_pub_key.r_key,
+                                                                        rev_key_pub,
+                                                                        rev_reg,
+                                                                        witness) {
+            Prover::_process_non_revocation_credential(non_revocation_cred,
+                                                       vr_prime,
+                                                       &r_key,
+                                                       r_key_pub,
+                                                       r_reg,
+                                                       witness)?;
+        }
+
+        Ok(())
+    }
+
+    fn _generate_blinded_primary_master_secret(p_pub_key: &CredentialPrimaryPublicKey,
+                                               master_secret: Option<&MasterSecret>) -> Result<PrimaryBlindedMasterSecretData, IndyCryptoError> {
+        trace!("Prover::_generate_blinded_primary_master_secret: >>> p_pub_key: {:?}, master_secret: {:?}", p_pub_key, master_secret);
+
+        let mut ctx = BigNumber::new_context()?;
+        let v_prime = bn_rand(p_pub_key.security_profile()?.vprime_bits())?;
+
+        let u = match Prover::_master_secret_for_key(master_secret, p_pub_key)? {
+            Some(ms) => get_pedersen_commitment(&p_pub_key.s, &v_prime, &p_pub_key.rms, ms, &p_pub_key.n, &mut ctx)?,
+            None => p_pub_key.s.mod_exp(&v_prime, &p_pub_key.n, Some(&mut ctx))?
+        };
+
+        let primary_blinded_master_secret = PrimaryBlindedMasterSecretData { u, v_prime };
+
+        trace!("Prover::_generate_blinded_primary_master_secret: <<< primary_blinded_master_secret: {:?}", primary_blinded_master_secret);
 
         Ok(primary_blinded_master_secret)
     }
@@ -292,17 +1073,25 @@ impl Prover {
     fn _new_blinded_master_secret_correctness_proof(p_pub_key: &CredentialPrimaryPublicKey,
                                                     blinded_master_secret: &PrimaryBlindedMasterSecretData,
                                                     nonce: &BigNumber,
-                                                    master_secret: &MasterSecret) -> Result<BlindedMasterSecretCorrectnessProof, IndyCryptoError> {
+                                                    master_secret: Option<&MasterSecret>) -> Result<BlindedMasterSecretCorrectnessProof, IndyCryptoError> {
         trace!("Prover::_new_blinded_master_secret_correctness_proof: >>> p_pub_key: {:?}, blinded_master_secret: {:?}, nonce: {:?}, master_secret: {:?}",
                blinded_master_secret, nonce, p_pub_key, master_secret);
 
-        let mut ctx = BigNumber::new_context()?;
+        let group = BnGroup::new(p_pub_key.n.clone()?, LARGE_VPRIME_TILDE)?;
 
-        let ms_tilde = bn_rand(LARGE_MTILDE)?;
-        let v_dash_tilde = bn_rand(LARGE_VPRIME_TILDE)?;
+        let ms = match Prover::_master_secret_for_key(master_secret, p_pub_key)? {
+            Some(ms) => ms,
+            None => return Prover::_new_blinding_factor_correctness_proof(p_pub_key, blinded_master_secret, nonce, &group)
+        };
+
+        let mut statement: Statement<BnGroup> = Statement::new(2);
+        statement.add_relation(blinded_master_secret.u.clone()?,
+                               vec![(p_pub_key.rms.clone()?, 0), (p_pub_key.s.clone()?, 1)])?;
+
+        let tildes = vec![bn_rand(LARGE_MTILDE)?, bn_rand(LARGE_VPRIME_TILDE)?];
+
+        let u_tilde = statement.commitments(&group, &tildes)?.remove(0);
 
-        let u_tilde = get_pedersen_commitment(&p_pub_key.rms, &ms_tilde, &p_pub_key.s,
-                                              &v_dash_tilde, &p_pub_key.n, &mut ctx)?;
         let mut values: Vec<u8> = Vec::new();
         values.extend_from_slice(&blinded_master_secret.u.to_bytes()?);
         values.extend_from_slice(&u_tilde.to_bytes()?);
@@ -310,13 +1099,9 @@ impl Prover {
 
         let c = get_hash_as_int(&mut vec![values])?;
 
-        let v_dash_cap =
-            c.mul(&blinded_master_secret.v_prime, Some(&mut ctx))?
-                .add(&v_dash_tilde)?;
-
-        let ms_cap =
-            c.mul(&master_secret.ms, Some(&mut ctx))?
-                .add(&ms_tilde)?;
+        let mut caps = statement.responses(&group, &[ms, &blinded_master_secret.v_prime], &tildes, &c)?;
+        let v_dash_cap = caps.remove(1);
+        let ms_cap = caps.remove(0);
 
         let blinded_primary_master_secret_correctness_proof = BlindedMasterSecretCorrectnessProof { c, v_dash_cap, ms_cap };
 
@@ -326,6 +1111,42 @@ impl Prover {
         Ok(blinded_primary_master_secret_correctness_proof)
     }
 
+    // Proves knowledge of blinding factor for keys without master secret, response for master secret is left zero.
+    fn _new_blinding_factor_correctness_proof(p_pub_key: &CredentialPrimaryPublicKey,
+                                              blinded_master_secret: &PrimaryBlindedMasterSecretData,
+                                              nonce: &BigNumber,
+                                              group: &BnGroup) -> Result<BlindedMasterSecretCorrectnessProof, IndyCryptoError> {
+        let mut statement: Statement<BnGroup> = Statement::new(1);
+        statement.add_relation(blinded_master_secret.u.clone()?, vec![(p_pub_key.s.clone()?, 0)])?;
+
+        let tildes = vec![bn_rand(LARGE_VPRIME_TILDE)?];
+
+        let u_tilde = statement.commitments(group, &tildes)?.remove(0);
+
+        let mut values: Vec<u8> = Vec::new();
+        values.extend_from_slice(&blinded_master_secret.u.to_bytes()?);
+        values.extend_from_slice(&u_tilde.to_bytes()?);
+        values.extend_from_slice(&nonce.to_bytes()?);
+
+        let c = get_hash_as_int(&mut vec![values])?;
+
+        let v_dash_cap = statement.responses(group, &[&blinded_master_secret.v_prime], &tildes, &c)?.remove(0);
+
+        Ok(BlindedMasterSecretCorrectnessProof { c, v_dash_cap, ms_cap: BigNumber::new()? })
+    }
+
+    // Returns master secret signed into credentials of the key, or None if key is created without master secret.
+    fn _master_secret_for_key<'a>(master_secret: Option<&'a MasterSecret>,
+                                  p_pub_key: &CredentialPrimaryPublicKey) -> Result<Option<&'a BigNumber>, IndyCryptoError> {
+        if p_pub_key.without_master_secret {
+            return Ok(None);
+        }
+
+        master_secret
+            .map(|master_secret| Some(&master_secret.ms))
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Credential public key requires master secret")))
+    }
+
     fn _process_primary_credential(p_cred: &mut PrimaryCredentialSignature,
                                    v_prime: &BigNumber) -> Result<(), IndyCryptoError> {
         trace!("Prover::_process_primary_credential: >>> p_cred: {:?}, v_prime: {:?}", p_cred, v_prime);
@@ -358,7 +1179,7 @@ impl Prover {
     fn _check_signature_correctness_proof(p_cred_sig: &PrimaryCredentialSignature,
                                           cred_values: &CredentialValues,
                                           signature_correctness_proof: &SignatureCorrectnessProof,
-                                          master_secret: &MasterSecret,
+                                          master_secret: Option<&MasterSecret>,
                                           p_pub_key: &CredentialPrimaryPublicKey,
                                           nonce: &Nonce) -> Result<(), IndyCryptoError> {
         trace!("Prover::_check_signature_correctness_proof: >>> p_cred_sig: {:?}, master_secret: {:?}, p_pub_key: {:?}, signature_correctness_proof: {:?}, \
@@ -370,22 +1191,7 @@ impl Prover {
             return Err(IndyCryptoError::InvalidStructure(format!("Invalid Signature correctness proof")));
         }
 
-        let mut generators_and_exponents = Vec::new();
-        generators_and_exponents.push((&p_pub_key.s, &p_cred_sig.v));
-        generators_and_exponents.push((&p_pub_key.rms, &master_secret.ms));
-        generators_and_exponents.push((&p_pub_key.rctxt, &p_cred_sig.m_2));
-
-        for (key, value) in cred_values.attrs_values.iter() {
-            let pk_r = p_pub_key.r
-                .get(key)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", key)))?;
-
-            generators_and_exponents.push((&pk_r, &value));
-        }
-
-        let rx = get_exponentiated_generators(generators_and_exponents, &p_pub_key.n, &mut ctx)?;
-
-        let q = p_pub_key.z.mod_div(&rx, &p_pub_key.n)?;
+        let q = Prover::_calc_primary_signature_q(p_cred_sig, cred_values, master_secret, p_pub_key, &mut ctx)?;
 
         let expected_q = p_cred_sig.a.mod_exp(&p_cred_sig.e, &p_pub_key.n, Some(&mut ctx))?;
 
@@ -393,31 +1199,171 @@ impl Prover {
             return Err(IndyCryptoError::InvalidStructure(format!("Invalid Signature correctness proof")));
         }
 
-        let degree = signature_correctness_proof.c.add(
-            &signature_correctness_proof.se.mul(&p_cred_sig.e, Some(&mut ctx))?
-        )?;
+        Prover::_check_signature_correctness_proof_challenge(p_cred_sig, &q, signature_correctness_proof, p_pub_key, nonce, &mut ctx)?;
 
-        let a_cap = p_cred_sig.a.mod_exp(&degree, &p_pub_key.n, Some(&mut ctx))?;
+        trace!("Prover::_check_signature_correctness_proof: <<<");
 
-        let mut values: Vec<u8> = Vec::new();
-        values.extend_from_slice(&q.to_bytes()?);
-        values.extend_from_slice(&p_cred_sig.a.to_bytes()?);
-        values.extend_from_slice(&a_cap.to_bytes()?);
-        values.extend_from_slice(&nonce.to_bytes()?);
+        Ok(())
+    }
 
-        let c = get_hash_as_int(&mut vec![values])?;
+    /// Checks signature correctness proofs of credentials signed with the same key.
+    /// Signature equations A^e * S^v * Rms^ms * Rctxt^m2 * R1^m1 * ... * Rl^ml = Z of all credentials are combined
+    /// with random exponents into one equation, so bases of public key are exponentiated once for whole batch.
+    fn _check_signature_correctness_proofs(credentials: &[(&PrimaryCredentialSignature, &CredentialValues, &SignatureCorrectnessProof, &Nonce)],
+                                           master_secret: Option<&MasterSecret>,
+                                           p_pub_key: &CredentialPrimaryPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("Prover::_check_signature_correctness_proofs: >>> credentials: {:?}, master_secret: {:?}, p_pub_key: {:?}",
+               credentials, master_secret, p_pub_key);
 
-        let valid = signature_correctness_proof.c.eq(&c);
+        let mut ctx = BigNumber::new_context()?;
 
-        if !valid {
+        let mut a_e_product = BigNumber::from_u32(1)?;
+        let mut z_exp = BigNumber::from_u32(0)?;
+        let mut s_exp = BigNumber::from_u32(0)?;
+        let mut rctxt_exp = BigNumber::from_u32(0)?;
+        let mut r_exps: BTreeMap<String, BigNumber> = BTreeMap::new();
+
+        for &(p_cred_sig, cred_values, signature_correctness_proof, nonce) in credentials.iter() {
+            if !p_cred_sig.e.is_prime(Some(&mut ctx))? {
+                return Err(IndyCryptoError::InvalidStructure(format!("Invalid Signature correctness proof")));
+            }
+
+            let q = p_cred_sig.a.mod_exp(&p_cred_sig.e, &p_pub_key.n, Some(&mut ctx))?;
+
+            Prover::_check_signature_correctness_proof_challenge(p_cred_sig, &q, signature_correctness_proof, p_pub_key, nonce, &mut ctx)?;
+
+            let r = bn_rand(LARGE_BATCH_EXP)?;
+
+            a_e_product = a_e_product.mod_mul(&q.mod_exp(&r, &p_pub_key.n, Some(&mut ctx))?, &p_pub_key.n, Some(&mut ctx))?;
+            z_exp = z_exp.add(&r)?;
+            s_exp = s_exp.add(&p_cred_sig.v.mul(&r, Some(&mut ctx))?)?;
+            rctxt_exp = rctxt_exp.add(&p_cred_sig.m_2.mul(&r, Some(&mut ctx))?)?;
+
+            for (key, value) in cred_values.attrs_values.iter() {
+                let exp = value.mul(&r, Some(&mut ctx))?;
+                let exp = match r_exps.get(key) {
+                    Some(acc) => acc.add(&exp)?,
+                    None => exp
+                };
+                r_exps.insert(key.clone(), exp);
+            }
+        }
+
+        let ms_exp = match Prover::_master_secret_for_key(master_secret, p_pub_key)? {
+            Some(ms) => Some(ms.mul(&z_exp, Some(&mut ctx))?),
+            None => None
+        };
+
+        let mut generators_and_exponents = Vec::new();
+        generators_and_exponents.push((&p_pub_key.s, &s_exp));
+        if let Some(ref ms_exp) = ms_exp {
+            generators_and_exponents.push((&p_pub_key.rms, ms_exp));
+        }
+        generators_and_exponents.push((&p_pub_key.rctxt, &rctxt_exp));
+
+        for (key, exp) in r_exps.iter() {
+            let pk_r = p_pub_key.r
+                .get(key)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: key.to_string(), context: "pk.r".to_string() })?;
+
+            generators_and_exponents.push((&pk_r, &exp));
+        }
+
+        let rx = get_exponentiated_generators(generators_and_exponents, &p_pub_key.n, &mut ctx)?;
+
+        let lhs = a_e_product.mod_mul(&rx, &p_pub_key.n, Some(&mut ctx))?;
+        let rhs = p_pub_key.z.mod_exp(&z_exp, &p_pub_key.n, Some(&mut ctx))?;
+
+        if lhs != rhs {
             return Err(IndyCryptoError::InvalidStructure(format!("Invalid Signature correctness proof")));
         }
 
-        trace!("Prover::_check_signature_correctness_proof: <<<");
+        trace!("Prover::_check_signature_correctness_proofs: <<<");
+
+        Ok(())
+    }
+
+    /// Checks challenge of signature correctness proof for Q = A^e.
+    fn _check_signature_correctness_proof_challenge(p_cred_sig: &PrimaryCredentialSignature,
+                                                    q: &BigNumber,
+                                                    signature_correctness_proof: &SignatureCorrectnessProof,
+                                                    p_pub_key: &CredentialPrimaryPublicKey,
+                                                    nonce: &Nonce,
+                                                    ctx: &mut BigNumberContext) -> Result<(), IndyCryptoError> {
+        let degree = signature_correctness_proof.c.add(
+            &signature_correctness_proof.se.mul(&p_cred_sig.e, Some(ctx))?
+        )?;
+
+        let a_cap = p_cred_sig.a.mod_exp(&degree, &p_pub_key.n, Some(ctx))?;
+
+        let mut values: Vec<u8> = Vec::new();
+        values.extend_from_slice(&q.to_bytes()?);
+        values.extend_from_slice(&p_cred_sig.a.to_bytes()?);
+        values.extend_from_slice(&a_cap.to_bytes()?);
+        values.extend_from_slice(&nonce.to_bytes()?);
+
+        let c = get_hash_as_int(&mut vec![values])?;
+
+        if !signature_correctness_proof.c.eq(&c) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid Signature correctness proof")));
+        }
 
         Ok(())
     }
 
+    fn _check_primary_credential_signature(p_cred_sig: &PrimaryCredentialSignature,
+                                           cred_values: &CredentialValues,
+                                           master_secret: Option<&MasterSecret>,
+                                           p_pub_key: &CredentialPrimaryPublicKey) -> Result<bool, IndyCryptoError> {
+        trace!("Prover::_check_primary_credential_signature: >>> p_cred_sig: {:?}, cred_values: {:?}, master_secret: {:?}, p_pub_key: {:?}",
+               p_cred_sig, cred_values, master_secret, p_pub_key);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let e_start = BigNumber::from_u32(2)?.exp(&BigNumber::from_u32(LARGE_E_START)?, Some(&mut ctx))?;
+        let e_end = BigNumber::from_u32(2)?
+            .exp(&BigNumber::from_u32(LARGE_E_END_RANGE)?, Some(&mut ctx))?
+            .add(&e_start)?;
+
+        let valid = if p_cred_sig.e < e_start || p_cred_sig.e > e_end || !p_cred_sig.e.is_prime(Some(&mut ctx))? {
+            false
+        } else {
+            let q = Prover::_calc_primary_signature_q(p_cred_sig, cred_values, master_secret, p_pub_key, &mut ctx)?;
+            let expected_q = p_cred_sig.a.mod_exp(&p_cred_sig.e, &p_pub_key.n, Some(&mut ctx))?;
+            q == expected_q
+        };
+
+        trace!("Prover::_check_primary_credential_signature: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Calculates Z / (S^v * Rms^ms * Rctxt^m2 * R1^m1 * ... * Rl^ml) that equals to A^e for valid signature.
+    fn _calc_primary_signature_q(p_cred_sig: &PrimaryCredentialSignature,
+                                 cred_values: &CredentialValues,
+                                 master_secret: Option<&MasterSecret>,
+                                 p_pub_key: &CredentialPrimaryPublicKey,
+                                 ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let mut generators_and_exponents = Vec::new();
+        generators_and_exponents.push((&p_pub_key.s, &p_cred_sig.v));
+        if let Some(ms) = Prover::_master_secret_for_key(master_secret, p_pub_key)? {
+            generators_and_exponents.push((&p_pub_key.rms, ms));
+        }
+        generators_and_exponents.push((&p_pub_key.rctxt, &p_cred_sig.m_2));
+
+        for (key, value) in cred_values.attrs_values.iter() {
+            let pk_r = p_pub_key.r
+                .get(key)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: key.to_string(), context: "pk.r".to_string() })?;
+
+            generators_and_exponents.push((&pk_r, &value));
+        }
+
+        let rx = get_exponentiated_generators(generators_and_exponents, &p_pub_key.n, ctx)?;
+
+        p_pub_key.z.mod_div(&rx, &p_pub_key.n)
+    }
+
     fn _test_witness_signature(r_cred: &NonRevocationCredentialSignature,
                                cred_rev_pub_key: &CredentialRevocationPublicKey,
                                rev_key_pub: &RevocationKeyPublic,
@@ -427,61 +1373,785 @@ impl Prover {
         trace!("Prover::_test_witness_signature: >>> r_cred: {:?}, cred_rev_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}, r_cnxt_m2: {:?}",
                r_cred, cred_rev_pub_key, rev_key_pub, rev_reg, r_cnxt_m2);
 
-        let z_calc = Pair::pair(&r_cred.witness_signature.g_i, &rev_reg.accum)?
-            .mul(&Pair::pair(&cred_rev_pub_key.g, &witness.omega)?.inverse()?)?;
+        let z_calc = Pair::pair(&r_cred.witness_signature.g_i, &rev_reg.accum)?
+            .mul(&Pair::pair(&cred_rev_pub_key.g, &witness.omega)?.inverse()?)?;
+
+        if z_calc != rev_key_pub.witness_pairing()? {
+            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
+        }
+        let pair_gg_calc = Pair::pair(&cred_rev_pub_key.pk.add(&r_cred.g_i)?, &r_cred.witness_signature.sigma_i)?;
+        let pair_gg = Pair::pair(&cred_rev_pub_key.g, &cred_rev_pub_key.g_dash)?;
+
+        if pair_gg_calc != pair_gg {
+            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
+        }
+
+        let m2 = GroupOrderElement::from_bytes(&r_cnxt_m2.to_bytes()?)?;
+
+        let pair_h1 = Pair::pair(&r_cred.sigma, &cred_rev_pub_key.y.add(&cred_rev_pub_key.h_cap.mul(&r_cred.c)?)?)?;
+        let pair_h2 = Pair::pair(
+            &cred_rev_pub_key.h0
+                .add(&cred_rev_pub_key.h1.mul(&m2)?)?
+                .add(&cred_rev_pub_key.h2.mul(&r_cred.vr_prime_prime)?)?
+                .add(&r_cred.g_i)?,
+            &cred_rev_pub_key.h_cap
+        )?;
+
+        if pair_h1 != pair_h2 {
+            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
+        }
+
+        trace!("Prover::_test_witness_signature: <<<");
+
+        Ok(())
+    }
+}
+
+// Length of seed of RNG each predicate proof is initialized with.
+const PREDICATE_SEED_LEN: usize = 32;
+
+enum PredicateInitProof {
+    GE(PrimaryPredicateGEInitProof),
+    Range(PrimaryPredicateRangeInitProof)
+}
+
+/// Builds proof for sub proof requests.
+///
+/// Intermediate state of builder (after `add_sub_proof_request` calls) can be serialized with `to_json`
+/// and resumed later with `from_json`, so commitments for already added sub proofs aren't computed again.
+/// Serialized state contains credential values and blinding factors, so it must be kept as secret as credentials.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProofBuilder {
+    pub m1_tilde: BigNumber,
+    pub init_proofs: Vec<InitProof>,
+    pub non_ownership_init_proofs: Vec<NonOwnershipInitProof>,
+    pub verifiable_encryption_init_proofs: Vec<VerifiableEncryptionInitProof>,
+    pub pseudonym_init_proofs: Vec<PseudonymInitProof>,
+    pub c_list: Vec<Vec<u8>>,
+    pub tau_list: Vec<Vec<u8>>,
+    #[serde(default)]
+    pub challenge_hash: ChallengeHash,
+    /// Blinding factors of additional master secrets (`m1_tilde` is used for the first one).
+    #[serde(default)]
+    pub extra_m1_tildes: BTreeMap<usize, BigNumber>,
+    #[serde(default)]
+    pub batch_verifiable: bool,
+    /// Set when proof is finalized with external challenge. Blinding factors must not be used
+    /// for another challenge, as responses for two challenges disclose hidden values.
+    #[serde(default)]
+    finalized: bool,
+    #[serde(skip)]
+    observer: ProofObserverSlot,
+}
+
+impl JsonEncodable for ProofBuilder {}
+
+impl<'a> JsonDecodable<'a> for ProofBuilder {}
+
+impl ProofBuilder {
+    /// Adds sub proof request to proof builder which will be used fo building of proof.
+    /// Part of proof request related to a particular schema-key.
+    /// The order of sub-proofs is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Proof builder.
+    /// * `sub_proof_request` -Requested attributes and predicates.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_signature` - Credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_reg_pub` - (Optional) Revocation registry public.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    /// ```
+    pub fn add_sub_proof_request(&mut self,
+                                 sub_proof_request: &SubProofRequest,
+                                 credential_schema: &CredentialSchema,
+                                 credential_signature: &CredentialSignature,
+                                 credential_values: &CredentialValues,
+                                 credential_pub_key: &CredentialPublicKey,
+                                 rev_reg: Option<&RevocationRegistry>,
+                                 witness: Option<&Witness>) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_sub_proof_request: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+        rev_reg: {:?}, sub_proof_request: {:?}, credential_schema: {:?}",
+               credential_signature, credential_values, credential_pub_key, rev_reg, sub_proof_request, credential_schema);
+
+        self._add_sub_proof_request(sub_proof_request, credential_schema, credential_signature, credential_values,
+                                    credential_pub_key, rev_reg, witness, None, None, 0)?;
+
+        trace!("ProofBuilder::add_sub_proof_request: <<<");
+
+        Ok(())
+    }
+
+    /// Adds sub proof request with non-revocation proof built against revocation registry state at given timestamp.
+    /// Timestamp is included into proof, so Verifier can check the proof against registry state it corresponds to.
+    /// If sub proof request contains non-revoked interval, timestamp must belong to it.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_signature` - Credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_reg` - Revocation registry state at `timestamp`.
+    /// * `witness` - Witness that corresponds to `rev_reg`.
+    /// * `timestamp` - Timestamp of revocation registry state (seconds since epoch).
+    pub fn add_sub_proof_request_with_timestamp(&mut self,
+                                                sub_proof_request: &SubProofRequest,
+                                                credential_schema: &CredentialSchema,
+                                                credential_signature: &CredentialSignature,
+                                                credential_values: &CredentialValues,
+                                                credential_pub_key: &CredentialPublicKey,
+                                                rev_reg: &RevocationRegistry,
+                                                witness: &Witness,
+                                                timestamp: u64) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_sub_proof_request_with_timestamp: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+        rev_reg: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, timestamp: {:?}",
+               credential_signature, credential_values, credential_pub_key, rev_reg, sub_proof_request, credential_schema, timestamp);
+
+        self._add_sub_proof_request(sub_proof_request, credential_schema, credential_signature, credential_values,
+                                    credential_pub_key, Some(rev_reg), Some(witness), Some(timestamp), None, 0)?;
+
+        trace!("ProofBuilder::add_sub_proof_request_with_timestamp: <<<");
+
+        Ok(())
+    }
+
+    /// Adds sub proof request with non-revocation proof built against one of several revocation registries
+    /// of credential definition. Registry identifier is included into proof, so Verifier can check the proof
+    /// against matching registry (see `ProofVerifier::add_sub_proof_request_with_rev_regs`).
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_signature` - Credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_reg_id` - Identifier of revocation registry credential was issued in.
+    /// * `rev_reg` - Revocation registry.
+    /// * `bound_witness` - Witness that corresponds to `rev_reg`, bound to `credential_signature` and `rev_reg_id`
+    ///   (see `Prover::bind_witness`). Returns `AnoncredsWitnessMismatch` error if it is bound to other ones.
+    /// * `timestamp` - Timestamp of revocation registry state (seconds since epoch), required if sub proof request
+    ///   contains non-revoked interval.
+    pub fn add_sub_proof_request_with_rev_reg_id(&mut self,
+                                                 sub_proof_request: &SubProofRequest,
+                                                 credential_schema: &CredentialSchema,
+                                                 credential_signature: &CredentialSignature,
+                                                 credential_values: &CredentialValues,
+                                                 credential_pub_key: &CredentialPublicKey,
+                                                 rev_reg_id: &str,
+                                                 rev_reg: &RevocationRegistry,
+                                                 bound_witness: &BoundWitness,
+                                                 timestamp: Option<u64>) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_sub_proof_request_with_rev_reg_id: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+        rev_reg_id: {:?}, rev_reg: {:?}, bound_witness: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, timestamp: {:?}",
+               credential_signature, credential_values, credential_pub_key, rev_reg_id, rev_reg, bound_witness, sub_proof_request, credential_schema, timestamp);
+
+        let witness = Prover::unbind_checked(bound_witness, credential_signature, rev_reg_id)?;
+
+        self._add_sub_proof_request(sub_proof_request, credential_schema, credential_signature, credential_values,
+                                    credential_pub_key, Some(rev_reg), Some(&witness), timestamp, Some(rev_reg_id), 0)?;
+
+        trace!("ProofBuilder::add_sub_proof_request_with_rev_reg_id: <<<");
+
+        Ok(())
+    }
+
+    /// Adds sub proof request for credential bound to one of several master secrets of prover.
+    /// Sub proofs added with other methods are bound to the first master secret (index 0).
+    /// Master secrets are passed to `ProofBuilder::finalize_with_master_secrets` in the order of indices.
+    /// Pseudonyms are always bound to the first master secret.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_signature` - Credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_reg` - (Optional) Revocation registry.
+    /// * `witness` - (Optional) Witness.
+    /// * `master_secret_idx` - Index of master secret credential is bound to.
+    pub fn add_sub_proof_request_with_master_secret(&mut self,
+                                                    sub_proof_request: &SubProofRequest,
+                                                    credential_schema: &CredentialSchema,
+                                                    credential_signature: &CredentialSignature,
+                                                    credential_values: &CredentialValues,
+                                                    credential_pub_key: &CredentialPublicKey,
+                                                    rev_reg: Option<&RevocationRegistry>,
+                                                    witness: Option<&Witness>,
+                                                    master_secret_idx: usize) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_sub_proof_request_with_master_secret: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+        rev_reg: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, master_secret_idx: {:?}",
+               credential_signature, credential_values, credential_pub_key, rev_reg, sub_proof_request, credential_schema, master_secret_idx);
+
+        self._add_sub_proof_request(sub_proof_request, credential_schema, credential_signature, credential_values,
+                                    credential_pub_key, rev_reg, witness, None, None, master_secret_idx)?;
+
+        trace!("ProofBuilder::add_sub_proof_request_with_master_secret: <<<");
+
+        Ok(())
+    }
+
+    /// Adds request to prove possession of credential signed with `credential_pub_key`
+    /// without revealing any attribute and proving any predicate (e.g. simple "is a member" check).
+    /// Only primary proof is built: revocation status of credential isn't proved.
+    /// Verifier should add the same request with `ProofVerifier::add_possession_only`.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_signature` - Credential signature.
+    /// * `credential_values` - Credential values.
+    /// * `credential_pub_key` - Credential public key.
+    pub fn add_possession_only(&mut self,
+                               credential_schema: &CredentialSchema,
+                               credential_signature: &CredentialSignature,
+                               credential_values: &CredentialValues,
+                               credential_pub_key: &CredentialPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_possession_only: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
+        credential_schema: {:?}",
+               credential_signature, credential_values, credential_pub_key, credential_schema);
+
+        let sub_proof_request = SubProofRequestBuilder::new()?.finalize()?;
+        let credential_values = credential_schema.canonicalize_credential_values(credential_values)?;
+
+        let primary_init_proof = ProofBuilder::_init_primary_proof(&credential_pub_key.p_key,
+                                                                   &credential_signature.p_credential,
+                                                                   &credential_values,
+                                                                   &credential_schema,
+                                                                   &sub_proof_request,
+                                                                   &self.m1_tilde,
+                                                                   None)?;
+
+        let sub_proof_idx = self.init_proofs.len();
+        self.observer.notify(ProofEvent::SubProofStarted { sub_proof_idx });
+
+        let tau_list = primary_init_proof.as_tau_list(&credential_pub_key.p_key.n)?;
+        self.observer.notify(ProofEvent::TValuesComputed { sub_proof_idx, count: tau_list.len() });
+
+        self.c_list.extend_from_slice(&primary_init_proof.as_c_list()?);
+        self.tau_list.extend_from_slice(&tau_list);
+
+        self.init_proofs.push(InitProof {
+            credential_pub_key: credential_pub_key.p_key.clone()?,
+            primary_init_proof,
+            non_revoc_init_proof: None,
+            credential_values,
+            sub_proof_request,
+            credential_schema: credential_schema.clone(),
+            key_id: None,
+            master_secret_idx: 0
+        });
+
+        trace!("ProofBuilder::add_possession_only: <<<");
+
+        Ok(())
+    }
+
+    fn _add_sub_proof_request(&mut self,
+                              sub_proof_request: &SubProofRequest,
+                              credential_schema: &CredentialSchema,
+                              credential_signature: &CredentialSignature,
+                              credential_values: &CredentialValues,
+                              credential_pub_key: &CredentialPublicKey,
+                              rev_reg: Option<&RevocationRegistry>,
+                              witness: Option<&Witness>,
+                              timestamp: Option<u64>,
+                              rev_reg_id: Option<&str>,
+                              master_secret_idx: usize) -> Result<(), IndyCryptoError> {
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        let credential_values = &credential_schema.canonicalize_credential_values(credential_values)?;
+
+        ProofBuilder::_check_add_sub_proof_request_params_consistency(credential_values, sub_proof_request, credential_schema)?;
+
+        let sub_proof_idx = self.init_proofs.len();
+        let tau_list_len = self.tau_list.len();
+        self.observer.notify(ProofEvent::SubProofStarted { sub_proof_idx });
+
+        let mut non_revoc_init_proof = None;
+        let mut m2_tilde: Option<BigNumber> = None;
+
+        if let (&Some(ref interval), &Some(_), &Some(_)) = (&sub_proof_request.non_revoked,
+                                                            &credential_signature.r_credential,
+                                                            &credential_pub_key.r_key) {
+            match timestamp {
+                Some(timestamp) if interval.contains(timestamp) => {}
+                Some(timestamp) =>
+                    return Err(IndyCryptoError::InvalidStructure(format!("Revocation registry state timestamp {} is out of requested interval", timestamp))),
+                None =>
+                    return Err(IndyCryptoError::InvalidStructure(format!("Sub proof request requires timestamp of revocation registry state")))
+            }
+        }
+
+        if let (&Some(ref r_cred), &Some(ref r_reg), &Some(ref r_pub_key), &Some(ref witness)) = (&credential_signature.r_credential,
+                                                                                                  &rev_reg,
+                                                                                                  &credential_pub_key.r_key,
+                                                                                                  &witness) {
+            let mut proof = ProofBuilder::_init_non_revocation_proof(&r_cred,
+                                                                     &r_reg,
+                                                                     &r_pub_key,
+                                                                     &witness)?;
+            proof.timestamp = timestamp;
+            proof.rev_reg_id = rev_reg_id.map(str::to_owned);
+
+            self.c_list.extend_from_slice(&proof.as_c_list()?);
+            self.tau_list.extend_from_slice(&proof.as_tau_list()?);
+            m2_tilde = Some(group_element_to_bignum(&proof.tau_list_params.m2)?);
+            non_revoc_init_proof = Some(proof);
+        }
+
+        let m1_tilde = self._get_m1_tilde(master_secret_idx)?;
+
+        let primary_init_proof = ProofBuilder::_init_primary_proof(&credential_pub_key.p_key,
+                                                                   &credential_signature.p_credential,
+                                                                   &credential_values,
+                                                                   &credential_schema,
+                                                                   &sub_proof_request,
+                                                                   &m1_tilde,
+                                                                   m2_tilde)?;
+
+        self.c_list.extend_from_slice(&primary_init_proof.as_c_list()?);
+        self.tau_list.extend_from_slice(&primary_init_proof.as_tau_list(&credential_pub_key.p_key.n)?);
+        self.observer.notify(ProofEvent::TValuesComputed { sub_proof_idx, count: self.tau_list.len() - tau_list_len });
+
+        let init_proof = InitProof {
+            credential_pub_key: credential_pub_key.p_key.clone()?,
+            primary_init_proof,
+            non_revoc_init_proof,
+            credential_values: credential_values.clone()?,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            key_id: None,
+            master_secret_idx
+        };
+        self.init_proofs.push(init_proof);
+
+        Ok(())
+    }
+
+    // Each master secret has own blinding factor, so responses for different secrets don't disclose their relation.
+    fn _get_m1_tilde(&mut self, master_secret_idx: usize) -> Result<BigNumber, IndyCryptoError> {
+        if master_secret_idx == 0 {
+            return self.m1_tilde.clone();
+        }
+
+        if !self.extra_m1_tildes.contains_key(&master_secret_idx) {
+            self.extra_m1_tildes.insert(master_secret_idx, bn_rand(LARGE_M1_TILDE)?);
+        }
+
+        self.extra_m1_tildes[&master_secret_idx].clone()
+    }
+
+    /// Adds request to prove that holder isn't enrolled to non-ownership registry.
+    /// Registry member of holder is hidden attribute of sub proof credential (see `Issuer::new_non_ownership_member`)
+    /// and only commitments to non-membership witness are revealed, so proofs for the same registry aren't linkable.
+    /// The order of non-ownership requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests) the attribute belongs to.
+    /// * `attr` - Name of hidden attribute that contains registry member.
+    /// * `registry` - Non-ownership registry.
+    /// * `witness` - Non-ownership witness issued for the current registry state.
+    pub fn add_non_ownership_request(&mut self,
+                                     sub_proof_idx: usize,
+                                     attr: &str,
+                                     registry: &NonOwnershipRegistry,
+                                     witness: &NonOwnershipWitness) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_non_ownership_request: >>> sub_proof_idx: {:?}, attr: {:?}, registry: {:?}, witness: {:?}",
+               sub_proof_idx, attr, registry, witness);
+
+        let init_proof = self.init_proofs.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof by index '{}' not found", sub_proof_idx)))?;
+
+        let x_tilde = init_proof.primary_init_proof.eq_proof.m_tilde.get(attr)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't hidden in sub proof", attr)))?;
+
+        let x = init_proof.credential_values.attrs_values.get(attr)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "credential values".to_string() })?;
+
+        let mut ctx = BigNumber::new_context()?;
+
+        // accum^a = d^x * g, where x is registry member of holder
+        let accum_a = registry.accum.mod_exp(&witness.a, &registry.n, Some(&mut ctx))?;
+        let d_x_g = witness.d
+            .mod_exp(x, &registry.n, Some(&mut ctx))?
+            .mod_mul(&registry.g, &registry.n, Some(&mut ctx))?;
+
+        if accum_a != d_x_g {
+            return Err(IndyCryptoError::InvalidStructure(format!("Non-ownership witness doesn't match registry")));
+        }
+
+        let r = bn_rand(LARGE_NON_OWNERSHIP_R)?;
+        let s = bn_rand(LARGE_NON_OWNERSHIP_R)?;
+
+        let c_d = witness.d
+            .mod_mul(&registry.h.mod_exp(&r, &registry.n, Some(&mut ctx))?, &registry.n, Some(&mut ctx))?;
+        let c_r = get_pedersen_commitment(&registry.g, &r, &registry.h, &s, &registry.n, &mut ctx)?;
+
+        let rx = r.mul(x, Some(&mut ctx))?;
+        let sx = s.mul(x, Some(&mut ctx))?;
+
+        let a_tilde = bn_rand(LARGE_MTILDE)?;
+        let r_tilde = bn_rand(LARGE_NON_OWNERSHIP_R_TILDE)?;
+        let s_tilde = bn_rand(LARGE_NON_OWNERSHIP_R_TILDE)?;
+        let rx_tilde = bn_rand(LARGE_NON_OWNERSHIP_RX_TILDE)?;
+        let sx_tilde = bn_rand(LARGE_NON_OWNERSHIP_RX_TILDE)?;
+
+        let c_d_inv = c_d.inverse(&registry.n, Some(&mut ctx))?;
+        let g_inv = registry.g.inverse(&registry.n, Some(&mut ctx))?;
+        let h_inv = registry.h.inverse(&registry.n, Some(&mut ctx))?;
+
+        // accum^a_tilde * c_d^-x_tilde * h^rx_tilde
+        let t1 = registry.accum.mod_exp(&a_tilde, &registry.n, Some(&mut ctx))?
+            .mod_mul(&get_pedersen_commitment(&c_d_inv, x_tilde, &registry.h, &rx_tilde, &registry.n, &mut ctx)?,
+                     &registry.n, Some(&mut ctx))?;
+        // g^r_tilde * h^s_tilde
+        let t2 = get_pedersen_commitment(&registry.g, &r_tilde, &registry.h, &s_tilde, &registry.n, &mut ctx)?;
+        // c_r^x_tilde * g^-rx_tilde * h^-sx_tilde
+        let t3 = c_r.mod_exp(x_tilde, &registry.n, Some(&mut ctx))?
+            .mod_mul(&get_pedersen_commitment(&g_inv, &rx_tilde, &h_inv, &sx_tilde, &registry.n, &mut ctx)?,
+                     &registry.n, Some(&mut ctx))?;
+
+        self.non_ownership_init_proofs.push(NonOwnershipInitProof {
+            c_d,
+            c_r,
+            a: witness.a.clone()?,
+            r,
+            s,
+            rx,
+            sx,
+            a_tilde,
+            r_tilde,
+            s_tilde,
+            rx_tilde,
+            sx_tilde,
+            t_list: vec![t1, t2, t3]
+        });
+
+        trace!("ProofBuilder::add_non_ownership_request: <<<");
+
+        Ok(())
+    }
+
+    /// Adds request to prove that pseudonym for domain is derived from master secret of presented credentials.
+    /// Pseudonym is revealed in proof.
+    /// The order of pseudonym requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `domain` - Domain of pseudonym.
+    /// * `master_secret` - Master secret that will be used for proof finalization.
+    pub fn add_pseudonym_request(&mut self,
+                                 domain: &str,
+                                 master_secret: &MasterSecret) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_pseudonym_request: >>> domain: {:?}, master_secret: {:?}", domain, master_secret);
+
+        let pseudonym = Prover::create_pseudonym(domain, master_secret)?;
+        let t = Pseudonym::base(domain)?
+            .mod_exp(&self.m1_tilde, &Pseudonym::modulus()?, None)?;
+
+        self.pseudonym_init_proofs.push(PseudonymInitProof { pseudonym, t });
+
+        trace!("ProofBuilder::add_pseudonym_request: <<<");
+
+        Ok(())
+    }
+
+    /// Adds request to encrypt hidden attribute of sub proof under auditor key.
+    /// Proof will contain attribute ciphertext and proof that it encrypts the same value as signed in credential.
+    /// The order of verifiable encryption requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests) the attribute belongs to.
+    /// * `attr` - Name of hidden attribute.
+    /// * `auditor_pub_key` - Auditor public key.
+    pub fn add_verifiable_encryption_request(&mut self,
+                                             sub_proof_idx: usize,
+                                             attr: &str,
+                                             auditor_pub_key: &AuditorPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_verifiable_encryption_request: >>> sub_proof_idx: {:?}, attr: {:?}, auditor_pub_key: {:?}",
+               sub_proof_idx, attr, auditor_pub_key);
+
+        let init_proof = self.init_proofs.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof by index '{}' not found", sub_proof_idx)))?;
+
+        let m_tilde = init_proof.primary_init_proof.eq_proof.m_tilde.get(attr)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't hidden in sub proof", attr)))?;
+
+        let m = init_proof.credential_values.attrs_values.get(attr)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "credential values".to_string() })?;
+
+        let mut ctx = BigNumber::new_context()?;
+        let n_sqr = auditor_pub_key.n_sqr()?;
+        let n_plus_one = auditor_pub_key.n.add(&BigNumber::from_u32(1)?)?;
+
+        let r = bn_rand(LARGE_AUDIT_R)?;
+        let r_tilde = bn_rand(LARGE_AUDIT_R_TILDE)?;
+
+        let ciphertext = AttributeCiphertext {
+            u: auditor_pub_key.g.mod_exp(&r, &n_sqr, Some(&mut ctx))?,
+            e: auditor_pub_key.y.mod_exp(&r, &n_sqr, Some(&mut ctx))?
+                .mod_mul(&n_plus_one.mod_exp(m, &n_sqr, Some(&mut ctx))?, &n_sqr, Some(&mut ctx))?
+        };
+
+        // Verifier checks squares of ciphertext (Camenisch-Shoup), so t-values are taken with doubled exponents.
+        let mut r_tilde_2 = r_tilde.clone()?;
+        r_tilde_2.mul_word(2)?;
+        let mut m_tilde_2 = m_tilde.clone()?;
+        m_tilde_2.mul_word(2)?;
+
+        let u_tilde = auditor_pub_key.g.mod_exp(&r_tilde_2, &n_sqr, Some(&mut ctx))?;
+        let e_tilde = auditor_pub_key.y.mod_exp(&r_tilde_2, &n_sqr, Some(&mut ctx))?
+            .mod_mul(&n_plus_one.mod_exp(&m_tilde_2, &n_sqr, Some(&mut ctx))?, &n_sqr, Some(&mut ctx))?;
+
+        self.verifiable_encryption_init_proofs.push(VerifiableEncryptionInitProof { ciphertext, r, r_tilde, u_tilde, e_tilde });
+
+        trace!("ProofBuilder::add_verifiable_encryption_request: <<<");
+
+        Ok(())
+    }
+
+    /// Sets identifier of credential key sub proof is built with.
+    /// Key id is included into proof, so Verifier that accepts credentials of several issuers
+    /// can select the key sub proof must be verified with (see `ProofVerifier::add_sub_proof_request_with_key_candidates`).
+    /// Proof created with credential key replaced by Issuer refers to it by `CredentialKeyRotation::old_key_id`.
+    /// Key id is hashed into challenge, so it can't be changed in finalized proof.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests).
+    /// * `key_id` - Identifier of credential key (e.g. id derived with `CredentialPublicKey::derive_id`).
+    pub fn set_sub_proof_key_id(&mut self, sub_proof_idx: usize, key_id: &str) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::set_sub_proof_key_id: >>> sub_proof_idx: {:?}, key_id: {:?}", sub_proof_idx, key_id);
+
+        let init_proof = self.init_proofs.get_mut(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof by index '{}' not found", sub_proof_idx)))?;
+
+        init_proof.key_id = Some(key_id.to_owned());
+
+        trace!("ProofBuilder::set_sub_proof_key_id: <<<");
+
+        Ok(())
+    }
+
+    /// Sets hash function used to compute proof challenge (SHA-256 by default).
+    /// Chosen hash is stored in proof, so verifier uses the same one.
+    ///
+    /// # Arguments
+    /// * `challenge_hash` - Challenge hash function.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::ChallengeHash;
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.set_challenge_hash(ChallengeHash::Sha3_256).unwrap();
+    /// ```
+    pub fn set_challenge_hash(&mut self, challenge_hash: ChallengeHash) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::set_challenge_hash: >>> challenge_hash: {:?}", challenge_hash);
+
+        self.challenge_hash = challenge_hash;
+
+        trace!("ProofBuilder::set_challenge_hash: <<<");
+
+        Ok(())
+    }
+
+    /// Sets whether proof includes t-values of equality and GE proofs (one number per equality proof and
+    /// six per GE proof), so `ProofVerifier::verify_batch` can combine their equations with equations
+    /// of other proofs instead of recomputing them. Proof is valid for `ProofVerifier::verify` either way.
+    ///
+    /// # Arguments
+    /// * `batch_verifiable` - Whether t-values are included into proof.
+    pub fn set_batch_verifiable(&mut self, batch_verifiable: bool) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::set_batch_verifiable: >>> batch_verifiable: {:?}", batch_verifiable);
+
+        self.batch_verifiable = batch_verifiable;
+
+        trace!("ProofBuilder::set_batch_verifiable: <<<");
+
+        Ok(())
+    }
+
+    /// Sets observer receiving structured events of proof building (sub proof started, t-values computed,
+    /// challenge computed). Observer isn't serialized with builder state, so it must be set again after `from_json`.
+    ///
+    /// # Arguments
+    /// * `observer` - Observer of proof building events.
+    pub fn set_observer(&mut self, observer: Arc<ProofObserver>) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::set_observer: >>>");
+
+        self.observer.set(observer);
+
+        trace!("ProofBuilder::set_observer: <<<");
+
+        Ok(())
+    }
+
+    /// Returns attribute values, predicates and revocation statements that the finalized proof will disclose,
+    /// so holder can confirm the disclosure before calling `finalize`. Doesn't change proof builder state.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// credential_schema_builder.add_attr("age").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// credential_values_builder.add_value("age", "28").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    ///
+    /// let preview = proof_builder.preview().unwrap();
+    /// let sub_proof_preview = &preview.sub_proofs()[0];
+    /// assert_eq!(vec!["sex"], sub_proof_preview.revealed_attrs().keys().collect::<Vec<&String>>());
+    /// assert_eq!("age", sub_proof_preview.predicates()[0].attr_name());
+    /// assert!(!sub_proof_preview.is_revocation_included());
+    /// ```
+    pub fn preview(&self) -> Result<ProofPreview, IndyCryptoError> {
+        trace!("ProofBuilder::preview: >>>");
+
+        let mut sub_proofs: Vec<SubProofPreview> = Vec::new();
 
-        if z_calc != rev_key_pub.z {
-            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
-        }
-        let pair_gg_calc = Pair::pair(&cred_rev_pub_key.pk.add(&r_cred.g_i)?, &r_cred.witness_signature.sigma_i)?;
-        let pair_gg = Pair::pair(&cred_rev_pub_key.g, &cred_rev_pub_key.g_dash)?;
+        for init_proof in self.init_proofs.iter() {
+            let mut revealed_attrs: BTreeMap<String, BigNumber> = BTreeMap::new();
+            for attr in init_proof.sub_proof_request.revealed_attrs.iter() {
+                let value = init_proof.credential_values.attrs_values.get(attr)
+                    .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "credential values".to_string() })?;
+                revealed_attrs.insert(attr.clone(), value.clone()?);
+            }
 
-        if pair_gg_calc != pair_gg {
-            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
+            let predicates = init_proof.primary_init_proof.ge_proofs.iter()
+                .map(|ge_proof| ge_proof.predicate.clone())
+                .chain(init_proof.primary_init_proof.range_proofs.iter().map(|range_proof| range_proof.predicate.clone()))
+                .collect::<Vec<Predicate>>();
+
+            let timestamp = init_proof.non_revoc_init_proof.as_ref()
+                .and_then(|non_revoc_init_proof| non_revoc_init_proof.timestamp);
+
+            sub_proofs.push(SubProofPreview {
+                revealed_attrs,
+                predicates,
+                linear_predicates: init_proof.sub_proof_request.linear_predicates.clone(),
+                revocation_included: init_proof.non_revoc_init_proof.is_some(),
+                timestamp
+            });
         }
 
-        let m2 = GroupOrderElement::from_bytes(&r_cnxt_m2.to_bytes()?)?;
-
-        let pair_h1 = Pair::pair(&r_cred.sigma, &cred_rev_pub_key.y.add(&cred_rev_pub_key.h_cap.mul(&r_cred.c)?)?)?;
-        let pair_h2 = Pair::pair(
-            &cred_rev_pub_key.h0
-                .add(&cred_rev_pub_key.h1.mul(&m2)?)?
-                .add(&cred_rev_pub_key.h2.mul(&r_cred.vr_prime_prime)?)?
-                .add(&r_cred.g_i)?,
-            &cred_rev_pub_key.h_cap
-        )?;
-
-        if pair_h1 != pair_h2 {
-            return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
-        }
+        let preview = ProofPreview { sub_proofs };
 
-        trace!("Prover::_test_witness_signature: <<<");
+        trace!("ProofBuilder::preview: <<< preview: {:?}", preview);
 
-        Ok(())
+        Ok(preview)
     }
-}
-
-#[derive(Debug)]
-pub struct ProofBuilder {
-    pub m1_tilde: BigNumber,
-    pub init_proofs: Vec<InitProof>,
-    pub c_list: Vec<Vec<u8>>,
-    pub tau_list: Vec<Vec<u8>>,
-}
 
-impl ProofBuilder {
-    /// Adds sub proof request to proof builder which will be used fo building of proof.
-    /// Part of proof request related to a particular schema-key.
-    /// The order of sub-proofs is important: both Prover and Verifier should use the same order.
+    /// Finalize proof.
     ///
     /// # Arguments
     /// * `proof_builder` - Proof builder.
-    /// * `sub_proof_request` -Requested attributes and predicates.
-    /// * `credential_schema` - Credential schema.
-    /// * `credential_signature` - Credential signature.
-    /// * `credential_values` - Credential values.
-    /// * `credential_pub_key` - Credential public key.
-    /// * `rev_reg_pub` - (Optional) Revocation registry public.
+    /// * `nonce` - Nonce.
+    /// * `master_secret` - Master secret.
     ///
     /// #Example
     /// ```
@@ -538,69 +2208,132 @@ impl ProofBuilder {
     ///                                     &credential_pub_key,
     ///                                     None,
     ///                                     None).unwrap();
+    ///
+    /// let proof_request_nonce = new_nonce().unwrap();
+    /// let _proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
     /// ```
-    pub fn add_sub_proof_request(&mut self,
-                                 sub_proof_request: &SubProofRequest,
-                                 credential_schema: &CredentialSchema,
-                                 credential_signature: &CredentialSignature,
-                                 credential_values: &CredentialValues,
-                                 credential_pub_key: &CredentialPublicKey,
-                                 rev_reg: Option<&RevocationRegistry>,
-                                 witness: Option<&Witness>) -> Result<(), IndyCryptoError> {
-        trace!("ProofBuilder::add_sub_proof_request: >>> credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, \
-        rev_reg: {:?}, sub_proof_request: {:?}, credential_schema: {:?}",
-               credential_signature, credential_values, credential_pub_key, rev_reg, sub_proof_request, credential_schema);
-
-        ProofBuilder::_check_add_sub_proof_request_params_consistency(credential_values, sub_proof_request, credential_schema)?;
+    pub fn finalize(&self, nonce: &Nonce, master_secret: &MasterSecret) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::finalize: >>> nonce: {:?}, master_secret: {:?}", nonce, master_secret);
 
-        let mut non_revoc_init_proof = None;
-        let mut m2_tilde: Option<BigNumber> = None;
+        let _timer = OperationTimer::start("ProofBuilder::finalize");
 
-        if let (&Some(ref r_cred), &Some(ref r_reg), &Some(ref r_pub_key), &Some(ref witness)) = (&credential_signature.r_credential,
-                                                                                                  &rev_reg,
-                                                                                                  &credential_pub_key.r_key,
-                                                                                                  &witness) {
-            let proof = ProofBuilder::_init_non_revocation_proof(&r_cred,
-                                                                 &r_reg,
-                                                                 &r_pub_key,
-                                                                 &witness)?;
+        let proof = self._finalize(&[nonce], &[master_secret])?;
 
-            self.c_list.extend_from_slice(&proof.as_c_list()?);
-            self.tau_list.extend_from_slice(&proof.as_tau_list()?);
-            m2_tilde = Some(group_element_to_bignum(&proof.tau_list_params.m2)?);
-            non_revoc_init_proof = Some(proof);
-        }
+        trace!("ProofBuilder::finalize: <<< proof: {:?}", proof);
 
-        let primary_init_proof = ProofBuilder::_init_primary_proof(&credential_pub_key.p_key,
-                                                                   &credential_signature.p_credential,
-                                                                   &credential_values,
-                                                                   &credential_schema,
-                                                                   &sub_proof_request,
-                                                                   &self.m1_tilde,
-                                                                   m2_tilde)?;
+        Ok(proof)
+    }
 
-        self.c_list.extend_from_slice(&primary_init_proof.as_c_list()?);
-        self.tau_list.extend_from_slice(&primary_init_proof.as_tau_list()?);
+    /// Finalize proof of credentials signed with keys without master secret (e.g. device credentials).
+    /// Fails if any of credentials requires master secret.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Proof builder.
+    /// * `nonce` - Nonce.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("serial").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_without_master_secret(&credential_schema, false).unwrap();
+    ///
+    /// let blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_credential_without_master_secret(&credential_pub_key, &cred_key_correctness_proof, &blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("serial", "1139481716457488690172217916278103335").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature_without_master_secret(&mut credential_signature,
+    ///                                                            &credential_values,
+    ///                                                            &signature_correctness_proof,
+    ///                                                            &master_secret_blinding_data,
+    ///                                                            &credential_pub_key,
+    ///                                                            &credential_issuance_nonce,
+    ///                                                            None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("serial").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    ///
+    /// let proof_request_nonce = new_nonce().unwrap();
+    /// let proof = proof_builder.finalize_without_master_secret(&proof_request_nonce).unwrap();
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    /// proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+    /// assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    /// ```
+    pub fn finalize_without_master_secret(&self, nonce: &Nonce) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::finalize_without_master_secret: >>> nonce: {:?}", nonce);
 
-        let init_proof = InitProof {
-            primary_init_proof,
-            non_revoc_init_proof,
-            credential_values: credential_values.clone()?,
-            sub_proof_request: sub_proof_request.clone(),
-            credential_schema: credential_schema.clone()
-        };
-        self.init_proofs.push(init_proof);
+        let proof = self._finalize(&[nonce], &[])?;
 
-        trace!("ProofBuilder::add_sub_proof_request: <<<");
+        trace!("ProofBuilder::finalize_without_master_secret: <<< proof: {:?}", proof);
 
-        Ok(())
+        Ok(proof)
     }
 
-    /// Finalize proof.
+    /// Finalize proof of credentials bound to different master secrets
+    /// (see `ProofBuilder::add_sub_proof_request_with_master_secret`).
     ///
     /// # Arguments
     /// * `proof_builder` - Proof builder.
     /// * `nonce` - Nonce.
+    /// * `master_secrets` - Master secrets in the order of their indices.
+    pub fn finalize_with_master_secrets(&self, nonce: &Nonce, master_secrets: &[&MasterSecret]) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::finalize_with_master_secrets: >>> nonce: {:?}, master_secrets: {:?}", nonce, master_secrets);
+
+        for init_proof in self.init_proofs.iter() {
+            if !init_proof.credential_pub_key.without_master_secret && init_proof.master_secret_idx >= master_secrets.len() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Master secret by index '{}' not found", init_proof.master_secret_idx)));
+            }
+        }
+
+        let proof = self._finalize(&[nonce], master_secrets)?;
+
+        trace!("ProofBuilder::finalize_with_master_secrets: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    /// Finalize proof bound to nonces of several verifiers (e.g. broker and auditor).
+    ///
+    /// Challenge is computed over all nonces in the given order and nonces are stored in proof,
+    /// so each verifier accepts the proof only if its own nonce is among them.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Proof builder.
+    /// * `nonces` - Nonces provided by verifiers.
     /// * `master_secret` - Master secret.
     ///
     /// #Example
@@ -659,19 +2392,217 @@ impl ProofBuilder {
     ///                                     None,
     ///                                     None).unwrap();
     ///
-    /// let proof_request_nonce = new_nonce().unwrap();
-    /// let _proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+    /// let broker_nonce = new_nonce().unwrap();
+    /// let auditor_nonce = new_nonce().unwrap();
+    /// let proof = proof_builder.finalize_multi(&[&broker_nonce, &auditor_nonce], &master_secret).unwrap();
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    /// proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+    /// assert!(proof_verifier.verify(&proof, &auditor_nonce).unwrap());
     /// ```
-    pub fn finalize(&self, nonce: &Nonce, master_secret: &MasterSecret) -> Result<Proof, IndyCryptoError> {
-        trace!("ProofBuilder::finalize: >>> nonce: {:?}, master_secret: {:?}", nonce, master_secret);
+    pub fn finalize_multi(&self, nonces: &[&Nonce], master_secret: &MasterSecret) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::finalize_multi: >>> nonces: {:?}, master_secret: {:?}", nonces, master_secret);
+
+        let _timer = OperationTimer::start("ProofBuilder::finalize_multi");
+
+        if nonces.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Proof must be bound to at least one nonce")));
+        }
+
+        for (idx, nonce) in nonces.iter().enumerate() {
+            if nonces[..idx].contains(nonce) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Nonce {:?} is duplicated", nonce)));
+            }
+        }
+
+        let mut proof = self._finalize(nonces, &[master_secret])?;
+        proof.nonces = nonces.iter().map(|nonce| (*nonce).clone()).collect::<Result<Vec<Nonce>, IndyCryptoError>>()?;
+
+        trace!("ProofBuilder::finalize_multi: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    /// Returns values proof challenge is computed over (t-values of sub proofs and c_list, without nonces),
+    /// so application can compose them into Fiat-Shamir transcript of larger protocol and pass
+    /// resulting challenge to `ProofBuilder::finalize_with_external_challenge`.
+    ///
+    /// `ProofVerifier::compute_challenge_contribution` returns the same values for finalized proof.
+    /// Linear predicates aren't supported as their commitments are randomized on finalization.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Proof builder.
+    pub fn commitments(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        trace!("ProofBuilder::commitments: >>>");
+
+        self._check_external_challenge_supported()?;
+
+        let mut commitments: Vec<Vec<u8>> = Vec::new();
+        self._append_commitments(&mut commitments, &[], &self.c_list)?;
+
+        trace!("ProofBuilder::commitments: <<< commitments: {:?}", commitments);
+
+        Ok(commitments)
+    }
+
+    /// Finalize proof with challenge computed by application over `ProofBuilder::commitments`
+    /// and its own statements. Proof isn't bound to verifier nonce by itself, so challenge should absorb it.
+    /// Verifier recomputes challenge from `ProofVerifier::compute_challenge_contribution` and compares it with `Proof::challenge`.
+    /// Builder can't be finalized again afterwards.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Proof builder.
+    /// * `challenge` - Challenge of at most `CHALLENGE_BYTES` bytes.
+    /// * `master_secret` - Master secret.
+    pub fn finalize_with_external_challenge(&mut self, challenge: &BigNumber, master_secret: &MasterSecret) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::finalize_with_external_challenge: >>> challenge: {:?}, master_secret: {:?}", challenge, master_secret);
+
+        self._check_external_challenge_supported()?;
+        self._check_finalize()?;
+
+        if challenge.is_negative()? || challenge.num_bits()? as usize > CHALLENGE_BYTES * 8 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Challenge must be non-negative number of at most {} bits", CHALLENGE_BYTES * 8)));
+        }
+
+        self.finalized = true;
+        self.observer.notify(ProofEvent::ChallengeComputed { challenge: challenge.to_hex()? });
+
+        let proof = self._finalize_with_challenge(challenge.clone()?, Vec::new(), self.c_list.clone(), &[master_secret])?;
+
+        trace!("ProofBuilder::finalize_with_external_challenge: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    fn _check_external_challenge_supported(&self) -> Result<(), IndyCryptoError> {
+        if self.init_proofs.iter().any(|init_proof| !init_proof.sub_proof_request.linear_predicates.is_empty()) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Linear predicates aren't supported with external challenge")));
+        }
+        Ok(())
+    }
+
+    fn _finalize(&self, nonces: &[&Nonce], master_secrets: &[&MasterSecret]) -> Result<Proof, IndyCryptoError> {
+        self._check_finalize()?;
+
+        let linear_ge_init_proofs = self._init_linear_ge_proofs()?;
+        let c_list = ProofBuilder::_c_list(&self.c_list, &linear_ge_init_proofs)?;
+
+        let mut hasher = self.challenge_hash.hasher();
+        self._append_commitments(&mut hasher, &linear_ge_init_proofs, &c_list)?;
+        for nonce in nonces.iter() {
+            hasher.update_bignum(nonce)?;
+        }
+
+        // In the anoncreds whitepaper, `challenge` is denoted by `c_h`
+        let challenge = hasher.finalize()?;
+        self.observer.notify(ProofEvent::ChallengeComputed { challenge: challenge.to_hex()? });
+
+        self._finalize_with_challenge(challenge, linear_ge_init_proofs, c_list, master_secrets)
+    }
+
+    fn _check_finalize(&self) -> Result<(), IndyCryptoError> {
+        if self.finalized {
+            return Err(IndyCryptoError::InvalidState(format!("Proof builder is already finalized with external challenge")));
+        }
+
+        if !self.pseudonym_init_proofs.is_empty() && self.init_proofs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Pseudonym proof requires at least one sub proof")));
+        }
+
+        // Pseudonyms are linked through response for the first master secret
+        if !self.pseudonym_init_proofs.is_empty() &&
+            self.init_proofs.iter().all(|init_proof| init_proof.credential_pub_key.without_master_secret || init_proof.master_secret_idx != 0) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Proof of master secret ownership requires at least one credential with master secret")));
+        }
+
+        Ok(())
+    }
 
-        let mut values: Vec<Vec<u8>> = Vec::new();
-        values.extend_from_slice(&self.tau_list);
-        values.extend_from_slice(&self.c_list);
-        values.push(nonce.to_bytes()?);
+    // Linear predicates are initialized on finalization as their terms can refer to any sub proof
+    fn _init_linear_ge_proofs(&self) -> Result<Vec<(usize, &LinearPredicate, PrimaryPredicateGEInitProof)>, IndyCryptoError> {
+        let mut linear_ge_init_proofs: Vec<(usize, &LinearPredicate, PrimaryPredicateGEInitProof)> = Vec::new();
+        for (idx, init_proof) in self.init_proofs.iter().enumerate() {
+            for linear_predicate in init_proof.sub_proof_request.linear_predicates.iter() {
+                let linear_ge_init_proof = ProofBuilder::_init_linear_ge_proof(&self.init_proofs,
+                                                                                &init_proof.credential_pub_key,
+                                                                                linear_predicate)?;
+                linear_ge_init_proofs.push((idx, linear_predicate, linear_ge_init_proof));
+            }
+        }
+        Ok(linear_ge_init_proofs)
+    }
 
-        // In the anoncreds whitepaper, `challenge` is denoted by `c_h`
-        let challenge = get_hash_as_int(&values)?;
+    fn _c_list(c_list: &Vec<Vec<u8>>,
+               linear_ge_init_proofs: &[(usize, &LinearPredicate, PrimaryPredicateGEInitProof)]) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        let mut c_list: Vec<Vec<u8>> = c_list.clone();
+        for &(_, _, ref linear_ge_init_proof) in linear_ge_init_proofs.iter() {
+            c_list.append_vec(linear_ge_init_proof.as_list()?)?;
+        }
+        Ok(c_list)
+    }
+
+    // Values Fiat-Shamir challenge is computed over (except nonces) in the order `ProofVerifier` recomputes them
+    fn _append_commitments<A: AppendByteArray>(&self,
+                                               out: &mut A,
+                                               linear_ge_init_proofs: &[(usize, &LinearPredicate, PrimaryPredicateGEInitProof)],
+                                               c_list: &Vec<Vec<u8>>) -> Result<(), IndyCryptoError> {
+        for tau in self.tau_list.iter() {
+            out.append_bytes(tau);
+        }
+        for &(idx, _, ref linear_ge_init_proof) in linear_ge_init_proofs.iter() {
+            out.append_vec(&linear_ge_init_proof.as_tau_list(&self.init_proofs[idx].credential_pub_key.n)?)?;
+        }
+        for init_proof in self.non_ownership_init_proofs.iter() {
+            out.append_bytes(&init_proof.c_d.to_bytes()?);
+            out.append_bytes(&init_proof.c_r.to_bytes()?);
+            for t in init_proof.t_list.iter() {
+                out.append_bytes(&t.to_bytes()?);
+            }
+        }
+        for init_proof in self.verifiable_encryption_init_proofs.iter() {
+            out.append_bytes(&init_proof.ciphertext.u.to_bytes()?);
+            out.append_bytes(&init_proof.ciphertext.e.to_bytes()?);
+            out.append_bytes(&init_proof.u_tilde.to_bytes()?);
+            out.append_bytes(&init_proof.e_tilde.to_bytes()?);
+        }
+        for init_proof in self.pseudonym_init_proofs.iter() {
+            out.append_bytes(&init_proof.pseudonym.value.to_bytes()?);
+            out.append_bytes(&init_proof.t.to_bytes()?);
+        }
+        for c in c_list.iter() {
+            out.append_bytes(c);
+        }
+        for (idx, init_proof) in self.init_proofs.iter().enumerate() {
+            if let Some(ref key_id) = init_proof.key_id {
+                out.append_bytes(&key_id_bytes(idx, key_id));
+            }
+        }
+        Ok(())
+    }
+
+    // Absolute values of t-values of equality and GE proofs in the order they are committed to (see `ProofVerifier::verify_batch`)
+    fn _primary_t_list(&self,
+                       linear_ge_init_proofs: &[(usize, &LinearPredicate, PrimaryPredicateGEInitProof)]) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let mut t_list: Vec<BigNumber> = Vec::new();
+        for init_proof in self.init_proofs.iter() {
+            let n = &init_proof.credential_pub_key.n;
+            t_list.push(abs_mod(&init_proof.primary_init_proof.eq_proof.t, n)?);
+            for ge_proof in init_proof.primary_init_proof.ge_proofs.iter() {
+                t_list.append(&mut ge_proof.as_tau_list(n)?);
+            }
+        }
+        for &(idx, _, ref linear_ge_init_proof) in linear_ge_init_proofs.iter() {
+            t_list.append(&mut linear_ge_init_proof.as_tau_list(&self.init_proofs[idx].credential_pub_key.n)?);
+        }
+        Ok(t_list)
+    }
+
+    fn _finalize_with_challenge(&self,
+                                challenge: BigNumber,
+                                linear_ge_init_proofs: Vec<(usize, &LinearPredicate, PrimaryPredicateGEInitProof)>,
+                                c_list: Vec<Vec<u8>>,
+                                master_secrets: &[&MasterSecret]) -> Result<Proof, IndyCryptoError> {
+        let t_list = if self.batch_verifiable { self._primary_t_list(&linear_ge_init_proofs)? } else { Vec::new() };
 
         let mut proofs: Vec<SubProof> = Vec::new();
 
@@ -681,24 +2612,107 @@ impl ProofBuilder {
                 non_revoc_proof = Some(ProofBuilder::_finalize_non_revocation_proof(&non_revoc_init_proof, &challenge)?);
             }
 
-            let primary_proof = ProofBuilder::_finalize_primary_proof(&master_secret.ms,
+            let master_secret = master_secrets.get(init_proof.master_secret_idx).map(|master_secret| *master_secret);
+            let ms = Prover::_master_secret_for_key(master_secret, &init_proof.credential_pub_key)?;
+
+            let primary_proof = ProofBuilder::_finalize_primary_proof(ms,
                                                                       &init_proof.primary_init_proof,
                                                                       &challenge,
                                                                       &init_proof.credential_schema,
                                                                       &init_proof.credential_values,
                                                                       &init_proof.sub_proof_request)?;
 
-            let proof = SubProof { primary_proof, non_revoc_proof };
+            let proof = SubProof { primary_proof, non_revoc_proof, key_id: init_proof.key_id.clone() };
             proofs.push(proof);
         }
 
-        let aggregated_proof = AggregatedProof { c_hash: challenge, c_list: self.c_list.clone() };
+        for (idx, linear_predicate, linear_ge_init_proof) in linear_ge_init_proofs {
+            let mj = linear_predicate.combine(|term| proofs[term.sub_proof_idx].primary_proof.eq_proof.m
+                .get(&term.attr_name)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: term.attr_name.to_string(), context: "eq_proof.m".to_string() })?
+                .clone())?;
 
-        let proof = Proof { proofs, aggregated_proof };
+            let linear_ge_proof = ProofBuilder::_finalize_ge_proof_for_mj(&challenge, &linear_ge_init_proof, &mj)?;
+            proofs[idx].primary_proof.linear_ge_proofs.push(linear_ge_proof);
+        }
 
-        trace!("ProofBuilder::finalize: <<< proof: {:?}", proof);
+        let aggregated_proof = AggregatedProof { c_hash: challenge, c_list, challenge_hash: self.challenge_hash, t_list };
+
+        let mut non_ownership_proofs: Vec<NonOwnershipProof> = Vec::new();
+        for init_proof in self.non_ownership_init_proofs.iter() {
+            non_ownership_proofs.push(NonOwnershipProof {
+                c_d: init_proof.c_d.clone()?,
+                c_r: init_proof.c_r.clone()?,
+                a_hat: challenge.mul(&init_proof.a, None)?.add(&init_proof.a_tilde)?,
+                r_hat: challenge.mul(&init_proof.r, None)?.add(&init_proof.r_tilde)?,
+                s_hat: challenge.mul(&init_proof.s, None)?.add(&init_proof.s_tilde)?,
+                rx_hat: challenge.mul(&init_proof.rx, None)?.add(&init_proof.rx_tilde)?,
+                sx_hat: challenge.mul(&init_proof.sx, None)?.add(&init_proof.sx_tilde)?
+            });
+        }
 
-        Ok(proof)
+        let mut verifiable_encryptions: Vec<VerifiableEncryptionProof> = Vec::new();
+        for init_proof in self.verifiable_encryption_init_proofs.iter() {
+            verifiable_encryptions.push(VerifiableEncryptionProof {
+                ciphertext: AttributeCiphertext {
+                    u: init_proof.ciphertext.u.clone()?,
+                    e: init_proof.ciphertext.e.clone()?
+                },
+                r_hat: challenge.mul(&init_proof.r, None)?.add(&init_proof.r_tilde)?
+            });
+        }
+
+        let mut pseudonyms: Vec<Pseudonym> = Vec::new();
+        for init_proof in self.pseudonym_init_proofs.iter() {
+            pseudonyms.push(init_proof.pseudonym.clone()?);
+        }
+
+        Ok(Proof {
+            proofs,
+            aggregated_proof,
+            non_ownership_proofs,
+            verifiable_encryptions,
+            pseudonyms,
+            nonces: Vec::new(),
+            #[cfg(feature = "sign_sodium")]
+            device_binding: None
+        })
+    }
+
+    fn _init_linear_ge_proof(init_proofs: &[InitProof],
+                             p_pub_key: &CredentialPrimaryPublicKey,
+                             linear_predicate: &LinearPredicate) -> Result<PrimaryPredicateGEInitProof, IndyCryptoError> {
+        trace!("ProofBuilder::_init_linear_ge_proof: >>> p_pub_key: {:?}, linear_predicate: {:?}", p_pub_key, linear_predicate);
+
+        for term in linear_predicate.terms.iter() {
+            let init_proof = init_proofs.get(term.sub_proof_idx)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof {} requested in linear predicate not found", term.sub_proof_idx)))?;
+
+            if !init_proof.primary_init_proof.eq_proof.m_tilde.contains_key(&term.attr_name) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of sub proof {} requested in linear predicate is not hidden", term.attr_name, term.sub_proof_idx)));
+            }
+        }
+
+        let combination = linear_predicate.combine(|term| init_proofs[term.sub_proof_idx].credential_values.attrs_values
+            .get(&term.attr_name)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: term.attr_name.to_string(), context: "cred_values".to_string() })?
+            .clone())?;
+
+        let mj = linear_predicate.combine(|term| init_proofs[term.sub_proof_idx].primary_init_proof.eq_proof.m_tilde[&term.attr_name].clone())?;
+        let mj = predicate_signed(&linear_predicate.p_type, &mj)?;
+
+        let delta = predicate_signed(&linear_predicate.p_type, &combination)?
+            .sub(&BigNumber::from_dec(&predicate_bound(&linear_predicate.p_type, linear_predicate.value).to_string())?)?
+            .to_dec()?
+            .parse::<i32>()
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Linear combination is out of predicate range")))?;
+
+        let linear_ge_init_proof = ProofBuilder::_init_ge_proof_for_delta(p_pub_key, delta, &mj, &linear_predicate.as_predicate())?;
+
+        trace!("ProofBuilder::_init_linear_ge_proof: <<< linear_ge_init_proof: {:?}", linear_ge_init_proof);
+
+        Ok(linear_ge_init_proof)
     }
 
     fn _check_add_sub_proof_request_params_consistency(cred_values: &CredentialValues,
@@ -713,8 +2727,8 @@ impl ProofBuilder {
             return Err(IndyCryptoError::InvalidStructure(format!("Credential doesn't correspond to credential schema")));
         }
 
-        if sub_proof_request.revealed_attrs.difference(&cred_attrs).count() != 0 {
-            return Err(IndyCryptoError::InvalidStructure(format!("Credential doesn't contain requested attribute")));
+        if let Some(attr) = sub_proof_request.revealed_attrs.difference(&cred_attrs).next() {
+            return Err(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential values".to_string() });
         }
 
         let predicates_attrs =
@@ -722,8 +2736,19 @@ impl ProofBuilder {
                 .map(|predicate| predicate.attr_name.clone())
                 .collect::<HashSet<String>>();
 
-        if predicates_attrs.difference(&cred_attrs).count() != 0 {
-            return Err(IndyCryptoError::InvalidStructure(format!("Credential doesn't contain attribute requested in predicate")));
+        if let Some(attr) = predicates_attrs.difference(&cred_attrs).next() {
+            return Err(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential values".to_string() });
+        }
+
+        sub_proof_request._check_attr_value_checks()?;
+
+        for (attr, value) in sub_proof_request.attr_value_checks.iter() {
+            let cred_value = cred_values.attrs_values.get(attr)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential values".to_string() })?;
+
+            if cred_value.to_dec()? != *value {
+                return Err(IndyCryptoError::InvalidStructure(format!("Value of attribute {} doesn't equal required value", attr)));
+            }
         }
 
         trace!("ProofBuilder::_check_add_sub_proof_request_params_consistency: <<<");
@@ -743,19 +2768,77 @@ impl ProofBuilder {
 
         let eq_proof = ProofBuilder::_init_eq_proof(&issuer_pub_key, c1, cred_schema, sub_proof_request, m1_t, m2_t)?;
 
+        // Randomness is consumed in sorted order, so proofs are reproducible with seeded RNG.
+        // Each predicate proof takes randomness from own seed drawn in this order, so proofs computed
+        // concurrently are the same as computed one by one.
+        let mut sorted_predicates: Vec<&Predicate> = sub_proof_request.predicates.iter().collect();
+        sorted_predicates.sort_by(|a, b| (&a.attr_name, a.value).cmp(&(&b.attr_name, b.value)));
+
+        let mut predicates: Vec<(&Predicate, Vec<u8>)> = Vec::new();
+        for predicate in sorted_predicates {
+            predicates.push((predicate, random_bytes(PREDICATE_SEED_LEN)?));
+        }
+
         let mut ge_proofs: Vec<PrimaryPredicateGEInitProof> = Vec::new();
-        for predicate in sub_proof_request.predicates.iter() {
-            let ge_proof = ProofBuilder::_init_ge_proof(&issuer_pub_key, &eq_proof.m_tilde, cred_values, predicate)?;
-            ge_proofs.push(ge_proof);
+        let mut range_proofs: Vec<PrimaryPredicateRangeInitProof> = Vec::new();
+        for predicate_init_proof in ProofBuilder::_init_predicate_proofs(&issuer_pub_key, &eq_proof.m_tilde, cred_values, &predicates)? {
+            match predicate_init_proof {
+                PredicateInitProof::GE(ge_proof) => ge_proofs.push(ge_proof),
+                PredicateInitProof::Range(range_proof) => range_proofs.push(range_proof)
+            }
         }
 
-        let primary_init_proof = PrimaryInitProof { eq_proof, ge_proofs };
+        let primary_init_proof = PrimaryInitProof { eq_proof, ge_proofs, range_proofs };
 
         trace!("ProofBuilder::_init_primary_proof: <<< primary_init_proof: {:?}", primary_init_proof);
 
         Ok(primary_init_proof)
     }
 
+    // Predicate proofs don't depend on each other, so with `parallel` feature they are computed concurrently.
+    fn _init_predicate_proofs(p_pub_key: &CredentialPrimaryPublicKey,
+                              m_tilde: &HashMap<String, BigNumber>,
+                              cred_values: &CredentialValues,
+                              predicates: &[(&Predicate, Vec<u8>)]) -> Result<Vec<PredicateInitProof>, IndyCryptoError> {
+        let init = |&(predicate, ref seed): &(&Predicate, Vec<u8>)|
+            ProofBuilder::_init_predicate_proof(p_pub_key, m_tilde, cred_values, predicate, seed);
+
+        #[cfg(feature = "parallel")]
+        {
+            if ProofBuilder::_parallel_enabled() {
+                return predicates.par_iter().map(&init).collect();
+            }
+        }
+
+        predicates.iter().map(&init).collect()
+    }
+
+    fn _init_predicate_proof(p_pub_key: &CredentialPrimaryPublicKey,
+                             m_tilde: &HashMap<String, BigNumber>,
+                             cred_values: &CredentialValues,
+                             predicate: &Predicate,
+                             seed: &[u8]) -> Result<PredicateInitProof, IndyCryptoError> {
+        with_seeded_rng(seed, || {
+            match predicate.proof_version {
+                PredicateProofVersion::FourSquares =>
+                    Ok(PredicateInitProof::GE(ProofBuilder::_init_ge_proof(p_pub_key, m_tilde, cred_values, predicate)?)),
+                PredicateProofVersion::RangeProof =>
+                    Ok(PredicateInitProof::Range(ProofBuilder::_init_range_proof(m_tilde, cred_values, predicate)?))
+            }
+        })
+    }
+
+    // Mocks are injected per thread, so tests with mocks compute predicate proofs in the current thread.
+    #[cfg(all(feature = "parallel", test))]
+    fn _parallel_enabled() -> bool {
+        !MockHelper::is_injected()
+    }
+
+    #[cfg(all(feature = "parallel", not(test)))]
+    fn _parallel_enabled() -> bool {
+        true
+    }
+
     fn _init_non_revocation_proof(r_cred: &NonRevocationCredentialSignature,
                                   rev_reg: &RevocationRegistry,
                                   cred_rev_pub_key: &CredentialRevocationPublicKey,
@@ -776,7 +2859,9 @@ impl ProofBuilder {
             c_list_params,
             tau_list_params,
             c_list,
-            tau_list
+            tau_list,
+            timestamp: None,
+            rev_reg_id: None
         };
 
         trace!("ProofBuilder::_init_non_revocation_proof: <<< r_init_proof: {:?}", r_init_proof);
@@ -795,17 +2880,15 @@ impl ProofBuilder {
 
         let mut ctx = BigNumber::new_context()?;
 
+        let profile = credr_pub_key.security_profile()?;
+
         let m2_tilde = m2_t.unwrap_or(bn_rand(LARGE_MVECT)?);
 
-        let r = bn_rand(LARGE_VPRIME)?;
+        let r = bn_rand(profile.vprime_bits())?;
         let e_tilde = bn_rand(LARGE_ETILDE)?;
-        let v_tilde = bn_rand(LARGE_VTILDE)?;
+        let v_tilde = bn_rand(profile.vtilde_bits())?;
 
-        let unrevealed_attrs: HashSet<String> =
-            cred_schema.attrs
-                .difference(&sub_proof_request.revealed_attrs)
-                .cloned()
-                .collect::<HashSet<String>>();
+        let unrevealed_attrs = sub_proof_request.unrevealed_attrs(cred_schema);
 
         let m_tilde = get_mtilde(&unrevealed_attrs)?;
 
@@ -821,7 +2904,7 @@ impl ProofBuilder {
             &BigNumber::from_dec("2")?.exp(&BigNumber::from_dec(&LARGE_E_START.to_string())?, Some(&mut ctx))?
         )?;
 
-        let t = calc_teq(&credr_pub_key, &a_prime, &e_tilde, &v_tilde, &m_tilde, m1_tilde, &m2_tilde, &unrevealed_attrs)?;
+        let t = calc_teq(credr_pub_key, &a_prime, &e_tilde, &v_tilde, &m_tilde, m1_tilde, &m2_tilde, &unrevealed_attrs)?;
 
         let primary_equal_init_proof = PrimaryEqualInitProof {
             a_prime,
@@ -848,16 +2931,78 @@ impl ProofBuilder {
         trace!("ProofBuilder::_init_ge_proof: >>> p_pub_key: {:?}, m_tilde: {:?}, cred_values: {:?}, predicate: {:?}",
                p_pub_key, m_tilde, cred_values, predicate);
 
-        let mut ctx = BigNumber::new_context()?;
         let (k, value) = (&predicate.attr_name, predicate.value);
 
         let attr_value = cred_values.attrs_values.get(k.as_str())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", k)))?
+            .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "cred_values".to_string() })?
+            .to_dec()?
+            .parse::<i32>()
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Value by key '{}' has invalid format", k)))?;
+
+        let delta = predicate_delta(&predicate.p_type, attr_value, value)?;
+
+        let mj = m_tilde.get(k.as_str())
+            .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "eq_proof.mtilde".to_string() })?;
+        let mj = predicate_signed(&predicate.p_type, mj)?;
+
+        let primary_predicate_ge_init_proof = ProofBuilder::_init_ge_proof_for_delta(p_pub_key, delta, &mj, predicate)?;
+
+        trace!("ProofBuilder::_init_ge_proof: <<< primary_predicate_ge_init_proof: {:?}", primary_predicate_ge_init_proof);
+
+        Ok(primary_predicate_ge_init_proof)
+    }
+
+    fn _init_range_proof(m_tilde: &HashMap<String, BigNumber>,
+                         cred_values: &CredentialValues,
+                         predicate: &Predicate) -> Result<PrimaryPredicateRangeInitProof, IndyCryptoError> {
+        trace!("ProofBuilder::_init_range_proof: >>> m_tilde: {:?}, cred_values: {:?}, predicate: {:?}", m_tilde, cred_values, predicate);
+
+        if predicate.p_type.is_less() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Range proof doesn't support predicate type: {:?}", predicate.p_type)));
+        }
+
+        let k = &predicate.attr_name;
+
+        let attr_value = cred_values.attrs_values.get(k.as_str())
+            .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "cred_values".to_string() })?
             .to_dec()?
             .parse::<i32>()
             .map_err(|_| IndyCryptoError::InvalidStructure(format!("Value by key '{}' has invalid format", k)))?;
 
-        let delta: i32 = attr_value - value;
+        let delta = predicate_delta(&predicate.p_type, attr_value, predicate.value)?;
+
+        let mj_tilde = m_tilde.get(k.as_str())
+            .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "eq_proof.mtilde".to_string() })?;
+
+        let gamma = GroupOrderElement::new()?;
+        let (range_proof, commitment) = RangeProof::prove(delta as u64, &gamma, RANGE_BITS)?;
+
+        let gamma_tilde = GroupOrderElement::new()?;
+        let t = commit(&bignum_to_scalar(mj_tilde)?, &gamma_tilde)?;
+
+        let primary_predicate_range_init_proof = PrimaryPredicateRangeInitProof {
+            predicate: predicate.clone(),
+            commitment,
+            gamma,
+            gamma_tilde,
+            t,
+            range_proof
+        };
+
+        trace!("ProofBuilder::_init_range_proof: <<< primary_predicate_range_init_proof: {:?}", primary_predicate_range_init_proof);
+
+        Ok(primary_predicate_range_init_proof)
+    }
+
+    fn _init_ge_proof_for_delta(p_pub_key: &CredentialPrimaryPublicKey,
+                                delta: i32,
+                                mj: &BigNumber,
+                                predicate: &Predicate) -> Result<PrimaryPredicateGEInitProof, IndyCryptoError> {
+        trace!("ProofBuilder::_init_ge_proof_for_delta: >>> p_pub_key: {:?}, delta: {:?}, mj: {:?}, predicate: {:?}",
+               p_pub_key, delta, mj, predicate);
+
+        let mut ctx = BigNumber::new_context()?;
+        let profile = p_pub_key.security_profile()?;
 
         if delta < 0 {
             return Err(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()));
@@ -873,7 +3018,7 @@ impl ProofBuilder {
             let cur_u = u.get(&i.to_string())
                 .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
 
-            let cur_r = bn_rand(LARGE_VPRIME)?;
+            let cur_r = bn_rand(profile.vprime_bits())?;
             let cut_t = get_pedersen_commitment(&p_pub_key.z, &cur_u, &p_pub_key.s,
                                                 &cur_r, &p_pub_key.n, &mut ctx)?;
 
@@ -882,7 +3027,7 @@ impl ProofBuilder {
             c_list.push(cut_t)
         }
 
-        let r_delta = bn_rand(LARGE_VPRIME)?;
+        let r_delta = bn_rand(profile.vprime_bits())?;
 
         let t_delta = get_pedersen_commitment(&p_pub_key.z, &BigNumber::from_dec(&delta.to_string())?,
                                               &p_pub_key.s, &r_delta, &p_pub_key.n, &mut ctx)?;
@@ -900,12 +3045,9 @@ impl ProofBuilder {
         }
 
         r_tilde.insert("DELTA".to_string(), bn_rand(LARGE_RTILDE)?);
-        let alpha_tilde = bn_rand(LARGE_ALPHATILDE)?;
-
-        let mj = m_tilde.get(k.as_str())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", k)))?;
+        let alpha_tilde = bn_rand(profile.alphatilde_bits())?;
 
-        let tau_list = calc_tge(&p_pub_key, &u_tilde, &r_tilde, &mj, &alpha_tilde, &t)?;
+        let tau_list = calc_tge(p_pub_key, &u_tilde, &r_tilde, &mj, &alpha_tilde, &t)?;
 
         let primary_predicate_ge_init_proof = PrimaryPredicateGEInitProof {
             c_list,
@@ -919,12 +3061,12 @@ impl ProofBuilder {
             t
         };
 
-        trace!("ProofBuilder::_init_ge_proof: <<< primary_predicate_ge_init_proof: {:?}", primary_predicate_ge_init_proof);
+        trace!("ProofBuilder::_init_ge_proof_for_delta: <<< primary_predicate_ge_init_proof: {:?}", primary_predicate_ge_init_proof);
 
         Ok(primary_predicate_ge_init_proof)
     }
 
-    fn _finalize_eq_proof(master_secret: &BigNumber,
+    fn _finalize_eq_proof(master_secret: Option<&BigNumber>,
                           init_proof: &PrimaryEqualInitProof,
                           challenge: &BigNumber,
                           cred_schema: &CredentialSchema,
@@ -945,18 +3087,14 @@ impl ProofBuilder {
 
         let mut m: HashMap<String, BigNumber> = HashMap::new();
 
-        let unrevealed_attrs: HashSet<String> =
-            cred_schema.attrs
-                .difference(&sub_proof_request.revealed_attrs)
-                .cloned()
-                .collect::<HashSet<String>>();
+        let unrevealed_attrs = sub_proof_request.unrevealed_attrs(cred_schema);
 
         for k in unrevealed_attrs.iter() {
             let cur_mtilde = init_proof.m_tilde.get(k)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in init_proof.mtilde", k)))?;
+                .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "init_proof.mtilde".to_string() })?;
 
             let cur_val = cred_values.attrs_values.get(k)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in attributes_values", k)))?;
+                .ok_or(IndyCryptoError::MissingAttribute { attr: k.to_string(), context: "attributes_values".to_string() })?;
 
             let val = challenge
                 .mul(&cur_val, Some(&mut ctx))?
@@ -965,9 +3103,13 @@ impl ProofBuilder {
             m.insert(k.clone(), val);
         }
 
-        let m1 = challenge
-            .mul(&master_secret, Some(&mut ctx))?
-            .add(&init_proof.m1_tilde)?;
+        // Response for master secret is not revealed for credentials without it as it would disclose m1_tilde
+        let m1 = match master_secret {
+            Some(master_secret) => challenge
+                .mul(master_secret, Some(&mut ctx))?
+                .add(&init_proof.m1_tilde)?,
+            None => BigNumber::new()?
+        };
 
         let m2 = challenge
             .mul(&init_proof.m2, Some(&mut ctx))?
@@ -1005,6 +3147,21 @@ impl ProofBuilder {
                           eq_proof: &PrimaryEqualProof) -> Result<PrimaryPredicateGEProof, IndyCryptoError> {
         trace!("ProofBuilder::_finalize_ge_proof: >>> c_h: {:?}, init_proof: {:?}, eq_proof: {:?}", c_h, init_proof, eq_proof);
 
+        let mj = eq_proof.m.get(&init_proof.predicate.attr_name)
+            .ok_or(IndyCryptoError::MissingAttribute { attr: init_proof.predicate.attr_name.to_string(), context: "eq_proof.m".to_string() })?;
+
+        let primary_predicate_ge_proof = ProofBuilder::_finalize_ge_proof_for_mj(c_h, init_proof, mj)?;
+
+        trace!("ProofBuilder::_finalize_ge_proof: <<< primary_predicate_ge_proof: {:?}", primary_predicate_ge_proof);
+
+        Ok(primary_predicate_ge_proof)
+    }
+
+    fn _finalize_ge_proof_for_mj(c_h: &BigNumber,
+                                 init_proof: &PrimaryPredicateGEInitProof,
+                                 mj: &BigNumber) -> Result<PrimaryPredicateGEProof, IndyCryptoError> {
+        trace!("ProofBuilder::_finalize_ge_proof_for_mj: >>> c_h: {:?}, init_proof: {:?}, mj: {:?}", c_h, init_proof, mj);
+
         let mut ctx = BigNumber::new_context()?;
         let mut u: HashMap<String, BigNumber> = HashMap::new();
         let mut r: HashMap<String, BigNumber> = HashMap::new();
@@ -1047,18 +3204,38 @@ impl ProofBuilder {
         let primary_predicate_ge_proof = PrimaryPredicateGEProof {
             u,
             r,
-            mj: eq_proof.m[&init_proof.predicate.attr_name].clone()?,
+            mj: mj.clone()?,
             alpha,
             t: clone_bignum_map(&init_proof.t)?,
             predicate: init_proof.predicate.clone()
         };
 
-        trace!("ProofBuilder::_finalize_ge_proof: <<< primary_predicate_ge_proof: {:?}", primary_predicate_ge_proof);
+        trace!("ProofBuilder::_finalize_ge_proof_for_mj: <<< primary_predicate_ge_proof: {:?}", primary_predicate_ge_proof);
 
         Ok(primary_predicate_ge_proof)
     }
 
-    fn _finalize_primary_proof(master_secret: &BigNumber,
+    fn _finalize_range_proof(c_h: &BigNumber,
+                             init_proof: &PrimaryPredicateRangeInitProof) -> Result<PrimaryPredicateRangeProof, IndyCryptoError> {
+        trace!("ProofBuilder::_finalize_range_proof: >>> c_h: {:?}, init_proof: {:?}", c_h, init_proof);
+
+        let gamma = bignum_to_scalar(c_h)?
+            .mul_mod(&init_proof.gamma)?
+            .add_mod(&init_proof.gamma_tilde)?;
+
+        let primary_predicate_range_proof = PrimaryPredicateRangeProof {
+            predicate: init_proof.predicate.clone(),
+            commitment: init_proof.commitment,
+            gamma,
+            range_proof: init_proof.range_proof.clone()
+        };
+
+        trace!("ProofBuilder::_finalize_range_proof: <<< primary_predicate_range_proof: {:?}", primary_predicate_range_proof);
+
+        Ok(primary_predicate_range_proof)
+    }
+
+    fn _finalize_primary_proof(master_secret: Option<&BigNumber>,
                                init_proof: &PrimaryInitProof,
                                challenge: &BigNumber,
                                cred_schema: &CredentialSchema,
@@ -1075,7 +3252,13 @@ impl ProofBuilder {
             ge_proofs.push(ge_proof);
         }
 
-        let primary_proof = PrimaryProof { eq_proof, ge_proofs };
+        let mut range_proofs: Vec<PrimaryPredicateRangeProof> = Vec::new();
+
+        for init_range_proof in init_proof.range_proofs.iter() {
+            range_proofs.push(ProofBuilder::_finalize_range_proof(challenge, init_range_proof)?);
+        }
+
+        let primary_proof = PrimaryProof { eq_proof, ge_proofs, linear_ge_proofs: Vec::new(), range_proofs };
 
         trace!("ProofBuilder::_finalize_primary_proof: <<< primary_proof: {:?}", primary_proof);
 
@@ -1217,7 +3400,9 @@ impl ProofBuilder {
 
         let non_revoc_proof = NonRevocProof {
             x_list: NonRevocProofXList::from_list(x_list),
-            c_list: init_proof.c_list.clone()
+            c_list: init_proof.c_list.clone(),
+            timestamp: init_proof.timestamp,
+            rev_reg_id: init_proof.rev_reg_id.clone()
         };
 
         trace!("ProofBuilder::_finalize_non_revocation_proof: <<< non_revoc_proof: {:?}", non_revoc_proof);
@@ -1230,6 +3415,65 @@ impl ProofBuilder {
 mod tests {
     use super::*;
     use cl::issuer;
+    use cl::issuer::Issuer;
+
+    #[test]
+    fn bind_witness_works() {
+        let credential_signature = issuer::mocks::credential();
+        let witness = issuer::mocks::witness();
+
+        let bound_witness = Prover::bind_witness(&witness, &credential_signature, "rev_reg_1").unwrap();
+        let unbound_witness = Prover::unbind_checked(&bound_witness, &credential_signature, "rev_reg_1").unwrap();
+
+        assert_eq!(witness.omega, unbound_witness.omega);
+    }
+
+    #[test]
+    fn unbind_checked_works_for_other_registry() {
+        let credential_signature = issuer::mocks::credential();
+        let bound_witness = Prover::bind_witness(&issuer::mocks::witness(), &credential_signature, "rev_reg_1").unwrap();
+
+        match Prover::unbind_checked(&bound_witness, &credential_signature, "rev_reg_2") {
+            Err(IndyCryptoError::AnoncredsWitnessMismatch(_)) => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn unbind_checked_works_for_other_credential() {
+        let credential_signature = issuer::mocks::credential();
+        let bound_witness = Prover::bind_witness(&issuer::mocks::witness(), &credential_signature, "rev_reg_1").unwrap();
+
+        let mut other_credential_signature = issuer::mocks::credential();
+        other_credential_signature.r_credential.as_mut().unwrap().i += 1;
+
+        match Prover::unbind_checked(&bound_witness, &other_credential_signature, "rev_reg_1") {
+            Err(IndyCryptoError::AnoncredsWitnessMismatch(_)) => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn add_sub_proof_request_with_rev_reg_id_works_for_other_registry() {
+        let credential_signature = issuer::mocks::credential();
+        let bound_witness = Prover::bind_witness(&issuer::mocks::witness(), &credential_signature, "rev_reg_1").unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        let res = proof_builder.add_sub_proof_request_with_rev_reg_id(&mocks::sub_proof_request(),
+                                                                      &issuer::mocks::credential_schema(),
+                                                                      &credential_signature,
+                                                                      &issuer::mocks::credential_values(),
+                                                                      &issuer::mocks::credential_public_key(),
+                                                                      "rev_reg_2",
+                                                                      &issuer::mocks::revocation_registry(),
+                                                                      &bound_witness,
+                                                                      None);
+        match res {
+            Err(IndyCryptoError::AnoncredsWitnessMismatch(_)) => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+        assert!(proof_builder.init_proofs.is_empty());
+    }
 
     #[test]
     fn generate_master_secret_works() {
@@ -1246,10 +3490,23 @@ mod tests {
         let pk = issuer::mocks::credential_primary_public_key();
         let ms = mocks::master_secret();
 
-        let blinded_primary_master_secret = Prover::_generate_blinded_primary_master_secret(&pk, &ms).unwrap();
+        let blinded_primary_master_secret = Prover::_generate_blinded_primary_master_secret(&pk, Some(&ms)).unwrap();
         assert_eq!(blinded_primary_master_secret, mocks::primary_blinded_master_secret_data());
     }
 
+    #[test]
+    fn generate_blinded_primary_master_secret_works_for_key_without_master_secret() {
+        MockHelper::inject();
+
+        let mut pk = issuer::mocks::credential_primary_public_key();
+        assert!(Prover::_generate_blinded_primary_master_secret(&pk, None).is_err());
+
+        pk.without_master_secret = true;
+        let blinded_primary_master_secret = Prover::_generate_blinded_primary_master_secret(&pk, None).unwrap();
+        let expected_u = pk.s.mod_exp(&blinded_primary_master_secret.v_prime, &pk.n, None).unwrap();
+        assert_eq!(expected_u, blinded_primary_master_secret.u);
+    }
+
     #[test]
     fn generate_blinded_revocation_master_secret_works() {
         MockHelper::inject();
@@ -1263,7 +3520,7 @@ mod tests {
         MockHelper::inject();
 
         let pk = issuer::mocks::credential_public_key();
-        let key_correctness_proof = issuer::mocks::credential_key_correctness_proof();
+        let key_correctness_proof = issuer::mocks::legacy_credential_key_correctness_proof();
         let ms = super::mocks::master_secret();
         let nonce = new_nonce().unwrap();
 
@@ -1277,6 +3534,62 @@ mod tests {
         assert_eq!(blinded_master_secret_correctness_proof, mocks::blinded_master_secret_correctness_proof())
     }
 
+    #[test]
+    fn check_credential_key_correctness_proof_works() {
+        let (cred_pub_key, _, key_correctness_proof) = Issuer::new_credential_def(&issuer::mocks::credential_schema(), true).unwrap();
+        assert_eq!(CREDENTIAL_KEY_CORRECTNESS_PROOF_VERSION, key_correctness_proof.version());
+        Prover::_check_credential_key_correctness_proof(&cred_pub_key, &key_correctness_proof).unwrap();
+    }
+
+    #[test]
+    fn check_credential_key_correctness_proof_works_for_legacy_version() {
+        let pk = issuer::mocks::credential_public_key();
+        let key_correctness_proof = issuer::mocks::legacy_credential_key_correctness_proof();
+        Prover::_check_credential_key_correctness_proof(&pk, &key_correctness_proof).unwrap();
+    }
+
+    #[test]
+    fn check_credential_offer_works() {
+        let pk = issuer::mocks::credential_public_key();
+        let key_correctness_proof = issuer::mocks::legacy_credential_key_correctness_proof();
+
+        let credential_offer = Issuer::new_credential_offer(&pk, &key_correctness_proof).unwrap();
+        assert_eq!(pk.fingerprint().unwrap(), credential_offer.key_fingerprint());
+
+        Prover::check_credential_offer(&credential_offer, &pk).unwrap();
+    }
+
+    #[test]
+    fn check_credential_offer_works_for_other_key() {
+        let pk = issuer::mocks::credential_public_key();
+        let key_correctness_proof = issuer::mocks::legacy_credential_key_correctness_proof();
+        let mut credential_offer = Issuer::new_credential_offer(&pk, &key_correctness_proof).unwrap();
+        credential_offer.key_fingerprint = "0".repeat(64);
+
+        assert!(Prover::check_credential_offer(&credential_offer, &pk).is_err());
+    }
+
+    #[test]
+    fn check_credential_key_correctness_proof_works_for_missed_revocation_part() {
+        let (cred_pub_key, _, mut key_correctness_proof) = Issuer::new_credential_def(&issuer::mocks::credential_schema(), true).unwrap();
+        key_correctness_proof.r_key_proof = None;
+        assert!(Prover::_check_credential_key_correctness_proof(&cred_pub_key, &key_correctness_proof).is_err());
+    }
+
+    #[test]
+    fn check_credential_key_correctness_proof_works_for_other_rms() {
+        let (mut cred_pub_key, _, key_correctness_proof) = Issuer::new_credential_def(&issuer::mocks::credential_schema(), false).unwrap();
+        cred_pub_key.p_key.rms = cred_pub_key.p_key.z.clone().unwrap();
+        assert!(Prover::_check_credential_key_correctness_proof(&cred_pub_key, &key_correctness_proof).is_err());
+    }
+
+    #[test]
+    fn check_credential_key_correctness_proof_works_for_unsupported_version() {
+        let (cred_pub_key, _, mut key_correctness_proof) = Issuer::new_credential_def(&issuer::mocks::credential_schema(), false).unwrap();
+        key_correctness_proof.version = CREDENTIAL_KEY_CORRECTNESS_PROOF_VERSION + 1;
+        assert!(Prover::_check_credential_key_correctness_proof(&cred_pub_key, &key_correctness_proof).is_err());
+    }
+
     #[test]
     fn process_primary_credential_works() {
         MockHelper::inject();
@@ -1308,6 +3621,111 @@ mod tests {
         assert_eq!(mocks::primary_credential(), credential_signature.p_credential);
     }
 
+    #[test]
+    fn process_credential_signatures_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let mut credential_values = Vec::new();
+        for age in &["28", "35"] {
+            let mut credential_values_builder = CredentialValuesBuilder::new().unwrap();
+            credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+            credential_values_builder.add_value("age", age).unwrap();
+            credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+            credential_values_builder.add_value("height", "175").unwrap();
+            credential_values.push(credential_values_builder.finalize().unwrap());
+        }
+
+        let mut signatures = Vec::new();
+        for values in credential_values.iter() {
+            let master_secret_blinding_nonce = new_nonce().unwrap();
+            let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+                Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+            let cred_issuance_nonce = new_nonce().unwrap();
+            let (cred_signature, signature_correctness_proof) =
+                Issuer::sign_credential(mocks::PROVER_DID,
+                                        &blinded_master_secret,
+                                        &blinded_master_secret_correctness_proof,
+                                        &master_secret_blinding_nonce,
+                                        &cred_issuance_nonce,
+                                        values,
+                                        &cred_pub_key,
+                                        &cred_priv_key).unwrap();
+            signatures.push((cred_signature, signature_correctness_proof, master_secret_blinding_data, cred_issuance_nonce));
+        }
+
+        {
+            let mut credentials: Vec<IssuedCredential> = signatures.iter_mut().zip(credential_values.iter())
+                .map(|(&mut (ref mut credential_signature, ref signature_correctness_proof, ref master_secret_blinding_data, ref nonce), credential_values)|
+                    IssuedCredential {
+                        credential_signature,
+                        credential_values,
+                        signature_correctness_proof,
+                        master_secret_blinding_data,
+                        nonce,
+                        rev_reg: None,
+                        witness: None
+                    })
+                .collect();
+
+            Prover::process_credential_signatures(&mut credentials, &master_secret, &cred_pub_key, None).unwrap();
+        }
+
+        for (&(ref cred_signature, _, _, _), values) in signatures.iter().zip(credential_values.iter()) {
+            assert!(Prover::check_credential_signature(cred_signature, values, &cred_pub_key, &master_secret).unwrap());
+        }
+
+        // Signatures are already processed, so batch with the same blinding data is rejected
+        let mut credentials: Vec<IssuedCredential> = signatures.iter_mut().zip(credential_values.iter())
+            .map(|(&mut (ref mut credential_signature, ref signature_correctness_proof, ref master_secret_blinding_data, ref nonce), credential_values)|
+                IssuedCredential {
+                    credential_signature,
+                    credential_values,
+                    signature_correctness_proof,
+                    master_secret_blinding_data,
+                    nonce,
+                    rev_reg: None,
+                    witness: None
+                })
+            .collect();
+
+        assert!(Prover::process_credential_signatures(&mut credentials, &master_secret, &cred_pub_key, None).is_err());
+    }
+
+    #[test]
+    fn check_credential_signature_works() {
+        let credential_signature = mocks::credential();
+        let credential_values = issuer::mocks::credential_values();
+        let pk = issuer::mocks::credential_public_key();
+        let master_secret = mocks::master_secret();
+
+        assert!(Prover::check_credential_signature(&credential_signature, &credential_values, &pk, &master_secret).unwrap());
+    }
+
+    #[test]
+    fn check_credential_signature_works_for_corrupted_signature() {
+        let mut credential_signature = mocks::credential();
+        let credential_values = issuer::mocks::credential_values();
+        let pk = issuer::mocks::credential_public_key();
+        let master_secret = mocks::master_secret();
+
+        credential_signature.p_credential.v.add_word(1).unwrap();
+
+        assert!(!Prover::check_credential_signature(&credential_signature, &credential_values, &pk, &master_secret).unwrap());
+    }
+
+    #[test]
+    fn check_credential_signature_works_for_other_master_secret() {
+        let credential_signature = mocks::credential();
+        let credential_values = issuer::mocks::credential_values();
+        let pk = issuer::mocks::credential_public_key();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        assert!(!Prover::check_credential_signature(&credential_signature, &credential_values, &pk, &master_secret).unwrap());
+    }
+
     #[test]
     fn init_eq_proof_works() {
         MockHelper::inject();
@@ -1366,6 +3784,31 @@ mod tests {
         assert_eq!(mocks::primary_init_proof(), init_proof);
     }
 
+    #[test]
+    fn init_predicate_proofs_works_for_seeded_rng() {
+        let pk = issuer::mocks::credential_primary_public_key();
+        let init_eq_proof = mocks::primary_equal_init_proof();
+        let credential_values = issuer::mocks::credential_values();
+        let predicate = mocks::predicate();
+        let predicates = vec![(&predicate, vec![1; PREDICATE_SEED_LEN]), (&predicate, vec![2; PREDICATE_SEED_LEN])];
+
+        let init_proofs = ProofBuilder::_init_predicate_proofs(&pk, &init_eq_proof.m_tilde, &credential_values, &predicates).unwrap();
+        assert_eq!(2, init_proofs.len());
+
+        for (&(predicate, ref seed), init_proof) in predicates.iter().zip(init_proofs.iter()) {
+            let expected = ProofBuilder::_init_predicate_proof(&pk, &init_eq_proof.m_tilde, &credential_values, predicate, seed).unwrap();
+            match (expected, init_proof) {
+                (PredicateInitProof::GE(ref expected), &PredicateInitProof::GE(ref init_proof)) => assert_eq!(expected, init_proof),
+                _ => panic!("Unexpected predicate init proof")
+            }
+        }
+
+        match (&init_proofs[0], &init_proofs[1]) {
+            (&PredicateInitProof::GE(ref first), &PredicateInitProof::GE(ref second)) => assert_ne!(first, second),
+            _ => panic!("Unexpected predicate init proof")
+        }
+    }
+
     #[test]
     fn finalize_eq_proof_works() {
         MockHelper::inject();
@@ -1377,7 +3820,7 @@ mod tests {
         let credential_schema = issuer::mocks::credential_schema();
         let sub_proof_request = mocks::sub_proof_request();
 
-        let eq_proof = ProofBuilder::_finalize_eq_proof(&ms.ms,
+        let eq_proof = ProofBuilder::_finalize_eq_proof(Some(&ms.ms),
                                                         &init_proof,
                                                         &c_h,
                                                         &credential_schema,
@@ -1412,7 +3855,7 @@ mod tests {
         let credential_values = issuer::mocks::credential_values();
         let sub_proof_request = mocks::sub_proof_request();
 
-        let proof = ProofBuilder::_finalize_primary_proof(&ms.ms,
+        let proof = ProofBuilder::_finalize_primary_proof(Some(&ms.ms),
                                                           &proof,
                                                           &c_h,
                                                           &credential_schema,
@@ -1457,6 +3900,38 @@ mod tests {
         assert_eq!(proof_tau_list.as_slice().unwrap(), proof_tau_list_calc.as_slice().unwrap());
     }
 
+    #[test]
+    fn create_tau_list_hat_values_works() {
+        let r_credential = issuer::mocks::revocation_credential();
+        let r_key = issuer::mocks::credential_revocation_public_key();
+        let rev_pub_key = issuer::mocks::revocation_key_public();
+        let rev_reg = issuer::mocks::revocation_registry();
+        let witness = issuer::mocks::witness();
+
+        let c_list_params = ProofBuilder::_gen_c_list_params(&r_credential).unwrap();
+        let proof_c_list = ProofBuilder::_create_c_list_values(&r_credential, &c_list_params, &r_key, &witness).unwrap();
+
+        let c_hash = GroupOrderElement::new().unwrap();
+
+        let tau_list_expected = create_tau_list_expected_values(&r_key, &rev_reg, &rev_pub_key, &proof_c_list).unwrap();
+        let tau_list_calc = create_tau_list_values(&r_key, &rev_reg, &c_list_params, &proof_c_list).unwrap();
+
+        let tau_list = NonRevocProofTauList {
+            t1: tau_list_expected.t1.mul(&c_hash).unwrap().add(&tau_list_calc.t1).unwrap(),
+            t2: tau_list_expected.t2.mul(&c_hash).unwrap().add(&tau_list_calc.t2).unwrap(),
+            t3: tau_list_expected.t3.pow(&c_hash).unwrap().mul(&tau_list_calc.t3).unwrap(),
+            t4: tau_list_expected.t4.pow(&c_hash).unwrap().mul(&tau_list_calc.t4).unwrap(),
+            t5: tau_list_expected.t5.mul(&c_hash).unwrap().add(&tau_list_calc.t5).unwrap(),
+            t6: tau_list_expected.t6.mul(&c_hash).unwrap().add(&tau_list_calc.t6).unwrap(),
+            t7: tau_list_expected.t7.pow(&c_hash).unwrap().mul(&tau_list_calc.t7).unwrap(),
+            t8: tau_list_expected.t8.pow(&c_hash).unwrap().mul(&tau_list_calc.t8).unwrap()
+        };
+
+        let tau_list_hat = create_tau_list_hat_values(&r_key, &rev_reg, &rev_pub_key, &c_hash, &c_list_params, &proof_c_list).unwrap();
+
+        assert_eq!(tau_list.as_slice().unwrap(), tau_list_hat.as_slice().unwrap());
+    }
+
     extern crate time;
 
     /*
@@ -1644,7 +4119,8 @@ pub mod mocks {
     pub fn primary_init_proof() -> PrimaryInitProof {
         PrimaryInitProof {
             eq_proof: primary_equal_init_proof(),
-            ge_proofs: vec![primary_ge_init_proof()]
+            ge_proofs: vec![primary_ge_init_proof()],
+            range_proofs: Vec::new()
         }
     }
 
@@ -1785,7 +4261,9 @@ pub mod mocks {
                          vec![1, 111, 80, 91, 53, 214, 139, 10, 197, 79, 134, 183, 50, 233, 244, 130, 80, 173, 167, 5, 130, 151, 183, 162, 97, 134, 246, 146, 37, 151, 103, 45, 68, 33, 204, 18, 157, 21, 98, 230, 225, 30, 162, 172, 75, 159, 115, 94, 72, 113, 153, 155, 117, 233, 95, 251, 29, 1, 149, 38, 117, 63, 112, 213, 48, 29, 3, 131, 238, 120, 48, 141, 105, 31, 127, 51, 176, 32, 203, 191, 155, 159, 91, 29, 87, 223, 30, 92, 146, 250, 182, 181, 155, 67, 253, 33, 165, 142, 195, 146, 180, 221, 83, 62, 46, 74, 29, 83, 175, 218, 132, 93, 42, 93, 105, 173, 189, 254, 193, 230, 113, 39, 45, 137, 143, 124, 190, 42, 19, 77, 13, 220, 137, 202, 128, 170, 10, 22, 37, 177, 200, 186, 3, 73, 171, 232, 81, 144, 36, 46, 70, 237, 208, 26, 84, 26, 141, 19, 37, 200, 83, 60, 27, 175, 96, 233, 246, 144, 137, 178, 140, 213, 13, 36, 137, 82, 107, 0, 239, 192, 187, 126, 20, 205, 40, 203, 33, 238, 88, 121, 132, 31, 87, 91, 65, 207, 144, 15, 249, 66, 58, 98, 64, 61, 236, 103, 203, 207, 20, 205, 48, 202, 247, 22, 248, 197, 188, 21, 178, 187, 193, 152, 164, 247, 53, 15, 33, 170, 145, 3, 213, 63, 205, 55, 158, 170, 62, 157, 207, 162, 117, 157, 215, 125, 94, 77, 251, 251, 25, 209, 207, 119, 16, 186, 210, 190, 83],
                          vec![1, 111, 80, 91, 53, 214, 139, 10, 197, 79, 134, 183, 50, 233, 244, 130, 80, 173, 167, 5, 130, 151, 183, 162, 97, 134, 246, 146, 37, 151, 103, 45, 68, 33, 204, 18, 157, 21, 98, 230, 225, 30, 162, 172, 75, 159, 115, 94, 72, 113, 153, 155, 117, 233, 95, 251, 29, 1, 149, 38, 117, 63, 112, 213, 48, 29, 3, 131, 238, 120, 48, 141, 105, 31, 127, 51, 176, 32, 203, 191, 155, 159, 91, 29, 87, 223, 30, 92, 146, 250, 182, 181, 155, 67, 253, 33, 165, 142, 195, 146, 180, 221, 83, 62, 46, 74, 29, 83, 175, 218, 132, 93, 42, 93, 105, 173, 189, 254, 193, 230, 113, 39, 45, 137, 143, 124, 190, 42, 19, 77, 13, 220, 137, 202, 128, 170, 10, 22, 37, 177, 200, 186, 3, 73, 171, 232, 81, 144, 36, 46, 70, 237, 208, 26, 84, 26, 141, 19, 37, 200, 83, 60, 27, 175, 96, 233, 246, 144, 137, 178, 140, 213, 13, 36, 137, 82, 107, 0, 239, 192, 187, 126, 20, 205, 40, 203, 33, 238, 88, 121, 132, 31, 87, 91, 65, 207, 144, 15, 249, 66, 58, 98, 64, 61, 236, 103, 203, 207, 20, 205, 48, 202, 247, 22, 248, 197, 188, 21, 178, 187, 193, 152, 164, 247, 53, 15, 33, 170, 145, 3, 213, 63, 205, 55, 158, 170, 62, 157, 207, 162, 117, 157, 215, 125, 94, 77, 251, 251, 25, 209, 207, 119, 16, 186, 210, 190, 83],
                          vec![1, 185, 37, 77, 23, 245, 214, 239, 127, 18, 101, 63, 229, 201, 171, 193, 32, 182, 124, 45, 15, 127, 58, 172, 226, 30, 246, 70, 33, 19, 117, 183, 29, 157, 209, 237, 41, 58, 208, 4, 105, 26, 73, 26, 69, 72, 21, 78, 106, 28, 72, 117, 102, 144, 199, 148, 3, 98, 81, 251, 246, 106, 50, 235, 129, 14, 186, 108, 216, 29, 41, 207, 233, 7, 179, 86, 224, 230, 187, 138, 125, 62, 68, 31, 66, 147, 205, 93, 100, 9, 134, 225, 210, 57, 36, 71, 134, 26, 179, 85, 37, 194, 32, 137, 91, 4, 91, 214, 220, 134, 173, 148, 14, 95, 209, 232, 79, 87, 12, 180, 217, 148, 240, 242, 190, 36, 229, 189, 16, 208, 75, 176, 153, 239, 212, 255, 45, 42, 250, 234, 139, 40, 104, 74, 21, 30, 184, 221, 126, 185, 23, 69, 114, 104, 249, 242, 248, 210, 97, 100, 141, 61, 176, 93, 200, 148, 152, 138, 31, 66, 99, 61, 237, 210, 42, 205, 60, 241, 92, 247, 1, 146, 203, 116, 237, 0, 171, 235, 250, 128, 74, 56, 223, 65, 189, 176, 91, 243, 174, 2, 111, 216, 233, 227, 28, 22, 41, 102, 225, 1, 21, 156, 212, 16, 243, 9, 94, 61, 246, 153, 193, 243, 188, 187, 154, 109, 168, 36, 89, 48, 236, 113, 74, 179, 158, 103, 51, 38, 15, 148, 18, 89, 218, 144, 71, 198, 8, 144, 104, 135, 160, 224, 98, 243, 106, 228, 198]],
-            c_hash: BigNumber::from_dec("63841489063440422591549130255324272391231497635167479821265935688468807059914").unwrap()
+            c_hash: BigNumber::from_dec("63841489063440422591549130255324272391231497635167479821265935688468807059914").unwrap(),
+            challenge_hash: ChallengeHash::Sha256,
+            t_list: Vec::new()
         }
     }
 
@@ -1828,7 +4306,26 @@ pub mod mocks {
     pub fn primary_proof() -> PrimaryProof {
         PrimaryProof {
             eq_proof: eq_proof(),
-            ge_proofs: vec![ge_proof()]
+            ge_proofs: vec![ge_proof()],
+            linear_ge_proofs: Vec::new(),
+            range_proofs: Vec::new()
+        }
+    }
+
+    pub fn proof() -> Proof {
+        Proof {
+            proofs: vec![SubProof {
+                primary_proof: primary_proof(),
+                non_revoc_proof: None,
+                key_id: None
+            }],
+            aggregated_proof: aggregated_proof(),
+            non_ownership_proofs: Vec::new(),
+            verifiable_encryptions: Vec::new(),
+            pseudonyms: Vec::new(),
+            nonces: Vec::new(),
+            #[cfg(feature = "sign_sodium")]
+            device_binding: None
         }
     }
 
@@ -1857,7 +4354,8 @@ pub mod mocks {
         Predicate {
             attr_name: "age".to_owned(),
             p_type: PredicateType::GE,
-            value: 18
+            value: 18,
+            proof_version: PredicateProofVersion::FourSquares
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,596 @@
+use bn::BigNumber;
+use cl::*;
+use cl::helpers::transform_u32_to_array_of_u8;
+use errors::IndyCryptoError;
+use pair::*;
+use range_proof::bignum_to_scalar;
+use sigma::{PointG1Group, SigmaProof, Statement};
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use sha2::{Digest, Sha256};
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Signature scheme of credential definition.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum CredentialSignatureBackend {
+    /// Camenisch-Lysyanskaya signatures in RSA group. Support predicates and revocation.
+    CL,
+    /// Pointcheval-Sanders signatures over pairing curve. Public key and proof contain
+    /// a few curve points and a group order element per attribute, but only revealed attributes are supported.
+    PS
+}
+
+/// Credential definition entities created by `Issuer::new_credential_def_with_backend`.
+#[derive(Debug)]
+pub enum CredentialDefinition {
+    CL {
+        pub_key: CredentialPublicKey,
+        priv_key: CredentialPrivateKey,
+        key_correctness_proof: CredentialKeyCorrectnessProof
+    },
+    PS {
+        pub_key: PsCredentialPublicKey,
+        priv_key: PsCredentialPrivateKey
+    }
+}
+
+impl CredentialDefinition {
+    pub fn backend(&self) -> CredentialSignatureBackend {
+        match *self {
+            CredentialDefinition::CL { .. } => CredentialSignatureBackend::CL,
+            CredentialDefinition::PS { .. } => CredentialSignatureBackend::PS
+        }
+    }
+}
+
+/// PS credential public key: `X~ = g~^x`, `Y~_i = g~^y_i` per attribute
+/// and master secret base in both groups (`Y_ms = g^y_ms` is used for blinding).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PsCredentialPublicKey {
+    g: PointG1,
+    g_tilde: PointG2,
+    x_tilde: PointG2,
+    y_ms: PointG1,
+    y_ms_tilde: PointG2,
+    y_tilde: BTreeMap<String /* attr_name */, PointG2>
+}
+
+impl PsCredentialPublicKey {
+    pub fn attrs(&self) -> BTreeSet<String> {
+        self.y_tilde.keys().cloned().collect()
+    }
+
+    /// Checks that master secret bases of both groups have the same discrete logarithm,
+    /// so blinded master secret doesn't leak it.
+    pub fn is_valid(&self) -> Result<bool, IndyCryptoError> {
+        Ok(!self.g.is_inf()? && !self.y_ms.is_inf()? &&
+            Pair::pair(&self.y_ms, &self.g_tilde)?.eq(&Pair::pair(&self.g, &self.y_ms_tilde)?))
+    }
+}
+
+impl JsonEncodable for PsCredentialPublicKey {}
+
+impl<'a> JsonDecodable<'a> for PsCredentialPublicKey {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsCredentialPrivateKey {
+    x: GroupOrderElement,
+    y: BTreeMap<String /* attr_name */, GroupOrderElement>
+}
+
+impl JsonEncodable for PsCredentialPrivateKey {}
+
+impl<'a> JsonDecodable<'a> for PsCredentialPrivateKey {}
+
+/// Commitment `C = g^t * Y_ms^ms` to master secret.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsBlindedMasterSecret {
+    c: PointG1
+}
+
+impl JsonEncodable for PsBlindedMasterSecret {}
+
+impl<'a> JsonDecodable<'a> for PsBlindedMasterSecret {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsMasterSecretBlindingData {
+    t: GroupOrderElement
+}
+
+impl JsonEncodable for PsMasterSecretBlindingData {}
+
+impl<'a> JsonDecodable<'a> for PsMasterSecretBlindingData {}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsBlindedMasterSecretCorrectnessProof {
+    proof: SigmaProof<GroupOrderElement>
+}
+
+impl JsonEncodable for PsBlindedMasterSecretCorrectnessProof {}
+
+impl<'a> JsonDecodable<'a> for PsBlindedMasterSecretCorrectnessProof {}
+
+/// PS credential signature `(sigma_1, sigma_2)` where `sigma_2 = sigma_1^(x + sum(y_i * m_i) + y_ms * ms)`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsCredentialSignature {
+    sigma_1: PointG1,
+    sigma_2: PointG1
+}
+
+impl JsonEncodable for PsCredentialSignature {}
+
+impl<'a> JsonDecodable<'a> for PsCredentialSignature {}
+
+/// Proof of knowledge of re-randomized PS credential signature that discloses revealed attributes.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PsProof {
+    sigma_1: PointG1,
+    sigma_2: PointG1,
+    c: GroupOrderElement,
+    t_response: GroupOrderElement,
+    ms_response: GroupOrderElement,
+    m_responses: BTreeMap<String /* attr_name */, GroupOrderElement>,
+    revealed_attrs: BTreeMap<String /* attr_name */, BigNumber>
+}
+
+impl PsProof {
+    pub fn revealed_attrs(&self) -> &BTreeMap<String, BigNumber> {
+        &self.revealed_attrs
+    }
+}
+
+impl JsonEncodable for PsProof {}
+
+impl<'a> JsonDecodable<'a> for PsProof {}
+
+pub fn new_keys(credential_schema: &CredentialSchema) -> Result<(PsCredentialPublicKey, PsCredentialPrivateKey), IndyCryptoError> {
+    let g = PointG1::new()?;
+    let g_tilde = PointG2::new()?;
+    let x = GroupOrderElement::new()?;
+    let y_ms = GroupOrderElement::new()?;
+
+    let mut y = BTreeMap::new();
+    let mut y_tilde = BTreeMap::new();
+
+    for attr in credential_schema.attrs.iter() {
+        let y_i = GroupOrderElement::new()?;
+        y_tilde.insert(attr.to_owned(), g_tilde.mul(&y_i)?);
+        y.insert(attr.to_owned(), y_i);
+    }
+
+    let pub_key = PsCredentialPublicKey {
+        g,
+        g_tilde,
+        x_tilde: g_tilde.mul(&x)?,
+        y_ms: g.mul(&y_ms)?,
+        y_ms_tilde: g_tilde.mul(&y_ms)?,
+        y_tilde
+    };
+
+    Ok((pub_key, PsCredentialPrivateKey { x, y }))
+}
+
+pub fn blind_master_secret(pub_key: &PsCredentialPublicKey,
+                           master_secret: &MasterSecret,
+                           nonce: &Nonce) -> Result<(PsBlindedMasterSecret,
+                                                     PsMasterSecretBlindingData,
+                                                     PsBlindedMasterSecretCorrectnessProof), IndyCryptoError> {
+    if !pub_key.is_valid()? {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid PS credential public key")));
+    }
+
+    let t = GroupOrderElement::new()?;
+    let ms = bignum_to_scalar(&master_secret.ms)?;
+
+    let c = PointG1::multi_scalar_mul(&[pub_key.g, pub_key.y_ms], &[t, ms])?;
+
+    let proof = _blinded_master_secret_statement(pub_key, &c)?
+        .prove(&PointG1Group::new(), &[&t, &ms], &[nonce.to_bytes()?])?;
+
+    Ok((PsBlindedMasterSecret { c },
+        PsMasterSecretBlindingData { t },
+        PsBlindedMasterSecretCorrectnessProof { proof }))
+}
+
+pub fn sign(blinded_master_secret: &PsBlindedMasterSecret,
+            blinded_master_secret_correctness_proof: &PsBlindedMasterSecretCorrectnessProof,
+            master_secret_blinding_nonce: &Nonce,
+            credential_values: &CredentialValues,
+            pub_key: &PsCredentialPublicKey,
+            priv_key: &PsCredentialPrivateKey) -> Result<PsCredentialSignature, IndyCryptoError> {
+    let valid = _blinded_master_secret_statement(pub_key, &blinded_master_secret.c)?
+        .verify(&PointG1Group::new(),
+                &blinded_master_secret_correctness_proof.proof,
+                &[master_secret_blinding_nonce.to_bytes()?])?;
+
+    if !valid {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid blinded master secret correctness proof")));
+    }
+
+    let values = _credential_values(credential_values, pub_key)?;
+
+    let mut exp = priv_key.x;
+    for (attr, value) in values.iter() {
+        let y_i = priv_key.y.get(attr)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in priv_key.y", attr)))?;
+        exp = exp.add_mod(&y_i.mul_mod(value)?)?;
+    }
+
+    let u = GroupOrderElement::new()?;
+
+    Ok(PsCredentialSignature {
+        sigma_1: pub_key.g.mul(&u)?,
+        sigma_2: pub_key.g.mul(&exp)?.add(&blinded_master_secret.c)?.mul(&u)?
+    })
+}
+
+pub fn process_signature(signature: &mut PsCredentialSignature,
+                         credential_values: &CredentialValues,
+                         master_secret_blinding_data: &PsMasterSecretBlindingData,
+                         master_secret: &MasterSecret,
+                         pub_key: &PsCredentialPublicKey) -> Result<(), IndyCryptoError> {
+    let unblinded = PsCredentialSignature {
+        sigma_1: signature.sigma_1,
+        sigma_2: signature.sigma_2.sub(&signature.sigma_1.mul(&master_secret_blinding_data.t)?)?
+    };
+
+    let values = _credential_values(credential_values, pub_key)?;
+    let ms = bignum_to_scalar(&master_secret.ms)?;
+
+    let mut x_m = pub_key.x_tilde.add(&pub_key.y_ms_tilde.mul(&ms)?)?;
+    for (attr, value) in values.iter() {
+        x_m = x_m.add(&pub_key.y_tilde[attr].mul(value)?)?;
+    }
+
+    if unblinded.sigma_1.is_inf()? ||
+        !Pair::pair(&unblinded.sigma_1, &x_m)?.eq(&Pair::pair(&unblinded.sigma_2, &pub_key.g_tilde)?) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid PS credential signature")));
+    }
+
+    *signature = unblinded;
+    Ok(())
+}
+
+pub fn prove(signature: &PsCredentialSignature,
+             credential_values: &CredentialValues,
+             master_secret: &MasterSecret,
+             pub_key: &PsCredentialPublicKey,
+             sub_proof_request: &SubProofRequest,
+             nonce: &Nonce) -> Result<PsProof, IndyCryptoError> {
+    _check_sub_proof_request(sub_proof_request, pub_key)?;
+
+    let values = _credential_values(credential_values, pub_key)?;
+    let ms = bignum_to_scalar(&master_secret.ms)?;
+
+    // Re-randomization (sigma_1^r, (sigma_2 * sigma_1^t)^r) turns sigma_2 into signature with additional g~^t term
+    let r = GroupOrderElement::new()?;
+    let t = GroupOrderElement::new()?;
+
+    let sigma_1 = signature.sigma_1.mul(&r)?;
+    let sigma_2 = signature.sigma_2.add(&signature.sigma_1.mul(&t)?)?.mul(&r)?;
+
+    let t_blinding = GroupOrderElement::new()?;
+    let ms_blinding = GroupOrderElement::new()?;
+    let mut m_blindings = BTreeMap::new();
+    let mut revealed_attrs = BTreeMap::new();
+
+    for attr in values.keys() {
+        if sub_proof_request.revealed_attrs.contains(attr) {
+            revealed_attrs.insert(attr.to_owned(), credential_values.attrs_values[attr].clone()?);
+        } else {
+            m_blindings.insert(attr.to_owned(), GroupOrderElement::new()?);
+        }
+    }
+
+    let t_value = _hidden_pairing(pub_key, &sigma_1, &t_blinding, &ms_blinding, &m_blindings)?;
+    let c = _challenge(&sigma_1, &sigma_2, &t_value, &revealed_attrs, nonce)?;
+
+    let mut m_responses = BTreeMap::new();
+    for (attr, blinding) in m_blindings.iter() {
+        m_responses.insert(attr.to_owned(), blinding.add_mod(&c.mul_mod(&values[attr])?)?);
+    }
+
+    Ok(PsProof {
+        sigma_1,
+        sigma_2,
+        c,
+        t_response: t_blinding.add_mod(&c.mul_mod(&t)?)?,
+        ms_response: ms_blinding.add_mod(&c.mul_mod(&ms)?)?,
+        m_responses,
+        revealed_attrs
+    })
+}
+
+pub fn verify(proof: &PsProof,
+              sub_proof_request: &SubProofRequest,
+              pub_key: &PsCredentialPublicKey,
+              nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+    _check_sub_proof_request(sub_proof_request, pub_key)?;
+
+    let revealed_attrs: HashSet<String> = proof.revealed_attrs.keys().cloned().collect();
+    let hidden_attrs: BTreeSet<String> = proof.m_responses.keys().cloned().collect();
+
+    if revealed_attrs != sub_proof_request.revealed_attrs ||
+        hidden_attrs.iter().any(|attr| revealed_attrs.contains(attr)) ||
+        revealed_attrs.len() + hidden_attrs.len() != pub_key.y_tilde.len() ||
+        hidden_attrs.iter().any(|attr| !pub_key.y_tilde.contains_key(attr)) ||
+        proof.sigma_1.is_inf()? {
+        return Ok(false);
+    }
+
+    let mut x_revealed = pub_key.x_tilde;
+    for (attr, value) in proof.revealed_attrs.iter() {
+        x_revealed = x_revealed.add(&pub_key.y_tilde[attr].mul(&_attr_to_scalar(attr, value)?)?)?;
+    }
+
+    // e(sigma_2, g~) / e(sigma_1, X~ * prod(Y~_i^m_i) for revealed) = e(sigma_1, g~^t * Y~_ms^ms * prod(Y~_j^m_j) for hidden)
+    let value = Pair::pair(&proof.sigma_2, &pub_key.g_tilde)?
+        .mul(&Pair::pair(&proof.sigma_1, &x_revealed)?.inverse()?)?;
+
+    let t_value = _hidden_pairing(pub_key, &proof.sigma_1, &proof.t_response, &proof.ms_response, &proof.m_responses)?
+        .mul(&value.pow(&proof.c)?.inverse()?)?;
+
+    Ok(_challenge(&proof.sigma_1, &proof.sigma_2, &t_value, &proof.revealed_attrs, nonce)? == proof.c)
+}
+
+// C = g^t * Y_ms^ms
+fn _blinded_master_secret_statement(pub_key: &PsCredentialPublicKey, c: &PointG1) -> Result<Statement<PointG1Group>, IndyCryptoError> {
+    let mut statement = Statement::new(2);
+    statement.add_relation(*c, vec![(pub_key.g, 0), (pub_key.y_ms, 1)])?;
+    Ok(statement)
+}
+
+// e(sigma_1, g~^t * Y~_ms^ms * prod(Y~_j^m_j))
+fn _hidden_pairing(pub_key: &PsCredentialPublicKey,
+                   sigma_1: &PointG1,
+                   t: &GroupOrderElement,
+                   ms: &GroupOrderElement,
+                   m: &BTreeMap<String, GroupOrderElement>) -> Result<Pair, IndyCryptoError> {
+    let mut bases = vec![pub_key.g_tilde, pub_key.y_ms_tilde];
+    let mut exps = vec![*t, *ms];
+
+    for (attr, value) in m.iter() {
+        bases.push(*pub_key.y_tilde.get(attr)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pub_key.y_tilde", attr)))?);
+        exps.push(*value);
+    }
+
+    Pair::pair(sigma_1, &PointG2::multi_scalar_mul(&bases, &exps)?)
+}
+
+fn _challenge(sigma_1: &PointG1,
+              sigma_2: &PointG1,
+              t_value: &Pair,
+              revealed_attrs: &BTreeMap<String, BigNumber>,
+              nonce: &Nonce) -> Result<GroupOrderElement, IndyCryptoError> {
+    let mut hasher = Sha256::default();
+    hasher.input(&sigma_1.to_bytes()?);
+    hasher.input(&sigma_2.to_bytes()?);
+    hasher.input(&t_value.to_bytes()?);
+
+    for (attr, value) in revealed_attrs.iter() {
+        let bytes = value.to_bytes()?;
+        hasher.input(&transform_u32_to_array_of_u8(attr.len() as u32));
+        hasher.input(attr.as_bytes());
+        hasher.input(&transform_u32_to_array_of_u8(bytes.len() as u32));
+        hasher.input(&bytes);
+    }
+
+    hasher.input(&nonce.to_bytes()?);
+
+    // Reduces hash modulo group order
+    GroupOrderElement::from_bytes(hasher.result().as_slice())?
+        .add_mod(&GroupOrderElement::from_bytes(&[])?)
+}
+
+fn _check_sub_proof_request(sub_proof_request: &SubProofRequest, pub_key: &PsCredentialPublicKey) -> Result<(), IndyCryptoError> {
+    if !sub_proof_request.predicates.is_empty() ||
+        !sub_proof_request.linear_predicates.is_empty() ||
+        !sub_proof_request.attr_value_checks.is_empty() ||
+        sub_proof_request.non_revoked.is_some() {
+        return Err(IndyCryptoError::InvalidStructure(format!("PS credentials support revealed attributes only")));
+    }
+
+    if let Some(attr) = sub_proof_request.revealed_attrs.iter().find(|attr| !pub_key.y_tilde.contains_key(*attr)) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Revealed attribute {} not found in credential public key", attr)));
+    }
+
+    Ok(())
+}
+
+fn _credential_values(credential_values: &CredentialValues,
+                      pub_key: &PsCredentialPublicKey) -> Result<BTreeMap<String, GroupOrderElement>, IndyCryptoError> {
+    if credential_values.attrs_values.len() != pub_key.y_tilde.len() {
+        return Err(IndyCryptoError::InvalidStructure(format!("Credential values don't correspond to credential public key")));
+    }
+
+    let mut values = BTreeMap::new();
+    for (attr, value) in credential_values.attrs_values.iter() {
+        if !pub_key.y_tilde.contains_key(attr) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pub_key.y_tilde", attr)));
+        }
+        values.insert(attr.to_owned(), _attr_to_scalar(attr, value)?);
+    }
+
+    Ok(values)
+}
+
+// Values are signed as group order elements, so they must be less than group order
+fn _attr_to_scalar(attr: &str, value: &BigNumber) -> Result<GroupOrderElement, IndyCryptoError> {
+    let order = BigNumber::from_bytes(&GroupOrderElement::order_bytes())?;
+
+    if value.is_negative()? || *value >= order {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Value of attribute {} is out of range of group order", attr)));
+    }
+
+    bignum_to_scalar(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::prover::Prover;
+    use cl::verifier::Verifier;
+
+    fn _credential_schema() -> CredentialSchema {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        credential_schema_builder.finalize().unwrap()
+    }
+
+    fn _credential_values() -> CredentialValues {
+        let mut credential_values_builder = CredentialValuesBuilder::new().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value("age", "28").unwrap();
+        credential_values_builder.add_value("height", "175").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    fn _issue(pub_key: &PsCredentialPublicKey,
+              priv_key: &PsCredentialPrivateKey,
+              master_secret: &MasterSecret,
+              credential_values: &CredentialValues) -> PsCredentialSignature {
+        let nonce = new_nonce().unwrap();
+        let (blinded_ms, blinding_data, blinded_ms_correctness_proof) = blind_master_secret(pub_key, master_secret, &nonce).unwrap();
+
+        let mut signature = sign(&blinded_ms, &blinded_ms_correctness_proof, &nonce, credential_values, pub_key, priv_key).unwrap();
+        process_signature(&mut signature, credential_values, &blinding_data, master_secret, pub_key).unwrap();
+        signature
+    }
+
+    fn _sub_proof_request(revealed_attrs: &[&str]) -> SubProofRequest {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        for attr in revealed_attrs {
+            sub_proof_request_builder.add_revealed_attr(attr).unwrap();
+        }
+        sub_proof_request_builder.finalize().unwrap()
+    }
+
+    #[test]
+    fn new_keys_works() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+
+        assert!(pub_key.is_valid().unwrap());
+        assert_eq!(4, pub_key.y_tilde.len());
+        assert_eq!(4, priv_key.y.len());
+    }
+
+    #[test]
+    fn sign_works_for_invalid_blinded_master_secret_correctness_proof() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let (blinded_ms, _, blinded_ms_correctness_proof) = blind_master_secret(&pub_key, &master_secret, &nonce).unwrap();
+
+        assert!(sign(&blinded_ms, &blinded_ms_correctness_proof, &new_nonce().unwrap(), &_credential_values(), &pub_key, &priv_key).is_err());
+    }
+
+    #[test]
+    fn process_signature_works_for_other_master_secret() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let nonce = new_nonce().unwrap();
+
+        let (blinded_ms, blinding_data, blinded_ms_correctness_proof) = blind_master_secret(&pub_key, &master_secret, &nonce).unwrap();
+        let mut signature = sign(&blinded_ms, &blinded_ms_correctness_proof, &nonce, &credential_values, &pub_key, &priv_key).unwrap();
+
+        let other_master_secret = Prover::new_master_secret().unwrap();
+        assert!(process_signature(&mut signature, &credential_values, &blinding_data, &other_master_secret, &pub_key).is_err());
+    }
+
+    #[test]
+    fn prove_and_verify_works() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+        let nonce = new_nonce().unwrap();
+
+        for revealed_attrs in [&[][..], &["age"][..], &["name", "sex", "age", "height"][..]].iter() {
+            let sub_proof_request = _sub_proof_request(revealed_attrs);
+            let proof = prove(&signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &nonce).unwrap();
+
+            assert!(verify(&proof, &sub_proof_request, &pub_key, &nonce).unwrap());
+            assert_eq!(revealed_attrs.len(), proof.revealed_attrs().len());
+        }
+    }
+
+    #[test]
+    fn verify_works_for_changed_revealed_value() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+        let nonce = new_nonce().unwrap();
+
+        let sub_proof_request = _sub_proof_request(&["age"]);
+        let mut proof = prove(&signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &nonce).unwrap();
+        proof.revealed_attrs.insert("age".to_string(), BigNumber::from_u32(29).unwrap());
+
+        assert!(!verify(&proof, &sub_proof_request, &pub_key, &nonce).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_nonce() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+
+        let sub_proof_request = _sub_proof_request(&["age"]);
+        let proof = prove(&signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &new_nonce().unwrap()).unwrap();
+
+        assert!(!verify(&proof, &sub_proof_request, &pub_key, &new_nonce().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_sub_proof_request() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+        let nonce = new_nonce().unwrap();
+
+        let proof = prove(&signature, &credential_values, &master_secret, &pub_key, &_sub_proof_request(&["age"]), &nonce).unwrap();
+
+        assert!(!verify(&proof, &_sub_proof_request(&["age", "height"]), &pub_key, &nonce).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_unknown_hidden_attr() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+        let nonce = new_nonce().unwrap();
+
+        let sub_proof_request = _sub_proof_request(&["age"]);
+        let mut proof = prove(&signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &nonce).unwrap();
+        let response = proof.m_responses.remove("height").unwrap();
+        proof.m_responses.insert("weight".to_string(), response);
+
+        assert!(!verify(&proof, &sub_proof_request, &pub_key, &nonce).unwrap());
+    }
+
+    #[test]
+    fn prove_works_for_predicates() {
+        let (pub_key, priv_key) = new_keys(&_credential_schema()).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = _credential_values();
+        let signature = _issue(&pub_key, &priv_key, &master_secret, &credential_values);
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert!(prove(&signature, &credential_values, &master_secret, &pub_key, &sub_proof_request, &new_nonce().unwrap()).is_err());
+    }
+}
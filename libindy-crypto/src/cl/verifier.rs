@@ -1,11 +1,25 @@
-use bn::BigNumber;
+use bls::{Generator, VerKey};
+use bn::{BigNumber, BigNumberContext};
 use cl::*;
-use cl::constants::{LARGE_E_START, ITERATION};
+use cl::constants::{LARGE_E_START, LARGE_ETILDE, LARGE_MTILDE, LARGE_UTILDE, LARGE_BATCH_EXP, ITERATION};
 use cl::helpers::*;
-use errors::IndyCryptoError;
-
-use std::collections::HashSet;
+use cl::ps;
+use cl::presentation::{Presentation, PresentationCredentialDef, ProofRequest};
+use commitments::get_pedersen_commitment;
+#[cfg(feature = "sign_sodium")]
+use ed25519;
+#[cfg(feature = "box_sodium")]
+use encryption::{PublicKey, SecretKey, SealedBox};
+use errors::{IndyCryptoError, RejectionReason};
+use pair::PointG1;
+use range_proof::{RANGE_BITS, bignum_to_scalar, commit, commitment_generators};
+use utils::json::JsonDecodable;
+use utils::logger::OperationTimer;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter::FromIterator;
+use std::ptr;
+use std::sync::Arc;
 
 /// Party that wants to check that prover has some credentials provided by issuer.
 pub struct Verifier {}
@@ -31,6 +45,28 @@ impl Verifier {
         Ok(res)
     }
 
+    /// Verifies proof of PS credential created by `Prover::new_ps_proof`.
+    /// Returns false if proof is invalid or doesn't disclose exactly revealed attributes of sub proof request.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof received from Prover.
+    /// * `sub_proof_request` - Requested attributes.
+    /// * `credential_pub_key` - PS credential public key.
+    /// * `nonce` - Proof request nonce.
+    pub fn verify_ps_proof(proof: &PsProof,
+                           sub_proof_request: &SubProofRequest,
+                           credential_pub_key: &PsCredentialPublicKey,
+                           nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        trace!("Verifier::verify_ps_proof: >>> proof: {:?}, sub_proof_request: {:?}, credential_pub_key: {:?}, nonce: {:?}",
+               proof, sub_proof_request, credential_pub_key, nonce);
+
+        let valid = ps::verify(proof, sub_proof_request, credential_pub_key, nonce)?;
+
+        trace!("Verifier::verify_ps_proof: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
     /// Creates and returns proof verifier.
     ///
     /// The purpose of `proof verifier` is check proof provided by Prover.
@@ -44,14 +80,217 @@ impl Verifier {
     pub fn new_proof_verifier() -> Result<ProofVerifier, IndyCryptoError> {
         Ok(ProofVerifier {
             credentials: Vec::new(),
+            non_ownership_requests: Vec::new(),
+            verifiable_encryption_requests: Vec::new(),
+            pseudonym_domains: Vec::new(),
+            same_holder_groups: Vec::new(),
+            #[cfg(feature = "sign_sodium")]
+            device_binding_request: None,
+            min_nonce_size: None,
+            observer: ProofObserverSlot::default()
         })
     }
+
+    /// Verifies proof of knowledge of master secret behind pseudonym for domain.
+    ///
+    /// # Arguments
+    /// * `master_secret_proof` - Master secret proof.
+    /// * `domain` - Domain of pseudonym.
+    /// * `nonce` - Nonce provided to prover.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let nonce = new_nonce().unwrap();
+    ///
+    /// let master_secret_proof = Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+    /// assert!(Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &nonce).unwrap());
+    /// assert!(!Verifier::verify_master_secret_proof(&master_secret_proof, "example.org", &nonce).unwrap());
+    /// ```
+    pub fn verify_master_secret_proof(master_secret_proof: &MasterSecretProof,
+                                      domain: &str,
+                                      nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        trace!("Verifier::verify_master_secret_proof: >>> master_secret_proof: {:?}, domain: {:?}, nonce: {:?}",
+               master_secret_proof, domain, nonce);
+
+        let pseudonym = &master_secret_proof.pseudonym;
+
+        if pseudonym.domain != domain {
+            return Ok(false);
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+        let p = Pseudonym::modulus()?;
+
+        if !pseudonym.is_in_subgroup(&mut ctx)? {
+            return Ok(false);
+        }
+
+        let t_hat = inverse_proof_value(&pseudonym.value, &p, "pseudonym", &mut ctx)?
+            .mod_exp(&master_secret_proof.c, &p, Some(&mut ctx))?
+            .mod_mul(
+                &Pseudonym::base(domain)?.mod_exp(&master_secret_proof.s, &p, Some(&mut ctx))?,
+                &p,
+                Some(&mut ctx)
+            )?;
+
+        let valid = MasterSecretProof::challenge(pseudonym, &t_hat, nonce)? == master_secret_proof.c;
+
+        trace!("Verifier::verify_master_secret_proof: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Checks that encoded attribute value corresponds to raw value with conventional encoding
+    /// (see `AttrValueEncoding::for_raw`).
+    ///
+    /// # Arguments
+    /// * `raw` - Raw attribute value.
+    /// * `encoded` - Encoded attribute value.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::bn::BigNumber;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// assert!(Verifier::check_encoding("28", &BigNumber::from_dec("28").unwrap()).unwrap());
+    /// assert!(!Verifier::check_encoding("Alex", &BigNumber::from_dec("28").unwrap()).unwrap());
+    /// ```
+    pub fn check_encoding(raw: &str, encoded: &BigNumber) -> Result<bool, IndyCryptoError> {
+        trace!("Verifier::check_encoding: >>> raw: {:?}, encoded: {:?}", raw, encoded);
+
+        let valid = AttrValueEncoding::for_raw(raw).encode(raw)? == *encoded;
+
+        trace!("Verifier::check_encoding: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Checks that raw values of revealed attribute bundle correspond to attributes revealed by sub proof.
+    /// Must be called only for proofs accepted by `ProofVerifier::verify`.
+    ///
+    /// # Arguments
+    /// * `bundle` - Revealed attribute bundle received from Prover.
+    /// * `proof` - Proof.
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests).
+    pub fn check_revealed_attribute_bundle(bundle: &RevealedAttributeBundle,
+                                           proof: &Proof,
+                                           sub_proof_idx: usize) -> Result<bool, IndyCryptoError> {
+        trace!("Verifier::check_revealed_attribute_bundle: >>> bundle: {:?}, proof: {:?}, sub_proof_idx: {:?}", bundle, proof, sub_proof_idx);
+
+        let proven_revealed_attrs = &proof.proofs.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof by index '{}' not found", sub_proof_idx)))?
+            .primary_proof.eq_proof.revealed_attrs;
+
+        let mut valid = true;
+        for (attr, revealed_attr) in bundle.attrs.iter() {
+            let proven_value = proven_revealed_attrs.get(attr)
+                .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "revealed attributes".to_string() })?;
+
+            if *proven_value != revealed_attr.encoded || revealed_attr.encoding.encode(&revealed_attr.raw)? != revealed_attr.encoded {
+                valid = false;
+                break;
+            }
+        }
+
+        trace!("Verifier::check_revealed_attribute_bundle: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Verifies presentation against full proof request policy: credential restrictions,
+    /// revealed attributes values claimed by presentation and proof itself.
+    /// Returns false if presentation doesn't satisfy proof request.
+    ///
+    /// # Arguments
+    /// * `presentation` - Presentation received from Prover.
+    /// * `proof_request` - Proof request sent to Prover.
+    /// * `credential_defs` - Credential definitions used by Prover keyed by referent from proof request.
+    /// * `nonce` - Proof request nonce.
+    pub fn verify_presentation(presentation: &Presentation,
+                               proof_request: &ProofRequest,
+                               credential_defs: &HashMap<String, PresentationCredentialDef>,
+                               nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        trace!("Verifier::verify_presentation: >>> presentation: {:?}, proof_request: {:?}, credential_defs: {:?}, nonce: {:?}",
+               presentation, proof_request, credential_defs, nonce);
+
+        let referents = proof_request.referents();
+        let proof = &presentation.proof;
+
+        if proof.proofs.len() != referents.len() || presentation.revealed_attrs.len() != referents.len() {
+            return Ok(false);
+        }
+
+        let mut proof_verifier = Verifier::new_proof_verifier()?;
+
+        for (referent, sub_proof) in referents.iter().zip(proof.proofs.iter()) {
+            let sub_proof_request = proof_request.sub_proof_request(referent)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request for referent '{}' not found", referent)))?;
+            let restrictions = proof_request.restrictions(referent);
+
+            let credential_def = credential_defs.get(referent)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Credential definition for referent '{}' not found", referent)))?;
+
+            if !restrictions.is_allowed(credential_def.issuer_key_id, credential_def.schema_id) {
+                return Ok(false);
+            }
+
+            if restrictions.non_revoked() {
+                if credential_def.rev_key_pub.is_none() || credential_def.rev_reg.is_none() {
+                    return Err(IndyCryptoError::InvalidStructure(
+                        format!("Revocation registry for referent '{}' is required by restrictions", referent)));
+                }
+
+                if sub_proof.non_revoc_proof.is_none() {
+                    return Ok(false);
+                }
+            }
+
+            let revealed_attrs = match presentation.revealed_attrs.get(referent) {
+                Some(revealed_attrs) => revealed_attrs,
+                None => return Ok(false)
+            };
+
+            let proven_revealed_attrs = &sub_proof.primary_proof.eq_proof.revealed_attrs;
+
+            if revealed_attrs.len() != sub_proof_request.revealed_attrs.len() ||
+                revealed_attrs.len() != proven_revealed_attrs.len() ||
+                revealed_attrs.iter().any(|(attr, value)|
+                    !sub_proof_request.revealed_attrs.contains(attr) || proven_revealed_attrs.get(attr) != Some(value)) {
+                return Ok(false);
+            }
+
+            proof_verifier.add_sub_proof_request(sub_proof_request,
+                                                 credential_def.credential_schema,
+                                                 credential_def.credential_pub_key,
+                                                 credential_def.rev_key_pub,
+                                                 credential_def.rev_reg)?;
+        }
+
+        let valid = proof_verifier.verify(proof, nonce)?;
+
+        trace!("Verifier::verify_presentation: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
 }
 
 
 #[derive(Debug)]
 pub struct ProofVerifier {
     credentials: Vec<VerifiableCredential>,
+    non_ownership_requests: Vec<NonOwnershipRequest>,
+    verifiable_encryption_requests: Vec<VerifiableEncryptionRequest>,
+    pseudonym_domains: Vec<String>,
+    same_holder_groups: Vec<Vec<usize>>,
+    #[cfg(feature = "sign_sodium")]
+    device_binding_request: Option<(usize, String)>,
+    min_nonce_size: Option<usize>,
+    observer: ProofObserverSlot
 }
 
 impl ProofVerifier {
@@ -94,336 +333,1941 @@ impl ProofVerifier {
                                  credential_pub_key: &CredentialPublicKey,
                                  rev_key_pub: Option<&RevocationKeyPublic>,
                                  rev_reg: Option<&RevocationRegistry>) -> Result<(), IndyCryptoError> {
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
         ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
 
         self.credentials.push(VerifiableCredential {
             pub_key: credential_pub_key.clone()?,
+            precomputed_key: None,
+            candidate_pub_keys: BTreeMap::new(),
+            key_id_required: false,
             sub_proof_request: sub_proof_request.clone(),
             credential_schema: credential_schema.clone(),
             rev_key_pub: rev_key_pub.map(Clone::clone),
-            rev_reg: rev_reg.map(Clone::clone)
+            rev_reg: rev_reg.map(Clone::clone),
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: BTreeMap::new()
         });
         Ok(())
     }
 
-    /// Verifies proof.
+    /// Add request to verify possession of credential signed with `credential_pub_key`
+    /// without revealed attributes and predicates. Counterpart of `ProofBuilder::add_possession_only`.
+    /// Revocation status of credential isn't verified.
     ///
     /// # Arguments
-    /// * `proof_verifier` - Proof verifier.
-    /// * `proof` - Proof generated by Prover.
-    /// * `nonce` - Nonce.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_pub_key` - Credential public key.
+    pub fn add_possession_only(&mut self,
+                               credential_schema: &CredentialSchema,
+                               credential_pub_key: &CredentialPublicKey) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::add_possession_only: >>> credential_schema: {:?}, credential_pub_key: {:?}", credential_schema, credential_pub_key);
+
+        self.credentials.push(VerifiableCredential {
+            pub_key: credential_pub_key.clone()?,
+            precomputed_key: None,
+            candidate_pub_keys: BTreeMap::new(),
+            key_id_required: false,
+            sub_proof_request: SubProofRequestBuilder::new()?.finalize()?,
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: None,
+            rev_reg: None,
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: BTreeMap::new()
+        });
+
+        trace!("ProofVerifier::add_possession_only: <<<");
+
+        Ok(())
+    }
+
+    /// Add sub proof request for credential that is verified with precomputed exponentiation tables of credential public key.
+    /// Precomputed key is shared, so the same tables can be used by many proof verifiers.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Sub proof request.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_pub_key_precomputed` - Credential public key with precomputed exponentiation tables.
+    /// * `rev_key_pub` - Revocation key public.
+    /// * `rev_reg` - Revocation registry.
+    pub fn add_sub_proof_request_with_precomputed_key(&mut self,
+                                                      sub_proof_request: &SubProofRequest,
+                                                      credential_schema: &CredentialSchema,
+                                                      credential_pub_key_precomputed: &Arc<CredentialPublicKeyPrecomputed>,
+                                                      rev_key_pub: Option<&RevocationKeyPublic>,
+                                                      rev_reg: Option<&RevocationRegistry>) -> Result<(), IndyCryptoError> {
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
+
+        self.credentials.push(VerifiableCredential {
+            pub_key: credential_pub_key_precomputed.pub_key().clone()?,
+            precomputed_key: Some(credential_pub_key_precomputed.clone()),
+            candidate_pub_keys: BTreeMap::new(),
+            key_id_required: false,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: rev_key_pub.map(Clone::clone),
+            rev_reg: rev_reg.map(Clone::clone),
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: BTreeMap::new()
+        });
+        Ok(())
+    }
+
+    /// Add sub proof request for credential definition that was rotated by Issuer.
+    /// Proof created with old key is accepted only if `timestamp` is within key rotation grace period
+    /// and sub proof refers to old key by its id (see `CredentialKeyRotation::old_key_id`).
+    /// Sub proof without key id is verified with new key.
     ///
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates instance pointer.
+    /// * `credential_schema` - Credential schema.
+    /// * `old_credential_pub_key` - Credential public key before rotation.
+    /// * `new_credential_pub_key` - Credential public key after rotation.
+    /// * `key_rotation` - Key rotation statement signed by Issuer.
+    /// * `issuer_vk` - Issuer BLS verification key.
+    /// * `gen` - BLS generator used for Issuer verification key creation.
+    /// * `timestamp` - Verification time (seconds since epoch).
+    /// * `rev_key_pub` - Revocation registry public key.
+    /// * `rev_reg` - Revocation registry.
     ///
     /// #Example
     /// ```
-    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::bls::{Generator, SignKey, VerKey};
     /// use indy_crypto::cl::issuer::Issuer;
-    /// use indy_crypto::cl::prover::Prover;
     /// use indy_crypto::cl::verifier::Verifier;
     ///
     /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
     /// credential_schema_builder.add_attr("sex").unwrap();
     /// let credential_schema = credential_schema_builder.finalize().unwrap();
     ///
-    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
     ///
-    /// let master_secret = Prover::new_master_secret().unwrap();
-    /// let master_secret_blinding_nonce = new_nonce().unwrap();
-    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
-    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    /// let (old_credential_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    /// let (new_credential_pub_key, _, _, key_rotation) =
+    ///     Issuer::rotate_credential_def(&credential_schema, &old_credential_pub_key, &sign_key, false, 1514764800).unwrap();
     ///
-    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
-    /// let credential_values = credential_values_builder.finalize().unwrap();
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
     ///
-    /// let credential_issuance_nonce = new_nonce().unwrap();
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
     ///
-    /// let (mut credential_signature, signature_correctness_proof) =
-    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
-    ///                             &blinded_master_secret,
-    ///                             &blinded_master_secret_correctness_proof,
-    ///                             &master_secret_blinding_nonce,
-    ///                             &credential_issuance_nonce,
-    ///                             &credential_values,
-    ///                             &credential_pub_key,
-    ///                             &credential_priv_key).unwrap();
+    /// proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request,
+    ///                                                        &credential_schema,
+    ///                                                        &old_credential_pub_key,
+    ///                                                        &new_credential_pub_key,
+    ///                                                        &key_rotation,
+    ///                                                        &ver_key,
+    ///                                                        &gen,
+    ///                                                        1514764000,
+    ///                                                        None,
+    ///                                                        None).unwrap();
+    /// ```
+    pub fn add_sub_proof_request_with_key_rotation(&mut self,
+                                                   sub_proof_request: &SubProofRequest,
+                                                   credential_schema: &CredentialSchema,
+                                                   old_credential_pub_key: &CredentialPublicKey,
+                                                   new_credential_pub_key: &CredentialPublicKey,
+                                                   key_rotation: &CredentialKeyRotation,
+                                                   issuer_vk: &VerKey,
+                                                   gen: &Generator,
+                                                   timestamp: u64,
+                                                   rev_key_pub: Option<&RevocationKeyPublic>,
+                                                   rev_reg: Option<&RevocationRegistry>) -> Result<(), IndyCryptoError> {
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
+        ProofVerifier::_check_key_rotation(old_credential_pub_key, new_credential_pub_key, key_rotation, issuer_vk, gen)?;
+
+        // Sub proof without key id is verified with new key
+        let mut candidate_pub_keys = BTreeMap::new();
+        candidate_pub_keys.insert(key_rotation.new_key_id.clone(), new_credential_pub_key.clone()?);
+        if timestamp <= key_rotation.grace_period_end {
+            candidate_pub_keys.insert(key_rotation.old_key_id.clone(), old_credential_pub_key.clone()?);
+        }
+
+        self.credentials.push(VerifiableCredential {
+            pub_key: new_credential_pub_key.clone()?,
+            precomputed_key: None,
+            candidate_pub_keys,
+            key_id_required: false,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: rev_key_pub.map(Clone::clone),
+            rev_reg: rev_reg.map(Clone::clone),
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: BTreeMap::new()
+        });
+        Ok(())
+    }
+
+    /// Add sub proof request for credential that can be issued with any of several credential keys
+    /// (e.g. by any of accepted issuers). Sub proof is verified with the key its key id refers to,
+    /// proofs without key id or with key id not among candidates are rejected.
+    /// Revocation status of credential isn't verified.
     ///
-    /// Prover::process_credential_signature(&mut credential_signature,
-    ///                                      &credential_values,
-    ///                                      &signature_correctness_proof,
-    ///                                      &master_secret_blinding_data,
-    ///                                      &master_secret,
-    ///                                      &credential_pub_key,
-    ///                                      &credential_issuance_nonce,
-    ///                                      None, None, None).unwrap();
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `credential_schema` - Credential schema.
+    /// * `candidate_pub_keys` - Accepted credential public keys with their identifiers.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key_1, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    /// let (credential_pub_key_2, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
     ///
     /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
     /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
     /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
     ///
-    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
-    /// proof_builder.add_sub_proof_request(&sub_proof_request,
-    ///                                     &credential_schema,
-    ///                                     &credential_signature,
-    ///                                     &credential_values,
-    ///                                     &credential_pub_key,
-    ///                                     None,
-    ///                                     None).unwrap();
-    ///
-    /// let proof_request_nonce = new_nonce().unwrap();
-    /// let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
-    ///
     /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
     ///
-    /// proof_verifier.add_sub_proof_request(&sub_proof_request,
-    ///                                      &credential_schema,
-    ///                                      &credential_pub_key,
-    ///                                      None,
-    ///                                      None).unwrap();
-    /// assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    /// proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request,
+    ///                                                          &credential_schema,
+    ///                                                          &[("issuer1:key", &credential_pub_key_1),
+    ///                                                            ("issuer2:key", &credential_pub_key_2)]).unwrap();
     /// ```
-    pub fn verify(self,
-                  proof: &Proof,
-                  nonce: &Nonce) -> Result<bool, IndyCryptoError> {
-        trace!("ProofVerifier::verify: >>> proof: {:?}, nonce: {:?}", proof, nonce);
-
-        ProofVerifier::_check_verify_params_consistency(&self.credentials, proof)?;
-
-        let mut tau_list: Vec<Vec<u8>> = Vec::new();
-
-        assert_eq!(proof.proofs.len(), self.credentials.len()); //FIXME return error
-        for idx in 0..proof.proofs.len() {
-            let proof_item = &proof.proofs[idx];
-            let credential = &self.credentials[idx];
-            if let (Some(non_revocation_proof), Some(cred_rev_pub_key), Some(rev_reg), Some(rev_key_pub)) = (proof_item.non_revoc_proof.as_ref(),
-                                                                                                             credential.pub_key.r_key.as_ref(),
-                                                                                                             credential.rev_reg.as_ref(),
-                                                                                                             credential.rev_key_pub.as_ref()) {
-                tau_list.extend_from_slice(
-                    &ProofVerifier::_verify_non_revocation_proof(&cred_rev_pub_key,
-                                                                 &rev_reg,
-                                                                 &rev_key_pub,
-                                                                 &proof.aggregated_proof.c_hash,
-                                                                 &non_revocation_proof)?.as_slice()?
-                );
-            };
+    pub fn add_sub_proof_request_with_key_candidates(&mut self,
+                                                     sub_proof_request: &SubProofRequest,
+                                                     credential_schema: &CredentialSchema,
+                                                     candidate_pub_keys: &[(&str, &CredentialPublicKey)]) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::add_sub_proof_request_with_key_candidates: >>> sub_proof_request: {:?}, credential_schema: {:?}, \
+        candidate_pub_keys: {:?}", sub_proof_request, credential_schema, candidate_pub_keys);
+
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
 
-            tau_list.append_vec(
-                &ProofVerifier::_verify_primary_proof(&credential.pub_key.p_key,
-                                                      &proof.aggregated_proof.c_hash,
-                                                      &proof_item.primary_proof,
-                                                      &credential.credential_schema,
-                                                      &credential.sub_proof_request)?
-            )?;
+        let mut pub_keys: BTreeMap<String, CredentialPublicKey> = BTreeMap::new();
+        for &(key_id, pub_key) in candidate_pub_keys.iter() {
+            if pub_keys.insert(key_id.to_owned(), pub_key.clone()?).is_some() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Duplicated candidate key id '{}'", key_id)));
+            }
         }
 
-        let mut values: Vec<Vec<u8>> = Vec::new();
-        values.extend_from_slice(&tau_list);
-        values.extend_from_slice(&proof.aggregated_proof.c_list);
-        values.push(nonce.to_bytes()?);
-
-        let c_hver = get_hash_as_int(&values)?;
-
-        info!(target: "anoncreds_service", "Verifier verify proof -> done");
+        let pub_key = match candidate_pub_keys.first() {
+            Some(&(_, pub_key)) => pub_key.clone()?,
+            None => return Err(IndyCryptoError::InvalidStructure(format!("Candidate keys are empty")))
+        };
 
-        let valid = c_hver == proof.aggregated_proof.c_hash;
+        self.credentials.push(VerifiableCredential {
+            pub_key,
+            precomputed_key: None,
+            candidate_pub_keys: pub_keys,
+            key_id_required: true,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: None,
+            rev_reg: None,
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: BTreeMap::new()
+        });
 
-        trace!("ProofVerifier::verify: <<< valid: {:?}", valid);
+        trace!("ProofVerifier::add_sub_proof_request_with_key_candidates: <<<");
 
-        Ok(valid)
+        Ok(())
     }
 
-    fn _check_add_sub_proof_request_params_consistency(sub_proof_request: &SubProofRequest,
-                                                       cred_schema: &CredentialSchema) -> Result<(), IndyCryptoError> {
-        trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: >>> sub_proof_request: {:?}, cred_schema: {:?}", sub_proof_request, cred_schema);
+    /// Add sub proof request with non-revoked interval and revocation registry states known to Verifier.
+    /// Non-revocation proof is checked against registry state with timestamp the proof was built for,
+    /// and the timestamp must belong to interval requested by `sub_proof_request`.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Sub proof request with non-revoked interval.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_key_pub` - Revocation key public.
+    /// * `rev_reg_states` - Revocation registry states keyed by timestamp (seconds since epoch).
+    pub fn add_sub_proof_request_with_rev_reg_states(&mut self,
+                                                     sub_proof_request: &SubProofRequest,
+                                                     credential_schema: &CredentialSchema,
+                                                     credential_pub_key: &CredentialPublicKey,
+                                                     rev_key_pub: &RevocationKeyPublic,
+                                                     rev_reg_states: &BTreeMap<u64, RevocationRegistry>) -> Result<(), IndyCryptoError> {
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
 
-        if sub_proof_request.revealed_attrs.difference(&cred_schema.attrs).count() != 0 {
-            return Err(IndyCryptoError::InvalidStructure(format!("Claim doesn't contain requested attribute")));
+        if sub_proof_request.non_revoked.is_none() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Sub proof request doesn't contain non-revoked interval")));
         }
 
-        let predicates_attrs =
-            sub_proof_request.predicates.iter()
-                .map(|predicate| predicate.attr_name.clone())
-                .collect::<HashSet<String>>();
+        self.credentials.push(VerifiableCredential {
+            pub_key: credential_pub_key.clone()?,
+            precomputed_key: None,
+            candidate_pub_keys: BTreeMap::new(),
+            key_id_required: false,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: Some(rev_key_pub.clone()),
+            rev_reg: None,
+            rev_reg_states: rev_reg_states.clone(),
+            rev_regs: BTreeMap::new()
+        });
+        Ok(())
+    }
+
+    /// Add sub proof request for credential definition which credentials are spread over several revocation registries.
+    /// Non-revocation proof must contain identifier of registry it was built against
+    /// (see `ProofBuilder::add_sub_proof_request_with_rev_reg_id`) and is checked against matching registry.
+    /// If sub proof request contains non-revoked interval, timestamp of proof must belong to it.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Sub proof request.
+    /// * `credential_schema` - Credential schema.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `rev_regs` - Revocation key public and registry keyed by registry identifier.
+    pub fn add_sub_proof_request_with_rev_regs(&mut self,
+                                               sub_proof_request: &SubProofRequest,
+                                               credential_schema: &CredentialSchema,
+                                               credential_pub_key: &CredentialPublicKey,
+                                               rev_regs: &BTreeMap<String, (RevocationKeyPublic, RevocationRegistry)>) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::add_sub_proof_request_with_rev_regs: >>> sub_proof_request: {:?}, credential_schema: {:?}, credential_pub_key: {:?}, rev_regs: {:?}",
+               sub_proof_request, credential_schema, credential_pub_key, rev_regs);
+
+        let sub_proof_request = &credential_schema.canonicalize_sub_proof_request(sub_proof_request);
+        ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
 
-        if predicates_attrs.difference(&cred_schema.attrs).count() != 0 {
-            return Err(IndyCryptoError::InvalidStructure(format!("Claim doesn't contain attribute requested in predicate")));
+        if rev_regs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("No revocation registries are provided")));
         }
 
-        trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: <<<");
+        self.credentials.push(VerifiableCredential {
+            pub_key: credential_pub_key.clone()?,
+            precomputed_key: None,
+            candidate_pub_keys: BTreeMap::new(),
+            key_id_required: false,
+            sub_proof_request: sub_proof_request.clone(),
+            credential_schema: credential_schema.clone(),
+            rev_key_pub: None,
+            rev_reg: None,
+            rev_reg_states: BTreeMap::new(),
+            rev_regs: rev_regs.clone()
+        });
+
+        trace!("ProofVerifier::add_sub_proof_request_with_rev_regs: <<<");
 
         Ok(())
     }
 
-    fn _check_verify_params_consistency(credentials: &Vec<VerifiableCredential>,
-                                        proof: &Proof) -> Result<(), IndyCryptoError> {
-        trace!("ProofVerifier::_check_verify_params_consistency: >>> credentials: {:?}, proof: {:?}", credentials, proof);
+    /// Add request to check that prover isn't enrolled to non-ownership registry.
+    /// Registry member of prover is hidden attribute of sub proof credential.
+    /// The order of non-ownership requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests) the attribute belongs to.
+    /// * `attr` - Name of hidden attribute that contains registry member.
+    /// * `registry` - Non-ownership registry.
+    pub fn add_non_ownership_request(&mut self,
+                                     sub_proof_idx: usize,
+                                     attr: &str,
+                                     registry: &NonOwnershipRegistry) -> Result<(), IndyCryptoError> {
+        let credential = self.credentials.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request by index '{}' not found", sub_proof_idx)))?;
+
+        if !credential.credential_schema.attrs.contains(attr) || credential.sub_proof_request.revealed_attrs.contains(attr) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't hidden in sub proof", attr)));
+        }
 
-        assert_eq!(proof.proofs.len(), credentials.len()); //FIXME return error
-        for idx in 0..proof.proofs.len() {
+        self.non_ownership_requests.push(NonOwnershipRequest {
+            sub_proof_idx,
+            attr: attr.to_owned(),
+            registry: registry.clone()?
+        });
+        Ok(())
+    }
+
+    /// Add request to check that proof contains pseudonym for domain derived from master secret of presented credentials.
+    /// Verified pseudonyms can be read with `Proof::pseudonyms`.
+    /// The order of pseudonym requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `domain` - Domain of pseudonym.
+    pub fn add_pseudonym_request(&mut self, domain: &str) -> Result<(), IndyCryptoError> {
+        self.pseudonym_domains.push(domain.to_owned());
+        Ok(())
+    }
+
+    /// Add request to check that credentials of sub proofs are bound to the same master secret (issued to the same holder).
+    /// Without such requests all credentials with master secret must be bound to the same master secret.
+    /// If requests are added, only credentials within each group must share master secret
+    /// and pseudonyms are bound to master secret of the first group.
+    ///
+    /// # Arguments
+    /// * `sub_proof_indices` - Indices of sub proofs (in order of sub proof requests) that must share master secret.
+    pub fn add_same_holder_group(&mut self, sub_proof_indices: &[usize]) -> Result<(), IndyCryptoError> {
+        if sub_proof_indices.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Same holder group is empty")));
+        }
+
+        for (idx, sub_proof_idx) in sub_proof_indices.iter().enumerate() {
+            let credential = self.credentials.get(*sub_proof_idx)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request by index '{}' not found", sub_proof_idx)))?;
+
+            if !credential.pub_key.requires_master_secret() {
+                return Err(IndyCryptoError::InvalidStructure(format!("Credential of sub proof '{}' doesn't use master secret", sub_proof_idx)));
+            }
+
+            if sub_proof_indices[..idx].contains(sub_proof_idx) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Sub proof index '{}' is duplicated", sub_proof_idx)));
+            }
+        }
+
+        self.same_holder_groups.push(sub_proof_indices.to_vec());
+        Ok(())
+    }
+
+    /// Add request to check that hidden attribute of sub proof is encrypted under auditor key.
+    /// The order of verifiable encryption requests is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests) the attribute belongs to.
+    /// * `attr` - Name of hidden attribute.
+    /// * `auditor_pub_key` - Auditor public key.
+    pub fn add_verifiable_encryption_request(&mut self,
+                                             sub_proof_idx: usize,
+                                             attr: &str,
+                                             auditor_pub_key: &AuditorPublicKey) -> Result<(), IndyCryptoError> {
+        let credential = self.credentials.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request by index '{}' not found", sub_proof_idx)))?;
+
+        if !credential.credential_schema.attrs.contains(attr) || credential.sub_proof_request.revealed_attrs.contains(attr) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't hidden in sub proof", attr)));
+        }
+
+        self.verifiable_encryption_requests.push(VerifiableEncryptionRequest {
+            sub_proof_idx,
+            attr: attr.to_owned(),
+            auditor_pub_key: auditor_pub_key.clone()?
+        });
+        Ok(())
+    }
+
+    /// Add request to check that proof is bound to holder device key committed in revealed attribute of sub proof.
+    /// Proof is valid only if it contains signature of its challenge by device key (see `Proof::add_device_binding`)
+    /// and value of the attribute is encoding of device verification key (see `DeviceBinding::encode_ver_key`).
+    ///
+    /// # Arguments
+    /// * `sub_proof_idx` - Index of sub proof (in order of sub proof requests) the attribute belongs to.
+    /// * `attr` - Name of revealed attribute with device verification key.
+    #[cfg(feature = "sign_sodium")]
+    pub fn add_device_binding_request(&mut self,
+                                      sub_proof_idx: usize,
+                                      attr: &str) -> Result<(), IndyCryptoError> {
+        let credential = self.credentials.get(sub_proof_idx)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof request by index '{}' not found", sub_proof_idx)))?;
+
+        if !credential.credential_schema.attrs.contains(attr) || !credential.sub_proof_request.revealed_attrs.contains(attr) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't revealed in sub proof", attr)));
+        }
+
+        if self.device_binding_request.is_some() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Device binding is already requested")));
+        }
+
+        self.device_binding_request = Some((sub_proof_idx, attr.to_owned()));
+        Ok(())
+    }
+
+    /// Sets minimal size of nonce proofs are verified with, for deployments with nonce policy requirements.
+    /// Verification fails with error if nonce is shorter, so nonces must be created with `new_nonce_with_size`
+    /// or `new_nonce_from_entropy` (nonces of `new_nonce` don't have fixed size).
+    ///
+    /// # Arguments
+    /// * `bits` - Minimal nonce size in bits (from 80 to 1024).
+    pub fn set_min_nonce_size(&mut self, bits: usize) -> Result<(), IndyCryptoError> {
+        check_nonce_size(bits)?;
+        self.min_nonce_size = Some(bits);
+        Ok(())
+    }
+
+    /// Sets observer receiving structured events of proof verification (sub proof started, t-values computed,
+    /// challenge computed, check passed or failed), so verification decisions can be logged and replayed.
+    ///
+    /// # Arguments
+    /// * `observer` - Observer of proof verification events.
+    pub fn set_observer(&mut self, observer: Arc<ProofObserver>) -> Result<(), IndyCryptoError> {
+        self.observer.set(observer);
+        Ok(())
+    }
+
+    /// Verifies proof.
+    ///
+    /// Proof bound to several verifiers (see `ProofBuilder::finalize_multi`) is valid only if it includes `nonce`.
+    ///
+    /// # Arguments
+    /// * `proof_verifier` - Proof verifier.
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    ///
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::prover::Prover;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    /// let master_secret_blinding_nonce = new_nonce().unwrap();
+    /// let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+    ///     Prover::blind_master_secret(&credential_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let (mut credential_signature, signature_correctness_proof) =
+    ///     Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///                             &blinded_master_secret,
+    ///                             &blinded_master_secret_correctness_proof,
+    ///                             &master_secret_blinding_nonce,
+    ///                             &credential_issuance_nonce,
+    ///                             &credential_values,
+    ///                             &credential_pub_key,
+    ///                             &credential_priv_key).unwrap();
+    ///
+    /// Prover::process_credential_signature(&mut credential_signature,
+    ///                                      &credential_values,
+    ///                                      &signature_correctness_proof,
+    ///                                      &master_secret_blinding_data,
+    ///                                      &master_secret,
+    ///                                      &credential_pub_key,
+    ///                                      &credential_issuance_nonce,
+    ///                                      None, None, None).unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+    /// sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+    /// let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    ///
+    /// let mut proof_builder = Prover::new_proof_builder().unwrap();
+    /// proof_builder.add_sub_proof_request(&sub_proof_request,
+    ///                                     &credential_schema,
+    ///                                     &credential_signature,
+    ///                                     &credential_values,
+    ///                                     &credential_pub_key,
+    ///                                     None,
+    ///                                     None).unwrap();
+    ///
+    /// let proof_request_nonce = new_nonce().unwrap();
+    /// let proof = proof_builder.finalize(&proof_request_nonce, &master_secret).unwrap();
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    ///
+    /// proof_verifier.add_sub_proof_request(&sub_proof_request,
+    ///                                      &credential_schema,
+    ///                                      &credential_pub_key,
+    ///                                      None,
+    ///                                      None).unwrap();
+    /// assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    /// ```
+    pub fn verify(self,
+                  proof: &Proof,
+                  nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        trace!("ProofVerifier::verify: >>> proof: {:?}, nonce: {:?}", proof, nonce);
+
+        let _timer = OperationTimer::start("ProofVerifier::verify");
+
+        let valid = self._verify(proof, nonce)?;
+
+        info!(target: "anoncreds_service", "Verifier verify proof -> done");
+
+        trace!("ProofVerifier::verify: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Verifies batch of proofs created for the same sub proof requests, each with own nonce.
+    /// Returns validity of each proof in the order of proofs, so invalid proofs can be identified.
+    /// Proof that is rejected as malformed is reported as invalid instead of failing whole batch.
+    ///
+    /// Proofs built with `ProofBuilder::set_batch_verifiable` carry t-values of their equality and GE proofs,
+    /// so challenge is checked against them and their equations are combined with random exponents of
+    /// `LARGE_BATCH_EXP` bits into one check per credential key: bases of key are exponentiated once for whole batch.
+    /// T-values are committed to by their absolute values modulo `n`, so combined check is done exactly in squared subgroup,
+    /// where -1, element of order 2 random exponents can't detect, vanishes.
+    /// If combined check fails, proofs of batch are verified one by one to identify invalid ones.
+    /// Other proofs are verified one by one.
+    ///
+    /// # Arguments
+    /// * `proofs` - Proofs together with nonces they were created for.
+    pub fn verify_batch(self,
+                        proofs: &[(Proof, Nonce)]) -> Result<Vec<bool>, IndyCryptoError> {
+        trace!("ProofVerifier::verify_batch: >>> proofs: {:?}", proofs);
+
+        // Validity of proofs verified one by one, None for proofs whose equations are added to batch
+        let mut pending: Vec<Option<bool>> = Vec::with_capacity(proofs.len());
+        let mut batch = PrimaryEquationsBatch::new();
+
+        for &(ref proof, ref nonce) in proofs.iter() {
+            let equations = match self._prepare_batch_equations(proof, nonce) {
+                Ok(equations) => equations,
+                Err(IndyCryptoError::AnoncredsProofRejected { .. }) => None,
+                Err(err) => return Err(err)
+            };
+
+            match equations {
+                Some(equations) => {
+                    batch.add_equations(equations)?;
+                    pending.push(None);
+                }
+                None => pending.push(Some(self._verify_in_batch(proof, nonce)?))
+            }
+        }
+
+        let batch_valid = batch.verify()?;
+
+        let mut results: Vec<bool> = Vec::with_capacity(proofs.len());
+        for (result, &(ref proof, ref nonce)) in pending.into_iter().zip(proofs.iter()) {
+            results.push(match result {
+                Some(result) => result,
+                None if batch_valid => {
+                    self.observer.notify(ProofEvent::CheckPassed { check: ProofCheck::Proof });
+                    true
+                }
+                None => self._verify_in_batch(proof, nonce)?
+            });
+        }
+
+        info!(target: "anoncreds_service", "Verifier verify proofs batch -> done");
+
+        trace!("ProofVerifier::verify_batch: <<< results: {:?}", results);
+
+        Ok(results)
+    }
+
+    /// Recomputes values proof challenge is computed over (see `ProofBuilder::commitments`)
+    /// for proof finalized with `ProofBuilder::finalize_with_external_challenge`.
+    /// Proof is valid if challenge application computes over returned values and its own statements
+    /// is equal to `Proof::challenge`. Returns None if any of checks of proof fails.
+    ///
+    /// # Arguments
+    /// * `proof_verifier` - Proof verifier.
+    /// * `proof` - Proof generated by Prover.
+    pub fn compute_challenge_contribution(&self, proof: &Proof) -> Result<Option<Vec<Vec<u8>>>, IndyCryptoError> {
+        trace!("ProofVerifier::compute_challenge_contribution: >>> proof: {:?}", proof);
+
+        let extra_tau_list = match self._verify_structure(proof)? {
+            Some(tau_list) => tau_list,
+            None => return Ok(None)
+        };
+
+        let mut pub_keys: Vec<&CredentialPublicKey> = Vec::new();
+        for (credential, sub_proof) in self.credentials.iter().zip(proof.proofs.iter()) {
+            pub_keys.push(ProofVerifier::_select_candidate_pub_key(credential, sub_proof)?.unwrap_or(&credential.pub_key));
+        }
+
+        let mut contribution: Vec<Vec<u8>> = Vec::new();
+        if !ProofVerifier::_append_t_values(&self.credentials, &pub_keys, &extra_tau_list, proof, &self.observer, &mut RecomputedTValues, &mut contribution)? {
+            return Ok(None);
+        }
+
+        trace!("ProofVerifier::compute_challenge_contribution: <<< contribution: {:?}", contribution);
+
+        Ok(Some(contribution))
+    }
+
+    fn _verify_in_batch(&self,
+                        proof: &Proof,
+                        nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        match self._verify(proof, nonce) {
+            Ok(valid) => Ok(valid),
+            Err(IndyCryptoError::AnoncredsProofRejected { .. }) => Ok(false),
+            Err(err) => Err(err)
+        }
+    }
+
+    // Checks challenge of proof against t-values it carries and returns equations t-values must satisfy.
+    // Returns None if proof doesn't carry t-values or any check fails,
+    // so proof is verified individually.
+    fn _prepare_batch_equations<'a>(&'a self,
+                                    proof: &'a Proof,
+                                    nonce: &Nonce) -> Result<Option<Vec<PrimaryEquation<'a>>>, IndyCryptoError> {
+        if proof.aggregated_proof.t_list.is_empty() || !self._check_nonce(proof, nonce)? {
+            return Ok(None);
+        }
+
+        let extra_tau_list = match self._verify_structure(proof)? {
+            Some(tau_list) => tau_list,
+            None => return Ok(None)
+        };
+
+        let mut pub_keys: Vec<&CredentialPublicKey> = Vec::new();
+        for (credential, sub_proof) in self.credentials.iter().zip(proof.proofs.iter()) {
+            pub_keys.push(ProofVerifier::_select_candidate_pub_key(credential, sub_proof)?.unwrap_or(&credential.pub_key));
+        }
+
+        let mut t_values = BatchTValues::new(&proof.aggregated_proof.t_list);
+
+        if !ProofVerifier::_verify_with_keys(&self.credentials, &pub_keys, &extra_tau_list, proof, nonce, &self.observer, &mut t_values)? ||
+            !t_values.is_complete() {
+            return Ok(None);
+        }
+
+        Ok(Some(t_values.equations))
+    }
+
+    fn _verify(&self,
+               proof: &Proof,
+               nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        let res = self._verify_proof(proof, nonce);
+
+        match res {
+            Ok(true) => self.observer.notify(ProofEvent::CheckPassed { check: ProofCheck::Proof }),
+            Ok(false) => self.observer.notify(ProofEvent::CheckFailed { check: ProofCheck::Proof, reason: format!("Proof is invalid") }),
+            Err(ref err) => self.observer.notify(ProofEvent::CheckFailed { check: ProofCheck::Proof, reason: err.to_string() })
+        }
+
+        res
+    }
+
+    fn _verify_proof(&self,
+                     proof: &Proof,
+                     nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        if !self._check_nonce(proof, nonce)? {
+            return Ok(false);
+        }
+
+        let extra_tau_list = match self._verify_structure(proof)? {
+            Some(tau_list) => tau_list,
+            None => return Ok(false)
+        };
+
+        // Credentials with candidate keys are verified against the key sub proof refers to by key id.
+        let mut selected_pub_keys: Vec<Option<&CredentialPublicKey>> = Vec::new();
+        for (credential, sub_proof) in self.credentials.iter().zip(proof.proofs.iter()) {
+            selected_pub_keys.push(ProofVerifier::_select_candidate_pub_key(credential, sub_proof)?);
+        }
+
+        let pub_keys: Vec<&CredentialPublicKey> = self.credentials.iter()
+            .zip(selected_pub_keys.into_iter())
+            .map(|(credential, selected_pub_key)| selected_pub_key.unwrap_or(&credential.pub_key))
+            .collect();
+
+        ProofVerifier::_verify_with_keys(&self.credentials, &pub_keys, &extra_tau_list, proof, nonce, &self.observer, &mut RecomputedTValues)
+    }
+
+    fn _check_nonce(&self,
+                    proof: &Proof,
+                    nonce: &Nonce) -> Result<bool, IndyCryptoError> {
+        if let Some(min_nonce_size) = self.min_nonce_size {
+            if (nonce.num_bits()? as usize) < min_nonce_size {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Nonce size {} is less than required {} bits", nonce.num_bits()?, min_nonce_size)));
+            }
+        }
+
+        // Proof bound to several verifiers is valid only for verifiers whose nonce it includes
+        Ok(self._report_check(ProofCheck::Nonce, proof.nonces.is_empty() || proof.nonces.contains(nonce),
+                              "Proof isn't bound to nonce"))
+    }
+
+    // Checks proof matches requested sub proofs and verifies proofs challenge isn't computed for.
+    // Returns t-values of non-ownership proofs, verifiable encryptions and pseudonyms, or None if any of checks fails.
+    fn _verify_structure(&self, proof: &Proof) -> Result<Option<Vec<Vec<u8>>>, IndyCryptoError> {
+        ProofVerifier::_check_verify_params_consistency(&self.credentials, proof)?;
+
+        if !self._report_check(ProofCheck::SameHolder,
+                               ProofVerifier::_check_same_holder_groups(&self.credentials, &self.same_holder_groups, proof),
+                               "Credentials of same holder group don't share master secret") {
+            return Ok(None);
+        }
+
+        #[cfg(feature = "sign_sodium")]
+        {
+            if self.device_binding_request.is_some() &&
+                !self._report_check(ProofCheck::DeviceBinding,
+                                    ProofVerifier::_verify_device_binding(&self.device_binding_request, proof)?,
+                                    "Proof isn't signed by device key committed in credential") {
+                return Ok(None);
+            }
+        }
+
+        let mut extra_tau_list = ProofVerifier::_verify_non_ownership_proofs(&self.non_ownership_requests, proof)?;
+        extra_tau_list.extend_from_slice(
+            &ProofVerifier::_verify_verifiable_encryptions(&self.verifiable_encryption_requests, proof)?);
+
+        let pseudonyms_tau_list = ProofVerifier::_verify_pseudonyms(&self.pseudonym_domains, &self._master_secret_sub_proofs(), proof)?;
+        if !self.pseudonym_domains.is_empty() &&
+            !self._report_check(ProofCheck::Pseudonyms, pseudonyms_tau_list.is_some(), "Pseudonyms aren't derived from master secret") {
+            return Ok(None);
+        }
+        match pseudonyms_tau_list {
+            Some(tau_list) => extra_tau_list.extend_from_slice(&tau_list),
+            None => return Ok(None)
+        };
+
+        Ok(Some(extra_tau_list))
+    }
+
+    #[cfg(feature = "sign_sodium")]
+    fn _verify_device_binding(device_binding_request: &Option<(usize, String)>,
+                              proof: &Proof) -> Result<bool, IndyCryptoError> {
+        let (sub_proof_idx, ref attr) = match *device_binding_request {
+            Some(ref request) => (request.0, &request.1),
+            None => return Ok(true)
+        };
+
+        let device_binding = match proof.device_binding {
+            Some(ref device_binding) => device_binding,
+            None => return Ok(false)
+        };
+
+        let committed_ver_key = proof.proofs[sub_proof_idx].primary_proof.eq_proof.revealed_attrs.get(attr.as_str())
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevealedAttrsMismatch })?;
+
+        if committed_ver_key.to_dec()? != DeviceBinding::encode_ver_key(&device_binding.ver_key)? {
+            return Ok(false);
+        }
+
+        ed25519::verify(&device_binding.signature, &proof.device_binding_message()?, &device_binding.ver_key)
+    }
+
+    fn _report_check(&self, check: ProofCheck, passed: bool, reason: &str) -> bool {
+        if passed {
+            self.observer.notify(ProofEvent::CheckPassed { check });
+        } else {
+            self.observer.notify(ProofEvent::CheckFailed { check, reason: reason.to_owned() });
+        }
+        passed
+    }
+
+    fn _select_candidate_pub_key<'a>(credential: &'a VerifiableCredential,
+                                     sub_proof: &SubProof) -> Result<Option<&'a CredentialPublicKey>, IndyCryptoError> {
+        if credential.candidate_pub_keys.is_empty() {
+            return Ok(None);
+        }
+
+        let key_id = match sub_proof.key_id {
+            Some(ref key_id) => key_id,
+            None if !credential.key_id_required => return Ok(None),
+            None => return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::KeyIdNotFound })
+        };
+
+        let pub_key = credential.candidate_pub_keys.get(key_id)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::KeyIdNotAllowed(key_id.clone()) })?;
+
+        Ok(Some(pub_key))
+    }
+
+    // Precomputed key can be used only if proof is checked against current key of credential.
+    fn _get_primary_key_exp<'a>(credential: &'a VerifiableCredential,
+                                pub_key: &'a CredentialPublicKey) -> &'a PrimaryPublicKeyExp {
+        match credential.precomputed_key {
+            Some(ref precomputed_key) if ptr::eq(pub_key, &credential.pub_key) => &**precomputed_key,
+            _ => &pub_key.p_key
+        }
+    }
+
+    // Selects revocation key and registry state non-revocation proof must be checked against.
+    // If registries are keyed by identifier, registry is chosen by identifier from proof.
+    // If non-revoked interval is requested, registry state is chosen by timestamp from proof.
+    // Registry passed to `add_sub_proof_request` is used for any timestamp from interval if states aren't supplied.
+    fn _get_rev_reg<'a>(credential: &'a VerifiableCredential,
+                        pub_key: &CredentialPublicKey,
+                        sub_proof: &SubProof) -> Result<(Option<&'a RevocationKeyPublic>, Option<&'a RevocationRegistry>), IndyCryptoError> {
+        if credential.rev_regs.is_empty() || pub_key.r_key.is_none() {
+            return Ok((credential.rev_key_pub.as_ref(), ProofVerifier::_get_rev_reg_state(credential, pub_key, sub_proof)?));
+        }
+
+        let non_revoc_proof = match sub_proof.non_revoc_proof {
+            Some(ref non_revoc_proof) => non_revoc_proof,
+            None => return Ok((None, None))
+        };
+
+        let rev_reg_id = non_revoc_proof.rev_reg_id.as_ref()
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevocationRegistryIdNotFound })?;
+
+        let &(ref rev_key_pub, ref rev_reg) = credential.rev_regs.get(rev_reg_id)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevocationRegistryIdNotAllowed(rev_reg_id.clone()) })?;
+
+        if let Some(ref interval) = credential.sub_proof_request.non_revoked {
+            ProofVerifier::_get_timestamp(interval, sub_proof)?;
+        }
+
+        Ok((Some(rev_key_pub), Some(rev_reg)))
+    }
+
+    fn _get_rev_reg_state<'a>(credential: &'a VerifiableCredential,
+                              pub_key: &CredentialPublicKey,
+                              sub_proof: &SubProof) -> Result<Option<&'a RevocationRegistry>, IndyCryptoError> {
+        let interval = match credential.sub_proof_request.non_revoked {
+            Some(ref interval) if pub_key.r_key.is_some() => interval,
+            _ => return Ok(credential.rev_reg.as_ref())
+        };
+
+        let timestamp = ProofVerifier::_get_timestamp(interval, sub_proof)?;
+
+        match credential.rev_reg_states.get(&timestamp) {
+            Some(rev_reg) => Ok(Some(rev_reg)),
+            None if credential.rev_reg_states.is_empty() => Ok(credential.rev_reg.as_ref()),
+            None => Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevocationRegistryStateNotFound(timestamp) })
+        }
+    }
+
+    fn _get_timestamp(interval: &NonRevokedInterval, sub_proof: &SubProof) -> Result<u64, IndyCryptoError> {
+        let timestamp = sub_proof.non_revoc_proof.as_ref()
+            .and_then(|non_revoc_proof| non_revoc_proof.timestamp)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::NonRevocationTimestampNotFound })?;
+
+        if !interval.contains(timestamp) {
+            return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::NonRevocationTimestampOutOfInterval(timestamp) });
+        }
+
+        Ok(timestamp)
+    }
+
+    fn _verify_with_keys<'a, P: PrimaryTValues<'a>>(credentials: &'a Vec<VerifiableCredential>,
+                                                    pub_keys: &Vec<&'a CredentialPublicKey>,
+                                                    extra_tau_list: &Vec<Vec<u8>>,
+                                                    proof: &Proof,
+                                                    nonce: &Nonce,
+                                                    observer: &ProofObserverSlot,
+                                                    t_values: &mut P) -> Result<bool, IndyCryptoError> {
+        // T values are absorbed into challenge hash as soon as they are computed
+        let mut hasher = proof.aggregated_proof.challenge_hash.hasher();
+
+        if !ProofVerifier::_append_t_values(credentials, pub_keys, extra_tau_list, proof, observer, t_values, &mut hasher)? {
+            return Ok(false);
+        }
+
+        if proof.nonces.is_empty() {
+            hasher.update_bignum(nonce)?;
+        } else {
+            for proof_nonce in proof.nonces.iter() {
+                hasher.update_bignum(proof_nonce)?;
+            }
+        }
+
+        let c_hver = hasher.finalize()?;
+        observer.notify(ProofEvent::ChallengeComputed { challenge: c_hver.to_hex()? });
+
+        let valid = c_hver == proof.aggregated_proof.c_hash;
+        if valid {
+            observer.notify(ProofEvent::CheckPassed { check: ProofCheck::Challenge });
+        } else {
+            observer.notify(ProofEvent::CheckFailed { check: ProofCheck::Challenge, reason: format!("Recomputed challenge differs from challenge of proof") });
+        }
+
+        Ok(valid)
+    }
+
+    // Appends t-values in the order `ProofBuilder` commits to them. Returns false if linear predicate proof is invalid.
+    fn _append_t_values<'a, P: PrimaryTValues<'a>, A: AppendByteArray>(credentials: &'a Vec<VerifiableCredential>,
+                                                                       pub_keys: &Vec<&'a CredentialPublicKey>,
+                                                                       extra_tau_list: &Vec<Vec<u8>>,
+                                                                       proof: &Proof,
+                                                                       observer: &ProofObserverSlot,
+                                                                       t_values: &mut P,
+                                                                       out: &mut A) -> Result<bool, IndyCryptoError> {
+        for idx in 0..proof.proofs.len() {
+            observer.notify(ProofEvent::SubProofStarted { sub_proof_idx: idx });
+            let mut tau_count = 0;
+
+            let proof_item = &proof.proofs[idx];
+            let credential = &credentials[idx];
+            let pub_key = pub_keys[idx];
+            let (rev_key_pub, rev_reg) = ProofVerifier::_get_rev_reg(credential, pub_key, proof_item)?;
+            if let (Some(non_revocation_proof), Some(cred_rev_pub_key), Some(rev_reg), Some(rev_key_pub)) = (proof_item.non_revoc_proof.as_ref(),
+                                                                                                             pub_key.r_key.as_ref(),
+                                                                                                             rev_reg,
+                                                                                                             rev_key_pub) {
+                let non_revoc_tau_list = ProofVerifier::_verify_non_revocation_proof(&cred_rev_pub_key,
+                                                                                     &rev_reg,
+                                                                                     &rev_key_pub,
+                                                                                     &proof.aggregated_proof.c_hash,
+                                                                                     &non_revocation_proof)?.as_slice()?;
+                for tau in non_revoc_tau_list.iter() {
+                    out.append_bytes(tau);
+                }
+                tau_count += non_revoc_tau_list.len();
+            };
+
+            let primary_tau_list = t_values.primary_t_values(ProofVerifier::_get_primary_key_exp(credential, pub_key),
+                                                             &proof.aggregated_proof.c_hash,
+                                                             &proof_item.primary_proof,
+                                                             &credential.credential_schema,
+                                                             &credential.sub_proof_request)?;
+            out.append_vec(&primary_tau_list)?;
+            tau_count += primary_tau_list.len();
+
+            let range_tau_list = ProofVerifier::_verify_range_proofs(&proof.aggregated_proof.c_hash, &proof_item.primary_proof)?;
+            for tau in range_tau_list.iter() {
+                out.append_bytes(tau);
+            }
+            tau_count += range_tau_list.len();
+
+            observer.notify(ProofEvent::TValuesComputed { sub_proof_idx: idx, count: tau_count });
+        }
+
+        for idx in 0..proof.proofs.len() {
+            let linear_predicates = &credentials[idx].sub_proof_request.linear_predicates;
+            let linear_ge_proofs = &proof.proofs[idx].primary_proof.linear_ge_proofs;
+
+            if linear_predicates.len() != linear_ge_proofs.len() {
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::LinearPredicatesMismatch });
+            }
+
+            for (linear_predicate, linear_ge_proof) in linear_predicates.iter().zip(linear_ge_proofs.iter()) {
+                if linear_ge_proof.predicate != linear_predicate.as_predicate() {
+                    return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::LinearPredicatesMismatch });
+                }
+
+                let mj = linear_predicate.combine(|term| proof.proofs.get(term.sub_proof_idx)
+                    .and_then(|sub_proof| sub_proof.primary_proof.eq_proof.m.get(&term.attr_name))
+                    .ok_or(IndyCryptoError::AnoncredsProofRejected {
+                        reason: RejectionReason::HiddenAttrNotFound { attr: term.attr_name.clone(), sub_proof_idx: term.sub_proof_idx }
+                    })?
+                    .clone())?;
+
+                if mj != linear_ge_proof.mj {
+                    return Ok(false);
+                }
+
+                out.append_vec(
+                    &t_values.ge_t_values(ProofVerifier::_get_primary_key_exp(&credentials[idx], pub_keys[idx]),
+                                          linear_ge_proof,
+                                          &proof.aggregated_proof.c_hash)?
+                )?;
+            }
+        }
+
+        for tau in extra_tau_list.iter() {
+            out.append_bytes(tau);
+        }
+        for c in proof.aggregated_proof.c_list.iter() {
+            out.append_bytes(c);
+        }
+        for (idx, sub_proof) in proof.proofs.iter().enumerate() {
+            if let Some(ref key_id) = sub_proof.key_id {
+                out.append_bytes(&key_id_bytes(idx, key_id));
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn _verify_verifiable_encryptions(requests: &Vec<VerifiableEncryptionRequest>,
+                                      proof: &Proof) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_verifiable_encryptions: >>> requests: {:?}, proof: {:?}", requests, proof);
+
+        if requests.len() != proof.verifiable_encryptions.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::VerifiableEncryptionsMismatch });
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+        let c_hash = &proof.aggregated_proof.c_hash;
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        for (request, encryption) in requests.iter().zip(proof.verifiable_encryptions.iter()) {
+            let m_hat = proof.proofs.get(request.sub_proof_idx)
+                .and_then(|sub_proof| sub_proof.primary_proof.eq_proof.m.get(&request.attr))
+                .ok_or(IndyCryptoError::AnoncredsProofRejected {
+                    reason: RejectionReason::HiddenAttrNotFound { attr: request.attr.clone(), sub_proof_idx: request.sub_proof_idx }
+                })?;
+
+            let pub_key = &request.auditor_pub_key;
+            let n_sqr = pub_key.n_sqr()?;
+            let n_plus_one = pub_key.n.add(&BigNumber::from_u32(1)?)?;
+
+            // Camenisch-Shoup: equations are checked for squares of ciphertext elements,
+            // so elements outside of the subgroup of squares (e.g. negated ciphertext) give no advantage.
+            let mut c_hash_2 = c_hash.clone()?;
+            c_hash_2.mul_word(2)?;
+            let mut r_hat_2 = encryption.r_hat.clone()?;
+            r_hat_2.mul_word(2)?;
+            let mut m_hat_2 = m_hat.clone()?;
+            m_hat_2.mul_word(2)?;
+
+            let u_hat = inverse_proof_value(&encryption.ciphertext.u, &n_sqr, "ciphertext.u", &mut ctx)?
+                .mod_exp(&c_hash_2, &n_sqr, Some(&mut ctx))?
+                .mod_mul(&pub_key.g.mod_exp(&r_hat_2, &n_sqr, Some(&mut ctx))?, &n_sqr, Some(&mut ctx))?;
+
+            let e_hat = inverse_proof_value(&encryption.ciphertext.e, &n_sqr, "ciphertext.e", &mut ctx)?
+                .mod_exp(&c_hash_2, &n_sqr, Some(&mut ctx))?
+                .mod_mul(&pub_key.y.mod_exp(&r_hat_2, &n_sqr, Some(&mut ctx))?, &n_sqr, Some(&mut ctx))?
+                .mod_mul(&n_plus_one.mod_exp(&m_hat_2, &n_sqr, Some(&mut ctx))?, &n_sqr, Some(&mut ctx))?;
+
+            tau_list.push(encryption.ciphertext.u.to_bytes()?);
+            tau_list.push(encryption.ciphertext.e.to_bytes()?);
+            tau_list.push(u_hat.to_bytes()?);
+            tau_list.push(e_hat.to_bytes()?);
+        }
+
+        trace!("ProofVerifier::_verify_verifiable_encryptions: <<< tau_list: {:?}", tau_list);
+
+        Ok(tau_list)
+    }
+
+    // Returns None if any of pseudonym proofs is invalid.
+    fn _verify_pseudonyms(domains: &Vec<String>,
+                          sub_proof_indices: &[usize],
+                          proof: &Proof) -> Result<Option<Vec<Vec<u8>>>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_pseudonyms: >>> domains: {:?}, proof: {:?}", domains, proof);
+
+        if domains.len() != proof.pseudonyms.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::PseudonymsMismatch });
+        }
+
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        if domains.is_empty() {
+            return Ok(Some(tau_list));
+        }
+
+        // Pseudonym is bound to master secret through the response for master secret shared by all sub proofs
+        let m1 = match ProofVerifier::_get_master_secret_response(sub_proof_indices, proof)? {
+            Some(m1) => m1,
+            None => return Ok(None)
+        };
+
+        let mut ctx = BigNumber::new_context()?;
+        let c_hash = &proof.aggregated_proof.c_hash;
+        let p = Pseudonym::modulus()?;
+
+        for (domain, pseudonym) in domains.iter().zip(proof.pseudonyms.iter()) {
+            if pseudonym.domain != *domain {
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::PseudonymsMismatch });
+            }
+
+            if !pseudonym.is_in_subgroup(&mut ctx)? {
+                return Ok(None);
+            }
+
+            let t_hat = inverse_proof_value(&pseudonym.value, &p, "pseudonym", &mut ctx)?
+                .mod_exp(c_hash, &p, Some(&mut ctx))?
+                .mod_mul(
+                    &Pseudonym::base(domain)?.mod_exp(m1, &p, Some(&mut ctx))?,
+                    &p,
+                    Some(&mut ctx)
+                )?;
+
+            tau_list.push(pseudonym.value.to_bytes()?);
+            tau_list.push(t_hat.to_bytes()?);
+        }
+
+        trace!("ProofVerifier::_verify_pseudonyms: <<< tau_list: {:?}", tau_list);
+
+        Ok(Some(tau_list))
+    }
+
+    // Returns response for master secret if it is the same in all given sub proofs, otherwise None.
+    fn _get_master_secret_response<'a>(sub_proof_indices: &[usize],
+                                       proof: &'a Proof) -> Result<Option<&'a BigNumber>, IndyCryptoError> {
+        let mut responses: Vec<&BigNumber> = Vec::new();
+        for sub_proof_idx in sub_proof_indices.iter() {
+            let sub_proof = proof.proofs.get(*sub_proof_idx)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::MalformedProof(format!("Sub proof by index '{}' not found", sub_proof_idx)) })?;
+            responses.push(&sub_proof.primary_proof.eq_proof.m1);
+        }
+
+        let m1 = *responses.get(0)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::MalformedProof(format!("Proof of master secret ownership requires at least one sub proof")) })?;
+
+        if responses.iter().any(|response| *response != m1) {
+            return Ok(None);
+        }
+
+        Ok(Some(m1))
+    }
+
+    // Sub proofs pseudonyms are bound to: the first same holder group if groups are requested,
+    // otherwise all sub proofs of credentials with master secret.
+    fn _master_secret_sub_proofs(&self) -> Vec<usize> {
+        match self.same_holder_groups.first() {
+            Some(group) => group.clone(),
+            None => ProofVerifier::_implicit_same_holder_group(&self.credentials)
+        }
+    }
+
+    fn _implicit_same_holder_group(credentials: &[VerifiableCredential]) -> Vec<usize> {
+        credentials.iter()
+            .enumerate()
+            .filter(|&(_, credential)| credential.pub_key.requires_master_secret())
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    // Responses for master secret are equal in sub proofs bound to the same master secret.
+    // Sub proofs of credentials without master secret don't contain the response and are never grouped.
+    fn _check_same_holder_groups(credentials: &[VerifiableCredential],
+                                 same_holder_groups: &[Vec<usize>],
+                                 proof: &Proof) -> bool {
+        let implicit_group;
+        let groups: &[Vec<usize>] = if same_holder_groups.is_empty() {
+            implicit_group = vec![ProofVerifier::_implicit_same_holder_group(credentials)];
+            &implicit_group
+        } else {
+            same_holder_groups
+        };
+
+        groups.iter().all(|group| {
+            let mut responses = group.iter()
+                .filter_map(|idx| proof.proofs.get(*idx))
+                .map(|sub_proof| &sub_proof.primary_proof.eq_proof.m1);
+
+            match responses.next() {
+                Some(m1) => responses.all(|response| response == m1),
+                None => true
+            }
+        })
+    }
+
+    fn _verify_non_ownership_proofs(requests: &Vec<NonOwnershipRequest>,
+                                    proof: &Proof) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_non_ownership_proofs: >>> requests: {:?}, proof: {:?}", requests, proof);
+
+        if requests.len() != proof.non_ownership_proofs.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::NonOwnershipProofsMismatch });
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+        let c_hash = &proof.aggregated_proof.c_hash;
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        for (request, non_ownership_proof) in requests.iter().zip(proof.non_ownership_proofs.iter()) {
+            // Registry member is bound to credential through the response for hidden attribute
+            let x_hat = proof.proofs.get(request.sub_proof_idx)
+                .and_then(|sub_proof| sub_proof.primary_proof.eq_proof.m.get(&request.attr))
+                .ok_or(IndyCryptoError::AnoncredsProofRejected {
+                    reason: RejectionReason::HiddenAttrNotFound { attr: request.attr.clone(), sub_proof_idx: request.sub_proof_idx }
+                })?;
+
+            let registry = &request.registry;
+
+            let c_d_inv = inverse_proof_value(&non_ownership_proof.c_d, &registry.n, "c_d", &mut ctx)?;
+            let c_r_inv = inverse_proof_value(&non_ownership_proof.c_r, &registry.n, "c_r", &mut ctx)?;
+            let g_inv = registry.g.inverse(&registry.n, Some(&mut ctx))?;
+            let h_inv = registry.h.inverse(&registry.n, Some(&mut ctx))?;
+
+            // g^-c * accum^a_hat * c_d^-x_hat * h^rx_hat
+            let t1_hat = g_inv.mod_exp(c_hash, &registry.n, Some(&mut ctx))?
+                .mod_mul(&registry.accum.mod_exp(&non_ownership_proof.a_hat, &registry.n, Some(&mut ctx))?, &registry.n, Some(&mut ctx))?
+                .mod_mul(&get_pedersen_commitment(&c_d_inv, x_hat, &registry.h, &non_ownership_proof.rx_hat, &registry.n, &mut ctx)?,
+                         &registry.n, Some(&mut ctx))?;
+
+            // c_r^-c * g^r_hat * h^s_hat
+            let t2_hat = c_r_inv.mod_exp(c_hash, &registry.n, Some(&mut ctx))?
+                .mod_mul(&get_pedersen_commitment(&registry.g, &non_ownership_proof.r_hat, &registry.h, &non_ownership_proof.s_hat,
+                                                  &registry.n, &mut ctx)?,
+                         &registry.n, Some(&mut ctx))?;
+
+            // c_r^x_hat * g^-rx_hat * h^-sx_hat
+            let t3_hat = non_ownership_proof.c_r.mod_exp(x_hat, &registry.n, Some(&mut ctx))?
+                .mod_mul(&get_pedersen_commitment(&g_inv, &non_ownership_proof.rx_hat, &h_inv, &non_ownership_proof.sx_hat,
+                                                  &registry.n, &mut ctx)?,
+                         &registry.n, Some(&mut ctx))?;
+
+            tau_list.push(non_ownership_proof.c_d.to_bytes()?);
+            tau_list.push(non_ownership_proof.c_r.to_bytes()?);
+            tau_list.push(t1_hat.to_bytes()?);
+            tau_list.push(t2_hat.to_bytes()?);
+            tau_list.push(t3_hat.to_bytes()?);
+        }
+
+        trace!("ProofVerifier::_verify_non_ownership_proofs: <<< tau_list: {:?}", tau_list);
+
+        Ok(tau_list)
+    }
+
+    fn _check_key_rotation(old_credential_pub_key: &CredentialPublicKey,
+                           new_credential_pub_key: &CredentialPublicKey,
+                           key_rotation: &CredentialKeyRotation,
+                           issuer_vk: &VerKey,
+                           gen: &Generator) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_key_rotation: >>> old_credential_pub_key: {:?}, new_credential_pub_key: {:?}, key_rotation: {:?}, \
+               issuer_vk: {:?}, gen: {:?}", old_credential_pub_key, new_credential_pub_key, key_rotation, issuer_vk, gen);
+
+        if old_credential_pub_key.fingerprint()? != key_rotation.old_key_id ||
+            new_credential_pub_key.fingerprint()? != key_rotation.new_key_id {
+            return Err(IndyCryptoError::InvalidStructure(format!("Key rotation statement doesn't match credential public keys")));
+        }
+
+        if !key_rotation.verify_signature(issuer_vk, gen)? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid key rotation signature")));
+        }
+
+        trace!("ProofVerifier::_check_key_rotation: <<<");
+
+        Ok(())
+    }
+
+    /// Decrypts proof sealed for verifier by `Proof::seal_for`.
+    ///
+    /// # Arguments
+    /// * `sealed_proof` - Sealed proof received from Prover.
+    /// * `verifier_pk` - Verifier encryption public key.
+    /// * `verifier_sk` - Verifier encryption secret key.
+    #[cfg(feature = "box_sodium")]
+    pub fn unseal(sealed_proof: &SealedProof,
+                  verifier_pk: &PublicKey,
+                  verifier_sk: &SecretKey) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofVerifier::unseal: >>> sealed_proof: {:?}, verifier_pk: {:?}", sealed_proof, verifier_pk);
+
+        let proof_json = SealedBox::open(&sealed_proof.ciphertext, verifier_pk, verifier_sk)?;
+        let proof_json = String::from_utf8(proof_json)
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Sealed proof contains invalid utf8")))?;
+
+        let proof = Proof::from_json(&proof_json)?;
+
+        trace!("ProofVerifier::unseal: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    fn _check_add_sub_proof_request_params_consistency(sub_proof_request: &SubProofRequest,
+                                                       cred_schema: &CredentialSchema) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: >>> sub_proof_request: {:?}, cred_schema: {:?}", sub_proof_request, cred_schema);
+
+        if let Some(attr) = sub_proof_request.revealed_attrs.difference(&cred_schema.attrs).next() {
+            return Err(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential schema".to_string() });
+        }
+
+        let predicates_attrs =
+            sub_proof_request.predicates.iter()
+                .map(|predicate| predicate.attr_name.clone())
+                .collect::<HashSet<String>>();
+
+        if let Some(attr) = predicates_attrs.difference(&cred_schema.attrs).next() {
+            return Err(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential schema".to_string() });
+        }
+
+        if let Some(attr) = sub_proof_request.attr_value_checks.keys().find(|attr| !cred_schema.attrs.contains(*attr)) {
+            return Err(IndyCryptoError::MissingAttribute { attr: attr.clone(), context: "credential schema".to_string() });
+        }
+
+        sub_proof_request._check_attr_value_checks()?;
+
+        trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: <<<");
+
+        Ok(())
+    }
+
+    fn _check_verify_params_consistency(credentials: &Vec<VerifiableCredential>,
+                                        proof: &Proof) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_verify_params_consistency: >>> credentials: {:?}, proof: {:?}", credentials, proof);
+
+        if proof.proofs.len() != credentials.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::SubProofsCountMismatch });
+        }
+
+        for idx in 0..proof.proofs.len() {
             let proof_for_credential = &proof.proofs[idx];
             let credential = &credentials[idx];
 
             let proof_revealed_attrs = HashSet::from_iter(proof_for_credential.primary_proof.eq_proof.revealed_attrs.keys().cloned());
 
             if proof_revealed_attrs != credential.sub_proof_request.revealed_attrs {
-                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof revealed attributes not correspond to requested attributes")));
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevealedAttrsMismatch });
             }
 
             let proof_predicates =
                 proof_for_credential.primary_proof.ge_proofs.iter()
                     .map(|ge_proof| ge_proof.predicate.clone())
+                    .chain(proof_for_credential.primary_proof.range_proofs.iter().map(|range_proof| range_proof.predicate.clone()))
                     .collect::<HashSet<Predicate>>();
 
-            if proof_predicates != credential.sub_proof_request.predicates {
-                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof predicates not correspond to requested predicates")));
-            }
-        }
+            if proof_predicates != credential.sub_proof_request.predicates {
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::PredicatesMismatch });
+            }
+
+            // Predicates are proven with the proof system requested by Verifier
+            if proof_for_credential.primary_proof.ge_proofs.iter().any(|ge_proof| ge_proof.predicate.proof_version != PredicateProofVersion::FourSquares) ||
+                proof_for_credential.primary_proof.range_proofs.iter().any(|range_proof| range_proof.predicate.proof_version != PredicateProofVersion::RangeProof) {
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::PredicatesMismatch });
+            }
+        }
+
+        trace!("ProofVerifier::_check_verify_params_consistency: <<<");
+
+        Ok(())
+    }
+
+    fn _verify_primary_proof(p_pub_key: &PrimaryPublicKeyExp,
+                             c_hash: &BigNumber,
+                             primary_proof: &PrimaryProof,
+                             cred_schema: &CredentialSchema,
+                             sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_primary_proof: >>> p_pub_key: {:?}, c_hash: {:?}, primary_proof: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
+               p_pub_key.primary_key(), c_hash, primary_proof, cred_schema, sub_proof_request);
+
+        let mut t_hat: Vec<BigNumber> = ProofVerifier::_verify_equality(p_pub_key,
+                                                                        &primary_proof.eq_proof,
+                                                                        c_hash,
+                                                                        cred_schema,
+                                                                        sub_proof_request)?;
+
+        for ge_proof in primary_proof.ge_proofs.iter() {
+            ProofVerifier::_check_ge_proof_mj(primary_proof, ge_proof)?;
+
+            t_hat.append(&mut ProofVerifier::_verify_ge_predicate(p_pub_key, ge_proof, c_hash)?)
+        }
+
+        trace!("ProofVerifier::_verify_primary_proof: <<< t_hat: {:?}", t_hat);
+
+        Ok(t_hat)
+    }
+
+    // Predicate is bound to credential attribute only through response of equality proof
+    fn _check_ge_proof_mj(primary_proof: &PrimaryProof,
+                          ge_proof: &PrimaryPredicateGEProof) -> Result<(), IndyCryptoError> {
+        if *get_proof_value(&primary_proof.eq_proof.m, &ge_proof.predicate.attr_name, "eq_proof.m")? != ge_proof.mj {
+            return Err(IndyCryptoError::AnoncredsProofRejected {
+                reason: RejectionReason::MalformedProof(format!("Response of predicate over '{}' differs from equality proof", ge_proof.predicate.attr_name))
+            });
+        }
+
+        Ok(())
+    }
+
+    fn _verify_equality(p_key_exp: &PrimaryPublicKeyExp,
+                        proof: &PrimaryEqualProof,
+                        c_hash: &BigNumber,
+                        cred_schema: &CredentialSchema,
+                        sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let p_pub_key = p_key_exp.primary_key();
+
+        trace!("ProofVerifier::_verify_equality: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
+               p_pub_key, proof, c_hash, cred_schema, sub_proof_request);
+
+        let unrevealed_attrs = ProofVerifier::_check_equality_responses(p_pub_key, proof, cred_schema, sub_proof_request)?;
+
+        let t1: BigNumber = calc_teq(p_key_exp, &proof.a_prime, &proof.e, &proof.v, &proof.m, &proof.m1, &proof.m2, &unrevealed_attrs)?;
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let degree: BigNumber =
+            BigNumber::from_dec("2")?
+                .exp(
+                    &BigNumber::from_dec(&LARGE_E_START.to_string())?,
+                    Some(&mut ctx)
+                )?;
+
+        let mut rar = proof.a_prime.mod_exp(&degree, &p_pub_key.n, Some(&mut ctx))?;
+
+        for (attr, encoded_value) in &proof.revealed_attrs {
+            let cur_r = p_pub_key.r.get(attr)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::MalformedProof(format!("Value by key '{}' not found in pk.r", attr)) })?;
+
+            rar = cur_r
+                .mod_exp(encoded_value, &p_pub_key.n, Some(&mut ctx))?
+                .mod_mul(&rar, &p_pub_key.n, Some(&mut ctx))?;
+        }
+
+        // Checked attributes are handled like revealed ones with values taken from request instead of proof
+        for (attr, dec_value) in sub_proof_request.attr_value_checks.iter() {
+            let cur_r = p_pub_key.r.get(attr)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::MalformedProof(format!("Value by key '{}' not found in pk.r", attr)) })?;
+
+            rar = cur_r
+                .mod_exp(&BigNumber::from_dec(dec_value)?, &p_pub_key.n, Some(&mut ctx))?
+                .mod_mul(&rar, &p_pub_key.n, Some(&mut ctx))?;
+        }
+
+        let t2: BigNumber = p_pub_key.z
+            .mod_mul(&inverse_proof_value(&rar, &p_pub_key.n, "a_prime", &mut ctx)?, &p_pub_key.n, Some(&mut ctx))?
+            .inverse(&p_pub_key.n, Some(&mut ctx))?
+            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?;
+
+        let t: BigNumber = t1.mod_mul(&t2, &p_pub_key.n, Some(&mut ctx))?;
+
+        trace!("ProofVerifier::_verify_equality: <<< t: {:?}", t);
+
+        Ok(vec![t])
+    }
+
+    // Checks responses of equality proof and returns attributes that are hidden by it
+    fn _check_equality_responses(p_pub_key: &CredentialPrimaryPublicKey,
+                                 proof: &PrimaryEqualProof,
+                                 cred_schema: &CredentialSchema,
+                                 sub_proof_request: &SubProofRequest) -> Result<HashSet<String>, IndyCryptoError> {
+        // Responses are tilde values plus challenge multiples of secrets, so they can't exceed tilde size by more than 1 bit
+        ProofVerifier::_check_response_size("e", &proof.e, LARGE_ETILDE + 1)?;
+        ProofVerifier::_check_response_size("v", &proof.v, p_pub_key.security_profile()?.vtilde_bits() + 1)?;
+        ProofVerifier::_check_response_size("m2", &proof.m2, LARGE_MTILDE + 1)?;
+
+        for (attr, m) in proof.m.iter() {
+            ProofVerifier::_check_response_size(&format!("m.{}", attr), m, LARGE_MTILDE + 1)?;
+        }
+
+        let unrevealed_attrs = sub_proof_request.unrevealed_attrs(cred_schema);
+
+        for attr in unrevealed_attrs.iter() {
+            get_proof_value(&proof.m, attr, "eq_proof.m")?;
+        }
+
+        Ok(unrevealed_attrs)
+    }
+
+    fn _verify_ge_predicate(p_key_exp: &PrimaryPublicKeyExp,
+                            proof: &PrimaryPredicateGEProof,
+                            c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let p_pub_key = p_key_exp.primary_key();
+
+        trace!("ProofVerifier::_verify_ge_predicate: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}", p_pub_key, proof, c_hash);
+
+        ProofVerifier::_check_ge_responses(proof)?;
+
+        let mut ctx = BigNumber::new_context()?;
+        let mj = predicate_signed(&proof.predicate.p_type, &proof.mj)?;
+        let mut tau_list = calc_tge(p_key_exp, &proof.u, &proof.r, &mj,
+                                    &proof.alpha, &proof.t)?;
+
+        for i in 0..ITERATION {
+            let cur_t = get_proof_value(&proof.t, &i.to_string(), "proof.t")?
+                .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?;
+
+            tau_list[i] = inverse_proof_value(&cur_t, &p_pub_key.n, &format!("t.{}", i), &mut ctx)?
+                .mod_mul(&tau_list[i], &p_pub_key.n, Some(&mut ctx))?;
+        }
+
+        let delta = get_proof_value(&proof.t, "DELTA", "proof.t")?;
+
+        // predicate bound can be negative for LE and LT predicates and predicates over linear combination of attributes
+        let z_delta = mod_exp_signed(&p_pub_key.z,
+                                     &BigNumber::from_dec(&predicate_bound(&proof.predicate.p_type, proof.predicate.value).to_string())?,
+                                     &p_pub_key.n, &mut ctx)?
+            .mul(&delta, Some(&mut ctx))?
+            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?;
+
+        tau_list[ITERATION] = inverse_proof_value(&z_delta, &p_pub_key.n, "t.DELTA", &mut ctx)?
+            .mod_mul(&tau_list[ITERATION], &p_pub_key.n, Some(&mut ctx))?;
+
+        let delta_c = delta.mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?;
+
+        tau_list[ITERATION + 1] = inverse_proof_value(&delta_c, &p_pub_key.n, "t.DELTA", &mut ctx)?
+            .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+
+        trace!("ProofVerifier::_verify_ge_predicate: <<< tau_list: {:?},", tau_list);
+
+        Ok(tau_list)
+    }
+
+    fn _check_ge_responses(proof: &PrimaryPredicateGEProof) -> Result<(), IndyCryptoError> {
+        for (key, u) in proof.u.iter() {
+            ProofVerifier::_check_response_size(&format!("u.{}", key), u, LARGE_UTILDE + 1)?;
+        }
+
+        for i in 0..ITERATION {
+            get_proof_value(&proof.u, &i.to_string(), "proof.u")?;
+            get_proof_value(&proof.r, &i.to_string(), "proof.r")?;
+            get_proof_value(&proof.t, &i.to_string(), "proof.t")?;
+        }
+        get_proof_value(&proof.r, "DELTA", "proof.r")?;
+
+        Ok(())
+    }
+
+    // Verifies range proofs and returns tau values linking their commitments to attributes of equality proof.
+    fn _verify_range_proofs(c_hash: &BigNumber,
+                            primary_proof: &PrimaryProof) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_range_proofs: >>> c_hash: {:?}, primary_proof: {:?}", c_hash, primary_proof);
+
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        if primary_proof.range_proofs.is_empty() {
+            return Ok(tau_list);
+        }
+
+        let (g, _) = commitment_generators()?;
+        let c = bignum_to_scalar(c_hash)?;
+
+        for proof in primary_proof.range_proofs.iter() {
+            let attr = &proof.predicate.attr_name;
+
+            // Commitment is linked to attribute, not to negated one, so range proofs support only GE and GT
+            if proof.predicate.p_type.is_less() {
+                return Err(IndyCryptoError::AnoncredsProofRejected {
+                    reason: RejectionReason::MalformedProof(format!("Range proof for '{}' has unsupported predicate type", attr))
+                });
+            }
+
+            if !proof.range_proof.verify(&proof.commitment, RANGE_BITS)? {
+                return Err(IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::InvalidRangeProof(attr.clone()) });
+            }
+
+            let mj = bignum_to_scalar(get_proof_value(&primary_proof.eq_proof.m, attr, "eq_proof.m")?)
+                .map_err(|_| IndyCryptoError::AnoncredsProofRejected {
+                    reason: RejectionReason::MalformedProof(format!("Response for '{}' isn't a scalar", attr))
+                })?;
+
+            // t = g^mj * h^gamma / (commitment * g^bound)^c
+            let value = bignum_to_scalar(&BigNumber::from_dec(&predicate_bound(&proof.predicate.p_type, proof.predicate.value).to_string())?)?;
+            let t = commit(&mj, &proof.gamma)?
+                .sub(&PointG1::multi_scalar_mul(&[proof.commitment, g], &[c, value.mul_mod(&c)?])?)?;
+
+            tau_list.push(proof.commitment.to_bytes()?);
+            tau_list.push(t.to_bytes()?);
+        }
+
+        trace!("ProofVerifier::_verify_range_proofs: <<< tau_list: {:?}", tau_list);
+
+        Ok(tau_list)
+    }
+
+    fn _check_response_size(name: &str, value: &BigNumber, max_bits: usize) -> Result<(), IndyCryptoError> {
+        if value.num_bits()? as usize > max_bits {
+            return Err(IndyCryptoError::AnoncredsProofRejected {
+                reason: RejectionReason::MalformedProof(format!("Response '{}' exceeds {} bits", name, max_bits))
+            });
+        }
+
+        Ok(())
+    }
+
+    fn _verify_non_revocation_proof(r_pub_key: &CredentialRevocationPublicKey,
+                                    rev_reg: &RevocationRegistry,
+                                    rev_key_pub: &RevocationKeyPublic,
+                                    c_hash: &BigNumber, proof: &NonRevocProof) -> Result<NonRevocProofTauList, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_non_revocation_proof: >>> r_pub_key: {:?}, rev_reg: {:?}, rev_key_pub: {:?}, c_hash: {:?}",
+               r_pub_key, rev_reg, rev_key_pub, c_hash);
+
+        let ch_num_z = bignum_to_group_element(&c_hash)?;
+
+        let non_revoc_proof_tau_list = create_tau_list_hat_values(r_pub_key, rev_reg, rev_key_pub, &ch_num_z, &proof.x_list, &proof.c_list);
+
+        trace!("ProofVerifier::_verify_non_revocation_proof: <<< non_revoc_proof_tau_list: {:?}", non_revoc_proof_tau_list);
+
+        non_revoc_proof_tau_list
+    }
+}
+
+// Source of t-values of equality and GE proofs challenge is computed over.
+trait PrimaryTValues<'a> {
+    fn primary_t_values(&mut self,
+                        p_key_exp: &'a PrimaryPublicKeyExp,
+                        c_hash: &BigNumber,
+                        primary_proof: &PrimaryProof,
+                        cred_schema: &CredentialSchema,
+                        sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError>;
+
+    fn ge_t_values(&mut self,
+                   p_key_exp: &'a PrimaryPublicKeyExp,
+                   ge_proof: &PrimaryPredicateGEProof,
+                   c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError>;
+}
+
+// T-values recomputed from responses, proof is valid if challenge computed over their absolute values
+// equals to challenge of proof.
+struct RecomputedTValues;
+
+impl<'a> PrimaryTValues<'a> for RecomputedTValues {
+    fn primary_t_values(&mut self,
+                        p_key_exp: &'a PrimaryPublicKeyExp,
+                        c_hash: &BigNumber,
+                        primary_proof: &PrimaryProof,
+                        cred_schema: &CredentialSchema,
+                        sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let n = &p_key_exp.primary_key().n;
+        ProofVerifier::_verify_primary_proof(p_key_exp, c_hash, primary_proof, cred_schema, sub_proof_request)?
+            .iter()
+            .map(|t| abs_mod(t, n))
+            .collect()
+    }
+
+    fn ge_t_values(&mut self,
+                   p_key_exp: &'a PrimaryPublicKeyExp,
+                   ge_proof: &PrimaryPredicateGEProof,
+                   c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let n = &p_key_exp.primary_key().n;
+        ProofVerifier::_verify_ge_predicate(p_key_exp, ge_proof, c_hash)?
+            .iter()
+            .map(|t| abs_mod(t, n))
+            .collect()
+    }
+}
+
+// Base of credential primary public key.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum KeyBase {
+    Z,
+    S,
+    Rms,
+    Rctxt,
+    R(String)
+}
+
+// Equation `t = prod(key_base^exp) * prod(base^exp) mod n` t-value of proof must satisfy.
+// Bases of proof are inverted for negative exponents, so only exponents of key bases can be negative.
+struct PrimaryEquation<'a> {
+    p_pub_key: &'a CredentialPrimaryPublicKey,
+    t_inv: BigNumber,
+    key_exps: Vec<(KeyBase, BigNumber)>,
+    exps: Vec<(BigNumber, BigNumber)>
+}
+
+// T-values taken from proof built for batch verification in the order they are committed to.
+// Instead of recomputing t-values, collects equations they must satisfy.
+struct BatchTValues<'a> {
+    t_list: &'a [BigNumber],
+    next: usize,
+    equations: Vec<PrimaryEquation<'a>>
+}
 
-        trace!("ProofVerifier::_check_verify_params_consistency: <<<");
+impl<'a> BatchTValues<'a> {
+    fn new(t_list: &'a [BigNumber]) -> BatchTValues<'a> {
+        BatchTValues { t_list, next: 0, equations: Vec::new() }
+    }
 
-        Ok(())
+    fn is_complete(&self) -> bool {
+        self.next == self.t_list.len()
     }
 
-    fn _verify_primary_proof(p_pub_key: &CredentialPrimaryPublicKey,
-                             c_hash: &BigNumber,
-                             primary_proof: &PrimaryProof,
-                             cred_schema: &CredentialSchema,
-                             sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
-        trace!("ProofVerifier::_verify_primary_proof: >>> p_pub_key: {:?}, c_hash: {:?}, primary_proof: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
-               p_pub_key, c_hash, primary_proof, cred_schema, sub_proof_request);
+    // Takes next t-value of proof and adds equation it must satisfy
+    fn _add_equation(&mut self,
+                     p_pub_key: &'a CredentialPrimaryPublicKey,
+                     key_exps: Vec<(KeyBase, BigNumber)>,
+                     exps: Vec<(&BigNumber, BigNumber, String)>,
+                     ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let t_list = self.t_list;
+        let t = t_list.get(self.next)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected {
+                reason: RejectionReason::MalformedProof(format!("T-values of proof don't match its sub proofs"))
+            })?;
+        self.next += 1;
+
+        // T-value is hashed into challenge as is, so it must be absolute value like recomputed one
+        if t.is_negative()? || *t >= p_pub_key.n || abs_mod(t, &p_pub_key.n)? != *t {
+            return Err(IndyCryptoError::AnoncredsProofRejected {
+                reason: RejectionReason::MalformedProof(format!("T-value isn't absolute value modulo n"))
+            });
+        }
 
-        let mut t_hat: Vec<BigNumber> = ProofVerifier::_verify_equality(p_pub_key,
-                                                                        &primary_proof.eq_proof,
-                                                                        c_hash,
-                                                                        cred_schema,
-                                                                        sub_proof_request)?;
+        for &(ref base, _) in key_exps.iter() {
+            if let KeyBase::R(ref attr) = *base {
+                if !p_pub_key.r.contains_key(attr) {
+                    return Err(IndyCryptoError::AnoncredsProofRejected {
+                        reason: RejectionReason::MalformedProof(format!("Value by key '{}' not found in pk.r", attr))
+                    });
+                }
+            }
+        }
 
-        for ge_proof in primary_proof.ge_proofs.iter() {
-            t_hat.append(&mut ProofVerifier::_verify_ge_predicate(p_pub_key, ge_proof, c_hash)?)
+        let mut proof_exps: Vec<(BigNumber, BigNumber)> = Vec::with_capacity(exps.len());
+        for (base, exp, name) in exps.into_iter() {
+            if exp.is_negative()? {
+                proof_exps.push((inverse_proof_value(base, &p_pub_key.n, &name, ctx)?, BigNumber::new()?.sub(&exp)?));
+            } else {
+                proof_exps.push((base.clone()?, exp));
+            }
         }
 
-        trace!("ProofVerifier::_verify_primary_proof: <<< t_hat: {:?}", t_hat);
+        self.equations.push(PrimaryEquation {
+            p_pub_key,
+            t_inv: inverse_proof_value(t, &p_pub_key.n, "t", ctx)?,
+            key_exps,
+            exps: proof_exps
+        });
 
-        Ok(t_hat)
+        t.clone()
     }
+}
 
-    fn _verify_equality(p_pub_key: &CredentialPrimaryPublicKey,
-                        proof: &PrimaryEqualProof,
+impl<'a> PrimaryTValues<'a> for BatchTValues<'a> {
+    // T = A'^(e + c * 2^596) * prod(R_unrevealed^m) * prod(R_revealed^(c * m)) * S^v * Rms^m1 * Rctxt^m2 * Z^-c
+    fn primary_t_values(&mut self,
+                        p_key_exp: &'a PrimaryPublicKeyExp,
                         c_hash: &BigNumber,
+                        primary_proof: &PrimaryProof,
                         cred_schema: &CredentialSchema,
                         sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
-        trace!("ProofVerifier::_verify_equality: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
-               p_pub_key, proof, c_hash, cred_schema, sub_proof_request);
-
-        let unrevealed_attrs: HashSet<String> =
-            cred_schema.attrs
-                .difference(&sub_proof_request.revealed_attrs)
-                .cloned()
-                .collect::<HashSet<String>>();
+        let p_pub_key = p_key_exp.primary_key();
+        let proof = &primary_proof.eq_proof;
 
-        let t1: BigNumber = calc_teq(&p_pub_key, &proof.a_prime, &proof.e, &proof.v, &proof.m, &proof.m1, &proof.m2, &unrevealed_attrs)?;
+        let unrevealed_attrs = ProofVerifier::_check_equality_responses(p_pub_key, proof, cred_schema, sub_proof_request)?;
 
         let mut ctx = BigNumber::new_context()?;
+        let mut key_exps: Vec<(KeyBase, BigNumber)> = Vec::new();
 
-        let degree: BigNumber =
-            BigNumber::from_dec("2")?
-                .exp(
-                    &BigNumber::from_dec(&LARGE_E_START.to_string())?,
-                    Some(&mut ctx)
-                )?;
+        for attr in unrevealed_attrs.iter() {
+            key_exps.push((KeyBase::R(attr.clone()), get_proof_value(&proof.m, attr, "eq_proof.m")?.clone()?));
+        }
 
-        let mut rar = proof.a_prime.mod_exp(&degree, &p_pub_key.n, Some(&mut ctx))?;
+        for (attr, encoded_value) in proof.revealed_attrs.iter() {
+            key_exps.push((KeyBase::R(attr.clone()), encoded_value.mul(c_hash, Some(&mut ctx))?));
+        }
 
-        for (attr, encoded_value) in &proof.revealed_attrs {
-            let cur_r = p_pub_key.r.get(attr)
-                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in pk.r", attr)))?;
+        for (attr, dec_value) in sub_proof_request.attr_value_checks.iter() {
+            key_exps.push((KeyBase::R(attr.clone()), BigNumber::from_dec(dec_value)?.mul(c_hash, Some(&mut ctx))?));
+        }
 
-            rar = cur_r
-                .mod_exp(encoded_value, &p_pub_key.n, Some(&mut ctx))?
-                .mod_mul(&rar, &p_pub_key.n, Some(&mut ctx))?;
+        key_exps.push((KeyBase::S, proof.v.clone()?));
+        if !p_pub_key.without_master_secret {
+            key_exps.push((KeyBase::Rms, proof.m1.clone()?));
         }
+        key_exps.push((KeyBase::Rctxt, proof.m2.clone()?));
+        key_exps.push((KeyBase::Z, BigNumber::new()?.sub(c_hash)?));
 
-        let t2: BigNumber = p_pub_key.z
-            .mod_div(&rar, &p_pub_key.n)?
-            .inverse(&p_pub_key.n, Some(&mut ctx))?
-            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?;
+        inverse_proof_value(&proof.a_prime, &p_pub_key.n, "a_prime", &mut ctx)?;
 
-        let t: BigNumber = t1.mod_mul(&t2, &p_pub_key.n, Some(&mut ctx))?;
+        let a_prime_exp = BigNumber::from_u32(2)?
+            .exp(&BigNumber::from_u32(LARGE_E_START)?, Some(&mut ctx))?
+            .mul(c_hash, Some(&mut ctx))?
+            .add(&proof.e)?;
 
-        trace!("ProofVerifier::_verify_equality: <<< t: {:?}", t);
+        let mut t_values = vec![self._add_equation(p_pub_key, key_exps, vec![(&proof.a_prime, a_prime_exp, "a_prime".to_string())], &mut ctx)?];
 
-        Ok(vec![t])
+        for ge_proof in primary_proof.ge_proofs.iter() {
+            ProofVerifier::_check_ge_proof_mj(primary_proof, ge_proof)?;
+
+            t_values.append(&mut self.ge_t_values(p_key_exp, ge_proof, c_hash)?);
+        }
+
+        Ok(t_values)
     }
 
-    fn _verify_ge_predicate(p_pub_key: &CredentialPrimaryPublicKey,
-                            proof: &PrimaryPredicateGEProof,
-                            c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
-        trace!("ProofVerifier::_verify_ge_predicate: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}", p_pub_key, proof, c_hash);
+    // T_i = Z^u_i * S^r_i * t_i^-c for i < 4
+    // T_4 = Z^(mj - c * bound) * S^r_delta * t_delta^-c, where mj is negated for LE and LT predicates
+    // T_5 = prod(t_i^u_i) * S^alpha * t_delta^-c
+    fn ge_t_values(&mut self,
+                   p_key_exp: &'a PrimaryPublicKeyExp,
+                   ge_proof: &PrimaryPredicateGEProof,
+                   c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let p_pub_key = p_key_exp.primary_key();
+
+        ProofVerifier::_check_ge_responses(ge_proof)?;
 
         let mut ctx = BigNumber::new_context()?;
-        let mut tau_list = calc_tge(&p_pub_key, &proof.u, &proof.r, &proof.mj,
-                                    &proof.alpha, &proof.t)?;
+        let minus_c = BigNumber::new()?.sub(c_hash)?;
+        let delta = get_proof_value(&ge_proof.t, "DELTA", "proof.t")?;
+        let mut t_values: Vec<BigNumber> = Vec::new();
+        let mut t_u_exps: Vec<(&BigNumber, BigNumber, String)> = Vec::new();
 
         for i in 0..ITERATION {
-            let cur_t = proof.t.get(&i.to_string())
-                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", i)))?;
+            let cur_u = get_proof_value(&ge_proof.u, &i.to_string(), "proof.u")?;
+            let cur_r = get_proof_value(&ge_proof.r, &i.to_string(), "proof.r")?;
+            let cur_t = get_proof_value(&ge_proof.t, &i.to_string(), "proof.t")?;
 
-            tau_list[i] = cur_t
-                .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
-                .inverse(&p_pub_key.n, Some(&mut ctx))?
-                .mod_mul(&tau_list[i], &p_pub_key.n, Some(&mut ctx))?;
+            t_values.push(self._add_equation(p_pub_key,
+                                             vec![(KeyBase::Z, cur_u.clone()?), (KeyBase::S, cur_r.clone()?)],
+                                             vec![(cur_t, minus_c.clone()?, format!("t.{}", i))],
+                                             &mut ctx)?);
+
+            t_u_exps.push((cur_t, cur_u.clone()?, format!("t.{}", i)));
         }
 
-        let delta = proof.t.get("DELTA")
-            .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", "DELTA")))?;
+        // predicate bound can be negative for LE and LT predicates and predicates over linear combination of attributes
+        let z_exp = BigNumber::from_dec(&predicate_bound(&ge_proof.predicate.p_type, ge_proof.predicate.value).to_string())?
+            .mul(c_hash, Some(&mut ctx))?;
+        let z_exp = predicate_signed(&ge_proof.predicate.p_type, &ge_proof.mj)?.sub(&z_exp)?;
 
-        tau_list[ITERATION] = p_pub_key.z
-            .mod_exp(
-                &BigNumber::from_dec(&proof.predicate.value.to_string())?,
-                &p_pub_key.n, Some(&mut ctx))?
-            .mul(&delta, Some(&mut ctx))?
-            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
-            .inverse(&p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(&tau_list[ITERATION], &p_pub_key.n, Some(&mut ctx))?;
+        t_values.push(self._add_equation(p_pub_key,
+                                         vec![(KeyBase::Z, z_exp), (KeyBase::S, get_proof_value(&ge_proof.r, "DELTA", "proof.r")?.clone()?)],
+                                         vec![(delta, minus_c.clone()?, "t.DELTA".to_string())],
+                                         &mut ctx)?);
 
-        tau_list[ITERATION + 1] = delta
-            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
-            .inverse(&p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+        t_u_exps.push((delta, minus_c, "t.DELTA".to_string()));
 
-        trace!("ProofVerifier::_verify_ge_predicate: <<< tau_list: {:?},", tau_list);
+        t_values.push(self._add_equation(p_pub_key,
+                                         vec![(KeyBase::S, ge_proof.alpha.clone()?)],
+                                         t_u_exps,
+                                         &mut ctx)?);
 
-        Ok(tau_list)
+        Ok(t_values)
     }
+}
 
-    fn _verify_non_revocation_proof(r_pub_key: &CredentialRevocationPublicKey,
-                                    rev_reg: &RevocationRegistry,
-                                    rev_key_pub: &RevocationKeyPublic,
-                                    c_hash: &BigNumber, proof: &NonRevocProof) -> Result<NonRevocProofTauList, IndyCryptoError> {
-        trace!("ProofVerifier::_verify_non_revocation_proof: >>> r_pub_key: {:?}, rev_reg: {:?}, rev_key_pub: {:?}, c_hash: {:?}",
-               r_pub_key, rev_reg, rev_key_pub, c_hash);
+// Equations of proofs combined with random exponents: t-value is absolute value of `prod(base^exp)`,
+// so for each equation with random `r` `prod(base^(2 * r * exp)) * t^(-2 * r) = 1` in squared subgroup,
+// and product over all equations of the same key must be exactly 1 as well.
+// Exponents of key bases are summed up, so each base of key is exponentiated once for whole batch.
+struct PrimaryEquationsBatch<'a> {
+    keys: Vec<KeyEquations<'a>>
+}
 
-        let ch_num_z = bignum_to_group_element(&c_hash)?;
+struct KeyEquations<'a> {
+    p_pub_key: &'a CredentialPrimaryPublicKey,
+    key_exps: BTreeMap<KeyBase, BigNumber>,
+    product: BigNumber
+}
 
-        let t_hat_expected_values = create_tau_list_expected_values(r_pub_key, rev_reg, rev_key_pub, &proof.c_list)?;
-        let t_hat_calc_values = create_tau_list_values(&r_pub_key, rev_reg, &proof.x_list, &proof.c_list)?;
+impl<'a> PrimaryEquationsBatch<'a> {
+    fn new() -> PrimaryEquationsBatch<'a> {
+        PrimaryEquationsBatch { keys: Vec::new() }
+    }
 
+    fn add_equations(&mut self, equations: Vec<PrimaryEquation<'a>>) -> Result<(), IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
 
-        let non_revoc_proof_tau_list = Ok(NonRevocProofTauList {
-            t1: t_hat_expected_values.t1.mul(&ch_num_z)?.add(&t_hat_calc_values.t1)?,
-            t2: t_hat_expected_values.t2.mul(&ch_num_z)?.add(&t_hat_calc_values.t2)?,
-            t3: t_hat_expected_values.t3.pow(&ch_num_z)?.mul(&t_hat_calc_values.t3)?,
-            t4: t_hat_expected_values.t4.pow(&ch_num_z)?.mul(&t_hat_calc_values.t4)?,
-            t5: t_hat_expected_values.t5.mul(&ch_num_z)?.add(&t_hat_calc_values.t5)?,
-            t6: t_hat_expected_values.t6.mul(&ch_num_z)?.add(&t_hat_calc_values.t6)?,
-            t7: t_hat_expected_values.t7.pow(&ch_num_z)?.mul(&t_hat_calc_values.t7)?,
-            t8: t_hat_expected_values.t8.pow(&ch_num_z)?.mul(&t_hat_calc_values.t8)?
-        });
+        for equation in equations.into_iter() {
+            let key_idx = match self.keys.iter().position(|key| ptr::eq(key.p_pub_key, equation.p_pub_key)) {
+                Some(key_idx) => key_idx,
+                None => {
+                    self.keys.push(KeyEquations { p_pub_key: equation.p_pub_key, key_exps: BTreeMap::new(), product: BigNumber::from_u32(1)? });
+                    self.keys.len() - 1
+                }
+            };
+            let key = &mut self.keys[key_idx];
+            let p_pub_key = key.p_pub_key;
+            let n = &p_pub_key.n;
+
+            // Squaring removes sign of t-value, so -1 can't be hidden by even random exponent
+            let r = BigNumber::from_u32(2)?.mul(&bn_rand(LARGE_BATCH_EXP)?, Some(&mut ctx))?;
+
+            for (base, exp) in equation.key_exps.into_iter() {
+                let exp = exp.mul(&r, Some(&mut ctx))?;
+                let exp = match key.key_exps.get(&base) {
+                    Some(acc) => acc.add(&exp)?,
+                    None => exp
+                };
+                key.key_exps.insert(base, exp);
+            }
 
-        trace!("ProofVerifier::_verify_non_revocation_proof: <<< non_revoc_proof_tau_list: {:?}", non_revoc_proof_tau_list);
+            let mut product = equation.t_inv.mod_exp(&r, n, Some(&mut ctx))?;
+            for &(ref base, ref exp) in equation.exps.iter() {
+                product = base.mod_exp(&exp.mul(&r, Some(&mut ctx))?, n, Some(&mut ctx))?
+                    .mod_mul(&product, n, Some(&mut ctx))?;
+            }
 
-        non_revoc_proof_tau_list
+            key.product = key.product.mod_mul(&product, n, Some(&mut ctx))?;
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self) -> Result<bool, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let one = BigNumber::from_u32(1)?;
+
+        for key in self.keys.iter() {
+            let p_pub_key = key.p_pub_key;
+            let mut result = key.product.clone()?;
+
+            for (base, exp) in key.key_exps.iter() {
+                let base = match *base {
+                    KeyBase::Z => &p_pub_key.z,
+                    KeyBase::S => &p_pub_key.s,
+                    KeyBase::Rms => &p_pub_key.rms,
+                    KeyBase::Rctxt => &p_pub_key.rctxt,
+                    KeyBase::R(ref attr) => p_pub_key.r.get(attr)
+                        .ok_or(IndyCryptoError::MissingAttribute { attr: attr.to_string(), context: "pk.r".to_string() })?
+                };
+
+                result = mod_exp_signed(base, exp, &p_pub_key.n, &mut ctx)?
+                    .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+            }
+
+            if result != one {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
     }
 }
 
@@ -432,8 +2276,561 @@ mod tests {
     use super::*;
     use cl::prover;
     use cl::issuer;
+    use cl::issuer::Issuer;
     use cl::helpers::MockHelper;
+    use cl::mocks;
+    use bls::SignKey;
     use cl::prover::mocks::*;
+    #[cfg(feature = "box_sodium")]
+    use encryption::new_key_pair;
+    use errors::ToErrorCode;
+    use ffi::ErrorCode;
+    use utils::json::JsonEncodable;
+
+    use proptest::prelude::ProptestConfig;
+
+    #[test]
+    fn verify_batch_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let sub_proof_request = sub_proof_request();
+
+        let mut proofs: Vec<(Proof, Nonce)> = Vec::new();
+        for _ in 0..3 {
+            let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+            proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                                &credential_values, &cred_pub_key, None, None).unwrap();
+            let nonce = new_nonce().unwrap();
+            proofs.push((proof_builder.finalize(&nonce, &master_secret).unwrap(), nonce));
+        }
+
+        // Proof checked against other nonce and proof with other sub proofs count are reported as invalid
+        proofs[1].1 = new_nonce().unwrap();
+        proofs[2].0.proofs.clear();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+
+        assert_eq!(vec![true, false, false], proof_verifier.verify_batch(&proofs).unwrap());
+    }
+
+    #[test]
+    fn verify_batch_works_for_batch_verifiable_proofs() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let sub_proof_request = sub_proof_request();
+
+        let new_proofs = |count: usize| -> Vec<(Proof, Nonce)> {
+            (0..count).map(|_| {
+                let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+                proof_builder.set_batch_verifiable(true).unwrap();
+                proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                                    &credential_values, &cred_pub_key, None, None).unwrap();
+                let nonce = new_nonce().unwrap();
+                (proof_builder.finalize(&nonce, &master_secret).unwrap(), nonce)
+            }).collect()
+        };
+
+        let new_proof_verifier = || {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+            proof_verifier
+        };
+
+        // Equality proof and GE proof each contribute their t-values
+        let proofs = new_proofs(3);
+        assert_eq!(1 + 6 * sub_proof_request.predicates.len(), proofs[0].0.aggregated_proof.t_list.len());
+        assert!(new_proof_verifier().verify(&proofs[0].0, &proofs[0].1).unwrap());
+        assert_eq!(vec![true, true, true], new_proof_verifier().verify_batch(&proofs).unwrap());
+
+        // Proof with tampered response fails combined check, so batch falls back to verifying proofs one by one
+        let mut proofs = new_proofs(3);
+        proofs[1].0.proofs[0].primary_proof.eq_proof.m2 = proofs[1].0.proofs[0].primary_proof.eq_proof.m2.add(&BigNumber::from_u32(1).unwrap()).unwrap();
+        assert_eq!(vec![true, false, true], new_proof_verifier().verify_batch(&proofs).unwrap());
+
+        // Proof with tampered t-value doesn't match its challenge and is verified individually
+        let mut proofs = new_proofs(2);
+        proofs[0].0.aggregated_proof.t_list[0] = BigNumber::from_u32(1).unwrap();
+        assert_eq!(vec![true, true], new_proof_verifier().verify_batch(&proofs).unwrap());
+
+        // Proof without t-values is verified individually together with batch
+        let mut proofs = new_proofs(2);
+        proofs[1].0.aggregated_proof.t_list.clear();
+        assert_eq!(vec![true, true], new_proof_verifier().verify_batch(&proofs).unwrap());
+
+        // Proof with challenge computed over -T instead of T is rejected both individually and in batch
+        let n = &cred_pub_key.p_key.n;
+        let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+        proof_builder.set_batch_verifiable(true).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        let minus_t = n.sub(&abs_mod(&proof_builder.init_proofs[0].primary_init_proof.eq_proof.t, n).unwrap()).unwrap();
+        proof_builder.tau_list[0] = minus_t.to_bytes().unwrap();
+        let nonce = new_nonce().unwrap();
+        let mut proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+        proof.aggregated_proof.t_list[0] = minus_t;
+
+        assert!(!new_proof_verifier().verify(&proof, &nonce).unwrap());
+
+        let mut proofs = new_proofs(2);
+        proofs.insert(1, (proof, nonce));
+        assert_eq!(vec![true, false, true], new_proof_verifier().verify_batch(&proofs).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_malformed_proofs() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let sub_proof_request = sub_proof_request();
+
+        let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_signature,
+                                            &credential_values, &cred_pub_key, None, None).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let zero = || BigNumber::new().unwrap();
+        let n = cred_pub_key.p_key.n.clone().unwrap();
+
+        let corpus: Vec<(&str, Box<Fn(&mut Proof)>)> = vec![
+            ("no sub proofs", Box::new(|proof: &mut Proof| proof.proofs.clear())),
+            ("no ge proofs", Box::new(|proof: &mut Proof| proof.proofs[0].primary_proof.ge_proofs.clear())),
+            ("zero a_prime", Box::new(move |proof: &mut Proof| proof.proofs[0].primary_proof.eq_proof.a_prime = zero())),
+            ("a_prime equal to n", Box::new(move |proof: &mut Proof| proof.proofs[0].primary_proof.eq_proof.a_prime = n.clone().unwrap())),
+            ("missing m", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.eq_proof.m.remove("height"); })),
+            ("missing predicate m", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.eq_proof.m.remove("age"); })),
+            ("other mj", Box::new(|proof: &mut Proof| proof.proofs[0].primary_proof.ge_proofs[0].mj = BigNumber::from_u32(1).unwrap())),
+            ("missing t", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].t.remove("0"); })),
+            ("missing t delta", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].t.remove("DELTA"); })),
+            ("missing u", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].u.remove("3"); })),
+            ("missing r delta", Box::new(|proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].r.remove("DELTA"); })),
+            ("zero t", Box::new(move |proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].t.insert("1".to_owned(), zero()); })),
+            ("zero t delta", Box::new(move |proof: &mut Proof| { proof.proofs[0].primary_proof.ge_proofs[0].t.insert("DELTA".to_owned(), zero()); })),
+        ];
+
+        for (name, mutation) in corpus {
+            let mut malformed_proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+            mutation(&mut malformed_proof);
+
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None).unwrap();
+
+            match proof_verifier.verify(&malformed_proof, &nonce) {
+                Err(IndyCryptoError::AnoncredsProofRejected { .. }) => (),
+                res => panic!("Malformed proof '{}' isn't rejected: {:?}", name, res)
+            }
+        }
+    }
+
+    #[test]
+    fn verify_works_for_possession_only() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+        proof_builder.add_possession_only(&credential_schema, &cred_signature, &credential_values, &cred_pub_key).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        assert!(proof.proofs[0].primary_proof.eq_proof.revealed_attrs.is_empty());
+        assert!(proof.proofs[0].non_revoc_proof.is_none());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&proof, &new_nonce().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_proof_bound_to_multiple_nonces() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+        proof_builder.add_possession_only(&credential_schema, &cred_signature, &credential_values, &cred_pub_key).unwrap();
+
+        let broker_nonce = new_nonce().unwrap();
+        let auditor_nonce = new_nonce().unwrap();
+
+        assert!(proof_builder.finalize_multi(&[], &master_secret).is_err());
+        assert!(proof_builder.finalize_multi(&[&broker_nonce, &broker_nonce], &master_secret).is_err());
+
+        let proof = proof_builder.finalize_multi(&[&broker_nonce, &auditor_nonce], &master_secret).unwrap();
+        assert_eq!(vec![broker_nonce.clone().unwrap(), auditor_nonce.clone().unwrap()], proof.nonces());
+
+        for nonce in [&broker_nonce, &auditor_nonce].iter() {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+            assert!(proof_verifier.verify(&proof, nonce).unwrap());
+        }
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&proof, &new_nonce().unwrap()).unwrap());
+
+        // Nonces bound by prover can't be replaced or dropped
+        let mut tampered_proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+        tampered_proof.nonces = vec![broker_nonce.clone().unwrap()];
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&tampered_proof, &broker_nonce).unwrap());
+
+        tampered_proof.nonces = Vec::new();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        assert!(!proof_verifier.verify(&tampered_proof, &broker_nonce).unwrap());
+    }
+
+    struct RecordingObserver {
+        events: ::std::sync::Mutex<Vec<ProofEvent>>
+    }
+
+    impl ProofObserver for RecordingObserver {
+        fn on_event(&self, event: &ProofEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn verify_works_for_observer() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            prover::Prover::blind_master_secret(&cred_pub_key, &cred_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_DID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &cred_issuance_nonce,
+                                    &credential_values,
+                                    &cred_pub_key,
+                                    &cred_priv_key).unwrap();
+
+        prover::Prover::process_credential_signature(&mut cred_signature,
+                                                     &credential_values,
+                                                     &signature_correctness_proof,
+                                                     &master_secret_blinding_data,
+                                                     &master_secret,
+                                                     &cred_pub_key,
+                                                     &cred_issuance_nonce,
+                                                     None, None, None).unwrap();
+
+        let prover_observer = Arc::new(RecordingObserver { events: ::std::sync::Mutex::new(Vec::new()) });
+
+        let mut proof_builder = prover::Prover::new_proof_builder().unwrap();
+        proof_builder.set_observer(prover_observer.clone()).unwrap();
+        proof_builder.add_possession_only(&credential_schema, &cred_signature, &credential_values, &cred_pub_key).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let challenge = proof.aggregated_proof.c_hash.to_hex().unwrap();
+        let prover_events = prover_observer.events.lock().unwrap().clone();
+        assert_eq!(3, prover_events.len());
+        assert_eq!(ProofEvent::SubProofStarted { sub_proof_idx: 0 }, prover_events[0]);
+        assert_eq!(ProofEvent::ChallengeComputed { challenge: challenge.clone() }, prover_events[2]);
+
+        let verifier_observer = Arc::new(RecordingObserver { events: ::std::sync::Mutex::new(Vec::new()) });
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        proof_verifier.set_observer(verifier_observer.clone()).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let verifier_events = verifier_observer.events.lock().unwrap().clone();
+        assert_eq!(prover_events[1], verifier_events[3]);
+        assert!(verifier_events.contains(&ProofEvent::ChallengeComputed { challenge }));
+        assert_eq!(Some(&ProofEvent::CheckPassed { check: ProofCheck::Proof }), verifier_events.last());
+
+        let verifier_observer = Arc::new(RecordingObserver { events: ::std::sync::Mutex::new(Vec::new()) });
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        proof_verifier.set_observer(verifier_observer.clone()).unwrap();
+        assert!(!proof_verifier.verify(&proof, &new_nonce().unwrap()).unwrap());
+
+        let verifier_events = verifier_observer.events.lock().unwrap().clone();
+        assert!(verifier_events.iter().any(|event| match *event {
+            ProofEvent::CheckFailed { check: ProofCheck::Challenge, .. } => true,
+            _ => false
+        }));
+    }
+
+    #[test]
+    fn verify_works_for_min_nonce_size() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        assert!(proof_verifier.set_min_nonce_size(64).is_err());
+
+        proof_verifier.add_possession_only(&credential_schema, &cred_pub_key).unwrap();
+        proof_verifier.set_min_nonce_size(128).unwrap();
+
+        let nonce = new_nonce_with_size(96).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, proof_verifier.verify(&proof(), &nonce).unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn check_encoding_works() {
+        assert!(Verifier::check_encoding("28", &BigNumber::from_dec("28").unwrap()).unwrap());
+        assert!(Verifier::check_encoding("Alex", &AttrValueEncoding::Sha256.encode("Alex").unwrap()).unwrap());
+        assert!(!Verifier::check_encoding("28", &AttrValueEncoding::Sha256.encode("28").unwrap()).unwrap());
+        assert!(!Verifier::check_encoding("Alex", &BigNumber::from_dec("28").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn check_revealed_attribute_bundle_works() {
+        let mut proof = proof();
+        proof.proofs[0].primary_proof.eq_proof.revealed_attrs.insert("name".to_string(), AttrValueEncoding::Sha256.encode("Alex").unwrap());
+
+        let mut bundle = RevealedAttributeBundle::new().unwrap();
+        bundle.add_raw_value("name", "Alex").unwrap();
+        assert!(Verifier::check_revealed_attribute_bundle(&bundle, &proof, 0).unwrap());
+        assert_eq!(Some("Alex"), bundle.get_raw("name"));
+
+        let mut bundle = RevealedAttributeBundle::new().unwrap();
+        bundle.add_raw_value("name", "Alice").unwrap();
+        assert!(!Verifier::check_revealed_attribute_bundle(&bundle, &proof, 0).unwrap());
+
+        let mut bundle = RevealedAttributeBundle::new().unwrap();
+        bundle.attrs.insert("name".to_string(), RevealedAttribute {
+            raw: "Alice".to_string(),
+            encoded: AttrValueEncoding::Sha256.encode("Alex").unwrap(),
+            encoding: AttrValueEncoding::Sha256
+        });
+        assert!(!Verifier::check_revealed_attribute_bundle(&bundle, &proof, 0).unwrap());
+
+        let mut bundle = RevealedAttributeBundle::new().unwrap();
+        bundle.add_raw_value("age", "28").unwrap();
+        match Verifier::check_revealed_attribute_bundle(&bundle, &proof, 0) {
+            Err(IndyCryptoError::MissingAttribute { ref attr, .. }) if attr == "age" => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn add_sub_proof_request_works_for_missing_attribute() {
+        MockHelper::inject();
+
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new().unwrap();
+        sub_proof_request_builder.add_revealed_attr("weight").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        match proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &cred_pub_key, None, None) {
+            Err(IndyCryptoError::MissingAttribute { ref attr, .. }) if attr == "weight" => {}
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn add_sub_proof_request_works_for_checked_revealed_or_predicate_attribute() {
+        MockHelper::inject();
+
+        let credential_schema = issuer::mocks::credential_schema();
+        let (cred_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // Builder rejects such requests, so they are built as deserialized ones would be
+        let mut revealed_sub_proof_request = prover::mocks::sub_proof_request();
+        revealed_sub_proof_request.attr_value_checks.insert("name".to_string(), "1139481716457488690172217916278103335".to_string());
+
+        let mut predicate_sub_proof_request = prover::mocks::sub_proof_request();
+        predicate_sub_proof_request.attr_value_checks.insert("age".to_string(), "28".to_string());
+
+        for sub_proof_request in [revealed_sub_proof_request, predicate_sub_proof_request].iter() {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            let res = proof_verifier.add_sub_proof_request(sub_proof_request, &credential_schema, &cred_pub_key, None, None);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+        }
+    }
+
+    #[test]
+    fn verify_master_secret_proof_works() {
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let master_secret_proof = prover::Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+
+        assert!(Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &nonce).unwrap());
+        assert!(!Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &new_nonce().unwrap()).unwrap());
+        assert_eq!(&prover::Prover::create_pseudonym("example.com", &master_secret).unwrap(), master_secret_proof.pseudonym());
+    }
+
+    #[test]
+    fn verify_master_secret_proof_works_for_other_pseudonym() {
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let other_master_secret = prover::Prover::new_master_secret().unwrap();
+        let nonce = new_nonce().unwrap();
+
+        let mut master_secret_proof = prover::Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+        master_secret_proof.pseudonym = prover::Prover::create_pseudonym("example.com", &other_master_secret).unwrap();
+
+        assert!(!Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &nonce).unwrap());
+    }
+
+    #[test]
+    fn verify_master_secret_proof_works_for_pseudonym_out_of_range() {
+        let master_secret = prover::Prover::new_master_secret().unwrap();
+        let nonce = new_nonce().unwrap();
+        let p = Pseudonym::modulus().unwrap();
+
+        let master_secret_proof = prover::Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+        let value = master_secret_proof.pseudonym.value.clone().unwrap();
+
+        for invalid_value in vec![BigNumber::new().unwrap(), BigNumber::from_u32(1).unwrap(), p.clone().unwrap(), p.add(&value).unwrap()] {
+            let mut master_secret_proof = prover::Prover::create_master_secret_proof("example.com", &master_secret, &nonce).unwrap();
+            master_secret_proof.pseudonym.value = invalid_value;
+            assert!(!Verifier::verify_master_secret_proof(&master_secret_proof, "example.com", &nonce).unwrap());
+        }
+    }
 
     #[test]
     fn sub_proof_request_builder_works() {
@@ -472,6 +2869,52 @@ mod tests {
         48679702777436593", res[0].to_dec().unwrap());
     }
 
+    #[test]
+    fn verify_equality_works_for_oversized_responses() {
+        MockHelper::inject();
+
+        let pk = issuer::mocks::credential_primary_public_key();
+        let c_h = prover::mocks::aggregated_proof().c_hash;
+        let credential_schema = issuer::mocks::credential_schema();
+
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let oversized = |bits: usize| BigNumber::from_dec("2").unwrap()
+            .exp(&BigNumber::from_dec(&bits.to_string()).unwrap(), None).unwrap();
+
+        let mut proof = prover::mocks::eq_proof();
+        proof.e = oversized(LARGE_ETILDE + 1);
+        let res = ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &sub_proof_request);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        let mut proof = prover::mocks::eq_proof();
+        proof.v = oversized(SecurityProfile::default().vtilde_bits() + 1);
+        let res = ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &sub_proof_request);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        let mut proof = prover::mocks::eq_proof();
+        proof.m.insert("age".to_string(), oversized(LARGE_MTILDE + 1));
+        let res = ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &sub_proof_request);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn verify_ge_predicate_works_for_oversized_responses() {
+        MockHelper::inject();
+
+        let pk = issuer::mocks::credential_primary_public_key();
+        let c_h = prover::mocks::aggregated_proof().c_hash;
+
+        let mut proof = prover::mocks::ge_proof();
+        proof.u.insert("0".to_string(), BigNumber::from_dec("2").unwrap()
+            .exp(&BigNumber::from_dec(&(LARGE_UTILDE + 1).to_string()).unwrap(), None).unwrap());
+
+        let res = ProofVerifier::_verify_ge_predicate(&pk, &proof, &c_h);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
     #[test]
     fn _verify_ge_predicate_works() {
         MockHelper::inject();
@@ -500,4 +2943,114 @@ mod tests {
         1864273991033137371106324132550175224820164581900030456410773386740196083471393997554706544523739752281900419801521207994038554809091738654313973079882387597672518908535\
         80982844825639097363091181044515877489450972963624109587697097258041963985607958610791800500711857115582406526050626576194", res_data[5].to_dec().unwrap());
     }
+
+    #[test]
+    #[cfg(feature = "box_sodium")]
+    fn unseal_works() {
+        let proof = prover::mocks::proof();
+        let (verifier_pk, verifier_sk) = new_key_pair().unwrap();
+
+        let sealed_proof = proof.seal_for(&verifier_pk).unwrap();
+        let unsealed_proof = ProofVerifier::unseal(&sealed_proof, &verifier_pk, &verifier_sk).unwrap();
+
+        assert_eq!(proof.proofs.len(), unsealed_proof.proofs.len());
+        assert_eq!(proof.aggregated_proof, unsealed_proof.aggregated_proof);
+    }
+
+    #[test]
+    #[cfg(feature = "box_sodium")]
+    fn unseal_works_for_other_verifier() {
+        let proof = prover::mocks::proof();
+        let (verifier_pk, _) = new_key_pair().unwrap();
+        let (other_pk, other_sk) = new_key_pair().unwrap();
+
+        let sealed_proof = proof.seal_for(&verifier_pk).unwrap();
+        assert!(ProofVerifier::unseal(&sealed_proof, &other_pk, &other_sk).is_err());
+    }
+
+    fn _bls_keys() -> (Generator, SignKey, VerKey) {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        (gen, sign_key, ver_key)
+    }
+
+    #[test]
+    fn check_key_rotation_works() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (gen, sign_key, ver_key) = _bls_keys();
+        let (old_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let (new_pub_key, _, _, key_rotation) =
+            Issuer::rotate_credential_def(&credential_schema, &old_pub_key, &sign_key, false, 1514764800).unwrap();
+
+        ProofVerifier::_check_key_rotation(&old_pub_key, &new_pub_key, &key_rotation, &ver_key, &gen).unwrap();
+        assert!(ProofVerifier::_check_key_rotation(&new_pub_key, &old_pub_key, &key_rotation, &ver_key, &gen).is_err());
+    }
+
+    #[test]
+    fn check_key_rotation_works_for_changed_grace_period() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (gen, sign_key, ver_key) = _bls_keys();
+        let (old_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let (new_pub_key, _, _, mut key_rotation) =
+            Issuer::rotate_credential_def(&credential_schema, &old_pub_key, &sign_key, false, 1514764800).unwrap();
+
+        key_rotation.grace_period_end += 1;
+        assert!(ProofVerifier::_check_key_rotation(&old_pub_key, &new_pub_key, &key_rotation, &ver_key, &gen).is_err());
+    }
+
+    #[test]
+    fn check_key_rotation_works_for_other_issuer_key() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let (gen, sign_key, _) = _bls_keys();
+        let other_ver_key = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+        let (old_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let (new_pub_key, _, _, key_rotation) =
+            Issuer::rotate_credential_def(&credential_schema, &old_pub_key, &sign_key, false, 1514764800).unwrap();
+
+        assert!(ProofVerifier::_check_key_rotation(&old_pub_key, &new_pub_key, &key_rotation, &other_ver_key, &gen).is_err());
+    }
+
+    #[test]
+    fn add_sub_proof_request_with_key_rotation_works_for_grace_period() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let sub_proof_request = prover::mocks::sub_proof_request();
+        let (gen, sign_key, ver_key) = _bls_keys();
+        let (old_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let (new_pub_key, _, _, key_rotation) =
+            Issuer::rotate_credential_def(&credential_schema, &old_pub_key, &sign_key, false, 1514764800).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request, &credential_schema, &old_pub_key, &new_pub_key,
+                                                               &key_rotation, &ver_key, &gen, 1514764800, None, None).unwrap();
+        proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request, &credential_schema, &old_pub_key, &new_pub_key,
+                                                               &key_rotation, &ver_key, &gen, 1514764801, None, None).unwrap();
+
+        assert_eq!(2, proof_verifier.credentials[0].candidate_pub_keys.len());
+        assert_eq!(1, proof_verifier.credentials[1].candidate_pub_keys.len());
+        assert!(proof_verifier.credentials[1].candidate_pub_keys.contains_key(key_rotation.new_key_id()));
+        assert!(!proof_verifier.credentials[1].key_id_required);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn verify_works_for_honest_proof(scenario in mocks::proof_scenario()) {
+            let fixture = scenario.prove().unwrap();
+            prop_assert!(fixture.verify(&fixture.proof, &fixture.nonce).unwrap());
+        }
+
+        #[test]
+        fn verify_works_for_mutated_proof(scenario in mocks::proof_scenario(), mutation in mocks::proof_mutation()) {
+            let fixture = scenario.prove().unwrap();
+
+            let mut proof = Proof::from_json(&fixture.proof.to_json().unwrap()).unwrap();
+            let mut nonce = fixture.nonce.clone().unwrap();
+            mutation.apply(&mut proof, &mut nonce).unwrap();
+
+            // Mutated proof is either rejected as inconsistent or reported as invalid
+            prop_assert!(!fixture.verify(&proof, &nonce).unwrap_or(false));
+        }
+    }
 }
@@ -0,0 +1,556 @@
+use bn::{BigNumber, BigNumberContext};
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+/// Additional bits of blinding values used in proofs, so responses statistically hide committed values.
+const BLINDING_EXTRA_BITS: usize = 256 + 80;
+
+/// Generate a pedersen commitment to a given number
+///
+/// # Arguments
+/// * `gen_1` - first generator
+/// * `m` - exponent of the first generator
+/// * `gen_2` - second generator
+/// * `r` - exponent of the second generator
+/// * `modulus` - all computations are done this modulo
+/// * `ctx` - big number context
+///
+/// # Result
+/// Return the pedersen commitment, i.e `(gen_1^m)*(gen_2^r)`
+pub fn get_pedersen_commitment(gen_1: &BigNumber, m: &BigNumber,
+                               gen_2: &BigNumber, r: &BigNumber,
+                               modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    let commitment = gen_1.mod_exp(m, modulus, Some(ctx))?
+        .mod_mul(&gen_2.mod_exp(r, modulus, Some(ctx))?,
+                 modulus, Some(ctx))?;
+    Ok(commitment)
+}
+
+
+/// Generate a pedersen commitment over `n` values
+///
+/// # Arguments
+/// * `to_commit` - a list of 2-tuples where the first element of the tuple is a generator and
+/// the second is the value being committed to, like [(g_1, m_1), (g_2, m_2), (g_3, m_3), ... (g_i, m_i)]
+/// * `gen_2` - second generator
+/// * `r` - exponent of the second generator
+/// * `modulus` - all computations are done this modulo
+/// * `ctx` - big number context
+///
+/// # Result
+/// Return the pedersen commitment, i.e `(g_1^m_1)*(g_2^m_2)*...(g_i^m_i)*(gen_2^r)`
+pub fn get_generalised_pedersen_commitment(to_commit: Vec<(&BigNumber, &BigNumber)>,
+                               gen_2: &BigNumber, r: &BigNumber,
+                               modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    let accumulated = get_exponentiated_generators(to_commit, modulus, ctx)?;
+    let commitment = accumulated.mod_mul(&gen_2.mod_exp(r, modulus, Some(ctx))?,
+                 modulus, Some(ctx))?;
+    Ok(commitment)
+}
+
+
+/// Exponentiate the given generators to corresponding exponents
+///
+/// # Arguments
+/// * `to_exponentiate` - a list of 2-tuples where the first element of the tuple is a generator and
+/// the second is the exponent, like [(g_1, e_1), (g_2, e_2), (g_3, e_3), ... (g_i, e_i)]
+/// * `modulus` - all computations are done this modulo
+/// * `ctx` - big number context
+///
+/// # Result
+/// Return the exponentiation, i.e `(g_1^e_1)*(g_2^e_2)*...(g_i^e_i)`
+pub fn get_exponentiated_generators(to_exponentiate: Vec<(&BigNumber, &BigNumber)>,
+                                    modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    let mut accumulated = BigNumber::from_dec("1")?;
+    for &(g, m) in to_exponentiate.iter() {
+        accumulated = accumulated.mod_mul(
+            &g.mod_exp(m, modulus, Some(ctx))?, modulus, Some(ctx)
+        )?;
+    }
+    Ok(accumulated)
+}
+
+/// Parameters of multi-base Pedersen commitment `C = g_1^m_1 * ... * g_n^m_n * h^r mod modulus`.
+/// Group can be of known (safe prime modulus) or unknown (RSA modulus) order,
+/// proofs use integer responses that don't require group order.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommitmentParams {
+    generators: Vec<BigNumber>,
+    h: BigNumber,
+    modulus: BigNumber
+}
+
+impl CommitmentParams {
+    /// Creates commitment parameters.
+    ///
+    /// # Arguments
+    /// * `generators` - Bases of committed values. Discrete logs between bases must be unknown to committer.
+    /// * `h` - Base of blinding factor.
+    /// * `modulus` - Group modulus.
+    pub fn new(generators: Vec<BigNumber>, h: BigNumber, modulus: BigNumber) -> Result<CommitmentParams, IndyCryptoError> {
+        trace!("CommitmentParams::new: >>> generators: {:?}, h: {:?}, modulus: {:?}", generators, h, modulus);
+
+        if generators.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Commitment requires at least one generator")));
+        }
+
+        let params = CommitmentParams { generators, h, modulus };
+
+        trace!("CommitmentParams::new: <<< params: {:?}", params);
+
+        Ok(params)
+    }
+
+    /// Returns count of values that can be committed with these parameters.
+    pub fn values_count(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Commits to values with random blinding factor.
+    ///
+    /// # Arguments
+    /// * `values` - Committed values, one per generator.
+    pub fn commit(&self, values: &[BigNumber]) -> Result<(Commitment, CommitmentOpening), IndyCryptoError> {
+        trace!("CommitmentParams::commit: >>> values: {:?}", values);
+
+        let r = BigNumber::rand(self.modulus.num_bits()? as usize)?;
+
+        let opening = CommitmentOpening {
+            values: values.iter().map(|value| value.clone()).collect::<Result<Vec<BigNumber>, IndyCryptoError>>()?,
+            r
+        };
+        let commitment = self.commit_with_opening(&opening)?;
+
+        trace!("CommitmentParams::commit: <<< commitment: {:?}, opening: {:?}", commitment, opening);
+
+        Ok((commitment, opening))
+    }
+
+    /// Commits to values of opening with its blinding factor.
+    ///
+    /// # Arguments
+    /// * `opening` - Committed values and blinding factor.
+    pub fn commit_with_opening(&self, opening: &CommitmentOpening) -> Result<Commitment, IndyCryptoError> {
+        trace!("CommitmentParams::commit_with_opening: >>> opening: {:?}", opening);
+
+        self._check_opening(opening)?;
+
+        let mut ctx = BigNumber::new_context()?;
+        let to_commit: Vec<(&BigNumber, &BigNumber)> = self.generators.iter().zip(opening.values.iter()).collect();
+        let value = get_generalised_pedersen_commitment(to_commit, &self.h, &opening.r, &self.modulus, &mut ctx)?;
+
+        let commitment = Commitment { value };
+
+        trace!("CommitmentParams::commit_with_opening: <<< commitment: {:?}", commitment);
+
+        Ok(commitment)
+    }
+
+    /// Checks that opening corresponds to commitment.
+    ///
+    /// # Arguments
+    /// * `commitment` - Commitment.
+    /// * `opening` - Committed values and blinding factor.
+    pub fn verify_opening(&self, commitment: &Commitment, opening: &CommitmentOpening) -> Result<bool, IndyCryptoError> {
+        trace!("CommitmentParams::verify_opening: >>> commitment: {:?}, opening: {:?}", commitment, opening);
+
+        let valid = self.commit_with_opening(opening)?.value == commitment.value;
+
+        trace!("CommitmentParams::verify_opening: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Creates zero-knowledge proof of knowledge of commitment opening.
+    ///
+    /// # Arguments
+    /// * `commitment` - Commitment.
+    /// * `opening` - Committed values and blinding factor.
+    /// * `nonce` - Verifier nonce.
+    pub fn prove_opening(&self,
+                         commitment: &Commitment,
+                         opening: &CommitmentOpening,
+                         nonce: &BigNumber) -> Result<OpeningProof, IndyCryptoError> {
+        trace!("CommitmentParams::prove_opening: >>> commitment: {:?}, opening: {:?}, nonce: {:?}", commitment, opening, nonce);
+
+        self._check_opening(opening)?;
+
+        let (tildes, r_tilde) = self._gen_tildes(None)?;
+        let t = self._calc_t(&tildes, &r_tilde)?;
+        let c = _challenge(&[(self, commitment, &t)], nonce)?;
+
+        let (values, r) = self._responses(opening, &tildes, &r_tilde, &c)?;
+        let proof = OpeningProof { c, values, r };
+
+        trace!("CommitmentParams::prove_opening: <<< proof: {:?}", proof);
+
+        Ok(proof)
+    }
+
+    /// Verifies zero-knowledge proof of knowledge of commitment opening.
+    ///
+    /// # Arguments
+    /// * `commitment` - Commitment.
+    /// * `proof` - Opening proof.
+    /// * `nonce` - Verifier nonce.
+    pub fn verify_opening_proof(&self,
+                                commitment: &Commitment,
+                                proof: &OpeningProof,
+                                nonce: &BigNumber) -> Result<bool, IndyCryptoError> {
+        trace!("CommitmentParams::verify_opening_proof: >>> commitment: {:?}, proof: {:?}, nonce: {:?}", commitment, proof, nonce);
+
+        let t = self._calc_t_hat(commitment, &proof.values, &proof.r, &proof.c)?;
+        let valid = _challenge(&[(self, commitment, &t)], nonce)? == proof.c;
+
+        trace!("CommitmentParams::verify_opening_proof: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    fn _check_opening(&self, opening: &CommitmentOpening) -> Result<(), IndyCryptoError> {
+        if opening.values.len() != self.generators.len() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid count of committed values: expected {}, actual {}", self.generators.len(), opening.values.len())));
+        }
+
+        let max_bits = self.modulus.num_bits()?;
+        for value in opening.values.iter().chain(Some(&opening.r)) {
+            if value.is_negative()? || value.num_bits()? > max_bits {
+                return Err(IndyCryptoError::InvalidStructure(format!("Committed value is out of range")));
+            }
+        }
+
+        Ok(())
+    }
+
+    // `shared` sets blinding value used for the value at index, so responses for it are equal across proofs.
+    fn _gen_tildes(&self, shared: Option<(usize, &BigNumber)>) -> Result<(Vec<BigNumber>, BigNumber), IndyCryptoError> {
+        let size = self.modulus.num_bits()? as usize + BLINDING_EXTRA_BITS;
+
+        let mut tildes: Vec<BigNumber> = Vec::new();
+        for idx in 0..self.generators.len() {
+            match shared {
+                Some((shared_idx, tilde)) if shared_idx == idx => tildes.push(tilde.clone()?),
+                _ => tildes.push(BigNumber::rand(size)?)
+            }
+        }
+
+        Ok((tildes, BigNumber::rand(size)?))
+    }
+
+    fn _calc_t(&self, tildes: &[BigNumber], r_tilde: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let to_commit: Vec<(&BigNumber, &BigNumber)> = self.generators.iter().zip(tildes.iter()).collect();
+        get_generalised_pedersen_commitment(to_commit, &self.h, r_tilde, &self.modulus, &mut ctx)
+    }
+
+    fn _calc_t_hat(&self,
+                   commitment: &Commitment,
+                   values: &[BigNumber],
+                   r: &BigNumber,
+                   c: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        if values.len() != self.generators.len() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid count of responses: expected {}, actual {}", self.generators.len(), values.len())));
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let c_inverse = commitment.value
+            .inverse(&self.modulus, Some(&mut ctx))?
+            .mod_exp(c, &self.modulus, Some(&mut ctx))?;
+
+        self._calc_t(values, r)?
+            .mod_mul(&c_inverse, &self.modulus, Some(&mut ctx))
+    }
+
+    fn _responses(&self,
+                  opening: &CommitmentOpening,
+                  tildes: &[BigNumber],
+                  r_tilde: &BigNumber,
+                  c: &BigNumber) -> Result<(Vec<BigNumber>, BigNumber), IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        let mut values: Vec<BigNumber> = Vec::new();
+        for (value, tilde) in opening.values.iter().zip(tildes.iter()) {
+            values.push(c.mul(value, Some(&mut ctx))?.add(tilde)?);
+        }
+
+        let r = c.mul(&opening.r, Some(&mut ctx))?.add(r_tilde)?;
+
+        Ok((values, r))
+    }
+}
+
+impl JsonEncodable for CommitmentParams {}
+
+impl<'a> JsonDecodable<'a> for CommitmentParams {}
+
+/// Multi-base Pedersen commitment.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Commitment {
+    value: BigNumber
+}
+
+impl Commitment {
+    pub fn value(&self) -> &BigNumber {
+        &self.value
+    }
+}
+
+impl JsonEncodable for Commitment {}
+
+impl<'a> JsonDecodable<'a> for Commitment {}
+
+/// Committed values and blinding factor. Must be kept secret by committer.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommitmentOpening {
+    values: Vec<BigNumber>,
+    r: BigNumber
+}
+
+impl CommitmentOpening {
+    pub fn new(values: Vec<BigNumber>, r: BigNumber) -> CommitmentOpening {
+        CommitmentOpening { values, r }
+    }
+
+    pub fn values(&self) -> &[BigNumber] {
+        &self.values
+    }
+}
+
+impl JsonEncodable for CommitmentOpening {}
+
+impl<'a> JsonDecodable<'a> for CommitmentOpening {}
+
+/// Zero-knowledge proof of knowledge of commitment opening.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OpeningProof {
+    c: BigNumber,
+    values: Vec<BigNumber>,
+    r: BigNumber
+}
+
+impl JsonEncodable for OpeningProof {}
+
+impl<'a> JsonDecodable<'a> for OpeningProof {}
+
+/// Zero-knowledge proof that two commitments contain the same value at given positions.
+/// Commitments can use different parameters (for example generators of different issuers).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EqualityProof {
+    c: BigNumber,
+    values1: Vec<BigNumber>,
+    r1: BigNumber,
+    values2: Vec<BigNumber>,
+    r2: BigNumber
+}
+
+impl JsonEncodable for EqualityProof {}
+
+impl<'a> JsonDecodable<'a> for EqualityProof {}
+
+/// Creates zero-knowledge proof that value at `idx1` of the first commitment
+/// equals to value at `idx2` of the second commitment.
+///
+/// # Arguments
+/// * `params1` - Parameters of the first commitment.
+/// * `commitment1` - The first commitment.
+/// * `opening1` - Opening of the first commitment.
+/// * `idx1` - Index of value in the first commitment.
+/// * `params2` - Parameters of the second commitment.
+/// * `commitment2` - The second commitment.
+/// * `opening2` - Opening of the second commitment.
+/// * `idx2` - Index of value in the second commitment.
+/// * `nonce` - Verifier nonce.
+pub fn prove_equality(params1: &CommitmentParams,
+                      commitment1: &Commitment,
+                      opening1: &CommitmentOpening,
+                      idx1: usize,
+                      params2: &CommitmentParams,
+                      commitment2: &Commitment,
+                      opening2: &CommitmentOpening,
+                      idx2: usize,
+                      nonce: &BigNumber) -> Result<EqualityProof, IndyCryptoError> {
+    trace!("commitments::prove_equality: >>> commitment1: {:?}, idx1: {:?}, commitment2: {:?}, idx2: {:?}, nonce: {:?}",
+           commitment1, idx1, commitment2, idx2, nonce);
+
+    params1._check_opening(opening1)?;
+    params2._check_opening(opening2)?;
+
+    let value1 = opening1.values.get(idx1)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by index '{}' not found in opening1", idx1)))?;
+    let value2 = opening2.values.get(idx2)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by index '{}' not found in opening2", idx2)))?;
+
+    if value1 != value2 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Committed values aren't equal")));
+    }
+
+    let size = ::std::cmp::max(params1.modulus.num_bits()?, params2.modulus.num_bits()?) as usize + BLINDING_EXTRA_BITS;
+    let shared_tilde = BigNumber::rand(size)?;
+
+    let (tildes1, r_tilde1) = params1._gen_tildes(Some((idx1, &shared_tilde)))?;
+    let (tildes2, r_tilde2) = params2._gen_tildes(Some((idx2, &shared_tilde)))?;
+
+    let t1 = params1._calc_t(&tildes1, &r_tilde1)?;
+    let t2 = params2._calc_t(&tildes2, &r_tilde2)?;
+
+    let c = _challenge(&[(params1, commitment1, &t1), (params2, commitment2, &t2)], nonce)?;
+
+    let (values1, r1) = params1._responses(opening1, &tildes1, &r_tilde1, &c)?;
+    let (values2, r2) = params2._responses(opening2, &tildes2, &r_tilde2, &c)?;
+
+    let proof = EqualityProof { c, values1, r1, values2, r2 };
+
+    trace!("commitments::prove_equality: <<< proof: {:?}", proof);
+
+    Ok(proof)
+}
+
+/// Verifies zero-knowledge proof that value at `idx1` of the first commitment
+/// equals to value at `idx2` of the second commitment.
+///
+/// # Arguments
+/// * `params1` - Parameters of the first commitment.
+/// * `commitment1` - The first commitment.
+/// * `idx1` - Index of value in the first commitment.
+/// * `params2` - Parameters of the second commitment.
+/// * `commitment2` - The second commitment.
+/// * `idx2` - Index of value in the second commitment.
+/// * `proof` - Equality proof.
+/// * `nonce` - Verifier nonce.
+pub fn verify_equality(params1: &CommitmentParams,
+                       commitment1: &Commitment,
+                       idx1: usize,
+                       params2: &CommitmentParams,
+                       commitment2: &Commitment,
+                       idx2: usize,
+                       proof: &EqualityProof,
+                       nonce: &BigNumber) -> Result<bool, IndyCryptoError> {
+    trace!("commitments::verify_equality: >>> commitment1: {:?}, idx1: {:?}, commitment2: {:?}, idx2: {:?}, proof: {:?}, nonce: {:?}",
+           commitment1, idx1, commitment2, idx2, proof, nonce);
+
+    let response1 = proof.values1.get(idx1)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by index '{}' not found in proof.values1", idx1)))?;
+    let response2 = proof.values2.get(idx2)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by index '{}' not found in proof.values2", idx2)))?;
+
+    let t1 = params1._calc_t_hat(commitment1, &proof.values1, &proof.r1, &proof.c)?;
+    let t2 = params2._calc_t_hat(commitment2, &proof.values2, &proof.r2, &proof.c)?;
+
+    let valid = response1 == response2 &&
+        _challenge(&[(params1, commitment1, &t1), (params2, commitment2, &t2)], nonce)? == proof.c;
+
+    trace!("commitments::verify_equality: <<< valid: {:?}", valid);
+
+    Ok(valid)
+}
+
+fn _challenge(statements: &[(&CommitmentParams, &Commitment, &BigNumber)], nonce: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    let mut values: Vec<Vec<u8>> = Vec::new();
+
+    for &(params, commitment, t) in statements.iter() {
+        for generator in params.generators.iter() {
+            values.push(generator.to_bytes()?);
+        }
+        values.push(params.h.to_bytes()?);
+        values.push(params.modulus.to_bytes()?);
+        values.push(commitment.value.to_bytes()?);
+        values.push(t.to_bytes()?);
+    }
+    values.push(nonce.to_bytes()?);
+
+    BigNumber::from_bytes(&BigNumber::hash_array(&values)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _params(count: usize) -> CommitmentParams {
+        let modulus = BigNumber::generate_safe_prime(256).unwrap();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        let mut random_qrs: Vec<BigNumber> = Vec::new();
+        for _ in 0..count + 1 {
+            random_qrs.push(BigNumber::rand(256).unwrap().sqr(Some(&mut ctx)).unwrap().modulus(&modulus, Some(&mut ctx)).unwrap());
+        }
+
+        let h = random_qrs.pop().unwrap();
+        CommitmentParams::new(random_qrs, h, modulus).unwrap()
+    }
+
+    fn _values(values: &[u32]) -> Vec<BigNumber> {
+        values.iter().map(|value| BigNumber::from_u32(*value as usize).unwrap()).collect()
+    }
+
+    #[test]
+    fn get_pedersen_commitment_works() {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let modulus = BigNumber::from_u32(23).unwrap();
+        let commitment = get_pedersen_commitment(&BigNumber::from_u32(2).unwrap(), &BigNumber::from_u32(3).unwrap(),
+                                                 &BigNumber::from_u32(3).unwrap(), &BigNumber::from_u32(2).unwrap(),
+                                                 &modulus, &mut ctx).unwrap();
+        assert_eq!(BigNumber::from_u32(3).unwrap(), commitment); // 8 * 9 mod 23
+    }
+
+    #[test]
+    fn commit_works() {
+        let params = _params(3);
+        let (commitment, opening) = params.commit(&_values(&[1, 2, 3])).unwrap();
+        assert!(params.verify_opening(&commitment, &opening).unwrap());
+
+        let other_opening = CommitmentOpening::new(_values(&[1, 2, 4]), opening.r.clone().unwrap());
+        assert!(!params.verify_opening(&commitment, &other_opening).unwrap());
+    }
+
+    #[test]
+    fn commit_works_for_invalid_values_count() {
+        let params = _params(3);
+        assert!(params.commit(&_values(&[1, 2])).is_err());
+    }
+
+    #[test]
+    fn prove_opening_works() {
+        let params = _params(2);
+        let nonce = BigNumber::rand(80).unwrap();
+
+        let (commitment, opening) = params.commit(&_values(&[10, 20])).unwrap();
+        let proof = params.prove_opening(&commitment, &opening, &nonce).unwrap();
+
+        assert!(params.verify_opening_proof(&commitment, &proof, &nonce).unwrap());
+        assert!(!params.verify_opening_proof(&commitment, &proof, &BigNumber::rand(80).unwrap()).unwrap());
+
+        let (other_commitment, _) = params.commit(&_values(&[10, 20])).unwrap();
+        assert!(!params.verify_opening_proof(&other_commitment, &proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn prove_equality_works() {
+        let params1 = _params(2);
+        let params2 = _params(3);
+        let nonce = BigNumber::rand(80).unwrap();
+
+        let (commitment1, opening1) = params1.commit(&_values(&[5, 42])).unwrap();
+        let (commitment2, opening2) = params2.commit(&_values(&[42, 7, 8])).unwrap();
+
+        let proof = prove_equality(&params1, &commitment1, &opening1, 1,
+                                   &params2, &commitment2, &opening2, 0, &nonce).unwrap();
+
+        assert!(verify_equality(&params1, &commitment1, 1, &params2, &commitment2, 0, &proof, &nonce).unwrap());
+        assert!(!verify_equality(&params1, &commitment1, 0, &params2, &commitment2, 0, &proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn prove_equality_works_for_different_values() {
+        let params = _params(1);
+        let nonce = BigNumber::rand(80).unwrap();
+
+        let (commitment1, opening1) = params.commit(&_values(&[5])).unwrap();
+        let (commitment2, opening2) = params.commit(&_values(&[6])).unwrap();
+
+        assert!(prove_equality(&params, &commitment1, &opening1, 0,
+                               &params, &commitment2, &opening2, 0, &nonce).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use errors::IndyCryptoError;
+use utils::base58;
+
+use sodiumoxide;
+use sodiumoxide::crypto::sign;
+
+#[cfg(feature = "serialization")]
+use serde::ser::{Serialize, Serializer};
+#[cfg(feature = "serialization")]
+use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
+#[cfg(feature = "serialization")]
+use std::fmt;
+
+use std::sync::{Once, ONCE_INIT};
+
+static SODIUM_INIT: Once = ONCE_INIT;
+
+fn _init() {
+    SODIUM_INIT.call_once(|| { sodiumoxide::init(); });
+}
+
+/// Length of DID created from verkey (first half of verkey).
+pub const DID_BYTES_SIZE: usize = 16;
+
+/// Ed25519 signing key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignKey {
+    key: sign::SecretKey
+}
+
+impl SignKey {
+    pub const BYTES_REPR_SIZE: usize = sign::SECRETKEYBYTES;
+    pub const SEED_SIZE: usize = sign::SEEDBYTES;
+
+    /// Returns sign key bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key.0
+    }
+
+    /// Creates and returns sign key from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignKey, IndyCryptoError> {
+        Ok(SignKey {
+            key: sign::SecretKey::from_slice(bytes)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of sign key: expected {}, actual {}",
+                                                                 Self::BYTES_REPR_SIZE, bytes.len())))?
+        })
+    }
+}
+
+/// Ed25519 verification key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerKey {
+    key: sign::PublicKey
+}
+
+impl VerKey {
+    pub const BYTES_REPR_SIZE: usize = sign::PUBLICKEYBYTES;
+
+    /// Returns verification key bytes representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::ed25519::*;
+    /// let (ver_key, _sign_key) = new_key_pair(None).unwrap();
+    /// assert_eq!(VerKey::BYTES_REPR_SIZE, ver_key.as_bytes().len());
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key.0
+    }
+
+    /// Creates and returns verification key from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VerKey, IndyCryptoError> {
+        Ok(VerKey {
+            key: sign::PublicKey::from_slice(bytes)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of verkey: expected {}, actual {}",
+                                                                 Self::BYTES_REPR_SIZE, bytes.len())))?
+        })
+    }
+
+    /// Returns full base58 representation of verification key.
+    pub fn to_base58(&self) -> String {
+        base58::encode(self.as_bytes())
+    }
+
+    /// Creates verification key from full base58 representation.
+    pub fn from_base58(verkey: &str) -> Result<VerKey, IndyCryptoError> {
+        VerKey::from_bytes(&base58::decode(verkey)?)
+    }
+
+    /// Returns indy DID derived from verification key (base58 of first 16 bytes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::ed25519::*;
+    /// let (ver_key, _sign_key) = new_key_pair(Some(b"000000000000000000000000Trustee1")).unwrap();
+    /// assert_eq!("V4SGRU86Z58d6TV7PBUe6f", ver_key.to_did());
+    /// ```
+    pub fn to_did(&self) -> String {
+        base58::encode(&self.as_bytes()[..DID_BYTES_SIZE])
+    }
+
+    /// Returns abbreviated verkey (`~` and base58 of last 16 bytes) if DID is derived from this verkey,
+    /// otherwise full base58 verkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - Indy DID the verkey belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::ed25519::*;
+    /// let (ver_key, _sign_key) = new_key_pair(None).unwrap();
+    /// let abbreviated = ver_key.abbreviate(&ver_key.to_did()).unwrap();
+    /// assert!(abbreviated.starts_with("~"));
+    /// assert_eq!(ver_key, VerKey::from_did_and_verkey(&ver_key.to_did(), &abbreviated).unwrap());
+    /// ```
+    pub fn abbreviate(&self, did: &str) -> Result<String, IndyCryptoError> {
+        let did_bytes = base58::decode(did)?;
+
+        if did_bytes[..] == self.as_bytes()[..DID_BYTES_SIZE] {
+            Ok(format!("~{}", base58::encode(&self.as_bytes()[DID_BYTES_SIZE..])))
+        } else {
+            Ok(self.to_base58())
+        }
+    }
+
+    /// Creates verification key from full or abbreviated verkey as stored on indy ledger.
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - Indy DID the verkey belongs to.
+    /// * `verkey` - Full base58 verkey or abbreviated one starting with `~`.
+    pub fn from_did_and_verkey(did: &str, verkey: &str) -> Result<VerKey, IndyCryptoError> {
+        if verkey.starts_with('~') {
+            let mut bytes = base58::decode(did)?;
+
+            if bytes.len() != DID_BYTES_SIZE {
+                return Err(IndyCryptoError::InvalidStructure(format!("Invalid len of DID: expected {}, actual {}",
+                                                                     DID_BYTES_SIZE, bytes.len())));
+            }
+
+            bytes.extend(base58::decode(&verkey[1..])?);
+            VerKey::from_bytes(&bytes)
+        } else {
+            VerKey::from_base58(verkey)
+        }
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for VerKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("VerKey", &self.to_base58())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for VerKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct VerKeyVisitor;
+
+        impl<'a> Visitor<'a> for VerKeyVisitor {
+            type Value = VerKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected VerKey")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<VerKey, E>
+                where E: DError
+            {
+                Ok(VerKey::from_base58(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(VerKeyVisitor)
+    }
+}
+
+/// Ed25519 detached signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    sig: sign::Signature
+}
+
+impl Signature {
+    pub const BYTES_REPR_SIZE: usize = sign::SIGNATUREBYTES;
+
+    /// Returns signature bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.sig.0
+    }
+
+    /// Creates and returns signature from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, IndyCryptoError> {
+        Ok(Signature {
+            sig: sign::Signature::from_slice(bytes)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of signature: expected {}, actual {}",
+                                                                 Self::BYTES_REPR_SIZE, bytes.len())))?
+        })
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("Signature", &base58::encode(self.as_bytes()))
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct SignatureVisitor;
+
+        impl<'a> Visitor<'a> for SignatureVisitor {
+            type Value = Signature;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected Signature")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Signature, E>
+                where E: DError
+            {
+                Ok(base58::decode(value).and_then(|bytes| Signature::from_bytes(&bytes)).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(SignatureVisitor)
+    }
+}
+
+/// Creates and returns key pair. The same seed always gives the same key pair.
+///
+/// # Arguments
+///
+/// * `seed` - Optional 32 bytes seed, random key pair is created if not set.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::ed25519::new_key_pair;
+/// new_key_pair(None).unwrap();
+/// ```
+pub fn new_key_pair(seed: Option<&[u8]>) -> Result<(VerKey, SignKey), IndyCryptoError> {
+    _init();
+
+    let (pk, sk) = match seed {
+        Some(seed) => {
+            let seed = sign::Seed::from_slice(seed)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of seed: expected {}, actual {}",
+                                                                 SignKey::SEED_SIZE, seed.len())))?;
+            sign::keypair_from_seed(&seed)
+        }
+        None => sign::gen_keypair()
+    };
+
+    Ok((VerKey { key: pk }, SignKey { key: sk }))
+}
+
+/// Signs message with sign key.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::ed25519::*;
+/// let (ver_key, sign_key) = new_key_pair(None).unwrap();
+/// let signature = sign(&[1, 2, 3], &sign_key).unwrap();
+/// assert!(verify(&signature, &[1, 2, 3], &ver_key).unwrap());
+/// ```
+pub fn sign(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
+    _init();
+    Ok(Signature { sig: sign::sign_detached(message, &sign_key.key) })
+}
+
+/// Verifies message signature with verification key.
+pub fn verify(signature: &Signature, message: &[u8], ver_key: &VerKey) -> Result<bool, IndyCryptoError> {
+    _init();
+    Ok(sign::verify_detached(&signature.sig, message, &ver_key.key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_key_pair_works_for_seed() {
+        let (ver_key1, sign_key1) = new_key_pair(Some(&[1; 32])).unwrap();
+        let (ver_key2, sign_key2) = new_key_pair(Some(&[1; 32])).unwrap();
+
+        assert_eq!(ver_key1, ver_key2);
+        assert_eq!(sign_key1, sign_key2);
+    }
+
+    #[test]
+    fn new_key_pair_works_for_invalid_seed() {
+        assert!(new_key_pair(Some(&[1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn new_key_pair_works_for_indy_trustee_seed() {
+        let (ver_key, _) = new_key_pair(Some(b"000000000000000000000000Trustee1")).unwrap();
+
+        assert_eq!("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL", ver_key.to_base58());
+        assert_eq!("V4SGRU86Z58d6TV7PBUe6f", ver_key.to_did());
+        assert_eq!("~CoRER63DVYnWZtK8uAzNbx", ver_key.abbreviate("V4SGRU86Z58d6TV7PBUe6f").unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_message() {
+        let (ver_key, sign_key) = new_key_pair(None).unwrap();
+        let signature = sign(&[1, 2, 3], &sign_key).unwrap();
+        assert!(!verify(&signature, &[1, 2, 4], &ver_key).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_key() {
+        let (_, sign_key) = new_key_pair(None).unwrap();
+        let (other_ver_key, _) = new_key_pair(None).unwrap();
+        let signature = sign(&[1, 2, 3], &sign_key).unwrap();
+        assert!(!verify(&signature, &[1, 2, 3], &other_ver_key).unwrap());
+    }
+
+    #[test]
+    fn abbreviate_works_for_other_did() {
+        let (ver_key, _) = new_key_pair(None).unwrap();
+        let (other_ver_key, _) = new_key_pair(None).unwrap();
+
+        let verkey = ver_key.abbreviate(&other_ver_key.to_did()).unwrap();
+        assert_eq!(ver_key.to_base58(), verkey);
+        assert_eq!(ver_key, VerKey::from_did_and_verkey(&other_ver_key.to_did(), &verkey).unwrap());
+    }
+
+    #[test]
+    fn from_did_and_verkey_works_for_abbreviated_verkey_and_other_did() {
+        let (ver_key, _) = new_key_pair(None).unwrap();
+        let (other_ver_key, _) = new_key_pair(None).unwrap();
+
+        let abbreviated = ver_key.abbreviate(&ver_key.to_did()).unwrap();
+        assert_ne!(ver_key, VerKey::from_did_and_verkey(&other_ver_key.to_did(), &abbreviated).unwrap());
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn serialization_works() {
+        extern crate serde_json;
+
+        let (ver_key, sign_key) = new_key_pair(None).unwrap();
+        let signature = sign(&[1, 2, 3], &sign_key).unwrap();
+
+        let ver_key_json = serde_json::to_string(&ver_key).unwrap();
+        assert_eq!(format!("\"{}\"", ver_key.to_base58()), ver_key_json);
+        assert_eq!(ver_key, serde_json::from_str::<VerKey>(&ver_key_json).unwrap());
+
+        let signature_json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(signature, serde_json::from_str::<Signature>(&signature_json).unwrap());
+    }
+}
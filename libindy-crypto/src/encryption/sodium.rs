@@ -0,0 +1,497 @@
+use errors::IndyCryptoError;
+#[cfg(feature = "sign_sodium")]
+use ed25519::{SignKey, VerKey};
+
+#[cfg(feature = "sign_sodium")]
+use libc::c_int;
+
+use sodiumoxide;
+use sodiumoxide::crypto::box_;
+use sodiumoxide::crypto::sealedbox;
+use sodiumoxide::crypto::secretbox;
+
+use std::sync::{Once, ONCE_INIT};
+
+static SODIUM_INIT: Once = ONCE_INIT;
+
+#[cfg(feature = "sign_sodium")]
+extern {
+    fn crypto_sign_ed25519_pk_to_curve25519(curve25519_pk: *mut u8, ed25519_pk: *const u8) -> c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(curve25519_sk: *mut u8, ed25519_sk: *const u8) -> c_int;
+}
+
+fn _init() {
+    SODIUM_INIT.call_once(|| { sodiumoxide::init(); });
+}
+
+/// X25519 public key used as encryption target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKey {
+    key: box_::PublicKey
+}
+
+impl PublicKey {
+    pub const BYTES_REPR_SIZE: usize = box_::PUBLICKEYBYTES;
+
+    /// Returns public key bytes representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let (pk, _sk) = new_key_pair().unwrap();
+    /// assert_eq!(PublicKey::BYTES_REPR_SIZE, pk.as_bytes().len());
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key.0
+    }
+
+    /// Creates and returns public key from bytes representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let (pk, _sk) = new_key_pair().unwrap();
+    /// PublicKey::from_bytes(pk.as_bytes()).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, IndyCryptoError> {
+        Ok(PublicKey {
+            key: box_::PublicKey::from_slice(bytes)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of public key: expected {}, actual {}",
+                                                                 Self::BYTES_REPR_SIZE, bytes.len())))?
+        })
+    }
+
+    /// Converts ed25519 verification key to encryption public key,
+    /// so messages can be encrypted for the owner of verkey (for example indy DID).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::ed25519;
+    /// use indy_crypto::encryption::PublicKey;
+    /// let (ver_key, _sign_key) = ed25519::new_key_pair(None).unwrap();
+    /// PublicKey::from_ver_key(&ver_key).unwrap();
+    /// ```
+    #[cfg(feature = "sign_sodium")]
+    pub fn from_ver_key(ver_key: &VerKey) -> Result<PublicKey, IndyCryptoError> {
+        _init();
+        let mut bytes = [0u8; box_::PUBLICKEYBYTES];
+
+        if unsafe { crypto_sign_ed25519_pk_to_curve25519(bytes.as_mut_ptr(), ver_key.as_bytes().as_ptr()) } != 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unable to convert verkey to public key")));
+        }
+
+        PublicKey::from_bytes(&bytes)
+    }
+}
+
+/// X25519 secret key that corresponds to encryption public key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretKey {
+    key: box_::SecretKey
+}
+
+impl SecretKey {
+    pub const BYTES_REPR_SIZE: usize = box_::SECRETKEYBYTES;
+
+    /// Returns secret key bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key.0
+    }
+
+    /// Creates and returns secret key from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SecretKey, IndyCryptoError> {
+        Ok(SecretKey {
+            key: box_::SecretKey::from_slice(bytes)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of secret key: expected {}, actual {}",
+                                                                 Self::BYTES_REPR_SIZE, bytes.len())))?
+        })
+    }
+
+    /// Converts ed25519 sign key to encryption secret key.
+    #[cfg(feature = "sign_sodium")]
+    pub fn from_sign_key(sign_key: &SignKey) -> Result<SecretKey, IndyCryptoError> {
+        _init();
+        let mut bytes = [0u8; box_::SECRETKEYBYTES];
+
+        if unsafe { crypto_sign_ed25519_sk_to_curve25519(bytes.as_mut_ptr(), sign_key.as_bytes().as_ptr()) } != 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unable to convert sign key to secret key")));
+        }
+
+        SecretKey::from_bytes(&bytes)
+    }
+}
+
+/// Creates and returns random encryption key pair.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::encryption::new_key_pair;
+/// new_key_pair().unwrap();
+/// ```
+pub fn new_key_pair() -> Result<(PublicKey, SecretKey), IndyCryptoError> {
+    _init();
+    let (pk, sk) = box_::gen_keypair();
+    Ok((PublicKey { key: pk }, SecretKey { key: sk }))
+}
+
+/// Anonymous (sender-less) authenticated encryption.
+/// Ciphertext contains ephemeral public key, so recipient doesn't learn anything about sender.
+pub struct SealedBox {}
+
+impl SealedBox {
+    /// Encrypts message for recipient public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to encrypt
+    /// * `pk` - Recipient public key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let (pk, _sk) = new_key_pair().unwrap();
+    /// SealedBox::seal(&[1, 2, 3], &pk).unwrap();
+    /// ```
+    pub fn seal(message: &[u8], pk: &PublicKey) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+        Ok(sealedbox::seal(message, &pk.key))
+    }
+
+    /// Decrypts message sealed for recipient key pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - Sealed message
+    /// * `pk` - Recipient public key
+    /// * `sk` - Recipient secret key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let (pk, sk) = new_key_pair().unwrap();
+    /// let ciphertext = SealedBox::seal(&[1, 2, 3], &pk).unwrap();
+    /// let message = SealedBox::open(&ciphertext, &pk, &sk).unwrap();
+    /// assert_eq!(vec![1, 2, 3], message);
+    /// ```
+    pub fn open(ciphertext: &[u8], pk: &PublicKey, sk: &SecretKey) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+        sealedbox::open(ciphertext, &pk.key, &sk.key)
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Unable to open sealed box")))
+    }
+}
+
+/// Authenticated encryption between two key pairs (NaCl `crypto_box`: X25519, XSalsa20-Poly1305).
+/// Recipient learns sender public key and can check that message was created by sender.
+pub struct CryptoBox {}
+
+impl CryptoBox {
+    pub const NONCE_SIZE: usize = box_::NONCEBYTES;
+
+    /// Encrypts message from sender to recipient. Random nonce is prepended to ciphertext.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to encrypt
+    /// * `recipient_pk` - Recipient public key
+    /// * `sender_sk` - Sender secret key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let (sender_pk, sender_sk) = new_key_pair().unwrap();
+    /// let (recipient_pk, recipient_sk) = new_key_pair().unwrap();
+    /// let ciphertext = CryptoBox::seal(&[1, 2, 3], &recipient_pk, &sender_sk).unwrap();
+    /// let message = CryptoBox::open(&ciphertext, &sender_pk, &recipient_sk).unwrap();
+    /// assert_eq!(vec![1, 2, 3], message);
+    /// ```
+    pub fn seal(message: &[u8], recipient_pk: &PublicKey, sender_sk: &SecretKey) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+        let nonce = box_::gen_nonce();
+
+        let mut ciphertext = nonce.0.to_vec();
+        ciphertext.extend(box_::seal(message, &nonce, &recipient_pk.key, &sender_sk.key));
+        Ok(ciphertext)
+    }
+
+    /// Decrypts message encrypted by `CryptoBox::seal` and checks it was created by sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - Nonce and encrypted message
+    /// * `sender_pk` - Sender public key
+    /// * `recipient_sk` - Recipient secret key
+    pub fn open(ciphertext: &[u8], sender_pk: &PublicKey, recipient_sk: &SecretKey) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+
+        if ciphertext.len() < Self::NONCE_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(format!("Ciphertext is too short")));
+        }
+
+        let nonce = box_::Nonce::from_slice(&ciphertext[..Self::NONCE_SIZE])
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid nonce")))?;
+
+        box_::open(&ciphertext[Self::NONCE_SIZE..], &nonce, &sender_pk.key, &recipient_sk.key)
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Unable to open crypto box")))
+    }
+}
+
+/// Authenticated encryption with symmetric key (NaCl `crypto_secretbox`: XSalsa20-Poly1305).
+pub struct SecretBox {}
+
+impl SecretBox {
+    pub const KEY_SIZE: usize = secretbox::KEYBYTES;
+    pub const NONCE_SIZE: usize = secretbox::NONCEBYTES;
+
+    /// Encrypts message with symmetric key. Random nonce is prepended to ciphertext.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to encrypt
+    /// * `key` - Symmetric key of `KEY_SIZE` bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::encryption::*;
+    /// let key = [7u8; SecretBox::KEY_SIZE];
+    /// let ciphertext = SecretBox::seal(&[1, 2, 3], &key).unwrap();
+    /// assert_eq!(vec![1, 2, 3], SecretBox::open(&ciphertext, &key).unwrap());
+    /// ```
+    pub fn seal(message: &[u8], key: &[u8]) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+        let key = SecretBox::_key(key)?;
+        let nonce = secretbox::gen_nonce();
+
+        let mut ciphertext = nonce.0.to_vec();
+        ciphertext.extend(secretbox::seal(message, &nonce, &key));
+        Ok(ciphertext)
+    }
+
+    /// Decrypts message encrypted by `SecretBox::seal` and checks its integrity.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - Nonce and encrypted message
+    /// * `key` - Symmetric key of `KEY_SIZE` bytes
+    pub fn open(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, IndyCryptoError> {
+        _init();
+        let key = SecretBox::_key(key)?;
+
+        if ciphertext.len() < Self::NONCE_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(format!("Ciphertext is too short")));
+        }
+
+        let nonce = secretbox::Nonce::from_slice(&ciphertext[..Self::NONCE_SIZE])
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid nonce")))?;
+
+        secretbox::open(&ciphertext[Self::NONCE_SIZE..], &nonce, &key)
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Unable to open secret box")))
+    }
+
+    fn _key(key: &[u8]) -> Result<secretbox::Key, IndyCryptoError> {
+        secretbox::Key::from_slice(key)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid len of symmetric key: expected {}, actual {}",
+                                                             Self::KEY_SIZE, key.len())))
+    }
+}
+
+/// Anonymously encrypts message for owner of ed25519 verification key (indy `anoncrypt`).
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::ed25519;
+/// use indy_crypto::encryption::*;
+/// let (ver_key, sign_key) = ed25519::new_key_pair(None).unwrap();
+/// let ciphertext = anon_crypt(&[1, 2, 3], &ver_key).unwrap();
+/// assert_eq!(vec![1, 2, 3], anon_decrypt(&ciphertext, &ver_key, &sign_key).unwrap());
+/// ```
+#[cfg(feature = "sign_sodium")]
+pub fn anon_crypt(message: &[u8], recipient_vk: &VerKey) -> Result<Vec<u8>, IndyCryptoError> {
+    SealedBox::seal(message, &PublicKey::from_ver_key(recipient_vk)?)
+}
+
+/// Decrypts message encrypted by `anon_crypt`.
+#[cfg(feature = "sign_sodium")]
+pub fn anon_decrypt(ciphertext: &[u8], recipient_vk: &VerKey, recipient_sk: &SignKey) -> Result<Vec<u8>, IndyCryptoError> {
+    SealedBox::open(ciphertext,
+                    &PublicKey::from_ver_key(recipient_vk)?,
+                    &SecretKey::from_sign_key(recipient_sk)?)
+}
+
+/// Encrypts message from owner of sender verification key for owner of recipient verification key (indy `authcrypt`).
+/// Sender verkey is hidden from anybody except recipient.
+///
+/// # Arguments
+///
+/// * `message` - Message to encrypt
+/// * `sender_vk` - Sender verification key
+/// * `sender_sk` - Sender sign key
+/// * `recipient_vk` - Recipient verification key
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::ed25519;
+/// use indy_crypto::encryption::*;
+/// let (sender_vk, sender_sk) = ed25519::new_key_pair(None).unwrap();
+/// let (recipient_vk, recipient_sk) = ed25519::new_key_pair(None).unwrap();
+/// let ciphertext = auth_crypt(&[1, 2, 3], &sender_vk, &sender_sk, &recipient_vk).unwrap();
+/// let (vk, message) = auth_decrypt(&ciphertext, &recipient_vk, &recipient_sk).unwrap();
+/// assert_eq!(sender_vk, vk);
+/// assert_eq!(vec![1, 2, 3], message);
+/// ```
+#[cfg(feature = "sign_sodium")]
+pub fn auth_crypt(message: &[u8], sender_vk: &VerKey, sender_sk: &SignKey, recipient_vk: &VerKey) -> Result<Vec<u8>, IndyCryptoError> {
+    let recipient_pk = PublicKey::from_ver_key(recipient_vk)?;
+
+    let mut payload = sender_vk.as_bytes().to_vec();
+    payload.extend(CryptoBox::seal(message, &recipient_pk, &SecretKey::from_sign_key(sender_sk)?)?);
+
+    SealedBox::seal(&payload, &recipient_pk)
+}
+
+/// Decrypts message encrypted by `auth_crypt`, returns sender verification key and message.
+#[cfg(feature = "sign_sodium")]
+pub fn auth_decrypt(ciphertext: &[u8], recipient_vk: &VerKey, recipient_sk: &SignKey) -> Result<(VerKey, Vec<u8>), IndyCryptoError> {
+    let recipient_sk = SecretKey::from_sign_key(recipient_sk)?;
+    let payload = SealedBox::open(ciphertext, &PublicKey::from_ver_key(recipient_vk)?, &recipient_sk)?;
+
+    if payload.len() < VerKey::BYTES_REPR_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(format!("Authenticated message is too short")));
+    }
+
+    let sender_vk = VerKey::from_bytes(&payload[..VerKey::BYTES_REPR_SIZE])?;
+    let message = CryptoBox::open(&payload[VerKey::BYTES_REPR_SIZE..], &PublicKey::from_ver_key(&sender_vk)?, &recipient_sk)?;
+
+    Ok((sender_vk, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_key_pair_works() {
+        let (pk, sk) = new_key_pair().unwrap();
+        assert_eq!(PublicKey::BYTES_REPR_SIZE, pk.as_bytes().len());
+        assert_eq!(SecretKey::BYTES_REPR_SIZE, sk.as_bytes().len());
+    }
+
+    #[test]
+    fn public_key_from_bytes_works_for_invalid_len() {
+        assert!(PublicKey::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn sealed_box_works() {
+        let (pk, sk) = new_key_pair().unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let ciphertext = SealedBox::seal(&message, &pk).unwrap();
+        assert_ne!(message, ciphertext);
+
+        let decrypted = SealedBox::open(&ciphertext, &pk, &sk).unwrap();
+        assert_eq!(message, decrypted);
+    }
+
+    #[test]
+    fn sealed_box_open_works_for_other_key() {
+        let (pk, _sk) = new_key_pair().unwrap();
+        let (other_pk, other_sk) = new_key_pair().unwrap();
+
+        let ciphertext = SealedBox::seal(&[1, 2, 3], &pk).unwrap();
+        assert!(SealedBox::open(&ciphertext, &other_pk, &other_sk).is_err());
+    }
+
+    #[test]
+    fn sealed_box_open_works_for_tampered_ciphertext() {
+        let (pk, sk) = new_key_pair().unwrap();
+
+        let mut ciphertext = SealedBox::seal(&[1, 2, 3], &pk).unwrap();
+        let last = ciphertext.len() - 1;
+        ciphertext[last] ^= 1;
+
+        assert!(SealedBox::open(&ciphertext, &pk, &sk).is_err());
+    }
+
+    #[test]
+    fn crypto_box_works() {
+        let (sender_pk, sender_sk) = new_key_pair().unwrap();
+        let (recipient_pk, recipient_sk) = new_key_pair().unwrap();
+
+        let ciphertext = CryptoBox::seal(&[1, 2, 3], &recipient_pk, &sender_sk).unwrap();
+        assert_eq!(vec![1, 2, 3], CryptoBox::open(&ciphertext, &sender_pk, &recipient_sk).unwrap());
+    }
+
+    #[test]
+    fn crypto_box_open_works_for_other_sender() {
+        let (_, sender_sk) = new_key_pair().unwrap();
+        let (other_pk, _) = new_key_pair().unwrap();
+        let (recipient_pk, recipient_sk) = new_key_pair().unwrap();
+
+        let ciphertext = CryptoBox::seal(&[1, 2, 3], &recipient_pk, &sender_sk).unwrap();
+        assert!(CryptoBox::open(&ciphertext, &other_pk, &recipient_sk).is_err());
+    }
+
+    #[test]
+    fn crypto_box_open_works_for_short_ciphertext() {
+        let (pk, sk) = new_key_pair().unwrap();
+        assert!(CryptoBox::open(&[1, 2, 3], &pk, &sk).is_err());
+    }
+
+    #[test]
+    fn secret_box_open_works_for_other_key() {
+        let ciphertext = SecretBox::seal(&[1, 2, 3], &[1; SecretBox::KEY_SIZE]).unwrap();
+        assert!(SecretBox::open(&ciphertext, &[2; SecretBox::KEY_SIZE]).is_err());
+    }
+
+    #[test]
+    fn secret_box_open_works_for_tampered_ciphertext() {
+        let key = [1; SecretBox::KEY_SIZE];
+
+        let mut ciphertext = SecretBox::seal(&[1, 2, 3], &key).unwrap();
+        let last = ciphertext.len() - 1;
+        ciphertext[last] ^= 1;
+
+        assert!(SecretBox::open(&ciphertext, &key).is_err());
+    }
+
+    #[test]
+    fn secret_box_seal_works_for_invalid_key_len() {
+        assert!(SecretBox::seal(&[1, 2, 3], &[1, 2, 3]).is_err());
+    }
+
+    #[cfg(feature = "sign_sodium")]
+    #[test]
+    fn auth_crypt_works_for_other_recipient() {
+        use ed25519;
+
+        let (sender_vk, sender_sk) = ed25519::new_key_pair(None).unwrap();
+        let (recipient_vk, _) = ed25519::new_key_pair(None).unwrap();
+        let (other_vk, other_sk) = ed25519::new_key_pair(None).unwrap();
+
+        let ciphertext = auth_crypt(&[1, 2, 3], &sender_vk, &sender_sk, &recipient_vk).unwrap();
+        assert!(auth_decrypt(&ciphertext, &other_vk, &other_sk).is_err());
+    }
+
+    #[cfg(feature = "sign_sodium")]
+    #[test]
+    fn anon_crypt_works_for_tampered_ciphertext() {
+        use ed25519;
+
+        let (vk, sk) = ed25519::new_key_pair(None).unwrap();
+
+        let mut ciphertext = anon_crypt(&[1, 2, 3], &vk).unwrap();
+        ciphertext[0] ^= 1;
+
+        assert!(anon_decrypt(&ciphertext, &vk, &sk).is_err());
+    }
+}
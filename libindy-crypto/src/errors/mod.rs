@@ -9,6 +9,69 @@ pub trait ToErrorCode {
     fn to_error_code(&self) -> ErrorCode;
 }
 
+/// Cause of proof rejection by Verifier.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RejectionReason {
+    /// Count of sub proofs differs from count of sub proof requests.
+    SubProofsCountMismatch,
+    /// Revealed attributes of sub proof differ from requested.
+    RevealedAttrsMismatch,
+    /// Predicates of sub proof differ from requested.
+    PredicatesMismatch,
+    /// Linear predicate proofs differ from requested linear predicates.
+    LinearPredicatesMismatch,
+    /// Verifiable encryptions differ from requested.
+    VerifiableEncryptionsMismatch,
+    /// Pseudonyms differ from requested domains.
+    PseudonymsMismatch,
+    /// Non-ownership proofs differ from requested registries.
+    NonOwnershipProofsMismatch,
+    /// Attribute that must be hidden isn't found in equality proof of sub proof.
+    HiddenAttrNotFound { attr: String, sub_proof_idx: usize },
+    /// Non-revocation proof doesn't contain timestamp of revocation registry state.
+    NonRevocationTimestampNotFound,
+    /// Timestamp of revocation registry state is out of requested interval.
+    NonRevocationTimestampOutOfInterval(u64),
+    /// Revocation registry state with timestamp of non-revocation proof is unknown to Verifier.
+    RevocationRegistryStateNotFound(u64),
+    /// Non-revocation proof doesn't contain identifier of revocation registry.
+    RevocationRegistryIdNotFound,
+    /// Revocation registry identifier of non-revocation proof isn't among registries accepted by Verifier.
+    RevocationRegistryIdNotAllowed(String),
+    /// Sub proof doesn't contain identifier of credential key.
+    KeyIdNotFound,
+    /// Credential key identifier of sub proof isn't among keys accepted by Verifier.
+    KeyIdNotAllowed(String),
+    /// Range proof of predicate over attribute is invalid.
+    InvalidRangeProof(String),
+    /// Proof lacks values required for verification.
+    MalformedProof(String),
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RejectionReason::SubProofsCountMismatch => write!(f, "Invalid sub proofs count"),
+            RejectionReason::RevealedAttrsMismatch => write!(f, "Proof revealed attributes not correspond to requested attributes"),
+            RejectionReason::PredicatesMismatch => write!(f, "Proof predicates not correspond to requested predicates"),
+            RejectionReason::LinearPredicatesMismatch => write!(f, "Linear predicate proofs not correspond to requested linear predicates"),
+            RejectionReason::VerifiableEncryptionsMismatch => write!(f, "Verifiable encryptions not correspond to requested encryptions"),
+            RejectionReason::PseudonymsMismatch => write!(f, "Pseudonyms not correspond to requested domains"),
+            RejectionReason::NonOwnershipProofsMismatch => write!(f, "Non-ownership proofs not correspond to requested registries"),
+            RejectionReason::HiddenAttrNotFound { ref attr, sub_proof_idx } => write!(f, "Hidden attribute '{}' of sub proof {} not found in proof", attr, sub_proof_idx),
+            RejectionReason::NonRevocationTimestampNotFound => write!(f, "Non-revocation proof with timestamp not found in sub proof"),
+            RejectionReason::NonRevocationTimestampOutOfInterval(timestamp) => write!(f, "Revocation registry state timestamp {} is out of requested interval", timestamp),
+            RejectionReason::RevocationRegistryStateNotFound(timestamp) => write!(f, "Revocation registry state for timestamp {} not found", timestamp),
+            RejectionReason::RevocationRegistryIdNotFound => write!(f, "Revocation registry id not found in non-revocation proof"),
+            RejectionReason::RevocationRegistryIdNotAllowed(ref rev_reg_id) => write!(f, "Revocation registry id '{}' is not allowed", rev_reg_id),
+            RejectionReason::KeyIdNotFound => write!(f, "Credential key id not found in sub proof"),
+            RejectionReason::KeyIdNotAllowed(ref key_id) => write!(f, "Credential key id '{}' is not allowed", key_id),
+            RejectionReason::InvalidRangeProof(ref attr) => write!(f, "Range proof of predicate over attribute '{}' is invalid", attr),
+            RejectionReason::MalformedProof(ref description) => write!(f, "Malformed proof: {}", description),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum IndyCryptoError {
     InvalidParam1(String),
@@ -22,11 +85,22 @@ pub enum IndyCryptoError {
     InvalidParam9(String),
     InvalidState(String),
     InvalidStructure(String),
+    /// Decoded point isn't on the curve or doesn't belong to the expected prime order subgroup.
+    InvalidGroupElement(String),
+    /// Attribute required by operation is absent in `context` (credential values, public key, proof and etc.).
+    MissingAttribute { attr: String, context: String },
+    /// Decimal or hexadecimal number can't be parsed: `input_prefix` is the beginning of input
+    /// and `position` is the index of the first invalid character.
+    ParseError { input_prefix: String, position: usize },
+    /// Object can't be serialized to or deserialized from JSON.
+    Json(serde_json::Error),
     IOError(io::Error),
     AnoncredsRevocationAccumulatorIsFull(String),
     AnoncredsInvalidRevocationAccumulatorIndex(String),
     AnoncredsClaimRevoked(String),
-    AnoncredsProofRejected(String),
+    AnoncredsProofRejected { reason: RejectionReason },
+    AnoncredsWitnessMismatch(String),
+    AnoncredsTooManyAttributes(String),
 }
 
 impl fmt::Display for IndyCryptoError {
@@ -43,11 +117,17 @@ impl fmt::Display for IndyCryptoError {
             IndyCryptoError::InvalidParam9(ref description) => write!(f, "Invalid param 4: {}", description),
             IndyCryptoError::InvalidState(ref description) => write!(f, "Invalid library state: {}", description),
             IndyCryptoError::InvalidStructure(ref description) => write!(f, "Invalid structure: {}", description),
+            IndyCryptoError::InvalidGroupElement(ref description) => write!(f, "Invalid group element: {}", description),
+            IndyCryptoError::MissingAttribute { ref attr, ref context } => write!(f, "Attribute '{}' not found in {}", attr, context),
+            IndyCryptoError::ParseError { ref input_prefix, position } => write!(f, "Invalid number '{}' at position {}", input_prefix, position),
+            IndyCryptoError::Json(ref err) => write!(f, "Invalid structure: {}", err),
             IndyCryptoError::IOError(ref err) => err.fmt(f),
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(ref description) => write!(f, "Revocation accumulator is full: {}", description),
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(ref description) => write!(f, "Invalid revocation accumulator index: {}", description),
             IndyCryptoError::AnoncredsClaimRevoked(ref description) => write!(f, "Claim revoked: {}", description),
-            IndyCryptoError::AnoncredsProofRejected(ref description) => write!(f, "Proof rejected: {}", description),
+            IndyCryptoError::AnoncredsProofRejected { ref reason } => write!(f, "Proof rejected: {}", reason),
+            IndyCryptoError::AnoncredsWitnessMismatch(ref description) => write!(f, "Witness mismatch: {}", description),
+            IndyCryptoError::AnoncredsTooManyAttributes(ref description) => write!(f, "Too many attributes: {}", description),
         }
     }
 }
@@ -66,11 +146,17 @@ impl Error for IndyCryptoError {
             IndyCryptoError::InvalidParam9(ref description) => description,
             IndyCryptoError::InvalidState(ref description) => description,
             IndyCryptoError::InvalidStructure(ref description) => description,
+            IndyCryptoError::InvalidGroupElement(ref description) => description,
+            IndyCryptoError::MissingAttribute { .. } => "Attribute not found",
+            IndyCryptoError::ParseError { .. } => "Invalid number",
+            IndyCryptoError::Json(ref err) => err.description(),
             IndyCryptoError::IOError(ref err) => err.description(),
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(ref description) => description,
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(ref description) => description,
             IndyCryptoError::AnoncredsClaimRevoked(ref description) => description,
-            IndyCryptoError::AnoncredsProofRejected(ref description) => description,
+            IndyCryptoError::AnoncredsProofRejected { .. } => "Proof rejected",
+            IndyCryptoError::AnoncredsWitnessMismatch(ref description) => description,
+            IndyCryptoError::AnoncredsTooManyAttributes(ref description) => description,
         }
     }
 
@@ -86,12 +172,18 @@ impl Error for IndyCryptoError {
             IndyCryptoError::InvalidParam8(_) |
             IndyCryptoError::InvalidParam9(_) |
             IndyCryptoError::InvalidState(_) |
-            IndyCryptoError::InvalidStructure(_) => None,
+            IndyCryptoError::InvalidStructure(_) |
+            IndyCryptoError::InvalidGroupElement(_) |
+            IndyCryptoError::MissingAttribute { .. } |
+            IndyCryptoError::ParseError { .. } => None,
+            IndyCryptoError::Json(ref err) => Some(err),
             IndyCryptoError::IOError(ref err) => Some(err),
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(_) => None,
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(_) => None,
             IndyCryptoError::AnoncredsClaimRevoked(_) => None,
-            IndyCryptoError::AnoncredsProofRejected(_) => None,
+            IndyCryptoError::AnoncredsProofRejected { .. } => None,
+            IndyCryptoError::AnoncredsWitnessMismatch(_) => None,
+            IndyCryptoError::AnoncredsTooManyAttributes(_) => None,
         }
     }
 }
@@ -110,17 +202,61 @@ impl ToErrorCode for IndyCryptoError {
             IndyCryptoError::InvalidParam9(_) => ErrorCode::CommonInvalidParam9,
             IndyCryptoError::InvalidState(_) => ErrorCode::CommonInvalidState,
             IndyCryptoError::InvalidStructure(_) => ErrorCode::CommonInvalidStructure,
+            IndyCryptoError::InvalidGroupElement(_) => ErrorCode::CommonInvalidGroupElement,
+            IndyCryptoError::MissingAttribute { .. } => ErrorCode::CommonInvalidStructure,
+            IndyCryptoError::ParseError { .. } => ErrorCode::CommonInvalidStructure,
+            IndyCryptoError::Json(_) => ErrorCode::CommonInvalidStructure,
             IndyCryptoError::IOError(_) => ErrorCode::CommonIOError,
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(_) => ErrorCode::AnoncredsRevocationAccumulatorIsFull,
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(_) => ErrorCode::AnoncredsInvalidRevocationAccumulatorIndex,
             IndyCryptoError::AnoncredsClaimRevoked(_) => ErrorCode::AnoncredsClaimRevoked,
-            IndyCryptoError::AnoncredsProofRejected(_) => ErrorCode::AnoncredsProofRejected,
+            IndyCryptoError::AnoncredsProofRejected { .. } => ErrorCode::AnoncredsProofRejected,
+            IndyCryptoError::AnoncredsWitnessMismatch(_) => ErrorCode::AnoncredsWitnessMismatch,
+            IndyCryptoError::AnoncredsTooManyAttributes(_) => ErrorCode::AnoncredsTooManyAttributes,
         }
     }
 }
 
 impl From<serde_json::Error> for IndyCryptoError {
     fn from(err: serde_json::Error) -> IndyCryptoError {
-        IndyCryptoError::InvalidStructure(err.description().to_string())
+        IndyCryptoError::Json(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_error_code_works_for_structured_errors() {
+        let err = IndyCryptoError::MissingAttribute { attr: "age".to_string(), context: "credential values".to_string() };
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+        assert_eq!("Attribute 'age' not found in credential values", err.to_string());
+
+        let err = IndyCryptoError::ParseError { input_prefix: "12a".to_string(), position: 2 };
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+        assert_eq!("Invalid number '12a' at position 2", err.to_string());
+
+        let err = IndyCryptoError::AnoncredsProofRejected { reason: RejectionReason::RevocationRegistryStateNotFound(10) };
+        assert_eq!(ErrorCode::AnoncredsProofRejected, err.to_error_code());
+        assert_eq!("Proof rejected: Revocation registry state for timestamp 10 not found", err.to_string());
+    }
+
+    #[test]
+    fn json_error_works() {
+        let err: IndyCryptoError = serde_json::from_str::<u32>("{").unwrap_err().into();
+
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+        assert!(err.cause().is_some());
+    }
+
+    #[test]
+    fn error_codes_are_stable() {
+        assert_eq!(113, ErrorCode::CommonInvalidStructure as usize);
+        assert_eq!(114, ErrorCode::CommonIOError as usize);
+        assert_eq!(117, ErrorCode::AnoncredsClaimRevoked as usize);
+        assert_eq!(118, ErrorCode::AnoncredsProofRejected as usize);
+        assert_eq!(121, ErrorCode::CommonPanic as usize);
+        assert_eq!(122, ErrorCode::CommonInvalidGroupElement as usize);
     }
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
 use bls::*;
 
 use ffi::ErrorCode;
+use ffi::handles::{Handle, Handles};
 use errors::ToErrorCode;
-use std::os::raw::c_void;
 use std::slice;
 
 /// Creates and returns random generator point that satisfy BLS algorithm requirements.
@@ -13,27 +13,29 @@ use std::slice;
 /// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
 ///
 /// # Arguments
-/// * `gen_p` - Reference that will contain generator instance pointer
+/// * `gen_p` - Reference that will contain generator instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_generator_new(gen_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_new: >>> gen_p: {:?}", gen_p);
-
-    check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Generator::new() {
-        Ok(gen) => {
-            trace!("indy_crypto_bls_generator_new: gen: {:?}", gen);
-            unsafe {
-                *gen_p = Box::into_raw(Box::new(gen)) as *const c_void;
-                trace!("indy_crypto_bls_generator_new: *gen_p: {:?}", *gen_p);
+pub extern fn indy_crypto_bls_generator_new(gen_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_generator_new: >>> gen_p: {:?}", gen_p);
+
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Generator::new() {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_new: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = Handles::insert(gen);
+                    trace!("indy_crypto_bls_generator_new: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_generator_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_new: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns generator point from bytes representation.
@@ -43,32 +45,34 @@ pub extern fn indy_crypto_bls_generator_new(gen_p: *mut *const c_void) -> ErrorC
 /// # Arguments
 /// * `bytes` - Bytes buffer pointer
 /// * `bytes_len` - Bytes buffer len
-/// * `gen_p` - Reference that will contain generator instance pointer
+/// * `gen_p` - Reference that will contain generator instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_generator_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                   gen_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, gen_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_generator_from_bytes: bytes: {:?}", bytes);
+                                                   gen_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_generator_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, gen_p);
 
-    let res = match Generator::from_bytes(bytes) {
-        Ok(gen) => {
-            trace!("indy_crypto_bls_generator_from_bytes: gen: {:?}", gen);
-            unsafe {
-                *gen_p = Box::into_raw(Box::new(gen)) as *const c_void;
-                trace!("indy_crypto_bls_generator_from_bytes: *gen_p: {:?}", *gen_p);
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_generator_from_bytes: bytes: {:?}", bytes);
+
+        let res = match Generator::from_bytes(bytes) {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_from_bytes: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = Handles::insert(gen);
+                    trace!("indy_crypto_bls_generator_from_bytes: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_generator_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns bytes representation of generator point.
@@ -76,46 +80,48 @@ pub extern fn indy_crypto_bls_generator_from_bytes(bytes: *const u8, bytes_len:
 /// Note: Returned buffer lifetime is the same as generator instance.
 ///
 /// # Arguments
-/// * `gen` - Generator instance pointer
+/// * `gen` - Generator instance handle
 /// * `bytes_p` - Pointer that will contains bytes buffer
 /// * `bytes_len_p` - Pointer that will contains bytes buffer len
 #[no_mangle]
-pub extern fn indy_crypto_bls_generator_as_bytes(gen: *const c_void,
+pub extern fn indy_crypto_bls_generator_as_bytes(gen: Handle,
                                                  bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", gen, bytes_p, bytes_len_p);
+    catch_panic!({
+        trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", gen, bytes_p, bytes_len_p);
 
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
 
-    trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}", gen);
+        trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}", gen);
 
-    unsafe {
-        *bytes_p = gen.as_bytes().as_ptr();
-        *bytes_len_p = gen.as_bytes().len();
-    };
+        unsafe {
+            *bytes_p = gen.as_bytes().as_ptr();
+            *bytes_len_p = gen.as_bytes().len();
+        };
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_generator_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_as_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates generator instance.
 ///
 /// # Arguments
-/// * `gen` - Generator instance pointer
+/// * `gen` - Generator instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_generator_free(gen: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_free: >>> gen: {:?}", gen);
-
-    check_useful_c_ptr!(gen, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_bls_generator_free(gen: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_generator_free: >>> gen: {:?}", gen);
 
-    unsafe { Box::from_raw(gen as *mut Generator); }
-    let res = ErrorCode::Success;
+        check_useful_removed_handle!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_generator_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns random (or seeded from seed) BLS sign key algorithm requirements.
@@ -125,32 +131,34 @@ pub extern fn indy_crypto_bls_generator_free(gen: *const c_void) -> ErrorCode {
 /// # Arguments
 /// * `seed` - Seed buffer pointer. For random generation null must be passed.
 /// * `seed` - Seed buffer len.
-/// * `gen_p` - Reference that will contain sign key instance pointer
+/// * `gen_p` - Reference that will contain sign key instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_sign_key_new(seed: *const u8,
                                            seed_len: usize,
-                                           sign_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_new: >>> seed: {:?}, seed_len: {:?}, sign_key_p: {:?}", seed, seed_len, sign_key_p);
-
-    check_useful_opt_c_byte_array!(seed, seed_len,
-                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_bls_sign_key_new: seed: {:?}", seed);
-
-    let res = match SignKey::new(seed) {
-        Ok(sign_key) => {
-            trace!("indy_crypto_bls_generator_new: gen: {:?}", sign_key);
-            unsafe {
-                *sign_key_p = Box::into_raw(Box::new(sign_key)) as *const c_void;
-                trace!("indy_crypto_bls_sign_key_new: *sign_key_p: {:?}", *sign_key_p);
+                                           sign_key_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_sign_key_new: >>> seed: {:?}, seed_len: {:?}, sign_key_p: {:?}", seed, seed_len, sign_key_p);
+
+        check_useful_opt_c_byte_array!(seed, seed_len,
+                                       ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_sign_key_new: seed: {:?}", seed);
+
+        let res = match SignKey::new(seed) {
+            Ok(sign_key) => {
+                trace!("indy_crypto_bls_generator_new: gen: {:?}", sign_key);
+                unsafe {
+                    *sign_key_p = Handles::insert(sign_key);
+                    trace!("indy_crypto_bls_sign_key_new: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns sign key from bytes representation.
@@ -160,32 +168,34 @@ pub extern fn indy_crypto_bls_sign_key_new(seed: *const u8,
 /// # Arguments
 /// * `bytes` - Bytes buffer pointer
 /// * `bytes_len` - Bytes buffer len
-/// * `sign_key_p` - Reference that will contain sign key instance pointer
+/// * `sign_key_p` - Reference that will contain sign key instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_sign_key_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                  sign_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, sign_key_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_sign_key_from_bytes: bytes: {:?}", bytes);
+                                                  sign_key_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_sign_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, sign_key_p);
 
-    let res = match SignKey::from_bytes(bytes) {
-        Ok(sign_key) => {
-            trace!("indy_crypto_bls_sign_key_from_bytes: sign_key: {:?}", sign_key);
-            unsafe {
-                *sign_key_p = Box::into_raw(Box::new(sign_key)) as *const c_void;
-                trace!("indy_crypto_bls_sign_key_from_bytes: *sign_key_p: {:?}", *sign_key_p);
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_sign_key_from_bytes: bytes: {:?}", bytes);
+
+        let res = match SignKey::from_bytes(bytes) {
+            Ok(sign_key) => {
+                trace!("indy_crypto_bls_sign_key_from_bytes: sign_key: {:?}", sign_key);
+                unsafe {
+                    *sign_key_p = Handles::insert(sign_key);
+                    trace!("indy_crypto_bls_sign_key_from_bytes: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_sign_key_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns bytes representation of sign key.
@@ -193,46 +203,49 @@ pub extern fn indy_crypto_bls_sign_key_from_bytes(bytes: *const u8, bytes_len: u
 /// Note: Returned buffer lifetime is the same as sign key instance.
 ///
 /// # Arguments
-/// * `sign_key` - Sign key instance pointer
+/// * `sign_key` - Sign key instance handle
 /// * `bytes_p` - Pointer that will contains bytes buffer
 /// * `bytes_len_p` - Pointer that will contains bytes buffer len
 #[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_as_bytes(sign_key: *const c_void,
+pub extern fn indy_crypto_bls_sign_key_as_bytes(sign_key: Handle,
                                                 bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_as_bytes: >>> sign_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", sign_key, bytes_p, bytes_len_p);
+    catch_panic!({
+        trace!("indy_crypto_bls_sign_key_as_bytes: >>> sign_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", sign_key, bytes_p, bytes_len_p);
 
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
 
-    trace!("indy_crypto_bls_sign_key_as_bytes: sign_key: {:?}", sign_key);
+        trace!("indy_crypto_bls_sign_key_as_bytes: sign_key: {:?}", sign_key);
 
-    unsafe {
-        *bytes_p = sign_key.as_bytes().as_ptr();
-        *bytes_len_p = sign_key.as_bytes().len();
-    };
+        unsafe {
+            *bytes_p = sign_key.as_bytes().as_ptr();
+            *bytes_len_p = sign_key.as_bytes().len();
+        };
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_sign_key_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_as_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates sign key instance.
 ///
 /// # Arguments
-/// * `sign_key` - Sign key instance pointer
+/// * `sign_key` - Sign key instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_free(sign_key: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(sign_key, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_bls_sign_key_free(sign_key: Handle) -> ErrorCode {
+    catch_panic!({
 
-    trace!("indy_crypto_bls_sign_key_free: >>> sign_key: {:?}", sign_key);
+        trace!("indy_crypto_bls_sign_key_free: >>> sign_key: {:?}", sign_key);
 
-    unsafe { Box::from_raw(sign_key as *mut SignKey); }
-    let res = ErrorCode::Success;
+        check_useful_removed_handle!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_sign_key_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns BLS ver key that corresponds to sign key.
@@ -242,32 +255,34 @@ pub extern fn indy_crypto_bls_sign_key_free(sign_key: *const c_void) -> ErrorCod
 /// # Arguments
 /// * `gen` - Generator point instance
 /// * `sign_key` - Sign key instance
-/// * `ver_key_p` - Reference that will contain verification key instance pointer
+/// * `ver_key_p` - Reference that will contain verification key instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_new(gen: *const c_void,
-                                          sign_key: *const c_void,
-                                          ver_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_ver_key_new: >>> gen: {:?}, sign_key: {:?}, ver_key_p: {:?}", gen, sign_key, ver_key_p);
-
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_bls_ver_key_new: gen: {:?}, sign_key: {:?}", gen, sign_key);
-
-    let res = match VerKey::new(gen, sign_key) {
-        Ok(ver_key) => {
-            trace!("indy_crypto_bls_ver_key_new: ver_key: {:?}", ver_key);
-            unsafe {
-                *ver_key_p = Box::into_raw(Box::new(ver_key)) as *const c_void;
-                trace!("indy_crypto_bls_ver_key_new: *ver_key_p: {:?}", *ver_key_p);
+pub extern fn indy_crypto_bls_ver_key_new(gen: Handle,
+                                          sign_key: Handle,
+                                          ver_key_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_ver_key_new: >>> gen: {:?}, sign_key: {:?}, ver_key_p: {:?}", gen, sign_key, ver_key_p);
+
+        check_useful_handle!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_ver_key_new: gen: {:?}, sign_key: {:?}", gen, sign_key);
+
+        let res = match VerKey::new(gen, sign_key) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_new: ver_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = Handles::insert(ver_key);
+                    trace!("indy_crypto_bls_ver_key_new: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns verification key from bytes representation.
@@ -277,32 +292,34 @@ pub extern fn indy_crypto_bls_ver_key_new(gen: *const c_void,
 /// # Arguments
 /// * `bytes` - Bytes buffer pointer
 /// * `bytes_len` - Bytes buffer len
-/// * `ver_key_p` - Reference that will contain verification key instance pointer
+/// * `ver_key_p` - Reference that will contain verification key instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_ver_key_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                 ver_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_ver_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, ver_key_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_ver_key_from_bytes: bytes: {:?}", bytes);
+                                                 ver_key_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_ver_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, ver_key_p);
 
-    let res = match VerKey::from_bytes(bytes) {
-        Ok(ver_key) => {
-            trace!("indy_crypto_bls_ver_key_from_bytes: sign_key: {:?}", ver_key);
-            unsafe {
-                *ver_key_p = Box::into_raw(Box::new(ver_key)) as *const c_void;
-                trace!("indy_crypto_bls_ver_key_from_bytes: *ver_key_p: {:?}", *ver_key_p);
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_ver_key_from_bytes: bytes: {:?}", bytes);
+
+        let res = match VerKey::from_bytes(bytes) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_from_bytes: sign_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = Handles::insert(ver_key);
+                    trace!("indy_crypto_bls_ver_key_from_bytes: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_ver_key_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns bytes representation of verification key.
@@ -310,46 +327,49 @@ pub extern fn indy_crypto_bls_ver_key_from_bytes(bytes: *const u8, bytes_len: us
 /// Note: Returned buffer lifetime is the same as verification key instance.
 ///
 /// # Arguments
-/// * `ver_key` - Verification key instance pointer
+/// * `ver_key` - Verification key instance handle
 /// * `bytes_p` - Pointer that will contains bytes buffer
 /// * `bytes_len_p` - Pointer that will contains bytes buffer len
 #[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_as_bytes(ver_key: *const c_void,
+pub extern fn indy_crypto_bls_ver_key_as_bytes(ver_key: Handle,
                                                bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_as_bytes: >>> ver_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", ver_key, bytes_p, bytes_len_p);
+    catch_panic!({
+        trace!("indy_crypto_bls_sign_key_as_bytes: >>> ver_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", ver_key, bytes_p, bytes_len_p);
 
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
 
-    trace!("indy_crypto_bls_ver_key_as_bytes: ver_key: {:?}", ver_key);
+        trace!("indy_crypto_bls_ver_key_as_bytes: ver_key: {:?}", ver_key);
 
-    unsafe {
-        *bytes_p = ver_key.as_bytes().as_ptr();
-        *bytes_len_p = ver_key.as_bytes().len();
-    };
+        unsafe {
+            *bytes_p = ver_key.as_bytes().as_ptr();
+            *bytes_len_p = ver_key.as_bytes().len();
+        };
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_ver_key_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_as_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates verification key instance.
 ///
 /// # Arguments
-/// * `ver_key` - Verification key instance pointer
+/// * `ver_key` - Verification key instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_free(ver_key: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(ver_key, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_bls_ver_key_free(ver_key: Handle) -> ErrorCode {
+    catch_panic!({
 
-    trace!("indy_crypto_bls_ver_key_free: >>> ver_key: {:?}", ver_key);
+        trace!("indy_crypto_bls_ver_key_free: >>> ver_key: {:?}", ver_key);
 
-    unsafe { Box::from_raw(ver_key as *mut VerKey); }
-    let res = ErrorCode::Success;
+        check_useful_removed_handle!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_ver_key_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns signature from bytes representation.
@@ -359,32 +379,34 @@ pub extern fn indy_crypto_bls_ver_key_free(ver_key: *const c_void) -> ErrorCode
 /// # Arguments
 /// * `bytes` - Bytes buffer pointer
 /// * `bytes_len` - Bytes buffer len
-/// * `signature_p` - Reference that will contain signature instance pointer
+/// * `signature_p` - Reference that will contain signature instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_signature_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                   signature_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_p: {:?}", bytes, bytes_len, signature_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam1);
+                                                   signature_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_p: {:?}", bytes, bytes_len, signature_p);
 
-    trace!("indy_crypto_bls_signature_from_bytes: bytes: {:?}", bytes);
-
-    let res = match Signature::from_bytes(bytes) {
-        Ok(signature) => {
-            trace!("indy_crypto_bls_signature_from_bytes: signature: {:?}", signature);
-            unsafe {
-                *signature_p = Box::into_raw(Box::new(signature)) as *const c_void;
-                trace!("indy_crypto_bls_signature_from_bytes: *signature_p: {:?}", *signature_p);
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_signature_from_bytes: bytes: {:?}", bytes);
+
+        let res = match Signature::from_bytes(bytes) {
+            Ok(signature) => {
+                trace!("indy_crypto_bls_signature_from_bytes: signature: {:?}", signature);
+                unsafe {
+                    *signature_p = Handles::insert(signature);
+                    trace!("indy_crypto_bls_signature_from_bytes: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_signature_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_signature_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns bytes representation of signature.
@@ -392,46 +414,49 @@ pub extern fn indy_crypto_bls_signature_from_bytes(bytes: *const u8, bytes_len:
 /// Note: Returned buffer lifetime is the same as signature instance.
 ///
 /// # Arguments
-/// * `signature` - Signature instance pointer
+/// * `signature` - Signature instance handle
 /// * `bytes_p` - Pointer that will contains bytes buffer
 /// * `bytes_len_p` - Pointer that will contains bytes buffer len
 #[no_mangle]
-pub extern fn indy_crypto_bls_signature_as_bytes(signature: *const c_void,
+pub extern fn indy_crypto_bls_signature_as_bytes(signature: Handle,
                                                  bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_signature_as_bytes: >>> signature: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", signature, bytes_p, bytes_len_p);
+    catch_panic!({
+        trace!("indy_crypto_bls_signature_as_bytes: >>> signature: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", signature, bytes_p, bytes_len_p);
 
-    check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
 
-    trace!("indy_crypto_bls_signature_as_bytes: signature: {:?}", signature);
+        trace!("indy_crypto_bls_signature_as_bytes: signature: {:?}", signature);
 
-    unsafe {
-        *bytes_p = signature.as_bytes().as_ptr();
-        *bytes_len_p = signature.as_bytes().len();
-    };
+        unsafe {
+            *bytes_p = signature.as_bytes().as_ptr();
+            *bytes_len_p = signature.as_bytes().len();
+        };
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_signature_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_signature_as_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates signature instance.
 ///
 /// # Arguments
-/// * `signature` - Signature instance pointer
+/// * `signature` - Signature instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_signature_free(signature: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(signature, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_bls_signature_free(signature: Handle) -> ErrorCode {
+    catch_panic!({
 
-    trace!("indy_crypto_bls_signature_free: >>> signature: {:?}", signature);
+        trace!("indy_crypto_bls_signature_free: >>> signature: {:?}", signature);
 
-    unsafe { Box::from_raw(signature as *mut Signature); }
-    let res = ErrorCode::Success;
+        check_useful_removed_handle!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_signature_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_signature_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns multi signature for provided list of signatures.
@@ -439,34 +464,36 @@ pub extern fn indy_crypto_bls_signature_free(signature: *const c_void) -> ErrorC
 /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
 ///
 /// # Arguments
-/// * `signatures` - Signature instance pointers array
-/// * `signatures_len` - Signature instance pointers array len
-/// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+/// * `signatures` - Signature instance handles array
+/// * `signatures_len` - Signature instance handles array len
+/// * `multi_sig_p` - Reference that will contain multi signature instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_new(signatures: *const *const c_void,
+pub extern fn indy_crypto_bls_multi_signature_new(signatures: *const Handle,
                                                   signatures_len: usize,
-                                                  multi_sig_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_new: >>> signatures: {:?}, signatures_len: {:?}, multi_sig_p: {:?}", signatures, signatures_len, multi_sig_p);
-
-    check_useful_c_reference_array!(signatures, signatures_len, Signature, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_bls_multi_signature_new: signatures: {:?}", signatures);
-
-    let res = match MultiSignature::new(&signatures) {
-        Ok(multi_sig) => {
-            trace!("indy_crypto_bls_multi_signature_new: multi_sig: {:?}", multi_sig);
-            unsafe {
-                *multi_sig_p = Box::into_raw(Box::new(multi_sig)) as *const c_void;
-                trace!("indy_crypto_bls_multi_signature_new: *multi_sig_p: {:?}", *multi_sig_p);
+                                                  multi_sig_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_multi_signature_new: >>> signatures: {:?}, signatures_len: {:?}, multi_sig_p: {:?}", signatures, signatures_len, multi_sig_p);
+
+        check_useful_handle_array!(signatures, signatures_len, Signature, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_multi_signature_new: signatures: {:?}", signatures);
+
+        let res = match MultiSignature::new(&signatures) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_new: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = Handles::insert(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_new: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_multi_signature_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_multi_signature_new: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns multi signature from bytes representation.
@@ -476,32 +503,34 @@ pub extern fn indy_crypto_bls_multi_signature_new(signatures: *const *const c_vo
 /// # Arguments
 /// * `bytes` - Bytes buffer pointer
 /// * `bytes_len` - Bytes buffer len
-/// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+/// * `multi_sig_p` - Reference that will contain multi signature instance handle
 #[no_mangle]
 pub extern fn indy_crypto_bls_multi_signature_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                         multi_sig_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, multi_sig_p: {:?}", bytes, bytes_len, multi_sig_p);
+                                                         multi_sig_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_multi_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, multi_sig_p: {:?}", bytes, bytes_len, multi_sig_p);
 
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_multi_signature_from_bytes: bytes: {:?}", bytes);
-
-    let res = match MultiSignature::from_bytes(bytes) {
-        Ok(multi_sig) => {
-            trace!("indy_crypto_bls_multi_signature_from_bytes: multi_sig: {:?}", multi_sig);
-            unsafe {
-                *multi_sig_p = Box::into_raw(Box::new(multi_sig)) as *const c_void;
-                trace!("indy_crypto_bls_multi_signature_from_bytes: *multi_sig_p: {:?}", *multi_sig_p);
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_multi_signature_from_bytes: bytes: {:?}", bytes);
+
+        let res = match MultiSignature::from_bytes(bytes) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_from_bytes: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = Handles::insert(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_from_bytes: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_multi_signature_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_multi_signature_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns bytes representation of multi signature.
@@ -509,47 +538,50 @@ pub extern fn indy_crypto_bls_multi_signature_from_bytes(bytes: *const u8, bytes
 /// Note: Returned buffer lifetime is the same as multi signature instance.
 ///
 /// # Arguments
-/// * `multi_sig` - Multi signature instance pointer
+/// * `multi_sig` - Multi signature instance handle
 /// * `bytes_p` - Pointer that will contains bytes buffer
 /// * `bytes_len_p` - Pointer that will contains bytes buffer len
 #[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_as_bytes(multi_sig: *const c_void,
+pub extern fn indy_crypto_bls_multi_signature_as_bytes(multi_sig: Handle,
                                                        bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_as_bytes: >>> multi_sig: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", multi_sig, bytes_p, bytes_len_p);
+    catch_panic!({
+        trace!("indy_crypto_bls_multi_signature_as_bytes: >>> multi_sig: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", multi_sig, bytes_p, bytes_len_p);
 
-    check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
 
-    let multi_sig = unsafe { &*(multi_sig as *const MultiSignature) };
-    trace!("indy_crypto_bls_multi_signature_as_bytes: multi_sig: {:?}", multi_sig);
+        let multi_sig = unsafe { &*(multi_sig as *const MultiSignature) };
+        trace!("indy_crypto_bls_multi_signature_as_bytes: multi_sig: {:?}", multi_sig);
 
-    unsafe {
-        *bytes_p = multi_sig.as_bytes().as_ptr();
-        *bytes_len_p = multi_sig.as_bytes().len();
-    };
+        unsafe {
+            *bytes_p = multi_sig.as_bytes().as_ptr();
+            *bytes_len_p = multi_sig.as_bytes().len();
+        };
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_multi_signature_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_multi_signature_as_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates multi signature instance.
 ///
 /// # Arguments
-/// * `multi_sig` - Multi signature instance pointer
+/// * `multi_sig` - Multi signature instance handle
 #[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_free(multi_sig: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_bls_multi_signature_free(multi_sig: Handle) -> ErrorCode {
+    catch_panic!({
 
-    trace!("indy_crypto_bls_multi_signature_free: >>> multi_sig: {:?}", multi_sig);
+        trace!("indy_crypto_bls_multi_signature_free: >>> multi_sig: {:?}", multi_sig);
 
-    unsafe { Box::from_raw(multi_sig as *mut MultiSignature); }
-    let res = ErrorCode::Success;
+        check_useful_removed_handle!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_bls_multi_signature_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_multi_signature_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Signs the message and returns signature.
@@ -566,128 +598,135 @@ pub extern fn indy_crypto_bls_multi_signature_free(multi_sig: *const c_void) ->
 #[no_mangle]
 pub extern fn indy_crypto_bls_sign(message: *const u8,
                                    message_len: usize,
-                                   sign_key: *const c_void,
-                                   signature_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign: >>> message: {:?}, message_len: {:?}, sign_key: {:?}, signature_p: {:?}", message, message_len, sign_key, signature_p);
-
-    check_useful_c_byte_array!(message, message_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam3);
-    check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam5);
-
-    trace!("indy_crypto_bls_sign: message: {:?}, sign_key: {:?}", message, sign_key);
-
-    let res = match Bls::sign(message, sign_key) {
-        Ok(signature) => {
-            unsafe {
-                trace!("indy_crypto_bls_sign: signature: {:?}", signature);
-                *signature_p = Box::into_raw(Box::new(signature)) as *const c_void;
-                trace!("indy_crypto_bls_sign: *signature_p: {:?}", *signature_p);
+                                   sign_key: Handle,
+                                   signature_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_bls_sign: >>> message: {:?}, message_len: {:?}, sign_key: {:?}, signature_p: {:?}", message, message_len, sign_key, signature_p);
+
+        check_useful_c_byte_array!(message, message_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(sign_key, SignKey, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam5);
+
+        trace!("indy_crypto_bls_sign: message: {:?}, sign_key: {:?}", message, sign_key);
+
+        let res = match Bls::sign(message, sign_key) {
+            Ok(signature) => {
+                unsafe {
+                    trace!("indy_crypto_bls_sign: signature: {:?}", signature);
+                    *signature_p = Handles::insert(signature);
+                    trace!("indy_crypto_bls_sign: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Verifies the message signature and returns true - if signature valid or false otherwise.
 ///
 /// # Arguments
 ///
-/// * `signature` - Signature instance pointer
+/// * `signature` - Signature instance handle
 /// * `message` - Message to verify buffer pointer
 /// * `message_len` - Message to verify buffer len
 /// * `ver_key` - Verification key instance pinter
-/// * `gen` - Generator instance pointer
+/// * `gen` - Generator instance handle
 /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
 #[no_mangle]
-pub extern fn indy_crypto_bsl_verify(signature: *const c_void,
+pub extern fn indy_crypto_bsl_verify(signature: Handle,
                                      message: *const u8,
                                      message_len: usize,
-                                     ver_key: *const c_void,
-                                     gen: *const c_void,
+                                     ver_key: Handle,
+                                     gen: Handle,
                                      valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_bsl_verify: >>> signature: {:?}, message: {:?}, message_len: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", signature, message, message_len, ver_key, gen, valid_p);
-
-    check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_byte_array!(message, message_len,
-                               ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam5);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
-
-    trace!("indy_crypto_bsl_verify: signature: {:?}, message: {:?}, ver_key: {:?}, gen: {:?}", signature, message, ver_key, gen);
-
-    let res = match Bls::verify(signature, message, ver_key, gen) {
-        Ok(valid) => {
-            trace!("indy_crypto_bsl_verify: valid: {:?}", valid);
-            unsafe { *valid_p = valid; }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
-    res
+    catch_panic!({
+        trace!("indy_crypto_bsl_verify: >>> signature: {:?}, message: {:?}, message_len: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", signature, message, message_len, ver_key, gen, valid_p);
+
+        check_useful_handle!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_byte_array!(message, message_len,
+                                   ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(ver_key, VerKey, ErrorCode::CommonInvalidParam4);
+        check_useful_handle!(gen, Generator, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_bsl_verify: signature: {:?}, message: {:?}, ver_key: {:?}, gen: {:?}", signature, message, ver_key, gen);
+
+        let res = match Bls::verify(signature, message, ver_key, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bsl_verify: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Verifies the message multi signature and returns true - if signature valid or false otherwise.
 ///
 /// # Arguments
 ///
-/// * `multi_sig` - Multi signature instance pointer
+/// * `multi_sig` - Multi signature instance handle
 /// * `message` - Message to verify buffer pointer
 /// * `message_len` - Message to verify buffer len
-/// * `ver_keys` - Verification key instance pointers array
-/// * `ver_keys_len` - Verification keys instance pointers array len
+/// * `ver_keys` - Verification key instance handles array
+/// * `ver_keys_len` - Verification keys instance handles array len
 /// * `gen` - Generator point instance
 /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
 #[no_mangle]
-pub extern fn indy_crypto_bls_verify_multi_sig(multi_sig: *const c_void,
+pub extern fn indy_crypto_bls_verify_multi_sig(multi_sig: Handle,
                                                message: *const u8,
                                                message_len: usize,
-                                               ver_keys: *const *const c_void,
+                                               ver_keys: *const Handle,
                                                ver_keys_len: usize,
-                                               gen: *const c_void,
+                                               gen: Handle,
                                                valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_bls_verify_multi_sig: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
-
-    check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam6);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
-
-    trace!("indy_crypto_bls_verify_multi_sig: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
-
-    let res = match Bls::verify_multi_sig(multi_sig, message, &ver_keys, gen) {
-        Ok(valid) => {
-            trace!("indy_crypto_bls_verify_multi_sig: valid: {:?}", valid);
-            unsafe { *valid_p = valid; }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_bls_verify_multi_sig: <<< res: {:?}", res);
-    res
+    catch_panic!({
+        trace!("indy_crypto_bls_verify_multi_sig: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
+
+        check_useful_handle!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+        check_useful_handle_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+        check_useful_handle!(gen, Generator, ErrorCode::CommonInvalidParam6);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
+
+        trace!("indy_crypto_bls_verify_multi_sig: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
+
+        let res = match Bls::verify_multi_sig(multi_sig, message, &ver_keys, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_multi_sig: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_bls_verify_multi_sig: <<< res: {:?}", res);
+        res
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ffi::handles::INVALID_HANDLE;
     use std::ptr;
 
     #[test]
     fn indy_crypto_bls_generator_new_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
 
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
+        assert_ne!(gen, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
@@ -695,7 +734,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_generator_as_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -703,7 +742,7 @@ mod tests {
         let mut bytes_len: usize = 0;
         let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
+        assert_ne!(gen, INVALID_HANDLE);
         assert!(bytes_len > 0);
 
         let err_code = indy_crypto_bls_generator_free(gen);
@@ -712,7 +751,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_generator_from_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -721,7 +760,7 @@ mod tests {
         let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut gen2: *const c_void = ptr::null();
+        let mut gen2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_from_bytes(bytes, bytes_len, &mut gen2);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -734,11 +773,11 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_generator_free_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
 
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
+        assert_ne!(gen, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
@@ -746,13 +785,13 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_sign_key_new_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
 
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!sign_key.is_null());
+        assert_ne!(sign_key, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_sign_key_free(sign_key);
         assert_eq!(err_code, ErrorCode::Success);
@@ -760,7 +799,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_sign_key_new_works_for_seed() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
 
         let seed_v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
                           11, 2, 3, 4, 5, 6, 7, 8, 9, 10,
@@ -770,7 +809,7 @@ mod tests {
 
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!sign_key.is_null());
+        assert_ne!(sign_key, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_sign_key_free(sign_key);
         assert_eq!(err_code, ErrorCode::Success);
@@ -778,7 +817,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_sign_key_as_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -797,7 +836,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_sign_key_from_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -808,7 +847,7 @@ mod tests {
         let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign_key_from_bytes(bytes, bytes_len, &mut sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -821,7 +860,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_sign_key_free_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -833,20 +872,20 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_ver_key_new_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!ver_key.is_null());
+        assert_ne!(ver_key, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
@@ -860,17 +899,17 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_ver_key_as_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -893,17 +932,17 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_ver_key_from_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -914,7 +953,7 @@ mod tests {
         assert!(!bytes.is_null());
         assert!(bytes_len > 0);
 
-        let mut ver_key2: *const c_void = ptr::null();
+        let mut ver_key2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_from_bytes(bytes, bytes_len, &mut ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -933,17 +972,17 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_ver_key_free_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -959,7 +998,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_signature_as_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -969,7 +1008,7 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature: *const c_void = ptr::null();
+        let mut signature: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -989,7 +1028,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_signature_from_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -999,7 +1038,7 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature: *const c_void = ptr::null();
+        let mut signature: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1010,7 +1049,7 @@ mod tests {
         assert!(!bytes.is_null());
         assert!(bytes_len > 0);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_signature_from_bytes(bytes, bytes_len, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1026,7 +1065,7 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_signature_free_works() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
@@ -1036,7 +1075,7 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature: *const c_void = ptr::null();
+        let mut signature: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1049,13 +1088,13 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_multi_signature_new_works() {
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
@@ -1065,20 +1104,20 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig.is_null());
+        assert_ne!(multi_sig, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_sign_key_free(sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
@@ -1096,15 +1135,48 @@ mod tests {
         assert_eq!(err_code, ErrorCode::Success);
     }
 
+    #[test]
+    fn indy_crypto_bls_multi_signature_new_works_for_duplicated_handle() {
+        let mut sign_key: Handle = INVALID_HANDLE;
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature, signature];
+
+        let mut multi_sig: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_ne!(multi_sig, INVALID_HANDLE);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
     #[test]
     fn indy_crypto_bls_multi_signature_as_bytes_works() {
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
@@ -1114,20 +1186,20 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig.is_null());
+        assert_ne!(multi_sig, INVALID_HANDLE);
 
         let mut bytes: *const u8 = ptr::null();
         let mut bytes_len: usize = 0;
@@ -1154,13 +1226,13 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_multi_signature_from_bytes_works() {
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
@@ -1170,17 +1242,17 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1191,10 +1263,10 @@ mod tests {
         assert!(!bytes.is_null());
         assert!(bytes_len > 0);
 
-        let mut multi_sig2: *const c_void = ptr::null();
+        let mut multi_sig2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_from_bytes(bytes, bytes_len, &mut multi_sig2);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig2.is_null());
+        assert_ne!(multi_sig2, INVALID_HANDLE);
 
         let err_code = indy_crypto_bls_sign_key_free(sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
@@ -1217,13 +1289,13 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_multi_signature_free_works() {
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
@@ -1233,17 +1305,17 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1266,17 +1338,17 @@ mod tests {
 
     #[test]
     fn indy_crypto_bsl_verify_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1284,7 +1356,7 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature: *const c_void = ptr::null();
+        let mut signature: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1312,23 +1384,23 @@ mod tests {
 
     #[test]
     fn indy_crypto_bsl_verify_works_for_invalid() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
+        let mut ver_key: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1336,7 +1408,7 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature: *const c_void = ptr::null();
+        let mut signature: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1367,17 +1439,17 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_verify_multi_sig_works() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
@@ -1387,25 +1459,25 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key1: *const c_void = ptr::null();
+        let mut ver_key1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key2: *const c_void = ptr::null();
+        let mut ver_key2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1447,23 +1519,23 @@ mod tests {
 
     #[test]
     fn indy_crypto_bls_verify_multi_sig_works_for_invalid() {
-        let mut gen: *const c_void = ptr::null();
+        let mut gen: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key1: *const c_void = ptr::null();
+        let mut sign_key1: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
+        let mut sign_key2: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key3: *const c_void = ptr::null();
+        let mut sign_key3: Handle = INVALID_HANDLE;
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
         let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key3);
@@ -1473,25 +1545,25 @@ mod tests {
         let message = message_v.as_ptr();
         let message_len = message_v.len();
 
-        let mut signature1: *const c_void = ptr::null();
+        let mut signature1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
+        let mut signature2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
         let signatures = [signature1, signature2];
 
-        let mut multi_sig: *const c_void = ptr::null();
+        let mut multi_sig: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key1: *const c_void = ptr::null();
+        let mut ver_key1: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key2: *const c_void = ptr::null();
+        let mut ver_key2: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key3, &mut ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
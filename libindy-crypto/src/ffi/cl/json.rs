@@ -0,0 +1,688 @@
+use cl::issuer::Issuer;
+use cl::prover::Prover;
+use cl::verifier::Verifier;
+use cl::*;
+use errors::{IndyCryptoError, ToErrorCode};
+use ffi::ErrorCode;
+use utils::ctypes::CTypesUtils;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use libc::c_char;
+use serde_json;
+
+use std::collections::BTreeMap;
+
+/// Credential schema json: list of attribute names.
+type CredentialSchemaJson = Vec<String>;
+
+/// Credential values json: attribute name to decimal string of encoded value.
+type CredentialValuesJson = BTreeMap<String, String>;
+
+#[derive(Debug, Deserialize)]
+struct PredicateJson {
+    attr_name: String,
+    p_type: String,
+    value: i32,
+    #[serde(default)]
+    proof_version: PredicateProofVersion
+}
+
+#[derive(Debug, Deserialize)]
+struct NonRevokedIntervalJson {
+    from: u64,
+    to: u64
+}
+
+#[derive(Debug, Deserialize)]
+struct SubProofRequestJson {
+    #[serde(default)]
+    revealed_attrs: Vec<String>,
+    #[serde(default)]
+    predicates: Vec<PredicateJson>,
+    #[serde(default)]
+    non_revoked: Option<NonRevokedIntervalJson>
+}
+
+#[derive(Debug, Deserialize)]
+struct ProverSubProofJson {
+    sub_proof_request: SubProofRequestJson,
+    credential_schema: CredentialSchemaJson,
+    credential_signature: CredentialSignature,
+    credential_values: CredentialValuesJson,
+    credential_pub_key: CredentialPublicKey,
+    #[serde(default)]
+    rev_reg: Option<RevocationRegistry>,
+    #[serde(default)]
+    witness: Option<Witness>
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifierSubProofJson {
+    sub_proof_request: SubProofRequestJson,
+    credential_schema: CredentialSchemaJson,
+    credential_pub_key: CredentialPublicKey,
+    #[serde(default)]
+    rev_key_pub: Option<RevocationKeyPublic>,
+    #[serde(default)]
+    rev_reg: Option<RevocationRegistry>
+}
+
+fn _credential_schema(credential_schema: &CredentialSchemaJson) -> Result<CredentialSchema, IndyCryptoError> {
+    let mut credential_schema_builder = Issuer::new_credential_schema_builder()?;
+    for attr in credential_schema.iter() {
+        credential_schema_builder.add_attr(attr)?;
+    }
+    credential_schema_builder.finalize()
+}
+
+fn _credential_values(credential_values: &CredentialValuesJson) -> Result<CredentialValues, IndyCryptoError> {
+    let mut credential_values_builder = Issuer::new_credential_values_builder()?;
+    for (attr, value) in credential_values.iter() {
+        credential_values_builder.add_value(attr, value)?;
+    }
+    credential_values_builder.finalize()
+}
+
+fn _sub_proof_request(sub_proof_request: &SubProofRequestJson) -> Result<SubProofRequest, IndyCryptoError> {
+    let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder()?;
+    for attr in sub_proof_request.revealed_attrs.iter() {
+        sub_proof_request_builder.add_revealed_attr(attr)?;
+    }
+    for predicate in sub_proof_request.predicates.iter() {
+        sub_proof_request_builder.add_predicate_with_proof_version(&predicate.attr_name, &predicate.p_type, predicate.value, predicate.proof_version)?;
+    }
+    if let Some(ref non_revoked) = sub_proof_request.non_revoked {
+        sub_proof_request_builder.set_non_revoked_interval(non_revoked.from, non_revoked.to)?;
+    }
+    sub_proof_request_builder.finalize()
+}
+
+fn _set_json(json: String, json_p: *mut *const c_char) {
+    let json = CTypesUtils::string_to_cstring(json);
+    unsafe { *json_p = json.into_raw(); }
+}
+
+/// Creates credential definition from credential schema json and returns keys and correctness proof as json.
+///
+/// # Arguments
+/// * `credential_schema_json` - Credential schema json: list of attribute names.
+/// * `support_revocation` - If true non revocation part of credential keys will be generated.
+/// * `credential_pub_key_json_p` - Reference that will contain credential public key json.
+/// * `credential_priv_key_json_p` - Reference that will contain credential private key json.
+/// * `credential_key_correctness_proof_json_p` - Reference that will contain credential key correctness proof json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_issuer_new_credential_def_json(credential_schema_json: *const c_char,
+                                                            support_revocation: bool,
+                                                            credential_pub_key_json_p: *mut *const c_char,
+                                                            credential_priv_key_json_p: *mut *const c_char,
+                                                            credential_key_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_issuer_new_credential_def_json: >>> credential_schema_json: {:?}, support_revocation: {:?}, credential_pub_key_json_p: {:?}, \
+        credential_priv_key_json_p: {:?}, credential_key_correctness_proof_json_p: {:?}",
+               credential_schema_json, support_revocation, credential_pub_key_json_p, credential_priv_key_json_p, credential_key_correctness_proof_json_p);
+
+        check_useful_c_str!(credential_schema_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_pub_key_json_p, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(credential_priv_key_json_p, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(credential_key_correctness_proof_json_p, ErrorCode::CommonInvalidParam5);
+
+        let res = match _new_credential_def_json(&credential_schema_json, support_revocation) {
+            Ok((credential_pub_key_json, credential_priv_key_json, credential_key_correctness_proof_json)) => {
+                _set_json(credential_pub_key_json, credential_pub_key_json_p);
+                _set_json(credential_priv_key_json, credential_priv_key_json_p);
+                _set_json(credential_key_correctness_proof_json, credential_key_correctness_proof_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_issuer_new_credential_def_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _new_credential_def_json(credential_schema_json: &str,
+                            support_revocation: bool) -> Result<(String, String, String), IndyCryptoError> {
+    let credential_schema = _credential_schema(&serde_json::from_str(credential_schema_json)?)?;
+
+    let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+        Issuer::new_credential_def(&credential_schema, support_revocation)?;
+
+    Ok((credential_pub_key.to_json()?, credential_priv_key.to_json()?, credential_key_correctness_proof.to_json()?))
+}
+
+/// Creates master secret and returns it as json.
+///
+/// # Arguments
+/// * `master_secret_json_p` - Reference that will contain master secret json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_prover_new_master_secret_json(master_secret_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_new_master_secret_json: >>> master_secret_json_p: {:?}", master_secret_json_p);
+
+        check_useful_c_ptr!(master_secret_json_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Prover::new_master_secret().and_then(|master_secret| master_secret.to_json()) {
+            Ok(master_secret_json) => {
+                _set_json(master_secret_json, master_secret_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_prover_new_master_secret_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Creates random nonce and returns it as json.
+///
+/// # Arguments
+/// * `nonce_json_p` - Reference that will contain nonce json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_new_nonce_json(nonce_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_new_nonce_json: >>> nonce_json_p: {:?}", nonce_json_p);
+
+        check_useful_c_ptr!(nonce_json_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match new_nonce().and_then(|nonce| nonce.to_json()) {
+            Ok(nonce_json) => {
+                _set_json(nonce_json, nonce_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_new_nonce_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Blinds master secret and returns blinded master secret, blinding data and correctness proof as json.
+///
+/// # Arguments
+/// * `credential_pub_key_json` - Credential public key json.
+/// * `credential_key_correctness_proof_json` - Credential key correctness proof json.
+/// * `master_secret_json` - Master secret json.
+/// * `master_secret_blinding_nonce_json` - Nonce json used for creation of blinded_master_secret_correctness_proof.
+/// * `blinded_master_secret_json_p` - Reference that will contain blinded master secret json.
+/// * `master_secret_blinding_data_json_p` - Reference that will contain master secret blinding data json.
+/// * `blinded_master_secret_correctness_proof_json_p` - Reference that will contain blinded master secret correctness proof json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_prover_blind_master_secret_json(credential_pub_key_json: *const c_char,
+                                                             credential_key_correctness_proof_json: *const c_char,
+                                                             master_secret_json: *const c_char,
+                                                             master_secret_blinding_nonce_json: *const c_char,
+                                                             blinded_master_secret_json_p: *mut *const c_char,
+                                                             master_secret_blinding_data_json_p: *mut *const c_char,
+                                                             blinded_master_secret_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_blind_master_secret_json: >>> credential_pub_key_json: {:?}, credential_key_correctness_proof_json: {:?}, \
+        master_secret_json: {:?}, master_secret_blinding_nonce_json: {:?}, blinded_master_secret_json_p: {:?}, master_secret_blinding_data_json_p: {:?}, \
+        blinded_master_secret_correctness_proof_json_p: {:?}",
+               credential_pub_key_json, credential_key_correctness_proof_json, master_secret_json, master_secret_blinding_nonce_json,
+               blinded_master_secret_json_p, master_secret_blinding_data_json_p, blinded_master_secret_correctness_proof_json_p);
+
+        check_useful_c_str!(credential_pub_key_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(credential_key_correctness_proof_json, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_str!(master_secret_blinding_nonce_json, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(blinded_master_secret_json_p, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(master_secret_blinding_data_json_p, ErrorCode::CommonInvalidParam6);
+        check_useful_c_ptr!(blinded_master_secret_correctness_proof_json_p, ErrorCode::CommonInvalidParam7);
+
+        let res = match _blind_master_secret_json(&credential_pub_key_json,
+                                                  &credential_key_correctness_proof_json,
+                                                  &master_secret_json,
+                                                  &master_secret_blinding_nonce_json) {
+            Ok((blinded_master_secret_json, master_secret_blinding_data_json, blinded_master_secret_correctness_proof_json)) => {
+                _set_json(blinded_master_secret_json, blinded_master_secret_json_p);
+                _set_json(master_secret_blinding_data_json, master_secret_blinding_data_json_p);
+                _set_json(blinded_master_secret_correctness_proof_json, blinded_master_secret_correctness_proof_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_prover_blind_master_secret_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _blind_master_secret_json(credential_pub_key_json: &str,
+                             credential_key_correctness_proof_json: &str,
+                             master_secret_json: &str,
+                             master_secret_blinding_nonce_json: &str) -> Result<(String, String, String), IndyCryptoError> {
+    let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+        Prover::blind_master_secret(&CredentialPublicKey::from_json(credential_pub_key_json)?,
+                                    &CredentialKeyCorrectnessProof::from_json(credential_key_correctness_proof_json)?,
+                                    &MasterSecret::from_json(master_secret_json)?,
+                                    &Nonce::from_json(master_secret_blinding_nonce_json)?)?;
+
+    Ok((blinded_master_secret.to_json()?, master_secret_blinding_data.to_json()?, blinded_master_secret_correctness_proof.to_json()?))
+}
+
+/// Signs credential values json with primary keys only and returns credential signature and signature correctness proof as json.
+///
+/// # Arguments
+/// * `prover_id` - Prover identifier.
+/// * `blinded_master_secret_json` - Blinded master secret json.
+/// * `blinded_master_secret_correctness_proof_json` - Blinded master secret correctness proof json.
+/// * `master_secret_blinding_nonce_json` - Nonce json used for verification of blinded_master_secret_correctness_proof.
+/// * `credential_issuance_nonce_json` - Nonce json used for creation of signature_correctness_proof.
+/// * `credential_values_json` - Credential values json: attribute name to decimal string of encoded value.
+/// * `credential_pub_key_json` - Credential public key json.
+/// * `credential_priv_key_json` - Credential private key json.
+/// * `credential_signature_json_p` - Reference that will contain credential signature json.
+/// * `signature_correctness_proof_json_p` - Reference that will contain signature correctness proof json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_issuer_sign_credential_json(prover_id: *const c_char,
+                                                         blinded_master_secret_json: *const c_char,
+                                                         blinded_master_secret_correctness_proof_json: *const c_char,
+                                                         master_secret_blinding_nonce_json: *const c_char,
+                                                         credential_issuance_nonce_json: *const c_char,
+                                                         credential_values_json: *const c_char,
+                                                         credential_pub_key_json: *const c_char,
+                                                         credential_priv_key_json: *const c_char,
+                                                         credential_signature_json_p: *mut *const c_char,
+                                                         signature_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_issuer_sign_credential_json: >>> prover_id: {:?}, blinded_master_secret_json: {:?}, blinded_master_secret_correctness_proof_json: {:?}, \
+        master_secret_blinding_nonce_json: {:?}, credential_issuance_nonce_json: {:?}, credential_values_json: {:?}, credential_pub_key_json: {:?}, \
+        credential_priv_key_json: {:?}, credential_signature_json_p: {:?}, signature_correctness_proof_json_p: {:?}",
+               prover_id, blinded_master_secret_json, blinded_master_secret_correctness_proof_json, master_secret_blinding_nonce_json,
+               credential_issuance_nonce_json, credential_values_json, credential_pub_key_json, credential_priv_key_json,
+               credential_signature_json_p, signature_correctness_proof_json_p);
+
+        check_useful_c_str!(prover_id, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(blinded_master_secret_json, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(blinded_master_secret_correctness_proof_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_str!(master_secret_blinding_nonce_json, ErrorCode::CommonInvalidParam4);
+        check_useful_c_str!(credential_issuance_nonce_json, ErrorCode::CommonInvalidParam5);
+        check_useful_c_str!(credential_values_json, ErrorCode::CommonInvalidParam6);
+        check_useful_c_str!(credential_pub_key_json, ErrorCode::CommonInvalidParam7);
+        check_useful_c_str!(credential_priv_key_json, ErrorCode::CommonInvalidParam8);
+        check_useful_c_ptr!(credential_signature_json_p, ErrorCode::CommonInvalidParam9);
+        check_useful_c_ptr!(signature_correctness_proof_json_p, ErrorCode::CommonInvalidParam10);
+
+        let res = match _sign_credential_json(&prover_id,
+                                              &blinded_master_secret_json,
+                                              &blinded_master_secret_correctness_proof_json,
+                                              &master_secret_blinding_nonce_json,
+                                              &credential_issuance_nonce_json,
+                                              &credential_values_json,
+                                              &credential_pub_key_json,
+                                              &credential_priv_key_json) {
+            Ok((credential_signature_json, signature_correctness_proof_json)) => {
+                _set_json(credential_signature_json, credential_signature_json_p);
+                _set_json(signature_correctness_proof_json, signature_correctness_proof_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_issuer_sign_credential_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _sign_credential_json(prover_id: &str,
+                         blinded_master_secret_json: &str,
+                         blinded_master_secret_correctness_proof_json: &str,
+                         master_secret_blinding_nonce_json: &str,
+                         credential_issuance_nonce_json: &str,
+                         credential_values_json: &str,
+                         credential_pub_key_json: &str,
+                         credential_priv_key_json: &str) -> Result<(String, String), IndyCryptoError> {
+    let (credential_signature, signature_correctness_proof) =
+        Issuer::sign_credential(prover_id,
+                                &BlindedMasterSecret::from_json(blinded_master_secret_json)?,
+                                &BlindedMasterSecretCorrectnessProof::from_json(blinded_master_secret_correctness_proof_json)?,
+                                &Nonce::from_json(master_secret_blinding_nonce_json)?,
+                                &Nonce::from_json(credential_issuance_nonce_json)?,
+                                &_credential_values(&serde_json::from_str(credential_values_json)?)?,
+                                &CredentialPublicKey::from_json(credential_pub_key_json)?,
+                                &CredentialPrivateKey::from_json(credential_priv_key_json)?)?;
+
+    Ok((credential_signature.to_json()?, signature_correctness_proof.to_json()?))
+}
+
+/// Checks and updates credential signature received from Issuer and returns processed signature as json.
+///
+/// # Arguments
+/// * `credential_signature_json` - Credential signature json generated by Issuer.
+/// * `credential_values_json` - Credential values json: attribute name to decimal string of encoded value.
+/// * `signature_correctness_proof_json` - Signature correctness proof json.
+/// * `master_secret_blinding_data_json` - Master secret blinding data json.
+/// * `master_secret_json` - Master secret json.
+/// * `credential_pub_key_json` - Credential public key json.
+/// * `credential_issuance_nonce_json` - Nonce json used by Issuer for the creation of signature_correctness_proof.
+/// * `rev_key_pub_json` - (Optional) Revocation registry public key json.
+/// * `rev_reg_json` - (Optional) Revocation registry json.
+/// * `witness_json` - (Optional) Witness json.
+/// * `credential_signature_json_p` - Reference that will contain processed credential signature json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_prover_process_credential_signature_json(credential_signature_json: *const c_char,
+                                                                      credential_values_json: *const c_char,
+                                                                      signature_correctness_proof_json: *const c_char,
+                                                                      master_secret_blinding_data_json: *const c_char,
+                                                                      master_secret_json: *const c_char,
+                                                                      credential_pub_key_json: *const c_char,
+                                                                      credential_issuance_nonce_json: *const c_char,
+                                                                      rev_key_pub_json: *const c_char,
+                                                                      rev_reg_json: *const c_char,
+                                                                      witness_json: *const c_char,
+                                                                      credential_signature_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_process_credential_signature_json: >>> credential_signature_json: {:?}, credential_values_json: {:?}, \
+        signature_correctness_proof_json: {:?}, master_secret_blinding_data_json: {:?}, master_secret_json: {:?}, credential_pub_key_json: {:?}, \
+        credential_issuance_nonce_json: {:?}, rev_key_pub_json: {:?}, rev_reg_json: {:?}, witness_json: {:?}, credential_signature_json_p: {:?}",
+               credential_signature_json, credential_values_json, signature_correctness_proof_json, master_secret_blinding_data_json, master_secret_json,
+               credential_pub_key_json, credential_issuance_nonce_json, rev_key_pub_json, rev_reg_json, witness_json, credential_signature_json_p);
+
+        check_useful_c_str!(credential_signature_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(credential_values_json, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(signature_correctness_proof_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_str!(master_secret_blinding_data_json, ErrorCode::CommonInvalidParam4);
+        check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam5);
+        check_useful_c_str!(credential_pub_key_json, ErrorCode::CommonInvalidParam6);
+        check_useful_c_str!(credential_issuance_nonce_json, ErrorCode::CommonInvalidParam7);
+        check_useful_opt_c_str!(rev_key_pub_json, ErrorCode::CommonInvalidParam8);
+        check_useful_opt_c_str!(rev_reg_json, ErrorCode::CommonInvalidParam9);
+        check_useful_opt_c_str!(witness_json, ErrorCode::CommonInvalidParam10);
+        check_useful_c_ptr!(credential_signature_json_p, ErrorCode::CommonInvalidParam11);
+
+        let res = match _process_credential_signature_json(&credential_signature_json,
+                                                           &credential_values_json,
+                                                           &signature_correctness_proof_json,
+                                                           &master_secret_blinding_data_json,
+                                                           &master_secret_json,
+                                                           &credential_pub_key_json,
+                                                           &credential_issuance_nonce_json,
+                                                           rev_key_pub_json.as_ref().map(String::as_str),
+                                                           rev_reg_json.as_ref().map(String::as_str),
+                                                           witness_json.as_ref().map(String::as_str)) {
+            Ok(credential_signature_json) => {
+                _set_json(credential_signature_json, credential_signature_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_prover_process_credential_signature_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _process_credential_signature_json(credential_signature_json: &str,
+                                      credential_values_json: &str,
+                                      signature_correctness_proof_json: &str,
+                                      master_secret_blinding_data_json: &str,
+                                      master_secret_json: &str,
+                                      credential_pub_key_json: &str,
+                                      credential_issuance_nonce_json: &str,
+                                      rev_key_pub_json: Option<&str>,
+                                      rev_reg_json: Option<&str>,
+                                      witness_json: Option<&str>) -> Result<String, IndyCryptoError> {
+    let mut credential_signature = CredentialSignature::from_json(credential_signature_json)?;
+
+    let rev_key_pub = match rev_key_pub_json {
+        Some(rev_key_pub_json) => Some(RevocationKeyPublic::from_json(rev_key_pub_json)?),
+        None => None
+    };
+
+    let rev_reg = match rev_reg_json {
+        Some(rev_reg_json) => Some(RevocationRegistry::from_json(rev_reg_json)?),
+        None => None
+    };
+
+    let witness = match witness_json {
+        Some(witness_json) => Some(Witness::from_json(witness_json)?),
+        None => None
+    };
+
+    Prover::process_credential_signature(&mut credential_signature,
+                                         &_credential_values(&serde_json::from_str(credential_values_json)?)?,
+                                         &SignatureCorrectnessProof::from_json(signature_correctness_proof_json)?,
+                                         &MasterSecretBlindingData::from_json(master_secret_blinding_data_json)?,
+                                         &MasterSecret::from_json(master_secret_json)?,
+                                         &CredentialPublicKey::from_json(credential_pub_key_json)?,
+                                         &Nonce::from_json(credential_issuance_nonce_json)?,
+                                         rev_key_pub.as_ref(),
+                                         rev_reg.as_ref(),
+                                         witness.as_ref())?;
+
+    credential_signature.to_json()
+}
+
+/// Creates proof for sub proofs json and returns it as json.
+///
+/// Sub proofs json is a list of objects with fields:
+/// `sub_proof_request` ({"revealed_attrs": [..], "predicates": [{"attr_name", "p_type", "value"}], "non_revoked": {"from", "to"}}),
+/// `credential_schema` (list of attribute names), `credential_signature`, `credential_values` (attribute name to decimal string),
+/// `credential_pub_key` and optional `rev_reg` and `witness`.
+///
+/// # Arguments
+/// * `sub_proofs_json` - Sub proofs json.
+/// * `nonce_json` - Proof request nonce json.
+/// * `master_secret_json` - Master secret json.
+/// * `proof_json_p` - Reference that will contain proof json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_prover_create_proof_json(sub_proofs_json: *const c_char,
+                                                      nonce_json: *const c_char,
+                                                      master_secret_json: *const c_char,
+                                                      proof_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_create_proof_json: >>> sub_proofs_json: {:?}, nonce_json: {:?}, master_secret_json: {:?}, proof_json_p: {:?}",
+               sub_proofs_json, nonce_json, master_secret_json, proof_json_p);
+
+        check_useful_c_str!(sub_proofs_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(nonce_json, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(proof_json_p, ErrorCode::CommonInvalidParam4);
+
+        let res = match _create_proof_json(&sub_proofs_json, &nonce_json, &master_secret_json) {
+            Ok(proof_json) => {
+                _set_json(proof_json, proof_json_p);
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_prover_create_proof_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _create_proof_json(sub_proofs_json: &str,
+                      nonce_json: &str,
+                      master_secret_json: &str) -> Result<String, IndyCryptoError> {
+    let sub_proofs: Vec<ProverSubProofJson> = serde_json::from_str(sub_proofs_json)?;
+
+    let mut proof_builder = Prover::new_proof_builder()?;
+    for sub_proof in sub_proofs.iter() {
+        proof_builder.add_sub_proof_request(&_sub_proof_request(&sub_proof.sub_proof_request)?,
+                                            &_credential_schema(&sub_proof.credential_schema)?,
+                                            &sub_proof.credential_signature,
+                                            &_credential_values(&sub_proof.credential_values)?,
+                                            &sub_proof.credential_pub_key,
+                                            sub_proof.rev_reg.as_ref(),
+                                            sub_proof.witness.as_ref())?;
+    }
+
+    let proof = proof_builder.finalize(&Nonce::from_json(nonce_json)?, &MasterSecret::from_json(master_secret_json)?)?;
+
+    proof.to_json()
+}
+
+/// Verifies proof json against sub proofs json.
+///
+/// Sub proofs json is a list of objects with fields:
+/// `sub_proof_request`, `credential_schema`, `credential_pub_key` and optional `rev_key_pub` and `rev_reg`
+/// in the same order as sub proofs were added by Prover.
+///
+/// # Arguments
+/// * `proof_json` - Proof json.
+/// * `sub_proofs_json` - Sub proofs json.
+/// * `nonce_json` - Proof request nonce json.
+/// * `valid_p` - Reference that will be filled with true - if proof valid or false otherwise.
+#[no_mangle]
+pub extern fn indy_crypto_cl_verifier_verify_proof_json(proof_json: *const c_char,
+                                                        sub_proofs_json: *const c_char,
+                                                        nonce_json: *const c_char,
+                                                        valid_p: *mut bool) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_verifier_verify_proof_json: >>> proof_json: {:?}, sub_proofs_json: {:?}, nonce_json: {:?}, valid_p: {:?}",
+               proof_json, sub_proofs_json, nonce_json, valid_p);
+
+        check_useful_c_str!(proof_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(sub_proofs_json, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(nonce_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
+
+        let res = match _verify_proof_json(&proof_json, &sub_proofs_json, &nonce_json) {
+            Ok(valid) => {
+                trace!("indy_crypto_cl_verifier_verify_proof_json: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_verifier_verify_proof_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+fn _verify_proof_json(proof_json: &str,
+                      sub_proofs_json: &str,
+                      nonce_json: &str) -> Result<bool, IndyCryptoError> {
+    let sub_proofs: Vec<VerifierSubProofJson> = serde_json::from_str(sub_proofs_json)?;
+
+    let mut proof_verifier = Verifier::new_proof_verifier()?;
+    for sub_proof in sub_proofs.iter() {
+        proof_verifier.add_sub_proof_request(&_sub_proof_request(&sub_proof.sub_proof_request)?,
+                                             &_credential_schema(&sub_proof.credential_schema)?,
+                                             &sub_proof.credential_pub_key,
+                                             sub_proof.rev_key_pub.as_ref(),
+                                             sub_proof.rev_reg.as_ref())?;
+    }
+
+    proof_verifier.verify(&Proof::from_json(proof_json)?, &Nonce::from_json(nonce_json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CString;
+    use std::ptr;
+
+    fn _string(json_p: *const c_char) -> String {
+        CTypesUtils::c_str_to_string(json_p).unwrap().unwrap()
+    }
+
+    #[test]
+    fn json_api_works() {
+        let credential_schema_json = CString::new(r#"["name","age"]"#).unwrap();
+        let credential_values_json = CString::new(r#"{"name":"1139481716457488690172217916278103335","age":"28"}"#).unwrap();
+
+        let mut credential_pub_key_json_p: *const c_char = ptr::null();
+        let mut credential_priv_key_json_p: *const c_char = ptr::null();
+        let mut credential_key_correctness_proof_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_issuer_new_credential_def_json(credential_schema_json.as_ptr(),
+                                                                     false,
+                                                                     &mut credential_pub_key_json_p,
+                                                                     &mut credential_priv_key_json_p,
+                                                                     &mut credential_key_correctness_proof_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut master_secret_json_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_cl_prover_new_master_secret_json(&mut master_secret_json_p), ErrorCode::Success);
+
+        let mut master_secret_blinding_nonce_json_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_cl_new_nonce_json(&mut master_secret_blinding_nonce_json_p), ErrorCode::Success);
+
+        let mut blinded_master_secret_json_p: *const c_char = ptr::null();
+        let mut master_secret_blinding_data_json_p: *const c_char = ptr::null();
+        let mut blinded_master_secret_correctness_proof_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_prover_blind_master_secret_json(credential_pub_key_json_p,
+                                                                      credential_key_correctness_proof_json_p,
+                                                                      master_secret_json_p,
+                                                                      master_secret_blinding_nonce_json_p,
+                                                                      &mut blinded_master_secret_json_p,
+                                                                      &mut master_secret_blinding_data_json_p,
+                                                                      &mut blinded_master_secret_correctness_proof_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let prover_id = CString::new("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW").unwrap();
+        let mut credential_issuance_nonce_json_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_cl_new_nonce_json(&mut credential_issuance_nonce_json_p), ErrorCode::Success);
+
+        let mut credential_signature_json_p: *const c_char = ptr::null();
+        let mut signature_correctness_proof_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_issuer_sign_credential_json(prover_id.as_ptr(),
+                                                                  blinded_master_secret_json_p,
+                                                                  blinded_master_secret_correctness_proof_json_p,
+                                                                  master_secret_blinding_nonce_json_p,
+                                                                  credential_issuance_nonce_json_p,
+                                                                  credential_values_json.as_ptr(),
+                                                                  credential_pub_key_json_p,
+                                                                  credential_priv_key_json_p,
+                                                                  &mut credential_signature_json_p,
+                                                                  &mut signature_correctness_proof_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut processed_credential_signature_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_prover_process_credential_signature_json(credential_signature_json_p,
+                                                                               credential_values_json.as_ptr(),
+                                                                               signature_correctness_proof_json_p,
+                                                                               master_secret_blinding_data_json_p,
+                                                                               master_secret_json_p,
+                                                                               credential_pub_key_json_p,
+                                                                               credential_issuance_nonce_json_p,
+                                                                               ptr::null(),
+                                                                               ptr::null(),
+                                                                               ptr::null(),
+                                                                               &mut processed_credential_signature_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let sub_proof_request_json = r#"{"revealed_attrs":["name"],"predicates":[{"attr_name":"age","p_type":"GE","value":18}]}"#;
+
+        let sub_proofs_json = CString::new(format!(
+            r#"[{{"sub_proof_request":{},"credential_schema":["name","age"],"credential_signature":{},"credential_values":{{"name":"1139481716457488690172217916278103335","age":"28"}},"credential_pub_key":{}}}]"#,
+            sub_proof_request_json, _string(processed_credential_signature_json_p), _string(credential_pub_key_json_p))).unwrap();
+
+        let mut nonce_json_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_cl_new_nonce_json(&mut nonce_json_p), ErrorCode::Success);
+
+        let mut proof_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_prover_create_proof_json(sub_proofs_json.as_ptr(), nonce_json_p, master_secret_json_p, &mut proof_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let verifier_sub_proofs_json = CString::new(format!(
+            r#"[{{"sub_proof_request":{},"credential_schema":["name","age"],"credential_pub_key":{}}}]"#,
+            sub_proof_request_json, _string(credential_pub_key_json_p))).unwrap();
+
+        let mut valid = false;
+        let err_code = indy_crypto_cl_verifier_verify_proof_json(proof_json_p, verifier_sub_proofs_json.as_ptr(), nonce_json_p, &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+    }
+
+    #[test]
+    fn indy_crypto_cl_issuer_new_credential_def_json_works_for_invalid_schema() {
+        let credential_schema_json = CString::new(r#"{"name":"age"}"#).unwrap();
+
+        let mut credential_pub_key_json_p: *const c_char = ptr::null();
+        let mut credential_priv_key_json_p: *const c_char = ptr::null();
+        let mut credential_key_correctness_proof_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_issuer_new_credential_def_json(credential_schema_json.as_ptr(),
+                                                                     false,
+                                                                     &mut credential_pub_key_json_p,
+                                                                     &mut credential_priv_key_json_p,
+                                                                     &mut credential_key_correctness_proof_json_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+    }
+}
@@ -2,12 +2,14 @@ use cl::prover::*;
 use cl::*;
 use errors::ToErrorCode;
 use ffi::ErrorCode;
+use ffi::handles::{Handle, Handles};
 use utils::ctypes::CTypesUtils;
 use utils::json::{JsonEncodable, JsonDecodable};
 
 use libc::c_char;
 
-use std::os::raw::c_void;
+use std::slice;
+
 
 /// Creates a master secret.
 ///
@@ -15,59 +17,63 @@ use std::os::raw::c_void;
 /// calling indy_crypto_cl_master_secret_free.
 ///
 /// # Arguments
-/// * `master_secret_p` - Reference that will contain master secret instance pointer.
+/// * `master_secret_p` - Reference that will contain master secret instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_prover_new_master_secret(master_secret_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_new_master_secret: >>> {:?}", master_secret_p);
-
-    check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Prover::new_master_secret() {
-        Ok(master_secret) => {
-            trace!("indy_crypto_cl_prover_new_master_secret: master_secret: {:?}", master_secret);
-            unsafe {
-                *master_secret_p = Box::into_raw(Box::new(master_secret)) as *const c_void;
-                trace!("indy_crypto_cl_prover_new_master_secret: *master_secret_p: {:?}", *master_secret_p);
+pub extern fn indy_crypto_cl_prover_new_master_secret(master_secret_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_new_master_secret: >>> {:?}", master_secret_p);
+
+        check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Prover::new_master_secret() {
+            Ok(master_secret) => {
+                trace!("indy_crypto_cl_prover_new_master_secret: master_secret: {:?}", master_secret);
+                unsafe {
+                    *master_secret_p = Handles::insert(master_secret);
+                    trace!("indy_crypto_cl_prover_new_master_secret: *master_secret_p: {:?}", *master_secret_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_prover_new_master_secret: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_new_master_secret: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns json representation of master secret.
 ///
 /// # Arguments
-/// * `master_secret` - Reference that contains master secret instance pointer.
+/// * `master_secret` - Reference that contains master secret instance handle.
 /// * `master_secret_json_p` - Reference that will contain master secret json.
 #[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_to_json(master_secret: *const c_void,
+pub extern fn indy_crypto_cl_master_secret_to_json(master_secret: Handle,
                                                    master_secret_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_to_json: >>> master_secret: {:?}, master_secret_json_p: {:?}", master_secret, master_secret_json_p);
-
-    check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_to_json: entity >>> master_secret: {:?}", master_secret);
-
-    let res = match master_secret.to_json() {
-        Ok(master_secret_json) => {
-            trace!("indy_crypto_cl_master_secret_to_json: master_secret_json: {:?}", master_secret_json);
-            unsafe {
-                let master_secret_json = CTypesUtils::string_to_cstring(master_secret_json);
-                *master_secret_json_p = master_secret_json.into_raw();
-                trace!("indy_crypto_cl_master_secret_to_json: master_secret_json_p: {:?}", *master_secret_json_p);
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_to_json: >>> master_secret: {:?}, master_secret_json_p: {:?}", master_secret, master_secret_json_p);
+
+        check_useful_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_to_json: entity >>> master_secret: {:?}", master_secret);
+
+        let res = match master_secret.to_json() {
+            Ok(master_secret_json) => {
+                trace!("indy_crypto_cl_master_secret_to_json: master_secret_json: {:?}", master_secret_json);
+                unsafe {
+                    let master_secret_json = CTypesUtils::string_to_cstring(master_secret_json);
+                    *master_secret_json_p = master_secret_json.into_raw();
+                    trace!("indy_crypto_cl_master_secret_to_json: master_secret_json_p: {:?}", *master_secret_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_master_secret_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_to_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns master secret from json.
@@ -77,50 +83,52 @@ pub extern fn indy_crypto_cl_master_secret_to_json(master_secret: *const c_void,
 ///
 /// # Arguments
 /// * `master_secret_json` - Reference that contains master secret json.
-/// * `master_secret_p` - Reference that will contain master secret instance pointer.
+/// * `master_secret_p` - Reference that will contain master secret instance handle.
 #[no_mangle]
 pub extern fn indy_crypto_cl_master_secret_from_json(master_secret_json: *const c_char,
-                                                     master_secret_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_from_json: >>> master_secret_json: {:?}, master_secret_p: {:?}", master_secret_json, master_secret_p);
-
-    check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_from_json: entity: master_secret_json: {:?}", master_secret_json);
-
-    let res = match MasterSecret::from_json(&master_secret_json) {
-        Ok(master_secret) => {
-            trace!("indy_crypto_cl_master_secret_from_json: master_secret: {:?}", master_secret);
-            unsafe {
-                *master_secret_p = Box::into_raw(Box::new(master_secret)) as *const c_void;
-                trace!("indy_crypto_cl_master_secret_from_json: *master_secret_p: {:?}", *master_secret_p);
+                                                     master_secret_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_from_json: >>> master_secret_json: {:?}, master_secret_p: {:?}", master_secret_json, master_secret_p);
+
+        check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_from_json: entity: master_secret_json: {:?}", master_secret_json);
+
+        let res = match MasterSecret::from_json(&master_secret_json) {
+            Ok(master_secret) => {
+                trace!("indy_crypto_cl_master_secret_from_json: master_secret: {:?}", master_secret);
+                unsafe {
+                    *master_secret_p = Handles::insert(master_secret);
+                    trace!("indy_crypto_cl_master_secret_from_json: *master_secret_p: {:?}", *master_secret_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_master_secret_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_from_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates master secret instance.
 ///
 /// # Arguments
-/// * `master_secret` - Reference that contains master secret instance pointer.
+/// * `master_secret` - Reference that contains master secret instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_free(master_secret: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_free: >>> master_secret: {:?}", master_secret);
+pub extern fn indy_crypto_cl_master_secret_free(master_secret: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_free: >>> master_secret: {:?}", master_secret);
 
-    check_useful_c_ptr!(master_secret, ErrorCode::CommonInvalidParam1);
+        check_useful_removed_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_master_secret_free: entity: master_secret: {:?}", master_secret);
 
-    let master_secret = unsafe { Box::from_raw(master_secret as *mut MasterSecret); };
-    trace!("indy_crypto_cl_master_secret_free: entity: master_secret: {:?}", master_secret);
+        let res = ErrorCode::Success;
+        trace!("indy_crypto_cl_master_secret_free: <<< res: {:?}", res);
 
-    let res = ErrorCode::Success;
-    trace!("indy_crypto_cl_master_secret_free: <<< res: {:?}", res);
-
-    res
+        res
+    })
 }
 
 /// Creates blinded master secret for given issuer key and master secret.
@@ -135,56 +143,58 @@ pub extern fn indy_crypto_cl_master_secret_free(master_secret: *const c_void) ->
 /// calling indy_crypto_cl_blinded_master_secret_correctness_proof_free.
 ///
 /// # Arguments
-/// * `credential_pub_key` - Reference that contains credential public key instance pointer.
-/// * `credential_key_correctness_proof` - Reference that contains credential key correctness proof instance pointer.
-/// * `master_secret` - Reference that contains master secret instance pointer.
-/// * `master_secret_blinding_nonce` - Reference that contains nonce instance pointer.
-/// * `blinded_master_secret_p` - Reference that will contain blinded master secret instance pointer.
-/// * `master_secret_blinding_data_p` - Reference that will contain master secret blinding data instance pointer.
-/// * `blinded_master_secret_correctness_proof_p` - Reference that will contain blinded master secret correctness proof instance pointer.
+/// * `credential_pub_key` - Reference that contains credential public key instance handle.
+/// * `credential_key_correctness_proof` - Reference that contains credential key correctness proof instance handle.
+/// * `master_secret` - Reference that contains master secret instance handle.
+/// * `master_secret_blinding_nonce` - Reference that contains nonce instance handle.
+/// * `blinded_master_secret_p` - Reference that will contain blinded master secret instance handle.
+/// * `master_secret_blinding_data_p` - Reference that will contain master secret blinding data instance handle.
+/// * `blinded_master_secret_correctness_proof_p` - Reference that will contain blinded master secret correctness proof instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_prover_blind_master_secret(credential_pub_key: *const c_void,
-                                                        credential_key_correctness_proof: *const c_void,
-                                                        master_secret: *const c_void,
-                                                        master_secret_blinding_nonce: *const c_void,
-                                                        blinded_master_secret_p: *mut *const c_void,
-                                                        master_secret_blinding_data_p: *mut *const c_void,
-                                                        blinded_master_secret_correctness_proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_blind_master_secret: >>> credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, master_secret: {:?}, \
-    master_secret_blinding_nonce: {:?}, blinded_master_secret_p: {:?}, master_secret_blinding_data_p: {:?}, blinded_master_secret_correctness_proof_p: {:?}",
-           credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce, blinded_master_secret_p,
-           master_secret_blinding_data_p, blinded_master_secret_correctness_proof_p);
-
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(credential_key_correctness_proof, CredentialKeyCorrectnessProof, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(master_secret_blinding_nonce, Nonce, ErrorCode::CommonInvalidParam4);
-    check_useful_c_ptr!(blinded_master_secret_p, ErrorCode::CommonInvalidParam5);
-    check_useful_c_ptr!(master_secret_blinding_data_p, ErrorCode::CommonInvalidParam6);
-    check_useful_c_ptr!(blinded_master_secret_correctness_proof_p, ErrorCode::CommonInvalidParam7);
-
-    trace!("indy_crypto_cl_prover_blind_master_secret: entities: credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, master_secret: {:?}, \
-    master_secret_blinding_nonce: {:?}", credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce);
-
-    let res = match Prover::blind_master_secret(credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce) {
-        Ok((blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof)) => {
-            trace!("indy_crypto_cl_prover_blind_master_secret: blinded_master_secret: {:?}, master_secret_blinding_data: {:?}, \
-            blinded_master_secret_correctness_proof: {:?}", blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
-            unsafe {
-                *blinded_master_secret_p = Box::into_raw(Box::new(blinded_master_secret)) as *const c_void;
-                *master_secret_blinding_data_p = Box::into_raw(Box::new(master_secret_blinding_data)) as *const c_void;
-                *blinded_master_secret_correctness_proof_p = Box::into_raw(Box::new(blinded_master_secret_correctness_proof)) as *const c_void;
-                trace!("indy_crypto_cl_prover_blind_master_secret: *blinded_master_secret_p: {:?}, *master_secret_blinding_data_p: {:?}, \
-                *blinded_master_secret_correctness_proof_p: {:?}",
-                       *blinded_master_secret_p, *master_secret_blinding_data_p, *blinded_master_secret_correctness_proof_p);
+pub extern fn indy_crypto_cl_prover_blind_master_secret(credential_pub_key: Handle,
+                                                        credential_key_correctness_proof: Handle,
+                                                        master_secret: Handle,
+                                                        master_secret_blinding_nonce: Handle,
+                                                        blinded_master_secret_p: *mut Handle,
+                                                        master_secret_blinding_data_p: *mut Handle,
+                                                        blinded_master_secret_correctness_proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_blind_master_secret: >>> credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, master_secret: {:?}, \
+        master_secret_blinding_nonce: {:?}, blinded_master_secret_p: {:?}, master_secret_blinding_data_p: {:?}, blinded_master_secret_correctness_proof_p: {:?}",
+               credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce, blinded_master_secret_p,
+               master_secret_blinding_data_p, blinded_master_secret_correctness_proof_p);
+
+        check_useful_handle!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(credential_key_correctness_proof, CredentialKeyCorrectnessProof, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(master_secret_blinding_nonce, Nonce, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(blinded_master_secret_p, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(master_secret_blinding_data_p, ErrorCode::CommonInvalidParam6);
+        check_useful_c_ptr!(blinded_master_secret_correctness_proof_p, ErrorCode::CommonInvalidParam7);
+
+        trace!("indy_crypto_cl_prover_blind_master_secret: entities: credential_pub_key: {:?}, credential_key_correctness_proof: {:?}, master_secret: {:?}, \
+        master_secret_blinding_nonce: {:?}", credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce);
+
+        let res = match Prover::blind_master_secret(credential_pub_key, credential_key_correctness_proof, master_secret, master_secret_blinding_nonce) {
+            Ok((blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof)) => {
+                trace!("indy_crypto_cl_prover_blind_master_secret: blinded_master_secret: {:?}, master_secret_blinding_data: {:?}, \
+                blinded_master_secret_correctness_proof: {:?}", blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
+                unsafe {
+                    *blinded_master_secret_p = Handles::insert(blinded_master_secret);
+                    *master_secret_blinding_data_p = Handles::insert(master_secret_blinding_data);
+                    *blinded_master_secret_correctness_proof_p = Handles::insert(blinded_master_secret_correctness_proof);
+                    trace!("indy_crypto_cl_prover_blind_master_secret: *blinded_master_secret_p: {:?}, *master_secret_blinding_data_p: {:?}, \
+                    *blinded_master_secret_correctness_proof_p: {:?}",
+                           *blinded_master_secret_p, *master_secret_blinding_data_p, *blinded_master_secret_correctness_proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_prover_blind_master_secret: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_blind_master_secret: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns json representation of blinded master secret.
@@ -193,31 +203,33 @@ pub extern fn indy_crypto_cl_prover_blind_master_secret(credential_pub_key: *con
 /// * `blinded_master_secret` - Reference that contains Blinded master secret pointer.
 /// * `blinded_master_secret_json_p` - Reference that will contain blinded master secret json.
 #[no_mangle]
-pub extern fn indy_crypto_cl_blinded_master_secret_to_json(blinded_master_secret: *const c_void,
+pub extern fn indy_crypto_cl_blinded_master_secret_to_json(blinded_master_secret: Handle,
                                                            blinded_master_secret_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_to_json: >>> blinded_master_secret: {:?}, blinded_master_secret_json_p: {:?}", blinded_master_secret, blinded_master_secret_json_p);
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_to_json: >>> blinded_master_secret: {:?}, blinded_master_secret_json_p: {:?}", blinded_master_secret, blinded_master_secret_json_p);
 
-    check_useful_c_reference!(blinded_master_secret, BlindedMasterSecret, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_master_secret_json_p, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(blinded_master_secret, BlindedMasterSecret, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_master_secret_json_p, ErrorCode::CommonInvalidParam2);
 
-    trace!("indy_crypto_cl_blinded_master_secret_to_json: entity >>> blinded_master_secret: {:?}", blinded_master_secret);
+        trace!("indy_crypto_cl_blinded_master_secret_to_json: entity >>> blinded_master_secret: {:?}", blinded_master_secret);
 
-    let res = match blinded_master_secret.to_json() {
-        Ok(blinded_master_secret_json) => {
-            trace!("indy_crypto_cl_blinded_master_secret_to_json: blinded_master_secret_json: {:?}", blinded_master_secret_json);
-            unsafe {
-                let blinded_master_secret_json = CTypesUtils::string_to_cstring(blinded_master_secret_json);
-                *blinded_master_secret_json_p = blinded_master_secret_json.into_raw();
+        let res = match blinded_master_secret.to_json() {
+            Ok(blinded_master_secret_json) => {
+                trace!("indy_crypto_cl_blinded_master_secret_to_json: blinded_master_secret_json: {:?}", blinded_master_secret_json);
+                unsafe {
+                    let blinded_master_secret_json = CTypesUtils::string_to_cstring(blinded_master_secret_json);
+                    *blinded_master_secret_json_p = blinded_master_secret_json.into_raw();
 
-                trace!("indy_crypto_cl_blinded_master_secret_to_json: blinded_master_secret_json_p: {:?}", *blinded_master_secret_json_p);
+                    trace!("indy_crypto_cl_blinded_master_secret_to_json: blinded_master_secret_json_p: {:?}", *blinded_master_secret_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_blinded_master_secret_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_to_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns blinded master secret from json.
@@ -227,50 +239,52 @@ pub extern fn indy_crypto_cl_blinded_master_secret_to_json(blinded_master_secret
 ///
 /// # Arguments
 /// * `blinded_master_secret_json` - Reference that contains blinded master secret json.
-/// * `blinded_master_secret_p` - Reference that will contain blinded master secret instance pointer.
+/// * `blinded_master_secret_p` - Reference that will contain blinded master secret instance handle.
 #[no_mangle]
 pub extern fn indy_crypto_cl_blinded_master_secret_from_json(blinded_master_secret_json: *const c_char,
-                                                             blinded_master_secret_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_from_json: >>> blinded_master_secret_json: {:?}, blinded_master_secret_p: {:?}", blinded_master_secret_json, blinded_master_secret_p);
-
-    check_useful_c_str!(blinded_master_secret_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_master_secret_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_master_secret_from_json: entity: blinded_master_secret_json: {:?}", blinded_master_secret_json);
-
-    let res = match BlindedMasterSecret::from_json(&blinded_master_secret_json) {
-        Ok(blinded_master_secret) => {
-            trace!("indy_crypto_cl_blinded_master_secret_from_json: blinded_master_secret: {:?}", blinded_master_secret);
-            unsafe {
-                *blinded_master_secret_p = Box::into_raw(Box::new(blinded_master_secret)) as *const c_void;
-                trace!("indy_crypto_cl_blinded_master_secret_from_json: *blinded_master_secret_p: {:?}", *blinded_master_secret_p);
+                                                             blinded_master_secret_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_from_json: >>> blinded_master_secret_json: {:?}, blinded_master_secret_p: {:?}", blinded_master_secret_json, blinded_master_secret_p);
+
+        check_useful_c_str!(blinded_master_secret_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_master_secret_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_master_secret_from_json: entity: blinded_master_secret_json: {:?}", blinded_master_secret_json);
+
+        let res = match BlindedMasterSecret::from_json(&blinded_master_secret_json) {
+            Ok(blinded_master_secret) => {
+                trace!("indy_crypto_cl_blinded_master_secret_from_json: blinded_master_secret: {:?}", blinded_master_secret);
+                unsafe {
+                    *blinded_master_secret_p = Handles::insert(blinded_master_secret);
+                    trace!("indy_crypto_cl_blinded_master_secret_from_json: *blinded_master_secret_p: {:?}", *blinded_master_secret_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_blinded_master_secret_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_from_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates  blinded master secret instance.
 ///
 /// # Arguments
-/// * `blinded_master_secret` - Reference that contains blinded master secret instance pointer.
+/// * `blinded_master_secret` - Reference that contains blinded master secret instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_blinded_master_secret_free(blinded_master_secret: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_free: >>> blinded_master_secret: {:?}", blinded_master_secret);
-
-    check_useful_c_ptr!(blinded_master_secret, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_cl_blinded_master_secret_free(blinded_master_secret: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_free: >>> blinded_master_secret: {:?}", blinded_master_secret);
 
-    let blinded_master_secret = unsafe { Box::from_raw(blinded_master_secret as *mut MasterSecret); };
-    trace!("indy_crypto_cl_master_secret_free: entity: blinded_master_secret: {:?}", blinded_master_secret);
+        check_useful_removed_handle!(blinded_master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_master_secret_free: entity: blinded_master_secret: {:?}", blinded_master_secret);
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_cl_blinded_master_secret_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns json representation of master secret blinding data.
@@ -279,30 +293,32 @@ pub extern fn indy_crypto_cl_blinded_master_secret_free(blinded_master_secret: *
 /// * `master_secret_blinding_data` - Reference that contains master secret blinding data pointer.
 /// * `master_secret_blinding_data_json_p` - Reference that will contain master secret blinding data json.
 #[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_blinding_data_to_json(master_secret_blinding_data: *const c_void,
+pub extern fn indy_crypto_cl_master_secret_blinding_data_to_json(master_secret_blinding_data: Handle,
                                                                  master_secret_blinding_data_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_blinding_data_to_json: >>> master_secret_blinding_data: {:?}, master_secret_blinding_data_json_p: {:?}", master_secret_blinding_data, master_secret_blinding_data_json_p);
-
-    check_useful_c_reference!(master_secret_blinding_data, MasterSecretBlindingData, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_blinding_data_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_blinding_data_to_json: entity >>> master_secret_blinding_data: {:?}", master_secret_blinding_data);
-
-    let res = match master_secret_blinding_data.to_json() {
-        Ok(master_secret_blinding_data_json) => {
-            trace!("indy_crypto_cl_master_secret_blinding_data_to_json: master_secret_blinding_data_json: {:?}", master_secret_blinding_data_json);
-            unsafe {
-                let master_secret_blinding_data_json = CTypesUtils::string_to_cstring(master_secret_blinding_data_json);
-                *master_secret_blinding_data_json_p = master_secret_blinding_data_json.into_raw();
-                trace!("indy_crypto_cl_master_secret_blinding_data_to_json: master_secret_blinding_data_json_p: {:?}", *master_secret_blinding_data_json_p);
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_blinding_data_to_json: >>> master_secret_blinding_data: {:?}, master_secret_blinding_data_json_p: {:?}", master_secret_blinding_data, master_secret_blinding_data_json_p);
+
+        check_useful_handle!(master_secret_blinding_data, MasterSecretBlindingData, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_blinding_data_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_blinding_data_to_json: entity >>> master_secret_blinding_data: {:?}", master_secret_blinding_data);
+
+        let res = match master_secret_blinding_data.to_json() {
+            Ok(master_secret_blinding_data_json) => {
+                trace!("indy_crypto_cl_master_secret_blinding_data_to_json: master_secret_blinding_data_json: {:?}", master_secret_blinding_data_json);
+                unsafe {
+                    let master_secret_blinding_data_json = CTypesUtils::string_to_cstring(master_secret_blinding_data_json);
+                    *master_secret_blinding_data_json_p = master_secret_blinding_data_json.into_raw();
+                    trace!("indy_crypto_cl_master_secret_blinding_data_to_json: master_secret_blinding_data_json_p: {:?}", *master_secret_blinding_data_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_blinded_master_secret_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_to_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns master secret blinding data json.
@@ -312,50 +328,52 @@ pub extern fn indy_crypto_cl_master_secret_blinding_data_to_json(master_secret_b
 ///
 /// # Arguments
 /// * `master_secret_blinding_data_json` - Reference that contains master secret blinding data json.
-/// * `blinded_master_secret_p` - Reference that will contain master secret blinding data instance pointer.
+/// * `blinded_master_secret_p` - Reference that will contain master secret blinding data instance handle.
 #[no_mangle]
 pub extern fn indy_crypto_cl_master_secret_blinding_data_from_json(master_secret_blinding_data_json: *const c_char,
-                                                                   master_secret_blinding_data_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_blinding_data_from_json: >>> master_secret_blinding_data_json: {:?}, blinded_master_secret_p: {:?}", master_secret_blinding_data_json, master_secret_blinding_data_p);
-
-    check_useful_c_str!(master_secret_blinding_data_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_blinding_data_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_blinding_data_from_json: entity: master_secret_blinding_data_json: {:?}", master_secret_blinding_data_json);
-
-    let res = match MasterSecretBlindingData::from_json(&master_secret_blinding_data_json) {
-        Ok(master_secret_blinding_data) => {
-            trace!("indy_crypto_cl_master_secret_blinding_data_from_json: master_secret_blinding_data: {:?}", master_secret_blinding_data);
-            unsafe {
-                *master_secret_blinding_data_p = Box::into_raw(Box::new(master_secret_blinding_data)) as *const c_void;
-                trace!("indy_crypto_cl_master_secret_blinding_data_from_json: *blinded_master_secret_p: {:?}", *master_secret_blinding_data_p);
+                                                                   master_secret_blinding_data_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_blinding_data_from_json: >>> master_secret_blinding_data_json: {:?}, blinded_master_secret_p: {:?}", master_secret_blinding_data_json, master_secret_blinding_data_p);
+
+        check_useful_c_str!(master_secret_blinding_data_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_blinding_data_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_blinding_data_from_json: entity: master_secret_blinding_data_json: {:?}", master_secret_blinding_data_json);
+
+        let res = match MasterSecretBlindingData::from_json(&master_secret_blinding_data_json) {
+            Ok(master_secret_blinding_data) => {
+                trace!("indy_crypto_cl_master_secret_blinding_data_from_json: master_secret_blinding_data: {:?}", master_secret_blinding_data);
+                unsafe {
+                    *master_secret_blinding_data_p = Handles::insert(master_secret_blinding_data);
+                    trace!("indy_crypto_cl_master_secret_blinding_data_from_json: *blinded_master_secret_p: {:?}", *master_secret_blinding_data_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_master_secret_blinding_data_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_blinding_data_from_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates master secret blinding data instance.
 ///
 /// # Arguments
-/// * `master_secret_blinding_data` - Reference that contains master secret  blinding data instance pointer.
+/// * `master_secret_blinding_data` - Reference that contains master secret  blinding data instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_blinding_data_free(master_secret_blinding_data: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_blinding_data_free: >>> master_secret_blinding_data: {:?}", master_secret_blinding_data);
-
-    check_useful_c_ptr!(master_secret_blinding_data, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_cl_master_secret_blinding_data_free(master_secret_blinding_data: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_master_secret_blinding_data_free: >>> master_secret_blinding_data: {:?}", master_secret_blinding_data);
 
-    let master_secret_blinding_data = unsafe { Box::from_raw(master_secret_blinding_data as *mut MasterSecretBlindingData); };
-    trace!("indy_crypto_cl_master_secret_blinding_data_free: entity: master_secret_blinding_data: {:?}", master_secret_blinding_data);
+        check_useful_removed_handle!(master_secret_blinding_data, MasterSecretBlindingData, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_master_secret_blinding_data_free: entity: master_secret_blinding_data: {:?}", master_secret_blinding_data);
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_cl_master_secret_blinding_data_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_blinding_data_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Returns json representation of blinded master secret correctness proof.
@@ -364,34 +382,36 @@ pub extern fn indy_crypto_cl_master_secret_blinding_data_free(master_secret_blin
 /// * `blinded_master_secret_correctness_proof` - Reference that contains blinded master_secret correctness proof pointer.
 /// * `blinded_master_secret_correctness_proof_json_p` - Reference that will contain blinded master secret correctness proof json.
 #[no_mangle]
-pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_to_json(blinded_master_secret_correctness_proof: *const c_void,
+pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_to_json(blinded_master_secret_correctness_proof: Handle,
                                                                              blinded_master_secret_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: >>> blinded_master_secret_correctness_proof: {:?},\
-     blinded_master_secret_correctness_proof_json_p: {:?}", blinded_master_secret_correctness_proof, blinded_master_secret_correctness_proof_json_p);
-
-    check_useful_c_reference!(blinded_master_secret_correctness_proof, BlindedMasterSecretCorrectnessProof, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_master_secret_correctness_proof_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: entity >>> blinded_master_secret_correctness_proof: {:?}",
-           blinded_master_secret_correctness_proof);
-
-    let res = match blinded_master_secret_correctness_proof.to_json() {
-        Ok(blinded_master_secret_correctness_proof_json) => {
-            trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: blinded_master_secret_correctness_proof: {:?}",
-                   blinded_master_secret_correctness_proof_json);
-            unsafe {
-                let blinded_master_secret_correctness_proof_json = CTypesUtils::string_to_cstring(blinded_master_secret_correctness_proof_json);
-                *blinded_master_secret_correctness_proof_json_p = blinded_master_secret_correctness_proof_json.into_raw();
-                trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: blinded_master_secret_correctness_proof_json_p: {:?}",
-                       *blinded_master_secret_correctness_proof_json_p);
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: >>> blinded_master_secret_correctness_proof: {:?},\
+         blinded_master_secret_correctness_proof_json_p: {:?}", blinded_master_secret_correctness_proof, blinded_master_secret_correctness_proof_json_p);
+
+        check_useful_handle!(blinded_master_secret_correctness_proof, BlindedMasterSecretCorrectnessProof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_master_secret_correctness_proof_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: entity >>> blinded_master_secret_correctness_proof: {:?}",
+               blinded_master_secret_correctness_proof);
+
+        let res = match blinded_master_secret_correctness_proof.to_json() {
+            Ok(blinded_master_secret_correctness_proof_json) => {
+                trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: blinded_master_secret_correctness_proof: {:?}",
+                       blinded_master_secret_correctness_proof_json);
+                unsafe {
+                    let blinded_master_secret_correctness_proof_json = CTypesUtils::string_to_cstring(blinded_master_secret_correctness_proof_json);
+                    *blinded_master_secret_correctness_proof_json_p = blinded_master_secret_correctness_proof_json.into_raw();
+                    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: blinded_master_secret_correctness_proof_json_p: {:?}",
+                           *blinded_master_secret_correctness_proof_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_to_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns blinded master secret correctness proof json.
@@ -401,117 +421,122 @@ pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_to_json(bli
 ///
 /// # Arguments
 /// * `blinded_master_secret_correctness_proof_json` - Reference that contains blinded master secret correctness proof json.
-/// * `blinded_master_secret_correctness_proof_p` - Reference that will contain blinded master secret correctness proof instance pointer.
+/// * `blinded_master_secret_correctness_proof_p` - Reference that will contain blinded master secret correctness proof instance handle.
 #[no_mangle]
 pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_from_json(blinded_master_secret_correctness_proof_json: *const c_char,
-                                                                               blinded_master_secret_correctness_proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: >>> blinded_master_secret_correctness_proof_json: {:?},\
-     blinded_master_secret_correctness_proof_p: {:?}", blinded_master_secret_correctness_proof_json, blinded_master_secret_correctness_proof_p);
-
-    check_useful_c_str!(blinded_master_secret_correctness_proof_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_master_secret_correctness_proof_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: entity: blinded_master_secret_correctness_proof_json: {:?}",
-           blinded_master_secret_correctness_proof_json);
-
-    let res = match BlindedMasterSecretCorrectnessProof::from_json(&blinded_master_secret_correctness_proof_json) {
-        Ok(blinded_master_secret_correctness_proof) => {
-            trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: blinded_master_secret_correctness_proof: {:?}",
-                   blinded_master_secret_correctness_proof);
-            unsafe {
-                *blinded_master_secret_correctness_proof_p = Box::into_raw(Box::new(blinded_master_secret_correctness_proof)) as *const c_void;
-                trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: *blinded_master_secret_correctness_proof_p: {:?}",
-                       *blinded_master_secret_correctness_proof_p);
+                                                                               blinded_master_secret_correctness_proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: >>> blinded_master_secret_correctness_proof_json: {:?},\
+         blinded_master_secret_correctness_proof_p: {:?}", blinded_master_secret_correctness_proof_json, blinded_master_secret_correctness_proof_p);
+
+        check_useful_c_str!(blinded_master_secret_correctness_proof_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_master_secret_correctness_proof_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: entity: blinded_master_secret_correctness_proof_json: {:?}",
+               blinded_master_secret_correctness_proof_json);
+
+        let res = match BlindedMasterSecretCorrectnessProof::from_json(&blinded_master_secret_correctness_proof_json) {
+            Ok(blinded_master_secret_correctness_proof) => {
+                trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: blinded_master_secret_correctness_proof: {:?}",
+                       blinded_master_secret_correctness_proof);
+                unsafe {
+                    *blinded_master_secret_correctness_proof_p = Handles::insert(blinded_master_secret_correctness_proof);
+                    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: *blinded_master_secret_correctness_proof_p: {:?}",
+                           *blinded_master_secret_correctness_proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_from_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates blinded master secret correctness proof instance.
 ///
 /// # Arguments
-/// * `blinded_master_secret_correctness_proof` - Reference that contains blinded master secret correctness proof instance pointer.
+/// * `blinded_master_secret_correctness_proof` - Reference that contains blinded master secret correctness proof instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_free(blinded_master_secret_correctness_proof: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: >>> blinded_master_secret_correctness_proof: {:?}",
-           blinded_master_secret_correctness_proof);
+pub extern fn indy_crypto_cl_blinded_master_secret_correctness_proof_free(blinded_master_secret_correctness_proof: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: >>> blinded_master_secret_correctness_proof: {:?}",
+               blinded_master_secret_correctness_proof);
 
-    check_useful_c_ptr!(blinded_master_secret_correctness_proof, ErrorCode::CommonInvalidParam1);
 
-    let blinded_master_secret_correctness_proof = unsafe { Box::from_raw(blinded_master_secret_correctness_proof as *mut BlindedMasterSecretCorrectnessProof); };
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: entity: blinded_master_secret_correctness_proof: {:?}", blinded_master_secret_correctness_proof);
+        check_useful_removed_handle!(blinded_master_secret_correctness_proof, BlindedMasterSecretCorrectnessProof, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: entity: blinded_master_secret_correctness_proof: {:?}", blinded_master_secret_correctness_proof);
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_master_secret_correctness_proof_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Updates the credential signature by a master secret blinding data.
 ///
 /// # Arguments
-/// * `credential_signature` - Credential signature instance pointer generated by Issuer.
-/// * `credential_values` - Credential values instance pointer.
-/// * `signature_correctness_proof` - Credential signature correctness proof instance pointer.
-/// * `master_secret_blinding_data` - Master secret blinding data instance pointer.
-/// * `master_secret` - Master secret instance pointer.
-/// * `credential_pub_key` - Credential public key instance pointer.
-/// * `nonce` -  Nonce instance pointer was used by Issuer for the creation of signature_correctness_proof.
-/// * `rev_key_pub` - (Optional) Revocation registry public key  instance pointer.
-/// * `rev_reg` - (Optional) Revocation registry  instance pointer.
-/// * `witness` - (Optional) Witness instance pointer.
+/// * `credential_signature` - Credential signature instance handle generated by Issuer.
+/// * `credential_values` - Credential values instance handle.
+/// * `signature_correctness_proof` - Credential signature correctness proof instance handle.
+/// * `master_secret_blinding_data` - Master secret blinding data instance handle.
+/// * `master_secret` - Master secret instance handle.
+/// * `credential_pub_key` - Credential public key instance handle.
+/// * `nonce` -  Nonce instance handle was used by Issuer for the creation of signature_correctness_proof.
+/// * `rev_key_pub` - (Optional) Revocation registry public key  instance handle.
+/// * `rev_reg` - (Optional) Revocation registry  instance handle.
+/// * `witness` - (Optional) Witness instance handle.
 #[no_mangle]
 #[allow(unused_variables)]
-pub extern fn indy_crypto_cl_prover_process_credential_signature(credential_signature: *const c_void,
-                                                                 credential_values: *const c_void,
-                                                                 signature_correctness_proof: *const c_void,
-                                                                 master_secret_blinding_data: *const c_void,
-                                                                 master_secret: *const c_void,
-                                                                 credential_pub_key: *const c_void,
-                                                                 credential_issuance_nonce: *const c_void,
-                                                                 rev_key_pub: *const c_void,
-                                                                 rev_reg: *const c_void,
-                                                                 witness: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}, signature_correctness_proof: {:?}, master_secret_blinding_data: {:?}, \
-        master_secret: {:?}, credential_pub_key: {:?}, credential_issuance_nonce: {:?}, rev_key_pub: {:?}, rev_reg {:?}, witness {:?}",
-           credential_signature, signature_correctness_proof, master_secret_blinding_data, master_secret, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
-
-    check_useful_mut_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(signature_correctness_proof, SignatureCorrectnessProof, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(master_secret_blinding_data, MasterSecretBlindingData, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(credential_issuance_nonce, Nonce, ErrorCode::CommonInvalidParam6);
-    check_useful_opt_c_reference!(rev_key_pub, RevocationKeyPublic);
-    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
-    check_useful_opt_c_reference!(witness, Witness);
-
-    trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}, credential_values: {:?}, signature_correctness_proof: {:?}, \
-        master_secret: {:?}, credential_pub_key: {:?}, credential_issuance_nonce: {:?}, rev_key_pub: {:?}, rev_reg {:?}, witness {:?}",
-           credential_signature, signature_correctness_proof, master_secret_blinding_data, master_secret, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
-
-    let res = match Prover::process_credential_signature(credential_signature,
-                                                         credential_values,
-                                                         signature_correctness_proof,
-                                                         master_secret_blinding_data,
-                                                         master_secret,
-                                                         credential_pub_key,
-                                                         credential_issuance_nonce,
-                                                         rev_key_pub,
-                                                         rev_reg,
-                                                         witness) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_prover_process_credential_signature: <<< res: {:?}", res);
-    ErrorCode::Success
+pub extern fn indy_crypto_cl_prover_process_credential_signature(credential_signature: Handle,
+                                                                 credential_values: Handle,
+                                                                 signature_correctness_proof: Handle,
+                                                                 master_secret_blinding_data: Handle,
+                                                                 master_secret: Handle,
+                                                                 credential_pub_key: Handle,
+                                                                 credential_issuance_nonce: Handle,
+                                                                 rev_key_pub: Handle,
+                                                                 rev_reg: Handle,
+                                                                 witness: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}, signature_correctness_proof: {:?}, master_secret_blinding_data: {:?}, \
+            master_secret: {:?}, credential_pub_key: {:?}, credential_issuance_nonce: {:?}, rev_key_pub: {:?}, rev_reg {:?}, witness {:?}",
+               credential_signature, signature_correctness_proof, master_secret_blinding_data, master_secret, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
+
+        check_useful_mut_handle!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(signature_correctness_proof, SignatureCorrectnessProof, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(master_secret_blinding_data, MasterSecretBlindingData, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam4);
+        check_useful_handle!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam5);
+        check_useful_handle!(credential_issuance_nonce, Nonce, ErrorCode::CommonInvalidParam6);
+        check_useful_opt_handle!(rev_key_pub, RevocationKeyPublic, ErrorCode::CommonInvalidParam8);
+        check_useful_opt_handle!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam9);
+        check_useful_opt_handle!(witness, Witness, ErrorCode::CommonInvalidParam10);
+
+        trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}, credential_values: {:?}, signature_correctness_proof: {:?}, \
+            master_secret: {:?}, credential_pub_key: {:?}, credential_issuance_nonce: {:?}, rev_key_pub: {:?}, rev_reg {:?}, witness {:?}",
+               credential_signature, signature_correctness_proof, master_secret_blinding_data, master_secret, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
+
+        let res = match Prover::process_credential_signature(credential_signature,
+                                                             credential_values,
+                                                             signature_correctness_proof,
+                                                             master_secret_blinding_data,
+                                                             master_secret,
+                                                             credential_pub_key,
+                                                             credential_issuance_nonce,
+                                                             rev_key_pub,
+                                                             rev_reg,
+                                                             witness) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_prover_process_credential_signature: <<< res: {:?}", res);
+        ErrorCode::Success
+    })
 }
 
 /// Creates and returns proof builder.
@@ -522,68 +547,72 @@ pub extern fn indy_crypto_cl_prover_process_credential_signature(credential_sign
 /// calling indy_crypto_cl_proof_builder_finalize.
 ///
 /// # Arguments
-/// * `proof_builder_p` - Reference that will contain proof builder instance pointer.
+/// * `proof_builder_p` - Reference that will contain proof builder instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_prover_new_proof_builder(proof_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_new_proof_builder: >>> {:?}", proof_builder_p);
-
-    check_useful_c_ptr!(proof_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Prover::new_proof_builder() {
-        Ok(proof_builder) => {
-            trace!("indy_crypto_cl_prover_new_proof_builder: proof_builder: {:?}", proof_builder);
-            unsafe {
-                *proof_builder_p = Box::into_raw(Box::new(proof_builder)) as *const c_void;
-                trace!("indy_crypto_cl_prover_new_proof_builder: *proof_builder_p: {:?}", *proof_builder_p);
+pub extern fn indy_crypto_cl_prover_new_proof_builder(proof_builder_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_prover_new_proof_builder: >>> {:?}", proof_builder_p);
+
+        check_useful_c_ptr!(proof_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Prover::new_proof_builder() {
+            Ok(proof_builder) => {
+                trace!("indy_crypto_cl_prover_new_proof_builder: proof_builder: {:?}", proof_builder);
+                unsafe {
+                    *proof_builder_p = Handles::insert(proof_builder);
+                    trace!("indy_crypto_cl_prover_new_proof_builder: *proof_builder_p: {:?}", *proof_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_prover_new_proof_builder: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_new_proof_builder: <<< res: {:?}", res);
+        res
+    })
 }
 
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder: *const c_void,
-                                                                 sub_proof_request: *const c_void,
-                                                                 credential_schema: *const c_void,
-                                                                 credential_signature: *const c_void,
-                                                                 credential_values: *const c_void,
-                                                                 credential_pub_key: *const c_void,
-                                                                 rev_reg: *const c_void,
-                                                                 witness: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: >>> proof_builder: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, \
-                credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, rev_reg: {:?}, witness: {:?}",
-           proof_builder, sub_proof_request, credential_schema, credential_signature, credential_values, credential_pub_key, rev_reg, witness);
-
-    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam6);
-    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
-    check_useful_opt_c_reference!(witness, Witness);
-
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: entities: proof_builder: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, \
-                credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, rev_reg: {:?}, witness: {:?}",
-           proof_builder, sub_proof_request, credential_schema, credential_signature, credential_values, credential_pub_key, rev_reg, witness);
-
-    let res = match proof_builder.add_sub_proof_request(sub_proof_request,
-                                                        credential_schema,
-                                                        credential_signature,
-                                                        credential_values,
-                                                        credential_pub_key,
-                                                        rev_reg,
-                                                        witness) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: <<< res: {:?}", res);
-    ErrorCode::Success
+pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder: Handle,
+                                                                 sub_proof_request: Handle,
+                                                                 credential_schema: Handle,
+                                                                 credential_signature: Handle,
+                                                                 credential_values: Handle,
+                                                                 credential_pub_key: Handle,
+                                                                 rev_reg: Handle,
+                                                                 witness: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: >>> proof_builder: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, \
+                    credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, rev_reg: {:?}, witness: {:?}",
+               proof_builder, sub_proof_request, credential_schema, credential_signature, credential_values, credential_pub_key, rev_reg, witness);
+
+        check_useful_mut_handle!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam4);
+        check_useful_handle!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam5);
+        check_useful_handle!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam6);
+        check_useful_opt_handle!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam7);
+        check_useful_opt_handle!(witness, Witness, ErrorCode::CommonInvalidParam8);
+
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: entities: proof_builder: {:?}, sub_proof_request: {:?}, credential_schema: {:?}, \
+                    credential_signature: {:?}, credential_values: {:?}, credential_pub_key: {:?}, rev_reg: {:?}, witness: {:?}",
+               proof_builder, sub_proof_request, credential_schema, credential_signature, credential_values, credential_pub_key, rev_reg, witness);
+
+        let res = match proof_builder.add_sub_proof_request(sub_proof_request,
+                                                            credential_schema,
+                                                            credential_signature,
+                                                            credential_values,
+                                                            credential_pub_key,
+                                                            rev_reg,
+                                                            witness) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: <<< res: {:?}", res);
+        ErrorCode::Success
+    })
 }
 
 
@@ -593,74 +622,198 @@ pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder:
 /// calling indy_crypto_cl_proof_free.
 ///
 /// # Arguments
-/// * `proof_builder` - Reference that contain proof builder instance pointer.
-/// * `nonce` - Reference that contain nonce instance pointer.
-/// * `master_secret` - Reference that contain master secret instance pointer.
-/// * `proof_p` - Reference that will contain proof instance pointer.
+/// * `proof_builder` - Reference that contain proof builder instance handle.
+/// * `nonce` - Reference that contain nonce instance handle.
+/// * `master_secret` - Reference that contain master secret instance handle.
+/// * `proof_p` - Reference that will contain proof instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_builder_finalize(proof_builder: *const c_void,
-                                                    nonce: *const c_void,
-                                                    master_secret: *const c_void,
-                                                    proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_builder_finalize: >>> proof_builder: {:?}, nonce: {:?}, master_secret: {:?}, proof_p: {:?}",
-           proof_builder, nonce, master_secret, proof_p);
-
-    check_useful_c_ptr!(proof_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam3);
-    check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam4);
-
-    let proof_builder = unsafe { Box::from_raw(proof_builder as *mut ProofBuilder) };
-
-    trace!("indy_crypto_cl_proof_builder_finalize: entities: proof_builder: {:?}, nonce: {:?}, master_secret: {:?}",
-           proof_builder, nonce, master_secret);
-
-    let res = match proof_builder.finalize(nonce, master_secret) {
-        Ok(proof) => {
-            trace!("indy_crypto_cl_proof_builder_finalize: proof: {:?}", proof);
-            unsafe {
-                *proof_p = Box::into_raw(Box::new(proof)) as *const c_void;
-                trace!("indy_crypto_cl_proof_builder_finalize: *proof_p: {:?}", *proof_p);
+pub extern fn indy_crypto_cl_proof_builder_finalize(proof_builder: Handle,
+                                                    nonce: Handle,
+                                                    master_secret: Handle,
+                                                    proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_builder_finalize: >>> proof_builder: {:?}, nonce: {:?}, master_secret: {:?}, proof_p: {:?}",
+               proof_builder, nonce, master_secret, proof_p);
+
+        check_useful_handle!(nonce, Nonce, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam4);
+
+        check_useful_removed_handle!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_proof_builder_finalize: entities: proof_builder: {:?}, nonce: {:?}, master_secret: {:?}",
+               proof_builder, nonce, master_secret);
+
+        let res = match proof_builder.finalize(nonce, master_secret) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_builder_finalize: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = Handles::insert(proof);
+                    trace!("indy_crypto_cl_proof_builder_finalize: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_builder_finalize: <<< res: {:?}", res);
+        res
+    })
 }
 
-/// Returns json representation of proof.
+/// Finalize proof bound to nonces of several verifiers.
+/// Each verifier accepts the proof only if its own nonce is among the nonces.
+///
+/// Note that proof deallocation must be performed by
+/// calling indy_crypto_cl_proof_free.
 ///
 /// # Arguments
-/// * `proof` - Reference that contains proof instance pointer.
-/// * `proof_json_p` - Reference that will contain proof json.
+/// * `proof_builder` - Reference that contain proof builder instance handle.
+/// * `nonces` - Nonce instance handles array.
+/// * `nonces_len` - Nonce instance handles array len.
+/// * `master_secret` - Reference that contain master secret instance handle.
+/// * `proof_p` - Reference that will contain proof instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_to_json(proof: *const c_void,
-                                           proof_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_to_json: >>> proof: {:?}, proof_json_p: {:?}", proof, proof_json_p);
+pub extern fn indy_crypto_cl_proof_builder_finalize_multi(proof_builder: Handle,
+                                                          nonces: *const Handle,
+                                                          nonces_len: usize,
+                                                          master_secret: Handle,
+                                                          proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_builder_finalize_multi: >>> proof_builder: {:?}, nonces: {:?}, nonces_len: {:?}, master_secret: {:?}, proof_p: {:?}",
+               proof_builder, nonces, nonces_len, master_secret, proof_p);
+
+        check_useful_handle_array!(nonces, nonces_len, Nonce, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam5);
+
+        check_useful_removed_handle!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_proof_builder_finalize_multi: entities: proof_builder: {:?}, nonces: {:?}, master_secret: {:?}",
+               proof_builder, nonces, master_secret);
+
+        let res = match proof_builder.finalize_multi(&nonces, master_secret) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_builder_finalize_multi: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = Handles::insert(proof);
+                    trace!("indy_crypto_cl_proof_builder_finalize_multi: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_proof_builder_finalize_multi: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Returns json representation of proof builder state.
+///
+/// Proof builder can be restored from json by calling indy_crypto_cl_proof_builder_from_json
+/// to add other sub proof requests and finalize proof later.
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contains proof builder instance handle.
+/// * `proof_builder_json_p` - Reference that will contain proof builder json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_to_json(proof_builder: Handle,
+                                                   proof_builder_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_builder_to_json: >>> proof_builder: {:?}, proof_builder_json_p: {:?}", proof_builder, proof_builder_json_p);
+
+        check_useful_handle!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_builder_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_builder_to_json: entity >>> proof_builder: {:?}", proof_builder);
+
+        let res = match proof_builder.to_json() {
+            Ok(proof_builder_json) => {
+                trace!("indy_crypto_cl_proof_builder_to_json: proof_builder_json: {:?}", proof_builder_json);
+                unsafe {
+                    let proof_builder_json = CTypesUtils::string_to_cstring(proof_builder_json);
+                    *proof_builder_json_p = proof_builder_json.into_raw();
+                    trace!("indy_crypto_cl_proof_builder_to_json: proof_builder_json_p: {:?}", *proof_builder_json_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
 
-    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(proof_json_p, ErrorCode::CommonInvalidParam2);
+        trace!("indy_crypto_cl_proof_builder_to_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Creates and returns proof builder from json.
+///
+/// Note that proof builder deallocation must be performed by
+/// calling indy_crypto_cl_proof_builder_finalize.
+///
+/// # Arguments
+/// * `proof_builder_json` - Reference that contains proof builder json.
+/// * `proof_builder_p` - Reference that will contain proof builder instance handle.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_from_json(proof_builder_json: *const c_char,
+                                                     proof_builder_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_builder_from_json: >>> proof_builder_json: {:?}, proof_builder_p: {:?}", proof_builder_json, proof_builder_p);
+
+        check_useful_c_str!(proof_builder_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_builder_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_builder_from_json: entity: proof_builder_json: {:?}", proof_builder_json);
+
+        let res = match ProofBuilder::from_json(&proof_builder_json) {
+            Ok(proof_builder) => {
+                trace!("indy_crypto_cl_proof_builder_from_json: proof_builder: {:?}", proof_builder);
+                unsafe {
+                    *proof_builder_p = Handles::insert(proof_builder);
+                    trace!("indy_crypto_cl_proof_builder_from_json: *proof_builder_p: {:?}", *proof_builder_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_to_json: entity >>> proof: {:?}", proof);
+        trace!("indy_crypto_cl_proof_builder_from_json: <<< res: {:?}", res);
+        res
+    })
+}
 
-    let res = match proof.to_json() {
-        Ok(proof_json) => {
-            trace!("indy_crypto_cl_proof_to_json: proof_json: {:?}", proof_json);
-            unsafe {
-                let proof_json = CTypesUtils::string_to_cstring(proof_json);
-                *proof_json_p = proof_json.into_raw();
-                trace!("indy_crypto_cl_proof_to_json: proof_json_p: {:?}", *proof_json_p);
+/// Returns json representation of proof.
+///
+/// # Arguments
+/// * `proof` - Reference that contains proof instance handle.
+/// * `proof_json_p` - Reference that will contain proof json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_to_json(proof: Handle,
+                                           proof_json_p: *mut *const c_char) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_to_json: >>> proof: {:?}, proof_json_p: {:?}", proof, proof_json_p);
+
+        check_useful_handle!(proof, Proof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_to_json: entity >>> proof: {:?}", proof);
+
+        let res = match proof.to_json() {
+            Ok(proof_json) => {
+                trace!("indy_crypto_cl_proof_to_json: proof_json: {:?}", proof_json);
+                unsafe {
+                    let proof_json = CTypesUtils::string_to_cstring(proof_json);
+                    *proof_json_p = proof_json.into_raw();
+                    trace!("indy_crypto_cl_proof_to_json: proof_json_p: {:?}", *proof_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_to_json: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Creates and returns proof json.
@@ -669,56 +822,137 @@ pub extern fn indy_crypto_cl_proof_to_json(proof: *const c_void,
 ///
 /// # Arguments
 /// * `proof_json` - Reference that contains proof json.
-/// * `proof_p` - Reference that will contain proof instance pointer.
+/// * `proof_p` - Reference that will contain proof instance handle.
 #[no_mangle]
 pub extern fn indy_crypto_cl_proof_from_json(proof_json: *const c_char,
-                                             proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_from_json: >>> proof_json: {:?}, proof_p: {:?}", proof_json, proof_p);
+                                             proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_from_json: >>> proof_json: {:?}, proof_p: {:?}", proof_json, proof_p);
+
+        check_useful_c_str!(proof_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_from_json: entity: proof_json: {:?}", proof_json);
+
+        let res = match Proof::from_json(&proof_json) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_from_json: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = Handles::insert(proof);
+                    trace!("indy_crypto_cl_proof_from_json: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
 
-    check_useful_c_str!(proof_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam2);
+        trace!("indy_crypto_cl_proof_from_json: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Returns compact binary representation of proof.
+///
+/// Note: Returned buffer is owned by the caller and must be deallocated by calling indy_crypto_buffer_free.
+///
+/// # Arguments
+/// * `proof` - Reference that contains proof instance handle.
+/// * `bytes_p` - Reference that will contain pointer to the first byte of proof bytes.
+/// * `bytes_len_p` - Reference that will contain proof bytes length.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_to_bytes(proof: Handle,
+                                            bytes_p: *mut *const u8,
+                                            bytes_len_p: *mut usize) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_to_bytes: >>> proof: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", proof, bytes_p, bytes_len_p);
+
+        check_useful_handle!(proof, Proof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_cl_proof_to_bytes: entity >>> proof: {:?}", proof);
+
+        let res = match proof.to_compact_bytes() {
+            Ok(bytes) => {
+                trace!("indy_crypto_cl_proof_to_bytes: bytes: {:?}", bytes);
+                unsafe {
+                    let (buffer, buffer_len) = CTypesUtils::vec_to_buffer(bytes);
+                    *bytes_p = buffer;
+                    *bytes_len_p = buffer_len;
+                    trace!("indy_crypto_cl_proof_to_bytes: *bytes_p: {:?}, *bytes_len_p: {:?}", *bytes_p, *bytes_len_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_from_json: entity: proof_json: {:?}", proof_json);
+        trace!("indy_crypto_cl_proof_to_bytes: <<< res: {:?}", res);
+        res
+    })
+}
 
-    let res = match Proof::from_json(&proof_json) {
-        Ok(proof) => {
-            trace!("indy_crypto_cl_proof_from_json: proof: {:?}", proof);
-            unsafe {
-                *proof_p = Box::into_raw(Box::new(proof)) as *const c_void;
-                trace!("indy_crypto_cl_proof_from_json: *proof_p: {:?}", *proof_p);
+/// Creates and returns proof from compact binary representation.
+///
+/// Note: Proof instance deallocation must be performed by calling indy_crypto_cl_proof_free.
+///
+/// # Arguments
+/// * `bytes` - Bytes buffer pointer.
+/// * `bytes_len` - Bytes buffer len.
+/// * `proof_p` - Reference that will contain proof instance handle.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_from_bytes(bytes: *const u8, bytes_len: usize,
+                                              proof_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, proof_p: {:?}", bytes, bytes_len, proof_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_cl_proof_from_bytes: bytes: {:?}", bytes);
+
+        let res = match Proof::from_compact_bytes(bytes) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_from_bytes: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = Handles::insert(proof);
+                    trace!("indy_crypto_cl_proof_from_bytes: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_from_bytes: <<< res: {:?}", res);
+        res
+    })
 }
 
 /// Deallocates proof instance.
 ///
 /// # Arguments
-/// * `proof` - Reference that contains proof instance pointer.
+/// * `proof` - Reference that contains proof instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_free(proof: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_free: >>> proof: {:?}", proof);
-
-    check_useful_c_ptr!(proof, ErrorCode::CommonInvalidParam1);
+pub extern fn indy_crypto_cl_proof_free(proof: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_free: >>> proof: {:?}", proof);
 
-    let proof = unsafe { Box::from_raw(proof as *mut Proof); };
-    trace!("indy_crypto_cl_proof_free: entity: proof: {:?}", proof);
+        check_useful_removed_handle!(proof, Proof, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_proof_free: entity: proof: {:?}", proof);
 
-    let res = ErrorCode::Success;
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_cl_proof_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_free: <<< res: {:?}", res);
+        res
+    })
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ffi::handles::INVALID_HANDLE;
+
+    use ffi::indy_crypto_buffer_free;
 
     use std::ptr;
     use ffi::cl::mocks::*;
@@ -727,10 +961,10 @@ mod tests {
 
     #[test]
     fn indy_crypto_cl_prover_new_master_secret_works() {
-        let mut master_secret_p: *const c_void = ptr::null();
+        let mut master_secret_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_prover_new_master_secret(&mut master_secret_p);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!master_secret_p.is_null());
+        assert_ne!(master_secret_p, INVALID_HANDLE);
 
         _free_master_secret(master_secret_p)
     }
@@ -754,7 +988,7 @@ mod tests {
         let err_code = indy_crypto_cl_master_secret_to_json(master_secret, &mut master_secret_json_p);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut master_secret_p: *const c_void = ptr::null();
+        let mut master_secret_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_master_secret_from_json(master_secret_json_p, &mut master_secret_p);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -775,9 +1009,9 @@ mod tests {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
         let master_secret_blinding_nonce = _nonce();
 
-        let mut blinded_master_secret_p: *const c_void = ptr::null();
-        let mut master_secret_blinding_data_p: *const c_void = ptr::null();
-        let mut blinded_master_secret_correctness_proof_p: *const c_void = ptr::null();
+        let mut blinded_master_secret_p: Handle = INVALID_HANDLE;
+        let mut master_secret_blinding_data_p: Handle = INVALID_HANDLE;
+        let mut blinded_master_secret_correctness_proof_p: Handle = INVALID_HANDLE;
 
         let err_code = indy_crypto_cl_prover_blind_master_secret(credential_pub_key,
                                                                  credential_key_correctness_proof,
@@ -787,8 +1021,8 @@ mod tests {
                                                                  &mut master_secret_blinding_data_p,
                                                                  &mut blinded_master_secret_correctness_proof_p);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!blinded_master_secret_p.is_null());
-        assert!(!master_secret_blinding_data_p.is_null());
+        assert_ne!(blinded_master_secret_p, INVALID_HANDLE);
+        assert_ne!(master_secret_blinding_data_p, INVALID_HANDLE);
 
         _free_blinded_master_secret(blinded_master_secret_p,
                                     master_secret_blinding_data_p,
@@ -858,7 +1092,7 @@ mod tests {
         let err_code = indy_crypto_cl_blinded_master_secret_to_json(blinded_master_secret, &mut blinded_master_secret_json_p);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut blinded_master_secret_p: *const c_void = ptr::null();
+        let mut blinded_master_secret_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_blinded_master_secret_from_json(blinded_master_secret_json_p,
                                                                       &mut blinded_master_secret_p);
         assert_eq!(err_code, ErrorCode::Success);
@@ -907,7 +1141,7 @@ mod tests {
                                                                           &mut master_secret_blinding_data_json_p);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut master_secret_blinding_data_p: *const c_void = ptr::null();
+        let mut master_secret_blinding_data_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_master_secret_blinding_data_from_json(master_secret_blinding_data_json_p,
                                                                             &mut master_secret_blinding_data_p);
         assert_eq!(err_code, ErrorCode::Success);
@@ -956,7 +1190,7 @@ mod tests {
                                                                                       &mut blinded_master_secret_correctness_proof_json_p);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut blinded_master_secret_correctness_proof_p: *const c_void = ptr::null();
+        let mut blinded_master_secret_correctness_proof_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_blinded_master_secret_correctness_proof_from_json(blinded_master_secret_correctness_proof_json_p,
                                                                                         &mut blinded_master_secret_correctness_proof_p);
         assert_eq!(err_code, ErrorCode::Success);
@@ -994,9 +1228,9 @@ mod tests {
                                                                           master_secret,
                                                                           credential_pub_key,
                                                                           credential_issuance_nonce,
-                                                                          ptr::null(),
-                                                                          ptr::null(),
-                                                                          ptr::null());
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE);
         assert_eq!(err_code, ErrorCode::Success);
 
         _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
@@ -1009,10 +1243,10 @@ mod tests {
 
     #[test]
     fn indy_crypto_cl_prover_proof_builder_new_works() {
-        let mut proof_builder: *const c_void = ptr::null();
+        let mut proof_builder: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_prover_new_proof_builder(&mut proof_builder);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof_builder.is_null());
+        assert_ne!(proof_builder, INVALID_HANDLE);
 
         let nonce = _nonce();
         let master_secret = _master_secret();
@@ -1046,9 +1280,9 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
         let proof_builder = _proof_builder();
 
         let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder,
@@ -1057,8 +1291,8 @@ mod tests {
                                                                           credential_signature,
                                                                           credential_values,
                                                                           credential_pub_key,
-                                                                          ptr::null(),
-                                                                          ptr::null());
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE);
         assert_eq!(err_code, ErrorCode::Success);
 
         let nonce = _nonce();
@@ -1100,9 +1334,9 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
         let proof_builder = _proof_builder();
 
         let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder,
@@ -1111,28 +1345,163 @@ mod tests {
                                                                           credential_signature,
                                                                           credential_values,
                                                                           credential_pub_key,
-                                                                          ptr::null(),
-                                                                          ptr::null());
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE);
         assert_eq!(err_code, ErrorCode::Success);
 
         let nonce = _nonce();
 
-        let mut proof: *const c_void = ptr::null();
+        let mut proof: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder, nonce, master_secret, &mut proof);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof.is_null());
+        assert_ne!(proof, INVALID_HANDLE);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_master_secret(master_secret);
+        _free_blinded_master_secret(blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
+        _free_nonce(master_secret_blinding_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_credential_values(credential_values);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+        _free_proof(proof);
+    }
+
+    #[test]
+    fn indy_crypto_cl_prover_proof_builder_finalize_multi_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let master_secret = _master_secret();
+        let master_secret_blinding_nonce = _nonce();
+        let (blinded_master_secret, master_secret_blinding_data,
+            blinded_master_secret_correctness_proof) = _blinded_master_secret(credential_pub_key,
+                                                                              credential_key_correctness_proof,
+                                                                              master_secret,
+                                                                              master_secret_blinding_nonce);
+        let credential_values = _credential_values();
+        let sub_proof_request = _sub_proof_request();
+        let credential_schema = _credential_schema();
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_master_secret,
+                                                                                        blinded_master_secret_correctness_proof,
+                                                                                        master_secret_blinding_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      master_secret_blinding_data,
+                                      master_secret,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
+        let proof_builder = _proof_builder();
+
+        let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder,
+                                                                          sub_proof_request,
+                                                                          credential_schema,
+                                                                          credential_signature,
+                                                                          credential_values,
+                                                                          credential_pub_key,
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let nonces = [_nonce(), _nonce()];
+
+        let mut proof: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_cl_proof_builder_finalize_multi(proof_builder, nonces.as_ptr(), nonces.len(), master_secret, &mut proof);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_ne!(proof, INVALID_HANDLE);
 
         _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
         _free_master_secret(master_secret);
         _free_blinded_master_secret(blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
         _free_nonce(master_secret_blinding_nonce);
         _free_nonce(credential_issuance_nonce);
+        _free_nonce(nonces[0]);
+        _free_nonce(nonces[1]);
         _free_credential_values(credential_values);
         _free_sub_proof_request(sub_proof_request);
         _free_credential_signature(credential_signature, signature_correctness_proof);
         _free_proof(proof);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_builder_to_json_and_from_json_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let master_secret = _master_secret();
+        let master_secret_blinding_nonce = _nonce();
+        let (blinded_master_secret, master_secret_blinding_data,
+            blinded_master_secret_correctness_proof) = _blinded_master_secret(credential_pub_key,
+                                                                              credential_key_correctness_proof,
+                                                                              master_secret,
+                                                                              master_secret_blinding_nonce);
+        let credential_values = _credential_values();
+        let sub_proof_request = _sub_proof_request();
+        let credential_schema = _credential_schema();
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_master_secret,
+                                                                                        blinded_master_secret_correctness_proof,
+                                                                                        master_secret_blinding_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      master_secret_blinding_data,
+                                      master_secret,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
+        let proof_builder = _proof_builder();
+
+        let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder,
+                                                                          sub_proof_request,
+                                                                          credential_schema,
+                                                                          credential_signature,
+                                                                          credential_values,
+                                                                          credential_pub_key,
+                                                                          INVALID_HANDLE,
+                                                                          INVALID_HANDLE);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut proof_builder_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_to_json(proof_builder, &mut proof_builder_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut restored_proof_builder: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_cl_proof_builder_from_json(proof_builder_json_p, &mut restored_proof_builder);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_ne!(restored_proof_builder, INVALID_HANDLE);
+
+        let nonce = _nonce();
+
+        let mut proof: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_cl_proof_builder_finalize(restored_proof_builder, nonce, master_secret, &mut proof);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_ne!(proof, INVALID_HANDLE);
+
+        let mut original_proof: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder, nonce, master_secret, &mut original_proof);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_master_secret(master_secret);
+        _free_blinded_master_secret(blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
+        _free_nonce(master_secret_blinding_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(nonce);
+        _free_credential_values(credential_values);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+        _free_proof(proof);
+        _free_proof(original_proof);
+    }
+
     #[test]
     fn indy_crypto_cl_proof_to_json_works() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -1156,17 +1525,17 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
         let mut proof_json_p: *const c_char = ptr::null();
         let err_code = indy_crypto_cl_proof_to_json(proof, &mut proof_json_p);
@@ -1205,23 +1574,23 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
         let mut proof_json_p: *const c_char = ptr::null();
         let err_code = indy_crypto_cl_proof_to_json(proof, &mut proof_json_p);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut proof_p: *const c_void = ptr::null();
+        let mut proof_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_proof_from_json(proof_json_p, &mut proof_p);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1235,6 +1604,64 @@ mod tests {
         _free_proof(proof);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_to_bytes_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let master_secret = _master_secret();
+        let master_secret_blinding_nonce = _nonce();
+        let (blinded_master_secret, master_secret_blinding_data,
+            blinded_master_secret_correctness_proof) = _blinded_master_secret(credential_pub_key,
+                                                                              credential_key_correctness_proof,
+                                                                              master_secret,
+                                                                              master_secret_blinding_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_master_secret,
+                                                                                        blinded_master_secret_correctness_proof,
+                                                                                        master_secret_blinding_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      master_secret_blinding_data,
+                                      master_secret,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof(credential_pub_key,
+                           credential_signature,
+                           proof_building_nonce,
+                           master_secret,
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
+
+        let mut bytes_p: *const u8 = ptr::null();
+        let mut bytes_len_p: usize = 0;
+        let err_code = indy_crypto_cl_proof_to_bytes(proof, &mut bytes_p, &mut bytes_len_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut proof_p: Handle = INVALID_HANDLE;
+        let err_code = indy_crypto_cl_proof_from_bytes(bytes_p, bytes_len_p, &mut proof_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_buffer_free(bytes_p as *mut u8, bytes_len_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_master_secret(master_secret);
+        _free_blinded_master_secret(blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof);
+        _free_nonce(master_secret_blinding_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+        _free_proof(proof);
+        _free_proof(proof_p);
+    }
+
     #[test]
     fn indy_crypto_cl_proof_free_works() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -1258,17 +1685,17 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
         _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
         _free_master_secret(master_secret);
@@ -1285,32 +1712,32 @@ mod tests {
 
 pub mod mocks {
     use super::*;
+    use ffi::handles::INVALID_HANDLE;
 
-    use std::ptr;
     use ffi::cl::mocks::*;
 
-    pub fn _master_secret() -> *const c_void {
-        let mut master_secret_p: *const c_void = ptr::null();
+    pub fn _master_secret() -> Handle {
+        let mut master_secret_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_prover_new_master_secret(&mut master_secret_p);
 
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!master_secret_p.is_null());
+        assert_ne!(master_secret_p, INVALID_HANDLE);
 
         master_secret_p
     }
 
-    pub fn _free_master_secret(master_secret: *const c_void) {
+    pub fn _free_master_secret(master_secret: Handle) {
         let err_code = indy_crypto_cl_master_secret_free(master_secret);
         assert_eq!(err_code, ErrorCode::Success);
     }
 
-    pub fn _blinded_master_secret(credential_pub_key: *const c_void,
-                                  credential_key_correctness_proof: *const c_void,
-                                  master_secret: *const c_void,
-                                  master_secret_blinding_nonce: *const c_void) -> (*const c_void, *const c_void, *const c_void) {
-        let mut blinded_master_secret_p: *const c_void = ptr::null();
-        let mut master_secret_blinding_data_p: *const c_void = ptr::null();
-        let mut blinded_master_secret_correctness_proof_p: *const c_void = ptr::null();
+    pub fn _blinded_master_secret(credential_pub_key: Handle,
+                                  credential_key_correctness_proof: Handle,
+                                  master_secret: Handle,
+                                  master_secret_blinding_nonce: Handle) -> (Handle, Handle, Handle) {
+        let mut blinded_master_secret_p: Handle = INVALID_HANDLE;
+        let mut master_secret_blinding_data_p: Handle = INVALID_HANDLE;
+        let mut blinded_master_secret_correctness_proof_p: Handle = INVALID_HANDLE;
 
         let err_code = indy_crypto_cl_prover_blind_master_secret(credential_pub_key,
                                                                  credential_key_correctness_proof,
@@ -1320,15 +1747,15 @@ pub mod mocks {
                                                                  &mut master_secret_blinding_data_p,
                                                                  &mut blinded_master_secret_correctness_proof_p);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!blinded_master_secret_p.is_null());
-        assert!(!master_secret_blinding_data_p.is_null());
-        assert!(!blinded_master_secret_correctness_proof_p.is_null());
+        assert_ne!(blinded_master_secret_p, INVALID_HANDLE);
+        assert_ne!(master_secret_blinding_data_p, INVALID_HANDLE);
+        assert_ne!(blinded_master_secret_correctness_proof_p, INVALID_HANDLE);
 
         (blinded_master_secret_p, master_secret_blinding_data_p, blinded_master_secret_correctness_proof_p)
     }
 
-    pub fn _free_blinded_master_secret(blinded_master_secret: *const c_void, master_secret_blinding_data: *const c_void,
-                                       blinded_master_secret_correctness_proof: *const c_void) {
+    pub fn _free_blinded_master_secret(blinded_master_secret: Handle, master_secret_blinding_data: Handle,
+                                       blinded_master_secret_correctness_proof: Handle) {
         let err_code = indy_crypto_cl_blinded_master_secret_free(blinded_master_secret);
         assert_eq!(err_code, ErrorCode::Success);
 
@@ -1339,10 +1766,10 @@ pub mod mocks {
         assert_eq!(err_code, ErrorCode::Success);
     }
 
-    pub fn _process_credential_signature(credential_signature: *const c_void, signature_correctness_proof: *const c_void,
-                                         master_secret_blinding_data: *const c_void, master_secret: *const c_void,
-                                         credential_pub_key: *const c_void, credential_issuance_nonce: *const c_void,
-                                         rev_key_pub: *const c_void, rev_reg: *const c_void, witness: *const c_void) {
+    pub fn _process_credential_signature(credential_signature: Handle, signature_correctness_proof: Handle,
+                                         master_secret_blinding_data: Handle, master_secret: Handle,
+                                         credential_pub_key: Handle, credential_issuance_nonce: Handle,
+                                         rev_key_pub: Handle, rev_reg: Handle, witness: Handle) {
         let credential_values = _credential_values();
         let err_code = indy_crypto_cl_prover_process_credential_signature(credential_signature,
                                                                           credential_values,
@@ -1358,26 +1785,26 @@ pub mod mocks {
         _free_credential_values(credential_values);
     }
 
-    pub fn _proof_builder() -> *const c_void {
-        let mut proof_builder: *const c_void = ptr::null();
+    pub fn _proof_builder() -> Handle {
+        let mut proof_builder: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_prover_new_proof_builder(&mut proof_builder);
 
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof_builder.is_null());
+        assert_ne!(proof_builder, INVALID_HANDLE);
 
         proof_builder
     }
 
-    pub fn _free_proof_builder(proof_builder: *const c_void, nonce: *const c_void, master_secret: *const c_void) {
-        let mut proof: *const c_void = ptr::null();
+    pub fn _free_proof_builder(proof_builder: Handle, nonce: Handle, master_secret: Handle) {
+        let mut proof: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder, nonce, master_secret, &mut proof);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof.is_null());
+        assert_ne!(proof, INVALID_HANDLE);
     }
 
-    pub fn _proof(credential_pub_key: *const c_void, credential_signature: *const c_void,
-                  nonce: *const c_void, master_secret: *const c_void,
-                  rev_reg: *const c_void, witness: *const c_void) -> *const c_void {
+    pub fn _proof(credential_pub_key: Handle, credential_signature: Handle,
+                  nonce: Handle, master_secret: Handle,
+                  rev_reg: Handle, witness: Handle) -> Handle {
         let proof_builder = _proof_builder();
         let credential_schema = _credential_schema();
         let credential_values = _credential_values();
@@ -1392,13 +1819,13 @@ pub mod mocks {
                                                            rev_reg,
                                                            witness);
 
-        let mut proof: *const c_void = ptr::null();
+        let mut proof: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder,
                                                              nonce,
                                                              master_secret,
                                                              &mut proof);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof.is_null());
+        assert_ne!(proof, INVALID_HANDLE);
 
         _free_credential_schema(credential_schema);
         _free_credential_values(credential_values);
@@ -1407,7 +1834,7 @@ pub mod mocks {
         proof
     }
 
-    pub fn _free_proof(proof: *const c_void) {
+    pub fn _free_proof(proof: Handle) {
         let err_code = indy_crypto_cl_proof_free(proof);
         assert_eq!(err_code, ErrorCode::Success);
     }
@@ -2,8 +2,8 @@ use cl::verifier::*;
 use cl::*;
 use errors::ToErrorCode;
 use ffi::ErrorCode;
+use ffi::handles::{Handle, Handles};
 
-use std::os::raw::c_void;
 
 /// Creates and returns proof verifier.
 ///
@@ -11,109 +11,114 @@ use std::os::raw::c_void;
 /// calling indy_crypto_cl_proof_verifier_finalize.
 ///
 /// # Arguments
-/// * `proof_verifier_p` - Reference that will contain proof verifier instance pointer.
+/// * `proof_verifier_p` - Reference that will contain proof verifier instance handle.
 #[no_mangle]
-pub extern fn indy_crypto_cl_verifier_new_proof_verifier(proof_verifier_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_verifier_new_proof_verifier: >>> {:?}", proof_verifier_p);
-
-    check_useful_c_ptr!(proof_verifier_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Verifier::new_proof_verifier() {
-        Ok(proof_verifier) => {
-            trace!("indy_crypto_cl_verifier_new_proof_verifier: proof_verifier: {:?}", proof_verifier);
-            unsafe {
-                *proof_verifier_p = Box::into_raw(Box::new(proof_verifier)) as *const c_void;
-                trace!("indy_crypto_cl_verifier_new_proof_verifier: *proof_verifier_p: {:?}", *proof_verifier_p);
+pub extern fn indy_crypto_cl_verifier_new_proof_verifier(proof_verifier_p: *mut Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_verifier_new_proof_verifier: >>> {:?}", proof_verifier_p);
+
+        check_useful_c_ptr!(proof_verifier_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Verifier::new_proof_verifier() {
+            Ok(proof_verifier) => {
+                trace!("indy_crypto_cl_verifier_new_proof_verifier: proof_verifier: {:?}", proof_verifier);
+                unsafe {
+                    *proof_verifier_p = Handles::insert(proof_verifier);
+                    trace!("indy_crypto_cl_verifier_new_proof_verifier: *proof_verifier_p: {:?}", *proof_verifier_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_verifier_new_proof_verifier: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_verifier_new_proof_verifier: <<< res: {:?}", res);
+        res
+    })
 }
 
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_verifier_add_sub_proof_request(proof_verifier: *const c_void,
-                                                                  sub_proof_request: *const c_void,
-                                                                  credential_schema: *const c_void,
-                                                                  credential_pub_key: *const c_void,
-                                                                  rev_key_pub: *const c_void,
-                                                                  rev_reg: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: >>> proof_verifier: {:?}, sub_proof_request: {:?} ,\
-                credential_schema: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}",
-           proof_verifier, sub_proof_request, credential_schema, credential_pub_key, rev_key_pub, rev_reg);
-
-    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam4);
-    check_useful_opt_c_reference!(rev_key_pub, RevocationKeyPublic);
-    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
-
-    trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: entities: proof_verifier: {:?}, sub_proof_request: {:?},\
-                credential_schema: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}",
-           proof_verifier, sub_proof_request, credential_schema, credential_pub_key, rev_key_pub, rev_reg);
-
-    let res = match proof_verifier.add_sub_proof_request(sub_proof_request,
-                                                         credential_schema,
-                                                         credential_pub_key,
-                                                         rev_key_pub,
-                                                         rev_reg) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: <<< res: {:?}", res);
-    ErrorCode::Success
+pub extern fn indy_crypto_cl_proof_verifier_add_sub_proof_request(proof_verifier: Handle,
+                                                                  sub_proof_request: Handle,
+                                                                  credential_schema: Handle,
+                                                                  credential_pub_key: Handle,
+                                                                  rev_key_pub: Handle,
+                                                                  rev_reg: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: >>> proof_verifier: {:?}, sub_proof_request: {:?} ,\
+                    credential_schema: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}",
+               proof_verifier, sub_proof_request, credential_schema, credential_pub_key, rev_key_pub, rev_reg);
+
+        check_useful_mut_handle!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+        check_useful_handle!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
+        check_useful_handle!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam4);
+        check_useful_opt_handle!(rev_key_pub, RevocationKeyPublic, ErrorCode::CommonInvalidParam5);
+        check_useful_opt_handle!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: entities: proof_verifier: {:?}, sub_proof_request: {:?},\
+                    credential_schema: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}",
+               proof_verifier, sub_proof_request, credential_schema, credential_pub_key, rev_key_pub, rev_reg);
+
+        let res = match proof_verifier.add_sub_proof_request(sub_proof_request,
+                                                             credential_schema,
+                                                             credential_pub_key,
+                                                             rev_key_pub,
+                                                             rev_reg) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_cl_proof_verifier_add_sub_proof_request: <<< res: {:?}", res);
+        ErrorCode::Success
+    })
 }
 
 
 /// Verifies proof and deallocates proof verifier.
 ///
 /// # Arguments
-/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
-/// * `proof` - Reference that contain proof instance pointer.
-/// * `nonce` - Reference that contain nonce instance pointer.
+/// * `proof_verifier` - Reference that contain proof verifier instance handle.
+/// * `proof` - Reference that contain proof instance handle.
+/// * `nonce` - Reference that contain nonce instance handle.
 /// * `valid_p` - Reference that will be filled with true - if proof valid or false otherwise.
 #[no_mangle]
-pub extern fn indy_crypto_cl_proof_verifier_verify(proof_verifier: *const c_void,
-                                                   proof: *const c_void,
-                                                   nonce: *const c_void,
+pub extern fn indy_crypto_cl_proof_verifier_verify(proof_verifier: Handle,
+                                                   proof: Handle,
+                                                   nonce: Handle,
                                                    valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_verifier_verify: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, valid_p: {:?}", proof_verifier, proof, nonce, valid_p);
+    catch_panic!({
+        trace!("indy_crypto_cl_proof_verifier_verify: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, valid_p: {:?}", proof_verifier, proof, nonce, valid_p);
 
-    check_useful_c_ptr!(proof_verifier, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam3);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
+        check_useful_handle!(proof, Proof, ErrorCode::CommonInvalidParam2);
+        check_useful_handle!(nonce, Nonce, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
 
-    let proof_verifier = unsafe { Box::from_raw(proof_verifier as *mut ProofVerifier) };
+        check_useful_removed_handle!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_proof_verifier_verify: entities: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}", proof_verifier, proof, nonce);
+        trace!("indy_crypto_cl_proof_verifier_verify: entities: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}", proof_verifier, proof, nonce);
 
-    let res = match proof_verifier.verify(proof, nonce) {
-        Ok(valid) => {
-            trace!("indy_crypto_cl_proof_verifier_verify: valid: {:?}", valid);
-            unsafe {
-                *valid_p = valid;
-                trace!("indy_crypto_cl_proof_verifier_verify: *valid_p: {:?}", *valid_p);
+        let res = match proof_verifier.verify(proof, nonce) {
+            Ok(valid) => {
+                trace!("indy_crypto_cl_proof_verifier_verify: valid: {:?}", valid);
+                unsafe {
+                    *valid_p = valid;
+                    trace!("indy_crypto_cl_proof_verifier_verify: *valid_p: {:?}", *valid_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.to_error_code()
+        };
 
-    trace!("indy_crypto_cl_proof_verifier_verify: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_verifier_verify: <<< res: {:?}", res);
+        res
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ffi::handles::INVALID_HANDLE;
 
-    use std::ptr;
     use ffi::cl::mocks::*;
     use super::mocks::*;
     use super::super::issuer::mocks::*;
@@ -144,24 +149,24 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
-        let mut proof_verifier_p: *const c_void = ptr::null();
+        let mut proof_verifier_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_verifier_new_proof_verifier(&mut proof_verifier_p);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof_verifier_p.is_null());
+        assert_ne!(proof_verifier_p, INVALID_HANDLE);
 
-        _add_sub_proof_request(proof_verifier_p, credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+        _add_sub_proof_request(proof_verifier_p, credential_schema, credential_pub_key, sub_proof_request, INVALID_HANDLE, INVALID_HANDLE);
         _free_proof_verifier(proof_verifier_p, proof, proof_building_nonce);
         _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
         _free_master_secret(master_secret);
@@ -199,17 +204,17 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
         let proof_verifier = _proof_verifier();
 
@@ -217,8 +222,8 @@ mod tests {
                                                                            sub_proof_request,
                                                                            credential_schema,
                                                                            credential_pub_key,
-                                                                           ptr::null(),
-                                                                           ptr::null());
+                                                                           INVALID_HANDLE,
+                                                                           INVALID_HANDLE);
         assert_eq!(err_code, ErrorCode::Success);
 
         _free_proof_verifier(proof_verifier, proof, proof_building_nonce);
@@ -258,20 +263,20 @@ mod tests {
                                       master_secret,
                                       credential_pub_key,
                                       credential_issuance_nonce,
-                                      ptr::null(),
-                                      ptr::null(),
-                                      ptr::null());
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE,
+                                      INVALID_HANDLE);
 
         let proof_building_nonce = _nonce();
         let proof = _proof(credential_pub_key,
                            credential_signature,
                            proof_building_nonce,
                            master_secret,
-                           ptr::null(),
-                           ptr::null());
+                           INVALID_HANDLE,
+                           INVALID_HANDLE);
 
         let proof_verifier = _proof_verifier();
-        _add_sub_proof_request(proof_verifier, credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+        _add_sub_proof_request(proof_verifier, credential_schema, credential_pub_key, sub_proof_request, INVALID_HANDLE, INVALID_HANDLE);
 
         let mut valid = false;
         let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
@@ -357,19 +362,19 @@ mod tests {
 
 pub mod mocks {
     use super::*;
-    use std::ptr;
+    use ffi::handles::INVALID_HANDLE;
 
-    pub fn _proof_verifier() -> *const c_void {
-        let mut proof_verifier_p: *const c_void = ptr::null();
+    pub fn _proof_verifier() -> Handle {
+        let mut proof_verifier_p: Handle = INVALID_HANDLE;
         let err_code = indy_crypto_cl_verifier_new_proof_verifier(&mut proof_verifier_p);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof_verifier_p.is_null());
+        assert_ne!(proof_verifier_p, INVALID_HANDLE);
 
         proof_verifier_p
     }
 
-    pub fn _add_sub_proof_request(proof_verifier: *const c_void, credential_schema: *const c_void,
-                                  credential_pub_key: *const c_void, sub_proof_request: *const c_void, rev_key_pub: *const c_void, rev_reg: *const c_void) {
+    pub fn _add_sub_proof_request(proof_verifier: Handle, credential_schema: Handle,
+                                  credential_pub_key: Handle, sub_proof_request: Handle, rev_key_pub: Handle, rev_reg: Handle) {
         let err_code = indy_crypto_cl_proof_verifier_add_sub_proof_request(proof_verifier,
                                                                            sub_proof_request,
                                                                            credential_schema,
@@ -379,7 +384,7 @@ pub mod mocks {
         assert_eq!(err_code, ErrorCode::Success);
     }
 
-    pub fn _free_proof_verifier(proof_verifier: *const c_void, proof: *const c_void, nonce: *const c_void) {
+    pub fn _free_proof_verifier(proof_verifier: Handle, proof: Handle, nonce: Handle) {
         let mut valid = false;
         let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, nonce, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
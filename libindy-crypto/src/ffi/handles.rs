@@ -0,0 +1,309 @@
+use errors::IndyCryptoError;
+
+use std::any::Any;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, Once, ONCE_INIT};
+
+/// Integer handle of library object passed through FFI.
+/// Low 32 bits contain slot index + 1, high 32 bits contain slot generation,
+/// so handle of freed object is never resolved to object stored in the same slot later.
+pub type Handle = u64;
+
+pub const INVALID_HANDLE: Handle = 0;
+
+struct Slot {
+    generation: u32,
+    value: Option<Arc<Any + Send + Sync>>,
+    in_use: bool
+}
+
+struct HandleTable {
+    slots: Vec<Slot>,
+    free_slots: Vec<usize>
+}
+
+impl HandleTable {
+    fn new() -> HandleTable {
+        HandleTable {
+            slots: Vec::new(),
+            free_slots: Vec::new()
+        }
+    }
+
+    fn slot(&mut self, handle: Handle) -> Result<(usize, &mut Slot), IndyCryptoError> {
+        let index = (handle & 0xFFFF_FFFF) as usize;
+        let generation = (handle >> 32) as u32;
+
+        if index == 0 || index > self.slots.len() || self.slots[index - 1].generation != generation {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid handle {}", handle)));
+        }
+
+        let slot = &mut self.slots[index - 1];
+
+        if slot.in_use {
+            return Err(IndyCryptoError::InvalidState(format!("Handle {} is used by other call", handle)));
+        }
+
+        if slot.value.is_none() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Handle {} is already freed", handle)));
+        }
+
+        Ok((index - 1, slot))
+    }
+
+    fn typed_slot<T>(&mut self, handle: Handle) -> Result<(usize, &mut Slot), IndyCryptoError> where T: Any + Send + Sync {
+        let (index, slot) = self.slot(handle)?;
+
+        if !slot.value.as_ref().map(|value| value.is::<T>()).unwrap_or(false) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Handle {} refers to object of other type", handle)));
+        }
+
+        Ok((index, slot))
+    }
+}
+
+// References to object are created only under table lock,
+// so object that isn't shared can't become shared until table is unlocked.
+fn _check_not_shared(handle: Handle, slot: &Slot) -> Result<(), IndyCryptoError> {
+    if slot.value.as_ref().map(|value| Arc::strong_count(value) > 1).unwrap_or(false) {
+        return Err(IndyCryptoError::InvalidState(format!("Handle {} is used by other call", handle)));
+    }
+
+    Ok(())
+}
+
+static mut HANDLE_TABLE: *const Mutex<HandleTable> = 0 as *const Mutex<HandleTable>;
+static HANDLE_TABLE_INIT: Once = ONCE_INIT;
+
+fn _table() -> MutexGuard<'static, HandleTable> {
+    HANDLE_TABLE_INIT.call_once(|| unsafe {
+        HANDLE_TABLE = Box::into_raw(Box::new(Mutex::new(HandleTable::new())));
+    });
+
+    // Table is never left in inconsistent state, so poisoning can be ignored
+    unsafe { &*HANDLE_TABLE }.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Global thread-safe registry of objects passed through FFI.
+/// Object can be borrowed for reading by any number of calls at once (see `Handles::get`),
+/// or for modification by a single call (see `Handles::get_mut`).
+pub struct Handles {}
+
+impl Handles {
+    /// Stores object in registry and returns its handle.
+    pub fn insert<T>(value: T) -> Handle where T: Any + Send + Sync {
+        let mut table = _table();
+
+        let index = match table.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                table.slots.push(Slot { generation: 0, value: None, in_use: false });
+                table.slots.len() - 1
+            }
+        };
+
+        let slot = &mut table.slots[index];
+        slot.value = Some(Arc::new(value));
+
+        ((slot.generation as u64) << 32) | (index as u64 + 1)
+    }
+
+    /// Borrows object from registry for reading.
+    /// Object can be borrowed several times at once (e.g. if the same handle is passed twice in one call)
+    /// and is kept alive by returned reference if handle is freed meanwhile.
+    pub fn get<T>(handle: Handle) -> Result<HandleRef<T>, IndyCryptoError> where T: Any + Send + Sync {
+        let mut table = _table();
+        let (_, slot) = table.typed_slot::<T>(handle)?;
+
+        let value = slot.value.as_ref().unwrap().clone().downcast::<T>()
+            .map_err(|_| IndyCryptoError::InvalidState(format!("Handle {} downcast failed", handle)))?;
+
+        Ok(HandleRef { value })
+    }
+
+    /// Borrows object from registry for modification until returned guard is dropped.
+    /// Concurrent calls with the same handle get an error instead of aliased access.
+    pub fn get_mut<T>(handle: Handle) -> Result<HandleGuard<T>, IndyCryptoError> where T: Any + Send + Sync {
+        let mut table = _table();
+        let (_, slot) = table.typed_slot::<T>(handle)?;
+        _check_not_shared(handle, slot)?;
+
+        let value = slot.value.take().unwrap().downcast::<T>()
+            .map_err(|_| IndyCryptoError::InvalidState(format!("Handle {} downcast failed", handle)))?;
+        slot.in_use = true;
+
+        Ok(HandleGuard { handle, value: Some(value) })
+    }
+
+    /// Removes object from registry and returns it.
+    pub fn remove<T>(handle: Handle) -> Result<T, IndyCryptoError> where T: Any + Send + Sync {
+        let mut table = _table();
+
+        let index = {
+            let (index, slot) = table.typed_slot::<T>(handle)?;
+            _check_not_shared(handle, slot)?;
+            index
+        };
+
+        let value = Handles::_release(&mut table, index);
+
+        value.downcast::<T>()
+            .map_err(|_| IndyCryptoError::InvalidState(format!("Handle {} downcast failed", handle)))
+            .and_then(|value| Arc::try_unwrap(value)
+                .map_err(|_| IndyCryptoError::InvalidState(format!("Handle {} is used by other call", handle))))
+    }
+
+    /// Removes object of any type from registry and drops it.
+    /// Object borrowed for reading is dropped when the last reference to it is dropped.
+    pub fn free(handle: Handle) -> Result<(), IndyCryptoError> {
+        let mut table = _table();
+        let (index, _) = table.slot(handle)?;
+        Handles::_release(&mut table, index);
+        Ok(())
+    }
+
+    fn _release(table: &mut HandleTable, index: usize) -> Arc<Any + Send + Sync> {
+        let value = {
+            let slot = &mut table.slots[index];
+            slot.generation = slot.generation.wrapping_add(1);
+            slot.value.take().unwrap()
+        };
+        table.free_slots.push(index);
+        value
+    }
+}
+
+/// Object borrowed from registry for reading by `Handles::get`.
+pub struct HandleRef<T> where T: Any + Send + Sync {
+    value: Arc<T>
+}
+
+impl<T> Deref for HandleRef<T> where T: Any + Send + Sync {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Object borrowed from registry for modification by `Handles::get_mut`. Returns object back to registry on drop.
+pub struct HandleGuard<T> where T: Any + Send + Sync {
+    handle: Handle,
+    value: Option<Arc<T>>
+}
+
+impl<T> Deref for HandleGuard<T> where T: Any + Send + Sync {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for HandleGuard<T> where T: Any + Send + Sync {
+    fn deref_mut(&mut self) -> &mut T {
+        // Object borrowed for modification isn't shared
+        Arc::get_mut(self.value.as_mut().unwrap()).unwrap()
+    }
+}
+
+impl<T> Drop for HandleGuard<T> where T: Any + Send + Sync {
+    fn drop(&mut self) {
+        let value = self.value.take().unwrap();
+
+        let mut table = _table();
+        let slot = &mut table.slots[(self.handle & 0xFFFF_FFFF) as usize - 1];
+        slot.value = Some(value);
+        slot.in_use = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_work() {
+        let handle = Handles::insert(42u32);
+        assert_ne!(handle, INVALID_HANDLE);
+
+        assert_eq!(42, *Handles::get::<u32>(handle).unwrap());
+        assert_eq!(42, *Handles::get_mut::<u32>(handle).unwrap());
+        assert_eq!(42, Handles::remove::<u32>(handle).unwrap());
+    }
+
+    #[test]
+    fn handles_work_for_freed_handle() {
+        let handle = Handles::insert(42u32);
+        Handles::free(handle).unwrap();
+
+        assert!(Handles::get::<u32>(handle).is_err());
+        assert!(Handles::free(handle).is_err());
+
+        let other_handle = Handles::insert(43u32);
+        assert!(Handles::get::<u32>(handle).is_err());
+        Handles::free(other_handle).unwrap();
+    }
+
+    #[test]
+    fn handles_work_for_other_type() {
+        let handle = Handles::insert(42u32);
+
+        assert!(Handles::get::<String>(handle).is_err());
+        assert!(Handles::get_mut::<String>(handle).is_err());
+        assert!(Handles::remove::<String>(handle).is_err());
+
+        Handles::free(handle).unwrap();
+    }
+
+    #[test]
+    fn handles_work_for_handle_in_use() {
+        let handle = Handles::insert(42u32);
+
+        {
+            let mut value = Handles::get_mut::<u32>(handle).unwrap();
+            *value = 43;
+
+            assert!(Handles::get::<u32>(handle).is_err());
+            assert!(Handles::get_mut::<u32>(handle).is_err());
+            assert!(Handles::free(handle).is_err());
+        }
+
+        assert_eq!(43, Handles::remove::<u32>(handle).unwrap());
+    }
+
+    #[test]
+    fn handles_work_for_shared_handle() {
+        let handle = Handles::insert(42u32);
+
+        {
+            let value = Handles::get::<u32>(handle).unwrap();
+            let same_value = Handles::get::<u32>(handle).unwrap();
+            assert_eq!(*value, *same_value);
+
+            assert!(Handles::get_mut::<u32>(handle).is_err());
+            assert!(Handles::remove::<u32>(handle).is_err());
+        }
+
+        assert_eq!(42, *Handles::get_mut::<u32>(handle).unwrap());
+        assert_eq!(42, Handles::remove::<u32>(handle).unwrap());
+    }
+
+    #[test]
+    fn handles_work_for_handle_freed_while_shared() {
+        let handle = Handles::insert(42u32);
+
+        let value = Handles::get::<u32>(handle).unwrap();
+        Handles::free(handle).unwrap();
+
+        assert_eq!(42, *value);
+        assert!(Handles::get::<u32>(handle).is_err());
+    }
+
+    #[test]
+    fn handles_work_for_invalid_handle() {
+        assert!(Handles::get::<u32>(INVALID_HANDLE).is_err());
+        assert!(Handles::get_mut::<u32>(INVALID_HANDLE).is_err());
+        assert!(Handles::free(INVALID_HANDLE).is_err());
+    }
+}
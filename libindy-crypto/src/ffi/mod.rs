@@ -1,7 +1,19 @@
 pub mod cl;
 pub mod bls;
+pub mod handles;
+
+use errors::ToErrorCode;
+use ffi::handles::{Handle, Handles};
+use utils::ctypes::CTypesUtils;
+use utils::logger::{CallbackLogger, LogCallback};
 
 use env_logger;
+use libc::c_char;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::ptr;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 #[repr(usize)]
@@ -67,9 +79,214 @@ pub enum ErrorCode
 
     // Proof rejected
     AnoncredsProofRejected = 118,
+
+    // Witness doesn't correspond to credential or revocation registry
+    AnoncredsWitnessMismatch = 119,
+
+    // Credential schema contains more attributes than supported
+    AnoncredsTooManyAttributes = 120,
+
+    // Library code panicked. Panic message can be got by indy_crypto_get_last_error
+    CommonPanic = 121,
+
+    // Deserialized point isn't on the curve or doesn't belong to the expected subgroup
+    CommonInvalidGroupElement = 122,
+}
+
+thread_local! {
+    // Kept until the next panic in the same thread, so pointers returned by indy_crypto_get_last_error stay valid
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+pub fn set_panic_error(err: Box<Any + Send>) -> ErrorCode {
+    let message = if let Some(message) = err.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = err.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    };
+
+    error!("Panic caught on FFI boundary: {}", message);
+
+    // Message is stored as C string, so it must not contain nul bytes
+    let message = CString::new(message.replace('\0', "\\0")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+
+    ErrorCode::CommonPanic
 }
 
+/// Returns message of the last panic caught in the current thread.
+///
+/// Note: Message is owned by the library and must not be freed by caller.
+/// It stays valid until the next panic is caught in the same thread.
+///
+/// # Arguments
+/// * `error_p` - Reference that will contain panic message or null if there were no panics.
+#[no_mangle]
+pub extern fn indy_crypto_get_last_error(error_p: *mut *const c_char) -> ErrorCode {
+    check_useful_c_ptr!(error_p, ErrorCode::CommonInvalidParam1);
+
+    LAST_ERROR.with(|last_error| {
+        let error = match *last_error.borrow() {
+            Some(ref message) => message.as_ptr(),
+            None => ptr::null()
+        };
+        unsafe { *error_p = error; }
+    });
+
+    ErrorCode::Success
+}
+
+/// Deallocates library object of any type by its handle.
+///
+/// # Arguments
+/// * `handle` - Handle of library object.
+#[no_mangle]
+pub extern fn indy_crypto_free_handle(handle: Handle) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_free_handle: >>> handle: {:?}", handle);
+
+        let res = match Handles::free(handle) {
+            Ok(()) => ErrorCode::Success,
+            Err(_) => ErrorCode::CommonInvalidParam1
+        };
+
+        trace!("indy_crypto_free_handle: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Deallocates byte buffer returned by the library.
+///
+/// Note: Only buffers allocated by the library (for example result of indy_crypto_cl_proof_to_bytes)
+/// can be passed here and each buffer must be passed exactly once.
+///
+/// # Arguments
+/// * `buffer` - Pointer to the first byte of buffer.
+/// * `buffer_len` - Buffer length as returned with the buffer.
+#[no_mangle]
+pub extern fn indy_crypto_buffer_free(buffer: *mut u8, buffer_len: usize) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_buffer_free: >>> buffer: {:?}, buffer_len: {:?}", buffer, buffer_len);
+
+        check_useful_c_ptr!(buffer, ErrorCode::CommonInvalidParam1);
+
+        unsafe { CTypesUtils::buffer_to_vec(buffer, buffer_len); }
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_buffer_free: <<< res: {:?}", res);
+        res
+    })
+}
+
+/// Initializes env logger.
+///
+/// Note: Call is ignored if logger is already set (e.g. by previous call or indy_crypto_set_logger).
 #[no_mangle]
 pub extern fn indy_crypto_init_logger() {
-    env_logger::init().unwrap();
+    let _: ErrorCode = catch_panic!({
+        match env_logger::init() {
+            Ok(()) => ErrorCode::Success,
+            Err(_) => ErrorCode::CommonInvalidState
+        }
+    });
+}
+
+/// Routes library log records to the host callback.
+///
+/// Note: Logger can be set only once per process, so it can't be combined with indy_crypto_init_logger.
+/// Operation timings are reported with debug level and `indy_crypto::timing` target
+/// as `operation=<name> elapsed_us=<microseconds>` messages.
+///
+/// # Arguments
+/// * `callback` - Callback that will be called for each log record. Calls are never concurrent.
+/// * `level` - Maximal level of records passed to callback: 0 - off, 1 - error, 2 - warn, 3 - info, 4 - debug, 5 - trace.
+#[no_mangle]
+pub extern fn indy_crypto_set_logger(callback: Option<LogCallback>, level: u32) -> ErrorCode {
+    catch_panic!({
+        trace!("indy_crypto_set_logger: >>> callback: {:?}, level: {:?}", callback.is_some(), level);
+
+        let callback = match callback {
+            Some(callback) => callback,
+            None => return ErrorCode::CommonInvalidParam1
+        };
+
+        let res = match CallbackLogger::init(callback, level) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.to_error_code()
+        };
+
+        trace!("indy_crypto_set_logger: <<< res: {:?}", res);
+        res
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_panic_works() {
+        let res: ErrorCode = catch_panic!({
+            panic!("Test panic");
+        });
+        assert_eq!(res, ErrorCode::CommonPanic);
+
+        let mut error_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_get_last_error(&mut error_p), ErrorCode::Success);
+        assert_eq!("Test panic", CTypesUtils::c_str_to_string(error_p).unwrap().unwrap());
+
+        let mut same_error_p: *const c_char = ptr::null();
+        assert_eq!(indy_crypto_get_last_error(&mut same_error_p), ErrorCode::Success);
+        assert_eq!(error_p, same_error_p);
+    }
+
+    #[test]
+    fn indy_crypto_init_logger_works_for_repeated_call() {
+        indy_crypto_init_logger();
+        indy_crypto_init_logger();
+    }
+
+    #[test]
+    fn indy_crypto_free_handle_works() {
+        let handle = Handles::insert(42u32);
+
+        assert_eq!(indy_crypto_free_handle(handle), ErrorCode::Success);
+        assert_eq!(indy_crypto_free_handle(handle), ErrorCode::CommonInvalidParam1);
+    }
+
+    #[test]
+    fn indy_crypto_set_logger_works_for_null_callback() {
+        assert_eq!(indy_crypto_set_logger(None, 5), ErrorCode::CommonInvalidParam1);
+    }
+
+    #[test]
+    fn indy_crypto_set_logger_works_for_invalid_level() {
+        extern fn callback(_level: u32, _target: *const c_char, _message: *const c_char,
+                           _module_path: *const c_char, _file: *const c_char, _line: u32) {}
+
+        assert_eq!(indy_crypto_set_logger(Some(callback), 6), ErrorCode::CommonInvalidParam2);
+    }
+
+    #[test]
+    fn indy_crypto_buffer_free_works() {
+        let (buffer, buffer_len) = CTypesUtils::vec_to_buffer(vec![1, 2, 3]);
+        assert_eq!(unsafe { ::std::slice::from_raw_parts(buffer, buffer_len) }, &[1, 2, 3]);
+
+        assert_eq!(indy_crypto_buffer_free(buffer as *mut u8, buffer_len), ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_buffer_free_works_for_empty_buffer() {
+        let (buffer, buffer_len) = CTypesUtils::vec_to_buffer(Vec::new());
+        assert_eq!(buffer_len, 0);
+
+        assert_eq!(indy_crypto_buffer_free(buffer as *mut u8, buffer_len), ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_buffer_free_works_for_null() {
+        assert_eq!(indy_crypto_buffer_free(ptr::null_mut(), 0), ErrorCode::CommonInvalidParam1);
+    }
 }
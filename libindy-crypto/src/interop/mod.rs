@@ -0,0 +1,2 @@
+#[cfg(feature = "interop_ursa")]
+pub mod ursa;
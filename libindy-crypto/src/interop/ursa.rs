@@ -0,0 +1,112 @@
+//! Conversions between credential structures of this crate and Hyperledger Ursa `cl` structures.
+//!
+//! Ursa anoncreds CL signatures were forked from this crate and keep the same serialized representation,
+//! so structures are converted through their JSON form. Conversion fails if structure contains data
+//! target format can't hold (e.g. security profile of key or linear predicates of proof),
+//! so nothing is silently dropped and credentials don't need to be re-issued after migration.
+
+use cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey, CredentialPublicKey, CredentialSignature, Proof,
+         RevocationKeyPublic, RevocationRegistry, SignatureCorrectnessProof, Witness};
+use errors::IndyCryptoError;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+use serde_json::Value;
+
+/// Structure that has counterpart in Hyperledger Ursa.
+pub trait UrsaConvertible: Sized {
+    type Ursa;
+
+    /// Converts structure to Ursa counterpart.
+    fn to_ursa(&self) -> Result<Self::Ursa, IndyCryptoError>;
+
+    /// Creates structure from Ursa counterpart.
+    fn from_ursa(value: &Self::Ursa) -> Result<Self, IndyCryptoError>;
+}
+
+macro_rules! impl_ursa_convertible {
+    ($t:ty, $ursa_t:ty) => {
+        impl UrsaConvertible for $t {
+            type Ursa = $ursa_t;
+
+            fn to_ursa(&self) -> Result<$ursa_t, IndyCryptoError> {
+                _convert(self)
+            }
+
+            fn from_ursa(value: &$ursa_t) -> Result<$t, IndyCryptoError> {
+                _convert(value)
+            }
+        }
+    }
+}
+
+impl_ursa_convertible!(CredentialPublicKey, ::ursa::cl::CredentialPublicKey);
+impl_ursa_convertible!(CredentialPrivateKey, ::ursa::cl::CredentialPrivateKey);
+impl_ursa_convertible!(CredentialKeyCorrectnessProof, ::ursa::cl::CredentialKeyCorrectnessProof);
+impl_ursa_convertible!(CredentialSignature, ::ursa::cl::CredentialSignature);
+impl_ursa_convertible!(SignatureCorrectnessProof, ::ursa::cl::SignatureCorrectnessProof);
+impl_ursa_convertible!(RevocationKeyPublic, ::ursa::cl::RevocationKeyPublic);
+impl_ursa_convertible!(RevocationRegistry, ::ursa::cl::RevocationRegistry);
+impl_ursa_convertible!(Witness, ::ursa::cl::Witness);
+impl_ursa_convertible!(Proof, ::ursa::cl::Proof);
+
+fn _convert<F, T>(value: &F) -> Result<T, IndyCryptoError> where F: Serialize, T: Serialize + DeserializeOwned {
+    let json = serde_json::to_value(value)?;
+    let converted: T = serde_json::from_value(json.clone())?;
+
+    // Fields unknown to target format are ignored by deserialization, so they are detected by comparing
+    // serialized forms. Absent optional fields and nulls are equivalent for both formats.
+    if _strip_nulls(serde_json::to_value(&converted)?) != _strip_nulls(json) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Structure contains data not supported by target format")));
+    }
+
+    Ok(converted)
+}
+
+fn _strip_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(map.into_iter()
+            .filter(|&(_, ref value)| !value.is_null())
+            .map(|(key, value)| (key, _strip_nulls(value)))
+            .collect()),
+        Value::Array(values) => Value::Array(values.into_iter().map(_strip_nulls).collect()),
+        value => value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::issuer;
+    use cl::prover;
+    use cl::SecurityProfile;
+    use cl::issuer::Issuer;
+
+    #[test]
+    fn credential_public_key_conversion_works() {
+        let pk = issuer::mocks::credential_public_key();
+        let ursa_pk = pk.to_ursa().unwrap();
+        assert_eq!(pk, CredentialPublicKey::from_ursa(&ursa_pk).unwrap());
+    }
+
+    #[test]
+    fn credential_public_key_conversion_works_for_security_profile() {
+        let (pk, _, _) = Issuer::new_credential_def_with_profile(&issuer::mocks::credential_schema(), false, SecurityProfile::modulus_3072()).unwrap();
+        assert!(pk.to_ursa().is_err());
+    }
+
+    #[test]
+    fn proof_conversion_works() {
+        let proof = prover::mocks::proof();
+        let ursa_proof = proof.to_ursa().unwrap();
+        assert_eq!(proof.fingerprint().unwrap(), Proof::from_ursa(&ursa_proof).unwrap().fingerprint().unwrap());
+    }
+
+    #[test]
+    fn strip_nulls_works() {
+        let value: Value = serde_json::from_str(r#"{"a":null,"b":[{"c":null,"d":1}]}"#).unwrap();
+        let expected: Value = serde_json::from_str(r#"{"b":[{"d":1}]}"#).unwrap();
+        assert_eq!(expected, _strip_nulls(value));
+    }
+}
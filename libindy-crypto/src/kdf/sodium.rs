@@ -0,0 +1,298 @@
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+use utils::rng::random_bytes;
+
+use libc::{c_char, c_int, c_ulonglong, size_t};
+use sha2::{Sha256, Digest};
+use sodiumoxide;
+
+use std::sync::{Once, ONCE_INIT};
+
+static SODIUM_INIT: Once = ONCE_INIT;
+
+fn _init() {
+    SODIUM_INIT.call_once(|| { sodiumoxide::init(); });
+}
+
+extern {
+    fn crypto_pwhash(out: *mut u8,
+                     outlen: c_ulonglong,
+                     passwd: *const c_char,
+                     passwdlen: c_ulonglong,
+                     salt: *const u8,
+                     opslimit: c_ulonglong,
+                     memlimit: size_t,
+                     alg: c_int) -> c_int;
+}
+
+const ARGON2ID13: c_int = 2;
+
+const HASH_SIZE: usize = 32;
+const KEY_SIZE: usize = 32;
+const HMAC_BLOCK_SIZE: usize = 64;
+
+/// Size of salt expected by `derive_key`.
+pub const SALT_SIZE: usize = 16;
+
+/// Maximal length of HKDF output for SHA-256.
+pub const HKDF_MAX_LEN: usize = 255 * HASH_SIZE;
+
+/// Argon2id parameters. Must be stored together with salt to derive the same key again.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct KdfParams {
+    ops_limit: u64,
+    mem_limit: usize
+}
+
+impl KdfParams {
+    pub const MIN_OPS_LIMIT: u64 = 1;
+    pub const MIN_MEM_LIMIT: usize = 8192;
+
+    /// Creates Argon2id parameters.
+    ///
+    /// # Arguments
+    /// * `ops_limit` - Count of passes over memory.
+    /// * `mem_limit` - Memory in bytes.
+    pub fn new(ops_limit: u64, mem_limit: usize) -> Result<KdfParams, IndyCryptoError> {
+        if ops_limit < Self::MIN_OPS_LIMIT || mem_limit < Self::MIN_MEM_LIMIT {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid KDF params: ops_limit {}, mem_limit {}", ops_limit, mem_limit)));
+        }
+
+        Ok(KdfParams { ops_limit, mem_limit })
+    }
+
+    /// Parameters for online operations (64 MiB, 2 passes).
+    pub fn interactive() -> KdfParams {
+        KdfParams { ops_limit: 2, mem_limit: 67_108_864 }
+    }
+
+    /// Parameters for wallet keys (256 MiB, 3 passes).
+    pub fn moderate() -> KdfParams {
+        KdfParams { ops_limit: 3, mem_limit: 268_435_456 }
+    }
+
+    /// Parameters for highly sensitive non-interactive operations (1 GiB, 4 passes).
+    pub fn sensitive() -> KdfParams {
+        KdfParams { ops_limit: 4, mem_limit: 1_073_741_824 }
+    }
+
+    pub fn ops_limit(&self) -> u64 {
+        self.ops_limit
+    }
+
+    pub fn mem_limit(&self) -> usize {
+        self.mem_limit
+    }
+}
+
+impl JsonEncodable for KdfParams {}
+
+impl<'a> JsonDecodable<'a> for KdfParams {}
+
+/// Fixed-size 256-bit key.
+#[derive(Clone, PartialEq)]
+pub struct DerivedKey {
+    key: [u8; KEY_SIZE]
+}
+
+impl DerivedKey {
+    pub const BYTES_REPR_SIZE: usize = KEY_SIZE;
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Derives independent subkey for `info` (for example purpose of key in wallet) with HKDF-Expand.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::kdf::*;
+    /// let key = derive_key(b"passphrase", &generate_salt().unwrap(), &KdfParams::new(1, 8192).unwrap()).unwrap();
+    /// let storage_key = key.expand(b"storage").unwrap();
+    /// assert_ne!(key.as_bytes(), storage_key.as_bytes());
+    /// ```
+    pub fn expand(&self, info: &[u8]) -> Result<DerivedKey, IndyCryptoError> {
+        let mut key = [0u8; KEY_SIZE];
+        key.copy_from_slice(&hkdf_expand(&self.key, info, DerivedKey::BYTES_REPR_SIZE)?);
+        Ok(DerivedKey { key })
+    }
+}
+
+impl ::std::fmt::Debug for DerivedKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "DerivedKey {{ .. }}")
+    }
+}
+
+/// Returns random salt for `derive_key`.
+pub fn generate_salt() -> Result<Vec<u8>, IndyCryptoError> {
+    random_bytes(SALT_SIZE)
+}
+
+/// Derives key from passphrase with Argon2id.
+///
+/// # Arguments
+/// * `passphrase` - Passphrase.
+/// * `salt` - Random salt of `SALT_SIZE` bytes.
+/// * `params` - Argon2id parameters.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::kdf::*;
+/// let salt = generate_salt().unwrap();
+/// let params = KdfParams::new(1, 8192).unwrap();
+///
+/// let key1 = derive_key(b"passphrase", &salt, &params).unwrap();
+/// let key2 = derive_key(b"passphrase", &salt, &params).unwrap();
+/// assert_eq!(key1, key2);
+/// ```
+pub fn derive_key(passphrase: &[u8], salt: &[u8], params: &KdfParams) -> Result<DerivedKey, IndyCryptoError> {
+    trace!("kdf::derive_key: >>> params: {:?}", params);
+
+    if salt.len() != SALT_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of salt: expected {}, actual {}", SALT_SIZE, salt.len())));
+    }
+
+    _init();
+
+    let mut key = [0u8; KEY_SIZE];
+
+    let res = unsafe {
+        crypto_pwhash(key.as_mut_ptr(),
+                      key.len() as c_ulonglong,
+                      passphrase.as_ptr() as *const c_char,
+                      passphrase.len() as c_ulonglong,
+                      salt.as_ptr(),
+                      params.ops_limit as c_ulonglong,
+                      params.mem_limit as size_t,
+                      ARGON2ID13)
+    };
+
+    if res != 0 {
+        return Err(IndyCryptoError::InvalidState(format!("Argon2id key derivation failed")));
+    }
+
+    trace!("kdf::derive_key: <<<");
+
+    Ok(DerivedKey { key })
+}
+
+/// HKDF-SHA256 (RFC 5869) extract and expand steps.
+///
+/// # Arguments
+/// * `ikm` - Input key material.
+/// * `salt` - Optional salt.
+/// * `info` - Context of derived key.
+/// * `len` - Length of output, at most `HKDF_MAX_LEN`.
+pub fn hkdf(ikm: &[u8], salt: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, IndyCryptoError> {
+    let prk = hmac_sha256(salt, &[ikm]);
+    hkdf_expand(&prk, info, len)
+}
+
+/// HKDF-SHA256 (RFC 5869) expand step for pseudorandom key.
+pub fn hkdf_expand(prk: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, IndyCryptoError> {
+    if len > HKDF_MAX_LEN {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of HKDF output: expected at most {}, actual {}", HKDF_MAX_LEN, len)));
+    }
+
+    let mut okm: Vec<u8> = Vec::new();
+    let mut block: Vec<u8> = Vec::new();
+    let mut counter = 1u8;
+
+    while okm.len() < len {
+        block = hmac_sha256(prk, &[&block, info, &[counter]]);
+        okm.extend_from_slice(&block);
+        counter = counter.wrapping_add(1);
+    }
+
+    okm.truncate(len);
+    Ok(okm)
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut key_block = [0u8; HMAC_BLOCK_SIZE];
+
+    if key.len() > HMAC_BLOCK_SIZE {
+        let mut hasher = Sha256::default();
+        hasher.input(key);
+        key_block[..HASH_SIZE].copy_from_slice(hasher.result().as_slice());
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::default();
+    inner.input(&key_block.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+    for part in data.iter() {
+        inner.input(part);
+    }
+
+    let mut outer = Sha256::default();
+    outer.input(&key_block.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+    outer.input(inner.result().as_slice());
+
+    outer.result().as_slice().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len() / 2).map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn hkdf_works_for_rfc5869_test_case_1() {
+        let ikm = vec![0x0b; 22];
+        let salt = _from_hex("000102030405060708090a0b0c");
+        let info = _from_hex("f0f1f2f3f4f5f6f7f8f9");
+
+        let okm = hkdf(&ikm, &salt, &info, 42).unwrap();
+        assert_eq!(_from_hex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"), okm);
+    }
+
+    #[test]
+    fn hkdf_works_for_rfc5869_test_case_3() {
+        let okm = hkdf(&vec![0x0b; 22], &[], &[], 42).unwrap();
+        assert_eq!(_from_hex("8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"), okm);
+    }
+
+    #[test]
+    fn hkdf_expand_works_for_too_long_output() {
+        assert!(hkdf_expand(&[1; 32], &[], HKDF_MAX_LEN + 1).is_err());
+    }
+
+    #[test]
+    fn derive_key_works_for_different_salt() {
+        let params = KdfParams::new(1, 8192).unwrap();
+
+        let key1 = derive_key(b"passphrase", &generate_salt().unwrap(), &params).unwrap();
+        let key2 = derive_key(b"passphrase", &generate_salt().unwrap(), &params).unwrap();
+        assert_ne!(key1, key2);
+    }
+
+    #[test]
+    fn derive_key_works_for_invalid_salt() {
+        assert!(derive_key(b"passphrase", &[1, 2, 3], &KdfParams::new(1, 8192).unwrap()).is_err());
+    }
+
+    #[test]
+    fn kdf_params_new_works_for_invalid_params() {
+        assert!(KdfParams::new(0, 8192).is_err());
+        assert!(KdfParams::new(1, 1024).is_err());
+    }
+
+    #[test]
+    fn kdf_params_serialization_works() {
+        let params = KdfParams::moderate();
+        let params_json = params.to_json().unwrap();
+
+        assert_eq!(r#"{"ops_limit":3,"mem_limit":268435456}"#, params_json);
+        assert_eq!(params, KdfParams::from_json(&params_json).unwrap());
+    }
+}
@@ -4,6 +4,7 @@ extern crate env_logger;
 extern crate log;
 extern crate rand;
 extern crate sha2;
+extern crate sha3;
 
 // To use macros from util inside of other modules it must me loaded first.
 #[macro_use]
@@ -26,17 +27,53 @@ extern crate openssl;
 #[cfg(feature = "bn_openssl")]
 extern crate int_traits;
 
+#[cfg(any(feature = "box_sodium", feature = "sign_sodium", feature = "kdf_sodium"))]
+extern crate sodiumoxide;
+
+#[cfg(feature = "verifier_core")]
+pub extern crate indy_crypto_verifier_core as verifier_core;
+
+#[cfg(feature = "interop_ursa")]
+extern crate ursa;
+
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 extern crate libc;
 
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+
 extern crate time;
 
+pub mod accumulator;
 pub mod cl;
 pub mod bls;
+pub mod bbs;
+pub mod interop;
+pub mod commitments;
+pub mod sigma;
+pub mod range_proof;
+pub mod sss;
+pub mod test_vectors;
 
 #[cfg(feature = "bn_openssl")]
 #[path = "bn/openssl.rs"]
 pub mod bn;
 
+#[cfg(feature = "box_sodium")]
+#[path = "encryption/sodium.rs"]
+pub mod encryption;
+
+#[cfg(feature = "sign_sodium")]
+#[path = "ed25519/sodium.rs"]
+pub mod ed25519;
+
+#[cfg(feature = "kdf_sodium")]
+#[path = "kdf/sodium.rs"]
+pub mod kdf;
+
 pub mod errors;
 pub mod ffi;
 
@@ -1,4 +1,5 @@
 use errors::IndyCryptoError;
+use utils::rng::random_bytes;
 
 use amcl::big::BIG;
 
@@ -20,9 +21,6 @@ use amcl::fp2::FP2;
 use amcl::pair::{ate, g1mul, g2mul, gtpow, fexp};
 use amcl::rand::RAND;
 
-use rand::os::OsRng;
-use rand::Rng;
-
 #[cfg(feature = "serialization")]
 use serde::ser::{Serialize, Serializer, Error as SError};
 #[cfg(feature = "serialization")]
@@ -30,10 +28,16 @@ use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
 #[cfg(feature = "serialization")]
 use std::fmt;
 
+const COMPRESSED_FLAG: u8 = 0x02;
+
+/// Cofactor of G2 subgroup of the twist curve (2 * p - r for BN curves), big-endian.
+const G2_COFACTOR: [u8; 32] = [
+    0x25, 0x23, 0x64, 0x82, 0x40, 0x00, 0x00, 0x01, 0xba, 0x34, 0x4d, 0x80, 0x00, 0x00, 0x00, 0x08,
+    0xc2, 0xa2, 0x80, 0x00, 0x00, 0x00, 0x00, 0x16, 0xad, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x19
+];
+
 fn random_mod_order() -> Result<BIG, IndyCryptoError> {
-    let mut seed = vec![0; MODBYTES];
-    let mut os_rng = OsRng::new().unwrap();
-    os_rng.fill_bytes(&mut seed.as_mut_slice());
+    let seed = random_bytes(MODBYTES)?;
     let mut rng = RAND::new();
     rng.clean();
     rng.seed(MODBYTES, &seed);
@@ -47,6 +51,7 @@ pub struct PointG1 {
 
 impl PointG1 {
     pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const BYTES_REPR_COMPRESSED_SIZE: usize = MODBYTES + 1;
 
     /// Creates new random PointG1
     pub fn new() -> Result<PointG1, IndyCryptoError> {
@@ -106,6 +111,26 @@ impl PointG1 {
         })
     }
 
+    /// PointG1_1 ^ GroupOrderElement_1 * ... * PointG1_n ^ GroupOrderElement_n
+    pub fn multi_scalar_mul(points: &[PointG1], scalars: &[GroupOrderElement]) -> Result<PointG1, IndyCryptoError> {
+        if points.len() != scalars.len() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Points and scalars count mismatch")));
+        }
+
+        let mut r = ECP::new();
+        r.inf();
+
+        for (p, e) in points.iter().zip(scalars.iter()) {
+            let mut point = p.point;
+            let mut bn = e.bn;
+            r.add(&mut g1mul(&mut point, &mut bn));
+        }
+
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
     /// 1 / PointG1
     pub fn neg(&self) -> Result<PointG1, IndyCryptoError> {
         let mut r = self.point;
@@ -120,9 +145,7 @@ impl PointG1 {
     }
 
     pub fn from_string(str: &str) -> Result<PointG1, IndyCryptoError> {
-        Ok(PointG1 {
-            point: ECP::from_hex(str.to_string())
-        })
+        PointG1::_checked(ECP::from_hex(str.to_string()))
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
@@ -137,11 +160,62 @@ impl PointG1 {
             return Err(IndyCryptoError::InvalidStructure(
                 "Invalid len of bytes representation".to_string()));
         }
-        Ok(
-            PointG1 {
-                point: ECP::frombytes(b)
-            }
-        )
+
+        let mut point = ECP::frombytes(b);
+
+        // Bytes that aren't a curve point are decoded to infinity
+        if point.is_infinity() && b != PointG1::new_inf()?.to_bytes()?.as_slice() {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "Bytes representation isn't a PointG1".to_string()));
+        }
+
+        PointG1::_checked(point)
+    }
+
+    /// Serializes point as flag byte followed by x coordinate.
+    /// Flag is 0x00 for infinity, otherwise 0x02 | parity of y.
+    pub fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut point = self.point;
+        let mut vec = vec![0u8; Self::BYTES_REPR_COMPRESSED_SIZE];
+
+        if point.is_infinity() {
+            return Ok(vec);
+        }
+
+        let mut x = point.getx();
+        let y = point.gety();
+
+        vec[0] = COMPRESSED_FLAG | (y.parity() as u8);
+        x.tobytes(&mut vec[1..]);
+        Ok(vec)
+    }
+
+    pub fn from_bytes_compressed(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_COMPRESSED_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of compressed bytes representation".to_string()));
+        }
+
+        if b[0] == 0 && b[1..].iter().all(|byte| *byte == 0) {
+            return PointG1::new_inf();
+        }
+
+        if b[0] & !1 != COMPRESSED_FLAG {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid flag of compressed bytes representation".to_string()));
+        }
+
+        let x = BIG::frombytes(&b[1..]);
+        let mut point = ECP::new_bigint(&x, (b[0] & 1) as isize);
+
+        if point.is_infinity() {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "Compressed bytes representation isn't a curve point".to_string()));
+        }
+
+        Ok(PointG1 {
+            point: point
+        })
     }
 
     pub fn from_hash(hash: &[u8]) -> Result<PointG1, IndyCryptoError> {
@@ -157,6 +231,25 @@ impl PointG1 {
             point: point
         })
     }
+
+    /// Checks that decoded point is on the curve.
+    /// G1 of BN curves has prime order, so any curve point belongs to the group.
+    fn _checked(point: ECP) -> Result<PointG1, IndyCryptoError> {
+        let mut r = point;
+
+        if !r.is_infinity() {
+            let mut affine = ECP::new_bigs(&r.getx(), &r.gety());
+
+            if affine.is_infinity() {
+                return Err(IndyCryptoError::InvalidGroupElement(
+                    "PointG1 isn't on the curve".to_string()));
+            }
+        }
+
+        Ok(PointG1 {
+            point: point
+        })
+    }
 }
 
 #[cfg(feature = "serialization")]
@@ -196,6 +289,7 @@ pub struct PointG2 {
 
 impl PointG2 {
     pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const BYTES_REPR_COMPRESSED_SIZE: usize = MODBYTES * 2 + 1;
 
     /// Creates new random PointG2
     pub fn new() -> Result<PointG2, IndyCryptoError> {
@@ -226,6 +320,60 @@ impl PointG2 {
         })
     }
 
+    /// Checks infinity
+    pub fn is_inf(&self) -> Result<bool, IndyCryptoError> {
+        let mut r = self.point;
+        Ok(r.is_infinity())
+    }
+
+    /// Checks that point isn't infinity and belongs to the prime order subgroup G2.
+    /// Points of the twist curve outside of G2 are rejected.
+    pub fn is_in_subgroup(&self) -> Result<bool, IndyCryptoError> {
+        let mut point = self.point;
+        if point.is_infinity() {
+            return Ok(false);
+        }
+
+        let mut order = vec![0u8; MODBYTES];
+        BIG::new_ints(&CURVE_ORDER).tobytes(&mut order);
+
+        // g2mul relies on point being in G2 already, so generic double-and-add is used here
+        let mut r = PointG2::_mul_by_bytes(&self.point, &order);
+        Ok(r.is_infinity())
+    }
+
+    /// Deterministically maps hash to PointG2.
+    /// Hash is interpreted as x coordinate (a and b parts) that is incremented until
+    /// it gives a twist curve point, then the point is moved to G2 by cofactor clearing.
+    ///
+    /// # Arguments
+    /// * `hash` - Hash bytes of 2 * MODBYTES length.
+    pub fn from_hash(hash: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        if hash.len() != MODBYTES * 2 {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of hash for mapping to PointG2".to_string()));
+        }
+
+        let mut xa = BIG::frombytes(&hash[..MODBYTES]);
+        let xb = BIG::frombytes(&hash[MODBYTES..]);
+
+        loop {
+            let mut point = ECP2::new_fp2(&FP2::new_bigs(&xa, &xb));
+
+            if !point.is_infinity() {
+                let mut point = PointG2::_mul_by_bytes(&point, &G2_COFACTOR);
+
+                if !point.is_infinity() {
+                    return Ok(PointG2 {
+                        point: point
+                    });
+                }
+            }
+
+            xa.inc(1);
+        }
+    }
+
     /// PointG2 * PointG2
     pub fn add(&self, q: &PointG2) -> Result<PointG2, IndyCryptoError> {
         let mut r = self.point;
@@ -257,14 +405,32 @@ impl PointG2 {
         })
     }
 
+    /// PointG2_1 ^ GroupOrderElement_1 * ... * PointG2_n ^ GroupOrderElement_n
+    pub fn multi_scalar_mul(points: &[PointG2], scalars: &[GroupOrderElement]) -> Result<PointG2, IndyCryptoError> {
+        if points.len() != scalars.len() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Points and scalars count mismatch")));
+        }
+
+        let mut r = ECP2::new();
+        r.inf();
+
+        for (p, e) in points.iter().zip(scalars.iter()) {
+            let mut point = p.point;
+            let mut bn = e.bn;
+            r.add(&mut g2mul(&mut point, &mut bn));
+        }
+
+        Ok(PointG2 {
+            point: r
+        })
+    }
+
     pub fn to_string(&self) -> Result<String, IndyCryptoError> {
         Ok(self.point.to_hex())
     }
 
     pub fn from_string(str: &str) -> Result<PointG2, IndyCryptoError> {
-        Ok(PointG2 {
-            point: ECP2::from_hex(str.to_string())
-        })
+        PointG2::_checked(ECP2::from_hex(str.to_string()))
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
@@ -279,11 +445,123 @@ impl PointG2 {
             return Err(IndyCryptoError::InvalidStructure(
                 "Invalid len of bytes representation".to_string()));
         }
-        Ok(
-            PointG2 {
-                point: ECP2::frombytes(b)
+
+        let mut point = ECP2::frombytes(b);
+
+        // Bytes that aren't a curve point are decoded to infinity
+        if point.is_infinity() && b != PointG2::new_inf()?.to_bytes()?.as_slice() {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "Bytes representation isn't a PointG2".to_string()));
+        }
+
+        PointG2::_checked(point)
+    }
+
+    /// Serializes point as flag byte followed by x coordinate (a and b parts).
+    /// Flag is 0x00 for infinity, otherwise 0x02 | sign of y, where sign of y
+    /// is parity of y.a or parity of y.b if y.a is zero.
+    pub fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut point = self.point;
+        let mut vec = vec![0u8; Self::BYTES_REPR_COMPRESSED_SIZE];
+
+        if point.is_infinity() {
+            return Ok(vec);
+        }
+
+        let mut x = point.getx();
+        let mut y = point.gety();
+
+        vec[0] = COMPRESSED_FLAG | PointG2::_sign(&mut y);
+        x.geta().tobytes(&mut vec[1..MODBYTES + 1]);
+        x.getb().tobytes(&mut vec[MODBYTES + 1..]);
+        Ok(vec)
+    }
+
+    pub fn from_bytes_compressed(b: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_COMPRESSED_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of compressed bytes representation".to_string()));
+        }
+
+        if b[0] == 0 && b[1..].iter().all(|byte| *byte == 0) {
+            return PointG2::new_inf();
+        }
+
+        if b[0] & !1 != COMPRESSED_FLAG {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid flag of compressed bytes representation".to_string()));
+        }
+
+        let x = FP2::new_bigs(&BIG::frombytes(&b[1..MODBYTES + 1]), &BIG::frombytes(&b[MODBYTES + 1..]));
+        let mut point = ECP2::new_fp2(&x);
+
+        if point.is_infinity() {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "Compressed bytes representation isn't a curve point".to_string()));
+        }
+
+        let mut y = point.gety();
+        if PointG2::_sign(&mut y) != b[0] & 1 {
+            point.neg();
+        }
+
+        PointG2::_checked(point)
+    }
+
+    /// Checks that decoded point is on the twist curve and belongs to G2.
+    /// Unlike G1, the twist curve has a large cofactor, so subgroup membership is checked too.
+    fn _checked(point: ECP2) -> Result<PointG2, IndyCryptoError> {
+        let mut r = point;
+
+        if r.is_infinity() {
+            return Ok(PointG2 {
+                point: point
+            });
+        }
+
+        let mut affine = ECP2::new_fp2s(&r.getx(), &r.gety());
+
+        if affine.is_infinity() {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "PointG2 isn't on the curve".to_string()));
+        }
+
+        let point = PointG2 {
+            point: point
+        };
+
+        if !point.is_in_subgroup()? {
+            return Err(IndyCryptoError::InvalidGroupElement(
+                "PointG2 doesn't belong to G2 subgroup".to_string()));
+        }
+
+        Ok(point)
+    }
+
+    fn _mul_by_bytes(point: &ECP2, scalar: &[u8]) -> ECP2 {
+        let mut r = ECP2::new();
+        r.inf();
+
+        for byte in scalar {
+            for i in (0..8).rev() {
+                r.dbl();
+                if (byte >> i) & 1 == 1 {
+                    let mut p = *point;
+                    r.add(&mut p);
+                }
             }
-        )
+        }
+
+        r
+    }
+
+    fn _sign(y: &mut FP2) -> u8 {
+        let a = y.geta();
+        if a.iszilch() {
+            y.getb().parity() as u8
+        } else {
+            a.parity() as u8
+        }
     }
 }
 
@@ -347,6 +625,13 @@ impl GroupOrderElement {
         })
     }
 
+    /// Returns group order as big-endian bytes.
+    pub fn order_bytes() -> Vec<u8> {
+        let mut order = vec![0u8; MODBYTES];
+        BIG::new_ints(&CURVE_ORDER).tobytes(&mut order);
+        order
+    }
+
     /// (GroupOrderElement ^ GroupOrderElement) mod GroupOrder
     pub fn pow_mod(&self, e: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
         let mut base = self.bn;
@@ -453,6 +738,23 @@ impl GroupOrderElement {
             }
         )
     }
+    /// Serializes element as big-endian bytes without leading zeros.
+    pub fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let bytes = self.to_bytes()?;
+        let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+        Ok(bytes[zeros..].to_vec())
+    }
+
+    pub fn from_bytes_compressed(b: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+        let element = GroupOrderElement::from_bytes(b)?;
+
+        if BIG::comp(&element.bn, &BIG::new_ints(&CURVE_ORDER)) >= 0 {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Compressed bytes representation isn't a group order element".to_string()));
+        }
+
+        Ok(element)
+    }
 }
 
 #[cfg(feature = "serialization")]
@@ -504,6 +806,18 @@ impl Pair {
         })
     }
 
+    /// e(PointG1_1, PointG2_1) * ... * e(PointG1_n, PointG2_n)
+    /// Miller loops outputs are multiplied before final exponentiation, so it is computed once.
+    pub fn product_of_pairings(pairs: &[(PointG1, PointG2)]) -> Result<Pair, IndyCryptoError> {
+        let mut acc = MillerAccumulator::new();
+
+        for &(ref p, ref q) in pairs.iter() {
+            acc.add_pair(p, q)?;
+        }
+
+        acc.finalize()
+    }
+
     /// e() * e()
     pub fn mul(&self, b: &Pair) -> Result<Pair, IndyCryptoError> {
         let mut base = self.pair;
@@ -552,6 +866,52 @@ impl Pair {
     }
 }
 
+/// Accumulator of Miller loops of pairings.
+/// Product of accumulated pairings is computed with a single final exponentiation,
+/// that is the most expensive part of pairing computation.
+#[derive(Debug, Copy, Clone)]
+pub struct MillerAccumulator {
+    acc: Option<FP12>
+}
+
+impl MillerAccumulator {
+    pub fn new() -> MillerAccumulator {
+        MillerAccumulator {
+            acc: None
+        }
+    }
+
+    /// Accumulates Miller loop of e(PointG1, PointG2)
+    pub fn add_pair(&mut self, p: &PointG1, q: &PointG2) -> Result<(), IndyCryptoError> {
+        let mut p_new = *p;
+        let mut q_new = *q;
+        let mut miller = ate(&mut q_new.point, &mut p_new.point);
+
+        self.acc = Some(match self.acc {
+            Some(mut acc) => {
+                acc.mul(&mut miller);
+                acc
+            }
+            None => miller
+        });
+
+        Ok(())
+    }
+
+    /// Returns product of accumulated pairings.
+    pub fn finalize(&self) -> Result<Pair, IndyCryptoError> {
+        let acc = self.acc
+            .ok_or(IndyCryptoError::InvalidState(format!("No pairings accumulated")))?;
+
+        let mut result = fexp(&acc);
+        result.reduce();
+
+        Ok(Pair {
+            pair: result
+        })
+    }
+}
+
 #[cfg(feature = "serialization")]
 impl Serialize for Pair {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
@@ -582,6 +942,93 @@ impl<'a> Deserialize<'a> for Pair {
     }
 }
 
+/// Serde helpers to serialize `PointG1`, `PointG2` and `GroupOrderElement` fields as hex of
+/// compressed bytes representation: `#[serde(with = "pair::compressed")]`.
+/// Deserialization also accepts legacy uncompressed representation.
+#[cfg(feature = "serialization")]
+pub mod compressed {
+    use super::*;
+
+    pub trait CompressedRepr: Sized {
+        fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError>;
+
+        fn from_bytes_compressed(b: &[u8]) -> Result<Self, IndyCryptoError>;
+
+        fn from_legacy_string(str: &str) -> Result<Self, IndyCryptoError>;
+    }
+
+    impl CompressedRepr for PointG1 {
+        fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+            PointG1::to_bytes_compressed(self)
+        }
+
+        fn from_bytes_compressed(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+            PointG1::from_bytes_compressed(b)
+        }
+
+        fn from_legacy_string(str: &str) -> Result<PointG1, IndyCryptoError> {
+            PointG1::from_string(str)
+        }
+    }
+
+    impl CompressedRepr for PointG2 {
+        fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+            PointG2::to_bytes_compressed(self)
+        }
+
+        fn from_bytes_compressed(b: &[u8]) -> Result<PointG2, IndyCryptoError> {
+            PointG2::from_bytes_compressed(b)
+        }
+
+        fn from_legacy_string(str: &str) -> Result<PointG2, IndyCryptoError> {
+            PointG2::from_string(str)
+        }
+    }
+
+    impl CompressedRepr for GroupOrderElement {
+        fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+            GroupOrderElement::to_bytes_compressed(self)
+        }
+
+        fn from_bytes_compressed(b: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+            GroupOrderElement::from_bytes_compressed(b)
+        }
+
+        fn from_legacy_string(str: &str) -> Result<GroupOrderElement, IndyCryptoError> {
+            GroupOrderElement::from_string(str)
+        }
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error> where T: CompressedRepr, S: Serializer {
+        let bytes = value.to_bytes_compressed().map_err(SError::custom)?;
+        let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'a, T, D>(deserializer: D) -> Result<T, D::Error> where T: CompressedRepr, D: Deserializer<'a> {
+        let value: String = Deserialize::deserialize(deserializer)?;
+
+        // Legacy representation consists of space separated hex limbs
+        if value.contains(' ') {
+            return T::from_legacy_string(&value).map_err(DError::custom);
+        }
+
+        T::from_bytes_compressed(&_decode_hex(&value).map_err(DError::custom)?).map_err(DError::custom)
+    }
+
+    fn _decode_hex(hex: &str) -> Result<Vec<u8>, IndyCryptoError> {
+        if hex.len() % 2 != 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid hex string: {}", hex)));
+        }
+
+        hex.as_bytes().chunks(2)
+            .map(|chunk| ::std::str::from_utf8(chunk).ok()
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid hex string: {}", hex))))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -605,6 +1052,72 @@ mod tests {
         assert_eq!(left, right);
     }
 
+    #[test]
+    fn point_g1_multi_scalar_mul_works() {
+        let points = [PointG1::new().unwrap(), PointG1::new().unwrap(), PointG1::new().unwrap()];
+        let scalars = [GroupOrderElement::new().unwrap(), GroupOrderElement::new().unwrap(), GroupOrderElement::new().unwrap()];
+
+        let expected = points[0].mul(&scalars[0]).unwrap()
+            .add(&points[1].mul(&scalars[1]).unwrap()).unwrap()
+            .add(&points[2].mul(&scalars[2]).unwrap()).unwrap();
+
+        assert_eq!(expected.to_bytes().unwrap(), PointG1::multi_scalar_mul(&points, &scalars).unwrap().to_bytes().unwrap());
+        assert!(PointG1::multi_scalar_mul(&points, &scalars[..2]).is_err());
+    }
+
+    #[test]
+    fn point_g2_multi_scalar_mul_works() {
+        let points = [PointG2::new().unwrap(), PointG2::new().unwrap()];
+        let scalars = [GroupOrderElement::new().unwrap(), GroupOrderElement::new().unwrap()];
+
+        let expected = points[0].mul(&scalars[0]).unwrap()
+            .add(&points[1].mul(&scalars[1]).unwrap()).unwrap();
+
+        assert_eq!(expected.to_bytes().unwrap(), PointG2::multi_scalar_mul(&points, &scalars).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn product_of_pairings_works() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+
+        let expected = Pair::pair(&p1, &q1).unwrap().mul(&Pair::pair(&p2, &q2).unwrap()).unwrap();
+
+        assert_eq!(expected, Pair::product_of_pairings(&[(p1, q1), (p2, q2)]).unwrap());
+        assert_eq!(Pair::pair(&p1, &q1).unwrap(), Pair::product_of_pairings(&[(p1, q1)]).unwrap());
+        assert!(Pair::product_of_pairings(&[]).is_err());
+    }
+
+    #[test]
+    fn miller_accumulator_works() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+
+        let mut acc = MillerAccumulator::new();
+        assert!(acc.finalize().is_err());
+
+        acc.add_pair(&p1, &q1).unwrap();
+        assert_eq!(Pair::pair(&p1, &q1).unwrap(), acc.finalize().unwrap());
+
+        acc.add_pair(&p2, &q2).unwrap();
+        let expected = Pair::pair(&p1, &q1).unwrap().mul(&Pair::pair(&p2, &q2).unwrap()).unwrap();
+        assert_eq!(expected, acc.finalize().unwrap());
+    }
+
+    #[test]
+    fn product_of_pairings_works_for_inverse() {
+        let p = PointG1::new().unwrap();
+        let q = PointG2::new().unwrap();
+
+        let expected = Pair::pair(&p, &q).unwrap().inverse().unwrap();
+
+        assert_eq!(expected.to_bytes().unwrap(), Pair::product_of_pairings(&[(p.neg().unwrap(), q)]).unwrap().to_bytes().unwrap());
+    }
+
     #[test]
     fn point_g1_infinity_test() {
         let p = PointG1::new_inf().unwrap();
@@ -622,6 +1135,91 @@ mod tests {
         assert_eq!(q, result);
     }
 
+    #[test]
+    fn point_g2_from_hash_works() {
+        let hash = [7u8; MODBYTES * 2];
+        let point1 = PointG2::from_hash(&hash).unwrap();
+        let point2 = PointG2::from_hash(&hash).unwrap();
+        assert_eq!(point1, point2);
+        assert!(point1.is_in_subgroup().unwrap());
+
+        let point3 = PointG2::from_hash(&[8u8; MODBYTES * 2]).unwrap();
+        assert_ne!(point1, point3);
+    }
+
+    #[test]
+    fn point_g2_from_hash_works_for_invalid_hash_len() {
+        let err = PointG2::from_hash(&[7u8; MODBYTES]).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidStructure);
+    }
+
+    #[test]
+    fn point_g2_is_in_subgroup_works() {
+        assert!(PointG2::new().unwrap().is_in_subgroup().unwrap());
+        assert!(!PointG2::new_inf().unwrap().is_in_subgroup().unwrap());
+    }
+
+    #[test]
+    fn point_g2_is_in_subgroup_works_for_point_outside_subgroup() {
+        let mut x = BIG::new_int(1);
+        let mut point = ECP2::new_fp2(&FP2::new_bigs(&x, &BIG::new_int(0)));
+
+        while point.is_infinity() {
+            x.inc(1);
+            point = ECP2::new_fp2(&FP2::new_bigs(&x, &BIG::new_int(0)));
+        }
+
+        assert!(!PointG2 { point: point }.is_in_subgroup().unwrap());
+    }
+
+    #[test]
+    fn from_bytes_works_for_point_g1_not_on_curve() {
+        let mut bytes = PointG1::new().unwrap().to_bytes().unwrap();
+        bytes[10] ^= 0xFF;
+        let err = PointG1::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidGroupElement);
+    }
+
+    #[test]
+    fn from_string_works_for_point_g1_not_on_curve() {
+        let string = PointG1::new().unwrap().to_string().unwrap();
+        let mut parts: Vec<&str> = string.split(' ').collect();
+        parts[1] = "1";
+        let err = PointG1::from_string(&parts.join(" ")).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidGroupElement);
+    }
+
+    #[test]
+    fn from_bytes_works_for_point_infinity() {
+        let p = PointG1::new_inf().unwrap();
+        assert_eq!(p, PointG1::from_bytes(&p.to_bytes().unwrap()).unwrap());
+
+        let q = PointG2::new_inf().unwrap();
+        assert_eq!(q, PointG2::from_bytes(&q.to_bytes().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn from_bytes_works_for_point_g2_outside_subgroup() {
+        let mut x = BIG::new_int(1);
+        let mut point = ECP2::new_fp2(&FP2::new_bigs(&x, &BIG::new_int(0)));
+
+        while point.is_infinity() {
+            x.inc(1);
+            point = ECP2::new_fp2(&FP2::new_bigs(&x, &BIG::new_int(0)));
+        }
+
+        let point = PointG2 { point: point };
+
+        let err = PointG2::from_bytes(&point.to_bytes().unwrap()).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidGroupElement);
+
+        let err = PointG2::from_bytes_compressed(&point.to_bytes_compressed().unwrap()).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidGroupElement);
+
+        let err = PointG2::from_string(&point.to_string().unwrap()).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidGroupElement);
+    }
+
     #[test]
     fn point_g2_infinity_test() {
         let p = PointG2::new_inf().unwrap();
@@ -642,6 +1240,52 @@ mod tests {
         let pair3 = pair_result.mul(&pair1.inverse().unwrap()).unwrap();
         assert_eq!(pair2, pair3);
     }
+
+    #[test]
+    fn to_bytes_compressed_works_for_point_g1() {
+        for _ in 0..10 {
+            let p = PointG1::new().unwrap();
+            let bytes = p.to_bytes_compressed().unwrap();
+            assert_eq!(PointG1::BYTES_REPR_COMPRESSED_SIZE, bytes.len());
+
+            let q = PointG1::from_bytes_compressed(&bytes).unwrap();
+            assert_eq!(p.to_bytes().unwrap(), q.to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn to_bytes_compressed_works_for_point_g1_infinity() {
+        let p = PointG1::new_inf().unwrap();
+        let q = PointG1::from_bytes_compressed(&p.to_bytes_compressed().unwrap()).unwrap();
+        assert!(q.is_inf().unwrap());
+    }
+
+    #[test]
+    fn from_bytes_compressed_works_for_invalid_point_g1() {
+        let mut bytes = PointG1::new().unwrap().to_bytes_compressed().unwrap();
+        bytes[0] = 0x04;
+        assert!(PointG1::from_bytes_compressed(&bytes).is_err());
+        assert!(PointG1::from_bytes_compressed(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn to_bytes_compressed_works_for_point_g2() {
+        for _ in 0..10 {
+            let p = PointG2::new().unwrap();
+            let bytes = p.to_bytes_compressed().unwrap();
+            assert_eq!(PointG2::BYTES_REPR_COMPRESSED_SIZE, bytes.len());
+
+            let q = PointG2::from_bytes_compressed(&bytes).unwrap();
+            assert_eq!(p.to_bytes().unwrap(), q.to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn to_bytes_compressed_works_for_point_g2_infinity() {
+        let p = PointG2::new_inf().unwrap();
+        let q = PointG2::from_bytes_compressed(&p.to_bytes_compressed().unwrap()).unwrap();
+        assert_eq!(p.to_bytes().unwrap(), q.to_bytes().unwrap());
+    }
 }
 
 #[cfg(feature = "serialization")]
@@ -671,6 +1315,16 @@ mod serialization_tests {
         field: Pair
     }
 
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestCompressedStructure {
+        #[serde(with = "compressed")]
+        g1: PointG1,
+        #[serde(with = "compressed")]
+        g2: PointG2,
+        #[serde(with = "compressed")]
+        element: GroupOrderElement
+    }
+
     #[test]
     fn from_bytes_to_bytes_works_for_group_order_element() {
         let vec = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 116, 221, 243, 243, 0, 77, 170, 65, 179, 245, 119, 182, 251, 185, 78, 98];
@@ -725,4 +1379,51 @@ mod serialization_tests {
 
         assert_eq!(pair, deserialized);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn to_bytes_compressed_works_for_group_order_element() {
+        let vec = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 116, 221, 243, 243, 0, 77, 170, 65, 179, 245, 119, 182, 251, 185, 78, 98];
+        let element = GroupOrderElement::from_bytes(&vec).unwrap();
+
+        let bytes = element.to_bytes_compressed().unwrap();
+        assert_eq!(vec[16..].to_vec(), bytes);
+        assert_eq!(element, GroupOrderElement::from_bytes_compressed(&bytes).unwrap());
+    }
+
+    #[test]
+    fn from_bytes_compressed_works_for_group_order_element_out_of_order() {
+        assert!(GroupOrderElement::from_bytes_compressed(&[0xFF; 32]).is_err());
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_compressed() {
+        let structure = TestCompressedStructure {
+            g1: PointG1::new().unwrap(),
+            g2: PointG2::new().unwrap(),
+            element: GroupOrderElement::new().unwrap()
+        };
+
+        let serialized = serde_json::to_string(&structure).unwrap();
+        let deserialized: TestCompressedStructure = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(structure.g1.to_bytes().unwrap(), deserialized.g1.to_bytes().unwrap());
+        assert_eq!(structure.g2.to_bytes().unwrap(), deserialized.g2.to_bytes().unwrap());
+        assert_eq!(structure.element, deserialized.element);
+        assert!(serialized.len() < serde_json::to_string(&(structure.g1, structure.g2, structure.element)).unwrap().len() / 2);
+    }
+
+    #[test]
+    fn deserialize_works_for_compressed_in_legacy_format() {
+        let g1 = PointG1::new().unwrap();
+        let g2 = PointG2::new().unwrap();
+        let element = GroupOrderElement::new().unwrap();
+
+        let legacy_json = format!(r#"{{"g1":"{}","g2":"{}","element":"{}"}}"#,
+                                  g1.to_string().unwrap(), g2.to_string().unwrap(), element.to_string().unwrap());
+        let deserialized: TestCompressedStructure = serde_json::from_str(&legacy_json).unwrap();
+
+        assert_eq!(g1, deserialized.g1);
+        assert_eq!(g2, deserialized.g2);
+        assert_eq!(element, deserialized.element);
+    }
+}
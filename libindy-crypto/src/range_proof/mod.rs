@@ -0,0 +1,531 @@
+use bn::BigNumber;
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, PointG1};
+
+/// Bits of values proven by predicate range proofs, so proven values are in `[0, 2^RANGE_BITS)`.
+pub const RANGE_BITS: usize = 32;
+
+const GENERATORS_DOMAIN: &'static str = "indy_crypto:range_proof";
+
+/// Logarithmic size proof that Pedersen commitment `V = g^v * h^gamma` opens to value `v` in `[0, 2^bits)`.
+///
+/// Follows range proof of Bulletproofs (Bünz et al.) with inner product argument,
+/// so proof contains `2 * log2(bits)` points in addition to 4 points and 5 scalars.
+/// Generators are derived by hashing to the curve, so nobody knows relations between them.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RangeProof {
+    #[serde(with = "::pair::compressed")]
+    a: PointG1,
+    #[serde(with = "::pair::compressed")]
+    s: PointG1,
+    #[serde(with = "::pair::compressed")]
+    t1: PointG1,
+    #[serde(with = "::pair::compressed")]
+    t2: PointG1,
+    #[serde(with = "::pair::compressed")]
+    tau_x: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    mu: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    t_hat: GroupOrderElement,
+    l: Vec<PointG1>,
+    r: Vec<PointG1>,
+    #[serde(with = "::pair::compressed")]
+    ipp_a: GroupOrderElement,
+    #[serde(with = "::pair::compressed")]
+    ipp_b: GroupOrderElement
+}
+
+impl Eq for RangeProof {}
+
+impl RangeProof {
+    /// Creates range proof for value committed with `blinding` and returns it with the commitment.
+    ///
+    /// # Arguments
+    /// * `value` - Committed value, must be less than `2^bits`.
+    /// * `blinding` - Blinding factor of commitment.
+    /// * `bits` - Bit size of range, power of two not exceeding 64.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::pair::GroupOrderElement;
+    /// use indy_crypto::range_proof::{RangeProof, RANGE_BITS};
+    ///
+    /// let blinding = GroupOrderElement::new().unwrap();
+    /// let (proof, commitment) = RangeProof::prove(42, &blinding, RANGE_BITS).unwrap();
+    /// assert!(proof.verify(&commitment, RANGE_BITS).unwrap());
+    /// ```
+    pub fn prove(value: u64, blinding: &GroupOrderElement, bits: usize) -> Result<(RangeProof, PointG1), IndyCryptoError> {
+        trace!("RangeProof::prove: >>> value: {:?}, blinding: {:?}, bits: {:?}", value, blinding, bits);
+
+        _check_bits(bits)?;
+
+        if bits < 64 && value >> bits != 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Value doesn't fit {} bits", bits)));
+        }
+
+        let (g, h) = commitment_generators()?;
+        let g_vec = _vector_generators("G", bits)?;
+        let h_vec = _vector_generators("H", bits)?;
+        let commitment = commit(&_scalar_from_u64(value)?, blinding)?;
+
+        let one = _scalar_from_u64(1)?;
+        let mut a_l: Vec<GroupOrderElement> = Vec::new();
+        let mut a_r: Vec<GroupOrderElement> = Vec::new();
+        for i in 0..bits {
+            let bit = _scalar_from_u64((value >> i) & 1)?;
+            a_r.push(bit.sub_mod(&one)?);
+            a_l.push(bit);
+        }
+
+        let alpha = GroupOrderElement::new()?;
+        let a = _commit_vectors(&h, &alpha, &g_vec, &a_l, &h_vec, &a_r)?;
+
+        let s_l = _random_vector(bits)?;
+        let s_r = _random_vector(bits)?;
+        let rho = GroupOrderElement::new()?;
+        let s = _commit_vectors(&h, &rho, &g_vec, &s_l, &h_vec, &s_r)?;
+
+        let mut transcript = Transcript::new(bits);
+        transcript.append_point(&commitment)?;
+        transcript.append_point(&a)?;
+        transcript.append_point(&s)?;
+        let y = transcript.challenge()?;
+        let z = transcript.challenge()?;
+
+        let z2 = z.mul_mod(&z)?;
+        let y_n = _powers(&y, bits)?;
+        let two_n = _powers(&_scalar_from_u64(2)?, bits)?;
+
+        // l(X) = l0 + l1 * X, r(X) = r0 + r1 * X
+        let mut l0: Vec<GroupOrderElement> = Vec::new();
+        let mut r0: Vec<GroupOrderElement> = Vec::new();
+        let mut r1: Vec<GroupOrderElement> = Vec::new();
+        for i in 0..bits {
+            l0.push(a_l[i].sub_mod(&z)?);
+            r0.push(y_n[i].mul_mod(&a_r[i].add_mod(&z)?)?.add_mod(&z2.mul_mod(&two_n[i])?)?);
+            r1.push(y_n[i].mul_mod(&s_r[i])?);
+        }
+        let l1 = s_l;
+
+        let t1 = _inner_product(&l0, &r1)?.add_mod(&_inner_product(&l1, &r0)?)?;
+        let t2 = _inner_product(&l1, &r1)?;
+
+        let tau1 = GroupOrderElement::new()?;
+        let tau2 = GroupOrderElement::new()?;
+        let t1_commitment = PointG1::multi_scalar_mul(&[g, h], &[t1, tau1])?;
+        let t2_commitment = PointG1::multi_scalar_mul(&[g, h], &[t2, tau2])?;
+
+        transcript.append_point(&t1_commitment)?;
+        transcript.append_point(&t2_commitment)?;
+        let x = transcript.challenge()?;
+
+        let mut l: Vec<GroupOrderElement> = Vec::new();
+        let mut r: Vec<GroupOrderElement> = Vec::new();
+        for i in 0..bits {
+            l.push(l0[i].add_mod(&l1[i].mul_mod(&x)?)?);
+            r.push(r0[i].add_mod(&r1[i].mul_mod(&x)?)?);
+        }
+
+        let t_hat = _inner_product(&l, &r)?;
+        let tau_x = tau2.mul_mod(&x.mul_mod(&x)?)?
+            .add_mod(&tau1.mul_mod(&x)?)?
+            .add_mod(&z2.mul_mod(blinding)?)?;
+        let mu = alpha.add_mod(&rho.mul_mod(&x)?)?;
+
+        transcript.append_scalar(&tau_x)?;
+        transcript.append_scalar(&mu)?;
+        transcript.append_scalar(&t_hat)?;
+        let q = _generator("U")?.mul(&transcript.challenge()?)?;
+
+        let h_prime = _scale_generators(&h_vec, &_powers(&y.inverse()?, bits)?)?;
+
+        let (l_points, r_points, ipp_a, ipp_b) = _prove_inner_product(&mut transcript, g_vec, h_prime, &q, l, r)?;
+
+        let proof = RangeProof {
+            a,
+            s,
+            t1: t1_commitment,
+            t2: t2_commitment,
+            tau_x,
+            mu,
+            t_hat,
+            l: l_points,
+            r: r_points,
+            ipp_a,
+            ipp_b
+        };
+
+        trace!("RangeProof::prove: <<< proof: {:?}, commitment: {:?}", proof, commitment);
+
+        Ok((proof, commitment))
+    }
+
+    /// Verifies that commitment opens to value in `[0, 2^bits)`.
+    ///
+    /// # Arguments
+    /// * `commitment` - Commitment to value.
+    /// * `bits` - Bit size of range proof was created for.
+    pub fn verify(&self, commitment: &PointG1, bits: usize) -> Result<bool, IndyCryptoError> {
+        trace!("RangeProof::verify: >>> proof: {:?}, commitment: {:?}, bits: {:?}", self, commitment, bits);
+
+        _check_bits(bits)?;
+
+        if self.l.len() != bits.trailing_zeros() as usize || self.r.len() != self.l.len() {
+            trace!("RangeProof::verify: <<< valid: false");
+            return Ok(false);
+        }
+
+        let (g, h) = commitment_generators()?;
+        let g_vec = _vector_generators("G", bits)?;
+        let h_vec = _vector_generators("H", bits)?;
+
+        let mut transcript = Transcript::new(bits);
+        transcript.append_point(commitment)?;
+        transcript.append_point(&self.a)?;
+        transcript.append_point(&self.s)?;
+        let y = transcript.challenge()?;
+        let z = transcript.challenge()?;
+        transcript.append_point(&self.t1)?;
+        transcript.append_point(&self.t2)?;
+        let x = transcript.challenge()?;
+        transcript.append_scalar(&self.tau_x)?;
+        transcript.append_scalar(&self.mu)?;
+        transcript.append_scalar(&self.t_hat)?;
+        let q = _generator("U")?.mul(&transcript.challenge()?)?;
+
+        let one = _scalar_from_u64(1)?;
+        let z2 = z.mul_mod(&z)?;
+        let z3 = z2.mul_mod(&z)?;
+        let y_n = _powers(&y, bits)?;
+        let two_n = _powers(&_scalar_from_u64(2)?, bits)?;
+
+        // delta(y, z) = (z - z^2) * <1, y^n> - z^3 * <1, 2^n>
+        let delta = z.sub_mod(&z2)?
+            .mul_mod(&_sum(&y_n)?)?
+            .sub_mod(&z3.mul_mod(&_sum(&two_n)?)?)?;
+
+        // g^t_hat * h^tau_x == V^(z^2) * g^delta * T1^x * T2^(x^2)
+        let lhs = PointG1::multi_scalar_mul(&[g, h], &[self.t_hat, self.tau_x])?;
+        let rhs = PointG1::multi_scalar_mul(&[*commitment, g, self.t1, self.t2],
+                                            &[z2, delta, x, x.mul_mod(&x)?])?;
+
+        if lhs.to_bytes()? != rhs.to_bytes()? {
+            trace!("RangeProof::verify: <<< valid: false");
+            return Ok(false);
+        }
+
+        let h_prime = _scale_generators(&h_vec, &_powers(&y.inverse()?, bits)?)?;
+
+        // P = A * S^x * G^(-z) * H'^(z * y^n + z^2 * 2^n) * h^(-mu) * Q^t_hat
+        let minus_z = z.mod_neg()?;
+        let mut points = vec![self.a, self.s, h, q];
+        let mut scalars = vec![one, x, self.mu.mod_neg()?, self.t_hat];
+        for i in 0..bits {
+            points.push(g_vec[i]);
+            scalars.push(minus_z);
+            points.push(h_prime[i]);
+            scalars.push(z.mul_mod(&y_n[i])?.add_mod(&z2.mul_mod(&two_n[i])?)?);
+        }
+        let mut p = PointG1::multi_scalar_mul(&points, &scalars)?;
+
+        let mut g_cur = g_vec;
+        let mut h_cur = h_prime;
+        for (l, r) in self.l.iter().zip(self.r.iter()) {
+            transcript.append_point(l)?;
+            transcript.append_point(r)?;
+            let u = transcript.challenge()?;
+            let u_inv = u.inverse()?;
+
+            p = PointG1::multi_scalar_mul(&[*l, p, *r], &[u.mul_mod(&u)?, one, u_inv.mul_mod(&u_inv)?])?;
+
+            let (g_next, h_next) = _fold_generators(&g_cur, &h_cur, &u, &u_inv)?;
+            g_cur = g_next;
+            h_cur = h_next;
+        }
+
+        let expected = PointG1::multi_scalar_mul(&[g_cur[0], h_cur[0], q],
+                                                 &[self.ipp_a, self.ipp_b, self.ipp_a.mul_mod(&self.ipp_b)?])?;
+
+        let valid = p.to_bytes()? == expected.to_bytes()?;
+
+        trace!("RangeProof::verify: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+}
+
+/// Returns generators `(g, h)` of Pedersen commitments to values proven by range proofs.
+pub fn commitment_generators() -> Result<(PointG1, PointG1), IndyCryptoError> {
+    Ok((_generator("g")?, _generator("h")?))
+}
+
+/// Returns Pedersen commitment `g^value * h^blinding` with range proof generators.
+pub fn commit(value: &GroupOrderElement, blinding: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+    let (g, h) = commitment_generators()?;
+    PointG1::multi_scalar_mul(&[g, h], &[*value, *blinding])
+}
+
+/// Reduces big number modulo group order, negative numbers are mapped to their additive inverses.
+pub fn bignum_to_scalar(num: &BigNumber) -> Result<GroupOrderElement, IndyCryptoError> {
+    let order = BigNumber::from_bytes(&GroupOrderElement::order_bytes())?;
+    GroupOrderElement::from_bytes(&num.modulus(&order, None)?.to_bytes()?)
+}
+
+/// Fiat-Shamir transcript, each challenge is hash of previous challenge and values appended after it.
+struct Transcript {
+    state: Vec<u8>
+}
+
+impl Transcript {
+    fn new(bits: usize) -> Transcript {
+        let mut state = GENERATORS_DOMAIN.as_bytes().to_vec();
+        state.push(bits as u8);
+        Transcript { state }
+    }
+
+    fn append_point(&mut self, point: &PointG1) -> Result<(), IndyCryptoError> {
+        self.state.extend_from_slice(&point.to_bytes_compressed()?);
+        Ok(())
+    }
+
+    fn append_scalar(&mut self, scalar: &GroupOrderElement) -> Result<(), IndyCryptoError> {
+        self.state.extend_from_slice(&scalar.to_bytes()?);
+        Ok(())
+    }
+
+    fn challenge(&mut self) -> Result<GroupOrderElement, IndyCryptoError> {
+        let digest = BigNumber::hash(&self.state)?;
+        self.state = digest.clone();
+        // Reduces digest modulo group order
+        GroupOrderElement::from_bytes(&digest)?
+            .add_mod(&GroupOrderElement::from_bytes(&[])?)
+    }
+}
+
+fn _prove_inner_product(transcript: &mut Transcript,
+                        mut g: Vec<PointG1>,
+                        mut h: Vec<PointG1>,
+                        q: &PointG1,
+                        mut a: Vec<GroupOrderElement>,
+                        mut b: Vec<GroupOrderElement>) -> Result<(Vec<PointG1>, Vec<PointG1>, GroupOrderElement, GroupOrderElement), IndyCryptoError> {
+    let mut l_points: Vec<PointG1> = Vec::new();
+    let mut r_points: Vec<PointG1> = Vec::new();
+
+    while a.len() > 1 {
+        let half = a.len() / 2;
+
+        let c_l = _inner_product(&a[..half], &b[half..])?;
+        let c_r = _inner_product(&a[half..], &b[..half])?;
+
+        let mut points = g[half..].to_vec();
+        points.extend_from_slice(&h[..half]);
+        points.push(*q);
+        let mut scalars = a[..half].to_vec();
+        scalars.extend_from_slice(&b[half..]);
+        scalars.push(c_l);
+        let l = PointG1::multi_scalar_mul(&points, &scalars)?;
+
+        let mut points = g[..half].to_vec();
+        points.extend_from_slice(&h[half..]);
+        points.push(*q);
+        let mut scalars = a[half..].to_vec();
+        scalars.extend_from_slice(&b[..half]);
+        scalars.push(c_r);
+        let r = PointG1::multi_scalar_mul(&points, &scalars)?;
+
+        transcript.append_point(&l)?;
+        transcript.append_point(&r)?;
+        let u = transcript.challenge()?;
+        let u_inv = u.inverse()?;
+
+        let mut a_next: Vec<GroupOrderElement> = Vec::new();
+        let mut b_next: Vec<GroupOrderElement> = Vec::new();
+        for i in 0..half {
+            a_next.push(a[i].mul_mod(&u)?.add_mod(&a[half + i].mul_mod(&u_inv)?)?);
+            b_next.push(b[i].mul_mod(&u_inv)?.add_mod(&b[half + i].mul_mod(&u)?)?);
+        }
+
+        let (g_next, h_next) = _fold_generators(&g, &h, &u, &u_inv)?;
+
+        l_points.push(l);
+        r_points.push(r);
+        a = a_next;
+        b = b_next;
+        g = g_next;
+        h = h_next;
+    }
+
+    Ok((l_points, r_points, a[0], b[0]))
+}
+
+fn _fold_generators(g: &[PointG1],
+                    h: &[PointG1],
+                    u: &GroupOrderElement,
+                    u_inv: &GroupOrderElement) -> Result<(Vec<PointG1>, Vec<PointG1>), IndyCryptoError> {
+    let half = g.len() / 2;
+
+    let mut g_next: Vec<PointG1> = Vec::new();
+    let mut h_next: Vec<PointG1> = Vec::new();
+    for i in 0..half {
+        g_next.push(PointG1::multi_scalar_mul(&[g[i], g[half + i]], &[*u_inv, *u])?);
+        h_next.push(PointG1::multi_scalar_mul(&[h[i], h[half + i]], &[*u, *u_inv])?);
+    }
+
+    Ok((g_next, h_next))
+}
+
+fn _commit_vectors(h: &PointG1,
+                   blinding: &GroupOrderElement,
+                   g_vec: &[PointG1],
+                   a: &[GroupOrderElement],
+                   h_vec: &[PointG1],
+                   b: &[GroupOrderElement]) -> Result<PointG1, IndyCryptoError> {
+    let mut points = vec![*h];
+    points.extend_from_slice(g_vec);
+    points.extend_from_slice(h_vec);
+
+    let mut scalars = vec![*blinding];
+    scalars.extend_from_slice(a);
+    scalars.extend_from_slice(b);
+
+    PointG1::multi_scalar_mul(&points, &scalars)
+}
+
+fn _scale_generators(points: &[PointG1], scalars: &[GroupOrderElement]) -> Result<Vec<PointG1>, IndyCryptoError> {
+    let mut res: Vec<PointG1> = Vec::new();
+    for (point, scalar) in points.iter().zip(scalars.iter()) {
+        res.push(point.mul(scalar)?);
+    }
+    Ok(res)
+}
+
+fn _generator(label: &str) -> Result<PointG1, IndyCryptoError> {
+    PointG1::from_hash(&BigNumber::hash(format!("{}:{}", GENERATORS_DOMAIN, label).as_bytes())?)
+}
+
+fn _vector_generators(label: &str, n: usize) -> Result<Vec<PointG1>, IndyCryptoError> {
+    let mut generators: Vec<PointG1> = Vec::new();
+    for i in 0..n {
+        generators.push(_generator(&format!("{}{}", label, i))?);
+    }
+    Ok(generators)
+}
+
+fn _check_bits(bits: usize) -> Result<(), IndyCryptoError> {
+    if bits == 0 || bits > 64 || !bits.is_power_of_two() {
+        return Err(IndyCryptoError::InvalidStructure(format!("Range bits must be power of two not exceeding 64, got {}", bits)));
+    }
+    Ok(())
+}
+
+fn _scalar_from_u64(value: u64) -> Result<GroupOrderElement, IndyCryptoError> {
+    let bytes: Vec<u8> = (0..8).map(|i| (value >> (56 - 8 * i)) as u8).collect();
+    GroupOrderElement::from_bytes(&bytes)
+}
+
+fn _random_vector(n: usize) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
+    let mut res: Vec<GroupOrderElement> = Vec::new();
+    for _ in 0..n {
+        res.push(GroupOrderElement::new()?);
+    }
+    Ok(res)
+}
+
+fn _powers(x: &GroupOrderElement, n: usize) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
+    let mut res: Vec<GroupOrderElement> = Vec::new();
+    let mut cur = _scalar_from_u64(1)?;
+    for _ in 0..n {
+        res.push(cur);
+        cur = cur.mul_mod(x)?;
+    }
+    Ok(res)
+}
+
+fn _sum(a: &[GroupOrderElement]) -> Result<GroupOrderElement, IndyCryptoError> {
+    let mut res = _scalar_from_u64(0)?;
+    for a_i in a.iter() {
+        res = res.add_mod(a_i)?;
+    }
+    Ok(res)
+}
+
+fn _inner_product(a: &[GroupOrderElement], b: &[GroupOrderElement]) -> Result<GroupOrderElement, IndyCryptoError> {
+    let mut res = _scalar_from_u64(0)?;
+    for (a_i, b_i) in a.iter().zip(b.iter()) {
+        res = res.add_mod(&a_i.mul_mod(b_i)?)?;
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_proof_works() {
+        let blinding = GroupOrderElement::new().unwrap();
+        let (proof, commitment) = RangeProof::prove(1234, &blinding, RANGE_BITS).unwrap();
+
+        assert_eq!(5, proof.l.len());
+        assert!(proof.verify(&commitment, RANGE_BITS).unwrap());
+    }
+
+    #[test]
+    fn range_proof_works_for_bounds() {
+        let blinding = GroupOrderElement::new().unwrap();
+
+        let (proof, commitment) = RangeProof::prove(0, &blinding, 8).unwrap();
+        assert!(proof.verify(&commitment, 8).unwrap());
+
+        let (proof, commitment) = RangeProof::prove(255, &blinding, 8).unwrap();
+        assert!(proof.verify(&commitment, 8).unwrap());
+    }
+
+    #[test]
+    fn range_proof_prove_fails_for_value_out_of_range() {
+        let blinding = GroupOrderElement::new().unwrap();
+        assert!(RangeProof::prove(256, &blinding, 8).is_err());
+    }
+
+    #[test]
+    fn range_proof_prove_fails_for_invalid_bits() {
+        let blinding = GroupOrderElement::new().unwrap();
+        assert!(RangeProof::prove(1, &blinding, 12).is_err());
+    }
+
+    #[test]
+    fn range_proof_verify_works_for_other_commitment() {
+        let blinding = GroupOrderElement::new().unwrap();
+        let (proof, _) = RangeProof::prove(1234, &blinding, RANGE_BITS).unwrap();
+
+        let other_commitment = commit(&_scalar_from_u64(1235).unwrap(), &blinding).unwrap();
+        assert!(!proof.verify(&other_commitment, RANGE_BITS).unwrap());
+    }
+
+    #[test]
+    fn range_proof_verify_works_for_other_bits() {
+        let blinding = GroupOrderElement::new().unwrap();
+        let (proof, commitment) = RangeProof::prove(12, &blinding, 16).unwrap();
+
+        assert!(!proof.verify(&commitment, RANGE_BITS).unwrap());
+    }
+
+    #[test]
+    fn commit_works() {
+        let (g, h) = commitment_generators().unwrap();
+        let value = _scalar_from_u64(7).unwrap();
+        let blinding = GroupOrderElement::new().unwrap();
+
+        let expected = g.mul(&value).unwrap().add(&h.mul(&blinding).unwrap()).unwrap();
+        assert_eq!(expected.to_bytes().unwrap(), commit(&value, &blinding).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn bignum_to_scalar_works() {
+        let order = BigNumber::from_bytes(&GroupOrderElement::order_bytes()).unwrap();
+        let num = order.add(&BigNumber::from_u32(5).unwrap()).unwrap();
+
+        assert_eq!(_scalar_from_u64(5).unwrap().to_bytes().unwrap(), bignum_to_scalar(&num).unwrap().to_bytes().unwrap());
+    }
+}
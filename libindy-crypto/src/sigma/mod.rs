@@ -0,0 +1,541 @@
+use bn::BigNumber;
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, PointG1};
+
+/// Bits of challenge computed as SHA-256 of transcript.
+pub const CHALLENGE_BITS: usize = 256;
+
+/// Group where sigma protocol statements are proven.
+pub trait SigmaGroup {
+    type Element;
+    type Scalar;
+
+    /// Group operation.
+    fn op(&self, a: &Self::Element, b: &Self::Element) -> Result<Self::Element, IndyCryptoError>;
+
+    /// Repeated group operation.
+    fn exp(&self, base: &Self::Element, exp: &Self::Scalar) -> Result<Self::Element, IndyCryptoError>;
+
+    fn inverse(&self, a: &Self::Element) -> Result<Self::Element, IndyCryptoError>;
+
+    fn identity(&self) -> Result<Self::Element, IndyCryptoError>;
+
+    fn element_to_bytes(&self, a: &Self::Element) -> Result<Vec<u8>, IndyCryptoError>;
+
+    /// Random value that hides secret in response.
+    fn random_blinding(&self) -> Result<Self::Scalar, IndyCryptoError>;
+
+    fn challenge_to_scalar(&self, c: &BigNumber) -> Result<Self::Scalar, IndyCryptoError>;
+
+    /// Returns `blinding + c * secret`.
+    fn response(&self, blinding: &Self::Scalar, c: &Self::Scalar, secret: &Self::Scalar) -> Result<Self::Scalar, IndyCryptoError>;
+}
+
+/// Multiplicative group modulo `modulus` with integer responses,
+/// so group order isn't required (RSA groups of CL signatures).
+#[derive(Debug)]
+pub struct BnGroup {
+    modulus: BigNumber,
+    blinding_bits: usize
+}
+
+impl BnGroup {
+    /// Creates group.
+    ///
+    /// # Arguments
+    /// * `modulus` - Group modulus.
+    /// * `blinding_bits` - Size of blinding values, must exceed secrets size plus `CHALLENGE_BITS` by security margin.
+    pub fn new(modulus: BigNumber, blinding_bits: usize) -> Result<BnGroup, IndyCryptoError> {
+        Ok(BnGroup { modulus, blinding_bits })
+    }
+}
+
+impl SigmaGroup for BnGroup {
+    type Element = BigNumber;
+    type Scalar = BigNumber;
+
+    fn op(&self, a: &BigNumber, b: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        a.mod_mul(b, &self.modulus, None)
+    }
+
+    fn exp(&self, base: &BigNumber, exp: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        base.mod_exp(exp, &self.modulus, None)
+    }
+
+    fn inverse(&self, a: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        a.inverse(&self.modulus, None)
+    }
+
+    fn identity(&self) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::from_u32(1)
+    }
+
+    fn element_to_bytes(&self, a: &BigNumber) -> Result<Vec<u8>, IndyCryptoError> {
+        a.to_bytes()
+    }
+
+    fn random_blinding(&self) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::rand(self.blinding_bits)
+    }
+
+    fn challenge_to_scalar(&self, c: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        c.clone()
+    }
+
+    fn response(&self, blinding: &BigNumber, c: &BigNumber, secret: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        c.mul(secret, None)?.add(blinding)
+    }
+}
+
+/// Group G1 of pairing with responses modulo group order.
+#[derive(Debug)]
+pub struct PointG1Group {}
+
+impl PointG1Group {
+    pub fn new() -> PointG1Group {
+        PointG1Group {}
+    }
+}
+
+impl SigmaGroup for PointG1Group {
+    type Element = PointG1;
+    type Scalar = GroupOrderElement;
+
+    fn op(&self, a: &PointG1, b: &PointG1) -> Result<PointG1, IndyCryptoError> {
+        a.add(b)
+    }
+
+    fn exp(&self, base: &PointG1, exp: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+        base.mul(exp)
+    }
+
+    fn inverse(&self, a: &PointG1) -> Result<PointG1, IndyCryptoError> {
+        a.neg()
+    }
+
+    fn identity(&self) -> Result<PointG1, IndyCryptoError> {
+        PointG1::new_inf()
+    }
+
+    fn element_to_bytes(&self, a: &PointG1) -> Result<Vec<u8>, IndyCryptoError> {
+        a.to_bytes()
+    }
+
+    fn random_blinding(&self) -> Result<GroupOrderElement, IndyCryptoError> {
+        GroupOrderElement::new()
+    }
+
+    fn challenge_to_scalar(&self, c: &BigNumber) -> Result<GroupOrderElement, IndyCryptoError> {
+        // Reduces challenge modulo group order
+        GroupOrderElement::from_bytes(&c.to_bytes()?)?
+            .add_mod(&GroupOrderElement::from_bytes(&[])?)
+    }
+
+    fn response(&self, blinding: &GroupOrderElement, c: &GroupOrderElement, secret: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        c.mul_mod(secret)?.add_mod(blinding)
+    }
+}
+
+/// Relation `value = base_1^secret_(idx_1) * ... * base_n^secret_(idx_n)`.
+pub struct Relation<G> where G: SigmaGroup {
+    value: G::Element,
+    terms: Vec<(G::Element, usize /* secret index */)>
+}
+
+/// Conjunction of relations over shared set of secrets.
+/// The same secret index used in several relations proves equality of discrete logs.
+pub struct Statement<G> where G: SigmaGroup {
+    relations: Vec<Relation<G>>,
+    secrets_count: usize
+}
+
+/// Non-interactive proof of statement.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SigmaProof<S> {
+    c: BigNumber,
+    responses: Vec<S>
+}
+
+impl<S> SigmaProof<S> {
+    pub fn c(&self) -> &BigNumber {
+        &self.c
+    }
+
+    pub fn responses(&self) -> &[S] {
+        &self.responses
+    }
+}
+
+/// Non-interactive proof that at least one of statements holds without disclosing which one.
+/// Challenges of branches XOR to the challenge computed from the transcript.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OrProof<S> {
+    branches: Vec<SigmaProof<S>>
+}
+
+impl<G> Statement<G> where G: SigmaGroup {
+    /// Creates empty conjunction over `secrets_count` secrets.
+    pub fn new(secrets_count: usize) -> Statement<G> {
+        Statement {
+            relations: Vec::new(),
+            secrets_count
+        }
+    }
+
+    /// Adds relation `value = prod(base^secret[idx])` to conjunction.
+    ///
+    /// # Arguments
+    /// * `value` - Public value.
+    /// * `terms` - Pairs of base and index of secret.
+    pub fn add_relation(&mut self, value: G::Element, terms: Vec<(G::Element, usize)>) -> Result<(), IndyCryptoError> {
+        if let Some(&(_, idx)) = terms.iter().find(|&&(_, idx)| idx >= self.secrets_count) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Secret index {} is out of range, secrets count: {}", idx, self.secrets_count)));
+        }
+
+        self.relations.push(Relation { value, terms });
+        Ok(())
+    }
+
+    /// Prover commitment step: `t = prod(base^blinding[idx])` per relation.
+    pub fn commitments(&self, group: &G, blindings: &[G::Scalar]) -> Result<Vec<G::Element>, IndyCryptoError> {
+        self._check_len(blindings.len())?;
+
+        let mut t_values: Vec<G::Element> = Vec::new();
+        for relation in self.relations.iter() {
+            let mut t = group.identity()?;
+            for &(ref base, idx) in relation.terms.iter() {
+                t = group.op(&t, &group.exp(base, &blindings[idx])?)?;
+            }
+            t_values.push(t);
+        }
+
+        Ok(t_values)
+    }
+
+    /// Prover response step: `blinding + c * secret` per secret.
+    pub fn responses(&self,
+                     group: &G,
+                     secrets: &[&G::Scalar],
+                     blindings: &[G::Scalar],
+                     c: &BigNumber) -> Result<Vec<G::Scalar>, IndyCryptoError> {
+        self._check_len(secrets.len())?;
+        self._check_len(blindings.len())?;
+
+        let c = group.challenge_to_scalar(c)?;
+
+        secrets.iter().zip(blindings.iter())
+            .map(|(secret, blinding)| group.response(blinding, &c, secret))
+            .collect()
+    }
+
+    /// Verifier step: restores prover commitments `value^(-c) * prod(base^response[idx])` per relation.
+    pub fn commitments_from_responses(&self,
+                                      group: &G,
+                                      c: &BigNumber,
+                                      responses: &[&G::Scalar]) -> Result<Vec<G::Element>, IndyCryptoError> {
+        self._check_len(responses.len())?;
+
+        let c = group.challenge_to_scalar(c)?;
+
+        let mut t_values: Vec<G::Element> = Vec::new();
+        for relation in self.relations.iter() {
+            let mut t = group.exp(&group.inverse(&relation.value)?, &c)?;
+            for &(ref base, idx) in relation.terms.iter() {
+                t = group.op(&t, &group.exp(base, responses[idx])?)?;
+            }
+            t_values.push(t);
+        }
+
+        Ok(t_values)
+    }
+
+    /// Fiat-Shamir challenge over relations, prover commitments and context (for example verifier nonce).
+    pub fn challenge(&self, group: &G, t_values: &[G::Element], context: &[Vec<u8>]) -> Result<BigNumber, IndyCryptoError> {
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        self._append_transcript(group, t_values, &mut values)?;
+        values.extend_from_slice(context);
+        _hash(&values)
+    }
+
+    /// Creates non-interactive proof of knowledge of secrets satisfying all relations.
+    ///
+    /// # Arguments
+    /// * `group` - Group of relations.
+    /// * `secrets` - Secrets in order of indices used in relations.
+    /// * `context` - Data proof is bound to (for example verifier nonce).
+    pub fn prove(&self, group: &G, secrets: &[&G::Scalar], context: &[Vec<u8>]) -> Result<SigmaProof<G::Scalar>, IndyCryptoError> {
+        let blindings = self._random_blindings(group)?;
+        let t_values = self.commitments(group, &blindings)?;
+        let c = self.challenge(group, &t_values, context)?;
+        let responses = self.responses(group, secrets, &blindings, &c)?;
+
+        Ok(SigmaProof { c, responses })
+    }
+
+    /// Verifies non-interactive proof created by `Statement::prove`.
+    pub fn verify(&self, group: &G, proof: &SigmaProof<G::Scalar>, context: &[Vec<u8>]) -> Result<bool, IndyCryptoError> {
+        let responses: Vec<&G::Scalar> = proof.responses.iter().collect();
+        let t_values = self.commitments_from_responses(group, &proof.c, &responses)?;
+        Ok(self.challenge(group, &t_values, context)? == proof.c)
+    }
+
+    fn _check_len(&self, len: usize) -> Result<(), IndyCryptoError> {
+        if len != self.secrets_count {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid count of statement values: expected {}, actual {}", self.secrets_count, len)));
+        }
+        Ok(())
+    }
+
+    fn _random_blindings(&self, group: &G) -> Result<Vec<G::Scalar>, IndyCryptoError> {
+        (0..self.secrets_count).map(|_| group.random_blinding()).collect()
+    }
+
+    fn _append_transcript(&self, group: &G, t_values: &[G::Element], values: &mut Vec<Vec<u8>>) -> Result<(), IndyCryptoError> {
+        for (relation, t) in self.relations.iter().zip(t_values.iter()) {
+            values.push(group.element_to_bytes(&relation.value)?);
+            for &(ref base, _) in relation.terms.iter() {
+                values.push(group.element_to_bytes(base)?);
+            }
+            values.push(group.element_to_bytes(t)?);
+        }
+        Ok(())
+    }
+}
+
+/// Creates non-interactive proof that one of statements holds.
+/// Proofs of other statements are simulated.
+///
+/// # Arguments
+/// * `group` - Group of relations.
+/// * `statements` - Statements of OR composition.
+/// * `known_idx` - Index of statement secrets are known for.
+/// * `secrets` - Secrets of known statement.
+/// * `context` - Data proof is bound to (for example verifier nonce).
+pub fn prove_or<G>(group: &G,
+                   statements: &[Statement<G>],
+                   known_idx: usize,
+                   secrets: &[&G::Scalar],
+                   context: &[Vec<u8>]) -> Result<OrProof<G::Scalar>, IndyCryptoError> where G: SigmaGroup {
+    trace!("sigma::prove_or: >>> statements count: {:?}, known_idx: {:?}", statements.len(), known_idx);
+
+    let known = statements.get(known_idx)
+        .ok_or(IndyCryptoError::InvalidStructure(format!("Statement by index '{}' not found", known_idx)))?;
+
+    let blindings = known._random_blindings(group)?;
+
+    let mut simulated: Vec<Option<SigmaProof<G::Scalar>>> = Vec::new();
+    let mut t_values: Vec<Vec<G::Element>> = Vec::new();
+
+    for (idx, statement) in statements.iter().enumerate() {
+        if idx == known_idx {
+            simulated.push(None);
+            t_values.push(known.commitments(group, &blindings)?);
+        } else {
+            let c = BigNumber::rand(CHALLENGE_BITS)?;
+            let responses = statement._random_blindings(group)?;
+            t_values.push(statement.commitments_from_responses(group, &c, &responses.iter().collect::<Vec<&G::Scalar>>())?);
+            simulated.push(Some(SigmaProof { c, responses }));
+        }
+    }
+
+    let mut c = _or_challenge(group, statements, &t_values, context)?;
+    for proof in simulated.iter() {
+        if let Some(ref proof) = *proof {
+            c = _xor(&c, &proof.c)?;
+        }
+    }
+
+    let mut responses = Some(known.responses(group, secrets, &blindings, &c)?);
+
+    let mut branches: Vec<SigmaProof<G::Scalar>> = Vec::new();
+    for proof in simulated.into_iter() {
+        match proof {
+            Some(proof) => branches.push(proof),
+            None => branches.push(SigmaProof { c: c.clone()?, responses: responses.take().unwrap_or_default() })
+        }
+    }
+
+    trace!("sigma::prove_or: <<<");
+
+    Ok(OrProof { branches })
+}
+
+/// Verifies non-interactive proof created by `prove_or`.
+pub fn verify_or<G>(group: &G,
+                    statements: &[Statement<G>],
+                    proof: &OrProof<G::Scalar>,
+                    context: &[Vec<u8>]) -> Result<bool, IndyCryptoError> where G: SigmaGroup {
+    trace!("sigma::verify_or: >>> statements count: {:?}", statements.len());
+
+    if proof.branches.len() != statements.len() {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid count of OR proof branches: expected {}, actual {}", statements.len(), proof.branches.len())));
+    }
+
+    let mut t_values: Vec<Vec<G::Element>> = Vec::new();
+    let mut c = BigNumber::new()?;
+
+    for (statement, branch) in statements.iter().zip(proof.branches.iter()) {
+        let responses: Vec<&G::Scalar> = branch.responses.iter().collect();
+        t_values.push(statement.commitments_from_responses(group, &branch.c, &responses)?);
+        c = _xor(&c, &branch.c)?;
+    }
+
+    let valid = _or_challenge(group, statements, &t_values, context)? == c;
+
+    trace!("sigma::verify_or: <<< valid: {:?}", valid);
+
+    Ok(valid)
+}
+
+fn _or_challenge<G>(group: &G,
+                    statements: &[Statement<G>],
+                    t_values: &[Vec<G::Element>],
+                    context: &[Vec<u8>]) -> Result<BigNumber, IndyCryptoError> where G: SigmaGroup {
+    let mut values: Vec<Vec<u8>> = Vec::new();
+    for (statement, t_values) in statements.iter().zip(t_values.iter()) {
+        statement._append_transcript(group, t_values, &mut values)?;
+    }
+    values.extend_from_slice(context);
+    _hash(&values)
+}
+
+fn _hash(values: &Vec<Vec<u8>>) -> Result<BigNumber, IndyCryptoError> {
+    BigNumber::from_bytes(&BigNumber::hash_array(values)?)
+}
+
+fn _xor(a: &BigNumber, b: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    let len = CHALLENGE_BITS / 8;
+
+    let a = a.to_bytes()?;
+    let b = b.to_bytes()?;
+
+    if a.len() > len || b.len() > len {
+        return Err(IndyCryptoError::InvalidStructure("Challenge is out of range".to_string()));
+    }
+
+    let mut result = vec![0u8; len];
+    for (i, byte) in a.iter().rev().enumerate() {
+        result[len - 1 - i] ^= *byte;
+    }
+    for (i, byte) in b.iter().rev().enumerate() {
+        result[len - 1 - i] ^= *byte;
+    }
+
+    BigNumber::from_bytes(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _bn_group() -> (BnGroup, BigNumber, BigNumber) {
+        let modulus = BigNumber::generate_safe_prime(256).unwrap();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        let g = BigNumber::rand(256).unwrap().sqr(Some(&mut ctx)).unwrap().modulus(&modulus, Some(&mut ctx)).unwrap();
+        let h = BigNumber::rand(256).unwrap().sqr(Some(&mut ctx)).unwrap().modulus(&modulus, Some(&mut ctx)).unwrap();
+
+        (BnGroup::new(modulus, 256 + CHALLENGE_BITS + 80).unwrap(), g, h)
+    }
+
+    fn _bn(value: u32) -> BigNumber {
+        BigNumber::from_u32(value as usize).unwrap()
+    }
+
+    #[test]
+    fn prove_works_for_bn_group() {
+        let (group, g, h) = _bn_group();
+        let x = _bn(42);
+        let r = _bn(7);
+
+        let mut statement: Statement<BnGroup> = Statement::new(2);
+        let value = group.op(&group.exp(&g, &x).unwrap(), &group.exp(&h, &r).unwrap()).unwrap();
+        statement.add_relation(value, vec![(g, 0), (h, 1)]).unwrap();
+
+        let context = vec![b"nonce".to_vec()];
+        let proof = statement.prove(&group, &[&x, &r], &context).unwrap();
+
+        assert!(statement.verify(&group, &proof, &context).unwrap());
+        assert!(!statement.verify(&group, &proof, &vec![b"other".to_vec()]).unwrap());
+    }
+
+    #[test]
+    fn prove_works_for_bn_group_and_wrong_secret() {
+        let (group, g, _) = _bn_group();
+
+        let mut statement: Statement<BnGroup> = Statement::new(1);
+        let value = group.exp(&g, &_bn(42)).unwrap();
+        statement.add_relation(value, vec![(g, 0)]).unwrap();
+
+        let proof = statement.prove(&group, &[&_bn(43)], &[]).unwrap();
+        assert!(!statement.verify(&group, &proof, &[]).unwrap());
+    }
+
+    #[test]
+    fn prove_works_for_conjunction_with_shared_secret() {
+        let group = PointG1Group::new();
+        let g = PointG1::new().unwrap();
+        let h = PointG1::new().unwrap();
+        let x = GroupOrderElement::new().unwrap();
+
+        let mut statement: Statement<PointG1Group> = Statement::new(1);
+        statement.add_relation(g.mul(&x).unwrap(), vec![(g, 0)]).unwrap();
+        statement.add_relation(h.mul(&x).unwrap(), vec![(h, 0)]).unwrap();
+
+        let proof = statement.prove(&group, &[&x], &[]).unwrap();
+        assert!(statement.verify(&group, &proof, &[]).unwrap());
+
+        let mut other_statement: Statement<PointG1Group> = Statement::new(1);
+        other_statement.add_relation(g.mul(&x).unwrap(), vec![(g, 0)]).unwrap();
+        other_statement.add_relation(h.mul(&GroupOrderElement::new().unwrap()).unwrap(), vec![(h, 0)]).unwrap();
+
+        let proof = other_statement.prove(&group, &[&x], &[]).unwrap();
+        assert!(!other_statement.verify(&group, &proof, &[]).unwrap());
+    }
+
+    #[test]
+    fn add_relation_works_for_invalid_secret_index() {
+        let mut statement: Statement<PointG1Group> = Statement::new(1);
+        assert!(statement.add_relation(PointG1::new().unwrap(), vec![(PointG1::new().unwrap(), 1)]).is_err());
+    }
+
+    #[test]
+    fn prove_or_works() {
+        let group = PointG1Group::new();
+        let g = PointG1::new().unwrap();
+        let x = GroupOrderElement::new().unwrap();
+
+        let mut statements: Vec<Statement<PointG1Group>> = Vec::new();
+        for idx in 0..3 {
+            let value = if idx == 1 { g.mul(&x).unwrap() } else { PointG1::new().unwrap() };
+            let mut statement: Statement<PointG1Group> = Statement::new(1);
+            statement.add_relation(value, vec![(g, 0)]).unwrap();
+            statements.push(statement);
+        }
+
+        let context = vec![b"nonce".to_vec()];
+        let proof = prove_or(&group, &statements, 1, &[&x], &context).unwrap();
+
+        assert!(verify_or(&group, &statements, &proof, &context).unwrap());
+        assert!(!verify_or(&group, &statements, &proof, &vec![b"other".to_vec()]).unwrap());
+    }
+
+    #[test]
+    fn prove_or_works_for_no_known_secret() {
+        let group = PointG1Group::new();
+        let g = PointG1::new().unwrap();
+
+        let mut statements: Vec<Statement<PointG1Group>> = Vec::new();
+        for _ in 0..2 {
+            let mut statement: Statement<PointG1Group> = Statement::new(1);
+            statement.add_relation(PointG1::new().unwrap(), vec![(g, 0)]).unwrap();
+            statements.push(statement);
+        }
+
+        let proof = prove_or(&group, &statements, 0, &[&GroupOrderElement::new().unwrap()], &[]).unwrap();
+        assert!(!verify_or(&group, &statements, &proof, &[]).unwrap());
+    }
+}
@@ -0,0 +1,343 @@
+use bn::{BigNumber, BigNumberContext};
+use errors::IndyCryptoError;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use std::collections::HashSet;
+
+/// Secrets are shared over prime field of Mersenne prime `2^521 - 1`.
+pub const FIELD_PRIME_BITS: usize = 521;
+
+/// Maximal count of shares secret can be split into.
+pub const MAX_SHARES_COUNT: usize = 255;
+
+/// Share of secret split by `split_secret`.
+///
+/// Every share contains digest of secret used to check result of reconstruction
+/// and checksum used to detect corrupted shares. Digest is keyed by random key shared
+/// together with secret, so less than `threshold` shares don't allow to check guesses
+/// of secret against digest even if secret has low entropy.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct SecretShare {
+    threshold: usize,
+    index: usize,
+    value: BigNumber,
+    digest_key: BigNumber,
+    secret_digest: Vec<u8>,
+    checksum: Vec<u8>
+}
+
+impl SecretShare {
+    /// Count of shares required to reconstruct secret.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Index of share in range `1..shares_count`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Checks share checksum.
+    pub fn is_valid(&self) -> Result<bool, IndyCryptoError> {
+        Ok(_checksum(self.threshold, self.index, &self.value, &self.digest_key, &self.secret_digest)? == self.checksum)
+    }
+
+    pub fn clone(&self) -> Result<SecretShare, IndyCryptoError> {
+        Ok(SecretShare {
+            threshold: self.threshold,
+            index: self.index,
+            value: self.value.clone()?,
+            digest_key: self.digest_key.clone()?,
+            secret_digest: self.secret_digest.clone(),
+            checksum: self.checksum.clone()
+        })
+    }
+}
+
+impl JsonEncodable for SecretShare {}
+
+impl<'a> JsonDecodable<'a> for SecretShare {}
+
+/// Splits secret into `shares_count` shares so any `threshold` of them reconstruct it.
+///
+/// # Arguments
+/// * `secret` - Non-negative secret less than `2^521 - 1`.
+/// * `threshold` - Count of shares required to reconstruct secret.
+/// * `shares_count` - Count of shares.
+///
+/// # Example
+/// ```
+/// use indy_crypto::bn::BigNumber;
+/// use indy_crypto::sss::{split_secret, recover_secret};
+///
+/// let secret = BigNumber::rand(256).unwrap();
+/// let shares = split_secret(&secret, 2, 3).unwrap();
+/// assert_eq!(secret, recover_secret(&shares[1..]).unwrap());
+/// ```
+pub fn split_secret(secret: &BigNumber, threshold: usize, shares_count: usize) -> Result<Vec<SecretShare>, IndyCryptoError> {
+    trace!("sss::split_secret: >>> threshold: {:?}, shares_count: {:?}", threshold, shares_count);
+
+    if threshold == 0 || threshold > shares_count {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid threshold {} for shares count {}", threshold, shares_count)));
+    }
+
+    if shares_count > MAX_SHARES_COUNT {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Shares count {} exceeds maximum {}", shares_count, MAX_SHARES_COUNT)));
+    }
+
+    let prime = _field_prime()?;
+
+    if secret.is_negative()? || *secret >= prime {
+        return Err(IndyCryptoError::InvalidStructure("Secret is out of field range".to_string()));
+    }
+
+    let mut ctx = BigNumber::new_context()?;
+
+    let digest_key = prime.rand_range()?;
+
+    let mut coefficients: Vec<BigNumber> = vec![secret.clone()?];
+    let mut key_coefficients: Vec<BigNumber> = vec![digest_key.clone()?];
+    for _ in 1..threshold {
+        coefficients.push(prime.rand_range()?);
+        key_coefficients.push(prime.rand_range()?);
+    }
+
+    let secret_digest = _secret_digest(secret, &digest_key)?;
+
+    let mut shares: Vec<SecretShare> = Vec::new();
+    for index in 1..(shares_count + 1) {
+        let value = _eval_polynomial(&coefficients, index, &prime, &mut ctx)?;
+        let digest_key = _eval_polynomial(&key_coefficients, index, &prime, &mut ctx)?;
+        let checksum = _checksum(threshold, index, &value, &digest_key, &secret_digest)?;
+
+        shares.push(SecretShare {
+            threshold,
+            index,
+            value,
+            digest_key,
+            secret_digest: secret_digest.clone(),
+            checksum
+        });
+    }
+
+    trace!("sss::split_secret: <<< shares count: {:?}", shares.len());
+
+    Ok(shares)
+}
+
+/// Reconstructs secret from shares created by `split_secret`.
+///
+/// Fails if some share is corrupted, shares belong to different secrets,
+/// count of shares is less than threshold or reconstructed secret doesn't match digest.
+///
+/// # Arguments
+/// * `shares` - At least `threshold` distinct shares.
+pub fn recover_secret(shares: &[SecretShare]) -> Result<BigNumber, IndyCryptoError> {
+    trace!("sss::recover_secret: >>> shares count: {:?}", shares.len());
+
+    let first = shares.first()
+        .ok_or(IndyCryptoError::InvalidStructure("Shares are empty".to_string()))?;
+
+    let mut indexes: HashSet<usize> = HashSet::new();
+
+    for share in shares.iter() {
+        if !share.is_valid()? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Share {} is corrupted", share.index)));
+        }
+
+        if share.threshold != first.threshold || share.secret_digest != first.secret_digest {
+            return Err(IndyCryptoError::InvalidStructure(format!("Share {} belongs to another secret", share.index)));
+        }
+
+        if share.index == 0 || !indexes.insert(share.index) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid or duplicated share index {}", share.index)));
+        }
+    }
+
+    if shares.len() < first.threshold {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Not enough shares: expected {}, actual {}", first.threshold, shares.len())));
+    }
+
+    let prime = _field_prime()?;
+    let secret = _interpolate_at_zero(&shares[..first.threshold], &prime, |share| &share.value)?;
+    let digest_key = _interpolate_at_zero(&shares[..first.threshold], &prime, |share| &share.digest_key)?;
+
+    if _secret_digest(&secret, &digest_key)? != first.secret_digest {
+        return Err(IndyCryptoError::InvalidStructure("Reconstructed secret doesn't match digest".to_string()));
+    }
+
+    trace!("sss::recover_secret: <<<");
+
+    Ok(secret)
+}
+
+fn _field_prime() -> Result<BigNumber, IndyCryptoError> {
+    let mut prime = BigNumber::from_u32(2)?.exp(&BigNumber::from_u32(FIELD_PRIME_BITS)?, None)?;
+    prime.sub_word(1)?;
+    Ok(prime)
+}
+
+fn _eval_polynomial(coefficients: &[BigNumber],
+                    x: usize,
+                    prime: &BigNumber,
+                    ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    let x = BigNumber::from_u32(x)?;
+    let mut result = BigNumber::new()?;
+
+    // Horner's method
+    for coefficient in coefficients.iter().rev() {
+        result = result
+            .mod_mul(&x, prime, Some(ctx))?
+            .add(coefficient)?
+            .modulus(prime, Some(ctx))?;
+    }
+
+    Ok(result)
+}
+
+fn _interpolate_at_zero<F>(shares: &[SecretShare], prime: &BigNumber, value: F) -> Result<BigNumber, IndyCryptoError>
+    where F: Fn(&SecretShare) -> &BigNumber {
+    let mut ctx = BigNumber::new_context()?;
+    let mut result = BigNumber::new()?;
+
+    for share_i in shares.iter() {
+        let x_i = BigNumber::from_u32(share_i.index)?;
+
+        let mut numerator = BigNumber::from_u32(1)?;
+        let mut denominator = BigNumber::from_u32(1)?;
+
+        for share_j in shares.iter().filter(|share_j| share_j.index != share_i.index) {
+            let x_j = BigNumber::from_u32(share_j.index)?;
+
+            numerator = numerator.mod_mul(&x_j, prime, Some(&mut ctx))?;
+            denominator = denominator.mod_mul(&x_j.mod_sub(&x_i, prime, Some(&mut ctx))?, prime, Some(&mut ctx))?;
+        }
+
+        let lagrange_coefficient = numerator.mod_div(&denominator, prime)?;
+
+        result = result
+            .add(&value(share_i).mod_mul(&lagrange_coefficient, prime, Some(&mut ctx))?)?
+            .modulus(prime, Some(&mut ctx))?;
+    }
+
+    Ok(result)
+}
+
+fn _secret_digest(secret: &BigNumber, digest_key: &BigNumber) -> Result<Vec<u8>, IndyCryptoError> {
+    BigNumber::hash_array(&vec![b"sss-secret".to_vec(), digest_key.to_bytes()?, secret.to_bytes()?])
+}
+
+fn _checksum(threshold: usize,
+             index: usize,
+             value: &BigNumber,
+             digest_key: &BigNumber,
+             secret_digest: &[u8]) -> Result<Vec<u8>, IndyCryptoError> {
+    BigNumber::hash_array(&vec![
+        b"sss-share".to_vec(),
+        BigNumber::from_u32(threshold)?.to_bytes()?,
+        BigNumber::from_u32(index)?.to_bytes()?,
+        value.to_bytes()?,
+        digest_key.to_bytes()?,
+        secret_digest.to_vec()
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_secret_works() {
+        let secret = BigNumber::rand(256).unwrap();
+        let shares = split_secret(&secret, 3, 5).unwrap();
+
+        assert_eq!(5, shares.len());
+        assert_eq!(secret, recover_secret(&shares[..3]).unwrap());
+        assert_eq!(secret, recover_secret(&shares[2..]).unwrap());
+        assert_eq!(secret, recover_secret(&shares).unwrap());
+    }
+
+    #[test]
+    fn split_secret_works_for_threshold_one() {
+        let secret = BigNumber::from_u32(42).unwrap();
+        let shares = split_secret(&secret, 1, 2).unwrap();
+        assert_eq!(secret, recover_secret(&shares[1..]).unwrap());
+    }
+
+    #[test]
+    fn split_secret_works_for_low_entropy_secret() {
+        let secret = BigNumber::from_u32(42).unwrap();
+        let shares1 = split_secret(&secret, 2, 3).unwrap();
+        let shares2 = split_secret(&secret, 2, 3).unwrap();
+
+        // Digest is keyed by shared key, so single share doesn't allow to check guess of secret
+        assert_ne!(shares1[0].secret_digest, shares2[0].secret_digest);
+        assert_ne!(BigNumber::hash_array(&vec![b"sss-secret".to_vec(), secret.to_bytes().unwrap()]).unwrap(), shares1[0].secret_digest);
+        assert_eq!(secret, recover_secret(&shares1[1..]).unwrap());
+    }
+
+    #[test]
+    fn recover_secret_works_for_tampered_digest_key() {
+        let secret = BigNumber::rand(256).unwrap();
+        let mut shares = split_secret(&secret, 2, 3).unwrap();
+        shares[0].digest_key = shares[0].digest_key.add(&BigNumber::from_u32(1).unwrap()).unwrap();
+        shares[0].checksum = _checksum(shares[0].threshold, shares[0].index, &shares[0].value,
+                                       &shares[0].digest_key, &shares[0].secret_digest).unwrap();
+
+        assert!(shares[0].is_valid().unwrap());
+        assert!(recover_secret(&shares[..2]).is_err());
+    }
+
+    #[test]
+    fn split_secret_works_for_invalid_threshold() {
+        let secret = BigNumber::from_u32(42).unwrap();
+        assert!(split_secret(&secret, 0, 2).is_err());
+        assert!(split_secret(&secret, 3, 2).is_err());
+    }
+
+    #[test]
+    fn split_secret_works_for_secret_out_of_range() {
+        assert!(split_secret(&_field_prime().unwrap(), 2, 3).is_err());
+    }
+
+    #[test]
+    fn recover_secret_works_for_not_enough_shares() {
+        let secret = BigNumber::rand(256).unwrap();
+        let shares = split_secret(&secret, 3, 5).unwrap();
+        assert!(recover_secret(&shares[..2]).is_err());
+    }
+
+    #[test]
+    fn recover_secret_works_for_duplicated_shares() {
+        let secret = BigNumber::rand(256).unwrap();
+        let shares = split_secret(&secret, 2, 3).unwrap();
+        assert!(recover_secret(&[shares[0].clone().unwrap(), shares[0].clone().unwrap()]).is_err());
+    }
+
+    #[test]
+    fn recover_secret_works_for_corrupted_share() {
+        let secret = BigNumber::rand(256).unwrap();
+        let mut shares = split_secret(&secret, 2, 3).unwrap();
+        shares[0].value = shares[0].value.add(&BigNumber::from_u32(1).unwrap()).unwrap();
+
+        assert!(!shares[0].is_valid().unwrap());
+        assert!(recover_secret(&shares[..2]).is_err());
+    }
+
+    #[test]
+    fn recover_secret_works_for_shares_of_different_secrets() {
+        let shares1 = split_secret(&BigNumber::rand(256).unwrap(), 2, 3).unwrap();
+        let shares2 = split_secret(&BigNumber::rand(256).unwrap(), 2, 3).unwrap();
+        assert!(recover_secret(&[shares1[0].clone().unwrap(), shares2[1].clone().unwrap()]).is_err());
+    }
+
+    #[test]
+    fn secret_share_serialization_works() {
+        let shares = split_secret(&BigNumber::rand(256).unwrap(), 2, 3).unwrap();
+        let share_json = shares[0].to_json().unwrap();
+        assert_eq!(shares[0], SecretShare::from_json(&share_json).unwrap());
+    }
+}
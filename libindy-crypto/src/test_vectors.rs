@@ -0,0 +1,317 @@
+//! Known-answer test vectors for interoperability of other implementations (Python, JS) with this crate.
+//!
+//! Vectors are generated deterministically from seed: all randomness of keys, signatures and proofs
+//! is taken from `utils::drbg::HashDrbg`, so the same seed and crate version always give the same vectors.
+//! Other implementations can verify published vectors or generate own ones and check them with `TestVectors::replay`.
+use bls::{Bls, Generator, MultiSignature, SignKey, Signature, VerKey};
+use bn::prime_search_threads;
+use cl::*;
+use cl::issuer::Issuer;
+use cl::prover::Prover;
+use cl::verifier::Verifier;
+use errors::IndyCryptoError;
+use utils::drbg::with_seeded_rng;
+use utils::json::{JsonEncodable, JsonDecodable};
+
+use serde_json;
+
+pub const TEST_VECTORS_VERSION: u32 = 1;
+
+const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+const BLS_SIGNERS_COUNT: usize = 3;
+const BLS_MESSAGE: &'static [u8] = b"indy-crypto test vector message";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestVectors {
+    pub version: u32,
+    pub seed: Vec<u8>,
+    pub bls: BlsTestVector,
+    pub cl: ClTestVector
+}
+
+/// BLS keys, signatures of all signers over the same message and their multi signature.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BlsTestVector {
+    pub message: Vec<u8>,
+    pub generator: Vec<u8>,
+    pub sign_keys: Vec<Vec<u8>>,
+    pub ver_keys: Vec<Vec<u8>>,
+    pub signatures: Vec<Vec<u8>>,
+    pub multi_signature: Vec<u8>
+}
+
+/// Credential definition, issued credential and proof of revealed attribute and predicate.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ClTestVector {
+    pub credential_schema: CredentialSchema,
+    pub credential_values: CredentialValues,
+    pub credential_pub_key: CredentialPublicKey,
+    pub credential_key_correctness_proof: CredentialKeyCorrectnessProof,
+    pub credential_signature: CredentialSignature,
+    pub sub_proof_request: SubProofRequest,
+    pub nonce: Nonce,
+    pub proof: Proof
+}
+
+impl TestVectors {
+    /// Generates test vectors from seed.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of deterministic RNG, at least 16 bytes.
+    ///
+    /// Generation of safe primes must be single threaded (see `bn::set_prime_search_threads`),
+    /// as worker threads don't use seeded RNG.
+    pub fn generate(seed: &[u8]) -> Result<TestVectors, IndyCryptoError> {
+        trace!("TestVectors::generate: >>> seed: {:?}", seed);
+
+        if prime_search_threads() != 1 {
+            return Err(IndyCryptoError::InvalidState("Test vectors can be generated only with single prime search thread".to_string()));
+        }
+
+        let test_vectors = with_seeded_rng(seed, || {
+            Ok(TestVectors {
+                version: TEST_VECTORS_VERSION,
+                seed: seed.to_vec(),
+                bls: TestVectors::_generate_bls()?,
+                cl: TestVectors::_generate_cl()?
+            })
+        })?;
+
+        trace!("TestVectors::generate: <<< test_vectors: {:?}", test_vectors);
+
+        Ok(test_vectors)
+    }
+
+    /// Replays test vectors: checks deterministic values (BLS verification keys and signatures)
+    /// against ones computed by this crate and verifies signatures and proof.
+    pub fn replay(&self) -> Result<(), IndyCryptoError> {
+        trace!("TestVectors::replay: >>> version: {:?}", self.version);
+
+        if self.version != TEST_VECTORS_VERSION {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported test vectors version: {}", self.version)));
+        }
+
+        self.bls.replay()?;
+        self.cl.replay()?;
+
+        trace!("TestVectors::replay: <<<");
+
+        Ok(())
+    }
+
+    /// Checks that vectors are equal to ones generated by this crate from the same seed.
+    pub fn check_generated(&self) -> Result<(), IndyCryptoError> {
+        let generated = TestVectors::generate(&self.seed)?;
+
+        // Maps are serialized in arbitrary order, so vectors are compared as JSON values
+        let expected: serde_json::Value = serde_json::from_str(&generated.to_json()?)?;
+        let actual: serde_json::Value = serde_json::from_str(&self.to_json()?)?;
+
+        if expected != actual {
+            return Err(IndyCryptoError::InvalidStructure("Test vectors differ from generated from the same seed".to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn _generate_bls() -> Result<BlsTestVector, IndyCryptoError> {
+        let generator = Generator::new()?;
+
+        let mut sign_keys: Vec<SignKey> = Vec::new();
+        let mut ver_keys: Vec<VerKey> = Vec::new();
+        let mut signatures: Vec<Signature> = Vec::new();
+
+        for _ in 0..BLS_SIGNERS_COUNT {
+            let sign_key = SignKey::new(None)?;
+            ver_keys.push(VerKey::new(&generator, &sign_key)?);
+            signatures.push(Bls::sign(BLS_MESSAGE, &sign_key)?);
+            sign_keys.push(sign_key);
+        }
+
+        let multi_signature = MultiSignature::new(&signatures.iter().collect::<Vec<&Signature>>())?;
+
+        Ok(BlsTestVector {
+            message: BLS_MESSAGE.to_vec(),
+            generator: generator.as_bytes().to_vec(),
+            sign_keys: sign_keys.iter().map(|sign_key| sign_key.as_bytes().to_vec()).collect(),
+            ver_keys: ver_keys.iter().map(|ver_key| ver_key.as_bytes().to_vec()).collect(),
+            signatures: signatures.iter().map(|signature| signature.as_bytes().to_vec()).collect(),
+            multi_signature: multi_signature.as_bytes().to_vec()
+        })
+    }
+
+    fn _generate_cl() -> Result<ClTestVector, IndyCryptoError> {
+        let mut credential_schema_builder = CredentialSchemaBuilder::new()?;
+        credential_schema_builder.add_attr("name")?;
+        credential_schema_builder.add_attr("sex")?;
+        credential_schema_builder.add_attr("age")?;
+        credential_schema_builder.add_attr("height")?;
+        let credential_schema = credential_schema_builder.finalize()?;
+
+        let mut credential_values_builder = CredentialValuesBuilder::new()?;
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335")?;
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103")?;
+        credential_values_builder.add_value("age", "28")?;
+        credential_values_builder.add_value("height", "175")?;
+        let credential_values = credential_values_builder.finalize()?;
+
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false)?;
+
+        let master_secret = Prover::new_master_secret()?;
+        let master_secret_blinding_nonce = new_nonce()?;
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key, &credential_key_correctness_proof, &master_secret, &master_secret_blinding_nonce)?;
+
+        let credential_issuance_nonce = new_nonce()?;
+        let (mut credential_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &credential_issuance_nonce,
+                                    &credential_values,
+                                    &credential_pub_key,
+                                    &credential_priv_key)?;
+
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None)?;
+
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new()?;
+        sub_proof_request_builder.add_revealed_attr("name")?;
+        sub_proof_request_builder.add_predicate("age", "GE", 18)?;
+        let sub_proof_request = sub_proof_request_builder.finalize()?;
+
+        let nonce = new_nonce()?;
+
+        let mut proof_builder = Prover::new_proof_builder()?;
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None)?;
+        let proof = proof_builder.finalize(&nonce, &master_secret)?;
+
+        Ok(ClTestVector {
+            credential_schema,
+            credential_values,
+            credential_pub_key,
+            credential_key_correctness_proof,
+            credential_signature,
+            sub_proof_request,
+            nonce,
+            proof
+        })
+    }
+}
+
+impl BlsTestVector {
+    fn replay(&self) -> Result<(), IndyCryptoError> {
+        if self.sign_keys.len() != self.ver_keys.len() || self.sign_keys.len() != self.signatures.len() {
+            return Err(IndyCryptoError::InvalidStructure("BLS test vector has different counts of keys and signatures".to_string()));
+        }
+
+        let generator = Generator::from_bytes(&self.generator)?;
+
+        let mut ver_keys: Vec<VerKey> = Vec::new();
+        let mut signatures: Vec<Signature> = Vec::new();
+
+        for ((sign_key, ver_key), signature) in self.sign_keys.iter().zip(self.ver_keys.iter()).zip(self.signatures.iter()) {
+            let sign_key = SignKey::from_bytes(sign_key)?;
+
+            if VerKey::new(&generator, &sign_key)?.as_bytes() != ver_key.as_slice() {
+                return Err(IndyCryptoError::InvalidStructure("BLS verification key doesn't correspond to sign key".to_string()));
+            }
+
+            if Bls::sign(&self.message, &sign_key)?.as_bytes() != signature.as_slice() {
+                return Err(IndyCryptoError::InvalidStructure("BLS signature differs from computed one".to_string()));
+            }
+
+            let ver_key = VerKey::from_bytes(ver_key)?;
+            let signature = Signature::from_bytes(signature)?;
+
+            if !Bls::verify(&signature, &self.message, &ver_key, &generator)? {
+                return Err(IndyCryptoError::InvalidStructure("BLS signature is invalid".to_string()));
+            }
+
+            ver_keys.push(ver_key);
+            signatures.push(signature);
+        }
+
+        let multi_signature = MultiSignature::from_bytes(&self.multi_signature)?;
+
+        if MultiSignature::new(&signatures.iter().collect::<Vec<&Signature>>())?.as_bytes() != multi_signature.as_bytes() {
+            return Err(IndyCryptoError::InvalidStructure("BLS multi signature differs from computed one".to_string()));
+        }
+
+        if !Bls::verify_multi_sig(&multi_signature, &self.message, &ver_keys.iter().collect::<Vec<&VerKey>>(), &generator)? {
+            return Err(IndyCryptoError::InvalidStructure("BLS multi signature is invalid".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl ClTestVector {
+    fn replay(&self) -> Result<(), IndyCryptoError> {
+        // Key correctness proof is checked by blinding of master secret
+        Prover::blind_master_secret(&self.credential_pub_key,
+                                    &self.credential_key_correctness_proof,
+                                    &Prover::new_master_secret()?,
+                                    &new_nonce()?)?;
+
+        let mut proof_verifier = Verifier::new_proof_verifier()?;
+        proof_verifier.add_sub_proof_request(&self.sub_proof_request,
+                                             &self.credential_schema,
+                                             &self.credential_pub_key,
+                                             None,
+                                             None)?;
+
+        if !proof_verifier.verify(&self.proof, &self.nonce)? {
+            return Err(IndyCryptoError::InvalidStructure("CL proof is invalid".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl JsonEncodable for TestVectors {}
+
+impl<'a> JsonDecodable<'a> for TestVectors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &'static [u8] = b"0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_vectors_work() {
+        let test_vectors = TestVectors::generate(SEED).unwrap();
+        test_vectors.replay().unwrap();
+
+        let restored_test_vectors = TestVectors::from_json(&test_vectors.to_json().unwrap()).unwrap();
+        restored_test_vectors.replay().unwrap();
+        restored_test_vectors.check_generated().unwrap();
+    }
+
+    #[test]
+    fn test_vectors_replay_works_for_changed_values() {
+        let mut test_vectors = TestVectors::generate(SEED).unwrap();
+
+        test_vectors.bls.signatures.swap(0, 1);
+        assert!(test_vectors.replay().is_err());
+        test_vectors.bls.signatures.swap(0, 1);
+
+        test_vectors.cl.nonce = new_nonce().unwrap();
+        assert!(test_vectors.replay().is_err());
+    }
+}
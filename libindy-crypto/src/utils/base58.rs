@@ -0,0 +1,82 @@
+use errors::IndyCryptoError;
+
+const ALPHABET: &'static [u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes bytes to base58 string with bitcoin alphabet (used by indy for DIDs and verkeys).
+pub fn encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // Little-endian digits in base 58
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in bytes[zeros..].iter() {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut result = String::with_capacity(zeros + digits.len());
+    for _ in 0..zeros {
+        result.push(ALPHABET[0] as char);
+    }
+    for &digit in digits.iter().rev() {
+        result.push(ALPHABET[digit as usize] as char);
+    }
+    result
+}
+
+/// Decodes base58 string with bitcoin alphabet.
+pub fn decode(s: &str) -> Result<Vec<u8>, IndyCryptoError> {
+    let zeros = s.bytes().take_while(|&c| c == ALPHABET[0]).count();
+
+    // Little-endian bytes
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in s.bytes().skip(zeros) {
+        let mut carry = ALPHABET.iter().position(|&a| a == c)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Invalid base58 character: {:?}", c as char)))? as u32;
+
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xFF) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xFF) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut result = vec![0u8; zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_works() {
+        assert_eq!("", encode(&[]));
+        assert_eq!("11", encode(&[0, 0]));
+        assert_eq!("StV1DL6CwTryKyV", encode(b"hello world"));
+        assert_eq!("1112", encode(&[0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn decode_works() {
+        assert_eq!(b"hello world".to_vec(), decode("StV1DL6CwTryKyV").unwrap());
+        assert_eq!(vec![0, 0, 0, 1], decode("1112").unwrap());
+    }
+
+    #[test]
+    fn decode_works_for_invalid_character() {
+        assert!(decode("0OIl").is_err());
+    }
+}
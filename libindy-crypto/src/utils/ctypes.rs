@@ -3,6 +3,7 @@ use libc::c_char;
 use std::ffi::CStr;
 use std::str::Utf8Error;
 use std::ffi::CString;
+use std::slice;
 
 pub struct CTypesUtils {}
 
@@ -23,6 +24,19 @@ impl CTypesUtils {
     pub fn string_to_cstring(s: String) -> CString {
         CString::new(s).unwrap()
     }
+
+    /// Passes ownership of bytes to the caller as (pointer, length) buffer.
+    /// Buffer must be returned to the library by indy_crypto_buffer_free.
+    pub fn vec_to_buffer(bytes: Vec<u8>) -> (*const u8, usize) {
+        let bytes = bytes.into_boxed_slice();
+        let len = bytes.len();
+        (Box::into_raw(bytes) as *mut u8 as *const u8, len)
+    }
+
+    /// Takes back ownership of buffer created by vec_to_buffer.
+    pub unsafe fn buffer_to_vec(buffer: *mut u8, len: usize) -> Vec<u8> {
+        Box::from_raw(slice::from_raw_parts_mut(buffer, len) as *mut [u8]).into_vec()
+    }
 }
 
 macro_rules! check_useful_c_byte_array {
@@ -53,51 +67,72 @@ macro_rules! check_useful_opt_c_byte_array {
     }
 }
 
-macro_rules! check_useful_c_reference {
-    ($ptr:ident, $type:ty, $err:expr) => {
-        if $ptr.is_null() {
-            return $err
-        }
+macro_rules! check_useful_handle {
+    ($handle:ident, $type:ty, $err:expr) => {
+        let handle_ref = match ::ffi::handles::Handles::get::<$type>($handle) {
+            Ok(handle_ref) => handle_ref,
+            Err(_) => return $err
+        };
 
-        let $ptr: &$type = unsafe { &*($ptr as *const $type) };;
+        let $handle: &$type = &*handle_ref;
     }
 }
 
-macro_rules! check_useful_mut_c_reference {
-    ($ptr:ident, $type:ty, $err:expr) => {
-        if $ptr.is_null() {
-            return $err
-        }
+macro_rules! check_useful_mut_handle {
+    ($handle:ident, $type:ty, $err:expr) => {
+        let mut handle_guard = match ::ffi::handles::Handles::get_mut::<$type>($handle) {
+            Ok(handle_guard) => handle_guard,
+            Err(_) => return $err
+        };
 
-        let $ptr: &mut $type = unsafe { &mut *($ptr as *mut $type) };;
+        let $handle: &mut $type = &mut *handle_guard;
     }
 }
 
-macro_rules! check_useful_opt_c_reference {
-    ($ptr:ident, $type:ty) => {
-        let $ptr: Option<&$type> = if $ptr.is_null() {
+macro_rules! check_useful_opt_handle {
+    ($handle:ident, $type:ty, $err:expr) => {
+        let handle_ref = if $handle == ::ffi::handles::INVALID_HANDLE {
             None
         } else {
-            Some(unsafe { & *($ptr as *const $type) })
+            match ::ffi::handles::Handles::get::<$type>($handle) {
+                Ok(handle_ref) => Some(handle_ref),
+                Err(_) => return $err
+            }
         };
+
+        let $handle: Option<&$type> = handle_ref.as_ref().map(|handle_ref| &**handle_ref);
     }
 }
 
-macro_rules! check_useful_c_reference_array {
-    ($ptrs:ident, $ptrs_len:ident, $type:ty, $err1:expr, $err2:expr) => {
-        if $ptrs.is_null() {
+macro_rules! check_useful_removed_handle {
+    ($handle:ident, $type:ty, $err:expr) => {
+        let $handle: $type = match ::ffi::handles::Handles::remove::<$type>($handle) {
+            Ok(value) => value,
+            Err(_) => return $err
+        };
+    }
+}
+
+macro_rules! check_useful_handle_array {
+    ($handles:ident, $handles_len:ident, $type:ty, $err1:expr, $err2:expr) => {
+        if $handles.is_null() {
             return $err1
         }
 
-        if $ptrs_len <= 0 {
+        if $handles_len <= 0 {
             return $err2
         }
 
-        let $ptrs: Vec<&$type> =
-            unsafe { slice::from_raw_parts($ptrs, $ptrs_len) }
-                .iter()
-                .map(|ptr| unsafe { &*(*ptr as *const $type) })
-                .collect();
+        // The same handle can be passed several times
+        let handle_refs = match unsafe { slice::from_raw_parts($handles, $handles_len) }
+            .iter()
+            .map(|handle| ::ffi::handles::Handles::get::<$type>(*handle))
+            .collect::<Result<Vec<_>, _>>() {
+            Ok(handle_refs) => handle_refs,
+            Err(_) => return $err1
+        };
+
+        let $handles: Vec<&$type> = handle_refs.iter().map(|handle_ref| &**handle_ref).collect();
     }
 }
 
@@ -120,4 +155,23 @@ macro_rules! check_useful_c_str {
             return $e
         }
     }
-}
\ No newline at end of file
+}
+
+macro_rules! check_useful_opt_c_str {
+    ($x:ident, $e:expr) => {
+        let $x = match CTypesUtils::c_str_to_string($x) {
+            Ok(Some(val)) => if val.is_empty() { return $e } else { Some(val) },
+            Ok(None) => None,
+            Err(_) => return $e
+        };
+    }
+}
+
+macro_rules! catch_panic {
+    ($body:block) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $body)) {
+            Ok(res) => res,
+            Err(err) => ::ffi::set_panic_error(err)
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use errors::IndyCryptoError;
+use utils::rng::{CryptoRng, with_rng};
+
+use sha2::{Sha256, Digest};
+
+
+pub const MIN_SEED_LEN: usize = 16;
+
+const DRBG_DOMAIN: &'static [u8] = b"indy-crypto-drbg";
+
+/// Deterministic random bit generator based on SHA-256 in counter mode.
+/// Output block `i` is `SHA-256(SHA-256(domain || seed) || i)`.
+#[derive(Debug)]
+pub struct HashDrbg {
+    key: Vec<u8>,
+    counter: u64,
+    buffer: Vec<u8>
+}
+
+impl HashDrbg {
+    pub fn new(seed: &[u8]) -> Result<HashDrbg, IndyCryptoError> {
+        if seed.len() < MIN_SEED_LEN {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid len of seed: expected at least {}, actual {}", MIN_SEED_LEN, seed.len())));
+        }
+
+        let mut hasher = Sha256::default();
+        hasher.input(DRBG_DOMAIN);
+        hasher.input(seed);
+
+        Ok(HashDrbg {
+            key: hasher.result().as_slice().to_vec(),
+            counter: 0,
+            buffer: Vec::new()
+        })
+    }
+
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            if self.buffer.is_empty() {
+                self._next_block();
+            }
+            *byte = self.buffer.pop().unwrap();
+        }
+    }
+
+    pub fn next_bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0; len];
+        self.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    fn _next_block(&mut self) {
+        let counter = [
+            (self.counter >> 56) as u8, (self.counter >> 48) as u8, (self.counter >> 40) as u8, (self.counter >> 32) as u8,
+            (self.counter >> 24) as u8, (self.counter >> 16) as u8, (self.counter >> 8) as u8, self.counter as u8
+        ];
+
+        let mut hasher = Sha256::default();
+        hasher.input(&self.key);
+        hasher.input(&counter);
+
+        let mut block = hasher.result().as_slice().to_vec();
+        block.reverse();
+
+        self.buffer = block;
+        self.counter += 1;
+    }
+}
+
+impl CryptoRng for HashDrbg {
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), IndyCryptoError> {
+        HashDrbg::fill_bytes(self, dest);
+        Ok(())
+    }
+}
+
+/// Runs `f` with all randomness of `bn` and `pair` modules in the current thread
+/// derived from `seed`, so the same seed always gives the same result.
+pub fn with_seeded_rng<F, T>(seed: &[u8], f: F) -> Result<T, IndyCryptoError>
+    where F: FnOnce() -> Result<T, IndyCryptoError> {
+    with_rng(Box::new(HashDrbg::new(seed)?), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::rng::random_bytes;
+
+    const SEED: &'static [u8] = b"0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn hash_drbg_works() {
+        let mut drbg1 = HashDrbg::new(SEED).unwrap();
+        let mut drbg2 = HashDrbg::new(SEED).unwrap();
+        assert_eq!(drbg1.next_bytes(100), drbg2.next_bytes(100));
+
+        let mut drbg3 = HashDrbg::new(b"fedcba9876543210fedcba9876543210").unwrap();
+        assert_ne!(drbg1.next_bytes(100), drbg3.next_bytes(100));
+    }
+
+    #[test]
+    fn hash_drbg_works_for_short_seed() {
+        assert!(HashDrbg::new(b"short").is_err());
+    }
+
+    #[test]
+    fn with_seeded_rng_works() {
+        let bytes1 = with_seeded_rng(SEED, || random_bytes(32)).unwrap();
+        let bytes2 = with_seeded_rng(SEED, || random_bytes(32)).unwrap();
+        assert_eq!(bytes1, bytes2);
+
+        assert_ne!(bytes1, random_bytes(32).unwrap());
+    }
+}
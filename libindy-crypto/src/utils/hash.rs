@@ -0,0 +1,122 @@
+use bn::BigNumber;
+use errors::IndyCryptoError;
+use pair::PointG1;
+
+use sha2::{Digest, Sha256, Sha384};
+use sha3::Sha3_256;
+
+enum HasherState {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha3_256(Sha3_256)
+}
+
+/// Streaming hash of Fiat-Shamir challenge values.
+/// Values are absorbed as they are produced, so hashing a proof with many credentials
+/// doesn't require collecting all of them in a temporary list.
+/// The result is the same as hashing the concatenation of all absorbed values.
+pub struct ChallengeHasher {
+    state: HasherState,
+    output_len: Option<usize>
+}
+
+impl ChallengeHasher {
+    pub fn sha256() -> ChallengeHasher {
+        ChallengeHasher { state: HasherState::Sha256(Sha256::default()), output_len: None }
+    }
+
+    /// SHA-384 hasher with output truncated to `output_len` bytes.
+    pub fn sha384_truncated(output_len: usize) -> ChallengeHasher {
+        ChallengeHasher { state: HasherState::Sha384(Sha384::default()), output_len: Some(output_len) }
+    }
+
+    pub fn sha3_256() -> ChallengeHasher {
+        ChallengeHasher { state: HasherState::Sha3_256(Sha3_256::default()), output_len: None }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) -> &mut ChallengeHasher {
+        match self.state {
+            HasherState::Sha256(ref mut hasher) => hasher.input(bytes),
+            HasherState::Sha384(ref mut hasher) => hasher.input(bytes),
+            HasherState::Sha3_256(ref mut hasher) => hasher.input(bytes)
+        }
+        self
+    }
+
+    pub fn update_all(&mut self, values: &[Vec<u8>]) -> &mut ChallengeHasher {
+        for value in values.iter() {
+            self.update(value);
+        }
+        self
+    }
+
+    pub fn update_bignum(&mut self, num: &BigNumber) -> Result<&mut ChallengeHasher, IndyCryptoError> {
+        Ok(self.update(&num.to_bytes()?))
+    }
+
+    pub fn update_point(&mut self, point: &PointG1) -> Result<&mut ChallengeHasher, IndyCryptoError> {
+        Ok(self.update(&point.to_bytes()?))
+    }
+
+    pub fn finalize_bytes(self) -> Vec<u8> {
+        let mut digest = match self.state {
+            HasherState::Sha256(hasher) => hasher.result().as_slice().to_vec(),
+            HasherState::Sha384(hasher) => hasher.result().as_slice().to_vec(),
+            HasherState::Sha3_256(hasher) => hasher.result().as_slice().to_vec()
+        };
+        if let Some(output_len) = self.output_len {
+            digest.truncate(output_len);
+        }
+        digest
+    }
+
+    pub fn finalize(self) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::from_bytes(&self.finalize_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge_hasher_works_for_split_input() {
+        let mut whole = ChallengeHasher::sha256();
+        whole.update(b"indy-crypto");
+
+        let mut split = ChallengeHasher::sha256();
+        split.update(b"indy").update(b"-").update(b"crypto");
+
+        assert_eq!(whole.finalize().unwrap(), split.finalize().unwrap());
+    }
+
+    #[test]
+    fn challenge_hasher_sha256_matches_hash_array() {
+        let values = vec![vec![1u8, 2, 3], vec![4u8, 5], vec![]];
+
+        let mut hasher = ChallengeHasher::sha256();
+        hasher.update_all(&values);
+
+        assert_eq!(BigNumber::hash_array(&values).unwrap(), hasher.finalize_bytes());
+    }
+
+    #[test]
+    fn challenge_hasher_works_for_truncated_output() {
+        let mut hasher = ChallengeHasher::sha384_truncated(32);
+        hasher.update(b"indy-crypto");
+        assert_eq!(32, hasher.finalize_bytes().len());
+    }
+
+    #[test]
+    fn challenge_hasher_works_for_bignum() {
+        let num = BigNumber::from_dec("123456789").unwrap();
+
+        let mut expected = ChallengeHasher::sha3_256();
+        expected.update(&num.to_bytes().unwrap());
+
+        let mut hasher = ChallengeHasher::sha3_256();
+        hasher.update_bignum(&num).unwrap();
+
+        assert_eq!(expected.finalize().unwrap(), hasher.finalize().unwrap());
+    }
+}
@@ -1,7 +1,9 @@
 extern crate serde;
 extern crate serde_json;
 
-use self::serde::{Serialize, Deserialize};
+use self::serde::{Serialize, Serializer, Deserialize};
+use std::collections::HashSet;
+use std::hash::Hash;
 use std::string::String;
 use errors::IndyCryptoError;
 
@@ -11,6 +13,19 @@ pub trait JsonEncodable: Serialize + Sized {
         serde_json::to_string(self)
             .map_err(|err| IndyCryptoError::from(err))
     }
+
+    /// Serializes value to canonical JSON: object keys are sorted bytewise on all levels, there are no whitespaces,
+    /// integers are written in shortest decimal form and big numbers as decimal strings.
+    /// Canonical JSON of equal values is byte-identical, so it can be hashed and signed
+    /// and reproduced by implementations in other languages.
+    fn to_canonical_json(&self) -> Result<String, IndyCryptoError> {
+        // serde_json::Value keeps object keys in BTreeMap, so serialization through it sorts keys.
+        let value = serde_json::to_value(self)
+            .map_err(|err| IndyCryptoError::from(err))?;
+
+        serde_json::to_string(&value)
+            .map_err(|err| IndyCryptoError::from(err))
+    }
 }
 
 pub trait JsonDecodable<'a>: Deserialize<'a> {
@@ -19,3 +34,43 @@ pub trait JsonDecodable<'a>: Deserialize<'a> {
             .map_err(|err| IndyCryptoError::from(err))
     }
 }
+
+/// Serializes set as array sorted in ascending order, so JSON of equal sets is the same.
+pub fn serialize_sorted_set<T, S>(set: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where T: Serialize + Ord + Hash, S: Serializer {
+    let mut items = set.iter().collect::<Vec<&T>>();
+    items.sort();
+    items.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Artifact {
+        name: String,
+        values: HashMap<String, u64>,
+        #[serde(serialize_with = "serialize_sorted_set")]
+        ids: HashSet<u64>
+    }
+
+    impl JsonEncodable for Artifact {}
+
+    #[test]
+    fn to_canonical_json_works() {
+        let mut values = HashMap::new();
+        values.insert("b".to_string(), 2);
+        values.insert("a".to_string(), 1);
+        values.insert("C".to_string(), 3);
+
+        let artifact = Artifact {
+            name: "artifact".to_string(),
+            values,
+            ids: vec![10, 2, 7].into_iter().collect()
+        };
+
+        assert_eq!(r#"{"ids":[2,7,10],"name":"artifact","values":{"C":3,"a":1,"b":2}}"#, artifact.to_canonical_json().unwrap());
+    }
+}
@@ -0,0 +1,163 @@
+use errors::IndyCryptoError;
+
+use libc::c_char;
+use log;
+use log::{Log, LogLevel, LogLevelFilter, LogMetadata, LogRecord};
+use time;
+
+use std::ffi::CString;
+use std::sync::Mutex;
+
+/// Log target of operation timing records.
+///
+/// Timing records have message of the form `operation=<name> elapsed_us=<microseconds>`.
+pub const TIMING_TARGET: &'static str = "indy_crypto::timing";
+
+/// Host callback receiving log records.
+///
+/// # Arguments
+/// * `level` - Record level: 1 - error, 2 - warn, 3 - info, 4 - debug, 5 - trace.
+/// * `target` - Record target.
+/// * `message` - Formatted record message.
+/// * `module_path` - Module that emitted record.
+/// * `file` - Source file that emitted record.
+/// * `line` - Source line that emitted record.
+///
+/// All strings are valid only during the call.
+pub type LogCallback = extern fn(level: u32,
+                                 target: *const c_char,
+                                 message: *const c_char,
+                                 module_path: *const c_char,
+                                 file: *const c_char,
+                                 line: u32);
+
+/// Logger that routes records of `log` facade to the host callback.
+///
+/// Callback invocations are serialized, so host callback is never called concurrently.
+pub struct CallbackLogger {
+    callback: LogCallback,
+    level: LogLevelFilter,
+    lock: Mutex<()>
+}
+
+impl CallbackLogger {
+    /// Installs callback logger as `log` facade logger.
+    ///
+    /// Logger can be installed only once per process, so it fails if any logger
+    /// (including env logger installed by indy_crypto_init_logger) was already set.
+    ///
+    /// # Arguments
+    /// * `callback` - Host callback receiving log records.
+    /// * `level` - Maximal level of records passed to callback: 0 - off, 1 - error, 2 - warn, 3 - info, 4 - debug, 5 - trace.
+    pub fn init(callback: LogCallback, level: u32) -> Result<(), IndyCryptoError> {
+        let level = level_filter_from_u32(level)?;
+
+        log::set_logger(|max_level| {
+            max_level.set(level);
+            Box::new(CallbackLogger {
+                callback,
+                level,
+                lock: Mutex::new(())
+            })
+        }).map_err(|_| IndyCryptoError::InvalidState(format!("Logger is already set")))
+    }
+}
+
+impl Log for CallbackLogger {
+    fn enabled(&self, metadata: &LogMetadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &LogRecord) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let target = _to_cstring(record.target());
+        let message = _to_cstring(&format!("{}", record.args()));
+        let module_path = _to_cstring(record.location().module_path());
+        let file = _to_cstring(record.location().file());
+
+        let _guard = self.lock.lock();
+
+        (self.callback)(level_to_u32(record.level()),
+                        target.as_ptr(),
+                        message.as_ptr(),
+                        module_path.as_ptr(),
+                        file.as_ptr(),
+                        record.location().line());
+    }
+}
+
+/// Measures duration of operation and reports it to `TIMING_TARGET` on drop.
+pub struct OperationTimer {
+    operation: &'static str,
+    started_at: u64
+}
+
+impl OperationTimer {
+    pub fn start(operation: &'static str) -> OperationTimer {
+        OperationTimer {
+            operation,
+            started_at: time::precise_time_ns()
+        }
+    }
+}
+
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        let elapsed_us = (time::precise_time_ns() - self.started_at) / 1000;
+        debug!(target: TIMING_TARGET, "operation={} elapsed_us={}", self.operation, elapsed_us);
+    }
+}
+
+pub fn level_filter_from_u32(level: u32) -> Result<LogLevelFilter, IndyCryptoError> {
+    match level {
+        0 => Ok(LogLevelFilter::Off),
+        1 => Ok(LogLevelFilter::Error),
+        2 => Ok(LogLevelFilter::Warn),
+        3 => Ok(LogLevelFilter::Info),
+        4 => Ok(LogLevelFilter::Debug),
+        5 => Ok(LogLevelFilter::Trace),
+        _ => Err(IndyCryptoError::InvalidParam2(format!("Invalid log level: {}", level)))
+    }
+}
+
+pub fn level_to_u32(level: LogLevel) -> u32 {
+    match level {
+        LogLevel::Error => 1,
+        LogLevel::Warn => 2,
+        LogLevel::Info => 3,
+        LogLevel::Debug => 4,
+        LogLevel::Trace => 5
+    }
+}
+
+fn _to_cstring(s: &str) -> CString {
+    // Interior nul bytes are dropped so the conversion can't fail
+    CString::new(s.replace('\0', "")).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_filter_from_u32_works() {
+        assert_eq!(LogLevelFilter::Off, level_filter_from_u32(0).unwrap());
+        assert_eq!(LogLevelFilter::Error, level_filter_from_u32(1).unwrap());
+        assert_eq!(LogLevelFilter::Trace, level_filter_from_u32(5).unwrap());
+    }
+
+    #[test]
+    fn level_filter_from_u32_works_for_invalid_level() {
+        assert!(level_filter_from_u32(6).is_err());
+    }
+
+    #[test]
+    fn level_to_u32_works() {
+        assert_eq!(1, level_to_u32(LogLevel::Error));
+        assert_eq!(3, level_to_u32(LogLevel::Info));
+        assert_eq!(5, level_to_u32(LogLevel::Trace));
+    }
+}
@@ -1,4 +1,8 @@
 #[macro_use]
 pub mod ctypes;
+pub mod base58;
 pub mod json;
-pub mod commitment;
+pub mod drbg;
+pub mod hash;
+pub mod rng;
+pub mod logger;
@@ -0,0 +1,133 @@
+use errors::IndyCryptoError;
+
+use rand::os::OsRng;
+use rand::Rng;
+
+use std::cell::RefCell;
+use std::sync::{Mutex, MutexGuard, Once, ONCE_INIT};
+
+/// Source of randomness used by `bn` and `pair` modules for all secret values
+/// (keys, primes, blinding factors, nonces).
+pub trait CryptoRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), IndyCryptoError>;
+}
+
+/// Default randomness source backed by operating system RNG.
+pub struct OsCryptoRng {}
+
+impl OsCryptoRng {
+    pub fn new() -> OsCryptoRng {
+        OsCryptoRng {}
+    }
+}
+
+impl CryptoRng for OsCryptoRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), IndyCryptoError> {
+        let mut os_rng = OsRng::new()
+            .map_err(|err| IndyCryptoError::InvalidState(format!("Can't access OS RNG: {:?}", err)))?;
+        os_rng.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+static mut DEFAULT_RNG: *const Mutex<Option<Box<CryptoRng + Send>>> = 0 as *const Mutex<Option<Box<CryptoRng + Send>>>;
+static DEFAULT_RNG_INIT: Once = ONCE_INIT;
+
+fn _default_rng() -> MutexGuard<'static, Option<Box<CryptoRng + Send>>> {
+    DEFAULT_RNG_INIT.call_once(|| unsafe {
+        DEFAULT_RNG = Box::into_raw(Box::new(Mutex::new(None)));
+    });
+
+    unsafe { &*DEFAULT_RNG }.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+thread_local! {
+    static THREAD_RNG: RefCell<Option<Box<CryptoRng>>> = RefCell::new(None);
+}
+
+/// Replaces process wide randomness source (for example with HSM backed one).
+pub fn set_default_rng(rng: Box<CryptoRng + Send>) {
+    *_default_rng() = Some(rng);
+}
+
+/// Restores operating system RNG as process wide randomness source.
+pub fn reset_default_rng() {
+    *_default_rng() = None;
+}
+
+struct ThreadRngScope {
+    prev: Option<Box<CryptoRng>>
+}
+
+impl Drop for ThreadRngScope {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        THREAD_RNG.with(|rng| *rng.borrow_mut() = prev);
+    }
+}
+
+/// Runs `f` with `rng` used as randomness source in the current thread.
+pub fn with_rng<F, T>(rng: Box<CryptoRng>, f: F) -> Result<T, IndyCryptoError>
+    where F: FnOnce() -> Result<T, IndyCryptoError> {
+    let _scope = ThreadRngScope {
+        prev: THREAD_RNG.with(|prev| prev.borrow_mut().take())
+    };
+
+    THREAD_RNG.with(|cur| *cur.borrow_mut() = Some(rng));
+
+    f()
+}
+
+/// Fills `dest` from randomness source of the current thread, process wide source or OS RNG.
+pub fn fill_random_bytes(dest: &mut [u8]) -> Result<(), IndyCryptoError> {
+    let res = THREAD_RNG.with(|rng| {
+        rng.borrow_mut().as_mut().map(|rng| rng.fill_bytes(dest))
+    });
+
+    if let Some(res) = res {
+        return res;
+    }
+
+    match *_default_rng() {
+        Some(ref mut rng) => rng.fill_bytes(dest),
+        None => OsCryptoRng::new().fill_bytes(dest)
+    }
+}
+
+pub fn random_bytes(len: usize) -> Result<Vec<u8>, IndyCryptoError> {
+    let mut bytes = vec![0; len];
+    fill_random_bytes(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ZeroRng {}
+
+    impl CryptoRng for ZeroRng {
+        fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), IndyCryptoError> {
+            for byte in dest.iter_mut() {
+                *byte = 0;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn random_bytes_works() {
+        let bytes1 = random_bytes(32).unwrap();
+        let bytes2 = random_bytes(32).unwrap();
+        assert_eq!(32, bytes1.len());
+        assert_ne!(bytes1, bytes2);
+    }
+
+    #[test]
+    fn with_rng_works() {
+        let bytes = with_rng(Box::new(ZeroRng {}), || random_bytes(32)).unwrap();
+        assert_eq!(vec![0; 32], bytes);
+
+        assert_ne!(vec![0; 32], random_bytes(32).unwrap());
+    }
+}
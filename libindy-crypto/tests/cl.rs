@@ -3,11 +3,18 @@ extern crate serde_derive;
 extern crate serde_json;
 extern crate indy_crypto;
 
-use indy_crypto::cl::{new_nonce, Witness, RevocationRegistry, RevocationRegistryDelta, SimpleTailsAccessor};
+use indy_crypto::bls::{Generator, SignKey, VerKey};
+use indy_crypto::cl::{new_nonce, AttrNameCanonicalization, ChallengeHash, CredentialDefinition, CredentialPublicKey, CredentialSignatureBackend, Nonce, PredicateProofVersion, Proof, PsCredentialPublicKey, PsProof, SecurityProfile, Witness, RevocationRegistry, RevocationRegistryDelta, SimpleTailsAccessor};
 use indy_crypto::cl::issuer::Issuer;
-use indy_crypto::cl::prover::Prover;
+use indy_crypto::cl::prover::{Prover, ProofBuilder};
 use indy_crypto::cl::verifier::Verifier;
+#[cfg(feature = "sign_sodium")]
+use indy_crypto::cl::DeviceBinding;
+#[cfg(feature = "sign_sodium")]
+use indy_crypto::ed25519;
 use indy_crypto::pair::PointG2;
+use indy_crypto::utils::hash::ChallengeHasher;
+use indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
 use std::collections::HashSet;
 
 pub const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
@@ -304,6 +311,770 @@ mod test {
         assert!(proof_verifier.verify(&proof, &nonce).unwrap());
     }
 
+    #[test]
+    fn anoncreds_works_for_multiple_issuer_key_candidates() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Two issuers create credential definitions, credential is issued by the second one
+        let (other_credential_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof_without_key_id = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        proof_builder.set_sub_proof_key_id(0, "issuer2:key").unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+        assert_eq!(Some("issuer2:key"), proof.sub_proofs()[0].key_id());
+
+        // 13. Verifier verifies proof accepting credentials of both issuers
+        let candidate_pub_keys = [("issuer1:key", &other_credential_pub_key), ("issuer2:key", &credential_pub_key)];
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request, &credential_schema, &candidate_pub_keys).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 14. Verifier rejects proof without key id
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request, &credential_schema, &candidate_pub_keys).unwrap();
+        let res = proof_verifier.verify(&proof_without_key_id, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // 15. Verifier rejects proof with key id of issuer that isn't accepted
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request,
+                                                                 &credential_schema,
+                                                                 &[("issuer1:key", &other_credential_pub_key)]).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // 16. Proof doesn't verify with key of other issuer referred by forged key id
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request,
+                                                                 &credential_schema,
+                                                                 &[("issuer2:key", &other_credential_pub_key)]).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 17. Key id is hashed into challenge, so it can't be replaced even with other id of the same key
+        let aliased_pub_keys = [("issuer2:key", &credential_pub_key), ("issuer2:alias", &credential_pub_key)];
+        let redirected_proof = Proof::from_json(&proof.to_json().unwrap().replace("issuer2:key", "issuer2:alias")).unwrap();
+        assert_eq!(Some("issuer2:alias"), redirected_proof.sub_proofs()[0].key_id());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request, &credential_schema, &aliased_pub_keys).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_candidates(&sub_proof_request, &credential_schema, &aliased_pub_keys).unwrap();
+        assert!(!proof_verifier.verify(&redirected_proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_key_rotation() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Issuer rotates credential definition and signs rotation statement with its BLS key
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let (new_credential_pub_key, _, _, key_rotation) =
+            Issuer::rotate_credential_def(&credential_schema, &credential_pub_key, &sign_key, false, 1514764800).unwrap();
+
+        // 11. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 12. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 13. Prover creates proof with old key and refers to it by key id
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof_without_key_id = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        proof_builder.set_sub_proof_key_id(0, key_rotation.old_key_id()).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Verifier accepts proof within grace period
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request, &credential_schema, &credential_pub_key,
+                                                               &new_credential_pub_key, &key_rotation, &ver_key, &gen,
+                                                               1514764800, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 15. Proof without key id is verified with new key
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request, &credential_schema, &credential_pub_key,
+                                                               &new_credential_pub_key, &key_rotation, &ver_key, &gen,
+                                                               1514764800, None, None).unwrap();
+        assert!(!proof_verifier.verify(&proof_without_key_id, &nonce).unwrap());
+
+        // 16. Verifier rejects proof with old key after grace period
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request_with_key_rotation(&sub_proof_request, &credential_schema, &credential_pub_key,
+                                                               &new_credential_pub_key, &key_rotation, &ver_key, &gen,
+                                                               1514764801, None, None).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_proof_preview() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        // 13. Prover reviews disclosure before finalization
+        let preview = proof_builder.preview().unwrap();
+        assert_eq!(1, preview.sub_proofs().len());
+
+        let sub_proof_preview = &preview.sub_proofs()[0];
+        assert_eq!(vec!["name"], sub_proof_preview.revealed_attrs().keys().collect::<Vec<&String>>());
+        assert_eq!("1139481716457488690172217916278103335", sub_proof_preview.revealed_attrs()["name"].to_dec().unwrap());
+        assert_eq!(1, sub_proof_preview.predicates().len());
+        assert_eq!("age", sub_proof_preview.predicates()[0].attr_name());
+        assert_eq!(18, sub_proof_preview.predicates()[0].value());
+        assert!(!sub_proof_preview.is_revocation_included());
+        assert!(sub_proof_preview.timestamp().is_none());
+
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Proof discloses exactly what was previewed
+        let sub_proof = &proof.sub_proofs()[0];
+        assert_eq!(sub_proof_preview.revealed_attrs().len(), sub_proof.revealed_attrs().len());
+        for (attr, value) in sub_proof_preview.revealed_attrs().iter() {
+            assert_eq!(value, &sub_proof.revealed_attrs()[attr]);
+        }
+        assert_eq!(sub_proof_preview.predicates().iter().collect::<Vec<_>>(), sub_proof.predicates());
+        assert_eq!(sub_proof_preview.is_revocation_included(), sub_proof.is_revocation_included());
+
+        // 15. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_range_proof_predicates() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier creates sub proof requests with the same predicate proven with range proof and four squares
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate_with_proof_version("age", "GE", 18, PredicateProofVersion::RangeProof).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let four_squares_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proofs
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&four_squares_sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let four_squares_proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        assert_eq!(PredicateProofVersion::RangeProof, proof.sub_proofs()[0].predicates()[0].proof_version());
+        assert!(proof.to_json().unwrap().len() < four_squares_proof.to_json().unwrap().len());
+
+        // 13. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 14. Proof survives serialization
+        let proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 15. Verifier rejects proof with predicate proven by other proof system
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&four_squares_sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).is_err());
+
+        // 16. Prover can't prove unsatisfied predicate
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate_with_proof_version("age", "GE", 50, PredicateProofVersion::RangeProof).unwrap();
+        let unsatisfied_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        assert!(proof_builder.add_sub_proof_request(&unsatisfied_sub_proof_request,
+                                                    &credential_schema,
+                                                    &credential_signature,
+                                                    &credential_values,
+                                                    &credential_pub_key,
+                                                    None,
+                                                    None).is_err());
+    }
+
+    #[test]
+    fn anoncreds_works_for_security_profile() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition with 3072-bit modulus
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def_with_profile(&credential_schema, false, SecurityProfile::modulus_3072()).unwrap();
+        assert_eq!(SecurityProfile::modulus_3072(), credential_pub_key.get_primary_key().unwrap().security_profile().unwrap());
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 13. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_canonicalized_attr_names() {
+        // 1. Issuer creates credential schema with case and whitespace insensitive attribute names
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.set_attr_canonicalization(AttrNameCanonicalization::LowercaseNoSpaces).unwrap();
+        credential_schema_builder.add_attr("Name").unwrap();
+        credential_schema_builder.add_attr("Sex").unwrap();
+        credential_schema_builder.add_attr("Age").unwrap();
+        credential_schema_builder.add_attr("Height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values with differently spelled attribute names
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.set_attr_canonicalization(credential_schema.attr_canonicalization()).unwrap();
+        credential_values_builder.add_value("NAME", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value(" age", "28").unwrap();
+        credential_values_builder.add_value("Height ", "175").unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier creates sub proof request without canonicalization
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("Name").unwrap();
+        sub_proof_request_builder.add_predicate("AGE", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 13. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_sha3_challenge_hash() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.set_challenge_hash(ChallengeHash::Sha3_256).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 13. Prover sends proof in JSON and compact forms, both keep challenge hash
+        let proof_json = proof.to_json().unwrap();
+        assert!(proof_json.contains("SHA3-256"));
+        let compact_proof = proof.to_compact_bytes().unwrap();
+
+        // 14. Verifier verifies proof
+        for proof in vec![Proof::from_json(&proof_json).unwrap(), Proof::from_compact_bytes(&compact_proof).unwrap()] {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                                 &credential_schema,
+                                                 &credential_pub_key,
+                                                 None,
+                                                 None).unwrap();
+            assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+        }
+    }
+
+    #[test]
+    fn anoncreds_works_for_resumed_proof_builder() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        // 8. Issuer signs credential values
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        // 9. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 10. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Prover adds sub proof request and saves proof builder state
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof_builder_json = proof_builder.to_json().unwrap();
+
+        // 13. Prover resumes proof builder and creates proof
+        let proof_builder = ProofBuilder::from_json(&proof_builder_json).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
     #[test]
     fn anoncreds_works_for_revocation_proof_issuance_on_demand() {
         // 1. Issuer creates credential schema
@@ -312,11 +1083,312 @@ mod test {
         // 2. Issuer creates credential definition(with revocation keys)
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
 
-        // 4. Issuer creates revocation registry with IssuanceOnDemand type
+        // 4. Issuer creates revocation registry with IssuanceOnDemand type
+        let max_cred_num = 5;
+        let issuance_by_default = false;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        // 4. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 5. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 6. Prover blinds master secret
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 7. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 8. Issuer creates and sign credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        let rev_idx = 1;
+        let (mut credential_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc(PROVER_ID,
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &credential_values,
+                                               &credential_pub_key,
+                                               &credential_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+
+        // 9. Prover creates witness
+        let witness = Witness::new(rev_idx,
+                                   max_cred_num,
+                                   &rev_reg_delta.unwrap(),
+                                   &simple_tail_accessor).unwrap();
+
+        // 10. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 13. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_compact_proof() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition(with revocation keys)
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        // 3. Issuer creates revocation registry
+        let max_cred_num = 5;
+        let issuance_by_default = false;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        // 4. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 5. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 6. Prover blinds master secret
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 7. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 8. Issuer creates and sign credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        let rev_idx = 1;
+        let (mut credential_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc(PROVER_ID,
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &credential_values,
+                                               &credential_pub_key,
+                                               &credential_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+
+        // 9. Prover creates witness
+        let witness = Witness::new(rev_idx,
+                                   max_cred_num,
+                                   &rev_reg_delta.unwrap(),
+                                   &simple_tail_accessor).unwrap();
+
+        // 10. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 13. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Prover converts proof to compact form
+        let compact_proof = proof.to_compact_bytes().unwrap();
+        assert!(proof.to_json().unwrap().len() >= 3 * compact_proof.len());
+
+        // 15. Verifier restores proof from compact form and verifies it
+        let restored_proof = Proof::from_compact_bytes(&compact_proof).unwrap();
+        assert_eq!(compact_proof, restored_proof.to_compact_bytes().unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&restored_proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_revocation_proof_issuance_by_default() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition(with revocation keys)
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        // 4. Issuer creates GVT revocation registry with IssuanceByDefault type
+        let max_cred_num = 5;
+        let issuance_by_default = true;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        // 4. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // 5. Issuer creates nonce used Prover to blind master secret
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+
+        // 6. Prover blinds master secret
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key,
+                                        &credential_key_correctness_proof,
+                                        &master_secret,
+                                        &master_secret_blinding_nonce).unwrap();
+
+        // 7. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 8. Issuer creates and sign credential values
+        let credential_values = helpers::gvt_credential_values();
+
+        let rev_idx = 1;
+        let (mut credential_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc(PROVER_ID,
+                                               &blinded_master_secret,
+                                               &blinded_master_secret_correctness_proof,
+                                               &master_secret_blinding_nonce,
+                                               &credential_issuance_nonce,
+                                               &credential_values,
+                                               &credential_pub_key,
+                                               &credential_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+        assert!(rev_reg_delta.is_none());
+
+        let rev_reg_delta = RegistryDelta::from_rev_reg(&rev_reg, max_cred_num);
+
+        // 9. Prover creates witness
+        let witness = Witness::new(rev_idx,
+                                   max_cred_num,
+                                   &rev_reg_delta.to_delta(),
+                                   &simple_tail_accessor).unwrap();
+
+        // 10. Prover processes credential signature
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        // 11. Verifier creates nonce
+        let nonce = new_nonce().unwrap();
+
+        // 12. Verifier create sub proof request
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+
+        // 13. Prover creates proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 14. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_non_membership_revocation_registry() {
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+
+        // 2. Issuer creates credential definition(with revocation keys)
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
+
+        // 3. Issuer creates revocation registry that accumulates revoked indices
         let max_cred_num = 5;
-        let issuance_by_default = false;
         let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
-            Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+            Issuer::new_non_membership_revocation_registry_def(&credential_pub_key, max_cred_num).unwrap();
 
         let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
 
@@ -351,16 +1423,18 @@ mod test {
                                                &credential_priv_key,
                                                rev_idx,
                                                max_cred_num,
-                                               issuance_by_default,
+                                               false,
                                                &mut rev_reg,
                                                &rev_key_priv,
                                                &simple_tail_accessor).unwrap();
+        assert!(rev_reg_delta.is_none());
 
-        // 9. Prover creates witness
-        let witness = Witness::new(rev_idx,
-                                   max_cred_num,
-                                   &rev_reg_delta.unwrap(),
-                                   &simple_tail_accessor).unwrap();
+        // 9. Prover creates witness over empty registry
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &HashSet::new());
+        let mut witness = Witness::new(rev_idx,
+                                       max_cred_num,
+                                       &rev_reg_delta,
+                                       &simple_tail_accessor).unwrap();
 
         // 10. Prover processes credential signature
         Prover::process_credential_signature(&mut credential_signature,
@@ -374,11 +1448,13 @@ mod test {
                                              Some(&rev_reg),
                                              Some(&witness)).unwrap();
 
+        // 11. Issuer revokes other credential and Prover updates witness
+        let rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &simple_tail_accessor).unwrap();
 
-        // 11. Verifier creates nonce
-        let nonce = new_nonce().unwrap();
+        witness.update(rev_idx, max_cred_num, &rev_reg_delta, &simple_tail_accessor).unwrap();
 
-        // 12. Verifier create sub proof request
+        // 12. Verifier creates nonce and sub proof request
+        let nonce = new_nonce().unwrap();
         let sub_proof_request = helpers::gvt_sub_proof_request();
 
         // 13. Prover creates proof
@@ -392,78 +1468,418 @@ mod test {
                                             Some(&witness)).unwrap();
         let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
 
-        // 14. Verifier verifies proof
+        // 14. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 15. Issuer revokes credential
+        Issuer::revoke_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
+
+        // 16. Proof created with non-membership witness of revoked index fails verification
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert_eq!(false, proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_ps_credential() {
+        // 1. Issuer creates credential schema and PS credential definition
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (credential_pub_key, credential_priv_key) =
+            match Issuer::new_credential_def_with_backend(&credential_schema, false, CredentialSignatureBackend::PS).unwrap() {
+                CredentialDefinition::PS { pub_key, priv_key } => (pub_key, priv_key),
+                _ => panic!("PS credential definition expected")
+            };
+
+        // 2. Issuer publishes public key, Prover and Verifier receive it as JSON
+        let credential_pub_key = PsCredentialPublicKey::from_json(&credential_pub_key.to_json().unwrap()).unwrap();
+
+        // 3. Prover creates master secret and blinds it
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret_ps(&credential_pub_key, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        // 4. Issuer signs credential values
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value("age", "28").unwrap();
+        credential_values_builder.add_value("height", "175").unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        let mut credential_signature = Issuer::sign_ps_credential(&blinded_master_secret,
+                                                                  &blinded_master_secret_correctness_proof,
+                                                                  &master_secret_blinding_nonce,
+                                                                  &credential_values,
+                                                                  &credential_pub_key,
+                                                                  &credential_priv_key).unwrap();
+
+        // 5. Prover processes credential signature
+        Prover::process_ps_credential_signature(&mut credential_signature,
+                                                &credential_values,
+                                                &master_secret_blinding_data,
+                                                &master_secret,
+                                                &credential_pub_key).unwrap();
+
+        // 6. Verifier requests name and Prover creates proof
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = Prover::new_ps_proof(&credential_signature,
+                                         &credential_values,
+                                         &master_secret,
+                                         &credential_pub_key,
+                                         &sub_proof_request,
+                                         &nonce).unwrap();
+
+        // 7. Verifier verifies proof received as JSON
+        let proof = PsProof::from_json(&proof.to_json().unwrap()).unwrap();
+        assert!(Verifier::verify_ps_proof(&proof, &sub_proof_request, &credential_pub_key, &nonce).unwrap());
+        assert_eq!("1139481716457488690172217916278103335", proof.revealed_attrs()["name"].to_dec().unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_credential_without_master_secret() {
+        // 1. Issuer creates credential definition for device credentials
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("serial").unwrap();
+        credential_schema_builder.add_attr("model").unwrap();
+        let device_credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let (device_credential_pub_key, device_credential_priv_key, device_credential_key_correctness_proof) =
+            Issuer::new_credential_def_without_master_secret(&device_credential_schema, false).unwrap();
+
+        // 2. Issuer publishes public key, Prover and Verifier receive it as JSON
+        let device_credential_pub_key = CredentialPublicKey::from_json(&device_credential_pub_key.to_json().unwrap()).unwrap();
+        assert!(!device_credential_pub_key.requires_master_secret());
+
+        // 3. Device creates blinding data without master secret
+        let device_blinding_nonce = new_nonce().unwrap();
+        let (device_blinded_master_secret, device_master_secret_blinding_data, device_blinded_master_secret_correctness_proof) =
+            Prover::blind_credential_without_master_secret(&device_credential_pub_key,
+                                                           &device_credential_key_correctness_proof,
+                                                           &device_blinding_nonce).unwrap();
+
+        // 4. Issuer signs device credential
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("serial", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("model", "42").unwrap();
+        let device_credential_values = credential_values_builder.finalize().unwrap();
+
+        let device_credential_issuance_nonce = new_nonce().unwrap();
+
+        let (mut device_credential_signature, device_signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &device_blinded_master_secret,
+                                    &device_blinded_master_secret_correctness_proof,
+                                    &device_blinding_nonce,
+                                    &device_credential_issuance_nonce,
+                                    &device_credential_values,
+                                    &device_credential_pub_key,
+                                    &device_credential_priv_key).unwrap();
+
+        // 5. Device processes credential signature
+        Prover::process_credential_signature_without_master_secret(&mut device_credential_signature,
+                                                                   &device_credential_values,
+                                                                   &device_signature_correctness_proof,
+                                                                   &device_master_secret_blinding_data,
+                                                                   &device_credential_pub_key,
+                                                                   &device_credential_issuance_nonce,
+                                                                   None, None, None).unwrap();
+
+        // 6. Device creates proof without master secret, Verifier verifies it
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("model").unwrap();
+        let device_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&device_sub_proof_request,
+                                            &device_credential_schema,
+                                            &device_credential_signature,
+                                            &device_credential_values,
+                                            &device_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize_without_master_secret(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&device_sub_proof_request,
+                                             &device_credential_schema,
+                                             &device_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 7. Holder issued GVT credential bound to master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let (gvt_credential_pub_key, gvt_credential_priv_key, gvt_credential_key_correctness_proof) =
+            Issuer::new_credential_def(&gvt_credential_schema, false).unwrap();
+
+        let gvt_master_secret_blinding_nonce = new_nonce().unwrap();
+        let (gvt_blinded_master_secret, gvt_master_secret_blinding_data, gvt_blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&gvt_credential_pub_key,
+                                        &gvt_credential_key_correctness_proof,
+                                        &master_secret,
+                                        &gvt_master_secret_blinding_nonce).unwrap();
+
+        let gvt_credential_issuance_nonce = new_nonce().unwrap();
+        let gvt_credential_values = helpers::gvt_credential_values();
+
+        let (mut gvt_credential_signature, gvt_signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &gvt_blinded_master_secret,
+                                    &gvt_blinded_master_secret_correctness_proof,
+                                    &gvt_master_secret_blinding_nonce,
+                                    &gvt_credential_issuance_nonce,
+                                    &gvt_credential_values,
+                                    &gvt_credential_pub_key,
+                                    &gvt_credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut gvt_credential_signature,
+                                             &gvt_credential_values,
+                                             &gvt_signature_correctness_proof,
+                                             &gvt_master_secret_blinding_data,
+                                             &master_secret,
+                                             &gvt_credential_pub_key,
+                                             &gvt_credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 8. Holder combines both credentials in one proof, proof without master secret is rejected
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&device_sub_proof_request,
+                                            &device_credential_schema,
+                                            &device_credential_signature,
+                                            &device_credential_values,
+                                            &device_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_pseudonym_request("example.com", &master_secret).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, proof_builder.finalize_without_master_secret(&nonce).unwrap_err().to_error_code());
+
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&device_sub_proof_request,
+                                             &device_credential_schema,
+                                             &device_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request,
+                                             &gvt_credential_schema,
+                                             &gvt_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_pseudonym_request("example.com").unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_credentials_bound_to_different_master_secrets() {
+        // 1. Holder has separate master secrets for personal and work credentials
+        let personal_master_secret = Prover::new_master_secret().unwrap();
+        let work_master_secret = Prover::new_master_secret().unwrap();
+
+        // 2. Issuer creates and signs GVT credential bound to personal master secret
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let (gvt_credential_pub_key, gvt_credential_priv_key, gvt_credential_key_correctness_proof) =
+            Issuer::new_credential_def(&gvt_credential_schema, false).unwrap();
+
+        let gvt_master_secret_blinding_nonce = new_nonce().unwrap();
+        let (gvt_blinded_master_secret, gvt_master_secret_blinding_data, gvt_blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&gvt_credential_pub_key,
+                                        &gvt_credential_key_correctness_proof,
+                                        &personal_master_secret,
+                                        &gvt_master_secret_blinding_nonce).unwrap();
+
+        let gvt_credential_issuance_nonce = new_nonce().unwrap();
+        let gvt_credential_values = helpers::gvt_credential_values();
+
+        let (mut gvt_credential_signature, gvt_signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &gvt_blinded_master_secret,
+                                    &gvt_blinded_master_secret_correctness_proof,
+                                    &gvt_master_secret_blinding_nonce,
+                                    &gvt_credential_issuance_nonce,
+                                    &gvt_credential_values,
+                                    &gvt_credential_pub_key,
+                                    &gvt_credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut gvt_credential_signature,
+                                             &gvt_credential_values,
+                                             &gvt_signature_correctness_proof,
+                                             &gvt_master_secret_blinding_data,
+                                             &personal_master_secret,
+                                             &gvt_credential_pub_key,
+                                             &gvt_credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 3. Issuer creates and signs XYZ credential bound to work master secret
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let (xyz_credential_pub_key, xyz_credential_priv_key, xyz_credential_key_correctness_proof) =
+            Issuer::new_credential_def(&xyz_credential_schema, false).unwrap();
+
+        let xyz_master_secret_blinding_nonce = new_nonce().unwrap();
+        let (xyz_blinded_master_secret, xyz_master_secret_blinding_data, xyz_blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&xyz_credential_pub_key,
+                                        &xyz_credential_key_correctness_proof,
+                                        &work_master_secret,
+                                        &xyz_master_secret_blinding_nonce).unwrap();
+
+        let xyz_credential_issuance_nonce = new_nonce().unwrap();
+        let xyz_credential_values = helpers::xyz_credential_values();
+
+        let (mut xyz_credential_signature, xyz_signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &xyz_blinded_master_secret,
+                                    &xyz_blinded_master_secret_correctness_proof,
+                                    &xyz_master_secret_blinding_nonce,
+                                    &xyz_credential_issuance_nonce,
+                                    &xyz_credential_values,
+                                    &xyz_credential_pub_key,
+                                    &xyz_credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut xyz_credential_signature,
+                                             &xyz_credential_values,
+                                             &xyz_signature_correctness_proof,
+                                             &xyz_master_secret_blinding_data,
+                                             &work_master_secret,
+                                             &xyz_credential_pub_key,
+                                             &xyz_credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 4. Holder creates proof binding each credential to its own master secret
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+        let xyz_sub_proof_request = helpers::xyz_sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request_with_master_secret(&xyz_sub_proof_request,
+                                                               &xyz_credential_schema,
+                                                               &xyz_credential_signature,
+                                                               &xyz_credential_values,
+                                                               &xyz_credential_pub_key,
+                                                               None,
+                                                               None,
+                                                               1).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   proof_builder.finalize_with_master_secrets(&nonce, &[&personal_master_secret]).unwrap_err().to_error_code());
+
+        let proof = proof_builder.finalize_with_master_secrets(&nonce, &[&personal_master_secret, &work_master_secret]).unwrap();
+
+        // 5. Verifier accepts proof if credentials are allowed to belong to different holders
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request, &gvt_credential_schema, &gvt_credential_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request, &xyz_credential_schema, &xyz_credential_pub_key, None, None).unwrap();
+        proof_verifier.add_same_holder_group(&[0]).unwrap();
+        proof_verifier.add_same_holder_group(&[1]).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 6. Verifier rejects proof if credentials must belong to the same holder
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request, &gvt_credential_schema, &gvt_credential_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request, &xyz_credential_schema, &xyz_credential_pub_key, None, None).unwrap();
+        proof_verifier.add_same_holder_group(&[0, 1]).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 7. Without explicit groups all credentials must belong to the same holder
         let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &credential_pub_key,
-                                             Some(&rev_key_pub),
-                                             Some(&rev_reg)).unwrap();
-        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request, &gvt_credential_schema, &gvt_credential_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request, &xyz_credential_schema, &xyz_credential_pub_key, None, None).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
     }
 
     #[test]
-    fn anoncreds_works_for_revocation_proof_issuance_by_default() {
-        // 1. Issuer creates credential schema
-        let credential_schema = helpers::gvt_credential_schema();
-
-        // 2. Issuer creates credential definition(with revocation keys)
-        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, true).unwrap();
-
-        // 4. Issuer creates GVT revocation registry with IssuanceByDefault type
-        let max_cred_num = 5;
-        let issuance_by_default = true;
-        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
-            Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+    #[cfg(feature = "sign_sodium")]
+    fn anoncreds_works_for_proof_bound_to_device_key() {
+        // 1. Holder creates master secret and device key pair
+        let master_secret = Prover::new_master_secret().unwrap();
+        let (device_ver_key, device_sign_key) = ed25519::new_key_pair(None).unwrap();
 
-        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        // 2. Issuer creates credential definition with attribute committing to device key
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("device_key").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
 
-        // 4. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, false).unwrap();
 
-        // 5. Issuer creates nonce used Prover to blind master secret
+        // 3. Issuer signs credential with encoded device verification key
         let master_secret_blinding_nonce = new_nonce().unwrap();
-
-        // 6. Prover blinds master secret
         let (blinded_master_secret, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
             Prover::blind_master_secret(&credential_pub_key,
                                         &credential_key_correctness_proof,
                                         &master_secret,
                                         &master_secret_blinding_nonce).unwrap();
 
-        // 7. Prover creates nonce used Issuer to credential issue
-        let credential_issuance_nonce = new_nonce().unwrap();
-
-        // 8. Issuer creates and sign credential values
-        let credential_values = helpers::gvt_credential_values();
-
-        let rev_idx = 1;
-        let (mut credential_signature, signature_correctness_proof, rev_reg_delta) =
-            Issuer::sign_credential_with_revoc(PROVER_ID,
-                                               &blinded_master_secret,
-                                               &blinded_master_secret_correctness_proof,
-                                               &master_secret_blinding_nonce,
-                                               &credential_issuance_nonce,
-                                               &credential_values,
-                                               &credential_pub_key,
-                                               &credential_priv_key,
-                                               rev_idx,
-                                               max_cred_num,
-                                               issuance_by_default,
-                                               &mut rev_reg,
-                                               &rev_key_priv,
-                                               &simple_tail_accessor).unwrap();
-        assert!(rev_reg_delta.is_none());
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_value("device_key", &DeviceBinding::encode_ver_key(&device_ver_key).unwrap()).unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
 
-        let rev_reg_delta = RegistryDelta::from_rev_reg(&rev_reg, max_cred_num);
+        let credential_issuance_nonce = new_nonce().unwrap();
 
-        // 9. Prover creates witness
-        let witness = Witness::new(rev_idx,
-                                   max_cred_num,
-                                   &rev_reg_delta.to_delta(),
-                                   &simple_tail_accessor).unwrap();
+        let (mut credential_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &blinded_master_secret,
+                                    &blinded_master_secret_correctness_proof,
+                                    &master_secret_blinding_nonce,
+                                    &credential_issuance_nonce,
+                                    &credential_values,
+                                    &credential_pub_key,
+                                    &credential_priv_key).unwrap();
 
-        // 10. Prover processes credential signature
         Prover::process_credential_signature(&mut credential_signature,
                                              &credential_values,
                                              &signature_correctness_proof,
@@ -471,35 +1887,62 @@ mod test {
                                              &master_secret,
                                              &credential_pub_key,
                                              &credential_issuance_nonce,
-                                             Some(&rev_key_pub),
-                                             Some(&rev_reg),
-                                             Some(&witness)).unwrap();
-
-        // 11. Verifier creates nonce
-        let nonce = new_nonce().unwrap();
+                                             None, None, None).unwrap();
 
-        // 12. Verifier create sub proof request
-        let sub_proof_request = helpers::gvt_sub_proof_request();
+        // 4. Holder creates proof revealing device key attribute
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("device_key").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        // 13. Prover creates proof
         let mut proof_builder = Prover::new_proof_builder().unwrap();
         proof_builder.add_sub_proof_request(&sub_proof_request,
                                             &credential_schema,
                                             &credential_signature,
                                             &credential_values,
                                             &credential_pub_key,
-                                            Some(&rev_reg),
-                                            Some(&witness)).unwrap();
-        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+                                            None,
+                                            None).unwrap();
 
-        // 14. Verifier verifies proof
+        let nonce = new_nonce().unwrap();
+        let mut proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+
+        // 5. Verifier rejects proof without device binding
         let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &credential_pub_key,
-                                             Some(&rev_key_pub),
-                                             Some(&rev_reg)).unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+        proof_verifier.add_device_binding_request(0, "device_key").unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 6. Signature by other key can't be attached and doesn't satisfy verifier
+        let (other_ver_key, other_sign_key) = ed25519::new_key_pair(None).unwrap();
+        let other_signature = ed25519::sign(&proof.device_binding_message().unwrap(), &other_sign_key).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   proof.add_device_binding(&device_ver_key, &other_signature).unwrap_err().to_error_code());
+
+        let mut other_proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+        other_proof.add_device_binding(&other_ver_key, &other_signature).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+        proof_verifier.add_device_binding_request(0, "device_key").unwrap();
+        assert!(!proof_verifier.verify(&other_proof, &nonce).unwrap());
+
+        // 7. Device signs proof challenge and Verifier accepts "credential + device" proof
+        let signature = ed25519::sign(&proof.device_binding_message().unwrap(), &device_sign_key).unwrap();
+        proof.add_device_binding(&device_ver_key, &signature).unwrap();
+
+        let proof = Proof::from_json(&proof.to_json().unwrap()).unwrap();
+        assert_eq!(&device_ver_key, proof.device_binding().unwrap().ver_key());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+        proof_verifier.add_device_binding_request(0, "device_key").unwrap();
         assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 8. Device binding can be requested only for revealed attribute
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &credential_pub_key, None, None).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   proof_verifier.add_device_binding_request(0, "name").unwrap_err().to_error_code());
     }
 
     #[test]
@@ -3061,6 +4504,221 @@ mod test {
                                                        None, None, None);
         assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
     }
+
+    #[test]
+    fn anoncreds_works_for_deterministic_credential_signature() {
+        let credential_schema = helpers::gvt_credential_schema();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key, &credential_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let credential_values = helpers::gvt_credential_values();
+
+        // 1. Issuer signs credential and retries issuance after network failure
+        let sign = |credential_issuance_nonce: &Nonce| Issuer::sign_credential_deterministic(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        let (mut credential_signature, signature_correctness_proof) = sign(&credential_issuance_nonce);
+        let (retried_credential_signature, retried_signature_correctness_proof) = sign(&credential_issuance_nonce);
+
+        // 2. Retried issuance gives bit-identical signature
+        assert_eq!(credential_signature.to_json().unwrap(), retried_credential_signature.to_json().unwrap());
+        assert_eq!(signature_correctness_proof.to_json().unwrap(), retried_signature_correctness_proof.to_json().unwrap());
+
+        // 3. Issuance with other nonce gives other signature
+        let (other_credential_signature, _) = sign(&new_nonce().unwrap());
+        assert_ne!(credential_signature.to_json().unwrap(), other_credential_signature.to_json().unwrap());
+
+        // 4. Prover processes credential signature as usual
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_attr_value_check() {
+        const SEX: &'static str = "5944657099558967239210949258394887428692050081607692519917050011144233115103";
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key, &credential_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let credential_values = helpers::gvt_credential_values();
+
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        // 1. Verifier requires sex attribute to be equal to known value
+        let sub_proof_request = |sex: &str| {
+            let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_revealed_attr("name").unwrap();
+            sub_proof_request_builder.add_attr_value_check("sex", sex).unwrap();
+            sub_proof_request_builder.finalize().unwrap()
+        };
+
+        let nonce = new_nonce().unwrap();
+
+        // 2. Prover creates proof, checked value isn't included in proof
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request(SEX),
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce, &master_secret).unwrap();
+        assert!(!proof.to_json().unwrap().contains(SEX));
+
+        // 3. Verifier verifies proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request(SEX),
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 4. Verifier requiring other value doesn't accept proof
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request("1"),
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 5. Prover can't create proof for other value
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request("1"),
+                                                      &credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+
+        // 6. Checked attribute can't be revealed or used in predicate
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("sex").unwrap();
+        sub_proof_request_builder.add_attr_value_check("sex", SEX).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, sub_proof_request_builder.finalize().unwrap_err().to_error_code());
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_attr_value_check("age", "28").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, sub_proof_request_builder.finalize().unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_external_challenge() {
+        const STATEMENT: &'static [u8] = b"payment of 10 tokens to 2bcE6b";
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let master_secret_blinding_nonce = new_nonce().unwrap();
+        let (blinded_ms, master_secret_blinding_data, blinded_master_secret_correctness_proof) =
+            Prover::blind_master_secret(&credential_pub_key, &credential_key_correctness_proof, &master_secret, &master_secret_blinding_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let credential_values = helpers::gvt_credential_values();
+
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_ms,
+                                                                                              &blinded_master_secret_correctness_proof,
+                                                                                              &master_secret_blinding_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              &credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &master_secret_blinding_data,
+                                             &master_secret,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let nonce = new_nonce().unwrap();
+
+        // Application challenge binds anoncreds commitments to its own statement and verifier nonce
+        let challenge = |commitments: &Vec<Vec<u8>>| {
+            let mut hasher = ChallengeHasher::sha256();
+            hasher.update_all(commitments).update(STATEMENT).update(&nonce.to_bytes().unwrap());
+            hasher.finalize().unwrap()
+        };
+
+        // 1. Prover composes anoncreds commitments into application transcript
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let commitments = proof_builder.commitments().unwrap();
+        let proof = proof_builder.finalize_with_external_challenge(&challenge(&commitments), &master_secret).unwrap();
+
+        // 2. Verifier recomputes commitments and application challenge
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let contribution = proof_verifier.compute_challenge_contribution(&proof).unwrap().unwrap();
+        assert_eq!(commitments, contribution);
+        assert_eq!(proof.challenge(), &challenge(&contribution));
+
+        // 3. Proof isn't valid as standalone proof for nonce
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 4. Builder can't be finalized again, as responses for other challenge would disclose hidden values
+        assert!(proof_builder.finalize_with_external_challenge(&challenge(&commitments), &master_secret).is_err());
+        assert!(proof_builder.finalize(&nonce, &master_secret).is_err());
+    }
 }
 
 mod helpers {
@@ -3120,14 +4778,14 @@ struct RegistryDelta {
     accum: PointG2,
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     #[serde(default)]
-    issued: HashSet<u32>,
+    issued: HashSet<u64>,
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     #[serde(default)]
-    revoked: HashSet<u32>
+    revoked: HashSet<u64>
 }
 
 impl RegistryDelta {
-    fn from_rev_reg(rev_reg: &RevocationRegistry, max_cred_num: u32) -> RegistryDelta {
+    fn from_rev_reg(rev_reg: &RevocationRegistry, max_cred_num: u64) -> RegistryDelta {
         let mut rev_reg_delta = serde_json::from_str::<RegistryDelta>(&serde_json::to_string(&rev_reg).unwrap()).unwrap();
         let mut issued = HashSet::new();
         for i in 1..max_cred_num + 1 {
@@ -0,0 +1,482 @@
+use alloc::vec::Vec;
+
+use core::cmp::Ordering;
+
+/// Arbitrary precision unsigned integer.
+/// Stored as little-endian 32 bit limbs without leading zero limbs, so zero has no limbs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from_u32(1)
+    }
+
+    pub fn from_u32(value: u32) -> BigUint {
+        BigUint::from_limbs(vec![value])
+    }
+
+    /// Returns 2^`bits`.
+    pub fn pow2(bits: usize) -> BigUint {
+        let mut limbs = vec![0u32; bits / 32 + 1];
+        limbs[bits / 32] = 1 << (bits % 32);
+        BigUint { limbs }
+    }
+
+    /// Converts big-endian bytes (the same as `BigNumber::to_bytes` gives) to number.
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let mut limbs = vec![0u32; (bytes.len() + 3) / 4];
+        for (i, &byte) in bytes.iter().rev().enumerate() {
+            limbs[i / 4] |= (byte as u32) << (8 * (i % 4));
+        }
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Returns minimal big-endian representation. Zero is represented by empty vector.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(self.limbs.len() * 4);
+        for limb in self.limbs.iter().rev() {
+            bytes.push((limb >> 24) as u8);
+            bytes.push((limb >> 16) as u8);
+            bytes.push((limb >> 8) as u8);
+            bytes.push(*limb as u8);
+        }
+
+        let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+        bytes[zeros..].to_vec()
+    }
+
+    /// Parses decimal string (the same as `BigNumber::to_dec` gives).
+    pub fn from_dec(dec: &str) -> Option<BigUint> {
+        if dec.is_empty() {
+            return None;
+        }
+
+        let mut result = BigUint::zero();
+        for c in dec.chars() {
+            let digit = c.to_digit(10)?;
+            result = result.mul_small_add(10, digit);
+        }
+        Some(result)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().map(|limb| limb & 1 == 1).unwrap_or(false)
+    }
+
+    /// Returns count of significant bits.
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(last) => 32 * (self.limbs.len() - 1) + (32 - last.leading_zeros() as usize),
+            None => 0
+        }
+    }
+
+    pub fn bit(&self, idx: usize) -> bool {
+        self.limbs.get(idx / 32).map(|limb| (limb >> (idx % 32)) & 1 == 1).unwrap_or(false)
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let (a, b) = if self.limbs.len() >= other.limbs.len() { (self, other) } else { (other, self) };
+
+        let mut limbs: Vec<u32> = Vec::with_capacity(a.limbs.len() + 1);
+        let mut carry = 0u64;
+        for i in 0..a.limbs.len() {
+            let sum = a.limbs[i] as u64 + b.limbs.get(i).cloned().unwrap_or(0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Returns `self - other` or None if result is negative.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut result = self.clone();
+        result.sub_assign(other);
+        Some(result)
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = limbs[i + j] as u64 + a as u64 * b as u64 + carry;
+                limbs[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Returns `self mod m` or None if `m` is zero.
+    pub fn rem(&self, m: &BigUint) -> Option<BigUint> {
+        if m.is_zero() {
+            return None;
+        }
+
+        if *self < *m {
+            return Some(self.clone());
+        }
+
+        let mut result = BigUint::zero();
+        for idx in (0..self.bits()).rev() {
+            result.shl1();
+            if self.bit(idx) {
+                result.set_bit0();
+            }
+            if result >= *m {
+                result.sub_assign(m);
+            }
+        }
+        Some(result)
+    }
+
+    pub fn mod_mul(&self, other: &BigUint, m: &BigUint) -> Option<BigUint> {
+        self.mul(other).rem(m)
+    }
+
+    /// Returns `self^exp mod m` or None if `m` is zero.
+    pub fn mod_exp(&self, exp: &BigUint, m: &BigUint) -> Option<BigUint> {
+        if m.is_zero() {
+            return None;
+        }
+
+        if !m.is_odd() {
+            let base = self.rem(m)?;
+            let mut result = BigUint::one().rem(m)?;
+            for idx in (0..exp.bits()).rev() {
+                result = result.mod_mul(&result, m)?;
+                if exp.bit(idx) {
+                    result = result.mod_mul(&base, m)?;
+                }
+            }
+            return Some(result);
+        }
+
+        let mont = Montgomery::new(m);
+        let base = mont.to_mont(self);
+        let mut result = mont.to_mont(&BigUint::one());
+
+        for idx in (0..exp.bits()).rev() {
+            result = mont.mul(&result, &result);
+            if exp.bit(idx) {
+                result = mont.mul(&result, &base);
+            }
+        }
+
+        Some(mont.from_mont(&result))
+    }
+
+    /// Returns `self^-1 mod m` for odd `m` or None if inverse doesn't exist.
+    pub fn mod_inverse(&self, m: &BigUint) -> Option<BigUint> {
+        if !m.is_odd() {
+            return None;
+        }
+
+        let one = BigUint::one();
+        let mut u = self.rem(m)?;
+        let mut v = m.clone();
+        let mut x1 = one.clone();
+        let mut x2 = BigUint::zero();
+
+        if u.is_zero() {
+            return None;
+        }
+
+        // Binary extended Euclidean algorithm, invariants: self * x1 = u, self * x2 = v (mod m)
+        while u != one && v != one {
+            while !u.is_odd() {
+                u.shr1();
+                x1 = BigUint::_half_mod(x1, m);
+            }
+            while !v.is_odd() {
+                v.shr1();
+                x2 = BigUint::_half_mod(x2, m);
+            }
+
+            if u >= v {
+                u.sub_assign(&v);
+                x1 = BigUint::_sub_mod(&x1, &x2, m);
+            } else {
+                v.sub_assign(&u);
+                x2 = BigUint::_sub_mod(&x2, &x1, m);
+            }
+
+            if u.is_zero() || v.is_zero() {
+                return None;
+            }
+        }
+
+        Some(if u == one { x1 } else { x2 })
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> BigUint {
+        let mut result = BigUint { limbs };
+        result.normalize();
+        result
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn mul_small_add(&self, mul: u32, add: u32) -> BigUint {
+        let mut limbs: Vec<u32> = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = add as u64;
+        for &limb in self.limbs.iter() {
+            let t = limb as u64 * mul as u64 + carry;
+            limbs.push(t as u32);
+            carry = t >> 32;
+        }
+        limbs.push(carry as u32);
+
+        BigUint::from_limbs(limbs)
+    }
+
+    // Requires self >= other
+    fn sub_assign(&mut self, other: &BigUint) {
+        let mut borrow = false;
+        for i in 0..self.limbs.len() {
+            let (diff, overflow1) = self.limbs[i].overflowing_sub(other.limbs.get(i).cloned().unwrap_or(0));
+            let (diff, overflow2) = diff.overflowing_sub(borrow as u32);
+            self.limbs[i] = diff;
+            borrow = overflow1 || overflow2;
+        }
+        self.normalize();
+    }
+
+    fn shl1(&mut self) {
+        let mut carry = 0u32;
+        for limb in self.limbs.iter_mut() {
+            let next_carry = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next_carry;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    fn shr1(&mut self) {
+        let mut carry = 0u32;
+        for limb in self.limbs.iter_mut().rev() {
+            let next_carry = *limb & 1;
+            *limb = (*limb >> 1) | (carry << 31);
+            carry = next_carry;
+        }
+        self.normalize();
+    }
+
+    fn set_bit0(&mut self) {
+        if self.limbs.is_empty() {
+            self.limbs.push(1);
+        } else {
+            self.limbs[0] |= 1;
+        }
+    }
+
+    fn padded(&self, len: usize) -> Vec<u32> {
+        let mut limbs = self.limbs.clone();
+        limbs.resize(len, 0);
+        limbs
+    }
+
+    // Returns x / 2 mod m for x < m and odd m
+    fn _half_mod(mut x: BigUint, m: &BigUint) -> BigUint {
+        if x.is_odd() {
+            x = x.add(m);
+        }
+        x.shr1();
+        x
+    }
+
+    // Returns a - b mod m for a, b < m
+    fn _sub_mod(a: &BigUint, b: &BigUint, m: &BigUint) -> BigUint {
+        match a.checked_sub(b) {
+            Some(diff) => diff,
+            None => {
+                let mut result = a.add(m);
+                result.sub_assign(b);
+                result
+            }
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        if self.limbs.len() != other.limbs.len() {
+            return self.limbs.len().cmp(&other.limbs.len());
+        }
+
+        for (a, b) in self.limbs.iter().rev().zip(other.limbs.iter().rev()) {
+            if a != b {
+                return a.cmp(b);
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Montgomery multiplication modulo odd number, numbers are kept as `k` limbs in Montgomery form `a * 2^(32k) mod n`.
+struct Montgomery<'a> {
+    n: &'a BigUint,
+    k: usize,
+    n0_inv: u32 /* -n^-1 mod 2^32 */
+}
+
+impl<'a> Montgomery<'a> {
+    fn new(n: &'a BigUint) -> Montgomery<'a> {
+        let n0 = n.limbs[0];
+
+        // Newton iteration doubles count of correct low bits of n0^-1
+        let mut inv = 1u32;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(n0.wrapping_mul(inv)));
+        }
+
+        Montgomery { n, k: n.limbs.len(), n0_inv: inv.wrapping_neg() }
+    }
+
+    fn to_mont(&self, a: &BigUint) -> Vec<u32> {
+        let mut limbs = vec![0u32; self.k];
+        limbs.extend_from_slice(&a.limbs);
+
+        BigUint::from_limbs(limbs)
+            .rem(self.n)
+            .unwrap_or(BigUint::zero())
+            .padded(self.k)
+    }
+
+    fn from_mont(&self, a: &[u32]) -> BigUint {
+        BigUint::from_limbs(self.mul(a, &BigUint::one().padded(self.k)))
+    }
+
+    // Returns a * b * 2^(-32k) mod n for a, b < n (CIOS method)
+    fn mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let k = self.k;
+        let n = &self.n.limbs;
+        let mut t = vec![0u32; k + 2];
+
+        for i in 0..k {
+            let a_i = a[i] as u64;
+            let mut carry = 0u64;
+            for j in 0..k {
+                let s = t[j] as u64 + a_i * b[j] as u64 + carry;
+                t[j] = s as u32;
+                carry = s >> 32;
+            }
+            let s = t[k] as u64 + carry;
+            t[k] = s as u32;
+            t[k + 1] = (s >> 32) as u32;
+
+            let m = t[0].wrapping_mul(self.n0_inv) as u64;
+            let s = t[0] as u64 + m * n[0] as u64;
+            let mut carry = s >> 32;
+            for j in 1..k {
+                let s = t[j] as u64 + m * n[j] as u64 + carry;
+                t[j - 1] = s as u32;
+                carry = s >> 32;
+            }
+            let s = t[k] as u64 + carry;
+            t[k - 1] = s as u32;
+            t[k] = (t[k + 1] as u64 + (s >> 32)) as u32;
+            t[k + 1] = 0;
+        }
+
+        t.truncate(k + 1);
+        let mut result = BigUint::from_limbs(t);
+        if result >= *self.n {
+            result.sub_assign(self.n);
+        }
+        result.padded(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _bn(dec: &str) -> BigUint {
+        BigUint::from_dec(dec).unwrap()
+    }
+
+    #[test]
+    fn bytes_conversion_works() {
+        assert_eq!(Vec::<u8>::new(), BigUint::zero().to_bytes_be());
+        assert_eq!(vec![1, 0, 0, 0, 0], BigUint::pow2(32).to_bytes_be());
+        assert_eq!(BigUint::pow2(32), BigUint::from_bytes_be(&[0, 0, 1, 0, 0, 0, 0]));
+        assert_eq!(_bn("1234567890123456789012345678901234567890"),
+                   BigUint::from_bytes_be(&_bn("1234567890123456789012345678901234567890").to_bytes_be()));
+    }
+
+    #[test]
+    fn arithmetic_works() {
+        let a = _bn("340282366920938463463374607431768211455"); // 2^128 - 1
+        let b = BigUint::one();
+
+        assert_eq!(BigUint::pow2(128), a.add(&b));
+        assert_eq!(a, BigUint::pow2(128).checked_sub(&b).unwrap());
+        assert!(b.checked_sub(&a).is_none());
+        assert_eq!(_bn("115792089237316195423570985008687907852589419931798687112530834793049593217025"), a.mul(&a));
+        assert_eq!(_bn("4"), _bn("1000000007").rem(&_bn("1000000003")).unwrap());
+    }
+
+    #[test]
+    fn mod_exp_works() {
+        let n = _bn("1000000007");
+        assert_eq!(_bn("1"), _bn("3").mod_exp(&_bn("1000000006"), &n).unwrap());
+        assert_eq!(_bn("1024"), _bn("2").mod_exp(&_bn("10"), &n).unwrap());
+        assert_eq!(_bn("1"), _bn("5").mod_exp(&BigUint::zero(), &n).unwrap());
+
+        // 2^127 - 1 is prime
+        let p = _bn("170141183460469231731687303715884105727");
+        let a = _bn("12345678901234567890123456789");
+        assert_eq!(a, a.mod_exp(&p, &p).unwrap());
+
+        assert_eq!(_bn("24"), _bn("2").mod_exp(&_bn("10"), &_bn("1000")).unwrap());
+    }
+
+    #[test]
+    fn mod_inverse_works() {
+        let n = _bn("170141183460469231731687303715884105727");
+        let a = _bn("12345678901234567890123456789");
+
+        let a_inv = a.mod_inverse(&n).unwrap();
+        assert_eq!(BigUint::one(), a.mod_mul(&a_inv, &n).unwrap());
+
+        assert!(_bn("21").mod_inverse(&_bn("35")).is_none());
+        assert!(BigUint::zero().mod_inverse(&n).is_none());
+    }
+}
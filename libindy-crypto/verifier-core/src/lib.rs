@@ -0,0 +1,386 @@
+//! Verification of primary anoncreds proofs (revealed attributes and GE predicates) without OpenSSL.
+//!
+//! Crate is `no_std` and needs only `alloc`, so it can be used by embedded verifiers
+//! (secure elements, kiosks) that can't link issuer and prover code of `indy-crypto`.
+//! Values are passed as big-endian bytes or decimal strings, the same as `BigNumber` of `indy-crypto` gives;
+//! with `verifier_core` feature `indy-crypto` converts its proofs, keys and requests to types of this crate.
+//!
+//! Non-revocation proofs, linear predicates, pseudonyms, verifiable encryptions
+//! and non-ownership proofs aren't supported.
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate sha2;
+extern crate sha3;
+
+mod bignum;
+
+pub use bignum::BigUint;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use sha2::{Digest, Sha256, Sha384};
+use sha3::Sha3_256;
+
+const LARGE_E_START: usize = 596;
+const ITERATION: usize = 4;
+const CHALLENGE_BYTES: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifierCoreError {
+    /// Sub proof request, credential public key or proof has invalid structure.
+    InvalidStructure(String),
+    /// Proof doesn't correspond to sub proof requests.
+    ProofRejected(String)
+}
+
+impl fmt::Display for VerifierCoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VerifierCoreError::InvalidStructure(ref description) => write!(f, "Invalid structure: {}", description),
+            VerifierCoreError::ProofRejected(ref description) => write!(f, "Proof rejected: {}", description)
+        }
+    }
+}
+
+/// Hash function of Fiat-Shamir challenge, the same as `ChallengeHash` of `indy-crypto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeHash {
+    Sha256,
+    Sha384,
+    Sha3_256
+}
+
+impl ChallengeHash {
+    fn digest(&self, nums: &[Vec<u8>]) -> Vec<u8> {
+        match *self {
+            ChallengeHash::Sha256 => _digest::<Sha256>(nums),
+            ChallengeHash::Sha384 => _digest::<Sha384>(nums)[..CHALLENGE_BYTES].to_vec(),
+            ChallengeHash::Sha3_256 => _digest::<Sha3_256>(nums)
+        }
+    }
+}
+
+fn _digest<D>(nums: &[Vec<u8>]) -> Vec<u8> where D: Digest {
+    let mut hasher = D::default();
+    for num in nums.iter() {
+        hasher.input(num);
+    }
+    hasher.result().as_slice().to_vec()
+}
+
+/// Primary part of credential public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimaryPublicKey {
+    pub n: BigUint,
+    pub s: BigUint,
+    pub rms: BigUint,
+    pub r: BTreeMap<String, BigUint>,
+    pub rctxt: BigUint,
+    pub z: BigUint
+}
+
+/// Predicate `attr_name >= value`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Predicate {
+    pub attr_name: String,
+    pub value: i32
+}
+
+/// Attributes to be revealed and predicates requested for credential.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SubProofRequest {
+    pub revealed_attrs: BTreeSet<String>,
+    pub predicates: BTreeSet<Predicate>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimaryEqualProof {
+    pub revealed_attrs: BTreeMap<String, BigUint>,
+    pub a_prime: BigUint,
+    pub e: BigUint,
+    pub v: BigUint,
+    pub m: BTreeMap<String, BigUint>,
+    pub m1: BigUint,
+    pub m2: BigUint
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimaryPredicateGEProof {
+    pub u: BTreeMap<String, BigUint>,
+    pub r: BTreeMap<String, BigUint>,
+    pub mj: BigUint,
+    pub alpha: BigUint,
+    pub t: BTreeMap<String, BigUint>,
+    pub predicate: Predicate
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimaryProof {
+    pub eq_proof: PrimaryEqualProof,
+    pub ge_proofs: Vec<PrimaryPredicateGEProof>
+}
+
+/// Proof that consists of primary sub proofs only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub proofs: Vec<PrimaryProof>,
+    pub c_hash: BigUint,
+    pub c_list: Vec<Vec<u8>>,
+    pub challenge_hash: ChallengeHash
+}
+
+#[derive(Debug)]
+struct VerifiableCredential {
+    pub_key: PrimaryPublicKey,
+    credential_schema: BTreeSet<String>,
+    sub_proof_request: SubProofRequest
+}
+
+/// Verifier of primary proofs. Gives the same result as `ProofVerifier` of `indy-crypto` for primary proofs.
+#[derive(Debug, Default)]
+pub struct PrimaryProofVerifier {
+    credentials: Vec<VerifiableCredential>
+}
+
+impl PrimaryProofVerifier {
+    pub fn new() -> PrimaryProofVerifier {
+        PrimaryProofVerifier { credentials: Vec::new() }
+    }
+
+    /// Adds sub proof request, in the same order as Prover added them to proof.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Requested attributes and predicates.
+    /// * `credential_schema` - Attribute names of credential schema.
+    /// * `pub_key` - Primary credential public key.
+    pub fn add_sub_proof_request(&mut self,
+                                 sub_proof_request: SubProofRequest,
+                                 credential_schema: BTreeSet<String>,
+                                 pub_key: PrimaryPublicKey) -> Result<(), VerifierCoreError> {
+        for attr in sub_proof_request.revealed_attrs.iter()
+            .chain(sub_proof_request.predicates.iter().map(|predicate| &predicate.attr_name)) {
+            if !credential_schema.contains(attr) {
+                return Err(VerifierCoreError::InvalidStructure(format!("Attribute '{}' not found in credential schema", attr)));
+            }
+        }
+
+        if !pub_key.n.is_odd() {
+            return Err(VerifierCoreError::InvalidStructure(format!("Invalid modulus of credential public key")));
+        }
+
+        self.credentials.push(VerifiableCredential { pub_key, credential_schema, sub_proof_request });
+        Ok(())
+    }
+
+    /// Verifies proof.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof.
+    /// * `nonce` - Nonce proof was created for.
+    pub fn verify(&self, proof: &Proof, nonce: &BigUint) -> Result<bool, VerifierCoreError> {
+        if proof.proofs.len() != self.credentials.len() {
+            return Err(VerifierCoreError::ProofRejected(format!("Invalid sub proofs count")));
+        }
+
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        for (credential, primary_proof) in self.credentials.iter().zip(proof.proofs.iter()) {
+            let revealed_attrs: BTreeSet<String> = primary_proof.eq_proof.revealed_attrs.keys().cloned().collect();
+            if revealed_attrs != credential.sub_proof_request.revealed_attrs {
+                return Err(VerifierCoreError::ProofRejected(format!("Proof revealed attributes not correspond to requested attributes")));
+            }
+
+            let predicates: BTreeSet<Predicate> = primary_proof.ge_proofs.iter().map(|ge_proof| ge_proof.predicate.clone()).collect();
+            if predicates != credential.sub_proof_request.predicates {
+                return Err(VerifierCoreError::ProofRejected(format!("Proof predicates not correspond to requested predicates")));
+            }
+
+            tau_list.push(_verify_equality(credential, &primary_proof.eq_proof, &proof.c_hash)?.to_bytes_be());
+
+            for ge_proof in primary_proof.ge_proofs.iter() {
+                for tau in _verify_ge_predicate(&credential.pub_key, ge_proof, &proof.c_hash)? {
+                    tau_list.push(tau.to_bytes_be());
+                }
+            }
+        }
+
+        let mut values: Vec<Vec<u8>> = tau_list;
+        values.extend_from_slice(&proof.c_list);
+        values.push(nonce.to_bytes_be());
+
+        let c_hver = BigUint::from_bytes_be(&proof.challenge_hash.digest(&values));
+
+        Ok(c_hver == proof.c_hash)
+    }
+}
+
+fn _verify_equality(credential: &VerifiableCredential,
+                    proof: &PrimaryEqualProof,
+                    c_hash: &BigUint) -> Result<BigUint, VerifierCoreError> {
+    let pub_key = &credential.pub_key;
+    let n = &pub_key.n;
+
+    let mut t1 = _mod_exp(&proof.a_prime, &proof.e, n)?;
+
+    for attr in credential.credential_schema.difference(&credential.sub_proof_request.revealed_attrs) {
+        let m = _get(&proof.m, attr, "eq_proof.m")?;
+        let r = _get(&pub_key.r, attr, "pk.r")?;
+        t1 = _mod_mul(&_mod_exp(r, m, n)?, &t1, n)?;
+    }
+
+    t1 = _mod_mul(&_mod_exp(&pub_key.s, &proof.v, n)?, &t1, n)?;
+    t1 = _mod_mul(&_mod_exp(&pub_key.rms, &proof.m1, n)?, &t1, n)?;
+    t1 = _mod_mul(&_mod_exp(&pub_key.rctxt, &proof.m2, n)?, &t1, n)?;
+
+    let mut rar = _mod_exp(&proof.a_prime, &BigUint::pow2(LARGE_E_START), n)?;
+
+    for (attr, encoded_value) in proof.revealed_attrs.iter() {
+        let r = _get(&pub_key.r, attr, "pk.r")?;
+        rar = _mod_mul(&_mod_exp(r, encoded_value, n)?, &rar, n)?;
+    }
+
+    // (z / rar)^-1 ^ c
+    let z_div_rar = _mod_mul(&pub_key.z, &_mod_inverse(&rar, n)?, n)?;
+    let t2 = _mod_exp(&_mod_inverse(&z_div_rar, n)?, c_hash, n)?;
+
+    _mod_mul(&t1, &t2, n)
+}
+
+fn _verify_ge_predicate(pub_key: &PrimaryPublicKey,
+                        proof: &PrimaryPredicateGEProof,
+                        c_hash: &BigUint) -> Result<Vec<BigUint>, VerifierCoreError> {
+    let n = &pub_key.n;
+    let mut tau_list = _calc_tge(pub_key, proof)?;
+
+    for i in 0..ITERATION {
+        let cur_t = _get(&proof.t, &format!("{}", i), "proof.t")?;
+        tau_list[i] = _mod_mul(&_mod_inverse(&_mod_exp(cur_t, c_hash, n)?, n)?, &tau_list[i], n)?;
+    }
+
+    let delta = _get(&proof.t, "DELTA", "proof.t")?;
+
+    let z_value = if proof.predicate.value < 0 {
+        _mod_exp(&_mod_inverse(&pub_key.z, n)?, &BigUint::from_u32((proof.predicate.value as i64).abs() as u32), n)?
+    } else {
+        _mod_exp(&pub_key.z, &BigUint::from_u32(proof.predicate.value as u32), n)?
+    };
+
+    let delta_predicate = _mod_mul(&z_value, delta, n)?;
+    tau_list[ITERATION] = _mod_mul(&_mod_inverse(&_mod_exp(&delta_predicate, c_hash, n)?, n)?, &tau_list[ITERATION], n)?;
+    tau_list[ITERATION + 1] = _mod_mul(&_mod_inverse(&_mod_exp(delta, c_hash, n)?, n)?, &tau_list[ITERATION + 1], n)?;
+
+    Ok(tau_list)
+}
+
+fn _calc_tge(pub_key: &PrimaryPublicKey,
+             proof: &PrimaryPredicateGEProof) -> Result<Vec<BigUint>, VerifierCoreError> {
+    let n = &pub_key.n;
+    let mut tau_list: Vec<BigUint> = Vec::new();
+
+    for i in 0..ITERATION {
+        let key = format!("{}", i);
+        let cur_u = _get(&proof.u, &key, "u")?;
+        let cur_r = _get(&proof.r, &key, "r")?;
+
+        tau_list.push(_mod_mul(&_mod_exp(&pub_key.z, cur_u, n)?, &_mod_exp(&pub_key.s, cur_r, n)?, n)?);
+    }
+
+    let delta = _get(&proof.r, "DELTA", "r")?;
+    tau_list.push(_mod_mul(&_mod_exp(&pub_key.z, &proof.mj, n)?, &_mod_exp(&pub_key.s, delta, n)?, n)?);
+
+    let mut q = BigUint::one();
+    for i in 0..ITERATION {
+        let key = format!("{}", i);
+        let cur_t = _get(&proof.t, &key, "t")?;
+        let cur_u = _get(&proof.u, &key, "u")?;
+
+        q = _mod_mul(&_mod_exp(cur_t, cur_u, n)?, &q, n)?;
+    }
+
+    tau_list.push(_mod_mul(&_mod_exp(&pub_key.s, &proof.alpha, n)?, &q, n)?);
+
+    Ok(tau_list)
+}
+
+fn _get<'a>(map: &'a BTreeMap<String, BigUint>, key: &str, name: &str) -> Result<&'a BigUint, VerifierCoreError> {
+    map.get(key)
+        .ok_or(VerifierCoreError::InvalidStructure(format!("Value by key '{}' not found in {}", key, name)))
+}
+
+fn _mod_exp(base: &BigUint, exp: &BigUint, n: &BigUint) -> Result<BigUint, VerifierCoreError> {
+    base.mod_exp(exp, n)
+        .ok_or(VerifierCoreError::InvalidStructure(format!("Invalid modulus")))
+}
+
+fn _mod_mul(a: &BigUint, b: &BigUint, n: &BigUint) -> Result<BigUint, VerifierCoreError> {
+    a.mod_mul(b, n)
+        .ok_or(VerifierCoreError::InvalidStructure(format!("Invalid modulus")))
+}
+
+fn _mod_inverse(a: &BigUint, n: &BigUint) -> Result<BigUint, VerifierCoreError> {
+    a.mod_inverse(n)
+        .ok_or(VerifierCoreError::ProofRejected(format!("Value isn't invertible modulo n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge_hash_digest_works() {
+        let nums = vec![vec![1u8, 2, 3], vec![4u8]];
+
+        assert_eq!(32, ChallengeHash::Sha256.digest(&nums).len());
+        assert_eq!(32, ChallengeHash::Sha384.digest(&nums).len());
+        assert_eq!(32, ChallengeHash::Sha3_256.digest(&nums).len());
+        assert_ne!(ChallengeHash::Sha256.digest(&nums), ChallengeHash::Sha3_256.digest(&nums));
+    }
+
+    #[test]
+    fn add_sub_proof_request_works_for_unknown_attribute() {
+        let mut sub_proof_request = SubProofRequest::default();
+        sub_proof_request.revealed_attrs.insert(format!("name"));
+
+        let pub_key = PrimaryPublicKey {
+            n: BigUint::from_u32(35),
+            s: BigUint::from_u32(4),
+            rms: BigUint::from_u32(9),
+            r: BTreeMap::new(),
+            rctxt: BigUint::from_u32(11),
+            z: BigUint::from_u32(16)
+        };
+
+        let mut verifier = PrimaryProofVerifier::new();
+        assert!(verifier.add_sub_proof_request(sub_proof_request, BTreeSet::new(), pub_key).is_err());
+    }
+
+    #[test]
+    fn verify_works_for_wrong_sub_proofs_count() {
+        let verifier = PrimaryProofVerifier::new();
+        let proof = Proof {
+            proofs: vec![PrimaryProof {
+                eq_proof: PrimaryEqualProof {
+                    revealed_attrs: BTreeMap::new(),
+                    a_prime: BigUint::one(),
+                    e: BigUint::one(),
+                    v: BigUint::one(),
+                    m: BTreeMap::new(),
+                    m1: BigUint::one(),
+                    m2: BigUint::one()
+                },
+                ge_proofs: Vec::new()
+            }],
+            c_hash: BigUint::one(),
+            c_list: Vec::new(),
+            challenge_hash: ChallengeHash::Sha256
+        };
+
+        assert!(verifier.verify(&proof, &BigUint::one()).is_err());
+    }
+}
@@ -14,7 +14,7 @@ class BlsEntity:
     as_bytes_handler = None
     free_handler = None
 
-    def __init__(self, c_instance: c_void_p):
+    def __init__(self, c_instance: c_uint64):
         logger = logging.getLogger(__name__)
         logger.debug("BlsEntity.__init__: >>> self: %r, instance: %r", self, c_instance)
 
@@ -30,7 +30,7 @@ class BlsEntity:
         logger = logging.getLogger(__name__)
         logger.debug("BlsEntity::from_bytes: >>>")
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call(cls.from_bytes_handler, xbytes, len(xbytes), byref(c_instance))
 
         res = cls(c_instance)
@@ -82,7 +82,7 @@ class Generator(BlsEntity):
         logger = logging.getLogger(__name__)
         logger.debug("Generator::new: >>>")
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call(cls.new_handler, byref(c_instance))
 
         res = cls(c_instance)
@@ -110,7 +110,7 @@ class SignKey(BlsEntity):
         logger = logging.getLogger(__name__)
         logger.debug("SignKey::new: >>>")
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call(cls.new_handler, seed, len(seed) if seed is not None else 0, byref(c_instance))
 
         res = cls(c_instance)
@@ -139,7 +139,7 @@ class VerKey(BlsEntity):
         logger = logging.getLogger(__name__)
         logger.debug("VerKey::new: >>>")
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call(cls.new_handler, gen.c_instance, sign_key.c_instance, byref(c_instance))
 
         res = cls(c_instance)
@@ -178,11 +178,11 @@ class MultiSignature(BlsEntity):
         logger.debug("MultiSignature::new: >>>")
 
         # noinspection PyCallingNonCallable,PyTypeChecker
-        signature_c_instances = (c_void_p * len(signatures))()
+        signature_c_instances = (c_uint64 * len(signatures))()
         for i in range(len(signatures)):
             signature_c_instances[i] = signatures[i].c_instance
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call(cls.new_handler, signature_c_instances, len(signatures), byref(c_instance))
 
         res = cls(c_instance)
@@ -209,7 +209,7 @@ class Bls:
         logger = logging.getLogger(__name__)
         logger.debug("Bls::sign: >>> message: %r, sign_key: %r", message, sign_key)
 
-        c_instance = c_void_p()
+        c_instance = c_uint64()
         do_call('indy_crypto_bls_sign',
                 message, len(message),
                 sign_key.c_instance,
@@ -265,7 +265,7 @@ class Bls:
                      multi_sig, message, ver_keys, gen)
 
         # noinspection PyCallingNonCallable,PyTypeChecker
-        ver_key_c_instances = (c_void_p * len(ver_keys))()
+        ver_key_c_instances = (c_uint64 * len(ver_keys))()
         for i in range(len(ver_keys)):
             ver_key_c_instances[i] = ver_keys[i].c_instance
 